@@ -1,62 +1,383 @@
 // ABOUTME: WASM component for boolean validation
-// ABOUTME: Provides a validate function to check if a value is a valid boolean
+// ABOUTME: Provides validate and coerce functions to check if a value is a valid boolean
 
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// The strictness mode shared with the other primitive validators; its number helpers are
+// for the number validators
+#[path = "../../../mode/mode.rs"]
+#[allow(dead_code)]
+mod mode;
+
+use mode::Mode;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct ValidationInput {
     value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
 }
 
-// Internal validation logic that can be tested without WASM
-fn validate_boolean(value: &Value) -> bool {
-    match value {
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // How far to go to read a value: "strict", "lenient" or "coerce"
+    mode: Mode,
+    // Accept only JSON booleans whatever the mode, as "strict" does; for API payloads, where
+    // "yes" or 1 in place of true is a client bug
+    strict_type: bool,
+    // Further words read as true and as false, matched like the built-in ones: ignoring case
+    // and surrounding whitespace
+    truthy: Vec<String>,
+    falsy: Vec<String>,
+    // Accept the built-in words too; when off, only truthy and falsy are
+    builtin_words: bool,
+    // Languages whose words for true and false are read too, such as ["de", "fr"]
+    locales: Vec<Language>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            mode: Mode::default(),
+            strict_type: false,
+            truthy: Vec::new(),
+            falsy: Vec::new(),
+            builtin_words: true,
+            locales: Vec::new(),
+        }
+    }
+}
+
+// Languages with a word pack, by their ISO 639-1 code; an unlisted code is rejected like any
+// other unknown option value
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Da,
+    De,
+    Es,
+    Fi,
+    Fr,
+    It,
+    Nl,
+    No,
+    Pl,
+    Pt,
+    Sv,
+}
+
+impl Language {
+    // The language's words for true and for false, in lowercase
+    fn words(self) -> (&'static [&'static str], &'static [&'static str]) {
+        match self {
+            Language::Da => (&["ja", "sand", "til"], &["nej", "falsk", "fra"]),
+            Language::De => (&["ja", "wahr", "an"], &["nein", "falsch", "aus"]),
+            Language::Es => (&["sí", "si", "verdadero"], &["no", "falso"]),
+            Language::Fi => (&["kyllä", "tosi"], &["ei", "epätosi"]),
+            Language::Fr => (&["oui", "vrai"], &["non", "faux"]),
+            Language::It => (&["sì", "si", "vero"], &["no", "falso"]),
+            Language::Nl => (&["ja", "waar", "aan"], &["nee", "onwaar", "uit"]),
+            Language::No => (&["ja", "sann", "på"], &["nei", "usann", "av"]),
+            Language::Pl => (&["tak", "prawda"], &["nie", "fałsz"]),
+            Language::Pt => (&["sim", "verdadeiro"], &["não", "nao", "falso"]),
+            Language::Sv => (&["ja", "sant", "på"], &["nej", "falskt", "av"]),
+        }
+    }
+
+    fn parse(self, word: &str) -> Option<bool> {
+        let (truthy, falsy) = self.words();
+        if truthy.contains(&word) {
+            Some(true)
+        } else if falsy.contains(&word) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// The boolean a value stands for under the options, or None when it is not a boolean
+fn parse_boolean(value: &Value, options: &ValidationOptions) -> Option<bool> {
+    let mode = if options.strict_type { Mode::Strict } else { options.mode };
+    match (value, mode) {
         // Direct boolean
-        Value::Bool(_) => true,
-        
-        // String representations of booleans
-        Value::String(s) => {
-            let lower = s.trim().to_lowercase();
-            matches!(
-                lower.as_str(),
-                "true" | "false" | 
-                "yes" | "no" | 
-                "on" | "off" |
-                "1" | "0" |
-                "y" | "n" |
-                "t" | "f"
-            )
-        },
-        
-        // Numbers: 0 and 1 are common boolean representations
-        Value::Number(n) => {
-            if let Some(i) = n.as_i64() {
-                i == 0 || i == 1
-            } else if let Some(u) = n.as_u64() {
-                u == 0 || u == 1
-            } else if let Some(f) = n.as_f64() {
-                f == 0.0 || f == 1.0
-            } else {
-                false
-            }
-        },
-        
+        (Value::Bool(b), _) => Some(*b),
+
+        // Strict mode accepts nothing else
+        (_, Mode::Strict) => None,
+
+        // String representations of booleans; coercive parsing also reads any numeric string
+        // as zero or not
+        (Value::String(s), Mode::Lenient) => parse_boolean_word(s, options),
+        (Value::String(s), Mode::Coerce) => parse_boolean_word(s, options).or_else(|| parse_number_str(s).map(|n| n != 0.0)),
+
+        // Numbers: 0 and 1 are common boolean representations, and coercive parsing reads
+        // any number as zero or not
+        (Value::Number(n), Mode::Lenient) => n.as_f64().and_then(parse_boolean_f64),
+        (Value::Number(n), Mode::Coerce) => n.as_f64().map(|n| n != 0.0),
+
         // All other types are not booleans
-        _ => false,
+        _ => None,
     }
 }
 
-#[wasm_bindgen]
-pub fn validate(input: JsValue) -> bool {
+// Internal coercion logic that can be tested without WASM
+// Returns the canonical boolean for a valid input, or None when the value is not a boolean
+fn coerce_boolean(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    parse_boolean(value, options).map(Value::Bool)
+}
+
+// The string spellings of true and false, shared with the validate_str fast path
+fn parse_boolean_str(text: &str) -> Option<bool> {
+    match text.trim().to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" | "y" | "t" => Some(true),
+        "false" | "no" | "off" | "0" | "n" | "f" => Some(false),
+        _ => None,
+    }
+}
+
+// The configured words first, then the selected languages' and the built-in ones; a word
+// configured as both true and false is neither
+fn parse_boolean_word(text: &str, options: &ValidationOptions) -> Option<bool> {
+    let word = text.trim().to_lowercase();
+    let listed = |words: &[String]| words.iter().any(|listed| listed.trim().to_lowercase() == word);
+
+    match (listed(&options.truthy), listed(&options.falsy)) {
+        (true, false) => Some(true),
+        (false, true) => Some(false),
+        (true, true) => None,
+        (false, false) => options
+            .locales
+            .iter()
+            .find_map(|language| language.parse(&word))
+            .or_else(|| options.builtin_words.then(|| parse_boolean_str(text)).flatten()),
+    }
+}
+
+// 0 and 1, shared with the validate_f64 fast path
+fn parse_boolean_f64(number: f64) -> Option<bool> {
+    if number == 0.0 {
+        Some(false)
+    } else if number == 1.0 {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+// Finite numbers written as strings, for coercive parsing
+fn parse_number_str(text: &str) -> Option<f64> {
+    text.trim().parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_boolean(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_boolean(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_boolean(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_boolean(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
     // Parse the input object
-    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input) {
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
         Ok(val) => val,
         Err(_) => return false,
     };
     
-    validate_boolean(&input_obj.value)
+    validate_boolean(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_boolean(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+// The boolean itself, for callers that want true or false out of "off" or "Y" rather than a
+// canonical value; null when the value is not a boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean | null"))]
+pub fn to_bool(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is not a boolean
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match parse_boolean(&input_obj.value, &input_obj.options) {
+        Some(b) => JsValue::from_bool(b),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_boolean(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_boolean(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_boolean(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    parse_boolean_str(value).is_some()
+}
+
+// Fast path for a JS number with the default options, as validate_str is for strings
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_f64(value: f64) -> bool {
+    parse_boolean_f64(value).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
 }
 
 #[cfg(test)]
@@ -67,112 +388,311 @@ mod tests {
     #[test]
     fn test_valid_booleans() {
         // Direct boolean values
-        assert!(validate_boolean(&json!(true)));
-        assert!(validate_boolean(&json!(false)));
+        assert!(validate_boolean(&json!(true), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(false), &ValidationOptions::default()));
         
         // String representations - common words
-        assert!(validate_boolean(&json!("true")));
-        assert!(validate_boolean(&json!("false")));
-        assert!(validate_boolean(&json!("True")));
-        assert!(validate_boolean(&json!("False")));
-        assert!(validate_boolean(&json!("TRUE")));
-        assert!(validate_boolean(&json!("FALSE")));
-        assert!(validate_boolean(&json!("  true  "))); // With whitespace
-        assert!(validate_boolean(&json!("\tfalse\n"))); // With tabs and newlines
+        assert!(validate_boolean(&json!("true"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("false"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("True"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("False"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("TRUE"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("FALSE"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("  true  "), &ValidationOptions::default())); // With whitespace
+        assert!(validate_boolean(&json!("\tfalse\n"), &ValidationOptions::default())); // With tabs and newlines
         
         // Yes/No variants
-        assert!(validate_boolean(&json!("yes")));
-        assert!(validate_boolean(&json!("no")));
-        assert!(validate_boolean(&json!("Yes")));
-        assert!(validate_boolean(&json!("No")));
-        assert!(validate_boolean(&json!("YES")));
-        assert!(validate_boolean(&json!("NO")));
+        assert!(validate_boolean(&json!("yes"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("no"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("Yes"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("No"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("YES"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("NO"), &ValidationOptions::default()));
         
         // On/Off variants
-        assert!(validate_boolean(&json!("on")));
-        assert!(validate_boolean(&json!("off")));
-        assert!(validate_boolean(&json!("On")));
-        assert!(validate_boolean(&json!("Off")));
-        assert!(validate_boolean(&json!("ON")));
-        assert!(validate_boolean(&json!("OFF")));
+        assert!(validate_boolean(&json!("on"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("off"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("On"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("Off"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("ON"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("OFF"), &ValidationOptions::default()));
         
         // Single character variants
-        assert!(validate_boolean(&json!("y")));
-        assert!(validate_boolean(&json!("n")));
-        assert!(validate_boolean(&json!("Y")));
-        assert!(validate_boolean(&json!("N")));
-        assert!(validate_boolean(&json!("t")));
-        assert!(validate_boolean(&json!("f")));
-        assert!(validate_boolean(&json!("T")));
-        assert!(validate_boolean(&json!("F")));
+        assert!(validate_boolean(&json!("y"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("n"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("Y"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("N"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("t"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("f"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("T"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("F"), &ValidationOptions::default()));
         
         // Numeric representations
-        assert!(validate_boolean(&json!("1")));
-        assert!(validate_boolean(&json!("0")));
-        assert!(validate_boolean(&json!(1)));
-        assert!(validate_boolean(&json!(0)));
-        assert!(validate_boolean(&json!(1.0)));
-        assert!(validate_boolean(&json!(0.0)));
+        assert!(validate_boolean(&json!("1"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("0"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(1), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(0), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(1.0), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(0.0), &ValidationOptions::default()));
     }
 
     #[test]
     fn test_invalid_booleans() {
         // Invalid string representations
-        assert!(!validate_boolean(&json!(""))); // Empty string
-        assert!(!validate_boolean(&json!("   "))); // Only whitespace
-        assert!(!validate_boolean(&json!("maybe")));
-        assert!(!validate_boolean(&json!("unknown")));
-        assert!(!validate_boolean(&json!("null")));
-        assert!(!validate_boolean(&json!("undefined")));
-        assert!(!validate_boolean(&json!("2")));
-        assert!(!validate_boolean(&json!("-1")));
-        assert!(!validate_boolean(&json!("0.5")));
-        assert!(!validate_boolean(&json!("truee"))); // Typo
-        assert!(!validate_boolean(&json!("fals"))); // Incomplete
-        assert!(!validate_boolean(&json!("yess"))); // Extra character
-        assert!(!validate_boolean(&json!("noo"))); // Extra character
+        assert!(!validate_boolean(&json!(""), &ValidationOptions::default())); // Empty string
+        assert!(!validate_boolean(&json!("   "), &ValidationOptions::default())); // Only whitespace
+        assert!(!validate_boolean(&json!("maybe"), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!("unknown"), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!("null"), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!("undefined"), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!("2"), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!("-1"), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!("0.5"), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!("truee"), &ValidationOptions::default())); // Typo
+        assert!(!validate_boolean(&json!("fals"), &ValidationOptions::default())); // Incomplete
+        assert!(!validate_boolean(&json!("yess"), &ValidationOptions::default())); // Extra character
+        assert!(!validate_boolean(&json!("noo"), &ValidationOptions::default())); // Extra character
         
         // Invalid numeric values
-        assert!(!validate_boolean(&json!(2)));
-        assert!(!validate_boolean(&json!(-1)));
-        assert!(!validate_boolean(&json!(0.5)));
-        assert!(!validate_boolean(&json!(100)));
-        assert!(!validate_boolean(&json!(1.1)));
-        assert!(!validate_boolean(&json!(-0.5)));
+        assert!(!validate_boolean(&json!(2), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!(-1), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!(0.5), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!(100), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!(1.1), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!(-0.5), &ValidationOptions::default()));
         
         // Other JSON types
-        assert!(!validate_boolean(&json!(null)));
-        assert!(!validate_boolean(&json!([])));
-        assert!(!validate_boolean(&json!({})));
-        assert!(!validate_boolean(&json!({"value": true})));
-        assert!(!validate_boolean(&json!([true, false])));
+        assert!(!validate_boolean(&json!(null), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!([]), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!({}), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!({"value": true}), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!([true, false]), &ValidationOptions::default()));
     }
     
     #[test]
     fn test_edge_cases() {
         // Mixed case variations
-        assert!(validate_boolean(&json!("TrUe")));
-        assert!(validate_boolean(&json!("FaLsE")));
-        assert!(validate_boolean(&json!("YeS")));
-        assert!(validate_boolean(&json!("nO")));
+        assert!(validate_boolean(&json!("TrUe"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("FaLsE"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("YeS"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("nO"), &ValidationOptions::default()));
         
         // Whitespace handling
-        assert!(validate_boolean(&json!("  yes  ")));
-        assert!(validate_boolean(&json!("\t\non\r\n")));
-        assert!(validate_boolean(&json!("  1  ")));
-        assert!(validate_boolean(&json!("  0  ")));
+        assert!(validate_boolean(&json!("  yes  "), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("\t\non\r\n"), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("  1  "), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!("  0  "), &ValidationOptions::default()));
         
         // Exact numeric values
-        assert!(validate_boolean(&json!(0u64)));
-        assert!(validate_boolean(&json!(1u64)));
-        assert!(validate_boolean(&json!(0i64)));
-        assert!(validate_boolean(&json!(1i64)));
-        assert!(validate_boolean(&json!(0.0f64)));
-        assert!(validate_boolean(&json!(1.0f64)));
+        assert!(validate_boolean(&json!(0u64), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(1u64), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(0i64), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(1i64), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(0.0f64), &ValidationOptions::default()));
+        assert!(validate_boolean(&json!(1.0f64), &ValidationOptions::default()));
         
         // Make sure close values don't match
-        assert!(!validate_boolean(&json!(0.99999999)));
-        assert!(!validate_boolean(&json!(1.00000001)));
-        assert!(!validate_boolean(&json!(0.00000001)));
+        assert!(!validate_boolean(&json!(0.99999999), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!(1.00000001), &ValidationOptions::default()));
+        assert!(!validate_boolean(&json!(0.00000001), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_options() {
+        // Options are optional and may be empty
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": true})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": true, "options": {}})).is_ok());
+
+        // Unknown options are rejected so typos never silently change behaviour
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": true, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_modes() {
+        let strict: ValidationOptions = serde_json::from_value(json!({"mode": "strict"})).unwrap();
+        let coerce: ValidationOptions = serde_json::from_value(json!({"mode": "coerce"})).unwrap();
+
+        assert_eq!(coerce_boolean(&json!(true), &strict), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("true"), &strict), None);
+        assert_eq!(coerce_boolean(&json!(1), &strict), None);
+
+        assert_eq!(coerce_boolean(&json!("yes"), &coerce), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!(2), &coerce), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!(-0.5), &coerce), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!(" 0.0 "), &coerce), Some(json!(false)));
+        assert_eq!(coerce_boolean(&json!("42"), &coerce), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("maybe"), &coerce), None);
+        assert_eq!(coerce_boolean(&json!("inf"), &coerce), None);
+        assert_eq!(coerce_boolean(&json!(null), &coerce), None);
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"mode": "loose"})).is_err());
+    }
+
+    #[test]
+    fn test_parse_boolean() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(parse_boolean(&json!("off"), &defaults), Some(false));
+        assert_eq!(parse_boolean(&json!("Y"), &defaults), Some(true));
+        assert_eq!(parse_boolean(&json!(0), &defaults), Some(false));
+        assert_eq!(parse_boolean(&json!("maybe"), &defaults), None);
+
+        // It reads exactly what coerce accepts
+        let words: ValidationOptions = serde_json::from_value(json!({"truthy": ["enabled"], "mode": "coerce"})).unwrap();
+        for value in [json!("enabled"), json!("no"), json!(2.5), json!("x"), json!(null)] {
+            assert_eq!(parse_boolean(&value, &words).map(Value::Bool), coerce_boolean(&value, &words), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_strict_type() {
+        let strict: ValidationOptions = serde_json::from_value(json!({"strict_type": true})).unwrap();
+
+        assert_eq!(coerce_boolean(&json!(false), &strict), Some(json!(false)));
+        for value in [json!("yes"), json!("true"), json!(1), json!("0"), json!(0.0), json!(null)] {
+            assert_eq!(coerce_boolean(&value, &strict), None, "{value}");
+        }
+
+        // It wins over a more lenient mode and over configured words
+        let overridden: ValidationOptions =
+            serde_json::from_value(json!({"strict_type": true, "mode": "coerce", "truthy": ["ja"]})).unwrap();
+        assert_eq!(coerce_boolean(&json!(2), &overridden), None);
+        assert_eq!(coerce_boolean(&json!("ja"), &overridden), None);
+    }
+
+    #[test]
+    fn test_word_sets() {
+        let extended: ValidationOptions =
+            serde_json::from_value(json!({"truthy": ["enabled", "Ja"], "falsy": ["disabled", "nein"]})).unwrap();
+
+        assert_eq!(coerce_boolean(&json!("enabled"), &extended), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!(" JA "), &extended), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("Disabled"), &extended), Some(json!(false)));
+        assert_eq!(coerce_boolean(&json!("nein"), &extended), Some(json!(false)));
+        assert_eq!(coerce_boolean(&json!("yes"), &extended), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("vielleicht"), &extended), None);
+
+        // Without the built-in words only the configured ones are read; numbers are not words
+        let replaced: ValidationOptions =
+            serde_json::from_value(json!({"truthy": ["ja"], "falsy": ["nein"], "builtin_words": false})).unwrap();
+        assert_eq!(coerce_boolean(&json!("ja"), &replaced), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("yes"), &replaced), None);
+        assert_eq!(coerce_boolean(&json!("1"), &replaced), None);
+        assert_eq!(coerce_boolean(&json!(1), &replaced), Some(json!(true)));
+
+        // Configured words take precedence over the built-in ones, and a word in both lists is neither
+        let overridden: ValidationOptions = serde_json::from_value(json!({"falsy": ["y"], "truthy": ["x"]})).unwrap();
+        assert_eq!(coerce_boolean(&json!("y"), &overridden), Some(json!(false)));
+        let ambiguous: ValidationOptions = serde_json::from_value(json!({"truthy": ["on"], "falsy": ["ON"]})).unwrap();
+        assert_eq!(coerce_boolean(&json!("on"), &ambiguous), None);
+
+        // Strict mode reads no words at all
+        let strict: ValidationOptions = serde_json::from_value(json!({"truthy": ["ja"], "mode": "strict"})).unwrap();
+        assert_eq!(coerce_boolean(&json!("ja"), &strict), None);
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"truthy": "ja"})).is_err());
+    }
+
+    #[test]
+    fn test_locales() {
+        let options: ValidationOptions = serde_json::from_value(json!({"locales": ["de", "fr", "es"]})).unwrap();
+
+        assert_eq!(coerce_boolean(&json!("Ja"), &options), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("nein"), &options), Some(json!(false)));
+        assert_eq!(coerce_boolean(&json!(" OUI "), &options), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("faux"), &options), Some(json!(false)));
+        assert_eq!(coerce_boolean(&json!("SÍ"), &options), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("yes"), &options), Some(json!(true)));
+
+        // Only the selected languages' words are read
+        assert_eq!(coerce_boolean(&json!("ja"), &ValidationOptions::default()), None);
+        assert_eq!(coerce_boolean(&json!("sim"), &options), None);
+
+        // Every pack's words are lowercase, and no word is both true and false in any pack
+        for language in [
+            Language::Da, Language::De, Language::Es, Language::Fi, Language::Fr, Language::It,
+            Language::Nl, Language::No, Language::Pl, Language::Pt, Language::Sv,
+        ] {
+            let (truthy, falsy) = language.words();
+            for word in truthy.iter().chain(falsy) {
+                assert_eq!(word.to_lowercase(), *word, "{language:?}");
+                assert_ne!(truthy.contains(word), falsy.contains(word), "{language:?}: {word}");
+            }
+        }
+
+        // Configured words still take precedence, and packs work without the built-in words
+        let custom: ValidationOptions =
+            serde_json::from_value(json!({"locales": ["de"], "falsy": ["an"], "builtin_words": false})).unwrap();
+        assert_eq!(coerce_boolean(&json!("an"), &custom), Some(json!(false)));
+        assert_eq!(coerce_boolean(&json!("ja"), &custom), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("yes"), &custom), None);
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"locales": ["de-CH"]})).is_err());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"locales": ["xx"]})).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_coerce() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(coerce_boolean(&json!(true), &options), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!(false), &options), Some(json!(false)));
+        assert_eq!(coerce_boolean(&json!("YES"), &options), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("  off "), &options), Some(json!(false)));
+        assert_eq!(coerce_boolean(&json!("t"), &options), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!("0"), &options), Some(json!(false)));
+        assert_eq!(coerce_boolean(&json!(1), &options), Some(json!(true)));
+        assert_eq!(coerce_boolean(&json!(0.0), &options), Some(json!(false)));
+
+        assert_eq!(coerce_boolean(&json!("maybe"), &options), None);
+        assert_eq!(coerce_boolean(&json!(2), &options), None);
+        assert_eq!(coerce_boolean(&json!(null), &options), None);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_boolean(&json!("Yes"), &options)),
+            ValidationResult { valid: true, value: json!(true) }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_boolean(&json!("maybe"), &options)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [true, "off", 2]})).unwrap();
+        assert_eq!(batch.values.len(), 3);
+        assert!(serde_json::from_value::<BatchInput>(json!({"values": [], "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["true", " Yes ", "off", "maybe", ""] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+        for number in [0.0, 1.0, 0.5, 2.0] {
+            assert_eq!(validate_f64(number), coerce_value(&Value::from(number), &Value::Null).is_some(), "{number}");
+        }
+    }
+}
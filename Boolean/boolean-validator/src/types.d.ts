@@ -0,0 +1,47 @@
+// Types for the boolean validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** How far to go to read a value: "strict" takes only true and false, "lenient" also the usual words and 0 and 1, and "coerce" also any number or numeric string, as zero or not (default: "lenient") */
+    mode?: "strict" | "lenient" | "coerce";
+    /** Accept only JSON booleans, rejecting "yes", 1 and "0" whatever the mode; the same as mode "strict" (default: false) */
+    strict_type?: boolean;
+    /** Further words read as true, ignoring case and surrounding whitespace, such as "enabled" or "ja" (default: []) */
+    truthy?: string[];
+    /** Further words read as false, ignoring case and surrounding whitespace, such as "disabled" or "nein" (default: []) */
+    falsy?: string[];
+    /** Accept the built-in words (true, yes, on, 1, y, t and their opposites) too; when false, only truthy and falsy are (default: true) */
+    builtin_words?: boolean;
+    /** Languages whose words for true and false are read too, such as ja/nein for "de" and oui/non for "fr" (default: []) */
+    locales?: ("da" | "de" | "es" | "fi" | "fr" | "it" | "nl" | "no" | "pl" | "pt" | "sv")[];
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = boolean | string | number;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = boolean;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
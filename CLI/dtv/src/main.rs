@@ -0,0 +1,348 @@
+// ABOUTME: Native command-line validator for shell pipelines, linking every validator's internal logic
+// ABOUTME: Checks values from arguments, stdin or a file against one datatype and reports failures as JSON
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use datatype_validators::Detail;
+use serde_json::{json, Map, Value};
+
+/// Validate values against a datatype: each argument, or each line of stdin or a file, is one value.
+/// Exits 0 when every value is valid, 1 with a JSON report when any is not, and 2 on usage errors.
+#[derive(Parser)]
+#[command(name = "dtv", version)]
+struct Args {
+    /// Datatype name, such as integer, boolean or url (see --list-types)
+    #[arg(short, long = "type", value_name = "TYPE", required_unless_present = "list_types")]
+    type_name: Option<String>,
+
+    /// Validator options as a JSON object
+    #[arg(long, value_name = "JSON")]
+    options: Option<String>,
+
+    /// One validator option; VALUE is read as JSON when it parses, otherwise as a string
+    #[arg(short = 'o', long = "option", value_name = "NAME=VALUE")]
+    option: Vec<String>,
+
+    /// Read values from a file, one per line ("-" for stdin)
+    #[arg(short, long, value_name = "PATH", conflicts_with = "values")]
+    file: Option<PathBuf>,
+
+    /// Parse each value as JSON instead of taking it as a string
+    #[arg(long)]
+    json: bool,
+
+    /// Print the datatype names and exit
+    #[arg(long)]
+    list_types: bool,
+
+    /// Values to validate; stdin is read when there are none and no --file
+    values: Vec<String>,
+}
+
+// Error codes for the reports; the exit status tells invalid values from usage errors
+const UNKNOWN_TYPE: &str = "unknown_type";
+const INVALID_OPTIONS: &str = "invalid_options";
+const INVALID_JSON: &str = "invalid_json";
+const UNREADABLE_INPUT: &str = "unreadable_input";
+
+// A value that failed, by its zero-based position among the inputs (the line number less one),
+// with the validator's error code, or its code for each field as the mail server validator
+// reports them, when it has one
+#[derive(Debug, PartialEq)]
+struct Failure {
+    index: usize,
+    input: String,
+    error: Option<String>,
+    errors: Option<Value>,
+}
+
+// Outcome of a run over every input
+#[derive(Debug, PartialEq)]
+struct Report {
+    checked: usize,
+    failures: Vec<Failure>,
+}
+
+impl Report {
+    fn to_json(&self, type_name: &str) -> Value {
+        let failures: Vec<Value> = self
+            .failures
+            .iter()
+            .map(|failure| {
+                let mut entry = json!({ "index": failure.index, "input": failure.input });
+                if let Some(error) = &failure.error {
+                    entry["error"] = json!(error);
+                }
+                if let Some(errors) = &failure.errors {
+                    entry["errors"] = errors.clone();
+                }
+                entry
+            })
+            .collect();
+
+        json!({
+            "type": type_name,
+            "checked": self.checked,
+            "failed": self.failures.len(),
+            "failures": failures,
+        })
+    }
+}
+
+// Merges --options and each --option NAME=VALUE into one options object
+fn parse_options(options: Option<&str>, pairs: &[String]) -> Result<Value, String> {
+    let mut merged = match options {
+        None => Map::new(),
+        Some(text) => match serde_json::from_str(text) {
+            Ok(Value::Object(object)) => object,
+            _ => return Err(format!("--options must be a JSON object: {text}")),
+        },
+    };
+
+    for pair in pairs {
+        let Some((name, value)) = pair.split_once('=') else {
+            return Err(format!("--option must be NAME=VALUE: {pair}"));
+        };
+        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+        merged.insert(name.to_string(), value);
+    }
+
+    Ok(if merged.is_empty() { Value::Null } else { Value::Object(merged) })
+}
+
+// Whether the validator accepts the options, judged once rather than by every value failing:
+// its detailed entry point gives no result at all for options it can't deserialize
+fn accepts_options(type_name: &str, options: &Value) -> bool {
+    datatype_validators::lookup_detail(type_name).is_some_and(|detail| detail(&Value::Null, options).is_some())
+}
+
+// Validates each input in turn with the validator's detailed entry point, so that failures
+// carry its reason; only the failures are kept, so inputs can be streamed
+fn check<I>(detail: Detail, inputs: I, options: &Value, parse_json: bool) -> io::Result<Report>
+where
+    I: IntoIterator<Item = io::Result<String>>,
+{
+    let mut report = Report { checked: 0, failures: Vec::new() };
+
+    for (index, input) in inputs.into_iter().enumerate() {
+        let input = input?;
+        let value = if parse_json {
+            serde_json::from_str(&input).map_err(|_| INVALID_JSON)
+        } else {
+            Ok(Value::String(input.clone()))
+        };
+
+        let result = match value {
+            Ok(value) => detail(&value, options).unwrap_or_else(|| json!({"valid": false})),
+            Err(code) => json!({"valid": false, "error": code}),
+        };
+        if result["valid"] != true {
+            let error = result["error"].as_str().map(String::from);
+            let errors = result.get("errors").cloned();
+            report.failures.push(Failure { index, input, error, errors });
+        }
+        report.checked += 1;
+    }
+
+    Ok(report)
+}
+
+// Lines without their terminators, including a trailing carriage return from CRLF files
+fn lines(reader: impl BufRead) -> impl Iterator<Item = io::Result<String>> {
+    reader.lines().map(|line| {
+        line.map(|mut line| {
+            if line.ends_with('\r') {
+                line.pop();
+            }
+            line
+        })
+    })
+}
+
+// Prints a usage error as JSON and exits with status 2
+fn fail(error: &str, message: String) -> ExitCode {
+    println!("{}", json!({ "error": error, "message": message }));
+    ExitCode::from(2)
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    if args.list_types {
+        for name in datatype_validators::type_names() {
+            println!("{name}");
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let type_name = args.type_name.unwrap_or_default();
+    let Some(detail) = datatype_validators::lookup_detail(&type_name) else {
+        return fail(UNKNOWN_TYPE, format!("no validator for datatype {type_name:?}"));
+    };
+    let options = match parse_options(args.options.as_deref(), &args.option) {
+        Ok(options) => options,
+        Err(message) => return fail(INVALID_OPTIONS, message),
+    };
+    if !accepts_options(&type_name, &options) {
+        return fail(INVALID_OPTIONS, format!("the {type_name} validator doesn't accept the options {options}"));
+    }
+
+    let report = match (&args.file, args.values.is_empty()) {
+        (None, false) => check(detail, args.values.into_iter().map(Ok), &options, args.json),
+        (Some(path), _) if path.as_os_str() != "-" => match File::open(path) {
+            Ok(file) => check(detail, lines(BufReader::new(file)), &options, args.json),
+            Err(error) => return fail(UNREADABLE_INPUT, format!("{}: {error}", path.display())),
+        },
+        _ => check(detail, lines(io::stdin().lock()), &options, args.json),
+    };
+
+    match report {
+        Ok(report) if report.failures.is_empty() => ExitCode::SUCCESS,
+        Ok(report) => {
+            println!("{}", report.to_json(&type_name));
+            ExitCode::FAILURE
+        },
+        Err(error) => fail(UNREADABLE_INPUT, error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn inputs(values: &[&str]) -> Vec<io::Result<String>> {
+        values.iter().map(|value| Ok(value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_arguments() {
+        Args::command().debug_assert();
+
+        assert!(Args::try_parse_from(["dtv", "--type", "integer", "1", "2"]).is_ok());
+        assert!(Args::try_parse_from(["dtv", "--list-types"]).is_ok());
+        assert!(Args::try_parse_from(["dtv", "1"]).is_err());
+        assert!(Args::try_parse_from(["dtv", "-t", "integer", "-f", "values.txt", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(parse_options(None, &[]), Ok(Value::Null));
+        assert_eq!(parse_options(Some(r#"{"min": 0}"#), &[]), Ok(json!({"min": 0})));
+        assert_eq!(
+            parse_options(Some(r#"{"min": 0}"#), &["max=10".to_string(), "min=1".to_string()]),
+            Ok(json!({"min": 1, "max": 10}))
+        );
+        assert_eq!(
+            parse_options(None, &["default_country=GB".to_string(), "strict=true".to_string()]),
+            Ok(json!({"default_country": "GB", "strict": true}))
+        );
+        assert!(parse_options(Some("[1]"), &[]).is_err());
+        assert!(parse_options(Some("{"), &[]).is_err());
+        assert!(parse_options(None, &["min".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_accepts_options() {
+        assert!(accepts_options("integer", &Value::Null));
+        assert!(accepts_options("integer", &json!({"min": 1})));
+        assert!(accepts_options("array", &json!({"item_type": "integer"})));
+
+        // A misspelled option, or a value of the wrong type, is a usage error
+        assert!(!accepts_options("integer", &json!({"minimun": 1})));
+        assert!(!accepts_options("integer", &json!({"min": "one"})));
+        assert!(!accepts_options("uuid", &json!({"strict": true})));
+    }
+
+    #[test]
+    fn test_check() {
+        let integer = datatype_validators::lookup_detail("integer").unwrap();
+
+        let report = check(integer, inputs(&["1", " 2 ", "x", "4.5"]), &Value::Null, false).unwrap();
+        assert_eq!(report.checked, 4);
+        assert_eq!(
+            report.failures,
+            vec![
+                Failure { index: 2, input: "x".to_string(), error: None, errors: None },
+                Failure { index: 3, input: "4.5".to_string(), error: None, errors: None },
+            ]
+        );
+
+        let report = check(integer, inputs(&[]), &Value::Null, false).unwrap();
+        assert_eq!(report, Report { checked: 0, failures: Vec::new() });
+    }
+
+    #[test]
+    fn test_failures_carry_the_reason() {
+        let integer = datatype_validators::lookup_detail("integer").unwrap();
+        let report = check(integer, inputs(&["5", "11"]), &json!({"max": 10}), false).unwrap();
+        assert_eq!(report.failures, vec![Failure { index: 1, input: "11".to_string(), error: Some("out_of_range".to_string()), errors: None }]);
+        assert_eq!(report.to_json("integer")["failures"], json!([{"index": 1, "input": "11", "error": "out_of_range"}]));
+
+        // Field codes come through as the validator reports them
+        let mailserver = datatype_validators::lookup_detail("mailserver").unwrap();
+        let report = check(mailserver, inputs(&[r#"{"host": "mail.example.com", "port": 99999}"#]), &Value::Null, true).unwrap();
+        assert!(report.failures[0].errors.as_ref().is_some_and(|errors| errors["port"].is_string()));
+    }
+
+    #[test]
+    fn test_options_are_forwarded() {
+        let uuid = datatype_validators::lookup_detail("uuid").unwrap();
+        let values = inputs(&["00000000-0000-0000-0000-000000000000", "123e4567-e89b-12d3-a456-426614174000"]);
+
+        let report = check(uuid, values, &json!({"reject_nil": true}), false).unwrap();
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].index, 0);
+    }
+
+    #[test]
+    fn test_check_json_values() {
+        let array = datatype_validators::lookup_detail("array").unwrap();
+        let integers = json!({"item_type": "integer"});
+
+        let report = check(array, inputs(&["[1, \"2\"]", "[1, \"x\"]", "[1,"]), &integers, true).unwrap();
+        assert_eq!(report.checked, 3);
+        assert_eq!(report.failures[0], Failure { index: 1, input: "[1, \"x\"]".to_string(), error: None, errors: None });
+        assert_eq!(report.failures[1].error.as_deref(), Some(INVALID_JSON));
+    }
+
+    #[test]
+    fn test_read_errors_stop_the_run() {
+        let integer = datatype_validators::lookup_detail("integer").unwrap();
+        let broken = vec![Ok("1".to_string()), Err(io::Error::new(io::ErrorKind::InvalidData, "not UTF-8"))];
+        assert!(check(integer, broken, &Value::Null, false).is_err());
+    }
+
+    #[test]
+    fn test_lines() {
+        let read: Vec<String> = lines("1\r\n2\n\n3".as_bytes()).map(Result::unwrap).collect();
+        assert_eq!(read, vec!["1", "2", "", "3"]);
+    }
+
+    #[test]
+    fn test_report_json() {
+        let report = Report {
+            checked: 3,
+            failures: vec![
+                Failure { index: 1, input: "x".to_string(), error: None, errors: None },
+                Failure { index: 2, input: "{".to_string(), error: Some(INVALID_JSON.to_string()), errors: None },
+            ],
+        };
+        assert_eq!(
+            report.to_json("integer"),
+            json!({
+                "type": "integer",
+                "checked": 3,
+                "failed": 2,
+                "failures": [
+                    {"index": 1, "input": "x"},
+                    {"index": 2, "input": "{", "error": "invalid_json"},
+                ],
+            })
+        );
+    }
+}
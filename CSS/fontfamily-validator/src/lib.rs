@@ -0,0 +1,434 @@
+// ABOUTME: WASM component for CSS font-family list validation
+// ABOUTME: Provides validate and coerce functions for comma-separated family names ending in an optional generic family
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Require the list to end with a generic family such as sans-serif
+    require_generic: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// CSS Fonts Level 4 generic family keywords
+const GENERIC_FAMILIES: &[&str] = &[
+    "serif", "sans-serif", "monospace", "cursive", "fantasy", "system-ui", "ui-serif",
+    "ui-sans-serif", "ui-monospace", "ui-rounded", "emoji", "math", "fangsong",
+];
+
+// Keywords that can never be unquoted family names
+const RESERVED_WORDS: &[&str] = &["inherit", "initial", "unset", "revert", "revert-layer", "default"];
+
+enum Family {
+    Quoted(String),
+    Named(Vec<String>),
+    Generic(String),
+}
+
+impl Family {
+    fn to_css(&self) -> String {
+        match self {
+            Family::Quoted(name) => format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\"")),
+            Family::Named(words) => words.join(" "),
+            Family::Generic(keyword) => keyword.clone(),
+        }
+    }
+}
+
+// A CSS identifier: no leading digit, and a leading hyphen must be followed by a name character
+fn is_identifier(word: &str) -> bool {
+    let name_start = |c: char| c.is_ascii_alphabetic() || c == '_' || !c.is_ascii();
+    let name_char = |c: char| name_start(c) || c.is_ascii_digit() || c == '-';
+
+    let body = word.strip_prefix('-').unwrap_or(word);
+    body.starts_with(name_start) && body.chars().all(name_char)
+}
+
+// Splits on commas outside quoted strings, unescaping the quoted contents as it goes
+fn parse_families(text: &str) -> Option<Vec<Family>> {
+    let mut families = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let family = match chars.peek() {
+            Some(&quote) if quote == '"' || quote == '\'' => {
+                chars.next();
+                let mut name = String::new();
+                loop {
+                    match chars.next()? {
+                        '\\' => name.push(chars.next()?),
+                        c if c == quote => break,
+                        '\n' => return None,
+                        c => name.push(c),
+                    }
+                }
+                if name.trim().is_empty() {
+                    return None;
+                }
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                Family::Quoted(name)
+            },
+            _ => {
+                let mut item = String::new();
+                while let Some(c) = chars.next_if(|&c| c != ',') {
+                    item.push(c);
+                }
+                let words: Vec<String> = item.split_whitespace().map(str::to_string).collect();
+                match words.as_slice() {
+                    [] => return None,
+                    [word] if GENERIC_FAMILIES.contains(&word.to_ascii_lowercase().as_str()) => {
+                        Family::Generic(word.to_ascii_lowercase())
+                    },
+                    _ => {
+                        let reserved = |w: &String| {
+                            let lower = w.to_ascii_lowercase();
+                            RESERVED_WORDS.contains(&lower.as_str()) || GENERIC_FAMILIES.contains(&lower.as_str())
+                        };
+                        if !words.iter().all(|w| is_identifier(w)) || (words.len() == 1 && reserved(&words[0])) {
+                            return None;
+                        }
+                        Family::Named(words)
+                    },
+                }
+            },
+        };
+        families.push(family);
+
+        match chars.next() {
+            Some(',') => continue,
+            None => return Some(families),
+            Some(_) => return None,
+        }
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the list with single spacing, double-quoted strings and lowercase generic keywords
+fn coerce_font_family_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let families = parse_families(text)?;
+    let (last, rest) = families.split_last()?;
+
+    // A generic family is the final fallback, so nothing may follow it
+    if rest.iter().any(|family| matches!(family, Family::Generic(_))) {
+        return None;
+    }
+    if options.require_generic && !matches!(last, Family::Generic(_)) {
+        return None;
+    }
+
+    let css: Vec<String> = families.iter().map(Family::to_css).collect();
+    Some(Value::String(css.join(", ")))
+}
+
+// Only strings can be valid; the fast path calls coerce_font_family_str directly
+fn coerce_font_family(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_font_family_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_font_family(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_font_family(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_font_family(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_font_family(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_font_family(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_font_family(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_font_family(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_font_family(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_font_family(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_font_family_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_lists() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_font_family(&json!("Arial"), &defaults));
+        assert!(validate_font_family(&json!("sans-serif"), &defaults));
+        assert!(validate_font_family(&json!("Helvetica Neue, Arial, sans-serif"), &defaults));
+        assert!(validate_font_family(&json!("\"Times New Roman\", Times, serif"), &defaults));
+        assert!(validate_font_family(&json!("'Segoe UI', system-ui"), &defaults));
+        assert!(validate_font_family(&json!("\"serif\", monospace"), &defaults)); // Quoted keywords are family names
+        assert!(validate_font_family(&json!("Gill Sans Extrabold, fantasy"), &defaults));
+        assert!(validate_font_family(&json!("\"Font \\\"Quoted\\\"\""), &defaults));
+        assert!(validate_font_family(&json!("ヒラギノ角ゴ Pro, sans-serif"), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_lists() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_font_family(&json!(""), &defaults));
+        assert!(!validate_font_family(&json!("Arial,"), &defaults));
+        assert!(!validate_font_family(&json!("Arial,, serif"), &defaults));
+        assert!(!validate_font_family(&json!(", Arial"), &defaults));
+        assert!(!validate_font_family(&json!("serif, Arial"), &defaults)); // Generic must be last
+        assert!(!validate_font_family(&json!("sans-serif, monospace"), &defaults));
+        assert!(!validate_font_family(&json!("\"Unterminated, serif"), &defaults));
+        assert!(!validate_font_family(&json!("\"Times\" New Roman"), &defaults));
+        assert!(!validate_font_family(&json!("\"\", serif"), &defaults));
+        assert!(!validate_font_family(&json!("3rd Font"), &defaults));
+        assert!(!validate_font_family(&json!("Font!, serif"), &defaults));
+        assert!(!validate_font_family(&json!("inherit"), &defaults));
+        assert!(!validate_font_family(&json!("Arial, initial"), &defaults));
+        assert!(!validate_font_family(&json!(12), &defaults));
+    }
+
+    #[test]
+    fn test_require_generic() {
+        let required: ValidationOptions = serde_json::from_value(json!({"require_generic": true})).unwrap();
+
+        assert!(validate_font_family(&json!("Arial, sans-serif"), &required));
+        assert!(validate_font_family(&json!("MONOSPACE"), &required));
+        assert!(!validate_font_family(&json!("Arial"), &required));
+        assert!(!validate_font_family(&json!("Arial, \"sans-serif\""), &required));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            coerce_font_family(&json!("  Helvetica   Neue ,'Segoe UI',SANS-SERIF "), &defaults),
+            Some(json!("Helvetica Neue, \"Segoe UI\", sans-serif"))
+        );
+        assert_eq!(
+            coerce_font_family(&json!("'It\\'s \"Fun\"'"), &defaults),
+            Some(json!("\"It's \\\"Fun\\\"\""))
+        );
+        assert_eq!(coerce_font_family(&json!("serif, Arial"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "Arial"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "Arial", "options": {"require_generic": true}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "Arial", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_font_family(&json!("Arial,serif"), &defaults)),
+            ValidationResult { valid: true, value: json!("Arial, serif") }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_font_family(&json!("Arial,"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["Arial, sans-serif", "\"Times New Roman\", serif", "", ",,"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
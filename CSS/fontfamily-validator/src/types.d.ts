@@ -0,0 +1,37 @@
+// Types for the fontfamily validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Require the list to end with a generic family such as sans-serif (default: false) */
+    require_generic?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,553 @@
+// ABOUTME: WASM component for iCalendar files (RFC 5545), for event imports in calendar apps
+// ABOUTME: Checks line folding, BEGIN/END pairs, required properties such as UID and DTSTART, and date and URI values
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// Unfolding and parsing content lines, shared with the vCard validator
+#[path = "../../../contentline/contentline.rs"]
+mod contentline;
+
+use contentline::{is_date, is_date_time, is_uri, Component, ContentLine, Problem};
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Require CRLF line breaks and lines folded at 75 octets, as RFC 5545 does
+    strict_lines: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The physical line the problem was found on, counting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    // The property at fault, when there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    property: Option<String>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+            line: None,
+            property: None,
+        }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from(problem: Problem) -> Self {
+        ValidationResult {
+            error: Some(problem.code),
+            line: Some(problem.line),
+            property: problem.property,
+            ..ValidationResult::from(None)
+        }
+    }
+}
+
+// Error codes reported in detailed results, besides those for malformed lines and unbalanced
+// BEGIN and END lines
+const INVALID_COMPONENT: &str = "invalid_component";
+const UNSUPPORTED_VERSION: &str = "unsupported_version";
+const MISSING_PROPERTY: &str = "missing_property";
+const INVALID_PROPERTY_VALUE: &str = "invalid_property_value";
+
+// Properties whose value is a DATE-TIME, or a DATE with VALUE=DATE; EXDATE and RDATE take lists
+const DATE_OR_DATE_TIME: &[&str] = &["DTSTART", "DTEND", "DUE", "RECURRENCE-ID", "EXDATE", "RDATE"];
+// Properties whose value must be a DATE-TIME in UTC
+const UTC_DATE_TIME: &[&str] = &["DTSTAMP", "CREATED", "LAST-MODIFIED", "COMPLETED"];
+// Properties whose value is a URI; ORGANIZER and ATTENDEE are calendar addresses, usually mailto:
+const URI: &[&str] = &["URL", "TZURL", "ATTACH", "ORGANIZER", "ATTENDEE"];
+
+fn is_digits(text: &str) -> bool {
+    text.bytes().all(|b| b.is_ascii_digit())
+}
+
+// A DATE: YYYYMMDD, a real date
+fn is_calendar_date(text: &str) -> bool {
+    text.len() == 8 && is_digits(text) && is_date(text)
+}
+
+// A DATE-TIME: YYYYMMDDTHHMMSS, with a Z for UTC, and otherwise in local or floating time
+fn is_calendar_date_time(text: &str, utc: bool) -> bool {
+    let local = match text.strip_suffix('Z') {
+        Some(local) => local,
+        None if utc => return false,
+        None => text,
+    };
+    match local.split_once('T') {
+        Some((date, time)) => date.len() == 8 && time.len() == 6 && is_digits(date) && is_digits(time) && is_date_time(text),
+        None => false,
+    }
+}
+
+// A UTC offset, as a time zone's TZOFFSETFROM and TZOFFSETTO: +HHMM or -HHMM, with optional
+// seconds
+fn is_utc_offset(text: &str) -> bool {
+    let digits = match text.strip_prefix(['+', '-']) {
+        Some(digits) if matches!(digits.len(), 4 | 6) && is_digits(digits) => digits,
+        _ => return false,
+    };
+    digits[..2] < *"24" && digits[2..4] < *"60" && digits.get(4..).is_none_or(|seconds| seconds < "60")
+}
+
+// Whether a property's value is of its type, for the properties whose type is a date, a date
+// and time, a URI or a UTC offset; other values are text, which anything is
+fn check_value(property: &ContentLine) -> bool {
+    let name = property.name.as_str();
+    let value = property.value.as_str();
+    if DATE_OR_DATE_TIME.contains(&name) {
+        // RDATE may also list periods, which aren't checked
+        if property.value_type_is("PERIOD") {
+            return true;
+        }
+        let date = property.value_type_is("DATE");
+        return value.split(',').all(|value| if date { is_calendar_date(value) } else { is_calendar_date_time(value, false) });
+    }
+    if UTC_DATE_TIME.contains(&name) {
+        return is_calendar_date_time(value, true);
+    }
+    if URI.contains(&name) {
+        // An attachment may be inline base64 data instead
+        return property.value_type_is("BINARY") || is_uri(value);
+    }
+    match name {
+        "TZOFFSETFROM" | "TZOFFSETTO" => is_utc_offset(value),
+        _ => true,
+    }
+}
+
+// Checks a component inside a calendar: that it belongs in its parent, has the properties it
+// requires and values of the right types, and that its own components do too. Events need a
+// DTSTART unless the calendar has a METHOD, as scheduling messages may leave it out
+fn check_component(component: &Component, parent: &str, method: bool) -> Result<(), Problem> {
+    let name = component.name.as_str();
+    let allowed = match (parent, name) {
+        ("VCALENDAR", "VEVENT" | "VTODO" | "VJOURNAL" | "VFREEBUSY" | "VTIMEZONE") => true,
+        ("VEVENT" | "VTODO", "VALARM") => true,
+        ("VTIMEZONE", "STANDARD" | "DAYLIGHT") => true,
+        // Experimental components may appear anywhere
+        (_, name) => name.starts_with("X-"),
+    };
+    if !allowed {
+        return Err(Problem::at(INVALID_COMPONENT, component.line));
+    }
+
+    let required: &[&str] = match name {
+        "VEVENT" if method => &["UID", "DTSTAMP"],
+        "VEVENT" => &["UID", "DTSTAMP", "DTSTART"],
+        "VTODO" | "VJOURNAL" | "VFREEBUSY" => &["UID", "DTSTAMP"],
+        "VTIMEZONE" => &["TZID"],
+        "STANDARD" | "DAYLIGHT" => &["DTSTART", "TZOFFSETFROM", "TZOFFSETTO"],
+        "VALARM" => &["ACTION", "TRIGGER"],
+        _ => &[],
+    };
+    if let Some(missing) = required.iter().find(|&&required| component.property(required).is_none()) {
+        return Err(Problem { code: MISSING_PROPERTY, line: component.line, property: Some(missing.to_string()) });
+    }
+    if let Some(property) = component.properties.iter().find(|property| !check_value(property)) {
+        return Err(Problem::with(INVALID_PROPERTY_VALUE, property));
+    }
+    // A time zone is defined by its standard and daylight saving observances
+    if name == "VTIMEZONE" && !component.children.iter().any(|child| matches!(child.name.as_str(), "STANDARD" | "DAYLIGHT")) {
+        return Err(Problem::at(INVALID_COMPONENT, component.line));
+    }
+
+    component.children.iter().try_for_each(|child| check_component(child, name, method))
+}
+
+// Checks a calendar: its version and PRODID, and each of its components
+fn check_calendar(calendar: &Component) -> Result<(), Problem> {
+    if calendar.name != "VCALENDAR" {
+        return Err(Problem::at(INVALID_COMPONENT, calendar.line));
+    }
+
+    let missing = |name: &str| Problem { code: MISSING_PROPERTY, line: calendar.line, property: Some(name.to_string()) };
+    match calendar.property("VERSION") {
+        Some(version) if version.value.trim() == "2.0" => {}
+        Some(version) => return Err(Problem::with(UNSUPPORTED_VERSION, version)),
+        None => return Err(missing("VERSION")),
+    }
+    if calendar.property("PRODID").is_none() {
+        return Err(missing("PRODID"));
+    }
+
+    let method = calendar.property("METHOD").is_some();
+    calendar.children.iter().try_for_each(|component| check_component(component, "VCALENDAR", method))
+}
+
+fn detail_ics_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no calendars, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    let calendars = match contentline::parse(text, options.strict_lines).and_then(contentline::components) {
+        Ok(calendars) => calendars,
+        Err(problem) => return ValidationResult::from(problem),
+    };
+    if let Err(problem) = calendars.iter().try_for_each(check_calendar) {
+        return ValidationResult::from(problem);
+    }
+
+    let count = |name: &str| calendars.iter().flat_map(|calendar| &calendar.children).filter(|component| component.name == name).count();
+    ValidationResult::from(Some(json!({ "events": count("VEVENT"), "todos": count("VTODO"), "journals": count("VJOURNAL") })))
+}
+
+// Only strings can be valid; the fast path calls detail_ics_str directly
+fn detail_ics(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_ics_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns how many events, to-dos and journal entries the file holds
+fn coerce_ics(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_ics(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_ics(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_ics(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_ics(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_ics(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_ics(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_ics(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_ics(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_ics(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_ics(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_ics_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Example Corp//Calendar 1.0//EN\r\nBEGIN:VEVENT\r\n\
+        UID:20240314T153000Z-1@example.com\r\nDTSTAMP:20240301T120000Z\r\nDTSTART:20240314T153000Z\r\nDTEND:20240314T163000Z\r\n\
+        ORGANIZER;CN=Jane Doe:mailto:jane@example.com\r\nSUMMARY:Planning meeting\r\nBEGIN:VALARM\r\nACTION:DISPLAY\r\n\
+        TRIGGER:-PT15M\r\nEND:VALARM\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+    const TIMEZONE: &str = "BEGIN:VTIMEZONE\r\nTZID:Europe/London\r\nBEGIN:STANDARD\r\nDTSTART:19701025T020000\r\n\
+        TZOFFSETFROM:+0100\r\nTZOFFSETTO:+0000\r\nEND:STANDARD\r\nBEGIN:DAYLIGHT\r\nDTSTART:19700329T010000\r\n\
+        TZOFFSETFROM:+0000\r\nTZOFFSETTO:+0100\r\nEND:DAYLIGHT\r\nEND:VTIMEZONE\r\n";
+
+    fn detail(text: &str) -> ValidationResult {
+        detail_ics_str(text, &ValidationOptions::default())
+    }
+
+    #[test]
+    fn test_valid_calendars() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_ics(&json!(CALENDAR), &defaults), Some(json!({"events": 1, "todos": 0, "journals": 0})));
+
+        // A calendar as exporters write it: LF line breaks, a folded line, a time zone, local
+        // times, all-day events, exceptions and experimental properties and components
+        let exported = format!(
+            "BEGIN:VCALENDAR\nVERSION:2.0\nPRODID:-//Example//EN\nX-WR-CALNAME:Team\n{}BEGIN:VEVENT\nUID:1@example.com\n\
+            DTSTAMP:20240301T120000Z\nDTSTART;TZID=Europe/London:20240314T093000\nRRULE:FREQ=WEEKLY;COUNT=4\n\
+            EXDATE;TZID=Europe/London:20240321T093000,20240328T093000\nDESCRIPTION:A long description that\n  carries on\n\
+            END:VEVENT\nBEGIN:VEVENT\nUID:2@example.com\nDTSTAMP:20240301T120000Z\nDTSTART;VALUE=DATE:20241225\n\
+            RDATE;VALUE=PERIOD:20241226T090000Z/PT1H\nEND:VEVENT\nBEGIN:VTODO\nUID:3@example.com\nDTSTAMP:20240301T120000Z\n\
+            DUE:20240401T170000\nEND:VTODO\nBEGIN:X-CUSTOM\nANYTHING:goes\nEND:X-CUSTOM\nEND:VCALENDAR\n",
+            TIMEZONE.replace("\r\n", "\n")
+        );
+        assert_eq!(coerce_ics(&json!(exported), &defaults), Some(json!({"events": 2, "todos": 1, "journals": 0})));
+
+        // Scheduling messages may leave out an event's DTSTART
+        let request = CALENDAR.replace("VERSION:2.0", "VERSION:2.0\r\nMETHOD:CANCEL").replace("DTSTART:20240314T153000Z\r\n", "");
+        assert!(validate_ics(&json!(request), &defaults));
+        // An empty calendar holds nothing
+        let empty = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Example//EN\r\nEND:VCALENDAR\r\n";
+        assert_eq!(coerce_ics(&json!(empty), &defaults), Some(json!({"events": 0, "todos": 0, "journals": 0})));
+    }
+
+    #[test]
+    fn test_invalid_calendars() {
+        assert_eq!(detail("").error, None);
+        assert_eq!(detail("Planning meeting, 14 March").error, Some(contentline::MALFORMED_LINE));
+        assert_eq!(detail(&CALENDAR.replace("END:VALARM\r\n", "")).error, Some(contentline::UNBALANCED_COMPONENT));
+
+        let result = detail(&CALENDAR.replace("VERSION:2.0", "VERSION:1.0"));
+        assert_eq!((result.error, result.line, result.property.as_deref()), (Some(UNSUPPORTED_VERSION), Some(2), Some("VERSION")));
+        for property in ["VERSION:2.0", "PRODID:-//Example Corp//Calendar 1.0//EN"] {
+            let result = detail(&CALENDAR.replace(&format!("{property}\r\n"), ""));
+            assert_eq!((result.error, result.line), (Some(MISSING_PROPERTY), Some(1)), "{property}");
+        }
+        for property in ["UID:20240314T153000Z-1@example.com", "DTSTAMP:20240301T120000Z", "DTSTART:20240314T153000Z", "TRIGGER:-PT15M"] {
+            let result = detail(&CALENDAR.replace(&format!("{property}\r\n"), ""));
+            assert_eq!(result.error, Some(MISSING_PROPERTY), "{property}");
+            assert_eq!(result.property.as_deref(), property.split(':').next(), "{property}");
+        }
+        let result = detail(&CALENDAR.replace("UID:20240314T153000Z-1@example.com\r\n", ""));
+        assert_eq!(result.line, Some(4));
+
+        for (from, to) in [
+            ("DTSTART:20240314T153000Z", "DTSTART:20240230T153000Z"),
+            ("DTSTART:20240314T153000Z", "DTSTART:2024-03-14T15:30:00Z"),
+            ("DTSTART:20240314T153000Z", "DTSTART;VALUE=DATE:20240314T153000Z"),
+            ("DTEND:20240314T163000Z", "DTEND:20240314T256000Z"),
+            ("DTSTAMP:20240301T120000Z", "DTSTAMP:20240301T120000"),
+            ("ORGANIZER;CN=Jane Doe:mailto:jane@example.com", "ORGANIZER:jane@example.com"),
+            ("SUMMARY:Planning meeting", "EXDATE:20240321T153000Z,tomorrow"),
+        ] {
+            let result = detail(&CALENDAR.replace(from, to));
+            assert_eq!(result.error, Some(INVALID_PROPERTY_VALUE), "{to}");
+            assert_eq!(result.property.as_deref(), to.split([':', ';']).next(), "{to}");
+        }
+        let offset = CALENDAR.replace("BEGIN:VEVENT", &format!("{TIMEZONE}BEGIN:VEVENT")).replace("TZOFFSETTO:+0100", "TZOFFSETTO:+2500");
+        assert_eq!(detail(&offset).property.as_deref(), Some("TZOFFSETTO"));
+
+        // Components only belong in their own parents
+        let card = "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:Jane Doe\r\nEND:VCARD\r\n";
+        assert_eq!(detail(card).error, Some(INVALID_COMPONENT));
+        let alarm = CALENDAR.replace("BEGIN:VEVENT", "BEGIN:VALARM\r\nACTION:DISPLAY\r\nTRIGGER:-PT5M\r\nEND:VALARM\r\nBEGIN:VEVENT");
+        assert_eq!((detail(&alarm).error, detail(&alarm).line), (Some(INVALID_COMPONENT), Some(4)));
+        let bare = CALENDAR.replace("BEGIN:VEVENT", "BEGIN:VTIMEZONE\r\nTZID:UTC\r\nEND:VTIMEZONE\r\nBEGIN:VEVENT");
+        assert_eq!(detail(&bare).error, Some(INVALID_COMPONENT));
+        assert!(!validate_ics(&json!(42), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_options() {
+        let strict: ValidationOptions = serde_json::from_value(json!({"strict_lines": true})).unwrap();
+        assert!(validate_ics(&json!(CALENDAR), &strict));
+        assert_eq!(detail_ics_str(&CALENDAR.replace("\r\n", "\n"), &strict).error, Some(contentline::INVALID_LINE_BREAK));
+        let long = CALENDAR.replace("SUMMARY:Planning meeting", &format!("SUMMARY:Planning {}", "meeting ".repeat(10)));
+        assert_eq!(detail_ics_str(&long, &strict).line, Some(10));
+        assert_eq!(detail_ics_str(&long, &strict).error, Some(contentline::LINE_TOO_LONG));
+        assert!(validate_ics(&json!(long), &ValidationOptions::default()));
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": CALENDAR, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        assert_eq!(
+            messages::localize(&detail(&CALENDAR.replace("DTEND:20240314T163000Z", "DTEND:later")), &json!(CALENDAR), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "invalid_property_value",
+                "line": 8,
+                "property": "DTEND",
+                "message": messages::message("invalid_property_value", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail("  "), &json!("  "), Some("en"))["error"], "empty_value");
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [CALENDAR, ""], "options": {"strict_lines": true}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [CALENDAR, "", "BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n", "not a calendar"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
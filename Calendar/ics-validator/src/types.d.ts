@@ -0,0 +1,45 @@
+// Types for the ics validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Require CRLF line breaks and lines folded at 75 octets, as RFC 5545 does; many exporters write bare LFs and long lines (default: false) */
+    strict_lines?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    events: number;
+    todos: number;
+    journals: number;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The physical line the problem was found on, counting from 1 */
+    line?: number;
+    /** The property at fault, when there is one */
+    property?: string;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "malformed_line" | "invalid_line_break" | "line_too_long" | "unbalanced_component" | "invalid_component" | "unsupported_version" | "missing_property" | "invalid_property_value" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
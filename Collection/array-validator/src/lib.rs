@@ -0,0 +1,345 @@
+// ABOUTME: WASM component for array validation
+// ABOUTME: Validates every element of a JSON array with another validator, named by its datatype
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Datatype every element must have, such as "integer" or "url"; any element passes when unset
+    item_type: Option<String>,
+    // Options passed to the item type's validator for each element
+    item_options: Value,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Indices of the elements that failed the item type's validation
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failed: Vec<usize>,
+    // Why the value was rejected, when the array itself could not be checked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            failed: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+// Error code reported in detailed results when item_type names no known validator
+const UNKNOWN_ITEM_TYPE: &str = "unknown_item_type";
+
+// Detailed validation: coerces each element, recording the indices of those that fail
+fn detail_array(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let Some(items) = value.as_array() else {
+        return ValidationResult::from(None);
+    };
+
+    let coerce_item = match options.item_type.as_deref() {
+        Some(name) => match validator_registry::lookup(name) {
+            Some(coerce) => coerce,
+            None => return ValidationResult { error: Some(UNKNOWN_ITEM_TYPE), ..ValidationResult::from(None) },
+        },
+        None => return ValidationResult::from(Some(value.clone())),
+    };
+
+    let mut coerced = Vec::with_capacity(items.len());
+    let mut failed = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        match coerce_item(item, &options.item_options) {
+            Some(value) => coerced.push(value),
+            None => failed.push(index),
+        }
+    }
+
+    if failed.is_empty() {
+        ValidationResult::from(Some(Value::Array(coerced)))
+    } else {
+        ValidationResult { failed, ..ValidationResult::from(None) }
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the array with every element replaced by its canonical value
+fn coerce_array(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_array(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_array(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_array(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_array(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_array(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_array(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_array(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_array(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_array(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_array(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_arrays() {
+        let integers = options(json!({"item_type": "integer"}));
+
+        assert!(validate_array(&json!([]), &integers));
+        assert!(validate_array(&json!([1, 2, 3]), &integers));
+        assert!(validate_array(&json!([1, "2", " 3 "]), &integers));
+        assert!(validate_array(&json!(["https://example.com"]), &options(json!({"item_type": "url"}))));
+        assert!(validate_array(&json!([true, "yes", 0]), &options(json!({"item_type": "boolean"}))));
+        assert!(validate_array(&json!([1, "two", null]), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_invalid_arrays() {
+        let integers = options(json!({"item_type": "integer"}));
+
+        assert!(!validate_array(&json!([1, "two", 3]), &integers));
+        assert!(!validate_array(&json!("[1, 2, 3]"), &integers));
+        assert!(!validate_array(&json!({"0": 1}), &integers));
+        assert!(!validate_array(&json!(null), &ValidationOptions::default()));
+        assert!(!validate_array(&json!([1]), &options(json!({"item_type": "nonsense"}))));
+    }
+
+    #[test]
+    fn test_item_options() {
+        let v4 = options(json!({"item_type": "ip", "item_options": {"version": 4}}));
+
+        assert!(validate_array(&json!(["192.168.0.1", "10.0.0.1"]), &v4));
+        assert!(!validate_array(&json!(["192.168.0.1", "::1"]), &v4));
+
+        // Options the item validator rejects make every element invalid
+        let bad = options(json!({"item_type": "ip", "item_options": {"strict": true}}));
+        assert_eq!(detail_array(&json!(["10.0.0.1", "::1"]), &bad).failed, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_coerce() {
+        let integers = options(json!({"item_type": "integer"}));
+
+        assert_eq!(coerce_array(&json!(["1", 2, " 3"]), &integers), Some(json!([1, 2, 3])));
+        assert_eq!(coerce_array(&json!([]), &integers), Some(json!([])));
+        assert_eq!(coerce_array(&json!(["a", 1]), &integers), None);
+        assert_eq!(coerce_array(&json!(["a", 1]), &ValidationOptions::default()), Some(json!(["a", 1])));
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": []})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": [], "options": {"item_type": "integer", "item_options": {}}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": [], "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let integers = options(json!({"item_type": "integer"}));
+
+        assert_eq!(
+            detail_array(&json!(["1", 2]), &integers),
+            ValidationResult { valid: true, value: json!([1, 2]), failed: vec![], error: None }
+        );
+        assert_eq!(
+            detail_array(&json!([1, "two", 3, 4.5]), &integers),
+            ValidationResult { valid: false, value: Value::Null, failed: vec![1, 3], error: None }
+        );
+        assert_eq!(
+            detail_array(&json!([1]), &options(json!({"item_type": "array"}))),
+            ValidationResult { valid: false, value: Value::Null, failed: vec![], error: Some(UNKNOWN_ITEM_TYPE) }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Types for the array validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Datatype every element must have; without it any element passes */
+    item_type?: string;
+    /** Options passed to the item type's validator for each element */
+    item_options?: unknown;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = unknown[];
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = unknown[];
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Indices of the elements that failed */
+    failed?: number[];
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "unknown_item_type" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
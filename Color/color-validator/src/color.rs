@@ -0,0 +1,325 @@
+// ABOUTME: CSS color parser for hex, rgb()/rgba(), hsl()/hsla() and named colors
+// ABOUTME: Produces sRGB channels with alpha, plus WCAG relative luminance; shared with the contrast validator
+
+use serde::{Deserialize, Serialize};
+
+// The notation a color was written in
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Syntax {
+    Hex,
+    Rgb,
+    Hsl,
+    Named,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgba {
+    // Channels in 0-255
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    // Opacity in 0-1
+    pub a: f64,
+}
+
+impl Rgba {
+    // Relative luminance as defined by WCAG 2.x
+    pub fn luminance(&self) -> f64 {
+        fn linear(channel: f64) -> f64 {
+            let c = channel / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    // Alpha-composites this color over an opaque backdrop
+    pub fn over(&self, backdrop: &Rgba) -> Rgba {
+        let blend = |top: f64, bottom: f64| top * self.a + bottom * (1.0 - self.a);
+        Rgba {
+            r: blend(self.r, backdrop.r),
+            g: blend(self.g, backdrop.g),
+            b: blend(self.b, backdrop.b),
+            a: 1.0,
+        }
+    }
+
+    // Lowercase #rrggbb, with an alpha byte only when the color is not opaque
+    pub fn to_hex(self) -> String {
+        let byte = |c: f64| c.round().clamp(0.0, 255.0) as u8;
+        let rgb = format!("#{:02x}{:02x}{:02x}", byte(self.r), byte(self.g), byte(self.b));
+        if self.a < 1.0 {
+            format!("{}{:02x}", rgb, byte(self.a * 255.0))
+        } else {
+            rgb
+        }
+    }
+}
+
+// Parses any supported CSS color notation; names and function names are case-insensitive
+pub fn parse_color(text: &str) -> Option<Rgba> {
+    parse_color_syntax(text).map(|(color, _)| color)
+}
+
+// As parse_color, also reporting which notation the color was written in
+pub fn parse_color_syntax(text: &str) -> Option<(Rgba, Syntax)> {
+    let lower = text.trim().to_ascii_lowercase();
+
+    if let Some(hex) = lower.strip_prefix('#') {
+        return Some((parse_hex(hex)?, Syntax::Hex));
+    }
+
+    if let Some((name, args)) = lower.strip_suffix(')').and_then(|s| s.split_once('(')) {
+        return match name.trim_end() {
+            "rgb" | "rgba" => Some((parse_rgb_function(args)?, Syntax::Rgb)),
+            "hsl" | "hsla" => Some((parse_hsl_function(args)?, Syntax::Hsl)),
+            _ => None,
+        };
+    }
+
+    Some((named_color(&lower)?, Syntax::Named))
+}
+
+fn parse_hex(hex: &str) -> Option<Rgba> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| f64::from(d * 17));
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok().map(f64::from);
+
+    match hex.len() {
+        3 => Some(Rgba { r: digit(0)?, g: digit(1)?, b: digit(2)?, a: 1.0 }),
+        4 => Some(Rgba { r: digit(0)?, g: digit(1)?, b: digit(2)?, a: digit(3)? / 255.0 }),
+        6 => Some(Rgba { r: pair(0)?, g: pair(2)?, b: pair(4)?, a: 1.0 }),
+        8 => Some(Rgba { r: pair(0)?, g: pair(2)?, b: pair(4)?, a: pair(6)? / 255.0 }),
+        _ => None,
+    }
+}
+
+// Splits function arguments in either the legacy comma syntax (`1, 2, 3, 0.5`) or the
+// modern space syntax (`1 2 3 / 0.5`); returns the three components and the optional alpha
+fn split_arguments(args: &str) -> Option<([&str; 3], Option<&str>)> {
+    let (components, alpha): (Vec<&str>, Option<&str>) = if args.contains(',') {
+        let mut parts: Vec<&str> = args.split(',').map(str::trim).collect();
+        let alpha = if parts.len() == 4 { parts.pop() } else { None };
+        (parts, alpha)
+    } else {
+        let (main, alpha) = match args.split_once('/') {
+            Some((main, alpha)) => (main, Some(alpha.trim())),
+            None => (args, None),
+        };
+        (main.split_whitespace().collect(), alpha)
+    };
+
+    match components.as_slice() {
+        [a, b, c] if !a.is_empty() && !b.is_empty() && !c.is_empty() => Some(([*a, *b, *c], alpha)),
+        _ => None,
+    }
+}
+
+fn parse_number(text: &str) -> Option<f64> {
+    // Rust's float parser accepts "inf" and "nan", which CSS does not
+    if !text.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '-' || c == '+') {
+        return None;
+    }
+    text.parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+fn parse_percentage(text: &str) -> Option<f64> {
+    text.strip_suffix('%').and_then(parse_number)
+}
+
+// Alpha is a number in 0-1 or a percentage
+fn parse_alpha(text: Option<&str>) -> Option<f64> {
+    let alpha = match text {
+        None => return Some(1.0),
+        Some(text) => match parse_percentage(text) {
+            Some(percent) => percent / 100.0,
+            None => parse_number(text)?,
+        },
+    };
+
+    (0.0..=1.0).contains(&alpha).then_some(alpha)
+}
+
+fn parse_rgb_function(args: &str) -> Option<Rgba> {
+    let (components, alpha) = split_arguments(args)?;
+
+    // Each channel is a number in 0-255 or a percentage in 0-100%
+    let channel = |text: &str| {
+        let value = match parse_percentage(text) {
+            Some(percent) => percent / 100.0 * 255.0,
+            None => parse_number(text)?,
+        };
+        (0.0..=255.0).contains(&value).then_some(value)
+    };
+
+    Some(Rgba {
+        r: channel(components[0])?,
+        g: channel(components[1])?,
+        b: channel(components[2])?,
+        a: parse_alpha(alpha)?,
+    })
+}
+
+// Hue in degrees, accepting the deg, grad, rad and turn units
+fn parse_hue(text: &str) -> Option<f64> {
+    let degrees = if let Some(n) = text.strip_suffix("deg") {
+        parse_number(n)?
+    } else if let Some(n) = text.strip_suffix("grad") {
+        parse_number(n)? * 0.9
+    } else if let Some(n) = text.strip_suffix("rad") {
+        parse_number(n)?.to_degrees()
+    } else if let Some(n) = text.strip_suffix("turn") {
+        parse_number(n)? * 360.0
+    } else {
+        parse_number(text)?
+    };
+
+    Some(degrees.rem_euclid(360.0))
+}
+
+fn parse_hsl_function(args: &str) -> Option<Rgba> {
+    let (components, alpha) = split_arguments(args)?;
+
+    let hue = parse_hue(components[0])?;
+    let saturation = parse_percentage(components[1]).filter(|p| (0.0..=100.0).contains(p))? / 100.0;
+    let lightness = parse_percentage(components[2]).filter(|p| (0.0..=100.0).contains(p))? / 100.0;
+
+    // CSS Color 4 hsl-to-rgb conversion
+    let k = |n: f64| (n + hue / 30.0) % 12.0;
+    let a = saturation * lightness.min(1.0 - lightness);
+    let f = |n: f64| lightness - a * (k(n) - 3.0).min(9.0 - k(n)).clamp(-1.0, 1.0);
+
+    Some(Rgba {
+        r: f(0.0) * 255.0,
+        g: f(8.0) * 255.0,
+        b: f(4.0) * 255.0,
+        a: parse_alpha(alpha)?,
+    })
+}
+
+// The CSS named colors, plus transparent
+const NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff), ("antiquewhite", 0xfaebd7), ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4), ("azure", 0xf0ffff), ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4), ("black", 0x000000), ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff), ("blueviolet", 0x8a2be2), ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887), ("cadetblue", 0x5f9ea0), ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e), ("coral", 0xff7f50), ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc), ("crimson", 0xdc143c), ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b), ("darkcyan", 0x008b8b), ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9), ("darkgreen", 0x006400), ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b), ("darkmagenta", 0x8b008b), ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00), ("darkorchid", 0x9932cc), ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a), ("darkseagreen", 0x8fbc8f), ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f), ("darkslategrey", 0x2f4f4f), ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3), ("deeppink", 0xff1493), ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969), ("dimgrey", 0x696969), ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222), ("floralwhite", 0xfffaf0), ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff), ("gainsboro", 0xdcdcdc), ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700), ("goldenrod", 0xdaa520), ("gray", 0x808080),
+    ("green", 0x008000), ("greenyellow", 0xadff2f), ("grey", 0x808080),
+    ("honeydew", 0xf0fff0), ("hotpink", 0xff69b4), ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082), ("ivory", 0xfffff0), ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa), ("lavenderblush", 0xfff0f5), ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd), ("lightblue", 0xadd8e6), ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff), ("lightgoldenrodyellow", 0xfafad2), ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90), ("lightgrey", 0xd3d3d3), ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a), ("lightseagreen", 0x20b2aa), ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899), ("lightslategrey", 0x778899), ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0), ("lime", 0x00ff00), ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6), ("magenta", 0xff00ff), ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa), ("mediumblue", 0x0000cd), ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db), ("mediumseagreen", 0x3cb371), ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a), ("mediumturquoise", 0x48d1cc), ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970), ("mintcream", 0xf5fffa), ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5), ("navajowhite", 0xffdead), ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6), ("olive", 0x808000), ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500), ("orangered", 0xff4500), ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa), ("palegreen", 0x98fb98), ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093), ("papayawhip", 0xffefd5), ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f), ("pink", 0xffc0cb), ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6), ("purple", 0x800080), ("rebeccapurple", 0x663399),
+    ("red", 0xff0000), ("rosybrown", 0xbc8f8f), ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513), ("salmon", 0xfa8072), ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57), ("seashell", 0xfff5ee), ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0), ("skyblue", 0x87ceeb), ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090), ("slategrey", 0x708090), ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f), ("steelblue", 0x4682b4), ("tan", 0xd2b48c),
+    ("teal", 0x008080), ("thistle", 0xd8bfd8), ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0), ("violet", 0xee82ee), ("wheat", 0xf5deb3),
+    ("white", 0xffffff), ("whitesmoke", 0xf5f5f5), ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+fn named_color(name: &str) -> Option<Rgba> {
+    if name == "transparent" {
+        return Some(Rgba { r: 0.0, g: 0.0, b: 0.0, a: 0.0 });
+    }
+
+    NAMED_COLORS.iter().find(|(n, _)| *n == name).map(|(_, rgb)| Rgba {
+        r: f64::from((rgb >> 16) & 0xff),
+        g: f64::from((rgb >> 8) & 0xff),
+        b: f64::from(rgb & 0xff),
+        a: 1.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(text: &str) -> Option<String> {
+        parse_color(text).map(|c| c.to_hex())
+    }
+
+    #[test]
+    fn test_hex_colors() {
+        assert_eq!(hex("#fff"), Some("#ffffff".to_string()));
+        assert_eq!(hex("#FF8800"), Some("#ff8800".to_string()));
+        assert_eq!(hex("#0008"), Some("#00000088".to_string()));
+        assert_eq!(hex("#11223344"), Some("#11223344".to_string()));
+
+        assert_eq!(hex("#ff"), None);
+        assert_eq!(hex("#ggg"), None);
+        assert_eq!(hex("fff"), None);
+    }
+
+    #[test]
+    fn test_functional_colors() {
+        assert_eq!(hex("rgb(255, 0, 0)"), Some("#ff0000".to_string()));
+        assert_eq!(hex("RGBA(0,0,255,0.5)"), Some("#0000ff80".to_string()));
+        assert_eq!(hex("rgb(100% 50% 0% / 100%)"), Some("#ff8000".to_string()));
+        assert_eq!(hex("hsl(120, 100%, 50%)"), Some("#00ff00".to_string()));
+        assert_eq!(hex("hsl(0.5turn 100% 25%)"), Some("#008080".to_string()));
+        assert_eq!(hex("hsla(240deg, 100%, 50%, 0.5)"), Some("#0000ff80".to_string()));
+
+        assert_eq!(hex("rgb(256, 0, 0)"), None); // Out of range
+        assert_eq!(hex("rgb(1, 2)"), None); // Missing channel
+        assert_eq!(hex("rgb(0, 0, 0, 2)"), None); // Alpha out of range
+        assert_eq!(hex("hsl(120, 100, 50)"), None); // Saturation and lightness need %
+        assert_eq!(hex("rgb(inf, 0, 0)"), None);
+        assert_eq!(hex("cmyk(0, 0, 0, 0)"), None);
+    }
+
+    #[test]
+    fn test_named_colors() {
+        assert_eq!(hex("rebeccapurple"), Some("#663399".to_string()));
+        assert_eq!(hex(" Navy "), Some("#000080".to_string()));
+        assert_eq!(hex("transparent"), Some("#00000000".to_string()));
+        assert_eq!(hex("notacolor"), None);
+    }
+
+    #[test]
+    fn test_luminance() {
+        assert!((parse_color("white").unwrap().luminance() - 1.0).abs() < 1e-9);
+        assert!(parse_color("black").unwrap().luminance().abs() < 1e-9);
+    }
+}
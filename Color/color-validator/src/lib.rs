@@ -0,0 +1,427 @@
+// ABOUTME: WASM component for CSS color validation
+// ABOUTME: Provides validate and coerce functions for hex, rgb(), hsl() and named colors with per-syntax restrictions and palettes
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Luminance and compositing helpers here are only used by the contrast validator
+#[allow(dead_code)]
+mod color;
+
+use color::{parse_color_syntax, Syntax};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Only accept these notations: "hex", "rgb", "hsl" and "named"
+    syntaxes: Option<Vec<Syntax>>,
+    // Take an array of colors, such as a design system's palette, instead of a single color
+    palette: bool,
+    // Most colors a palette may have
+    max_colors: Option<usize>,
+    // Require every color in a palette to be written in the same notation
+    same_notation: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why a palette was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The palette entry the problem was found in, counting from 0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+            index: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results for palettes
+const INVALID_COLOR: &str = "invalid_color";
+const MIXED_NOTATION: &str = "mixed_notation";
+const TOO_MANY_ITEMS: &str = "too_many_items";
+const DUPLICATE_ITEM: &str = "duplicate_item";
+
+// A color's canonical hex form and the notation it was written in, when the syntaxes option
+// allows that notation
+fn parse_allowed(text: &str, options: &ValidationOptions) -> Option<(String, Syntax)> {
+    let (color, syntax) = parse_color_syntax(text)?;
+    if options.syntaxes.as_ref().is_some_and(|allowed| !allowed.contains(&syntax)) {
+        return None;
+    }
+    Some((color.to_hex(), syntax))
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the color as lowercase #rrggbb, or #rrggbbaa when it is not fully opaque
+fn coerce_color_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    parse_allowed(text, options).map(|(hex, _)| Value::String(hex))
+}
+
+// Checks a palette: every entry a color, at most max_colors of them, none the same color as
+// an earlier one once both are normalized, so that "#FFF" repeats "white", and with
+// same_notation, all written the same way as the first. Returns the canonical colors in order
+fn check_palette(colors: &[Value], options: &ValidationOptions) -> Result<Value, (&'static str, Option<usize>)> {
+    if options.max_colors.is_some_and(|max| colors.len() > max) {
+        return Err((TOO_MANY_ITEMS, None));
+    }
+    let mut canonical: Vec<String> = Vec::with_capacity(colors.len());
+    let mut first_syntax = None;
+    for (index, color) in colors.iter().enumerate() {
+        let (hex, syntax) = color.as_str().and_then(|text| parse_allowed(text, options)).ok_or((INVALID_COLOR, Some(index)))?;
+        if options.same_notation && *first_syntax.get_or_insert(syntax) != syntax {
+            return Err((MIXED_NOTATION, Some(index)));
+        }
+        if canonical.contains(&hex) {
+            return Err((DUPLICATE_ITEM, Some(index)));
+        }
+        canonical.push(hex);
+    }
+    Ok(Value::from(canonical))
+}
+
+// A single color is a string, and a palette a non-empty array of them
+fn detail_color(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value {
+        Value::Array(colors) if options.palette && !colors.is_empty() => match check_palette(colors, options) {
+            Ok(canonical) => ValidationResult::from(Some(canonical)),
+            Err((code, index)) => ValidationResult { error: Some(code), index, ..ValidationResult::from(None) },
+        },
+        Value::String(text) if !options.palette => ValidationResult::from(coerce_color_str(text, options)),
+        _ => ValidationResult::from(None),
+    }
+}
+
+// The fast path calls coerce_color_str directly
+fn coerce_color(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_color(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_color(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_color(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_color(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_color(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_color(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_color(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_color(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_color(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_color(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_color_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn syntaxes(list: Value) -> ValidationOptions {
+        serde_json::from_value(json!({ "syntaxes": list })).unwrap()
+    }
+
+    #[test]
+    fn test_valid_colors() {
+        let defaults = ValidationOptions::default();
+
+        for color in [
+            "#fff", "#FFFFFF", "#ffff", "#11223344", "rgb(255, 0, 0)", "rgba(0, 0, 255, 0.5)",
+            "rgb(100% 50% 0% / 50%)", "hsl(120, 100%, 50%)", "hsla(240deg 100% 50% / 0.5)",
+            "rebeccapurple", "Transparent", " red ",
+        ] {
+            assert!(validate_color(&json!(color), &defaults), "{}", color);
+        }
+    }
+
+    #[test]
+    fn test_invalid_colors() {
+        let defaults = ValidationOptions::default();
+
+        for color in ["", "#ff", "#fffff", "#ggg", "fff", "rgb(256, 0, 0)", "rgb(1, 2)", "hsl(120, 100, 50)", "notacolor", "red blue"] {
+            assert!(!validate_color(&json!(color), &defaults), "{}", color);
+        }
+        assert!(!validate_color(&json!(0xffffff), &defaults));
+    }
+
+    #[test]
+    fn test_syntaxes() {
+        let hex_only = syntaxes(json!(["hex"]));
+        let functions = syntaxes(json!(["rgb", "hsl"]));
+
+        assert!(validate_color(&json!("#336699"), &hex_only));
+        assert!(!validate_color(&json!("rgb(51, 102, 153)"), &hex_only));
+        assert!(!validate_color(&json!("navy"), &hex_only));
+
+        assert!(validate_color(&json!("rgb(51, 102, 153)"), &functions));
+        assert!(validate_color(&json!("hsl(210, 50%, 40%)"), &functions));
+        assert!(!validate_color(&json!("#336699"), &functions));
+
+        assert!(validate_color(&json!("navy"), &syntaxes(json!(["named"]))));
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"syntaxes": ["lab"]})).is_err());
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_color(&json!("#ABC"), &defaults), Some(json!("#aabbcc")));
+        assert_eq!(coerce_color(&json!("rgba(255, 0, 0, 0.5)"), &defaults), Some(json!("#ff000080")));
+        assert_eq!(coerce_color(&json!("RebeccaPurple"), &defaults), Some(json!("#663399")));
+        assert_eq!(coerce_color(&json!("notacolor"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "#fff"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "#fff", "options": {"syntaxes": ["hex"]}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "#fff", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_color(&json!("hsl(0, 100%, 50%)"), &defaults),
+            ValidationResult { valid: true, value: json!("#ff0000"), error: None, index: None }
+        );
+        assert_eq!(
+            detail_color(&json!("#12"), &defaults),
+            ValidationResult { valid: false, value: Value::Null, error: None, index: None }
+        );
+        let palette = serde_json::from_value(json!({"palette": true})).unwrap();
+        assert_eq!(
+            detail_color(&json!(["red", "blue", "#F00"]), &palette),
+            ValidationResult { valid: false, value: Value::Null, error: Some(DUPLICATE_ITEM), index: Some(2) }
+        );
+    }
+
+    #[test]
+    fn test_palette() {
+        let palette: ValidationOptions = serde_json::from_value(json!({"palette": true})).unwrap();
+        let code = |value: Value, options: &ValidationOptions| detail_color(&value, options).error;
+
+        assert_eq!(coerce_color(&json!(["#ABC", "rgb(0, 0, 0)", "white"]), &palette), Some(json!(["#aabbcc", "#000000", "#ffffff"])));
+        assert_eq!(coerce_color(&json!("red"), &palette), None);
+        assert_eq!(coerce_color(&json!([]), &palette), None);
+        assert_eq!(coerce_color(&json!(["red"]), &ValidationOptions::default()), None);
+
+        assert_eq!(code(json!(["red", "notacolor"]), &palette), Some(INVALID_COLOR));
+        assert_eq!(code(json!(["red", 0xff0000]), &palette), Some(INVALID_COLOR));
+        assert_eq!(code(json!(["white", "#FFF"]), &palette), Some(DUPLICATE_ITEM));
+        // Transparency makes a different color
+        assert!(validate_color(&json!(["#fff", "#fff8"]), &palette));
+
+        let limited = serde_json::from_value(json!({"palette": true, "max_colors": 2})).unwrap();
+        assert!(validate_color(&json!(["red", "blue"]), &limited));
+        assert_eq!(code(json!(["red", "blue", "green"]), &limited), Some(TOO_MANY_ITEMS));
+
+        let same = serde_json::from_value(json!({"palette": true, "same_notation": true})).unwrap();
+        assert!(validate_color(&json!(["#f00", "#00ff00"]), &same));
+        assert_eq!(detail_color(&json!(["#f00", "#0f0", "blue"]), &same).index, Some(2));
+        assert_eq!(code(json!(["#f00", "#0f0", "blue"]), &same), Some(MIXED_NOTATION));
+
+        let hex_only = serde_json::from_value(json!({"palette": true, "syntaxes": ["hex"]})).unwrap();
+        assert_eq!(code(json!(["#f00", "blue"]), &hex_only), Some(INVALID_COLOR));
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["#ff0000", "red", "rgb(0, 0, 0)", "#ggg", "notacolor"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// Types for the color validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Only accept these notations */
+    syntaxes?: ("hex" | "rgb" | "hsl" | "named")[];
+    /** Take an array of colors instead of a single color, rejecting entries that repeat an earlier color once normalized (default: false) */
+    palette?: boolean;
+    /** Most colors a palette may have */
+    max_colors?: number;
+    /** Require every color in a palette to be written in the same notation (default: false) */
+    same_notation?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string | unknown[];
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string | string[];
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The palette entry the problem was found in, counting from 0 */
+    index?: number;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "invalid_color" | "too_many_items" | "duplicate_item" | "mixed_notation" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,388 @@
+// ABOUTME: WASM component for color contrast validation
+// ABOUTME: Provides validate and coerce functions to check a foreground/background pair against WCAG contrast levels
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// The color parser is shared with the color validator
+#[path = "../../color-validator/src/color.rs"]
+mod color;
+
+use color::{parse_color, Rgba};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // WCAG conformance level to check against: "AA" (default) or "AAA"
+    level: Level,
+    // Apply the lower thresholds WCAG allows for large-scale text
+    large_text: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Default, Clone, Copy)]
+pub enum Level {
+    #[default]
+    #[serde(rename = "AA")]
+    Aa,
+    #[serde(rename = "AAA")]
+    Aaa,
+}
+
+impl ValidationOptions {
+    // Minimum contrast ratio from WCAG 2.x success criteria 1.4.3 and 1.4.6
+    fn required_ratio(&self) -> f64 {
+        match (self.level, self.large_text) {
+            (Level::Aa, false) => 4.5,
+            (Level::Aa, true) => 3.0,
+            (Level::Aaa, false) => 7.0,
+            (Level::Aaa, true) => 4.5,
+        }
+    }
+}
+
+// Accepts { "foreground": ..., "background": ... } objects or [foreground, background] pairs
+fn color_pair(value: &Value) -> Option<(&str, &str)> {
+    match value {
+        Value::Object(map) if map.len() == 2 => {
+            Some((map.get("foreground")?.as_str()?, map.get("background")?.as_str()?))
+        },
+        Value::Array(items) => match items.as_slice() {
+            [foreground, background] => Some((foreground.as_str()?, background.as_str()?)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// WCAG contrast ratio between two opaque colors, from 1 (identical) to 21 (black on white)
+fn contrast_ratio(first: &Rgba, second: &Rgba) -> f64 {
+    let (a, b) = (first.luminance(), second.luminance());
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the normalized pair and its contrast ratio when the pair meets the required level
+fn coerce_contrast(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let (foreground_text, background_text) = color_pair(value)?;
+    let background = parse_color(background_text)?;
+    let foreground = parse_color(foreground_text)?;
+
+    // A translucent background depends on whatever lies beneath it, so contrast is undefined
+    if background.a < 1.0 {
+        return None;
+    }
+
+    // A translucent foreground is seen blended with the background
+    let ratio = contrast_ratio(&foreground.over(&background), &background);
+    if ratio < options.required_ratio() {
+        return None;
+    }
+
+    // The reported ratio is truncated so it never rounds up past a threshold it failed
+    Some(serde_json::json!({
+        "foreground": foreground.to_hex(),
+        "background": background.to_hex(),
+        "ratio": (ratio * 100.0).floor() / 100.0,
+    }))
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_contrast(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_contrast(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_contrast(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_contrast(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_contrast(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_contrast(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_contrast(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_contrast(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_contrast(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_pairs() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_contrast(&json!({"foreground": "#000", "background": "#fff"}), &defaults));
+        assert!(validate_contrast(&json!(["white", "navy"]), &defaults));
+        assert!(validate_contrast(&json!(["#767676", "#ffffff"]), &defaults)); // 4.54:1
+        assert!(validate_contrast(&json!(["rgb(0 0 0 / 80%)", "white"]), &defaults));
+        assert!(validate_contrast(&json!(["hsl(0, 0%, 20%)", "hsl(0, 0%, 100%)"]), &defaults));
+    }
+
+    #[test]
+    fn test_insufficient_contrast() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_contrast(&json!(["#777777", "#ffffff"]), &defaults)); // 4.48:1
+        assert!(!validate_contrast(&json!(["yellow", "white"]), &defaults));
+        assert!(!validate_contrast(&json!(["#000", "#000"]), &defaults));
+        assert!(!validate_contrast(&json!(["rgba(0, 0, 0, 0.1)", "white"]), &defaults));
+    }
+
+    #[test]
+    fn test_levels() {
+        let aa_large = options(json!({"large_text": true}));
+        let aaa = options(json!({"level": "AAA"}));
+        let aaa_large = options(json!({"level": "AAA", "large_text": true}));
+
+        // #777 on white is 4.48:1
+        assert!(validate_contrast(&json!(["#777", "#fff"]), &aa_large));
+        assert!(!validate_contrast(&json!(["#777", "#fff"]), &aaa_large));
+
+        // #595959 on white is 7.0:1
+        assert!(validate_contrast(&json!(["#595959", "#fff"]), &aaa));
+        assert!(!validate_contrast(&json!(["#767676", "#fff"]), &aaa));
+        assert!(validate_contrast(&json!(["#767676", "#fff"]), &aaa_large));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"level": "A"})).is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_contrast(&json!(["#000"]), &defaults));
+        assert!(!validate_contrast(&json!(["#000", "#fff", "#888"]), &defaults));
+        assert!(!validate_contrast(&json!(["#000", "notacolor"]), &defaults));
+        assert!(!validate_contrast(&json!({"foreground": "#000"}), &defaults));
+        assert!(!validate_contrast(&json!({"foreground": "#000", "background": "#fff", "extra": 1}), &defaults));
+        assert!(!validate_contrast(&json!(["#000", "rgba(255, 255, 255, 0.5)"]), &defaults)); // Translucent background
+        assert!(!validate_contrast(&json!("#000 on #fff"), &defaults));
+        assert!(!validate_contrast(&json!(null), &defaults));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            coerce_contrast(&json!(["Black", "#FFF"]), &defaults),
+            Some(json!({"foreground": "#000000", "background": "#ffffff", "ratio": 21.0}))
+        );
+        assert_eq!(
+            coerce_contrast(&json!({"foreground": "#767676", "background": "white"}), &defaults),
+            Some(json!({"foreground": "#767676", "background": "#ffffff", "ratio": 4.54}))
+        );
+        assert_eq!(coerce_contrast(&json!(["#777", "#fff"]), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": ["#000", "#fff"]})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": ["#000", "#fff"], "options": {"level": "AAA"}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": ["#000", "#fff"], "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_contrast(&json!(["#777", "#fff"]), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+
+        let batch: BatchInput = serde_json::from_value(json!({"values": [["#000", "#fff"], "x"]})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+}
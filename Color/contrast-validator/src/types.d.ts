@@ -0,0 +1,43 @@
+// Types for the contrast validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** WCAG conformance level to check against: AA (4.5:1) or AAA (7:1) (default: "AA") */
+    level?: "AA" | "AAA";
+    /** Use the large-text thresholds instead (3:1 for AA, 4.5:1 for AAA) (default: false) */
+    large_text?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = Record<string, unknown> | unknown[];
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    foreground: string;
+    background: string;
+    ratio: number;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
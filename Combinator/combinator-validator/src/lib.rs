@@ -0,0 +1,399 @@
+// ABOUTME: WASM component composing the other validators with anyOf, allOf, not and optional
+// ABOUTME: Evaluates an expression tree whose leaves are datatype names from the validator registry
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The expression to evaluate; any value passes when unset
+    expression: Option<Expr>,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when the expression itself could not be evaluated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error code reported in detailed results when the expression names no known validator
+const UNKNOWN_TYPE: &str = "unknown_type";
+
+// A node of the expression tree, written as JSON:
+// "integer", {"type": "integer", "options": {..}}, {"anyOf": [..]}, {"allOf": [..]},
+// {"not": ..} or {"optional": ..}
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(untagged)]
+pub enum Expr {
+    Type(String),
+    Typed(TypedExpr),
+    Combinator(Combinator),
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TypedExpr {
+    #[serde(rename = "type")]
+    type_name: String,
+    #[serde(default)]
+    options: Value,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub enum Combinator {
+    // Valid when any operand is; the value is coerced by the first that matches
+    AnyOf(Vec<Expr>),
+    // Valid when every operand is; the value is coerced by the first operand
+    AllOf(Vec<Expr>),
+    // Valid when the operand is not; the value is left as given
+    Not(Box<Expr>),
+    // Null is valid as it is; anything else must match the operand
+    Optional(Box<Expr>),
+}
+
+fn check_type(type_name: &str, options: &Value, value: &Value) -> Result<Option<Value>, &'static str> {
+    let coerce = validator_registry::lookup(type_name).ok_or(UNKNOWN_TYPE)?;
+    Ok(coerce(value, options))
+}
+
+// Evaluates the expression against the value, returning its canonical value when it matches.
+// An unknown datatype anywhere in the tree is an error, even where the result wouldn't matter,
+// so that a typo can't turn {"not": "ulr"} into a rule that rejects nothing
+fn evaluate(expr: &Expr, value: &Value) -> Result<Option<Value>, &'static str> {
+    match expr {
+        Expr::Type(type_name) => check_type(type_name, &Value::Null, value),
+        Expr::Typed(typed) => check_type(&typed.type_name, &typed.options, value),
+        Expr::Combinator(Combinator::AnyOf(operands)) => {
+            let results = operands.iter().map(|operand| evaluate(operand, value)).collect::<Result<Vec<_>, _>>()?;
+            Ok(results.into_iter().flatten().next())
+        },
+        Expr::Combinator(Combinator::AllOf(operands)) => {
+            let results = operands.iter().map(|operand| evaluate(operand, value)).collect::<Result<Vec<_>, _>>()?;
+            if results.iter().any(Option::is_none) {
+                return Ok(None);
+            }
+            Ok(Some(results.into_iter().flatten().next().unwrap_or_else(|| value.clone())))
+        },
+        Expr::Combinator(Combinator::Not(operand)) => {
+            Ok(evaluate(operand, value)?.is_none().then(|| value.clone()))
+        },
+        Expr::Combinator(Combinator::Optional(operand)) => {
+            let result = evaluate(operand, value)?;
+            Ok(if value.is_null() { Some(Value::Null) } else { result })
+        },
+    }
+}
+
+// Detailed validation; an unknown datatype is reported with its own error code
+fn detail_combinator(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let Some(expression) = &options.expression else {
+        return ValidationResult::from(Some(value.clone()));
+    };
+
+    match evaluate(expression, value) {
+        Ok(coerced) => ValidationResult::from(coerced),
+        Err(code) => ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the value as coerced by the validator that matched it
+fn coerce_combinator(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_combinator(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_combinator(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_combinator(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_combinator(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_combinator(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_combinator(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_combinator(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_combinator(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_combinator(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_combinator(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn expression(value: Value) -> ValidationOptions {
+        serde_json::from_value(json!({"expression": value})).unwrap()
+    }
+
+    #[test]
+    fn test_any_of() {
+        let number_or_flag = expression(json!({"anyOf": ["integer", "boolean"]}));
+
+        assert!(validate_combinator(&json!(42), &number_or_flag));
+        assert!(validate_combinator(&json!("yes"), &number_or_flag));
+        assert!(!validate_combinator(&json!("maybe"), &number_or_flag));
+        assert!(!validate_combinator(&json!(1), &expression(json!({"anyOf": []}))));
+    }
+
+    #[test]
+    fn test_all_of_and_not() {
+        let internal_v4 = expression(json!({"allOf": [
+            {"type": "ip", "options": {"version": 4}},
+            {"not": {"type": "ip", "options": {"reject_private": true, "reject_loopback": true}}},
+        ]}));
+
+        // IPv4 addresses that the private/loopback rejection would turn away
+        assert!(validate_combinator(&json!("192.168.1.1"), &internal_v4));
+        assert!(!validate_combinator(&json!("8.8.8.8"), &internal_v4));
+        assert!(!validate_combinator(&json!("::1"), &internal_v4));
+
+        let not_url = expression(json!({"not": "url"}));
+        assert!(validate_combinator(&json!("just some words"), &not_url));
+        assert!(!validate_combinator(&json!("https://example.com"), &not_url));
+        assert!(validate_combinator(&json!("anything"), &expression(json!({"allOf": []}))));
+    }
+
+    #[test]
+    fn test_optional() {
+        let optional_float = expression(json!({"optional": "float"}));
+
+        assert!(validate_combinator(&json!(null), &optional_float));
+        assert!(validate_combinator(&json!(1.5), &optional_float));
+        assert!(!validate_combinator(&json!("abc"), &optional_float));
+    }
+
+    #[test]
+    fn test_unknown_types() {
+        let typo = expression(json!({"not": "ulr"}));
+
+        assert!(!validate_combinator(&json!("anything"), &typo));
+        assert_eq!(detail_combinator(&json!("anything"), &typo).error, Some(UNKNOWN_TYPE));
+        assert_eq!(detail_combinator(&json!(1), &expression(json!({"anyOf": ["integer", "nope"]}))).error, Some(UNKNOWN_TYPE));
+
+        let malformed = [json!({"oneOf": ["integer"]}), json!({"anyOf": "integer"}), json!({"type": "integer", "min": 1}), json!(42)];
+        for expr in malformed {
+            assert!(serde_json::from_value::<ValidationOptions>(json!({"expression": expr})).is_err(), "{}", expr);
+        }
+    }
+
+    #[test]
+    fn test_coerce() {
+        let number_or_flag = expression(json!({"anyOf": ["integer", "boolean"]}));
+
+        assert_eq!(coerce_combinator(&json!("42"), &number_or_flag), Some(json!(42)));
+        assert_eq!(coerce_combinator(&json!("off"), &number_or_flag), Some(json!(false)));
+        assert_eq!(coerce_combinator(&json!("x"), &expression(json!({"not": "integer"}))), Some(json!("x")));
+        assert_eq!(coerce_combinator(&json!("anything"), &ValidationOptions::default()), Some(json!("anything")));
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 1})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 1, "options": {"expression": {"optional": "integer"}}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 1, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let optional_integer = expression(json!({"optional": "integer"}));
+
+        assert_eq!(
+            detail_combinator(&json!(" 7 "), &optional_integer),
+            ValidationResult { valid: true, value: json!(7), error: None }
+        );
+        assert_eq!(
+            detail_combinator(&json!("seven"), &optional_integer),
+            ValidationResult { valid: false, value: Value::Null, error: None }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+}
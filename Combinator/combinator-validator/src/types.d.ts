@@ -0,0 +1,45 @@
+// Types for the combinator validator's exports, added to the .d.ts that wasm-bindgen generates
+
+export type Expression =
+    | string
+    | { type: string; options?: unknown }
+    | { anyOf: Expression[] }
+    | { allOf: Expression[] }
+    | { not: Expression }
+    | { optional: Expression };
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Expression tree to evaluate: a datatype name, { type, options }, { anyOf }, { allOf }, { not } or { optional }; any value passes when unset */
+    expression?: Expression;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string | number | boolean | Record<string, unknown> | unknown[] | null;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = unknown;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "unknown_type" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
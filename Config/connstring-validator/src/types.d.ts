@@ -0,0 +1,54 @@
+// Types for the connstring validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Only accept connection strings for these databases */
+    engines?: ("postgres" | "mysql" | "mongodb" | "redis")[];
+    /** Whether a password may (allowed), must (required) or must not (forbidden) be embedded (default: "allowed") */
+    credentials?: "allowed" | "required" | "forbidden";
+    /** Parameters that may appear; any parameter may when unset */
+    allowed_params?: string[];
+    /** Reject connection strings that don't name a database (default: false) */
+    require_database?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    engine: "postgres" | "mysql" | "mongodb" | "redis";
+    hosts: ({
+        host: string;
+        port: number | null;
+    })[];
+    user: string | null;
+    database: string | null;
+    params: Record<string, string>;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Concerns that don't make the value invalid */
+    warnings?: "embedded_credentials"[];
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "malformed" | "unsupported_engine" | "missing_host" | "invalid_host" | "invalid_port" | "invalid_database" | "missing_database" | "missing_credentials" | "embedded_credentials" | "unknown_param" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,420 @@
+// ABOUTME: WASM component for feature-flag keys and environment names, for flag management tools
+// ABOUTME: Checks key hygiene: lowercase kebab or snake case, length, leading digits, reserved prefixes
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Separator between words
+    style: Style,
+    // Longest key accepted, in characters
+    max_length: usize,
+    // Accept keys that start with a digit
+    allow_leading_digit: bool,
+    // Prefixes kept for the platform's own flags; keys starting with one are rejected
+    reserved_prefixes: Vec<String>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            style: Style::default(),
+            max_length: 64,
+            allow_leading_digit: false,
+            reserved_prefixes: Vec::new(),
+        }
+    }
+}
+
+// How words are joined in a key
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Style {
+    // new-checkout-flow
+    Kebab,
+    // new_checkout_flow
+    Snake,
+    // Either of the above, though not both in one key
+    #[default]
+    Either,
+}
+
+impl Style {
+    fn allows(self, separator: u8) -> bool {
+        match self {
+            Style::Kebab => separator == b'-',
+            Style::Snake => separator == b'_',
+            Style::Either => true,
+        }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const INVALID_LENGTH: &str = "invalid_length";
+const INVALID_CHARACTER: &str = "invalid_character";
+const INVALID_BOUNDARY: &str = "invalid_boundary";
+const LEADING_DIGIT: &str = "leading_digit";
+const ADJACENT_SEPARATORS: &str = "adjacent_separators";
+const MIXED_SEPARATORS: &str = "mixed_separators";
+const RESERVED_PREFIX: &str = "reserved_prefix";
+
+fn is_separator(b: u8) -> bool {
+    b == b'-' || b == b'_'
+}
+
+// The key rules, checked in order so that each key gets the most basic of its problems
+fn check_key(text: &str, options: &ValidationOptions) -> Result<String, &'static str> {
+    let key = text.trim();
+    let bytes = key.as_bytes();
+
+    if key.is_empty() || key.len() > options.max_length {
+        return Err(INVALID_LENGTH);
+    }
+    if !bytes.iter().all(|&b| b.is_ascii_lowercase() || b.is_ascii_digit() || (is_separator(b) && options.style.allows(b))) {
+        return Err(INVALID_CHARACTER);
+    }
+    if is_separator(bytes[0]) || is_separator(bytes[bytes.len() - 1]) {
+        return Err(INVALID_BOUNDARY);
+    }
+    if bytes[0].is_ascii_digit() && !options.allow_leading_digit {
+        return Err(LEADING_DIGIT);
+    }
+    if bytes.windows(2).any(|pair| is_separator(pair[0]) && is_separator(pair[1])) {
+        return Err(ADJACENT_SEPARATORS);
+    }
+    if key.contains('-') && key.contains('_') {
+        return Err(MIXED_SEPARATORS);
+    }
+    if options.reserved_prefixes.iter().any(|prefix| key.starts_with(prefix.as_str())) {
+        return Err(RESERVED_PREFIX);
+    }
+
+    Ok(key.to_string())
+}
+
+// Detailed validation; only strings can be valid, and a string's problem is reported
+fn detail_key(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str().map(|text| check_key(text, options)) {
+        Some(Ok(key)) => ValidationResult::from(Some(Value::String(key))),
+        Some(Err(code)) => ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the key without surrounding whitespace; flag SDKs look keys up exactly, so uppercase
+// letters are rejected rather than lowercased
+fn coerce_key(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    check_key(value.as_str()?, options).ok().map(Value::String)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_key(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_key(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_key(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_key(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_key(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_key(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_key(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_key(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_key(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    check_key(value, &ValidationOptions::default()).is_ok()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error(key: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_key(&json!(key), options).error
+    }
+
+    #[test]
+    fn test_valid_keys() {
+        let defaults = ValidationOptions::default();
+
+        for key in ["new-checkout-flow", "enable_dark_mode", "beta", "v2-api", "a", &"a".repeat(64)] {
+            assert!(validate_key(&json!(key), &defaults), "{key}");
+        }
+        assert_eq!(coerce_key(&json!("  new-checkout-flow "), &defaults), Some(json!("new-checkout-flow")));
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(error("", &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error("   ", &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error(&"a".repeat(65), &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error("NewCheckout", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("new checkout", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("new.checkout", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("café", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("-beta", &defaults), Some(INVALID_BOUNDARY));
+        assert_eq!(error("beta_", &defaults), Some(INVALID_BOUNDARY));
+        assert_eq!(error("2024-redesign", &defaults), Some(LEADING_DIGIT));
+        assert_eq!(error("new--checkout", &defaults), Some(ADJACENT_SEPARATORS));
+        assert_eq!(error("new_-checkout", &defaults), Some(ADJACENT_SEPARATORS));
+        assert_eq!(error("new-checkout_flow", &defaults), Some(MIXED_SEPARATORS));
+
+        // Only strings can be keys, and other values have no specific reason
+        assert_eq!(detail_key(&json!(123), &defaults), ValidationResult::from(None));
+    }
+
+    #[test]
+    fn test_options() {
+        let kebab: ValidationOptions = serde_json::from_value(json!({"style": "kebab"})).unwrap();
+        assert!(validate_key(&json!("new-checkout"), &kebab));
+        assert_eq!(error("new_checkout", &kebab), Some(INVALID_CHARACTER));
+
+        let snake: ValidationOptions = serde_json::from_value(json!({"style": "snake"})).unwrap();
+        assert!(validate_key(&json!("new_checkout"), &snake));
+        assert_eq!(error("new-checkout", &snake), Some(INVALID_CHARACTER));
+
+        let short: ValidationOptions = serde_json::from_value(json!({"max_length": 8})).unwrap();
+        assert!(validate_key(&json!("staging"), &short));
+        assert_eq!(error("production", &short), Some(INVALID_LENGTH));
+
+        let digits: ValidationOptions = serde_json::from_value(json!({"allow_leading_digit": true})).unwrap();
+        assert!(validate_key(&json!("2024-redesign"), &digits));
+
+        let reserved: ValidationOptions =
+            serde_json::from_value(json!({"reserved_prefixes": ["internal-", "sys_"]})).unwrap();
+        assert_eq!(error("internal-billing", &reserved), Some(RESERVED_PREFIX));
+        assert_eq!(error("sys_audit", &reserved), Some(RESERVED_PREFIX));
+        assert!(validate_key(&json!("internals-page"), &reserved));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"style": "camel"})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "beta", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_key(&json!("beta"), &defaults),
+            ValidationResult { valid: true, value: json!("beta"), error: None }
+        );
+        assert_eq!(
+            detail_key(&json!("Beta"), &defaults),
+            ValidationResult { valid: false, value: Value::Null, error: Some(INVALID_CHARACTER) }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["beta", 1], "options": {"style": "snake"}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["new-checkout-flow", " beta ", "NewCheckout", "2024-redesign", ""] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,43 @@
+// Types for the flagkey validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Separator between words: hyphens for "kebab", underscores for "snake", or either one, though not both in the same key (default: "either") */
+    style?: "kebab" | "snake" | "either";
+    /** Longest key accepted, in characters (default: 64) */
+    max_length?: number;
+    /** Accept keys that start with a digit, such as "2024-redesign" (default: false) */
+    allow_leading_digit?: boolean;
+    /** Prefixes kept for the platform's own flags, such as "internal-"; keys starting with one are rejected (default: []) */
+    reserved_prefixes?: string[];
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "invalid_length" | "invalid_character" | "invalid_boundary" | "leading_digit" | "adjacent_separators" | "mixed_separators" | "reserved_prefix" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,557 @@
+// ABOUTME: WASM component for key-value pair strings such as annotations, connection strings and feature flags
+// ABOUTME: Parses `key1=value1;key2=value2`, checking the keys and validating each value with the datatype mapped to its key
+
+use std::collections::BTreeMap;
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+// Quoting follows the list validator's rules; only its quoted-item helpers are needed here
+#[path = "../../../List/list-validator/src/split.rs"]
+#[allow(dead_code)]
+mod split;
+
+use split::{quoted, skip_whitespace, SplitError};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Separator between pairs
+    pair_separator: Separator,
+    // Separator between a key and its value
+    key_value_separator: Separator,
+    // Character around a value that contains the pair separator; null turns quoting off
+    quote: Option<char>,
+    // Keys that may appear; any key may when unset
+    allowed_keys: Option<Vec<String>>,
+    // Keys that must appear
+    required_keys: Vec<String>,
+    // Key → datatype name, or {type, options}, for the values that must have a datatype
+    value_types: BTreeMap<String, ValueType>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            pair_separator: Separator(";".to_string()),
+            key_value_separator: Separator("=".to_string()),
+            quote: Some('"'),
+            allowed_keys: None,
+            required_keys: Vec::new(),
+            value_types: BTreeMap::new(),
+        }
+    }
+}
+
+// A non-empty separator
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Separator(String);
+
+impl TryFrom<String> for Separator {
+    type Error = &'static str;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        if text.is_empty() {
+            return Err("empty separator");
+        }
+        Ok(Separator(text))
+    }
+}
+
+impl From<Separator> for String {
+    fn from(separator: Separator) -> Self {
+        separator.0
+    }
+}
+
+// How one key's value is validated: either just a datatype name, or the full form
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ValueType {
+    Type(String),
+    Rule(ValueRule),
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValueRule {
+    #[serde(rename = "type")]
+    type_name: String,
+    // Options passed to the datatype's validator
+    #[serde(default)]
+    options: Value,
+}
+
+impl ValueType {
+    fn rule(&self) -> (&str, &Value) {
+        match self {
+            ValueType::Type(type_name) => (type_name, &Value::Null),
+            ValueType::Rule(rule) => (&rule.type_name, &rule.options),
+        }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Outcome for each key present, plus any missing required ones
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    keys: BTreeMap<String, KeyResult>,
+    // Why the value was rejected, when the string could not be parsed into pairs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            keys: BTreeMap::new(),
+            error: None,
+        }
+    }
+}
+
+// Outcome for a single key, shaped like a validator's own detailed result
+#[derive(Serialize, Debug, PartialEq)]
+pub struct KeyResult {
+    valid: bool,
+    value: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl KeyResult {
+    fn error(code: &'static str) -> Self {
+        KeyResult { valid: false, value: Value::Null, error: Some(code) }
+    }
+}
+
+// Error codes reported in detailed results, for the whole string and for individual keys
+const MALFORMED_PAIR: &str = "malformed_pair";
+const MISSING_KEY: &str = "missing_key";
+const UNKNOWN_KEY: &str = "unknown_key";
+const DUPLICATE_KEY: &str = "duplicate_key";
+const UNKNOWN_TYPE: &str = "unknown_type";
+
+// Splits text into its pairs; keys and unquoted values are trimmed, and empty pairs, such as
+// the one after a trailing separator, are skipped
+fn parse_pairs(text: &str, options: &ValidationOptions) -> Result<Vec<(String, String)>, &'static str> {
+    let pair_separator = options.pair_separator.0.as_str();
+    let key_value_separator = options.key_value_separator.0.as_str();
+
+    let mut pairs = Vec::new();
+    let mut rest = skip_whitespace(text, pair_separator);
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix(pair_separator) {
+            rest = skip_whitespace(after, pair_separator);
+            continue;
+        }
+
+        // The key runs up to the key-value separator, which must come before the next pair
+        let key_end = match (rest.find(key_value_separator), rest.find(pair_separator)) {
+            (Some(key_end), Some(pair_end)) if key_end < pair_end => key_end,
+            (Some(key_end), None) => key_end,
+            _ => return Err(MALFORMED_PAIR),
+        };
+        let key = rest[..key_end].trim();
+        if key.is_empty() {
+            return Err(MALFORMED_PAIR);
+        }
+
+        let after_key = skip_whitespace(&rest[key_end + key_value_separator.len()..], pair_separator);
+        let (value, remainder) = match options.quote.filter(|quote| after_key.starts_with(*quote)) {
+            Some(quote) => {
+                let (value, after) = quoted(&after_key[quote.len_utf8()..], quote).map_err(|error| error.code())?;
+                let after = skip_whitespace(after, pair_separator);
+                if !after.is_empty() && !after.starts_with(pair_separator) {
+                    return Err(SplitError::TextAfterQuote.code());
+                }
+                (value, after)
+            },
+            None => {
+                let value_end = after_key.find(pair_separator).unwrap_or(after_key.len());
+                (after_key[..value_end].trim().to_string(), &after_key[value_end..])
+            },
+        };
+
+        pairs.push((key.to_string(), value));
+        rest = remainder;
+    }
+
+    Ok(pairs)
+}
+
+fn check_value(value_type: Option<&ValueType>, value: String) -> KeyResult {
+    let Some((type_name, options)) = value_type.map(ValueType::rule) else {
+        return KeyResult { valid: true, value: Value::String(value), error: None };
+    };
+    let Some(coerce) = validator_registry::lookup(type_name) else {
+        return KeyResult::error(UNKNOWN_TYPE);
+    };
+
+    let coerced = coerce(&Value::String(value), options);
+    KeyResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None }
+}
+
+// Detailed validation: every key present is checked, and so is every required key; the
+// string is valid when all of them are
+fn detail_kv_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    let pairs = match parse_pairs(text, options) {
+        Ok(pairs) => pairs,
+        Err(code) => return ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+    };
+
+    let mut keys: BTreeMap<String, KeyResult> = BTreeMap::new();
+    for (key, value) in pairs {
+        let result = if keys.contains_key(&key) {
+            KeyResult::error(DUPLICATE_KEY)
+        } else if options.allowed_keys.as_ref().is_some_and(|allowed| !allowed.contains(&key)) {
+            KeyResult::error(UNKNOWN_KEY)
+        } else {
+            check_value(options.value_types.get(&key), value)
+        };
+        keys.insert(key, result);
+    }
+    for key in &options.required_keys {
+        keys.entry(key.clone()).or_insert_with(|| KeyResult::error(MISSING_KEY));
+    }
+
+    if !keys.values().all(|key| key.valid) {
+        return ValidationResult { keys, ..ValidationResult::from(None) };
+    }
+    let coerced: Map<String, Value> = keys.iter().map(|(key, result)| (key.clone(), result.value.clone())).collect();
+    ValidationResult { keys, ..ValidationResult::from(Some(Value::Object(coerced))) }
+}
+
+// Only strings can be valid; the fast path calls detail_kv_str directly
+fn detail_kv(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_kv_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the pairs as a JSON object of canonical values
+fn coerce_kv(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_kv(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_kv(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_kv(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_kv(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_kv(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_kv(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_kv(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_kv(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_kv(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_kv(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_kv_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn pairs(text: &str, options: &ValidationOptions) -> Vec<(String, String)> {
+        parse_pairs(text, options).unwrap()
+    }
+
+    fn pair(key: &str, value: &str) -> (String, String) {
+        (key.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn test_parse_pairs() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(pairs("a=1;b=2", &defaults), vec![pair("a", "1"), pair("b", "2")]);
+        assert_eq!(pairs(" a = 1 ; b= two words ;", &defaults), vec![pair("a", "1"), pair("b", "two words")]);
+        assert_eq!(pairs("token=abc==;x=", &defaults), vec![pair("token", "abc=="), pair("x", "")]);
+        assert_eq!(pairs(r#"note="a;b";q="say ""hi""""#, &defaults), vec![pair("note", "a;b"), pair("q", r#"say "hi""#)]);
+        assert_eq!(pairs("", &defaults), vec![]);
+        assert_eq!(pairs(";;", &defaults), vec![]);
+
+        assert_eq!(parse_pairs("a=1;b", &defaults), Err(MALFORMED_PAIR));
+        assert_eq!(parse_pairs("=1", &defaults), Err(MALFORMED_PAIR));
+        assert_eq!(parse_pairs(r#"a="1"x"#, &defaults), Err("text_after_quote"));
+        assert_eq!(parse_pairs(r#"a="1"#, &defaults), Err("unterminated_quote"));
+    }
+
+    #[test]
+    fn test_separators() {
+        let annotations = options(json!({"pair_separator": ",", "key_value_separator": ":"}));
+        assert_eq!(pairs("team: infra, tier: 1", &annotations), vec![pair("team", "infra"), pair("tier", "1")]);
+
+        let logfmt = options(json!({"pair_separator": " "}));
+        assert_eq!(pairs("level=info  msg=\"disk full\"", &logfmt), vec![pair("level", "info"), pair("msg", "disk full")]);
+
+        let unquoted = options(json!({"quote": null}));
+        assert_eq!(pairs(r#"a="1""#, &unquoted), vec![pair("a", r#""1""#)]);
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"pair_separator": ""})).is_err());
+    }
+
+    #[test]
+    fn test_keys() {
+        let connection = options(json!({
+            "allowed_keys": ["Server", "Database", "Timeout"],
+            "required_keys": ["Server"],
+        }));
+
+        assert!(validate_kv(&json!("Server=db1;Database=app"), &connection));
+        assert!(!validate_kv(&json!("Database=app"), &connection));
+        assert!(!validate_kv(&json!("Server=db1;Password=x"), &connection));
+        assert!(!validate_kv(&json!("Server=db1;Server=db2"), &ValidationOptions::default()));
+        assert!(!validate_kv(&json!(42), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_value_types() {
+        let flags = options(json!({
+            "value_types": {
+                "enabled": "boolean",
+                "rollout": {"type": "integer"},
+                "callback": {"type": "url"},
+            },
+        }));
+
+        assert_eq!(
+            coerce_kv(&json!("enabled=yes; rollout= 25 ;owner=ops"), &flags),
+            Some(json!({"enabled": true, "rollout": 25, "owner": "ops"}))
+        );
+        assert!(!validate_kv(&json!("enabled=maybe"), &flags));
+        assert!(!validate_kv(&json!("a=1"), &options(json!({"value_types": {"a": "nonsense"}}))));
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"value_types": {"a": {"type": "url", "strict": true}}})).is_err());
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a=1"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a=1", "options": {"required_keys": ["a"]}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a=1", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let rules = options(json!({
+            "allowed_keys": ["host", "port"],
+            "required_keys": ["host"],
+            "value_types": {"port": "integer"},
+        }));
+
+        let result = detail_kv(&json!("port=x;user=me;port=1"), &rules);
+        assert!(!result.valid);
+        assert_eq!(result.keys["host"], KeyResult::error(MISSING_KEY));
+        assert_eq!(result.keys["user"], KeyResult::error(UNKNOWN_KEY));
+        assert_eq!(result.keys["port"], KeyResult::error(DUPLICATE_KEY));
+
+        let result = detail_kv(&json!("host=db;port=x"), &rules);
+        assert_eq!(result.keys["port"], KeyResult { valid: false, value: Value::Null, error: None });
+        assert_eq!(result.keys["host"], KeyResult { valid: true, value: json!("db"), error: None });
+
+        assert_eq!(detail_kv(&json!("host"), &rules).error, Some(MALFORMED_PAIR));
+        assert_eq!(detail_kv(&json!("host=db;port=5432"), &rules).value, json!({"host": "db", "port": 5432}));
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["a=1; b=2", "key=value", "novalue", ""] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
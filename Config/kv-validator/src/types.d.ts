@@ -0,0 +1,51 @@
+// Types for the kv validator's exports, added to the .d.ts that wasm-bindgen generates
+
+export type ValueType = string | { type: string; options?: unknown };
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Separator between pairs; any non-empty string (default: ";") */
+    pair_separator?: string;
+    /** Separator between a key and its value (default: "=") */
+    key_value_separator?: string;
+    /** Character that quotes a value containing the pair separator; null turns quoting off (default: "\"") */
+    quote?: string | null;
+    /** Keys that may appear; any key may when unset */
+    allowed_keys?: string[];
+    /** Keys that must appear (default: []) */
+    required_keys?: string[];
+    /** Key to datatype name, or { type, options }, for the values that must have a datatype */
+    value_types?: Record<string, ValueType>;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = Record<string, unknown>;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Outcome for each key present, plus any missing required ones */
+    keys?: Record<string, { valid: boolean; value: unknown; error?: "missing_key" | "unknown_key" | "duplicate_key" | "unknown_type" | "empty_value" | "invalid"; message?: string }>;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "malformed_pair" | "unterminated_quote" | "text_after_quote" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,595 @@
+// ABOUTME: WASM component for mail server settings: an SMTP, IMAP or POP3 host, port and security mode
+// ABOUTME: Checks each field and the combinations between them, reporting an error code per field for settings forms
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Only accept settings for this protocol, which is also assumed when the settings don't name one
+    protocol: Option<Protocol>,
+    // Only accept the protocol's well-known ports
+    well_known_ports: bool,
+    // Reject settings without TLS or STARTTLS
+    require_encryption: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    Smtp,
+    Imap,
+    Pop3,
+}
+
+impl Protocol {
+    const ALL: [Protocol; 3] = [Protocol::Smtp, Protocol::Imap, Protocol::Pop3];
+
+    fn parse(text: &str) -> Option<Protocol> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "smtp" | "smtps" | "submission" => Some(Protocol::Smtp),
+            "imap" | "imaps" => Some(Protocol::Imap),
+            "pop3" | "pop3s" | "pop" => Some(Protocol::Pop3),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Protocol::Smtp => "smtp",
+            Protocol::Imap => "imap",
+            Protocol::Pop3 => "pop3",
+        }
+    }
+
+    // The port on which the connection is TLS from the first byte (SMTPS, IMAPS, POP3S)
+    fn implicit_tls_port(self) -> u16 {
+        match self {
+            Protocol::Smtp => 465,
+            Protocol::Imap => 993,
+            Protocol::Pop3 => 995,
+        }
+    }
+
+    // Ports that start in plain text and may upgrade with STARTTLS; the first is the one
+    // clients use by default (SMTP submission rather than relay)
+    fn plain_ports(self) -> &'static [u16] {
+        match self {
+            Protocol::Smtp => &[587, 25],
+            Protocol::Imap => &[143],
+            Protocol::Pop3 => &[110],
+        }
+    }
+
+    fn is_well_known(self, port: u16) -> bool {
+        port == self.implicit_tls_port() || self.plain_ports().contains(&port)
+    }
+
+    fn default_port(self, security: Security) -> u16 {
+        match security {
+            Security::Tls => self.implicit_tls_port(),
+            Security::Starttls | Security::None => self.plain_ports()[0],
+        }
+    }
+
+    // The security a well-known port implies; plain-text ports are assumed to upgrade
+    fn implied_security(self, port: u16) -> Option<Security> {
+        if port == self.implicit_tls_port() {
+            Some(Security::Tls)
+        } else if self.plain_ports().contains(&port) {
+            Some(Security::Starttls)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Security {
+    // Implicit TLS, often labelled SSL/TLS in mail clients
+    Tls,
+    Starttls,
+    None,
+}
+
+impl Security {
+    fn parse(text: &str) -> Option<Security> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "tls" | "ssl" | "ssl/tls" | "implicit" => Some(Security::Tls),
+            "starttls" | "start_tls" | "start-tls" => Some(Security::Starttls),
+            "none" | "plain" | "insecure" => Some(Security::None),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Security::Tls => "tls",
+            Security::Starttls => "starttls",
+            Security::None => "none",
+        }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Error code for each field that is wrong; a conflict between fields is reported on each of them
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    errors: BTreeMap<&'static str, &'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            errors: BTreeMap::new(),
+        }
+    }
+}
+
+// Error codes reported for individual fields in detailed results
+const MISSING_HOST: &str = "missing_host";
+const INVALID_HOST: &str = "invalid_host";
+const MISSING_PORT: &str = "missing_port";
+const INVALID_PORT: &str = "invalid_port";
+const NONSTANDARD_PORT: &str = "nonstandard_port";
+const MISSING_SECURITY: &str = "missing_security";
+const INVALID_SECURITY: &str = "invalid_security";
+const INSECURE: &str = "insecure";
+const MISSING_PROTOCOL: &str = "missing_protocol";
+const INVALID_PROTOCOL: &str = "invalid_protocol";
+const UNSUPPORTED_PROTOCOL: &str = "unsupported_protocol";
+// Cross-field errors: a port that belongs to another protocol, or one whose security differs
+const PORT_MISMATCH: &str = "port_mismatch";
+const SECURITY_MISMATCH: &str = "security_mismatch";
+
+// Forms commonly send null or an empty string for an empty field, so both count as absent
+fn field<'a>(settings: &'a Map<String, Value>, name: &str) -> Option<&'a Value> {
+    settings.get(name).filter(|value| !value.is_null() && value.as_str().is_none_or(|text| !text.trim().is_empty()))
+}
+
+// DNS names: dot-separated labels of letters, digits and inner hyphens, with a final label that
+// isn't all digits (so a mistyped IPv4 address isn't taken for a name); a trailing dot is dropped
+fn coerce_hostname(text: &str) -> Option<String> {
+    let host = text.strip_suffix('.').unwrap_or(text).to_ascii_lowercase();
+    let labels_valid = host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        });
+    let last = host.rsplit('.').next()?;
+    (labels_valid && !last.bytes().all(|b| b.is_ascii_digit())).then_some(host)
+}
+
+// A host name or an IP address; IPv6 addresses may be bracketed, as in URIs
+fn coerce_host(value: &Value) -> Result<String, &'static str> {
+    let text = value.as_str().ok_or(INVALID_HOST)?.trim();
+    let address = text.strip_prefix('[').and_then(|inner| inner.strip_suffix(']')).unwrap_or(text);
+    match address.parse::<IpAddr>() {
+        Ok(address) => Ok(address.to_string()),
+        Err(_) => coerce_hostname(text).ok_or(INVALID_HOST),
+    }
+}
+
+// A port number, or a string of digits as text inputs submit it
+fn coerce_port(value: &Value) -> Result<u16, &'static str> {
+    let port = match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => Some(s.trim()).filter(|s| s.bytes().all(|b| b.is_ascii_digit())).and_then(|s| s.parse().ok()),
+        _ => None,
+    };
+    port.and_then(|port| u16::try_from(port).ok()).filter(|port| *port != 0).ok_or(INVALID_PORT)
+}
+
+fn coerce_security(value: &Value) -> Result<Security, &'static str> {
+    value.as_str().and_then(Security::parse).ok_or(INVALID_SECURITY)
+}
+
+fn coerce_protocol(value: &Value) -> Result<Protocol, &'static str> {
+    value.as_str().and_then(Protocol::parse).ok_or(INVALID_PROTOCOL)
+}
+
+// Records a field's error code, keeping the value when there is none
+fn check<T>(errors: &mut BTreeMap<&'static str, &'static str>, name: &'static str, result: Result<T, &'static str>) -> Option<T> {
+    result.map_err(|code| errors.insert(name, code)).ok()
+}
+
+// Detailed validation: each field is checked on its own, then the missing ones are inferred from
+// the others and the combination is checked; the settings are valid when no field has an error
+fn detail_mailserver(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let Some(settings) = value.as_object() else {
+        return ValidationResult::from(None);
+    };
+
+    let mut errors = BTreeMap::new();
+    let host = check(&mut errors, "host", field(settings, "host").ok_or(MISSING_HOST).and_then(coerce_host));
+    let port = field(settings, "port").and_then(|port| check(&mut errors, "port", coerce_port(port)));
+    let security = field(settings, "security").and_then(|security| check(&mut errors, "security", coerce_security(security)));
+    let protocol = field(settings, "protocol").and_then(|protocol| check(&mut errors, "protocol", coerce_protocol(protocol)));
+    if !errors.is_empty() {
+        return ValidationResult { errors, ..ValidationResult::from(None) };
+    }
+
+    // The protocol comes from the settings, then the options, then the port
+    let protocol = match (protocol, options.protocol) {
+        (Some(protocol), Some(allowed)) if protocol != allowed => {
+            errors.insert("protocol", UNSUPPORTED_PROTOCOL);
+            return ValidationResult { errors, ..ValidationResult::from(None) };
+        },
+        (Some(protocol), _) | (None, Some(protocol)) => protocol,
+        (None, None) => match port.and_then(|port| Protocol::ALL.into_iter().find(|p| p.is_well_known(port))) {
+            Some(protocol) => protocol,
+            None => {
+                errors.insert("protocol", MISSING_PROTOCOL);
+                return ValidationResult { errors, ..ValidationResult::from(None) };
+            },
+        },
+    };
+
+    // A port that is another protocol's (IMAP settings on 587, say) is usually a field filled in
+    // from the wrong column
+    if port.is_some_and(|port| !protocol.is_well_known(port) && Protocol::ALL.iter().any(|p| p.is_well_known(port))) {
+        errors.insert("port", PORT_MISMATCH);
+        errors.insert("protocol", PORT_MISMATCH);
+        return ValidationResult { errors, ..ValidationResult::from(None) };
+    }
+
+    // A missing port defaults from the security, and missing security from a well-known port
+    let (port, security) = match (port, security) {
+        (Some(port), Some(security)) => (port, security),
+        (None, Some(security)) => (protocol.default_port(security), security),
+        (Some(port), None) => match protocol.implied_security(port) {
+            Some(security) => (port, security),
+            None => {
+                errors.insert("security", MISSING_SECURITY);
+                return ValidationResult { errors, ..ValidationResult::from(None) };
+            },
+        },
+        (None, None) => {
+            errors.insert("port", MISSING_PORT);
+            return ValidationResult { errors, ..ValidationResult::from(None) };
+        },
+    };
+
+    // Port 465 implies implicit TLS and port 587 doesn't
+    if protocol.implied_security(port).is_some_and(|implied| (implied == Security::Tls) != (security == Security::Tls)) {
+        errors.insert("port", SECURITY_MISMATCH);
+        errors.insert("security", SECURITY_MISMATCH);
+    } else if options.well_known_ports && !protocol.is_well_known(port) {
+        errors.insert("port", NONSTANDARD_PORT);
+    }
+    if options.require_encryption && security == Security::None {
+        errors.entry("security").or_insert(INSECURE);
+    }
+    if !errors.is_empty() {
+        return ValidationResult { errors, ..ValidationResult::from(None) };
+    }
+
+    ValidationResult::from(Some(json!({
+        "protocol": protocol.name(),
+        "host": host,
+        "port": port,
+        "security": security.name(),
+    })))
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the protocol, host, port and security, with missing ones inferred; other fields are dropped
+fn coerce_mailserver(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_mailserver(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_mailserver(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_mailserver(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_mailserver(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_mailserver(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_mailserver(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_mailserver(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_mailserver(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_mailserver(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_mailserver(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn errors(value: Value, options: &ValidationOptions) -> Vec<(&'static str, &'static str)> {
+        detail_mailserver(&value, options).errors.into_iter().collect()
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            coerce_mailserver(&json!({"host": " SMTP.Example.com. ", "port": "465", "security": "SSL"}), &defaults),
+            Some(json!({"protocol": "smtp", "host": "smtp.example.com", "port": 465, "security": "tls"}))
+        );
+        assert_eq!(
+            coerce_mailserver(&json!({"host": "mail.example.com", "protocol": "imap", "security": "starttls"}), &defaults),
+            Some(json!({"protocol": "imap", "host": "mail.example.com", "port": 143, "security": "starttls"}))
+        );
+        assert_eq!(
+            coerce_mailserver(&json!({"host": "[2001:DB8::1]", "port": 995, "username": "me"}), &defaults),
+            Some(json!({"protocol": "pop3", "host": "2001:db8::1", "port": 995, "security": "tls"}))
+        );
+        assert_eq!(
+            coerce_mailserver(&json!({"host": "10.0.0.5", "port": 2525, "security": "starttls", "protocol": "smtp"}), &defaults),
+            Some(json!({"protocol": "smtp", "host": "10.0.0.5", "port": 2525, "security": "starttls"}))
+        );
+        assert_eq!(coerce_mailserver(&json!("smtp.example.com:465"), &defaults), None);
+    }
+
+    #[test]
+    fn test_fields() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(errors(json!({"port": 465}), &defaults), vec![("host", MISSING_HOST)]);
+        assert_eq!(errors(json!({"host": "", "port": 465}), &defaults), vec![("host", MISSING_HOST)]);
+        assert_eq!(errors(json!({"host": "smtp..example.com", "port": 465}), &defaults), vec![("host", INVALID_HOST)]);
+        assert_eq!(errors(json!({"host": "-mail.example.com", "port": 465}), &defaults), vec![("host", INVALID_HOST)]);
+        assert_eq!(errors(json!({"host": "192.168.1.300", "port": 465}), &defaults), vec![("host", INVALID_HOST)]);
+        assert_eq!(
+            errors(json!({"host": "mail", "port": 70000, "security": "ssl-ish"}), &defaults),
+            vec![("port", INVALID_PORT), ("security", INVALID_SECURITY)]
+        );
+        assert_eq!(errors(json!({"host": "mail", "port": "0"}), &defaults), vec![("port", INVALID_PORT)]);
+        assert_eq!(errors(json!({"host": "mail", "port": 465, "protocol": "ftp"}), &defaults), vec![("protocol", INVALID_PROTOCOL)]);
+
+        // Nothing to infer the rest from
+        assert_eq!(errors(json!({"host": "mail", "security": "tls"}), &defaults), vec![("protocol", MISSING_PROTOCOL)]);
+        assert_eq!(errors(json!({"host": "mail", "protocol": "smtp"}), &defaults), vec![("port", MISSING_PORT)]);
+        assert_eq!(errors(json!({"host": "mail", "port": 2525, "protocol": "smtp"}), &defaults), vec![("security", MISSING_SECURITY)]);
+    }
+
+    #[test]
+    fn test_cross_field() {
+        let defaults = ValidationOptions::default();
+
+        // Implicit TLS ports can't be used without it, and STARTTLS ports can't be used with it
+        assert_eq!(
+            errors(json!({"host": "mail", "port": 465, "security": "starttls"}), &defaults),
+            vec![("port", SECURITY_MISMATCH), ("security", SECURITY_MISMATCH)]
+        );
+        assert_eq!(
+            errors(json!({"host": "mail", "port": 587, "security": "tls"}), &defaults),
+            vec![("port", SECURITY_MISMATCH), ("security", SECURITY_MISMATCH)]
+        );
+        assert!(validate_mailserver(&json!({"host": "mail", "port": 25, "security": "none"}), &defaults));
+
+        // Another protocol's port
+        assert_eq!(
+            errors(json!({"host": "mail", "port": 993, "protocol": "smtp"}), &defaults),
+            vec![("port", PORT_MISMATCH), ("protocol", PORT_MISMATCH)]
+        );
+    }
+
+    #[test]
+    fn test_options() {
+        let imap = options(json!({"protocol": "imap"}));
+        assert_eq!(coerce_mailserver(&json!({"host": "mail", "security": "tls"}), &imap).unwrap()["port"], 993);
+        assert_eq!(errors(json!({"host": "mail", "port": 465}), &imap), vec![("port", PORT_MISMATCH), ("protocol", PORT_MISMATCH)]);
+        assert_eq!(errors(json!({"host": "mail", "protocol": "pop3", "port": 995}), &imap), vec![("protocol", UNSUPPORTED_PROTOCOL)]);
+
+        let well_known = options(json!({"well_known_ports": true}));
+        assert!(validate_mailserver(&json!({"host": "mail", "port": 587}), &well_known));
+        assert_eq!(
+            errors(json!({"host": "mail", "port": 2525, "protocol": "smtp", "security": "starttls"}), &well_known),
+            vec![("port", NONSTANDARD_PORT)]
+        );
+
+        let encrypted = options(json!({"require_encryption": true}));
+        assert!(validate_mailserver(&json!({"host": "mail", "port": 25}), &encrypted));
+        assert_eq!(errors(json!({"host": "mail", "port": 25, "security": "none"}), &encrypted), vec![("security", INSECURE)]);
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": {}, "options": {"protocol": "ftp"}})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": {}, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let result = detail_mailserver(&json!({"host": "mail", "port": 465, "security": "none"}), &ValidationOptions::default());
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            json!({
+                "valid": false,
+                "value": null,
+                "errors": {"port": "security_mismatch", "security": "security_mismatch"},
+            })
+        );
+        assert_eq!(detail_mailserver(&json!(42), &ValidationOptions::default()), ValidationResult::from(None));
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// Types for the mailserver validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Only accept settings for this protocol, which is also assumed when the settings don't name one */
+    protocol?: "smtp" | "imap" | "pop3";
+    /** Only accept the protocol's well-known ports (25, 465 and 587; 143 and 993; 110 and 995) (default: false) */
+    well_known_ports?: boolean;
+    /** Reject settings without TLS or STARTTLS (default: false) */
+    require_encryption?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = Record<string, unknown>;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    protocol: "smtp" | "imap" | "pop3";
+    host: string;
+    port: number;
+    security: "tls" | "starttls" | "none";
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Error code for each field that is wrong; a conflict between fields is reported on each of them */
+    errors?: {
+        host?: "missing_host" | "invalid_host";
+        port?: "missing_port" | "invalid_port" | "nonstandard_port" | "port_mismatch" | "security_mismatch";
+        security?: "missing_security" | "invalid_security" | "insecure" | "security_mismatch";
+        protocol?: "missing_protocol" | "invalid_protocol" | "unsupported_protocol" | "port_mismatch";
+    };
+    /** The message for each field's error, when the input names a locale */
+    messages?: Record<string, string>;
+}
@@ -0,0 +1,534 @@
+// ABOUTME: WASM component for vCard files (RFC 6350 and RFC 2426), for contact imports in CRM apps
+// ABOUTME: Checks line folding, BEGIN/END pairs, VERSION and FN, and date and URI values, returning each card's name
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// Unfolding and parsing content lines, shared with the iCalendar validator
+#[path = "../../../contentline/contentline.rs"]
+mod contentline;
+
+use contentline::{is_date, is_date_time, is_uri, Component, ContentLine, Problem};
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Require CRLF line breaks and lines folded at 75 octets, as RFC 6350 does
+    strict_lines: bool,
+    // Require a UID on every card, for imports that match cards to existing contacts
+    require_uid: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The physical line the problem was found on, counting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    // The property at fault, when there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    property: Option<String>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+            line: None,
+            property: None,
+        }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from(problem: Problem) -> Self {
+        ValidationResult {
+            error: Some(problem.code),
+            line: Some(problem.line),
+            property: problem.property,
+            ..ValidationResult::from(None)
+        }
+    }
+}
+
+// Error codes reported in detailed results, besides those for malformed lines and unbalanced
+// BEGIN and END lines
+const INVALID_COMPONENT: &str = "invalid_component";
+const UNSUPPORTED_VERSION: &str = "unsupported_version";
+const MISSING_PROPERTY: &str = "missing_property";
+const INVALID_PROPERTY_VALUE: &str = "invalid_property_value";
+
+// vCard 3.0 (RFC 2426) and 4.0 (RFC 6350); 2.1 writes values in quoted-printable and bare
+// parameters, which aren't content lines as the later versions define them
+const VERSIONS: &[&str] = &["3.0", "4.0"];
+
+// A text value with its backslash escapes undone: \n for a line break, and \, \; \: and \\
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => unescaped.push('\n'),
+                Some(escaped) => unescaped.push(escaped),
+                None => unescaped.push('\\'),
+            },
+            (c, false) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+// Two digits from 01 up to a maximum
+fn two_digits(text: &str, max: u32) -> Option<u32> {
+    let number = (text.len() == 2 && text.bytes().all(|b| b.is_ascii_digit())).then(|| text.parse().ok())??;
+    (1..=max).contains(&number).then_some(number)
+}
+
+// A date without its year, as 4.0 writes birthdays whose year isn't known: --MMDD, --MM or
+// ---DD; February 29 is allowed, as some year has one
+fn is_yearless_date(text: &str) -> bool {
+    let days_in = |month: u32| match month {
+        2 => 29,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    match text.strip_prefix('-') {
+        Some(day) => two_digits(day, 31).is_some(),
+        None if text.len() == 4 => match (text.get(..2).and_then(|month| two_digits(month, 12)), text.get(2..)) {
+            (Some(month), Some(day)) => two_digits(day, days_in(month)).is_some(),
+            _ => false,
+        },
+        None => two_digits(text, 12).is_some(),
+    }
+}
+
+// A year alone or a year and month, as 4.0 writes dates known only that far
+fn is_reduced_date(text: &str) -> bool {
+    let (year, month) = text.split_once('-').map_or((text, None), |(year, month)| (year, Some(month)));
+    year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()) && month.is_none_or(|month| two_digits(month, 12).is_some())
+}
+
+// A date, a date and time, or a date reduced to fewer parts, as BDAY and ANNIVERSARY take them
+fn is_date_and_or_time(text: &str) -> bool {
+    match text.strip_prefix("--") {
+        Some(yearless) => is_yearless_date(yearless),
+        None if is_reduced_date(text) => true,
+        None if text.contains('T') => is_date_time(text),
+        None => is_date(text),
+    }
+}
+
+// Whether a property's value is of its type, for the properties whose type is a date or a URI;
+// other values are text, which anything is
+fn check_value(property: &ContentLine, version: &str) -> bool {
+    // Some exporters escape the colons in URIs as if they were text
+    let value = unescape(&property.value);
+    match property.name.as_str() {
+        "BDAY" | "ANNIVERSARY" if property.value_type_is("text") => true,
+        "BDAY" | "ANNIVERSARY" => is_date_and_or_time(&value),
+        "REV" => is_date_time(&value) || is_date(&value),
+        "URL" | "SOURCE" | "FBURL" | "CALURI" | "CALADRURI" => is_uri(&value),
+        // Media are URIs in 4.0, and inline base64 data in 3.0 unless VALUE=uri
+        "PHOTO" | "LOGO" | "SOUND" if version == "4.0" || property.value_type_is("uri") => is_uri(&value),
+        _ => true,
+    }
+}
+
+// Checks one card: its version, the properties that version requires, and the values whose
+// type can be checked; returns its version, formatted name and UID
+fn check_card(card: &Component, options: &ValidationOptions) -> Result<Value, Problem> {
+    if card.name != "VCARD" {
+        return Err(Problem::at(INVALID_COMPONENT, card.line));
+    }
+    // Cards hold no components; 3.0's AGENT embeds a card as an escaped text value instead
+    if let Some(nested) = card.children.first() {
+        return Err(Problem::at(INVALID_COMPONENT, nested.line));
+    }
+
+    let missing = |name: &str| Problem { code: MISSING_PROPERTY, line: card.line, property: Some(name.to_string()) };
+    let version = match card.property("VERSION") {
+        Some(version) if VERSIONS.contains(&version.value.trim()) => version.value.trim(),
+        Some(version) => return Err(Problem::with(UNSUPPORTED_VERSION, version)),
+        None => return Err(missing("VERSION")),
+    };
+    // FN is required by both versions, and N by 3.0 only
+    let required = ["FN", "N", "UID"]
+        .into_iter()
+        .filter(|&name| match name {
+            "N" => version == "3.0",
+            "UID" => options.require_uid,
+            _ => true,
+        });
+    for name in required {
+        if card.property(name).is_none() {
+            return Err(missing(name));
+        }
+    }
+    if let Some(property) = card.properties.iter().find(|property| !check_value(property, version)) {
+        return Err(Problem::with(INVALID_PROPERTY_VALUE, property));
+    }
+
+    let text = |name: &str| card.property(name).map(|property| unescape(&property.value));
+    Ok(json!({ "version": version, "fn": text("FN"), "uid": text("UID") }))
+}
+
+fn detail_vcard_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no cards, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    let cards = match contentline::parse(text, options.strict_lines).and_then(contentline::components) {
+        Ok(cards) => cards,
+        Err(problem) => return ValidationResult::from(problem),
+    };
+
+    match cards.iter().map(|card| check_card(card, options)).collect::<Result<Vec<Value>, Problem>>() {
+        Ok(cards) => ValidationResult::from(Some(json!({ "cards": cards }))),
+        Err(problem) => ValidationResult::from(problem),
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_vcard_str directly
+fn detail_vcard(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_vcard_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns each card's version, formatted name and UID, in the order given
+fn coerce_vcard(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_vcard(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_vcard(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_vcard(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_vcard(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_vcard(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_vcard(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_vcard(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_vcard(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_vcard(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_vcard(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_vcard_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARD: &str = "BEGIN:VCARD\r\nVERSION:4.0\r\nUID:urn:uuid:4fbe8971-0bc3-424c-9c26-36c3e1eff6b1\r\nFN:Jane Doe\r\n\
+        EMAIL;TYPE=work:jane@example.com\r\nBDAY:19850412\r\nURL:https://example.com/jane\r\nEND:VCARD\r\n";
+
+    fn detail(text: &str) -> ValidationResult {
+        detail_vcard_str(text, &ValidationOptions::default())
+    }
+
+    #[test]
+    fn test_valid_cards() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            coerce_vcard(&json!(CARD), &defaults),
+            Some(json!({"cards": [{"version": "4.0", "fn": "Jane Doe", "uid": "urn:uuid:4fbe8971-0bc3-424c-9c26-36c3e1eff6b1"}]}))
+        );
+        // A 3.0 card as exporters write it: LF line breaks, a folded line, groups, escaped text
+        // and colons, and a base64 photo
+        let exported = "BEGIN:VCARD\nVERSION:3.0\nN:Doe;John;;;\nFN:Doe\\, John\nitem1.URL:http\\://example.com/john\n\
+            item1.X-ABLabel:_$!<HomePage>!$_\nPHOTO;ENCODING=b;TYPE=JPEG:/9j/4AAQSkZJRgABAQ\n  AAAQABAAD\nBDAY:1985-04-12\n\
+            REV:2024-03-14T15:30:00Z\nEND:VCARD\n";
+        assert_eq!(
+            coerce_vcard(&json!(exported), &defaults),
+            Some(json!({"cards": [{"version": "3.0", "fn": "Doe, John", "uid": null}]}))
+        );
+        // Many cards in one file
+        let both = format!("{CARD}{exported}");
+        assert_eq!(coerce_vcard(&json!(both), &defaults).unwrap()["cards"].as_array().unwrap().len(), 2);
+
+        // Birthdays without a year, and as text
+        for bday in ["--0412", "--0229", "---12", "--04", "19850412T0930", "1985"] {
+            let card = CARD.replace("BDAY:19850412", &format!("BDAY:{bday}"));
+            assert!(validate_vcard(&json!(card), &defaults), "{bday}");
+        }
+        assert!(validate_vcard(&json!(CARD.replace("BDAY:19850412", "BDAY;VALUE=text:circa 1800")), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_cards() {
+        assert_eq!(detail("").error, None);
+        assert_eq!(detail("Jane Doe, jane@example.com").error, Some(contentline::MALFORMED_LINE));
+        assert_eq!(detail(&CARD.replace("END:VCARD\r\n", "")).error, Some(contentline::UNBALANCED_COMPONENT));
+
+        let result = detail(&CARD.replace("VERSION:4.0", "VERSION:2.1"));
+        assert_eq!((result.error, result.line, result.property.as_deref()), (Some(UNSUPPORTED_VERSION), Some(2), Some("VERSION")));
+        let result = detail(&CARD.replace("VERSION:4.0\r\n", ""));
+        assert_eq!((result.error, result.line, result.property.as_deref()), (Some(MISSING_PROPERTY), Some(1), Some("VERSION")));
+        let result = detail(&CARD.replace("FN:Jane Doe\r\n", ""));
+        assert_eq!((result.error, result.property.as_deref()), (Some(MISSING_PROPERTY), Some("FN")));
+        // 3.0 also requires N
+        let result = detail(&CARD.replace("VERSION:4.0", "VERSION:3.0"));
+        assert_eq!((result.error, result.property.as_deref()), (Some(MISSING_PROPERTY), Some("N")));
+
+        for (from, to) in [
+            ("BDAY:19850412", "BDAY:19851312"),
+            ("BDAY:19850412", "BDAY:--0230"),
+            ("BDAY:19850412", "BDAY:12 April"),
+            ("URL:https://example.com/jane", "URL:example.com/jane"),
+            ("URL:https://example.com/jane", "PHOTO:not a uri"),
+        ] {
+            let result = detail(&CARD.replace(from, to));
+            assert_eq!(result.error, Some(INVALID_PROPERTY_VALUE), "{to}");
+            assert_eq!(result.property.as_deref(), to.split(':').next(), "{to}");
+        }
+
+        // Calendars aren't cards, and cards hold no components
+        let calendar = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nEND:VCALENDAR\r\n";
+        assert_eq!(detail(calendar).error, Some(INVALID_COMPONENT));
+        let nested = CARD.replace("END:VCARD", "BEGIN:VCARD\r\nEND:VCARD\r\nEND:VCARD");
+        assert_eq!((detail(&nested).error, detail(&nested).line), (Some(INVALID_COMPONENT), Some(8)));
+        assert!(!validate_vcard(&json!(42), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_options() {
+        let strict: ValidationOptions = serde_json::from_value(json!({"strict_lines": true})).unwrap();
+        assert!(validate_vcard(&json!(CARD), &strict));
+        assert_eq!(detail_vcard_str(&CARD.replace("\r\n", "\n"), &strict).error, Some(contentline::INVALID_LINE_BREAK));
+        let long = CARD.replace("FN:Jane Doe", &format!("FN:Jane {}", "Doe".repeat(30)));
+        assert_eq!(detail_vcard_str(&long, &strict).line, Some(4));
+        assert_eq!(detail_vcard_str(&long, &strict).error, Some(contentline::LINE_TOO_LONG));
+
+        let uid: ValidationOptions = serde_json::from_value(json!({"require_uid": true})).unwrap();
+        assert!(validate_vcard(&json!(CARD), &uid));
+        let result = detail_vcard_str(&CARD.replace("UID:urn:uuid:4fbe8971-0bc3-424c-9c26-36c3e1eff6b1\r\n", ""), &uid);
+        assert_eq!((result.error, result.property.as_deref()), (Some(MISSING_PROPERTY), Some("UID")));
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": CARD, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        assert_eq!(
+            messages::localize(&detail(&CARD.replace("BDAY:19850412", "BDAY:tomorrow")), &json!(CARD), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "invalid_property_value",
+                "line": 6,
+                "property": "BDAY",
+                "message": messages::message("invalid_property_value", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail("  "), &json!("  "), Some("en"))["error"], "empty_value");
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [CARD, ""], "options": {"require_uid": true}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [CARD, "", "BEGIN:VCARD\r\nEND:VCARD\r\n", "not a card"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
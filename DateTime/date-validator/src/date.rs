@@ -0,0 +1,328 @@
+// ABOUTME: Calendar, week and ordinal date parsing, shared with the datetime validator
+// ABOUTME: Every accepted form resolves to a proleptic Gregorian year, month and day
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+// Always the RFC 3339 full-date form, whichever notation was parsed; years outside
+// 0000-9999 use the ISO 8601 expanded form with a sign
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.year {
+            year if year < 0 => write!(f, "-{:04}", -year)?,
+            year if year > 9999 => write!(f, "+{}", year)?,
+            year => write!(f, "{:04}", year)?,
+        }
+        write!(f, "-{:02}-{:02}", self.month, self.day)
+    }
+}
+
+// How to treat dates before the Gregorian calendar took effect on 1582-10-15
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PreGregorian {
+    // Extend Gregorian rules backwards, as ISO 8601 does
+    #[default]
+    Proleptic,
+    // Use Julian leap years before the switchover, and reject the ten days it skipped
+    Julian,
+    // Reject every date before the switchover
+    Reject,
+}
+
+// The first Gregorian date, and the first of the ten Julian dates it replaced
+const GREGORIAN_START: (i32, u32, u32) = (1582, 10, 15);
+const SKIPPED_START: (i32, u32, u32) = (1582, 10, 5);
+
+// Grammar and calendar-edge choices, set from each validator's options
+#[derive(Debug, Clone, Copy)]
+pub struct DateRules {
+    pub iso8601: bool,
+    pub year_zero: bool,
+    pub negative_years: bool,
+    pub pre_gregorian: PreGregorian,
+}
+
+impl Default for DateRules {
+    fn default() -> Self {
+        DateRules {
+            iso8601: false,
+            year_zero: true,
+            negative_years: false,
+            pre_gregorian: PreGregorian::Proleptic,
+        }
+    }
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year.rem_euclid(4) == 0 && year.rem_euclid(100) != 0) || year.rem_euclid(400) == 0
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+fn days_in_year(year: i32) -> u32 {
+    if is_leap_year(year) { 366 } else { 365 }
+}
+
+// Days since 1970-01-01 (Howard Hinnant's days_from_civil)
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = i64::from(if month <= 2 { year - 1 } else { year });
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let m = i64::from(month);
+    let day_of_year = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+// ISO weekday: Monday is 1, Sunday is 7
+pub fn weekday(year: i32, month: u32, day: u32) -> u32 {
+    // 1970-01-01 was a Thursday
+    (days_from_civil(year, month, day) + 3).rem_euclid(7) as u32 + 1
+}
+
+// Exactly `text.len()` ASCII digits, as a number
+fn digits(text: &str) -> Option<u32> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+fn calendar_date(year: i32, month: u32, day: u32, rules: &DateRules) -> Option<Date> {
+    if rules.pre_gregorian == PreGregorian::Julian && (year, month, day) < GREGORIAN_START {
+        // Julian leap years are every fourth year, without the century exception
+        let length = match month {
+            2 if year.rem_euclid(4) == 0 => 29,
+            month => days_in_month(year, month),
+        };
+        if !(1..=12).contains(&month) || day == 0 || day > length || (year, month, day) >= SKIPPED_START {
+            return None;
+        }
+        return Some(Date { year, month, day });
+    }
+
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    Some(Date { year, month, day })
+}
+
+fn ordinal_date(year: i32, ordinal: u32) -> Option<Date> {
+    if ordinal == 0 || ordinal > days_in_year(year) {
+        return None;
+    }
+
+    let mut remaining = ordinal;
+    for month in 1..=12 {
+        let length = days_in_month(year, month);
+        if remaining <= length {
+            return Some(Date { year, month, day: remaining });
+        }
+        remaining -= length;
+    }
+    None
+}
+
+// Week 1 is the week containing the year's first Thursday, so some years have 53 weeks
+pub fn weeks_in_year(year: i32) -> u32 {
+    match weekday(year, 1, 1) {
+        4 => 53,
+        3 if is_leap_year(year) => 53,
+        _ => 52,
+    }
+}
+
+pub fn week_date(year: i32, week: u32, day: u32) -> Option<Date> {
+    if week == 0 || week > weeks_in_year(year) || !(1..=7).contains(&day) {
+        return None;
+    }
+
+    // Ordinal day counted from January 4th, which always falls in week 1
+    let ordinal = (week * 7 + day) as i32 - (weekday(year, 1, 4) as i32 + 3);
+    if ordinal < 1 {
+        ordinal_date(year - 1, (ordinal + days_in_year(year - 1) as i32) as u32)
+    } else if ordinal as u32 > days_in_year(year) {
+        ordinal_date(year + 1, ordinal as u32 - days_in_year(year))
+    } else {
+        ordinal_date(year, ordinal as u32)
+    }
+}
+
+// The year: four digits, or with `negative_years` an ISO 8601 expanded year of
+// four to six digits with a sign (extended format only)
+fn split_year<'a>(text: &'a str, rules: &DateRules) -> Option<(i32, &'a str)> {
+    let negative = match text.as_bytes().first()? {
+        b'+' | b'-' if rules.negative_years => text.starts_with('-'),
+        _ => return Some((digits(text.get(..4)?)? as i32, &text[4..])),
+    };
+
+    let end = text[1..].find('-')? + 1;
+    if !(5..=7).contains(&end) {
+        return None;
+    }
+    let year = digits(&text[1..end])? as i32;
+    Some((if negative { -year } else { year }, &text[end..]))
+}
+
+// Parses an RFC 3339 full-date (YYYY-MM-DD). With `iso8601`, also accepts the basic
+// format (YYYYMMDD), week dates (YYYY-Www-D, YYYYWwwD) and ordinal dates (YYYY-DDD, YYYYDDD)
+pub fn parse_date(text: &str, rules: &DateRules) -> Option<Date> {
+    let (year, rest) = split_year(text, rules)?;
+    let iso8601 = rules.iso8601;
+
+    // Week and ordinal dates are defined on the Gregorian calendar, so they are
+    // converted as such and checked against the switchover afterwards
+    let mut calendar_form = false;
+    let date = if let Some(rest) = rest.strip_prefix('-') {
+        let parts: Vec<&str> = rest.split('-').collect();
+        match parts.as_slice() {
+            [month, day] if month.len() == 2 && day.len() == 2 => {
+                calendar_form = true;
+                calendar_date(year, digits(month)?, digits(day)?, rules)
+            },
+            [ordinal] if iso8601 && ordinal.len() == 3 => ordinal_date(year, digits(ordinal)?),
+            [week, day] if iso8601 && week.len() == 3 && day.len() == 1 => {
+                week_date(year, digits(week.strip_prefix('W')?)?, digits(day)?)
+            },
+            _ => None,
+        }
+    } else if !iso8601 || year < 0 || text.starts_with('+') {
+        None
+    } else if let Some(week) = rest.strip_prefix('W') {
+        if week.len() != 3 {
+            return None;
+        }
+        week_date(year, digits(week.get(..2)?)?, digits(week.get(2..)?)?)
+    } else {
+        match rest.len() {
+            4 => {
+                calendar_form = true;
+                calendar_date(year, digits(rest.get(..2)?)?, digits(rest.get(2..)?)?, rules)
+            },
+            3 => ordinal_date(year, digits(rest)?),
+            _ => None,
+        }
+    }?;
+
+    let before_gregorian = (date.year, date.month, date.day) < GREGORIAN_START;
+    let rejected_era = match rules.pre_gregorian {
+        PreGregorian::Proleptic => false,
+        PreGregorian::Julian => before_gregorian && !calendar_form,
+        PreGregorian::Reject => before_gregorian,
+    };
+
+    // Week dates near a year boundary can spill outside the four-digit range
+    let in_range = if rules.negative_years { (-999_999..=999_999).contains(&date.year) } else { (0..=9999).contains(&date.year) };
+
+    (in_range && !rejected_era && (rules.year_zero || date.year != 0)).then_some(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str, iso8601: bool) -> Option<String> {
+        parse_date(text, &DateRules { iso8601, ..DateRules::default() }).map(|d| d.to_string())
+    }
+
+    fn date_with(text: &str, rules: DateRules) -> Option<String> {
+        parse_date(text, &rules).map(|d| d.to_string())
+    }
+
+    #[test]
+    fn test_calendar_dates() {
+        assert_eq!(date("2024-03-14", false), Some("2024-03-14".to_string()));
+        assert_eq!(date("2024-02-29", false), Some("2024-02-29".to_string()));
+        assert_eq!(date("0000-01-01", false), Some("0000-01-01".to_string()));
+
+        assert_eq!(date("2023-02-29", false), None);
+        assert_eq!(date("1900-02-29", false), None);
+        assert_eq!(date("2024-3-14", false), None);
+        assert_eq!(date("20240314", false), None);
+        assert_eq!(date("+024-03-14", false), None);
+    }
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(weekday(1970, 1, 1), 4);
+        assert_eq!(weekday(2024, 3, 14), 4);
+        assert_eq!(weekday(2000, 1, 1), 6);
+        assert_eq!(weekday(1600, 3, 1), 3);
+    }
+
+    #[test]
+    fn test_iso8601_forms() {
+        assert_eq!(date("20240314", true), Some("2024-03-14".to_string()));
+        assert_eq!(date("2024-074", true), Some("2024-03-14".to_string()));
+        assert_eq!(date("2024074", true), Some("2024-03-14".to_string()));
+        assert_eq!(date("2024-366", true), Some("2024-12-31".to_string()));
+        assert_eq!(date("2024-W11-4", true), Some("2024-03-14".to_string()));
+        assert_eq!(date("2024W114", true), Some("2024-03-14".to_string()));
+
+        // Week-numbering years that straddle calendar years
+        assert_eq!(date("2020-W01-1", true), Some("2019-12-30".to_string()));
+        assert_eq!(date("2020-W53-7", true), Some("2021-01-03".to_string()));
+
+        assert_eq!(date("2023-366", true), None);
+        assert_eq!(date("2024-W54-1", true), None);
+        assert_eq!(date("2021-W53-1", true), None);
+        assert_eq!(date("2024-W11-8", true), None);
+        assert_eq!(date("2024-W11", true), None);
+
+        // 9999-12-31 is a Friday, so the last week of 9999 ends in year 10000
+        assert_eq!(date("9999-W52-5", true), Some("9999-12-31".to_string()));
+        assert_eq!(date("9999-W52-7", true), None);
+    }
+
+    #[test]
+    fn test_year_edges() {
+        let no_zero = DateRules { year_zero: false, ..DateRules::default() };
+        let negative = DateRules { negative_years: true, ..DateRules::default() };
+
+        assert_eq!(date_with("0000-06-01", no_zero), None);
+        assert_eq!(date_with("0001-06-01", no_zero), Some("0001-06-01".to_string()));
+
+        assert_eq!(date_with("-0044-03-15", negative), Some("-0044-03-15".to_string()));
+        assert_eq!(date_with("+12024-01-01", negative), Some("+12024-01-01".to_string()));
+        assert_eq!(date_with("+2024-01-01", negative), Some("2024-01-01".to_string()));
+        assert_eq!(date_with("-0004-02-29", negative), Some("-0004-02-29".to_string())); // 5 BC is a leap year
+        assert_eq!(date_with("-0001-02-29", negative), None);
+        assert_eq!(date_with("-44-03-15", negative), None);
+        assert_eq!(date_with("-1234567-01-01", negative), None);
+        assert_eq!(date_with("-0044-03-15", DateRules::default()), None);
+    }
+
+    #[test]
+    fn test_pre_gregorian() {
+        let julian = DateRules { pre_gregorian: PreGregorian::Julian, iso8601: true, ..DateRules::default() };
+        let reject = DateRules { pre_gregorian: PreGregorian::Reject, ..DateRules::default() };
+
+        // 1500 is a Julian leap year but not a Gregorian one
+        assert_eq!(date("1500-02-29", false), None);
+        assert_eq!(date_with("1500-02-29", julian), Some("1500-02-29".to_string()));
+        assert_eq!(date_with("1582-10-04", julian), Some("1582-10-04".to_string()));
+        assert_eq!(date_with("1582-10-10", julian), None); // Skipped by the switchover
+        assert_eq!(date_with("1582-10-15", julian), Some("1582-10-15".to_string()));
+        assert_eq!(date_with("1700-02-29", julian), None);
+        assert_eq!(date_with("1500-060", julian), None);
+
+        assert_eq!(date_with("1582-10-14", reject), None);
+        assert_eq!(date_with("1582-10-15", reject), Some("1582-10-15".to_string()));
+    }
+}
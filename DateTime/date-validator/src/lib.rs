@@ -0,0 +1,404 @@
+// ABOUTME: WASM component for date validation
+// ABOUTME: Provides validate and coerce functions for RFC 3339 full-dates and the broader ISO 8601 date forms
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod date;
+mod relative;
+
+use date::{parse_date, DateRules, PreGregorian};
+use relative::{is_relative_phrase, RELATIVE_DATE};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Also accept ISO 8601 basic format, week dates and ordinal dates
+    iso8601: bool,
+    // Report relative phrases such as "tomorrow" with the relative_date error code
+    detect_relative: bool,
+    // Reject year 0000 (1 BC), which ISO 8601 allows but many systems do not
+    reject_year_zero: bool,
+    // Accept ISO 8601 expanded years with a sign, such as -0044-03-15
+    allow_negative_years: bool,
+    // Dates before 1582-10-15: "proleptic" (default), "julian" or "reject"
+    pre_gregorian: PreGregorian,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when a specific reason was detected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+impl ValidationOptions {
+    fn date_rules(&self) -> DateRules {
+        DateRules {
+            iso8601: self.iso8601,
+            year_zero: !self.reject_year_zero,
+            negative_years: self.allow_negative_years,
+            pre_gregorian: self.pre_gregorian,
+        }
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the date as an RFC 3339 full-date (YYYY-MM-DD), whichever form was given
+fn coerce_date_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let date = parse_date(text, &options.date_rules())?;
+    Some(Value::String(date.to_string()))
+}
+
+// Only strings can be valid; the fast path calls coerce_date_str directly
+fn coerce_date(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_date_str(value.as_str()?, options)
+}
+
+// Detailed validation; relative phrases get their own error code when detect_relative is set
+fn detail_date(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let mut result = ValidationResult::from(coerce_date(value, options));
+    if !result.valid && options.detect_relative && value.as_str().is_some_and(is_relative_phrase) {
+        result.error = Some(RELATIVE_DATE);
+    }
+    result
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_date(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_date(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_date(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_date(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_date(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_date(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_date(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_date(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_date(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_date_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_dates() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_date(&json!("2024-03-14"), &defaults));
+        assert!(validate_date(&json!("2024-12-31"), &defaults));
+        assert!(validate_date(&json!("2024-02-29"), &defaults));
+        assert!(validate_date(&json!("2000-02-29"), &defaults));
+        assert!(validate_date(&json!("0000-01-01"), &defaults));
+        assert!(validate_date(&json!("9999-12-31"), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_dates() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_date(&json!("2024-13-01"), &defaults));
+        assert!(!validate_date(&json!("2024-00-01"), &defaults));
+        assert!(!validate_date(&json!("2024-03-32"), &defaults));
+        assert!(!validate_date(&json!("2024-03-00"), &defaults));
+        assert!(!validate_date(&json!("2024-04-31"), &defaults));
+        assert!(!validate_date(&json!("2023-02-29"), &defaults));
+        assert!(!validate_date(&json!("1900-02-29"), &defaults));
+        assert!(!validate_date(&json!("24-03-14"), &defaults));
+        assert!(!validate_date(&json!("2024-3-14"), &defaults));
+        assert!(!validate_date(&json!("2024-03-4"), &defaults));
+        assert!(!validate_date(&json!("2024/03/14"), &defaults));
+        assert!(!validate_date(&json!("03/14/2024"), &defaults));
+        assert!(!validate_date(&json!("2024-03-14T00:00:00Z"), &defaults));
+        assert!(!validate_date(&json!(" 2024-03-14"), &defaults));
+        assert!(!validate_date(&json!(""), &defaults));
+        assert!(!validate_date(&json!(20240314), &defaults));
+    }
+
+    #[test]
+    fn test_iso8601() {
+        let defaults = ValidationOptions::default();
+        let iso8601: ValidationOptions = serde_json::from_value(json!({"iso8601": true})).unwrap();
+
+        for text in ["20240314", "2024-W11-4", "2024W114", "2024-074", "2024074"] {
+            assert!(!validate_date(&json!(text), &defaults), "{}", text);
+            assert!(validate_date(&json!(text), &iso8601), "{}", text);
+        }
+
+        assert!(validate_date(&json!("2024-03-14"), &iso8601));
+        assert!(!validate_date(&json!("2023-366"), &iso8601));
+        assert!(!validate_date(&json!("2024-W11"), &iso8601));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let iso8601: ValidationOptions = serde_json::from_value(json!({"iso8601": true})).unwrap();
+
+        assert_eq!(coerce_date(&json!("2024-03-14"), &ValidationOptions::default()), Some(json!("2024-03-14")));
+        assert_eq!(coerce_date(&json!("2024-W11-4"), &iso8601), Some(json!("2024-03-14")));
+        assert_eq!(coerce_date(&json!("2024-366"), &iso8601), Some(json!("2024-12-31")));
+        assert_eq!(coerce_date(&json!("2024-02-30"), &iso8601), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "2024-03-14"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "2024-03-14", "options": {"iso8601": true}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "2024-03-14", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_date(&json!("2024-02-29"), &defaults)),
+            ValidationResult { valid: true, value: json!("2024-02-29"), error: None }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_date(&json!("2023-02-29"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null, error: None }
+        );
+    }
+
+
+    #[test]
+    fn test_relative_phrases() {
+        let defaults = ValidationOptions::default();
+        let detect: ValidationOptions = serde_json::from_value(json!({"detect_relative": true})).unwrap();
+
+        // Relative phrases are never valid; detection only changes how they are reported
+        assert!(!validate_date(&json!("tomorrow"), &detect));
+        assert_eq!(detail_date(&json!("tomorrow"), &defaults).error, None);
+        assert_eq!(
+            detail_date(&json!("in 3 days"), &detect),
+            ValidationResult { valid: false, value: Value::Null, error: Some("relative_date") }
+        );
+        assert_eq!(detail_date(&json!("next week"), &detect).error, Some(RELATIVE_DATE));
+        assert_eq!(detail_date(&json!("2024-02-30"), &detect).error, None);
+        assert_eq!(detail_date(&json!("2024-02-29"), &detect).error, None);
+    }
+
+
+    #[test]
+    fn test_calendar_edges() {
+        let defaults = ValidationOptions::default();
+        let options = |value: Value| -> ValidationOptions { serde_json::from_value(value).unwrap() };
+
+        assert!(validate_date(&json!("0000-01-01"), &defaults));
+        assert!(!validate_date(&json!("0000-01-01"), &options(json!({"reject_year_zero": true}))));
+
+        let negative = options(json!({"allow_negative_years": true}));
+        assert!(!validate_date(&json!("-0044-03-15"), &defaults));
+        assert_eq!(coerce_date(&json!("-0044-03-15"), &negative), Some(json!("-0044-03-15")));
+
+        assert!(!validate_date(&json!("1500-02-29"), &defaults));
+        assert!(validate_date(&json!("1500-02-29"), &options(json!({"pre_gregorian": "julian"}))));
+        assert!(!validate_date(&json!("1582-10-10"), &options(json!({"pre_gregorian": "julian"}))));
+        assert!(!validate_date(&json!("1066-10-14"), &options(json!({"pre_gregorian": "reject"}))));
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"pre_gregorian": "gregorian"})).is_err());
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["2024-02-29", "2023-02-29", "not a date"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// ABOUTME: Recognizes relative and natural-language date phrases such as "tomorrow" or "in 3 days"
+// ABOUTME: Lets the date and datetime validators report them separately from other invalid input
+
+// Error code reported in detailed results for relative phrases
+pub const RELATIVE_DATE: &str = "relative_date";
+
+const STANDALONE: &[&str] = &[
+    "today", "tomorrow", "yesterday", "now", "tonight", "tmrw", "tmr",
+    "day after tomorrow", "the day after tomorrow", "day before yesterday", "the day before yesterday",
+    "this morning", "this afternoon", "this evening", "right now",
+];
+
+const UNITS: &[&str] = &[
+    "second", "minute", "hour", "day", "week", "fortnight", "month", "quarter", "year", "decade",
+    "sec", "min", "hr", "wk", "mo", "yr",
+];
+
+const WEEKDAYS: &[&str] = &["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
+const MONTHS: &[&str] = &[
+    "january", "february", "march", "april", "may", "june", "july", "august", "september",
+    "october", "november", "december",
+];
+
+const QUANTITIES: &[&str] = &[
+    "a", "an", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "few", "a few", "couple", "a couple", "a couple of", "several",
+];
+
+fn is_unit(word: &str) -> bool {
+    UNITS.iter().any(|unit| word == *unit || word.strip_suffix('s') == Some(unit))
+}
+
+fn is_quantity(words: &str) -> bool {
+    (!words.is_empty() && words.bytes().all(|b| b.is_ascii_digit())) || QUANTITIES.contains(&words)
+}
+
+// "<quantity> <unit>", e.g. "3 days", "a couple of weeks"
+fn is_duration(words: &[&str]) -> bool {
+    match words.split_last() {
+        Some((unit, quantity)) => is_unit(unit) && is_quantity(&quantity.join(" ")),
+        None => false,
+    }
+}
+
+// True for phrases that describe a date relative to the present rather than naming one
+pub fn is_relative_phrase(text: &str) -> bool {
+    let lower = text.trim().to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    let phrase = words.join(" ");
+
+    if STANDALONE.contains(&phrase.as_str()) || WEEKDAYS.contains(&phrase.as_str()) {
+        return true;
+    }
+
+    match words.as_slice() {
+        // "next week", "last friday", "this month", "coming weekend"
+        [modifier, period] if ["next", "last", "this", "previous", "coming", "past"].contains(modifier) => {
+            is_unit(period) || *period == "weekend" || WEEKDAYS.contains(period) || MONTHS.contains(period)
+        },
+        // "in 3 days", "within a week"
+        ["in" | "within", rest @ ..] => is_duration(rest),
+        // "3 days ago", "2 weeks from now", "a month from today"
+        [rest @ .., "ago"] => is_duration(rest),
+        [rest @ .., "from", "now" | "today"] => is_duration(rest),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_phrases() {
+        for phrase in [
+            "tomorrow", "Today", " yesterday ", "now", "the day after tomorrow", "next week",
+            "last Friday", "this month", "coming weekend", "in 3 days", "in a week",
+            "within two months", "3 days ago", "a couple of weeks ago", "2 hours from now",
+            "in 10 yrs", "monday",
+        ] {
+            assert!(is_relative_phrase(phrase), "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn test_absolute_text() {
+        for text in [
+            "2024-03-14", "", "not a date", "next", "in", "ago", "3 days", "in the morning",
+            "March 14", "next 2024", "days ago", "in -3 days",
+        ] {
+            assert!(!is_relative_phrase(text), "{}", text);
+        }
+    }
+}
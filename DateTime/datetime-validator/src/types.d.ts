@@ -0,0 +1,49 @@
+// Types for the datetime validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Whether a UTC offset is required, optional or forbidden (default: "required") */
+    timezone?: "required" | "optional" | "forbidden";
+    /** Also accept the broader ISO8601 grammar: basic format, week and ordinal dates, reduced precision, comma fractions and ±hhmm/±hh offsets (default: false) */
+    iso8601?: boolean;
+    /** Report relative phrases such as "tomorrow" or "in 3 days" with the relative_date error code in detailed results (default: false) */
+    detect_relative?: boolean;
+    /** Reject second 60, which is otherwise accepted only where a leap second can occur (default: false) */
+    reject_leap_seconds?: boolean;
+    /** Reject year 0000 (1 BC), which ISO8601 allows but many systems do not (default: false) */
+    reject_year_zero?: boolean;
+    /** Accept ISO8601 expanded years with a sign, such as -0044-03-15 (default: false) */
+    allow_negative_years?: boolean;
+    /** Dates before 1582-10-15: proleptic Gregorian rules, Julian leap years, or rejected (default: "proleptic") */
+    pre_gregorian?: "proleptic" | "julian" | "reject";
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "relative_date" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
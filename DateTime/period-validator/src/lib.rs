@@ -0,0 +1,496 @@
+// ABOUTME: WASM component for reporting period validation
+// ABOUTME: Provides validate and coerce functions for year-month, quarter, ISO week and fiscal quarter identifiers
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Calendar arithmetic is shared with the date validator; only part of it is needed here
+#[path = "../../date-validator/src/date.rs"]
+#[allow(dead_code)]
+mod date;
+
+use date::{days_in_month, week_date, weeks_in_year, Date};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Only accept these granularities: "week", "month" and "quarter"
+    granularities: Option<Vec<Granularity>>,
+    // First month (1-12) of the fiscal year; enables FY2025-Q1 style identifiers
+    fiscal_year_start: Option<u32>,
+    // Whether FY2025 is the fiscal year that "ends" (default) or "starts" in calendar 2025
+    fiscal_year_named_by: FiscalNaming,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Week,
+    Month,
+    Quarter,
+}
+
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FiscalNaming {
+    #[default]
+    End,
+    Start,
+}
+
+struct Period {
+    year: i32,
+    granularity: Granularity,
+    // Week, month or quarter number within the year
+    index: u32,
+    // A quarter of the fiscal rather than the calendar year
+    fiscal: bool,
+}
+
+impl Period {
+    fn identifier(&self) -> String {
+        if self.fiscal {
+            return format!("FY{:04}-Q{}", self.year, self.index);
+        }
+        match self.granularity {
+            Granularity::Week => format!("{:04}-W{:02}", self.year, self.index),
+            Granularity::Month => format!("{:04}-{:02}", self.year, self.index),
+            Granularity::Quarter => format!("{:04}-Q{}", self.year, self.index),
+        }
+    }
+
+    // First and last calendar days of the period
+    fn range(&self, options: &ValidationOptions) -> Option<(Date, Date)> {
+        if self.fiscal {
+            return self.fiscal_range(options);
+        }
+
+        let month_range = |first: u32, last: u32| {
+            (
+                Date { year: self.year, month: first, day: 1 },
+                Date { year: self.year, month: last, day: days_in_month(self.year, last) },
+            )
+        };
+
+        match self.granularity {
+            Granularity::Week => Some((week_date(self.year, self.index, 1)?, week_date(self.year, self.index, 7)?)),
+            Granularity::Month => Some(month_range(self.index, self.index)),
+            Granularity::Quarter => Some(month_range(self.index * 3 - 2, self.index * 3)),
+        }
+    }
+
+    // A fiscal quarter can span two calendar years, so months are counted from the
+    // January of the calendar year the fiscal year starts in
+    fn fiscal_range(&self, options: &ValidationOptions) -> Option<(Date, Date)> {
+        let start_month = options.fiscal_year_start.filter(|month| (1..=12).contains(month))?;
+        let first_year = match options.fiscal_year_named_by {
+            FiscalNaming::End if start_month > 1 => self.year - 1,
+            _ => self.year,
+        };
+
+        let month_at = |offset: u32| (first_year + (offset / 12) as i32, offset % 12 + 1);
+        let first = start_month - 1 + (self.index - 1) * 3;
+        let (start_year, start_month) = month_at(first);
+        let (end_year, end_month) = month_at(first + 2);
+
+        Some((
+            Date { year: start_year, month: start_month, day: 1 },
+            Date { year: end_year, month: end_month, day: days_in_month(end_year, end_month) },
+        ))
+    }
+}
+
+fn number(text: &str, width: usize) -> Option<u32> {
+    if text.len() != width || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+// YYYY-MM, YYYY-Qn, YYYY-Www or FYYYYY-Qn; the designators are case-insensitive
+fn parse_period(text: &str) -> Option<Period> {
+    let fiscal = text.get(..2).is_some_and(|prefix| prefix.eq_ignore_ascii_case("fy"));
+    let text = if fiscal { &text[2..] } else { text };
+
+    let (year, rest) = text.split_once('-')?;
+    let year = number(year, 4)? as i32;
+
+    let (granularity, index) = match rest.as_bytes().first()? {
+        b'Q' | b'q' => (Granularity::Quarter, number(&rest[1..], 1)?),
+        b'W' | b'w' => (Granularity::Week, number(&rest[1..], 2)?),
+        _ => (Granularity::Month, number(rest, 2)?),
+    };
+    if fiscal && granularity != Granularity::Quarter {
+        return None;
+    }
+
+    let last = match granularity {
+        Granularity::Week => weeks_in_year(year),
+        Granularity::Month => 12,
+        Granularity::Quarter => 4,
+    };
+    (1..=last).contains(&index).then_some(Period { year, granularity, index, fiscal })
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the canonical identifier with its granularity and first and last calendar days
+fn coerce_period_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let period = parse_period(text)?;
+    if options.granularities.as_ref().is_some_and(|allowed| !allowed.contains(&period.granularity)) {
+        return None;
+    }
+
+    let (start, end) = period.range(options)?;
+    Some(serde_json::json!({
+        "period": period.identifier(),
+        "granularity": period.granularity,
+        "start": start.to_string(),
+        "end": end.to_string(),
+    }))
+}
+
+// Only strings can be valid; the fast path calls coerce_period_str directly
+fn coerce_period(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_period_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_period(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_period(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_period(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_period(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_period(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_period(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_period(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_period(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_period(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_period_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn granularities(list: Value) -> ValidationOptions {
+        serde_json::from_value(json!({ "granularities": list })).unwrap()
+    }
+
+    #[test]
+    fn test_valid_periods() {
+        let defaults = ValidationOptions::default();
+
+        for period in ["2024-05", "2024-12", "2024-Q1", "2024-Q4", "2024-q3", "2024-W01", "2024-W22", "2020-W53", "2024-w52"] {
+            assert!(validate_period(&json!(period), &defaults), "{}", period);
+        }
+    }
+
+    #[test]
+    fn test_invalid_periods() {
+        let defaults = ValidationOptions::default();
+
+        for period in [
+            "", "2024", "2024-13", "2024-00", "2024-5", "2024-Q0", "2024-Q5", "2024-Q12", "2024-W00",
+            "2024-W53", "2024-W1", "2024W22", "24-05", "2024-05-01", "2024/05", "2024-H1",
+        ] {
+            assert!(!validate_period(&json!(period), &defaults), "{}", period);
+        }
+        assert!(!validate_period(&json!(202405), &defaults));
+    }
+
+    #[test]
+    fn test_granularities() {
+        let quarters = granularities(json!(["quarter"]));
+
+        assert!(validate_period(&json!("2024-Q3"), &quarters));
+        assert!(!validate_period(&json!("2024-05"), &quarters));
+        assert!(!validate_period(&json!("2024-W22"), &quarters));
+        assert!(validate_period(&json!("2024-W22"), &granularities(json!(["week", "month"]))));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"granularities": ["day"]})).is_err());
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            coerce_period(&json!("2024-q1"), &defaults),
+            Some(json!({"period": "2024-Q1", "granularity": "quarter", "start": "2024-01-01", "end": "2024-03-31"}))
+        );
+        assert_eq!(
+            coerce_period(&json!("2024-02"), &defaults),
+            Some(json!({"period": "2024-02", "granularity": "month", "start": "2024-02-01", "end": "2024-02-29"}))
+        );
+        assert_eq!(
+            coerce_period(&json!("2020-w53"), &defaults),
+            Some(json!({"period": "2020-W53", "granularity": "week", "start": "2020-12-28", "end": "2021-01-03"}))
+        );
+        assert_eq!(coerce_period(&json!("2024-Q5"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "2024-Q3"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "2024-Q3", "options": {"granularities": ["quarter"]}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "2024-Q3", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_period(&json!("2024-Q4"), &defaults)),
+            ValidationResult {
+                valid: true,
+                value: json!({"period": "2024-Q4", "granularity": "quarter", "start": "2024-10-01", "end": "2024-12-31"}),
+            }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_period(&json!("2024-13"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_fiscal_quarters() {
+        let options = |value: Value| -> ValidationOptions { serde_json::from_value(value).unwrap() };
+        let october = options(json!({"fiscal_year_start": 10}));
+
+        // Without a fiscal year start there is no way to place a fiscal quarter
+        assert!(!validate_period(&json!("FY2025-Q1"), &ValidationOptions::default()));
+
+        assert_eq!(
+            coerce_period(&json!("fy2025-q1"), &october),
+            Some(json!({"period": "FY2025-Q1", "granularity": "quarter", "start": "2024-10-01", "end": "2024-12-31"}))
+        );
+        assert_eq!(
+            coerce_period(&json!("FY2025-Q2"), &october),
+            Some(json!({"period": "FY2025-Q2", "granularity": "quarter", "start": "2025-01-01", "end": "2025-03-31"}))
+        );
+
+        // A quarter that crosses the calendar year boundary
+        let november_start = options(json!({"fiscal_year_start": 11, "fiscal_year_named_by": "start"}));
+        assert_eq!(
+            coerce_period(&json!("FY2024-Q1"), &november_start),
+            Some(json!({"period": "FY2024-Q1", "granularity": "quarter", "start": "2024-11-01", "end": "2025-01-31"}))
+        );
+
+        // A January start makes fiscal quarters calendar quarters
+        let january = options(json!({"fiscal_year_start": 1}));
+        assert_eq!(coerce_period(&json!("FY2024-Q1"), &january).unwrap()["start"], json!("2024-01-01"));
+
+        assert!(!validate_period(&json!("FY2025-Q5"), &october));
+        assert!(!validate_period(&json!("FY2025-05"), &october));
+        assert!(!validate_period(&json!("FY2025-W10"), &october));
+        assert!(!validate_period(&json!("FY2025-Q1"), &options(json!({"fiscal_year_start": 13}))));
+        assert!(!validate_period(&json!("FY2025-Q1"), &options(json!({"fiscal_year_start": 10, "granularities": ["month"]}))));
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"fiscal_year_named_by": "middle"})).is_err());
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["P1Y2M", "PT30M", "P", "1 year"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
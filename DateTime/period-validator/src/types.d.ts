@@ -0,0 +1,46 @@
+// Types for the period validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Only accept these period kinds */
+    granularities?: ("week" | "month" | "quarter")[];
+    /** First month of the fiscal year; enables FY2025-Q1 identifiers */
+    fiscal_year_start?: number;
+    /** Whether FY2025 is the fiscal year that ends or starts in calendar 2025 (default: "end") */
+    fiscal_year_named_by?: "end" | "start";
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    period: string;
+    granularity: "week" | "month" | "quarter";
+    start: string;
+    end: string;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,391 @@
+// ABOUTME: WASM component for time-of-day validation
+// ABOUTME: Provides validate and coerce functions for RFC 3339 times and the broader ISO 8601 time forms
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod time;
+
+use time::{parse_time, Offset};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Whether a UTC offset is "required", "optional" (default) or "forbidden"
+    timezone: Timezone,
+    // Also accept ISO 8601 basic format, reduced precision and comma fractions
+    iso8601: bool,
+    // Reject second 60 outright instead of accepting it where a leap second can occur
+    reject_leap_seconds: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// Whether a UTC offset must, may or must not be present
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Timezone {
+    Required,
+    #[default]
+    Optional,
+    Forbidden,
+}
+
+impl Timezone {
+    fn allows(self, offset: Option<Offset>) -> bool {
+        match self {
+            Timezone::Required => offset.is_some(),
+            Timezone::Optional => true,
+            Timezone::Forbidden => offset.is_none(),
+        }
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the time in RFC 3339 extended form (hh:mm:ss[.fraction][offset])
+fn coerce_time_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let time = parse_time(text, options.iso8601, !options.reject_leap_seconds)?;
+    if !options.timezone.allows(time.offset) {
+        return None;
+    }
+    Some(Value::String(time.to_string()))
+}
+
+// Only strings can be valid; the fast path calls coerce_time_str directly
+fn coerce_time(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_time_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_time(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_time(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_time(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_time(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_time(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_time(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_time(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_time(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_time(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_time_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_times() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_time(&json!("12:30:45"), &defaults));
+        assert!(validate_time(&json!("00:00:00"), &defaults));
+        assert!(validate_time(&json!("23:59:59"), &defaults));
+        assert!(validate_time(&json!("23:59:60"), &defaults)); // Leap second
+        assert!(validate_time(&json!("12:30:45Z"), &defaults));
+        assert!(validate_time(&json!("12:30:45.123"), &defaults));
+        assert!(validate_time(&json!("12:30:45.999999999"), &defaults));
+        assert!(validate_time(&json!("12:30:45+00:00"), &defaults));
+        assert!(validate_time(&json!("12:30:45-05:00"), &defaults));
+        assert!(validate_time(&json!("12:30:45+14:00"), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_times() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_time(&json!("24:00:00"), &defaults));
+        assert!(!validate_time(&json!("12:60:00"), &defaults));
+        assert!(!validate_time(&json!("12:30:61"), &defaults));
+        assert!(!validate_time(&json!("1:30:45"), &defaults));
+        assert!(!validate_time(&json!("12:3:45"), &defaults));
+        assert!(!validate_time(&json!("12:30:4"), &defaults));
+        assert!(!validate_time(&json!("12:30"), &defaults));
+        assert!(!validate_time(&json!("12:30:45."), &defaults));
+        assert!(!validate_time(&json!("12:30:45+1:00"), &defaults));
+        assert!(!validate_time(&json!("12:30:45+15:00"), &defaults));
+        assert!(!validate_time(&json!("2024-03-14T12:30:45Z"), &defaults));
+        assert!(!validate_time(&json!(""), &defaults));
+        assert!(!validate_time(&json!("not a time"), &defaults));
+        assert!(!validate_time(&json!(1230), &defaults));
+    }
+
+    #[test]
+    fn test_timezone() {
+        let required = options(json!({"timezone": "required"}));
+        let forbidden = options(json!({"timezone": "forbidden"}));
+
+        assert!(validate_time(&json!("12:30:45Z"), &required));
+        assert!(validate_time(&json!("12:30:45-05:00"), &required));
+        assert!(!validate_time(&json!("12:30:45"), &required));
+
+        assert!(validate_time(&json!("12:30:45"), &forbidden));
+        assert!(!validate_time(&json!("12:30:45Z"), &forbidden));
+        assert!(!validate_time(&json!("12:30:45+01:00"), &forbidden));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"timezone": "always"})).is_err());
+    }
+
+    #[test]
+    fn test_iso8601() {
+        let defaults = ValidationOptions::default();
+        let iso8601 = options(json!({"iso8601": true}));
+
+        for text in ["123045", "12:30", "1230", "12:30:45,5", "12:30:45+0530", "12:30:45-08"] {
+            assert!(!validate_time(&json!(text), &defaults), "{}", text);
+            assert!(validate_time(&json!(text), &iso8601), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+        let iso8601 = options(json!({"iso8601": true}));
+
+        assert_eq!(coerce_time(&json!("12:30:45.50z"), &defaults), Some(json!("12:30:45.50Z")));
+        assert_eq!(coerce_time(&json!("1230+0530"), &iso8601), Some(json!("12:30:00+05:30")));
+        assert_eq!(coerce_time(&json!("12:30:45,25"), &iso8601), Some(json!("12:30:45.25")));
+        assert_eq!(coerce_time(&json!("25:00:00"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "12:30:45"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "12:30:45", "options": {"timezone": "forbidden"}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "12:30:45", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_time(&json!("12:30:45z"), &defaults)),
+            ValidationResult { valid: true, value: json!("12:30:45Z") }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_time(&json!("12:30"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+
+    #[test]
+    fn test_leap_seconds() {
+        let defaults = ValidationOptions::default();
+        let reject = options(json!({"reject_leap_seconds": true}));
+
+        assert!(validate_time(&json!("23:59:60Z"), &defaults));
+        assert!(validate_time(&json!("00:59:60+01:00"), &defaults));
+        assert!(!validate_time(&json!("12:30:60Z"), &defaults)); // Not 23:59 UTC
+        assert!(!validate_time(&json!("23:59:60Z"), &reject));
+        assert!(!validate_time(&json!("23:59:60"), &reject));
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["10:30", "23:59:59", "25:00", "noon?"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// ABOUTME: Time-of-day and UTC offset parsing, shared with the datetime validator
+// ABOUTME: Accepts RFC 3339 partial-time with an optional offset, or the broader ISO 8601 forms
+
+use std::fmt;
+
+// A UTC offset as written; -00:00 is kept distinct from Z, as RFC 3339 gives it its own meaning
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Offset {
+    Utc,
+    Numeric { negative: bool, hours: u32, minutes: u32 },
+}
+
+impl Offset {
+    fn minutes(&self) -> i32 {
+        match self {
+            Offset::Utc => 0,
+            Offset::Numeric { negative, hours, minutes } => {
+                let total = (hours * 60 + minutes) as i32;
+                if *negative { -total } else { total }
+            },
+        }
+    }
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Offset::Utc => write!(f, "Z"),
+            Offset::Numeric { negative, hours, minutes } => {
+                write!(f, "{}{:02}:{:02}", if *negative { '-' } else { '+' }, hours, minutes)
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Time {
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    // Fractional-second digits exactly as written, without the separator
+    pub fraction: Option<String>,
+    pub offset: Option<Offset>,
+}
+
+// Always the RFC 3339 extended form with seconds and a '.' fraction separator
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
+        if let Some(fraction) = &self.fraction {
+            write!(f, ".{}", fraction)?;
+        }
+        if let Some(offset) = &self.offset {
+            write!(f, "{}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+// Real-world offsets run from -12:00 to +14:00; both directions are capped at 14 hours
+const MAX_OFFSET_MINUTES: u32 = 14 * 60;
+
+fn two_digits(text: &str) -> Option<u32> {
+    if text.len() != 2 || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+// Z, or ±hh:mm; with `iso8601`, also ±hhmm and ±hh
+fn parse_offset(text: &str, iso8601: bool) -> Option<Offset> {
+    if text == "Z" || text == "z" {
+        return Some(Offset::Utc);
+    }
+
+    let negative = text.starts_with('-');
+    let digits = text.strip_prefix(['+', '-'])?;
+    let (hours, minutes) = match (digits.len(), digits.split_once(':')) {
+        (5, Some((hours, minutes))) => (hours, minutes),
+        (4, None) if iso8601 && digits.is_ascii() => digits.split_at(2),
+        (2, None) if iso8601 => (digits, "00"),
+        _ => return None,
+    };
+    let (hours, minutes) = (two_digits(hours)?, two_digits(minutes)?);
+
+    if minutes > 59 || hours * 60 + minutes > MAX_OFFSET_MINUTES {
+        return None;
+    }
+    Some(Offset::Numeric { negative, hours, minutes })
+}
+
+// Leap seconds are inserted at 23:59:60 UTC, so with a known offset only the matching
+// local minute can have a 60th second; without one, any minute is given the benefit of the doubt
+fn is_leap_second_minute(hour: u32, minute: u32, offset: Option<Offset>) -> bool {
+    match offset {
+        Some(offset) => ((hour * 60 + minute) as i32 - offset.minutes()).rem_euclid(24 * 60) == 23 * 60 + 59,
+        None => true,
+    }
+}
+
+// Parses hh:mm:ss with an optional fraction and offset. With `iso8601`, also accepts
+// the basic format (hhmmss), reduced precision (hh:mm, hhmm) and ',' as the fraction separator.
+// Second 60 is accepted only when `leap_seconds` is set and the time can be a leap second
+pub fn parse_time(text: &str, iso8601: bool, leap_seconds: bool) -> Option<Time> {
+    // The offset starts at the first Z, + or -; none of them can appear earlier in a time
+    let (clock, offset) = match text.find(['Z', 'z', '+', '-']) {
+        Some(index) => (&text[..index], Some(parse_offset(&text[index..], iso8601)?)),
+        None => (text, None),
+    };
+
+    let (clock, fraction) = match clock.find(['.', ',']) {
+        Some(index) => {
+            let fraction = &clock[index + 1..];
+            let comma = clock.as_bytes()[index] == b',';
+            if (comma && !iso8601) || fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            (&clock[..index], Some(fraction.to_string()))
+        },
+        None => (clock, None),
+    };
+
+    let fields: Vec<&str> = if clock.contains(':') {
+        clock.split(':').collect()
+    } else if iso8601 && clock.is_ascii() && clock.len() % 2 == 0 {
+        (0..clock.len()).step_by(2).map(|i| &clock[i..i + 2]).collect()
+    } else {
+        return None;
+    };
+
+    let (hour, minute, second) = match fields.as_slice() {
+        [hour, minute, second] => (two_digits(hour)?, two_digits(minute)?, two_digits(second)?),
+        // A fraction always belongs to the last field written, so it cannot follow minutes here
+        [hour, minute] if iso8601 && fraction.is_none() => (two_digits(hour)?, two_digits(minute)?, 0),
+        _ => return None,
+    };
+
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    if second == 60 && !(leap_seconds && is_leap_second_minute(hour, minute, offset)) {
+        return None;
+    }
+
+    Some(Time { hour, minute, second, fraction, offset })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(text: &str, iso8601: bool) -> Option<String> {
+        parse_time(text, iso8601, true).map(|t| t.to_string())
+    }
+
+    #[test]
+    fn test_rfc3339_times() {
+        assert_eq!(time("12:30:45", false), Some("12:30:45".to_string()));
+        assert_eq!(time("23:59:60", false), Some("23:59:60".to_string()));
+        assert_eq!(time("12:30:45.123456789z", false), Some("12:30:45.123456789Z".to_string()));
+        assert_eq!(time("12:30:45-00:00", false), Some("12:30:45-00:00".to_string()));
+        assert_eq!(time("12:30:45+14:00", false), Some("12:30:45+14:00".to_string()));
+
+        assert_eq!(time("12:30", false), None);
+        assert_eq!(time("123045", false), None);
+        assert_eq!(time("12:30:45,5", false), None);
+        assert_eq!(time("12:30:45.", false), None);
+        assert_eq!(time("12:30:45+0530", false), None);
+        assert_eq!(time("12:30:45+14:01", false), None);
+        assert_eq!(time("12:30:45+05:60", false), None);
+        assert_eq!(time("12:30:45Z+01:00", false), None);
+    }
+
+    #[test]
+    fn test_iso8601_times() {
+        assert_eq!(time("123045", true), Some("12:30:45".to_string()));
+        assert_eq!(time("12:30", true), Some("12:30:00".to_string()));
+        assert_eq!(time("1230Z", true), Some("12:30:00Z".to_string()));
+        assert_eq!(time("12:30:45,5", true), Some("12:30:45.5".to_string()));
+        assert_eq!(time("12:30:45+0530", true), Some("12:30:45+05:30".to_string()));
+        assert_eq!(time("12:30:45-08", true), Some("12:30:45-08:00".to_string()));
+
+        assert_eq!(time("12", true), None);
+        assert_eq!(time("12:30.5", true), None);
+        assert_eq!(time("12304", true), None);
+        assert_eq!(time("12:30:45+5", true), None);
+    }
+
+    #[test]
+    fn test_leap_seconds() {
+        assert!(parse_time("23:59:60Z", false, true).is_some());
+        assert!(parse_time("05:29:60+05:30", false, true).is_some());
+        assert!(parse_time("18:59:60-05:00", false, true).is_some());
+        assert!(parse_time("12:30:60", false, true).is_some()); // No offset, so not checkable
+
+        assert!(parse_time("12:30:60Z", false, true).is_none());
+        assert!(parse_time("23:59:60+01:00", false, true).is_none());
+        assert!(parse_time("23:59:60Z", false, false).is_none());
+        assert!(parse_time("23:59:60", false, false).is_none());
+    }
+}
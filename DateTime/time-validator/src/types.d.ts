@@ -0,0 +1,41 @@
+// Types for the time validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Whether a UTC offset is required, optional or forbidden (default: "optional") */
+    timezone?: "required" | "optional" | "forbidden";
+    /** Also accept the broader ISO8601 grammar: basic format, week and ordinal dates, reduced precision, comma fractions and ±hhmm/±hh offsets (default: false) */
+    iso8601?: boolean;
+    /** Reject second 60, which is otherwise accepted only where a leap second can occur (default: false) */
+    reject_leap_seconds?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,899 @@
+// ABOUTME: WASM component for email authentication DNS records, for domain onboarding wizards that ask users to paste them
+// ABOUTME: Checks SPF records (RFC 7208), DMARC records (RFC 7489) and DKIM public-key records (RFC 6376)
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The kind of record to accept; detected from the record's version when unset
+    kind: Option<Kind>,
+}
+
+// The records: SPF sender policies, DMARC policies and DKIM public keys
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Spf,
+    Dmarc,
+    Dkim,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The term at fault: an SPF mechanism or modifier as written, or a DMARC or DKIM tag's name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    term: Option<String>,
+    // Concerns that don't make the value invalid, such as an SPF record's lookup count
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, term: None, warnings: Vec::new() }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from((code, term): Problem) -> Self {
+        ValidationResult { error: Some(code), term, ..ValidationResult::from(None) }
+    }
+}
+
+// An error code, and the term it was found in
+type Problem = (&'static str, Option<String>);
+
+// Error codes reported in detailed results
+const UNTERMINATED_QUOTE: &str = "unterminated_quote";
+const TEXT_AFTER_QUOTE: &str = "text_after_quote";
+const NOT_AN_AUTH_RECORD: &str = "not_an_auth_record";
+const WRONG_RECORD_KIND: &str = "wrong_record_kind";
+const INVALID_MECHANISM: &str = "invalid_mechanism";
+const INVALID_MODIFIER: &str = "invalid_modifier";
+const DUPLICATE_MODIFIER: &str = "duplicate_modifier";
+const INVALID_DOMAIN: &str = "invalid_domain";
+const INVALID_NETWORK: &str = "invalid_network";
+const MALFORMED_PAIR: &str = "malformed_pair";
+const MISSING_TAG: &str = "missing_tag";
+const DUPLICATE_TAG: &str = "duplicate_tag";
+const INVALID_TAG: &str = "invalid_tag";
+const INVALID_URL: &str = "invalid_url";
+
+// Warning for an SPF record that makes more DNS lookups than a receiver will, which fails
+// every check of it (RFC 7208 section 4.6.4); includes may make more of their own
+const TOO_MANY_LOOKUPS: &str = "too_many_lookups";
+const MAX_LOOKUPS: usize = 10;
+
+// The letters of SPF macros, such as "%{d}" for the domain (RFC 7208 section 7.2)
+const MACRO_LETTERS: &str = "slodiphcrtv";
+
+// What a DMARC record can ask receivers to do with failing mail, and its alignment modes
+const POLICIES: &[&str] = &["none", "quarantine", "reject"];
+const ALIGNMENTS: &[&str] = &["r", "s"];
+const FAILURE_OPTIONS: &[&str] = &["0", "1", "d", "s"];
+
+// The DKIM key types (RFC 6376 and RFC 8463), and the length of an Ed25519 key in bytes
+const KEY_TYPES: &[&str] = &["rsa", "ed25519"];
+const ED25519_KEY_BYTES: usize = 32;
+
+// The record's text: as written, or, when it's pasted as a zone file gives it, the
+// concatenation of its quoted strings (RFC 7208 section 3.3)
+fn record_text(text: &str) -> Result<String, Problem> {
+    let text = text.trim();
+    if !text.starts_with('"') {
+        return Ok(text.to_string());
+    }
+    let mut record = String::new();
+    let mut chars = text.chars();
+    loop {
+        match chars.next() {
+            None => return Ok(record),
+            Some(c) if c.is_whitespace() => continue,
+            Some('"') => {}
+            Some(_) => return Err((TEXT_AFTER_QUOTE, None)),
+        }
+        loop {
+            match chars.next() {
+                None => return Err((UNTERMINATED_QUOTE, None)),
+                Some('"') => break,
+                Some('\\') => record.extend(chars.next()),
+                Some(c) => record.push(c),
+            }
+        }
+    }
+}
+
+// The kind of record its version marks: "v=spf1" starts an SPF record, and a first tag of
+// "v=DMARC1" or "v=DKIM1" the others
+fn detect(record: &str) -> Option<Kind> {
+    if record.split_ascii_whitespace().next().is_some_and(|version| version.eq_ignore_ascii_case("v=spf1")) {
+        return Some(Kind::Spf);
+    }
+    match record.split(';').next()?.split_once('=').map(|(name, value)| (name.trim(), value.trim())) {
+        Some(("v", "DMARC1")) => Some(Kind::Dmarc),
+        Some(("v", "DKIM1")) => Some(Kind::Dkim),
+        _ => None,
+    }
+}
+
+// Whether the record looks like a DKIM key without its optional version: a tag list with a
+// "p" tag that doesn't start with some other version
+fn is_unversioned_key(record: &str) -> bool {
+    let mut names = record.split(';').filter_map(|spec| spec.split_once('=')).map(|(name, _)| name.trim());
+    let first = names.next();
+    first != Some("v") && first.into_iter().chain(names).any(|name| name == "p")
+}
+
+// Whether the text is a CIDR prefix length of at most max bits, without leading zeros
+fn is_prefix_length(text: &str, max: u8) -> bool {
+    (1..=3).contains(&text.len())
+        && text.bytes().all(|b| b.is_ascii_digit())
+        && (text == "0" || !text.starts_with('0'))
+        && text.parse::<u8>().is_ok_and(|length| length <= max)
+}
+
+// Whether the text is the prefix lengths after an "a" or "mx" mechanism's first "/": "24"
+// for IPv4, "/64" for IPv6, or "24//64" for both
+fn is_dual_cidr(cidr: &str) -> bool {
+    match cidr.split_once("//") {
+        Some((ip4, ip6)) => is_prefix_length(ip4, 32) && is_prefix_length(ip6, 128),
+        None => match cidr.strip_prefix('/') {
+            Some(ip6) => is_prefix_length(ip6, 128),
+            None => is_prefix_length(cidr, 32),
+        },
+    }
+}
+
+// Whether the label can end a domain name: letters, digits and inner hyphens, not all digits
+fn is_toplabel(label: &str) -> bool {
+    label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        && label.bytes().any(|b| b.is_ascii_alphabetic())
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+}
+
+// Whether the text is a domain name of at least two labels, such as "_spf.example.com", with
+// an optional trailing dot; labels may hold underscores, as SPF and DKIM names do
+fn is_domain(text: &str) -> bool {
+    let name = text.strip_suffix('.').unwrap_or(text);
+    let labels: Vec<&str> = name.split('.').collect();
+    let is_label = |label: &&str| {
+        (1..=63).contains(&label.len())
+            && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    };
+    name.len() <= 253 && labels.len() >= 2 && labels.iter().all(is_label) && labels.last().is_some_and(|label| is_toplabel(label))
+}
+
+// Whether the text is the inside of a macro's braces: a letter, an optional number of parts to
+// keep, an optional "r" to reverse them and the delimiters to split on
+fn is_macro(body: &str) -> bool {
+    let mut chars = body.chars();
+    let letter = chars.next().is_some_and(|c| MACRO_LETTERS.contains(c.to_ascii_lowercase()));
+    let rest = chars.as_str().trim_start_matches(|c: char| c.is_ascii_digit());
+    let rest = rest.strip_prefix(['r', 'R']).unwrap_or(rest);
+    letter && rest.chars().all(|c| ".-+,/_=".contains(c))
+}
+
+// The text with each SPF macro, such as "%{d}" or "%{ir}", replaced by "%", and the escapes
+// "%%", "%_" and "%-" by "_", when its macros are well formed and the rest is visible ASCII
+// (RFC 7208 section 7.1)
+fn expand_macros(text: &str) -> Option<String> {
+    let mut expanded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('%') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(escaped) = after.strip_prefix(['%', '_', '-']) {
+            expanded.push('_');
+            rest = escaped;
+            continue;
+        }
+        let (body, tail) = after.strip_prefix('{')?.split_once('}')?;
+        if !is_macro(body) {
+            return None;
+        }
+        expanded.push('%');
+        rest = tail;
+    }
+    expanded.push_str(rest);
+    expanded.bytes().all(|b| b.is_ascii_graphic()).then_some(expanded)
+}
+
+// Whether the text is an SPF domain-spec: a domain name, or a string with macros that ends
+// in a macro or in "." and a top label
+fn is_domain_spec(text: &str) -> bool {
+    let Some(expanded) = expand_macros(text) else {
+        return false;
+    };
+    match expanded.rfind('%') {
+        None => is_domain(&expanded),
+        Some(end) => {
+            let tail = &expanded[end + 1..];
+            tail.is_empty() || tail.starts_with('.') && tail.trim_end_matches('.').rsplit('.').next().is_some_and(is_toplabel)
+        }
+    }
+}
+
+// Checks what follows an SPF mechanism's name, such as ":_spf.example.com", "/24" or
+// ":192.0.2.0/24"
+fn check_mechanism(name: &str, argument: &str) -> Result<(), &'static str> {
+    let (target, cidr) = match name {
+        "a" | "mx" => argument.split_once('/').map_or((argument, None), |(target, cidr)| (target, Some(cidr))),
+        _ => (argument, None),
+    };
+    let domain = |required: bool| match target.strip_prefix(':') {
+        Some(spec) if is_domain_spec(spec) => Ok(()),
+        Some(_) => Err(INVALID_DOMAIN),
+        None if target.is_empty() && !required => Ok(()),
+        None => Err(INVALID_MECHANISM),
+    };
+    match name {
+        "all" if argument.is_empty() => Ok(()),
+        "include" | "exists" => domain(true),
+        "ptr" => domain(false),
+        "a" | "mx" => {
+            domain(false)?;
+            if cidr.is_none_or(is_dual_cidr) { Ok(()) } else { Err(INVALID_NETWORK) }
+        }
+        "ip4" | "ip6" => {
+            let network = argument.strip_prefix(':').ok_or(INVALID_MECHANISM)?;
+            let (address, length) = network.split_once('/').map_or((network, None), |(address, length)| (address, Some(length)));
+            let (valid, max) = match name {
+                "ip4" => (address.parse::<Ipv4Addr>().is_ok(), 32),
+                _ => (address.parse::<Ipv6Addr>().is_ok(), 128),
+            };
+            if valid && length.is_none_or(|length| is_prefix_length(length, max)) { Ok(()) } else { Err(INVALID_NETWORK) }
+        }
+        _ => Err(INVALID_MECHANISM),
+    }
+}
+
+// An SPF record (RFC 7208 section 4.6): "v=spf1", then mechanisms, each with an optional
+// qualifier, and "name=value" modifiers. Returns the mechanisms with their qualifiers spelled
+// out, the modifiers and the number of DNS lookups the record makes itself, and a warning when
+// that's too many
+fn check_spf(record: &str) -> Result<(Value, Vec<&'static str>), Problem> {
+    let mut terms = record.split_ascii_whitespace();
+    if !terms.next().is_some_and(|version| version.eq_ignore_ascii_case("v=spf1")) {
+        return Err((NOT_AN_AUTH_RECORD, None));
+    }
+    let mut mechanisms = Vec::new();
+    let mut modifiers = serde_json::Map::new();
+    let mut lookups = 0;
+    for term in terms {
+        let problem = |code| (code, Some(term.to_string()));
+
+        // A modifier's name starts with a letter and goes on with letters, digits, "-", "_"
+        // and "."; a mechanism's argument starts with ":" or "/", so can't be mistaken for one
+        let modifier = term.split_once('=').filter(|(name, _)| {
+            name.starts_with(|c: char| c.is_ascii_alphabetic()) && name.bytes().all(|b| b.is_ascii_alphanumeric() || b"-_.".contains(&b))
+        });
+        if let Some((name, value)) = modifier {
+            let name = name.to_ascii_lowercase();
+            match name.as_str() {
+                "redirect" | "exp" if modifiers.contains_key(&name) => return Err(problem(DUPLICATE_MODIFIER)),
+                "redirect" | "exp" if !is_domain_spec(value) => return Err(problem(INVALID_DOMAIN)),
+                _ if expand_macros(value).is_none() => return Err(problem(INVALID_MODIFIER)),
+                _ => {}
+            }
+            lookups += usize::from(name == "redirect");
+            modifiers.entry(name).or_insert(Value::from(value));
+            continue;
+        }
+
+        let (qualifier, rest) = match term.chars().next() {
+            Some(qualifier @ ('+' | '-' | '~' | '?')) => (qualifier, &term[1..]),
+            _ => ('+', term),
+        };
+        let (name, argument) = rest.split_at(rest.find([':', '/']).unwrap_or(rest.len()));
+        let name = name.to_ascii_lowercase();
+        check_mechanism(&name, argument).map_err(problem)?;
+        lookups += usize::from(matches!(name.as_str(), "include" | "a" | "mx" | "ptr" | "exists"));
+        mechanisms.push(format!("{qualifier}{name}{argument}"));
+    }
+
+    let warnings = if lookups > MAX_LOOKUPS { vec![TOO_MANY_LOOKUPS] } else { Vec::new() };
+    Ok((json!({ "kind": Kind::Spf, "mechanisms": mechanisms, "modifiers": modifiers, "lookups": lookups }), warnings))
+}
+
+// A DKIM-style tag list (RFC 6376 section 3.2), as DMARC uses too: "name=value" pairs
+// separated by semicolons, with an optional one at the end. Names are a letter then letters,
+// digits or underscores, and values visible ASCII with inner whitespace
+fn tag_list(record: &str) -> Result<Vec<(&str, &str)>, Problem> {
+    let record = record.trim();
+    let mut tags: Vec<(&str, &str)> = Vec::new();
+    for spec in record.strip_suffix(';').unwrap_or(record).split(';') {
+        let malformed = || (MALFORMED_PAIR, Some(spec.trim().to_string()));
+        let (name, value) = spec.split_once('=').ok_or_else(malformed)?;
+        let (name, value) = (name.trim(), value.trim());
+        let is_name = name.starts_with(|c: char| c.is_ascii_alphabetic()) && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_');
+        if !is_name || !value.bytes().all(|b| b.is_ascii_graphic() || b.is_ascii_whitespace()) {
+            return Err(malformed());
+        }
+        if tags.iter().any(|(seen, _)| *seen == name) {
+            return Err((DUPLICATE_TAG, Some(name.to_string())));
+        }
+        tags.push((name, value));
+    }
+    Ok(tags)
+}
+
+fn invalid_tag(name: &str) -> Problem {
+    (INVALID_TAG, Some(name.to_string()))
+}
+
+fn missing_tag(name: &str) -> Problem {
+    (MISSING_TAG, Some(name.to_string()))
+}
+
+// A tag's value from a list of words, ignoring case, in lower case
+fn keyword(name: &str, value: &str, allowed: &[&str]) -> Result<String, Problem> {
+    let value = value.to_ascii_lowercase();
+    if allowed.contains(&value.as_str()) { Ok(value) } else { Err(invalid_tag(name)) }
+}
+
+// A colon-separated list of a tag's values, such as DKIM's "h=sha1:sha256", each "*" or a
+// word of letters, digits and hyphens
+fn words<'a>(name: &str, value: &'a str) -> Result<Vec<&'a str>, Problem> {
+    let words: Vec<&str> = value.split(':').map(str::trim).collect();
+    let is_word = |word: &&str| *word == "*" || !word.is_empty() && word.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-');
+    if words.iter().all(is_word) { Ok(words) } else { Err(invalid_tag(name)) }
+}
+
+// A tag's value as a whole number of at most ten digits that fits in 32 bits
+fn number(name: &str, value: &str) -> Result<u32, Problem> {
+    let digits = !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+    digits.then(|| value.parse().ok()).flatten().ok_or_else(|| invalid_tag(name))
+}
+
+// A DMARC report address list: URIs separated by commas, each with an optional size limit
+// such as "!10m". Commas and "!" inside a URI are percent-encoded, so the last "!" starts the
+// limit. The URIs are checked, and canonicalized, by the URL validator
+fn report_uris(name: &str, value: &str) -> Result<Vec<String>, Problem> {
+    let any_scheme = json!({ "allow_any_scheme": true });
+    let report_uri = |uri: &str| {
+        let (address, limit) = uri.trim().rsplit_once('!').map_or((uri.trim(), None), |(address, limit)| (address, Some(limit)));
+        let valid_limit = limit.is_none_or(|limit| {
+            let digits = limit.strip_suffix(['k', 'm', 'g', 't', 'K', 'M', 'G', 'T']).unwrap_or(limit);
+            !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+        });
+        let address = url_validator::coerce_value(&Value::from(address), &any_scheme).filter(|_| valid_limit);
+        match (address.as_ref().and_then(Value::as_str), limit) {
+            (Some(address), Some(limit)) => Ok(format!("{address}!{}", limit.to_ascii_lowercase())),
+            (Some(address), None) => Ok(address.to_string()),
+            (None, _) => Err((INVALID_URL, Some(name.to_string()))),
+        }
+    };
+    value.split(',').map(report_uri).collect()
+}
+
+// A DMARC policy record (RFC 7489 section 6.3), which starts with "v=DMARC1" and has a "p"
+// policy. Returns its policies and reporting, with the defaults of the tags it leaves out;
+// tags DMARC doesn't define are ignored
+fn check_dmarc(record: &str) -> Result<Value, Problem> {
+    let tags = tag_list(record)?;
+    match tags.first() {
+        Some(&("v", "DMARC1")) => {}
+        _ if tags.iter().any(|(name, _)| *name == "v") => return Err(invalid_tag("v")),
+        _ => return Err(missing_tag("v")),
+    }
+    let tag = |name: &str| tags.iter().find(|(tag, _)| *tag == name).map(|&(_, value)| value);
+
+    let policy = keyword("p", tag("p").ok_or_else(|| missing_tag("p"))?, POLICIES)?;
+    let subdomain_policy = tag("sp").map_or(Ok(policy.clone()), |sp| keyword("sp", sp, POLICIES))?;
+    let dkim_alignment = tag("adkim").map_or(Ok("r".to_string()), |adkim| keyword("adkim", adkim, ALIGNMENTS))?;
+    let spf_alignment = tag("aspf").map_or(Ok("r".to_string()), |aspf| keyword("aspf", aspf, ALIGNMENTS))?;
+    let percent = tag("pct").map_or(Ok(100), |pct| number("pct", pct).and_then(|pct| if pct <= 100 { Ok(pct) } else { Err(invalid_tag("pct")) }))?;
+    let aggregate_reports = tag("rua").map_or(Ok(Vec::new()), |rua| report_uris("rua", rua))?;
+    let failure_reports = tag("ruf").map_or(Ok(Vec::new()), |ruf| report_uris("ruf", ruf))?;
+    let failure_options = tag("fo").map_or(Ok(vec!["0".to_string()]), |fo| {
+        fo.split(':').map(|option| keyword("fo", option.trim(), FAILURE_OPTIONS)).collect()
+    })?;
+    let report_format = tag("rf").map_or(Ok(vec!["afrf"]), |rf| words("rf", rf))?;
+    let report_interval = tag("ri").map_or(Ok(86_400), |ri| number("ri", ri))?;
+
+    Ok(json!({
+        "kind": Kind::Dmarc,
+        "policy": policy,
+        "subdomain_policy": subdomain_policy,
+        "percent": percent,
+        "dkim_alignment": dkim_alignment,
+        "spf_alignment": spf_alignment,
+        "aggregate_reports": aggregate_reports,
+        "failure_reports": failure_reports,
+        "failure_options": failure_options,
+        "report_format": report_format,
+        "report_interval": report_interval,
+    }))
+}
+
+// The number of bytes a base64 string decodes to, when it's base64 with its padding
+fn base64_bytes(text: &str) -> Option<usize> {
+    let data = text.trim_end_matches('=');
+    let padding = text.len() - data.len();
+    let valid = text.len().is_multiple_of(4) && padding <= 2 && data.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/');
+    valid.then(|| text.len() / 4 * 3 - padding)
+}
+
+// A DKIM public-key record (RFC 6376 section 3.6.1), whose "v=DKIM1" is optional but first
+// when given, with a base64 key in "p"; an empty key means it was revoked. Returns the key and
+// its settings, with the defaults of the tags it leaves out
+fn check_dkim(record: &str) -> Result<Value, Problem> {
+    let tags = tag_list(record)?;
+    match tags.iter().position(|(name, _)| *name == "v") {
+        Some(0) if tags[0].1 == "DKIM1" => {}
+        Some(_) => return Err(invalid_tag("v")),
+        None => {}
+    }
+    let tag = |name: &str| tags.iter().find(|(tag, _)| *tag == name).map(|&(_, value)| value);
+
+    let key_type = tag("k").map_or(Ok("rsa".to_string()), |k| keyword("k", k, KEY_TYPES))?;
+    // Long keys are split across lines and strings, so whitespace in the key is ignored
+    let key: String = tag("p").ok_or_else(|| missing_tag("p"))?.split_ascii_whitespace().collect();
+    match base64_bytes(&key) {
+        Some(bytes) if key_type == "ed25519" && bytes != 0 && bytes != ED25519_KEY_BYTES => return Err(invalid_tag("p")),
+        Some(_) => {}
+        None => return Err(invalid_tag("p")),
+    }
+    let hash_algorithms = tag("h").map(|h| words("h", h)).transpose()?;
+    let service_types = tag("s").map_or(Ok(vec!["*"]), |s| words("s", s))?;
+    let flags = tag("t").map_or(Ok(Vec::new()), |t| words("t", t))?;
+
+    Ok(json!({
+        "kind": Kind::Dkim,
+        "key_type": key_type,
+        "public_key": key,
+        "revoked": key.is_empty(),
+        "hash_algorithms": hash_algorithms,
+        "service_types": service_types,
+        "flags": flags,
+        "notes": tag("n"),
+    }))
+}
+
+// Checks a record of the kind given, or else of the kind its version marks
+fn check_record(text: &str, options: &ValidationOptions) -> Result<(Value, Vec<&'static str>), Problem> {
+    let record = record_text(text)?;
+    let kind = match (options.kind, detect(&record)) {
+        (Some(expected), Some(kind)) if kind != expected => return Err((WRONG_RECORD_KIND, None)),
+        (Some(kind), _) | (None, Some(kind)) => kind,
+        (None, None) if is_unversioned_key(&record) => Kind::Dkim,
+        (None, None) => return Err((NOT_AN_AUTH_RECORD, None)),
+    };
+    match kind {
+        Kind::Spf => check_spf(&record),
+        Kind::Dmarc => Ok((check_dmarc(&record)?, Vec::new())),
+        Kind::Dkim => Ok((check_dkim(&record)?, Vec::new())),
+    }
+}
+
+fn detail_authrecord_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no record, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_record(text, options) {
+        Ok((summary, warnings)) => ValidationResult { warnings, ..ValidationResult::from(Some(summary)) },
+        Err(problem) => ValidationResult::from(problem),
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_authrecord_str directly
+fn detail_authrecord(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_authrecord_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the record's kind and its parsed contents
+fn coerce_authrecord(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_authrecord(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_authrecord(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_authrecord(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_authrecord(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_authrecord(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_authrecord(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_authrecord(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_authrecord(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_authrecord(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_authrecord(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_authrecord_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPF: &str = "v=spf1 ip4:192.0.2.0/24 ip6:2001:db8::/32 a mx:mail.example.com/28//64 include:_spf.example.net ~all";
+    const DMARC: &str = "v=DMARC1; p=quarantine; rua=mailto:dmarc@example.com,mailto:reports@example.net!10M; pct=50; adkim=s";
+    const DKIM: &str = "v=DKIM1; k=rsa; t=s; p=MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQDwIRP/UC3SBsEmGqZ9ZJW3/DkMoGeLnQg1fWn7/zYt\
+        IxN2SnFCjxOCKG9v3b4jYfcTNh5ijSsq631uBItLa7od+v/RtdC2UzJ1lWT947qR+Rcac2gbto/NMqJ0fzfVjH4OuKhitdY9tf6mcwGjaNBcWToIMmPSPDdQPNUYckcQ2QIDAQAB";
+
+    fn problem(text: &str) -> (Option<&'static str>, Option<String>) {
+        let result = detail_authrecord_str(text, &ValidationOptions::default());
+        (result.error, result.term)
+    }
+
+    fn at(code: &'static str, term: &str) -> (Option<&'static str>, Option<String>) {
+        (Some(code), Some(term.to_string()))
+    }
+
+    #[test]
+    fn test_spf() {
+        assert_eq!(
+            coerce_authrecord(&json!(SPF), &ValidationOptions::default()),
+            Some(json!({
+                "kind": "spf",
+                "mechanisms": ["+ip4:192.0.2.0/24", "+ip6:2001:db8::/32", "+a", "+mx:mail.example.com/28//64", "+include:_spf.example.net", "~all"],
+                "modifiers": {},
+                "lookups": 3,
+            }))
+        );
+        let redirect = coerce_authrecord(&json!("V=SPF1 -Exists:%{ir}.%{l1r+-}._spf.%{d} redirect=_spf.example.com exp=explain._spf.%{d}"), &ValidationOptions::default()).unwrap();
+        assert_eq!(redirect["mechanisms"], json!(["-exists:%{ir}.%{l1r+-}._spf.%{d}"]));
+        assert_eq!((redirect["modifiers"]["redirect"].clone(), redirect["lookups"].clone()), (json!("_spf.example.com"), json!(2)));
+        assert!(validate_authrecord(&json!("v=spf1 -all"), &ValidationOptions::default()));
+        assert!(validate_authrecord(&json!("v=spf1 a/24 mx//64 ptr ?include:example.com. -all"), &ValidationOptions::default()));
+
+        assert_eq!(problem("v=spf1 inclde:example.com -all"), at(INVALID_MECHANISM, "inclde:example.com"));
+        assert_eq!(problem("v=spf1 include -all"), at(INVALID_MECHANISM, "include"));
+        assert_eq!(problem("v=spf1 all:example.com"), at(INVALID_MECHANISM, "all:example.com"));
+        assert_eq!(problem("v=spf1 *all"), at(INVALID_MECHANISM, "*all"));
+        assert_eq!(problem("v=spf1 include:example -all"), at(INVALID_DOMAIN, "include:example"));
+        assert_eq!(problem("v=spf1 a:example.123 -all"), at(INVALID_DOMAIN, "a:example.123"));
+        assert_eq!(problem("v=spf1 exists:%{x}.example.com -all"), at(INVALID_DOMAIN, "exists:%{x}.example.com"));
+        assert_eq!(problem("v=spf1 exists:%{d}. -all"), at(INVALID_DOMAIN, "exists:%{d}."));
+        assert_eq!(problem("v=spf1 ip4:192.0.2.0/33 -all"), at(INVALID_NETWORK, "ip4:192.0.2.0/33"));
+        assert_eq!(problem("v=spf1 ip4:192.0.2.0/024 -all"), at(INVALID_NETWORK, "ip4:192.0.2.0/024"));
+        assert_eq!(problem("v=spf1 ip4:2001:db8:: -all"), at(INVALID_NETWORK, "ip4:2001:db8::"));
+        assert_eq!(problem("v=spf1 ip6:2001:db8::/129 -all"), at(INVALID_NETWORK, "ip6:2001:db8::/129"));
+        assert_eq!(problem("v=spf1 mx/24//129 -all"), at(INVALID_NETWORK, "mx/24//129"));
+        assert_eq!(problem("v=spf1 redirect=a.example.com redirect=b.example.com"), at(DUPLICATE_MODIFIER, "redirect=b.example.com"));
+        assert_eq!(problem("v=spf1 -all note=%x"), at(INVALID_MODIFIER, "note=%x"));
+    }
+
+    #[test]
+    fn test_spf_lookups() {
+        let includes: Vec<String> = (0..10).map(|i| format!("include:_spf{i}.example.com")).collect();
+        let record = format!("v=spf1 {} -all", includes.join(" "));
+        let result = detail_authrecord_str(&record, &ValidationOptions::default());
+        assert_eq!((result.valid, result.value["lookups"].clone(), result.warnings), (true, json!(10), Vec::new()));
+
+        // An eleventh lookup fails every check of the record, but is only a warning here, as
+        // includes make lookups of their own that aren't counted
+        let result = detail_authrecord_str(&format!("v=spf1 {} mx -all", includes.join(" ")), &ValidationOptions::default());
+        assert_eq!((result.valid, result.warnings.clone()), (true, vec![TOO_MANY_LOOKUPS]));
+        assert_eq!(
+            messages::localize(&result, &json!(record), Some("en"))["warnings"],
+            json!(["too_many_lookups"])
+        );
+    }
+
+    #[test]
+    fn test_dmarc() {
+        assert_eq!(
+            coerce_authrecord(&json!(DMARC), &ValidationOptions::default()),
+            Some(json!({
+                "kind": "dmarc",
+                "policy": "quarantine",
+                "subdomain_policy": "quarantine",
+                "percent": 50,
+                "dkim_alignment": "s",
+                "spf_alignment": "r",
+                "aggregate_reports": ["mailto:dmarc@example.com", "mailto:reports@example.net!10m"],
+                "failure_reports": [],
+                "failure_options": ["0"],
+                "report_format": ["afrf"],
+                "report_interval": 86400,
+            }))
+        );
+        let minimal = coerce_authrecord(&json!("v=DMARC1;p=REJECT;sp=none;fo=1:d;ri=3600;np=none;"), &ValidationOptions::default()).unwrap();
+        assert_eq!(
+            (minimal["policy"].clone(), minimal["subdomain_policy"].clone(), minimal["failure_options"].clone(), minimal["report_interval"].clone()),
+            (json!("reject"), json!("none"), json!(["1", "d"]), json!(3600))
+        );
+
+        assert_eq!(problem("v=DMARC1; rua=mailto:dmarc@example.com"), at(MISSING_TAG, "p"));
+        assert_eq!(problem("v=DMARC1; p=block"), at(INVALID_TAG, "p"));
+        assert_eq!(problem("v=DMARC1; p=none; pct=101"), at(INVALID_TAG, "pct"));
+        assert_eq!(problem("v=DMARC1; p=none; pct=+5"), at(INVALID_TAG, "pct"));
+        assert_eq!(problem("v=DMARC1; p=none; aspf=strict"), at(INVALID_TAG, "aspf"));
+        assert_eq!(problem("v=DMARC1; p=none; fo=0:x"), at(INVALID_TAG, "fo"));
+        assert_eq!(problem("v=DMARC1; p=none; rua=dmarc@example.com"), at(INVALID_URL, "rua"));
+        assert_eq!(problem("v=DMARC1; p=none; ruf=mailto:dmarc@example.com!big"), at(INVALID_URL, "ruf"));
+        assert_eq!(problem("v=DMARC1; p=none; p=reject"), at(DUPLICATE_TAG, "p"));
+        assert_eq!(problem("v=DMARC1; p=none; rua"), at(MALFORMED_PAIR, "rua"));
+        assert_eq!(problem("v=DMARC1; p=none;; rua=mailto:dmarc@example.com"), at(MALFORMED_PAIR, ""));
+
+        // The version comes first, and DMARC's tag names are case-sensitive
+        let dmarc = ValidationOptions { kind: Some(Kind::Dmarc) };
+        assert_eq!(detail_authrecord_str("p=none; v=DMARC1", &dmarc).error, Some(INVALID_TAG));
+        assert_eq!(detail_authrecord_str("V=DMARC1; p=none", &dmarc).error, Some(MISSING_TAG));
+    }
+
+    #[test]
+    fn test_dkim() {
+        let key = coerce_authrecord(&json!(DKIM), &ValidationOptions::default()).unwrap();
+        assert_eq!(
+            (key["kind"].clone(), key["key_type"].clone(), key["revoked"].clone(), key["flags"].clone(), key["service_types"].clone(), key["hash_algorithms"].clone()),
+            (json!("dkim"), json!("rsa"), json!(false), json!(["s"]), json!(["*"]), Value::Null)
+        );
+        assert_eq!(key["public_key"].as_str().map(str::len), Some(216));
+
+        // The version is optional, keys may be split by whitespace, and an empty key is revoked
+        let split = DKIM.replace("v=DKIM1; ", "h=sha256; ").replace("QUAA4", "QUAA4 \r\n\t");
+        assert_eq!(coerce_authrecord(&json!(split), &ValidationOptions::default()).unwrap()["public_key"], key["public_key"]);
+        let revoked = coerce_authrecord(&json!("v=DKIM1; p="), &ValidationOptions::default()).unwrap();
+        assert_eq!((revoked["revoked"].clone(), revoked["public_key"].clone()), (json!(true), json!("")));
+        assert!(validate_authrecord(&json!("v=DKIM1; k=ed25519; p=11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo="), &ValidationOptions::default()));
+
+        assert_eq!(problem("v=DKIM1; k=rsa"), at(MISSING_TAG, "p"));
+        assert_eq!(problem("v=DKIM1; k=dsa; p=AAAA"), at(INVALID_TAG, "k"));
+        assert_eq!(problem("v=DKIM1; p=not base64!"), at(INVALID_TAG, "p"));
+        assert_eq!(problem("v=DKIM1; p=AAA"), at(INVALID_TAG, "p"));
+        assert_eq!(problem("v=DKIM1; k=ed25519; p=AAAA"), at(INVALID_TAG, "p"));
+        assert_eq!(problem("k=rsa; v=DKIM1; p=AAAA"), at(INVALID_TAG, "v"));
+        assert_eq!(problem("v=DKIM1; h=sha256:; p=AAAA"), at(INVALID_TAG, "h"));
+    }
+
+    #[test]
+    fn test_kinds() {
+        // Records pasted as a zone file gives them are read as their quoted strings joined up
+        let quoted = "\"v=spf1 ip4:192.0.2.0/24 \" \"include:_spf.example.net ~all\"";
+        assert_eq!(
+            coerce_authrecord(&json!(quoted), &ValidationOptions::default()).unwrap()["mechanisms"],
+            json!(["+ip4:192.0.2.0/24", "+include:_spf.example.net", "~all"])
+        );
+        assert_eq!(problem("\"v=DMARC1; p=\\\"none\\\"\""), at(INVALID_TAG, "p"));
+        assert_eq!(problem("\"v=spf1 -all"), (Some(UNTERMINATED_QUOTE), None));
+        assert_eq!(problem("\"v=spf1\" -all"), (Some(TEXT_AFTER_QUOTE), None));
+
+        assert_eq!(problem("hello"), (Some(NOT_AN_AUTH_RECORD), None));
+        assert_eq!(problem("v=spf2.0/pra -all"), (Some(NOT_AN_AUTH_RECORD), None));
+        assert_eq!(problem("v=DMARC2; p=none"), (Some(NOT_AN_AUTH_RECORD), None));
+
+        let spf: ValidationOptions = serde_json::from_value(json!({"kind": "spf"})).unwrap();
+        assert!(validate_authrecord(&json!(SPF), &spf));
+        assert_eq!(detail_authrecord_str(DMARC, &spf).error, Some(WRONG_RECORD_KIND));
+        assert_eq!(detail_authrecord_str("include:example.com", &spf).error, Some(NOT_AN_AUTH_RECORD));
+        let dkim: ValidationOptions = serde_json::from_value(json!({"kind": "dkim"})).unwrap();
+        assert_eq!(detail_authrecord_str("k=rsa", &dkim).error, Some(MISSING_TAG));
+        assert_eq!(detail_authrecord_str(DKIM, &dkim).value["kind"], "dkim");
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"kind": "arc"})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let record = "v=spf1 include:example -all";
+        assert_eq!(
+            messages::localize(&detail_authrecord_str(record, &ValidationOptions::default()), &json!(record), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "invalid_domain",
+                "term": "include:example",
+                "message": messages::message("invalid_domain", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_authrecord_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+        assert!(!validate_authrecord(&json!(42), &ValidationOptions::default()));
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [SPF, DMARC], "options": {"kind": "dmarc"}})).unwrap();
+        let valid: Vec<bool> = batch.values.iter().map(|value| validate_authrecord(value, &batch.options)).collect();
+        assert_eq!(valid, vec![false, true]);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [SPF, DMARC, DKIM, "", "v=spf1 +al", "p=none"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Types for the authrecord validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The kind of record to accept; detected from the record's version when unset */
+    kind?: "spf" | "dmarc" | "dkim";
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    kind: "spf";
+    mechanisms: string[];
+    modifiers: Record<string, string>;
+    lookups: number;
+} | {
+    kind: "dmarc";
+    policy: "none" | "quarantine" | "reject";
+    subdomain_policy: "none" | "quarantine" | "reject";
+    percent: number;
+    dkim_alignment: "r" | "s";
+    spf_alignment: "r" | "s";
+    aggregate_reports: string[];
+    failure_reports: string[];
+    failure_options: ("0" | "1" | "d" | "s")[];
+    report_format: string[];
+    report_interval: number;
+} | {
+    kind: "dkim";
+    key_type: "rsa" | "ed25519";
+    public_key: string;
+    revoked: boolean;
+    hash_algorithms: string[] | null;
+    service_types: string[];
+    flags: string[];
+    notes: string | null;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The term at fault: an SPF mechanism or modifier as written, or a DMARC or DKIM tag's name */
+    term?: string;
+    /** Concerns that don't make the value invalid */
+    warnings?: "too_many_lookups"[];
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "unterminated_quote" | "text_after_quote" | "not_an_auth_record" | "wrong_record_kind" | "invalid_mechanism" | "invalid_modifier" | "duplicate_modifier" | "invalid_domain" | "invalid_network" | "malformed_pair" | "missing_tag" | "duplicate_tag" | "invalid_tag" | "invalid_url" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,506 @@
+// ABOUTME: WASM component for email address validation
+// ABOUTME: Provides validate and coerce functions applying practical RFC 5321/5322 address rules
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Require the domain to end in a top-level domain rather than being a bare host name
+    require_tld: bool,
+    // Accept a display name form such as "Jane Doe <jane@example.com>"
+    allow_display_name: bool,
+    // Accept non-ASCII local parts and domains (RFC 6531 internationalized addresses)
+    allow_international: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            require_tld: true,
+            allow_display_name: false,
+            allow_international: false,
+        }
+    }
+}
+
+// RFC 5321 limits, in octets
+const MAX_ADDRESS_LENGTH: usize = 254;
+const MAX_LOCAL_LENGTH: usize = 64;
+const MAX_DOMAIN_LENGTH: usize = 253;
+const MAX_LABEL_LENGTH: usize = 63;
+
+// RFC 5322 atext, extended to non-ASCII characters for internationalized addresses
+fn is_atext(c: char, options: &ValidationOptions) -> bool {
+    c.is_ascii_alphanumeric()
+        || "!#$%&'*+-/=?^_`{|}~".contains(c)
+        || (options.allow_international && !c.is_ascii() && !c.is_control() && !c.is_whitespace())
+}
+
+// A dot-atom: atext runs separated by single dots
+fn is_dot_atom(text: &str, options: &ValidationOptions) -> bool {
+    text.split('.').all(|atom| !atom.is_empty() && atom.chars().all(|c| is_atext(c, options)))
+}
+
+// A quoted string: printable characters and spaces, with backslash escaping quotes and backslashes
+fn is_quoted_string(text: &str, options: &ValidationOptions) -> bool {
+    let Some(inner) = text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) else {
+        return false;
+    };
+
+    let printable = |c: char| {
+        c == ' ' || c.is_ascii_graphic() || (options.allow_international && !c.is_ascii() && !c.is_control())
+    };
+
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if printable(escaped) => {},
+                _ => return false,
+            },
+            '"' => return false,
+            c if printable(c) => {},
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn is_local_part(text: &str, options: &ValidationOptions) -> bool {
+    text.len() <= MAX_LOCAL_LENGTH && (is_dot_atom(text, options) || is_quoted_string(text, options))
+}
+
+fn is_label(label: &str, options: &ValidationOptions) -> bool {
+    !label.is_empty()
+        && label.len() <= MAX_LABEL_LENGTH
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| {
+            c.is_ascii_alphanumeric() || c == '-' || (options.allow_international && c.is_alphanumeric())
+        })
+}
+
+// A host name, or an address literal such as [192.0.2.1] or [IPv6:2001:db8::1]
+fn is_domain(text: &str, options: &ValidationOptions) -> bool {
+    if let Some(literal) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return match literal.strip_prefix("IPv6:") {
+            Some(ipv6) => ipv6.parse::<Ipv6Addr>().is_ok(),
+            None => literal.parse::<Ipv4Addr>().is_ok(),
+        };
+    }
+
+    if text.len() > MAX_DOMAIN_LENGTH || !text.split('.').all(|label| is_label(label, options)) {
+        return false;
+    }
+
+    // A top-level domain is never all digits, which also keeps dotted IPv4 addresses out
+    match text.rsplit_once('.') {
+        Some((_, tld)) => !tld.chars().all(|c| c.is_ascii_digit()),
+        None => !options.require_tld,
+    }
+}
+
+// Strips a display name, leaving the bracketed address: `Jane Doe <jane@example.com>`
+// The name is either a quoted string or a run of words
+fn strip_display_name<'a>(text: &'a str, options: &ValidationOptions) -> Option<&'a str> {
+    let (name, rest) = text.split_once('<')?;
+    let address = rest.strip_suffix('>')?;
+    let name = name.trim();
+
+    let valid_name = name.is_empty()
+        || is_quoted_string(name, options)
+        || name.split_whitespace().all(|word| word.chars().all(|c| c == '.' || is_atext(c, options)));
+
+    valid_name.then_some(address)
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the bare address with its domain lowercased; the local part is case-sensitive and kept as written
+fn coerce_email_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let text = text.trim();
+
+    let address = if options.allow_display_name && text.ends_with('>') {
+        strip_display_name(text, options)?
+    } else {
+        text
+    };
+
+    if address.len() > MAX_ADDRESS_LENGTH || (!options.allow_international && !address.is_ascii()) {
+        return None;
+    }
+
+    // The domain never contains '@', but a quoted local part may
+    let (local, domain) = address.rsplit_once('@')?;
+    if !is_local_part(local, options) || !is_domain(domain, options) {
+        return None;
+    }
+
+    Some(Value::String(format!("{}@{}", local, domain.to_lowercase())))
+}
+
+// Only strings can be valid; the fast path calls coerce_email_str directly
+fn coerce_email(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_email_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_email(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_email(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_email(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_email(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_email(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_email(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_email(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_email(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_email(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_email_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_addresses() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_email(&json!("user@example.com"), &defaults));
+        assert!(validate_email(&json!("first.last+tag@sub.example.co.uk"), &defaults));
+        assert!(validate_email(&json!("x@example.io"), &defaults));
+        assert!(validate_email(&json!("o'brien@example.ie"), &defaults));
+        assert!(validate_email(&json!("\"john doe\"@example.com"), &defaults));
+        assert!(validate_email(&json!("\"a\\\"b\"@example.com"), &defaults));
+        assert!(validate_email(&json!("user@[192.0.2.1]"), &defaults));
+        assert!(validate_email(&json!("user@[IPv6:2001:db8::1]"), &defaults));
+        assert!(validate_email(&json!("  user@example.com  "), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_addresses() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_email(&json!(""), &defaults));
+        assert!(!validate_email(&json!("plainaddress"), &defaults));
+        assert!(!validate_email(&json!("@example.com"), &defaults));
+        assert!(!validate_email(&json!("user@"), &defaults));
+        assert!(!validate_email(&json!("user@@example.com"), &defaults));
+        assert!(!validate_email(&json!(".user@example.com"), &defaults));
+        assert!(!validate_email(&json!("user.@example.com"), &defaults));
+        assert!(!validate_email(&json!("us..er@example.com"), &defaults));
+        assert!(!validate_email(&json!("us er@example.com"), &defaults));
+        assert!(!validate_email(&json!("user@-example.com"), &defaults));
+        assert!(!validate_email(&json!("user@example..com"), &defaults));
+        assert!(!validate_email(&json!("user@example.123"), &defaults));
+        assert!(!validate_email(&json!("user@[300.0.0.1]"), &defaults));
+        assert!(!validate_email(&json!("\"unterminated@example.com"), &defaults));
+        assert!(!validate_email(&json!(42), &defaults));
+        assert!(!validate_email(&json!(null), &defaults));
+    }
+
+    #[test]
+    fn test_length_limits() {
+        let defaults = ValidationOptions::default();
+        let local = "a".repeat(64);
+        let label = "b".repeat(63);
+
+        assert!(validate_email(&json!(format!("{}@example.com", local)), &defaults));
+        assert!(!validate_email(&json!(format!("a{}@example.com", local)), &defaults));
+        assert!(validate_email(&json!(format!("user@{}.com", label)), &defaults));
+        assert!(!validate_email(&json!(format!("user@b{}.com", label)), &defaults));
+
+        // 64 + 1 + 191 octets is over the 254 octet path limit
+        let domain = format!("{}.{}.{}.com", label, label, "c".repeat(59));
+        assert!(!validate_email(&json!(format!("{}@{}", local, domain)), &defaults));
+    }
+
+    #[test]
+    fn test_require_tld() {
+        let defaults = ValidationOptions::default();
+        let no_tld = options(json!({"require_tld": false}));
+
+        assert!(!validate_email(&json!("root@localhost"), &defaults));
+        assert!(validate_email(&json!("root@localhost"), &no_tld));
+        assert!(validate_email(&json!("user@example.com"), &no_tld));
+        assert!(!validate_email(&json!("user@1.2.3.4"), &no_tld));
+    }
+
+    #[test]
+    fn test_display_names() {
+        let defaults = ValidationOptions::default();
+        let display = options(json!({"allow_display_name": true}));
+
+        assert!(!validate_email(&json!("Jane Doe <jane@example.com>"), &defaults));
+        assert!(validate_email(&json!("Jane Doe <jane@example.com>"), &display));
+        assert!(validate_email(&json!("\"Doe, Jane\" <jane@example.com>"), &display));
+        assert!(validate_email(&json!("J. R. Doe <jane@example.com>"), &display));
+        assert!(validate_email(&json!("<jane@example.com>"), &display));
+        assert!(validate_email(&json!("jane@example.com"), &display));
+
+        assert!(!validate_email(&json!("Doe, Jane <jane@example.com>"), &display));
+        assert!(!validate_email(&json!("Jane <jane@example.com"), &display));
+        assert!(!validate_email(&json!("Jane <not an address>"), &display));
+    }
+
+    #[test]
+    fn test_international() {
+        let defaults = ValidationOptions::default();
+        let eai = options(json!({"allow_international": true}));
+
+        assert!(!validate_email(&json!("用户@例子.广告"), &defaults));
+        assert!(validate_email(&json!("用户@例子.广告"), &eai));
+        assert!(validate_email(&json!("jörg@münchen.de"), &eai));
+        assert!(validate_email(&json!("user@example.com"), &eai));
+        assert!(!validate_email(&json!("us\u{0007}er@example.com"), &eai));
+
+        // Limits are in octets, so 22 three-byte characters exceed 64
+        assert!(!validate_email(&json!(format!("{}@example.com", "用".repeat(22))), &eai));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+        let display = options(json!({"allow_display_name": true}));
+
+        assert_eq!(coerce_email(&json!(" User@Example.COM "), &defaults), Some(json!("User@example.com")));
+        assert_eq!(coerce_email(&json!("Jane <jane@Example.com>"), &display), Some(json!("jane@example.com")));
+        assert_eq!(coerce_email(&json!("not an email"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a@example.com"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a@example.com", "options": {"require_tld": false}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a@example.com", "options": {"strict": true}})).is_err());
+
+        // Omitted options keep their defaults
+        let partial = options(json!({"allow_display_name": true}));
+        assert!(partial.require_tld);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_email(&json!("a@EXAMPLE.com"), &defaults)),
+            ValidationResult { valid: true, value: json!("a@example.com") }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_email(&json!("nope"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["user@example.com", "User@Example.COM", "not-an-email", "a@"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,843 @@
+// ABOUTME: WASM component for raw email header fields, for email template editors
+// ABOUTME: Checks folding, encoded words and the syntax of address and message identifier fields (RFC 5322, RFC 2047)
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Header names to accept, ignoring case, such as ["From", "Reply-To"]; any when unset
+    allowed_names: Option<Vec<String>>,
+    // Accept UTF-8 in header values and addresses (RFC 6532), as SMTPUTF8 mail allows; otherwise
+    // non-ASCII text must be in encoded words
+    allow_utf8: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The physical line a folding problem was found on, counting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, line: None }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from((code, line): Problem) -> Self {
+        ValidationResult { error: Some(code), line, ..ValidationResult::from(None) }
+    }
+}
+
+// An error code, and the physical line it was found on when it's about one
+type Problem = (&'static str, Option<usize>);
+
+// Error codes reported in detailed results
+const MALFORMED_HEADER: &str = "malformed_header";
+const INVALID_HEADER_NAME: &str = "invalid_header_name";
+const DISALLOWED_HEADER: &str = "disallowed_header";
+const INVALID_FOLDING: &str = "invalid_folding";
+const HEADER_LINE_TOO_LONG: &str = "header_line_too_long";
+const CONTROL_CHARACTER: &str = "control_character";
+const INVALID_CHARACTER: &str = "invalid_character";
+const UNTERMINATED_QUOTE: &str = "unterminated_quote";
+const UNBALANCED_COMMENT: &str = "unbalanced_comment";
+const INVALID_ADDRESS: &str = "invalid_address";
+const INVALID_MESSAGE_ID: &str = "invalid_message_id";
+const INVALID_ENCODED_WORD: &str = "invalid_encoded_word";
+const UNSUPPORTED_CHARSET: &str = "unsupported_charset";
+
+// The longest line a message may have, without its CRLF (RFC 5322 section 2.1.1), and the
+// longest encoded word (RFC 2047 section 2)
+const MAX_LINE_LENGTH: usize = 998;
+const MAX_ENCODED_WORD_LENGTH: usize = 75;
+
+// What a header field holds
+#[derive(Clone, Copy, PartialEq)]
+enum Syntax {
+    // One mailbox, or a list of them
+    Mailbox,
+    Mailboxes,
+    // A list of mailboxes and groups
+    Addresses,
+    // One message identifier, or a list of them
+    MessageId,
+    MessageIds,
+}
+
+// The header fields that hold addresses or message identifiers, spelled as RFC 5322 does;
+// other fields are unstructured text
+const FIELDS: &[(&str, Syntax)] = &[
+    ("From", Syntax::Mailboxes),
+    ("Sender", Syntax::Mailbox),
+    ("Reply-To", Syntax::Addresses),
+    ("To", Syntax::Addresses),
+    ("Cc", Syntax::Addresses),
+    ("Bcc", Syntax::Addresses),
+    ("Message-ID", Syntax::MessageId),
+    ("In-Reply-To", Syntax::MessageIds),
+    ("References", Syntax::MessageIds),
+];
+
+// RFC 5322 atext, and any non-ASCII character, which only gets this far when UTF-8 is allowed
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c) || !c.is_ascii()
+}
+
+// A dot-atom: atext runs separated by single dots
+fn is_dot_atom(text: &str) -> bool {
+    text.split('.').all(|atom| !atom.is_empty() && atom.chars().all(is_atext))
+}
+
+// The header's value with its folding undone, after checking that each continuation line
+// starts with whitespace and that no line is too long. A single line break may end the header
+fn unfold(text: &str) -> Result<String, Problem> {
+    let text = text.strip_suffix('\n').map_or(text, |text| text.strip_suffix('\r').unwrap_or(text));
+    let mut unfolded = String::with_capacity(text.len());
+    for (index, line) in text.split('\n').enumerate() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.chars().count() > MAX_LINE_LENGTH {
+            return Err((HEADER_LINE_TOO_LONG, Some(index + 1)));
+        }
+        // A continuation line starts with whitespace, and isn't only whitespace
+        if index > 0 && (!line.starts_with([' ', '\t']) || line.trim().is_empty()) {
+            return Err((INVALID_FOLDING, Some(index + 1)));
+        }
+        unfolded.push_str(line);
+    }
+    Ok(unfolded)
+}
+
+// The value with its comments, "(...)", which may nest and escape characters with "\",
+// replaced by a space; quoted strings are kept as they are
+fn strip_comments(value: &str) -> Result<String, &'static str> {
+    let mut stripped = String::with_capacity(value.len());
+    let (mut depth, mut quoted, mut escaped) = (0usize, false, false);
+    for c in value.chars() {
+        if escaped {
+            escaped = false;
+            if depth == 0 {
+                stripped.push(c);
+            }
+            continue;
+        }
+        match c {
+            '\\' if quoted || depth > 0 => {
+                escaped = true;
+                if depth == 0 {
+                    stripped.push(c);
+                }
+            }
+            '"' if depth == 0 => {
+                quoted = !quoted;
+                stripped.push(c);
+            }
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => {
+                depth = depth.checked_sub(1).ok_or(UNBALANCED_COMMENT)?;
+                if depth == 0 {
+                    stripped.push(' ');
+                }
+            }
+            _ if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+    match (quoted, depth) {
+        (true, _) => Err(UNTERMINATED_QUOTE),
+        (false, 0) => Ok(stripped),
+        _ => Err(UNBALANCED_COMMENT),
+    }
+}
+
+// Base64 without line breaks, with its padding
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(4) {
+        return None;
+    }
+    let data = text.strip_suffix("==").or_else(|| text.strip_suffix('=')).unwrap_or(text);
+    let mut bytes = Vec::with_capacity(data.len() / 4 * 3);
+    let (mut bits, mut count) = (0u32, 0);
+    for b in data.bytes() {
+        let value = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        bits = (bits << 6 | u32::from(value)) & 0xffff;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            bytes.push((bits >> count) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+// The Q encoding: visible ASCII as itself, "=" and two hex digits for an octet, and "_" for
+// a space
+fn q_decode(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        match b {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex = rest.get(..2).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+                bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+                rest = &rest[2..];
+            }
+            b'!'..=b'~' => bytes.push(b),
+            _ => return None,
+        }
+    }
+    Some(bytes)
+}
+
+// Whether the word looks like an encoded word, and so must be one
+fn is_encoded_word(word: &str) -> bool {
+    word.len() > 4 && word.starts_with("=?") && word.ends_with("?=")
+}
+
+// Decodes an encoded word (RFC 2047), "=?charset?encoding?text?=", where the encoding is B for
+// base64 or Q, and the charset UTF-8, ISO-8859-1 or US-ASCII, perhaps with a language after
+// "*" (RFC 2231)
+fn decode_encoded_word(word: &str) -> Result<String, &'static str> {
+    let inner = &word[2..word.len() - 2];
+    let mut parts = inner.splitn(3, '?');
+    let (Some(charset), Some(encoding), Some(encoded)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(INVALID_ENCODED_WORD);
+    };
+    let charset = charset.split('*').next().unwrap_or_default();
+    if word.len() > MAX_ENCODED_WORD_LENGTH || charset.is_empty() || encoded.is_empty() || encoded.contains('?') {
+        return Err(INVALID_ENCODED_WORD);
+    }
+    let bytes = match encoding {
+        "B" | "b" => base64_decode(encoded),
+        "Q" | "q" => q_decode(encoded),
+        _ => None,
+    };
+    let bytes = bytes.ok_or(INVALID_ENCODED_WORD)?;
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).map_err(|_| INVALID_ENCODED_WORD)
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") || charset.eq_ignore_ascii_case("us-ascii") && bytes.is_ascii() {
+        Ok(bytes.into_iter().map(char::from).collect())
+    } else if charset.eq_ignore_ascii_case("us-ascii") {
+        Err(INVALID_ENCODED_WORD)
+    } else {
+        Err(UNSUPPORTED_CHARSET)
+    }
+}
+
+// Unstructured text, such as a subject, with its encoded words decoded. An encoded word is a
+// whole word, and the whitespace between two of them is dropped
+fn unstructured(value: &str) -> Result<String, &'static str> {
+    let mut text = String::with_capacity(value.len());
+    let mut rest = value.trim_matches([' ', '\t']);
+    let (mut space, mut previous_encoded) = ("", false);
+    while !rest.is_empty() {
+        let end = rest.find([' ', '\t']).unwrap_or(rest.len());
+        let word = &rest[..end];
+        let encoded = is_encoded_word(word);
+        if !(encoded && previous_encoded) {
+            text.push_str(space);
+        }
+        if encoded {
+            text.push_str(&decode_encoded_word(word)?);
+        } else {
+            text.push_str(word);
+        }
+        previous_encoded = encoded;
+        let after = &rest[end..];
+        rest = after.trim_start_matches([' ', '\t']);
+        space = &after[..after.len() - rest.len()];
+    }
+    Ok(text)
+}
+
+// A display name or group name: atoms, which may hold dots, quoted strings and encoded words,
+// decoded and joined by single spaces, except that encoded words next to each other are
+// joined without one. None when it's blank
+fn phrase(text: &str) -> Result<Option<String>, &'static str> {
+    let mut words: Vec<(String, bool)> = Vec::new();
+    let mut rest = text.trim_start();
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let mut word = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = None;
+            while let Some((index, c)) = chars.next() {
+                match c {
+                    '\\' => word.extend(chars.next().map(|(_, c)| c)),
+                    '"' => {
+                        end = Some(index);
+                        break;
+                    }
+                    c => word.push(c),
+                }
+            }
+            let end = end.ok_or(UNTERMINATED_QUOTE)?;
+            words.push((word, false));
+            rest = &quoted[end + 1..];
+        } else {
+            let end = rest.find(|c: char| c.is_whitespace() || c == '"').unwrap_or(rest.len());
+            let word = &rest[..end];
+            if is_encoded_word(word) {
+                words.push((decode_encoded_word(word)?, true));
+            } else if word.chars().all(|c| c == '.' || is_atext(c)) {
+                words.push((word.to_string(), false));
+            } else {
+                return Err(INVALID_ADDRESS);
+            }
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start();
+    }
+
+    let mut name = String::new();
+    for (index, (word, encoded)) in words.iter().enumerate() {
+        if index > 0 && !(*encoded && words[index - 1].1) {
+            name.push(' ');
+        }
+        name.push_str(word);
+    }
+    Ok((!words.is_empty()).then_some(name))
+}
+
+// A mailbox: an address, or a display name and the address in angle brackets, such as
+// "Jane Doe <jane@example.com>". The address is checked, and canonicalized, by the email
+// validator
+fn mailbox(text: &str, options: &ValidationOptions) -> Result<Value, &'static str> {
+    let text = text.trim();
+    let (name, address) = match text.strip_suffix('>').and_then(|text| text.rsplit_once('<')) {
+        Some((name, address)) => (phrase(name)?, address.trim()),
+        None => (None, text),
+    };
+    let email_options = json!({ "allow_international": options.allow_utf8 });
+    let address = email_validator::coerce_value(&Value::from(address), &email_options).ok_or(INVALID_ADDRESS)?;
+    Ok(json!({ "name": name, "address": address }))
+}
+
+// Splits an address list at its commas, and at the colons and semicolons that start and end
+// groups, outside quoted strings, angle brackets and domain literals; each part comes with the
+// separator that ends it, or None for the last
+fn split_list(text: &str) -> Vec<(&str, Option<char>)> {
+    let mut parts = Vec::new();
+    let (mut start, mut quoted, mut escaped, mut nested) = (0, false, false, false);
+    for (index, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '<' | '[' if !quoted => nested = true,
+            '>' | ']' if !quoted => nested = false,
+            ',' | ':' | ';' if !quoted && !nested => {
+                parts.push((&text[start..index], Some(c)));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push((&text[start..], None));
+    parts
+}
+
+// An address list (RFC 5322 section 3.4): mailboxes and groups, "name: mailbox, ...;", which
+// may be empty, separated by commas. Returns the mailboxes, with those of groups, and whether
+// there were any groups
+fn address_list(text: &str, options: &ValidationOptions) -> Result<(Vec<Value>, bool), &'static str> {
+    let mut mailboxes = Vec::new();
+    let (mut group, mut grouped, mut closed) = (false, false, false);
+    for (part, separator) in split_list(text) {
+        // Only a comma, or the end, can follow a group
+        if closed {
+            if !part.trim().is_empty() || !matches!(separator, Some(',') | None) {
+                return Err(INVALID_ADDRESS);
+            }
+            closed = false;
+            continue;
+        }
+        match separator {
+            Some(':') if !group => {
+                if phrase(part)?.is_none() {
+                    return Err(INVALID_ADDRESS);
+                }
+                (group, grouped) = (true, true);
+            }
+            Some(';') if group => {
+                if !part.trim().is_empty() {
+                    mailboxes.push(mailbox(part, options)?);
+                }
+                (group, closed) = (false, true);
+            }
+            Some(',') | None => mailboxes.push(mailbox(part, options)?),
+            _ => return Err(INVALID_ADDRESS),
+        }
+    }
+    if group {
+        return Err(INVALID_ADDRESS);
+    }
+    Ok((mailboxes, grouped))
+}
+
+// Message identifiers (RFC 5322 section 3.6.4), "<left@right>", perhaps separated by
+// whitespace. The left is a dot-atom, and the right a dot-atom or a domain literal in brackets
+fn message_ids(value: &str) -> Result<Vec<String>, &'static str> {
+    let mut ids = Vec::new();
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        let (id, tail) = rest.strip_prefix('<').and_then(|rest| rest.split_once('>')).ok_or(INVALID_MESSAGE_ID)?;
+        let (left, right) = id.split_once('@').ok_or(INVALID_MESSAGE_ID)?;
+        let is_literal = |text: &str| {
+            text.strip_prefix('[')
+                .and_then(|text| text.strip_suffix(']'))
+                .is_some_and(|literal| literal.bytes().all(|b| b.is_ascii_graphic() && !b"[]\\".contains(&b)))
+        };
+        if !is_dot_atom(left) || !(is_dot_atom(right) || is_literal(right)) {
+            return Err(INVALID_MESSAGE_ID);
+        }
+        ids.push(format!("<{id}>"));
+        rest = tail.trim_start();
+    }
+    Ok(ids)
+}
+
+// Checks a header field: a name, a colon and a value, perhaps folded over several lines.
+// Returns its name, spelled as RFC 5322 does for the fields it parses, and its addresses,
+// message identifiers or decoded text
+fn check_header(text: &str, options: &ValidationOptions) -> Result<Value, Problem> {
+    let unfolded = unfold(text)?;
+    let (name, value) = unfolded.split_once(':').ok_or((MALFORMED_HEADER, None))?;
+    if name.is_empty() || !name.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err((INVALID_HEADER_NAME, None));
+    }
+    let allowed = |names: &Vec<String>| names.iter().any(|allowed| allowed.eq_ignore_ascii_case(name));
+    if !options.allowed_names.as_ref().is_none_or(allowed) {
+        return Err((DISALLOWED_HEADER, None));
+    }
+    if value.chars().any(|c| c.is_control() && c != '\t') {
+        return Err((CONTROL_CHARACTER, None));
+    }
+    if !options.allow_utf8 && !value.is_ascii() {
+        return Err((INVALID_CHARACTER, None));
+    }
+
+    let field = FIELDS.iter().find(|(field, _)| field.eq_ignore_ascii_case(name));
+    let summary = match field {
+        None => json!({ "name": name, "text": unstructured(value).map_err(|code| (code, None))? }),
+        Some(&(field, syntax)) => {
+            let value = strip_comments(value).map_err(|code| (code, None))?;
+            match syntax {
+                Syntax::MessageId | Syntax::MessageIds => {
+                    let ids = message_ids(&value).map_err(|code| (code, None))?;
+                    if ids.is_empty() || syntax == Syntax::MessageId && ids.len() > 1 {
+                        return Err((INVALID_MESSAGE_ID, None));
+                    }
+                    json!({ "name": field, "message_ids": ids })
+                }
+                // Bcc may be empty, so as not to say who else got the message
+                _ if field == "Bcc" && value.trim().is_empty() => json!({ "name": field, "addresses": [] }),
+                _ => {
+                    let (addresses, grouped) = address_list(&value, options).map_err(|code| (code, None))?;
+                    let single = syntax == Syntax::Mailbox && addresses.len() != 1;
+                    if single || grouped && syntax != Syntax::Addresses {
+                        return Err((INVALID_ADDRESS, None));
+                    }
+                    json!({ "name": field, "addresses": addresses })
+                }
+            }
+        }
+    };
+    Ok(summary)
+}
+
+fn detail_header_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no header, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_header(text, options) {
+        Ok(summary) => ValidationResult::from(Some(summary)),
+        Err(problem) => ValidationResult::from(problem),
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_header_str directly
+fn detail_header(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_header_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the header's name and its parsed value
+fn coerce_header(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_header(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_header(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_header(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_header(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_header(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_header(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_header(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_header(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_header(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_header(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_header_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(text: &str) -> (Option<&'static str>, Option<usize>) {
+        let result = detail_header_str(text, &ValidationOptions::default());
+        (result.error, result.line)
+    }
+
+    fn error(text: &str) -> Option<&'static str> {
+        problem(text).0
+    }
+
+    #[test]
+    fn test_addresses() {
+        assert_eq!(
+            coerce_header(&json!("from: \"Doe, Jane\" <jane@Example.COM>, John Q. Public <john@example.com> (work)\r\n"), &ValidationOptions::default()),
+            Some(json!({
+                "name": "From",
+                "addresses": [
+                    {"name": "Doe, Jane", "address": "jane@example.com"},
+                    {"name": "John Q. Public", "address": "john@example.com"},
+                ],
+            }))
+        );
+        assert_eq!(
+            coerce_header(&json!("Reply-To: support@example.com"), &ValidationOptions::default()).unwrap()["addresses"],
+            json!([{"name": null, "address": "support@example.com"}])
+        );
+        let groups = coerce_header(&json!("To: Team: a@example.com, <b@example.com>;, undisclosed-recipients:;, c@example.com"), &ValidationOptions::default());
+        assert_eq!(groups.unwrap()["addresses"].as_array().map(Vec::len), Some(3));
+        assert_eq!(coerce_header(&json!("Bcc:"), &ValidationOptions::default()).unwrap()["addresses"], json!([]));
+
+        assert_eq!(error("From: Jane Doe <jane@localhost>"), Some(INVALID_ADDRESS));
+        assert_eq!(error("From: Jane Doe jane@example.com"), Some(INVALID_ADDRESS));
+        assert_eq!(error("From: Jane@Doe <jane@example.com>"), Some(INVALID_ADDRESS));
+        assert_eq!(error("From: a@example.com,"), Some(INVALID_ADDRESS));
+        assert_eq!(error("From: Team: a@example.com;"), Some(INVALID_ADDRESS));
+        assert_eq!(error("Sender: a@example.com, b@example.com"), Some(INVALID_ADDRESS));
+        assert_eq!(error("To: Team: a@example.com"), Some(INVALID_ADDRESS));
+        assert_eq!(error("To: Team: a@example.com; b@example.com"), Some(INVALID_ADDRESS));
+        assert_eq!(error("Reply-To:"), Some(INVALID_ADDRESS));
+        assert_eq!(error("From: \"Jane <jane@example.com>"), Some(UNTERMINATED_QUOTE));
+        assert_eq!(error("From: jane@example.com (Jane"), Some(UNBALANCED_COMMENT));
+        assert_eq!(error("From: jane@example.com Jane)"), Some(UNBALANCED_COMMENT));
+    }
+
+    #[test]
+    fn test_encoded_words() {
+        let from = "From: =?UTF-8?B?Sm9zw6k=?= =?utf-8?q?_Garc=C3=ADa?= <jose@example.com>";
+        assert_eq!(coerce_header(&json!(from), &ValidationOptions::default()).unwrap()["addresses"][0]["name"], "José García");
+        let subject = "Subject: Re: =?ISO-8859-1?Q?Caf=E9?= =?UTF-8*fr?B?IMOgIDE1aA==?=  tomorrow";
+        assert_eq!(
+            coerce_header(&json!(subject), &ValidationOptions::default()),
+            Some(json!({"name": "Subject", "text": "Re: Café à 15h  tomorrow"}))
+        );
+        // Encoded words are whole words; anything else is left as it is
+        assert_eq!(coerce_header(&json!("Subject: a=?UTF-8?Q?b?="), &ValidationOptions::default()).unwrap()["text"], "a=?UTF-8?Q?b?=");
+
+        assert_eq!(error("Subject: =?UTF-8?B?Sm9zw6k?="), Some(INVALID_ENCODED_WORD));
+        assert_eq!(error("Subject: =?UTF-8?Q?caf=E?="), Some(INVALID_ENCODED_WORD));
+        assert_eq!(error("Subject: =?UTF-8?X?abc?="), Some(INVALID_ENCODED_WORD));
+        assert_eq!(error("Subject: =?UTF-8?Q?=E9?="), Some(INVALID_ENCODED_WORD));
+        assert_eq!(error("Subject: =?US-ASCII?Q?=E9?="), Some(INVALID_ENCODED_WORD));
+        assert_eq!(error(&format!("Subject: =?UTF-8?Q?{}?=", "a".repeat(64))), Some(INVALID_ENCODED_WORD));
+        assert_eq!(error("Subject: =?Shift_JIS?B?gqA=?="), Some(UNSUPPORTED_CHARSET));
+    }
+
+    #[test]
+    fn test_message_ids() {
+        assert_eq!(
+            coerce_header(&json!("Message-Id: <20240304.1234@mail.example.com>"), &ValidationOptions::default()),
+            Some(json!({"name": "Message-ID", "message_ids": ["<20240304.1234@mail.example.com>"]}))
+        );
+        let references = coerce_header(&json!("References: <a.1@example.com>\r\n <b@[192.0.2.1]><c@example.com> (thread)"), &ValidationOptions::default());
+        assert_eq!(references.unwrap()["message_ids"], json!(["<a.1@example.com>", "<b@[192.0.2.1]>", "<c@example.com>"]));
+
+        assert_eq!(error("Message-ID: 1234@example.com"), Some(INVALID_MESSAGE_ID));
+        assert_eq!(error("Message-ID: <1234>"), Some(INVALID_MESSAGE_ID));
+        assert_eq!(error("Message-ID: <a..b@example.com>"), Some(INVALID_MESSAGE_ID));
+        assert_eq!(error("Message-ID: <a@example.com> <b@example.com>"), Some(INVALID_MESSAGE_ID));
+        assert_eq!(error("In-Reply-To: "), Some(INVALID_MESSAGE_ID));
+        assert_eq!(error("In-Reply-To: <a@example.com"), Some(INVALID_MESSAGE_ID));
+    }
+
+    #[test]
+    fn test_lines() {
+        // Folded lines start with whitespace, and no line may be longer than 998 characters
+        assert!(validate_header(&json!("To: a@example.com,\r\n\tb@example.com,\n  c@example.com"), &ValidationOptions::default()));
+        assert_eq!(problem("To: a@example.com,\r\nb@example.com"), (Some(INVALID_FOLDING), Some(2)));
+        assert_eq!(problem("Subject: a\r\n \r\n b"), (Some(INVALID_FOLDING), Some(2)));
+        assert_eq!(problem("Subject: a\r\n\r\n"), (Some(INVALID_FOLDING), Some(2)));
+        let long = format!("Subject: {}", "a".repeat(MAX_LINE_LENGTH));
+        assert_eq!(problem(&format!("Subject: a\r\n {long}")), (Some(HEADER_LINE_TOO_LONG), Some(2)));
+        assert!(validate_header(&json!(&long[..MAX_LINE_LENGTH]), &ValidationOptions::default()));
+
+        assert_eq!(problem("Subject"), (Some(MALFORMED_HEADER), None));
+        assert_eq!(error("Subject : hello"), Some(INVALID_HEADER_NAME));
+        assert_eq!(error(": hello"), Some(INVALID_HEADER_NAME));
+        assert_eq!(error("Subject: a\u{0}b"), Some(CONTROL_CHARACTER));
+        assert_eq!(error("Subject: a\rb"), Some(CONTROL_CHARACTER));
+    }
+
+    #[test]
+    fn test_options() {
+        let names: ValidationOptions = serde_json::from_value(json!({"allowed_names": ["from", "Reply-To"]})).unwrap();
+        assert!(validate_header(&json!("From: jane@example.com"), &names));
+        assert_eq!(detail_header_str("Subject: hello", &names).error, Some(DISALLOWED_HEADER));
+
+        let utf8: ValidationOptions = serde_json::from_value(json!({"allow_utf8": true})).unwrap();
+        assert_eq!(error("Subject: Café"), Some(INVALID_CHARACTER));
+        assert_eq!(coerce_header(&json!("Subject: Café"), &utf8).unwrap()["text"], "Café");
+        assert_eq!(error("From: José <josé@example.com>"), Some(INVALID_CHARACTER));
+        assert_eq!(coerce_header(&json!("From: José <josé@example.com>"), &utf8).unwrap()["addresses"][0]["address"], "josé@example.com");
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"allowed_headers": ["From"]})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        assert_eq!(
+            messages::localize(&detail_header_str("To: a@example.com\nb@example.com", &ValidationOptions::default()), &json!(""), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "invalid_folding",
+                "line": 2,
+                "message": messages::message("invalid_folding", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_header_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+        assert!(!validate_header(&json!(42), &ValidationOptions::default()));
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["From: a@example.com", "To: b@example.com"], "options": {"allowed_names": ["To"]}})).unwrap();
+        let valid: Vec<bool> = batch.values.iter().map(|value| validate_header(value, &batch.options)).collect();
+        assert_eq!(valid, vec![false, true]);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in ["From: a@example.com", "Subject: =?UTF-8?Q?hi?=", "", "Message-ID: 1", "no colon"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// Types for the header validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Header names to accept, ignoring case, such as ["From", "Reply-To"]; any when unset */
+    allowed_names?: string[];
+    /** Accept UTF-8 in header values and addresses (RFC 6532); otherwise non-ASCII text must be in encoded words (default: false) */
+    allow_utf8?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    name: "From" | "Sender" | "Reply-To" | "To" | "Cc" | "Bcc";
+    addresses: ({
+        name: string | null;
+        address: string;
+    })[];
+} | {
+    name: "Message-ID" | "In-Reply-To" | "References";
+    message_ids: string[];
+} | {
+    name: string;
+    text: string;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The physical line a folding problem was found on, counting from 1 */
+    line?: number;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "malformed_header" | "invalid_header_name" | "disallowed_header" | "invalid_folding" | "header_line_too_long" | "control_character" | "invalid_character" | "unterminated_quote" | "unbalanced_comment" | "invalid_address" | "invalid_message_id" | "invalid_encoded_word" | "unsupported_charset" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
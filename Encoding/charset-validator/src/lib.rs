@@ -0,0 +1,479 @@
+// ABOUTME: WASM component for character encoding labels, for import tools that ask for a file's encoding
+// ABOUTME: Reads a label through the WHATWG Encoding Standard's label table, as browsers and TextDecoder do
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Only accept labels for UTF-8
+    require_utf8: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const UNKNOWN_ENCODING: &str = "unknown_encoding";
+const UNSUPPORTED_ENCODING: &str = "unsupported_encoding";
+const NOT_UTF8: &str = "not_utf8";
+
+// Each encoding's name, in lowercase as TextDecoder reports it, with its labels from the
+// WHATWG Encoding Standard (https://encoding.spec.whatwg.org/#names-and-labels)
+const ENCODINGS: &[(&str, &[&str])] = &[
+    ("utf-8", &["unicode-1-1-utf-8", "unicode11utf8", "unicode20utf8", "utf-8", "utf8", "x-unicode20utf8"]),
+    ("ibm866", &["866", "cp866", "csibm866", "ibm866"]),
+    (
+        "iso-8859-2",
+        &[
+            "csisolatin2", "iso-8859-2", "iso-ir-101", "iso8859-2", "iso88592", "iso_8859-2", "iso_8859-2:1987",
+            "l2", "latin2",
+        ],
+    ),
+    (
+        "iso-8859-3",
+        &[
+            "csisolatin3", "iso-8859-3", "iso-ir-109", "iso8859-3", "iso88593", "iso_8859-3", "iso_8859-3:1988",
+            "l3", "latin3",
+        ],
+    ),
+    (
+        "iso-8859-4",
+        &[
+            "csisolatin4", "iso-8859-4", "iso-ir-110", "iso8859-4", "iso88594", "iso_8859-4", "iso_8859-4:1988",
+            "l4", "latin4",
+        ],
+    ),
+    (
+        "iso-8859-5",
+        &[
+            "csisolatincyrillic", "cyrillic", "iso-8859-5", "iso-ir-144", "iso8859-5", "iso88595", "iso_8859-5",
+            "iso_8859-5:1988",
+        ],
+    ),
+    (
+        "iso-8859-6",
+        &[
+            "arabic", "asmo-708", "csiso88596e", "csiso88596i", "csisolatinarabic", "ecma-114", "iso-8859-6",
+            "iso-8859-6-e", "iso-8859-6-i", "iso-ir-127", "iso8859-6", "iso88596", "iso_8859-6",
+            "iso_8859-6:1987",
+        ],
+    ),
+    (
+        "iso-8859-7",
+        &[
+            "csisolatingreek", "ecma-118", "elot_928", "greek", "greek8", "iso-8859-7", "iso-ir-126",
+            "iso8859-7", "iso88597", "iso_8859-7", "iso_8859-7:1987", "sun_eu_greek",
+        ],
+    ),
+    (
+        "iso-8859-8",
+        &[
+            "csiso88598e", "csisolatinhebrew", "hebrew", "iso-8859-8", "iso-8859-8-e", "iso-ir-138",
+            "iso8859-8", "iso88598", "iso_8859-8", "iso_8859-8:1988", "visual",
+        ],
+    ),
+    ("iso-8859-8-i", &["csiso88598i", "iso-8859-8-i", "logical"]),
+    ("iso-8859-10", &["csisolatin6", "iso-8859-10", "iso-ir-157", "iso8859-10", "iso885910", "l6", "latin6"]),
+    ("iso-8859-13", &["iso-8859-13", "iso8859-13", "iso885913"]),
+    ("iso-8859-14", &["iso-8859-14", "iso8859-14", "iso885914"]),
+    ("iso-8859-15", &["csisolatin9", "iso-8859-15", "iso8859-15", "iso885915", "iso_8859-15", "l9"]),
+    ("iso-8859-16", &["iso-8859-16"]),
+    ("koi8-r", &["cskoi8r", "koi", "koi8", "koi8-r", "koi8_r"]),
+    ("koi8-u", &["koi8-ru", "koi8-u"]),
+    ("macintosh", &["csmacintosh", "mac", "macintosh", "x-mac-roman"]),
+    ("windows-874", &["dos-874", "iso-8859-11", "iso8859-11", "iso885911", "tis-620", "windows-874"]),
+    ("windows-1250", &["cp1250", "windows-1250", "x-cp1250"]),
+    ("windows-1251", &["cp1251", "windows-1251", "x-cp1251"]),
+    (
+        "windows-1252",
+        &[
+            "ansi_x3.4-1968", "ascii", "cp1252", "cp819", "csisolatin1", "ibm819", "iso-8859-1", "iso-ir-100",
+            "iso8859-1", "iso88591", "iso_8859-1", "iso_8859-1:1987", "l1", "latin1", "us-ascii",
+            "windows-1252", "x-cp1252",
+        ],
+    ),
+    ("windows-1253", &["cp1253", "windows-1253", "x-cp1253"]),
+    (
+        "windows-1254",
+        &[
+            "cp1254", "csisolatin5", "iso-8859-9", "iso-ir-148", "iso8859-9", "iso88599", "iso_8859-9",
+            "iso_8859-9:1989", "l5", "latin5", "windows-1254", "x-cp1254",
+        ],
+    ),
+    ("windows-1255", &["cp1255", "windows-1255", "x-cp1255"]),
+    ("windows-1256", &["cp1256", "windows-1256", "x-cp1256"]),
+    ("windows-1257", &["cp1257", "windows-1257", "x-cp1257"]),
+    ("windows-1258", &["cp1258", "windows-1258", "x-cp1258"]),
+    ("x-mac-cyrillic", &["x-mac-cyrillic", "x-mac-ukrainian"]),
+    (
+        "gbk",
+        &[
+            "chinese", "csgb2312", "csiso58gb231280", "gb2312", "gb_2312", "gb_2312-80", "gbk", "iso-ir-58",
+            "x-gbk",
+        ],
+    ),
+    ("gb18030", &["gb18030"]),
+    ("big5", &["big5", "big5-hkscs", "cn-big5", "csbig5", "x-x-big5"]),
+    ("euc-jp", &["cseucpkdfmtjapanese", "euc-jp", "x-euc-jp"]),
+    ("iso-2022-jp", &["csiso2022jp", "iso-2022-jp"]),
+    ("shift_jis", &["csshiftjis", "ms932", "ms_kanji", "shift-jis", "shift_jis", "sjis", "windows-31j", "x-sjis"]),
+    (
+        "euc-kr",
+        &[
+            "cseuckr", "csksc56011987", "euc-kr", "iso-ir-149", "korean", "ks_c_5601-1987", "ks_c_5601-1989",
+            "ksc5601", "ksc_5601", "windows-949",
+        ],
+    ),
+    ("replacement", &["csiso2022kr", "hz-gb-2312", "iso-2022-cn", "iso-2022-cn-ext", "iso-2022-kr", "replacement"]),
+    ("utf-16be", &["unicodefffe", "utf-16be"]),
+    ("utf-16le", &["csunicode", "iso-10646-ucs-2", "ucs-2", "unicode", "unicodefeff", "utf-16", "utf-16le"]),
+    ("x-user-defined", &["x-user-defined"]),
+];
+
+// Labels for encodings that are unsafe to decode content with (ISO-2022-KR, HZ and the like);
+// decoders read anything labelled with them as a single replacement character
+const REPLACEMENT: &str = "replacement";
+
+// The encoding a label names: ASCII whitespace around it is ignored and it is matched without
+// regard to ASCII case, as the standard's "get an encoding" does
+fn encoding_for_label(label: &str) -> Option<&'static str> {
+    let label = label.trim_matches(|c: char| c.is_ascii_whitespace()).to_ascii_lowercase();
+    ENCODINGS
+        .iter()
+        .find(|(_, labels)| labels.contains(&label.as_str()))
+        .map(|(name, _)| *name)
+}
+
+fn check_charset(text: &str, options: &ValidationOptions) -> Result<String, &'static str> {
+    match encoding_for_label(text) {
+        None => Err(UNKNOWN_ENCODING),
+        Some(REPLACEMENT) => Err(UNSUPPORTED_ENCODING),
+        Some(name) if options.require_utf8 && name != "utf-8" => Err(NOT_UTF8),
+        Some(name) => Ok(name.to_string()),
+    }
+}
+
+// Detailed validation; only strings can be valid, and a string's problem is reported
+fn detail_charset(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str().map(|text| check_charset(text, options)) {
+        Some(Ok(name)) => ValidationResult::from(Some(Value::String(name))),
+        Some(Err(code)) => ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the name of the encoding the label stands for, so "latin1" and "ISO-8859-1" both
+// become "windows-1252", the encoding browsers actually decode them with
+fn coerce_charset(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    check_charset(value.as_str()?, options).ok().map(Value::String)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_charset(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_charset(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_charset(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_charset(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_charset(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_charset(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_charset(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_charset(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_charset(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    check_charset(value, &ValidationOptions::default()).is_ok()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error(label: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_charset(&json!(label), options).error
+    }
+
+    #[test]
+    fn test_labels() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_charset(&json!("utf-8"), &defaults), Some(json!("utf-8")));
+        assert_eq!(coerce_charset(&json!(" UTF8\n"), &defaults), Some(json!("utf-8")));
+        assert_eq!(coerce_charset(&json!("latin1"), &defaults), Some(json!("windows-1252")));
+        assert_eq!(coerce_charset(&json!("ISO-8859-1"), &defaults), Some(json!("windows-1252")));
+        assert_eq!(coerce_charset(&json!("us-ascii"), &defaults), Some(json!("windows-1252")));
+        assert_eq!(coerce_charset(&json!("Shift-JIS"), &defaults), Some(json!("shift_jis")));
+        assert_eq!(coerce_charset(&json!("utf-16"), &defaults), Some(json!("utf-16le")));
+        assert_eq!(coerce_charset(&json!("gb2312"), &defaults), Some(json!("gbk")));
+    }
+
+    #[test]
+    fn test_table() {
+        // Every label names exactly one encoding, and every label is its own lowercase form
+        let mut labels: Vec<&str> = ENCODINGS.iter().flat_map(|(_, labels)| labels.iter().copied()).collect();
+        assert!(labels.iter().all(|label| label.to_ascii_lowercase() == *label));
+        let count = labels.len();
+        labels.sort_unstable();
+        labels.dedup();
+        assert_eq!(labels.len(), count);
+
+        // Each encoding's name is one of its labels, and the schema lists every usable name
+        let schema = description()["schema"]["enum"].clone();
+        for (name, labels) in ENCODINGS {
+            assert!(labels.contains(name), "{name}");
+            assert_eq!(schema.as_array().unwrap().contains(&json!(name)), *name != REPLACEMENT, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_labels() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(error("utf-9", &defaults), Some(UNKNOWN_ENCODING));
+        assert_eq!(error("utf 8", &defaults), Some(UNKNOWN_ENCODING));
+        assert_eq!(error("", &defaults), Some(UNKNOWN_ENCODING));
+        assert_eq!(error("utf-32", &defaults), Some(UNKNOWN_ENCODING));
+        // Only ASCII whitespace is ignored
+        assert_eq!(error("\u{a0}utf-8", &defaults), Some(UNKNOWN_ENCODING));
+        assert_eq!(error("iso-2022-kr", &defaults), Some(UNSUPPORTED_ENCODING));
+        assert_eq!(error("replacement", &defaults), Some(UNSUPPORTED_ENCODING));
+
+        // Only strings can be labels, and other values have no specific reason
+        assert_eq!(detail_charset(&json!(8), &defaults), ValidationResult::from(None));
+    }
+
+    #[test]
+    fn test_options() {
+        let utf8: ValidationOptions = serde_json::from_value(json!({"require_utf8": true})).unwrap();
+        assert_eq!(coerce_charset(&json!("unicode-1-1-utf-8"), &utf8), Some(json!("utf-8")));
+        assert_eq!(error("latin1", &utf8), Some(NOT_UTF8));
+        assert_eq!(error("utf-16", &utf8), Some(NOT_UTF8));
+        assert_eq!(error("utf-9", &utf8), Some(UNKNOWN_ENCODING));
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "utf-8", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_charset(&json!("latin1"), &defaults),
+            ValidationResult { valid: true, value: json!("windows-1252"), error: None }
+        );
+        assert_eq!(
+            detail_charset(&json!("latin-1"), &defaults),
+            ValidationResult { valid: false, value: Value::Null, error: Some(UNKNOWN_ENCODING) }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["utf-8", 1], "options": {"require_utf8": true}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["utf-8", " Latin1 ", "utf-9", "iso-2022-kr", ""] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,487 @@
+// ABOUTME: WASM component for raw bytes, for file-upload pipelines to check before any string validator runs
+// ABOUTME: Detects UTF-8 or UTF-16 from a byte order mark or the first character, reporting where decoding fails
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// The value type, shared with the other validators that take raw bytes
+#[path = "../../../bytes/bytes.rs"]
+mod bytes;
+
+use bytes::Bytes;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+
+    #[wasm_bindgen(typescript_type = "ValidationOptions")]
+    pub type JsValidationOptions;
+}
+
+#[derive(Deserialize)]
+pub struct ValidationInput {
+    value: Bytes,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Encodings to accept; any of the three when unset
+    encodings: Option<Vec<Encoding>>,
+    // Encoding assumed when there is no byte order mark; detected from the first character when
+    // unset
+    default_encoding: Option<Encoding>,
+    // Accept a byte order mark at the start
+    allow_bom: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { encodings: None, default_encoding: None, allow_bom: true }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    #[serde(rename = "utf-8")]
+    Utf8,
+    #[serde(rename = "utf-16le")]
+    Utf16Le,
+    #[serde(rename = "utf-16be")]
+    Utf16Be,
+}
+
+impl Encoding {
+    const ALL: [Encoding; 3] = [Encoding::Utf8, Encoding::Utf16Le, Encoding::Utf16Be];
+
+    fn bom(self) -> &'static [u8] {
+        match self {
+            Encoding::Utf8 => &[0xEF, 0xBB, 0xBF],
+            Encoding::Utf16Le => &[0xFF, 0xFE],
+            Encoding::Utf16Be => &[0xFE, 0xFF],
+        }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize)]
+pub struct BatchInput {
+    values: Vec<Bytes>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The encoding the bytes were read as, when they were rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding: Option<Encoding>,
+    // Where decoding first failed, as a byte offset from the start (byte order mark included)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<usize>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+            encoding: None,
+            offset: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const INVALID_SEQUENCE: &str = "invalid_sequence";
+const ENCODING_NOT_ALLOWED: &str = "encoding_not_allowed";
+const BOM_NOT_ALLOWED: &str = "bom_not_allowed";
+
+// The encoding of bytes without a byte order mark: text starting with an ASCII character has
+// a NUL byte beside it in UTF-16, which UTF-8 text never starts with
+fn sniff(bytes: &[u8]) -> Encoding {
+    match bytes {
+        [0, second, ..] if *second != 0 => Encoding::Utf16Be,
+        [first, 0, ..] if *first != 0 => Encoding::Utf16Le,
+        _ => Encoding::Utf8,
+    }
+}
+
+// The offset, within the text, of the first code unit that doesn't decode; an odd byte at the
+// end is a truncated code unit
+fn utf16_error(text: &[u8], encoding: Encoding) -> Option<usize> {
+    let units: Vec<u16> = text
+        .chunks_exact(2)
+        .map(|pair| match encoding {
+            Encoding::Utf16Be => u16::from_be_bytes([pair[0], pair[1]]),
+            _ => u16::from_le_bytes([pair[0], pair[1]]),
+        })
+        .collect();
+
+    let mut i = 0;
+    while i < units.len() {
+        match units[i] {
+            0xD800..=0xDBFF if matches!(units.get(i + 1), Some(0xDC00..=0xDFFF)) => i += 2,
+            0xD800..=0xDFFF => return Some(i * 2),
+            _ => i += 1,
+        }
+    }
+    (text.len() % 2 == 1).then(|| text.len() - 1)
+}
+
+fn detail_encoding(bytes: &[u8], options: &ValidationOptions) -> ValidationResult {
+    let bom = Encoding::ALL.into_iter().find(|encoding| bytes.starts_with(encoding.bom()));
+    let encoding = bom.or(options.default_encoding).unwrap_or_else(|| sniff(bytes));
+    let failure = |error, offset| ValidationResult {
+        error: Some(error),
+        encoding: Some(encoding),
+        offset,
+        ..ValidationResult::from(None)
+    };
+
+    if options.encodings.as_ref().is_some_and(|encodings| !encodings.contains(&encoding)) {
+        return failure(ENCODING_NOT_ALLOWED, None);
+    }
+    if bom.is_some() && !options.allow_bom {
+        return failure(BOM_NOT_ALLOWED, Some(0));
+    }
+
+    let start = bom.map_or(0, |encoding| encoding.bom().len());
+    let text = &bytes[start..];
+    let error = match encoding {
+        Encoding::Utf8 => std::str::from_utf8(text).err().map(|error| error.valid_up_to()),
+        _ => utf16_error(text, encoding),
+    };
+    match error {
+        Some(offset) => failure(INVALID_SEQUENCE, Some(start + offset)),
+        None => ValidationResult::from(Some(json!({ "encoding": encoding, "bom": bom.is_some() }))),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the encoding the bytes decode cleanly in, and whether they start with its byte order
+// mark
+fn coerce_encoding(bytes: &[u8], options: &ValidationOptions) -> Option<Value> {
+    let result = detail_encoding(bytes, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_encoding(bytes: &[u8], options: &ValidationOptions) -> bool {
+    coerce_encoding(bytes, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_encoding(&Bytes::deserialize(value).ok()?.0, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    let result = match Bytes::deserialize(value) {
+        Ok(bytes) => detail_encoding(&bytes.0, &options),
+        Err(_) => ValidationResult::from(None),
+    };
+    serde_json::to_value(result).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_encoding(&input_obj.value.0, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_encoding(&input_obj.value.0, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid. Every failure of bytes
+    // has its own error code, so the input needn't be kept to choose one
+    let (result, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_encoding(&input_obj.value.0, &input_obj.options), input_obj.locale),
+        Err(_) => (ValidationResult::from(None), None),
+    };
+
+    to_js(&messages::localize(&result, &Value::Null, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|bytes| JsValue::from_bool(validate_encoding(&bytes.0, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|bytes| {
+            let result = detail_encoding(&bytes.0, &batch.options);
+            to_js(&messages::localize(&result, &Value::Null, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for bytes: a Uint8Array is copied straight into WASM memory rather than into an
+// input object, element by element. Options are a separate, optional argument, and options
+// that don't deserialize make the bytes invalid
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_bytes(bytes: &[u8], options: Option<JsValidationOptions>) -> bool {
+    let options = match options {
+        Some(options) => match serde_wasm_bindgen::from_value(options.into()) {
+            Ok(options) => options,
+            Err(_) => return false,
+        },
+        None => ValidationOptions::default(),
+    };
+
+    validate_encoding(bytes, &options)
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    fn utf16be(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_be_bytes).collect()
+    }
+
+    #[test]
+    fn test_detection() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_encoding(b"caf\xC3\xA9", &defaults), Some(json!({"encoding": "utf-8", "bom": false})));
+        assert_eq!(coerce_encoding(b"\xEF\xBB\xBFhi", &defaults), Some(json!({"encoding": "utf-8", "bom": true})));
+        assert_eq!(coerce_encoding(b"", &defaults), Some(json!({"encoding": "utf-8", "bom": false})));
+
+        let bom_le = [&[0xFF, 0xFE][..], &utf16le("hé 🎉")].concat();
+        assert_eq!(coerce_encoding(&bom_le, &defaults), Some(json!({"encoding": "utf-16le", "bom": true})));
+        let bom_be = [&[0xFE, 0xFF][..], &utf16be("hé 🎉")].concat();
+        assert_eq!(coerce_encoding(&bom_be, &defaults), Some(json!({"encoding": "utf-16be", "bom": true})));
+
+        // Without a byte order mark, a NUL beside an ASCII first character means UTF-16
+        assert_eq!(coerce_encoding(&utf16le("id,name"), &defaults), Some(json!({"encoding": "utf-16le", "bom": false})));
+        assert_eq!(coerce_encoding(&utf16be("id,name"), &defaults), Some(json!({"encoding": "utf-16be", "bom": false})));
+    }
+
+    #[test]
+    fn test_invalid_sequences() {
+        let defaults = ValidationOptions::default();
+
+        let result = detail_encoding(b"caf\xE9 au lait", &defaults);
+        assert_eq!((result.error, result.encoding, result.offset), (Some(INVALID_SEQUENCE), Some(Encoding::Utf8), Some(3)));
+
+        // Offsets count the byte order mark
+        assert_eq!(detail_encoding(b"\xEF\xBB\xBFab\xFF", &defaults).offset, Some(5));
+        // A truncated sequence at the end fails where it starts
+        assert_eq!(detail_encoding(b"ab\xE2\x82", &defaults).offset, Some(2));
+
+        // An unpaired surrogate, and an odd trailing byte
+        let unpaired = [&[0xFF, 0xFE, b'a', 0][..], &0xD800u16.to_le_bytes(), &[b'b', 0]].concat();
+        let result = detail_encoding(&unpaired, &defaults);
+        assert_eq!((result.error, result.encoding, result.offset), (Some(INVALID_SEQUENCE), Some(Encoding::Utf16Le), Some(4)));
+        let low_first = [&[0xFE, 0xFF][..], &0xDC00u16.to_be_bytes()].concat();
+        assert_eq!(detail_encoding(&low_first, &defaults).offset, Some(2));
+        assert_eq!(detail_encoding(&[0xFF, 0xFE, b'a', 0, b'b'], &defaults).offset, Some(4));
+    }
+
+    #[test]
+    fn test_options() {
+        let utf8_only: ValidationOptions = serde_json::from_value(json!({"encodings": ["utf-8"]})).unwrap();
+        assert!(validate_encoding(b"plain", &utf8_only));
+        let result = detail_encoding(&[0xFF, 0xFE, b'a', 0], &utf8_only);
+        assert_eq!((result.error, result.encoding), (Some(ENCODING_NOT_ALLOWED), Some(Encoding::Utf16Le)));
+
+        let no_bom: ValidationOptions = serde_json::from_value(json!({"allow_bom": false})).unwrap();
+        assert_eq!(detail_encoding(b"\xEF\xBB\xBFhi", &no_bom).error, Some(BOM_NOT_ALLOWED));
+        assert!(validate_encoding(b"hi", &no_bom));
+
+        // A declared encoding replaces detection, but a byte order mark still wins
+        let declared: ValidationOptions = serde_json::from_value(json!({"default_encoding": "utf-16be"})).unwrap();
+        assert_eq!(coerce_encoding(b"hi", &declared), Some(json!({"encoding": "utf-16be", "bom": false})));
+        assert_eq!(coerce_encoding(b"\xEF\xBB\xBFhi", &declared), Some(json!({"encoding": "utf-8", "bom": true})));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"encodings": ["latin1"]})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": [104], "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_input() {
+        // Bytes arrive as arrays of numbers from JSON; anything else is not bytes
+        assert_eq!(coerce_value(&json!([104, 105]), &Value::Null), Some(json!({"encoding": "utf-8", "bom": false})));
+        assert_eq!(coerce_value(&json!([104, 256]), &Value::Null), None);
+        assert_eq!(coerce_value(&json!("hi"), &Value::Null), None);
+        assert_eq!(coerce_value(&json!(null), &Value::Null), None);
+
+        let input: ValidationInput = serde_json::from_value(json!({"value": [0xEF, 0xBB, 0xBF]})).unwrap();
+        assert_eq!(input.value, Bytes(vec![0xEF, 0xBB, 0xBF]));
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [[104], []], "options": {"allow_bom": false}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_encoding(b"ok", &defaults),
+            ValidationResult::from(Some(json!({"encoding": "utf-8", "bom": false})))
+        );
+        assert_eq!(
+            messages::localize(&detail_encoding(b"\xFF", &defaults), &Value::Null, Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "invalid_sequence",
+                "encoding": "utf-8",
+                "offset": 0,
+                "message": messages::message("invalid_sequence", "en").unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The bytes path agrees with the generic path under the default options
+        let samples: [&[u8]; 5] = [b"hi", b"\xEF\xBB\xBFhi", b"\xFF", &[0xFF, 0xFE, b'a'], b""];
+        for bytes in samples {
+            assert_eq!(validate_bytes(bytes, None), coerce_value(&json!(bytes), &Value::Null).is_some(), "{bytes:?}");
+        }
+    }
+}
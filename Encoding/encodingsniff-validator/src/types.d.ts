@@ -0,0 +1,48 @@
+// Types for the encodingsniff validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Encodings to accept; any of the three when unset */
+    encodings?: ("utf-8" | "utf-16le" | "utf-16be")[];
+    /** Encoding assumed when there is no byte order mark; null detects UTF-16 from a NUL byte beside the first character, and assumes UTF-8 otherwise (default: null) */
+    default_encoding?: "utf-8" | "utf-16le" | "utf-16be" | null;
+    /** Accept a byte order mark at the start (default: true) */
+    allow_bom?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = Uint8Array | ArrayBuffer | number[];
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    encoding: "utf-8" | "utf-16le" | "utf-16be";
+    bom: boolean;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The encoding the bytes were read as, when they were rejected */
+    encoding?: "utf-8" | "utf-16le" | "utf-16be";
+    /** Where decoding first failed, as a byte offset from the start (byte order mark included) */
+    offset?: number;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "invalid_sequence" | "encoding_not_allowed" | "bom_not_allowed" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
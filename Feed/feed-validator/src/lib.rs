@@ -0,0 +1,673 @@
+// ABOUTME: WASM component for RSS and Atom feeds, for podcast and news platforms that ingest user-submitted feeds
+// ABOUTME: Checks the XML and each format's required elements, with dates and links checked by the datetime and URL validators
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// Parsing XML, shared with the OPML and sitemap validators
+#[path = "../../../xml/xml.rs"]
+mod xml;
+
+use xml::{Element, Problem};
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The format to accept; detected from the root element when unset
+    format: Option<Format>,
+    // Reject a feed without items or entries, as a podcast directory would
+    require_items: bool,
+}
+
+// The feed formats: RSS 0.91 to 2.0, and Atom 1.0
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Rss,
+    Atom,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The line the problem was found on, counting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, line: None }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from((code, line): Problem) -> Self {
+        ValidationResult { error: Some(code), line: Some(line), ..ValidationResult::from(None) }
+    }
+}
+
+// Error codes reported in detailed results, besides the XML parser's
+const NOT_A_FEED: &str = "not_a_feed";
+const WRONG_FEED_FORMAT: &str = "wrong_feed_format";
+const UNSUPPORTED_VERSION: &str = "unsupported_version";
+const MISSING_ELEMENT: &str = "missing_element";
+const MISSING_ATTRIBUTE: &str = "missing_attribute";
+const INVALID_ATTRIBUTE: &str = "invalid_attribute";
+const INVALID_DATE: &str = "invalid_date";
+const INVALID_URL: &str = "invalid_url";
+
+const ATOM: Option<&str> = Some("http://www.w3.org/2005/Atom");
+const RSS_VERSIONS: &[&str] = &["0.91", "0.92", "0.93", "0.94", "2.0"];
+
+// Relative Atom links are resolved against this before the URL validator sees them; the
+// .invalid domain is reserved, so it never names a real host
+const BASE: &str = "https://feed.invalid/";
+
+const DAYS: &[&str] = &["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: &[&str] = &["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+// The zone names RFC 822 allows besides numeric offsets
+const ZONES: &[(&str, &str)] = &[
+    ("GMT", "Z"),
+    ("UT", "Z"),
+    ("UTC", "Z"),
+    ("Z", "Z"),
+    ("EST", "-05:00"),
+    ("EDT", "-04:00"),
+    ("CST", "-06:00"),
+    ("CDT", "-05:00"),
+    ("MST", "-07:00"),
+    ("MDT", "-06:00"),
+    ("PST", "-08:00"),
+    ("PDT", "-07:00"),
+];
+
+fn digits(text: &str, lengths: std::ops::RangeInclusive<usize>) -> Option<u32> {
+    let valid = lengths.contains(&text.len()) && text.bytes().all(|b| b.is_ascii_digit());
+    valid.then(|| text.parse().ok()).flatten()
+}
+
+// An RSS date, written as RFC 822 and RFC 2822 after it have them, such as
+// "Sat, 07 Sep 2002 09:42:31 GMT", rewritten as RFC 3339. Two-digit years are read as RFC 2822
+// reads them, before 50 as 20xx
+fn rfc822_to_rfc3339(text: &str) -> Option<String> {
+    let text = match text.split_once(',') {
+        Some((day, rest)) if DAYS.contains(&day.trim()) => rest,
+        Some(_) => return None,
+        None => text,
+    };
+    let [day, month, year, time, zone] = text.split_whitespace().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let day = digits(day, 1..=2)?;
+    let month = MONTHS.iter().position(|name| name.eq_ignore_ascii_case(month))? + 1;
+    let year = match (year.len(), digits(year, 2..=4)?) {
+        (2, year) if year < 50 => year + 2000,
+        (2, year) => year + 1900,
+        (4, year) => year,
+        _ => return None,
+    };
+    let clock: Vec<u32> = time.split(':').map(|part| digits(part, 2..=2)).collect::<Option<_>>()?;
+    let (hour, minute, second) = match clock[..] {
+        [hour, minute] => (hour, minute, 0),
+        [hour, minute, second] => (hour, minute, second),
+        _ => return None,
+    };
+    let offset = match ZONES.iter().find(|(name, _)| *name == zone) {
+        Some((_, offset)) => offset.to_string(),
+        None => {
+            let sign = zone.get(..1).filter(|sign| matches!(*sign, "+" | "-"))?;
+            let offset = &zone[1..];
+            digits(offset, 4..=4)?;
+            format!("{sign}{}:{}", &offset[..2], &offset[2..])
+        }
+    };
+    Some(format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}{offset}"))
+}
+
+// An RSS date, whose calendar and clock the datetime validator checks
+fn is_rss_date(text: &str) -> bool {
+    rfc822_to_rfc3339(text).is_some_and(|date| datetime_validator::coerce_value(&Value::from(date), &Value::Null).is_some())
+}
+
+// An Atom date, which is already RFC 3339
+fn is_atom_date(text: &str) -> bool {
+    datetime_validator::coerce_value(&Value::from(text), &Value::Null).is_some()
+}
+
+fn is_url(text: &str) -> bool {
+    url_validator::coerce_value(&Value::from(text), &Value::Null).is_some()
+}
+
+// An Atom link, which may be relative to the feed's own URL
+fn is_url_reference(text: &str) -> bool {
+    match Url::parse(text) {
+        Err(url::ParseError::RelativeUrlWithoutBase) => {
+            !text.contains(char::is_whitespace) && Url::parse(BASE).and_then(|base| base.join(text)).is_ok_and(|url| is_url(url.as_str()))
+        }
+        _ => is_url(text),
+    }
+}
+
+// An element's child, which it must have
+fn require<'a>(element: &'a Element, namespace: Option<&str>, name: &str) -> Result<&'a Element, Problem> {
+    element.child(namespace, name).ok_or((MISSING_ELEMENT, element.line))
+}
+
+// Checks an element's text, if there is the element
+fn check_text(element: Option<&Element>, valid: fn(&str) -> bool, code: &'static str) -> Result<(), Problem> {
+    match element {
+        Some(element) if !valid(element.text.trim()) => Err((code, element.line)),
+        _ => Ok(()),
+    }
+}
+
+// An enclosure names the file of a podcast episode: its URL, its length in bytes and its
+// media type
+fn check_enclosure(enclosure: &Element) -> Result<(), Problem> {
+    let (Some(url), Some(length), Some(media_type)) =
+        (enclosure.attribute("url"), enclosure.attribute("length"), enclosure.attribute("type"))
+    else {
+        return Err((MISSING_ATTRIBUTE, enclosure.line));
+    };
+    if !is_url(url.trim()) {
+        return Err((INVALID_URL, enclosure.line));
+    }
+    let sized = !length.trim().is_empty() && length.trim().bytes().all(|b| b.is_ascii_digit());
+    let typed = media_type.split_once('/').is_some_and(|(kind, subtype)| !kind.is_empty() && !subtype.is_empty());
+    if !sized || !typed {
+        return Err((INVALID_ATTRIBUTE, enclosure.line));
+    }
+    Ok(())
+}
+
+// An RSS feed: a channel with a title, link and description, and items that each have a
+// title or a description. Returns the version, the channel and its items
+fn check_rss(rss: &Element) -> Result<(&str, &Element, Vec<&Element>), Problem> {
+    let version = rss.attribute("version").unwrap_or_default();
+    if !RSS_VERSIONS.contains(&version) {
+        return Err((UNSUPPORTED_VERSION, rss.line));
+    }
+    let channel = require(rss, None, "channel")?;
+    require(channel, None, "title")?;
+    check_text(Some(require(channel, None, "link")?), is_url, INVALID_URL)?;
+    require(channel, None, "description")?;
+    for name in ["pubDate", "lastBuildDate"] {
+        check_text(channel.child(None, name), is_rss_date, INVALID_DATE)?;
+    }
+    if let Some(image) = channel.child(None, "image") {
+        check_text(Some(require(image, None, "url")?), is_url, INVALID_URL)?;
+    }
+
+    let items: Vec<&Element> = channel.children(None, "item").collect();
+    for item in &items {
+        if item.child(None, "title").is_none() && item.child(None, "description").is_none() {
+            return Err((MISSING_ELEMENT, item.line));
+        }
+        check_text(item.child(None, "link"), is_url, INVALID_URL)?;
+        check_text(item.child(None, "comments"), is_url, INVALID_URL)?;
+        check_text(item.child(None, "pubDate"), is_rss_date, INVALID_DATE)?;
+        for enclosure in item.children(None, "enclosure") {
+            check_enclosure(enclosure)?;
+        }
+    }
+    Ok((version, channel, items))
+}
+
+// Whether an Atom element names authors, each of whom must have a name
+fn has_authors(element: &Element) -> Result<bool, Problem> {
+    let mut named = false;
+    for author in element.children(ATOM, "author") {
+        require(author, ATOM, "name")?;
+        named = true;
+    }
+    Ok(named)
+}
+
+// Atom links, each with an href
+fn check_links(element: &Element) -> Result<(), Problem> {
+    for link in element.children(ATOM, "link") {
+        let href = link.attribute("href").ok_or((MISSING_ATTRIBUTE, link.line))?;
+        if !is_url_reference(href.trim()) {
+            return Err((INVALID_URL, link.line));
+        }
+    }
+    Ok(())
+}
+
+// An Atom feed: an id, title and updated date, and entries with the same; authors, who must
+// have names, are given for the feed or for every entry, and an entry without content links
+// to it. Returns the feed's entries
+fn check_atom(feed: &Element) -> Result<Vec<&Element>, Problem> {
+    require(feed, ATOM, "id")?;
+    require(feed, ATOM, "title")?;
+    check_text(Some(require(feed, ATOM, "updated")?), is_atom_date, INVALID_DATE)?;
+    check_links(feed)?;
+    let feed_author = has_authors(feed)?;
+
+    let entries: Vec<&Element> = feed.children(ATOM, "entry").collect();
+    for entry in &entries {
+        require(entry, ATOM, "id")?;
+        require(entry, ATOM, "title")?;
+        check_text(Some(require(entry, ATOM, "updated")?), is_atom_date, INVALID_DATE)?;
+        check_text(entry.child(ATOM, "published"), is_atom_date, INVALID_DATE)?;
+        check_links(entry)?;
+        let alternate = entry.children(ATOM, "link").any(|link| link.attribute("rel").unwrap_or("alternate") == "alternate");
+        if !(has_authors(entry)? || feed_author) || (entry.child(ATOM, "content").is_none() && !alternate) {
+            return Err((MISSING_ELEMENT, entry.line));
+        }
+    }
+    Ok(entries)
+}
+
+// Checks a feed. Returns its format, version, title and how many items or entries it has
+fn check_feed(text: &str, options: &ValidationOptions) -> Result<Value, Problem> {
+    let root = xml::parse(text)?;
+    let format = if root.is(None, "rss") {
+        Format::Rss
+    } else if root.is(ATOM, "feed") {
+        Format::Atom
+    } else {
+        return Err((NOT_A_FEED, root.line));
+    };
+    if options.format.is_some_and(|expected| expected != format) {
+        return Err((WRONG_FEED_FORMAT, root.line));
+    }
+
+    // The channel or feed, which holds the title and the items or entries
+    let (version, container, namespace, items) = match format {
+        Format::Rss => check_rss(&root).map(|(version, channel, items)| (version, channel, None, items))?,
+        Format::Atom => ("1.0", &root, ATOM, check_atom(&root)?),
+    };
+    if options.require_items && items.is_empty() {
+        return Err((MISSING_ELEMENT, container.line));
+    }
+    let title = container.child(namespace, "title").map_or("", |title| title.text.trim());
+    Ok(json!({ "format": format, "version": version, "title": title, "items": items.len() }))
+}
+
+fn detail_feed_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no elements, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_feed(text, options) {
+        Ok(summary) => ValidationResult::from(Some(summary)),
+        Err(problem) => ValidationResult::from(problem),
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_feed_str directly
+fn detail_feed(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_feed_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the feed's format, its title and how many items it has
+fn coerce_feed(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_feed(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_feed(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_feed(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_feed(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_feed(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_feed(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_feed(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_feed(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_feed(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_feed(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_feed_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <rss version=\"2.0\" xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\">\n<channel>\n\
+        <title>Tech Talk</title>\n<link>https://example.com/podcast</link>\n<description>Weekly interviews</description>\n\
+        <lastBuildDate>Mon, 04 Mar 2024 09:00:00 GMT</lastBuildDate>\n<itunes:author>Jane Doe</itunes:author>\n\
+        <item>\n<title>Episode 2</title>\n<link>https://example.com/podcast/2</link>\n\
+        <pubDate>Mon, 04 Mar 2024 09:00:00 +0000</pubDate>\n\
+        <enclosure url=\"https://cdn.example.com/ep2.mp3\" length=\"24986239\" type=\"audio/mpeg\"/>\n</item>\n\
+        <item>\n<description>A bonus &lt;b&gt;clip&lt;/b&gt;</description>\n<pubDate>26 Feb 24 17:30 EST</pubDate>\n</item>\n\
+        </channel>\n</rss>\n";
+
+    const ATOM_FEED: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n\
+        <title type=\"text\">Example News</title>\n<link href=\"https://example.com/\"/>\n<link rel=\"self\" href=\"/feed.atom\"/>\n\
+        <id>urn:uuid:60a76c80-d399-11d9-b93C-0003939e0af6</id>\n<updated>2024-03-04T18:30:02Z</updated>\n\
+        <author><name>Jane Doe</name></author>\n\
+        <entry>\n<title>Launch day</title>\n<link href=\"https://example.com/2024/03/04/launch\"/>\n<id>tag:example.com,2024:1</id>\n\
+        <updated>2024-03-04T18:30:02Z</updated>\n<published>2024-03-04T09:00:00-05:00</published>\n<summary>We launched.</summary>\n</entry>\n\
+        </feed>\n";
+
+    fn problem(text: &str) -> (Option<&'static str>, Option<usize>) {
+        let result = detail_feed_str(text, &ValidationOptions::default());
+        (result.error, result.line)
+    }
+
+    #[test]
+    fn test_valid_feeds() {
+        let defaults = ValidationOptions::default();
+        assert_eq!(
+            coerce_feed(&json!(RSS), &defaults),
+            Some(json!({"format": "rss", "version": "2.0", "title": "Tech Talk", "items": 2}))
+        );
+        assert_eq!(
+            coerce_feed(&json!(ATOM_FEED), &defaults),
+            Some(json!({"format": "atom", "version": "1.0", "title": "Example News", "items": 1}))
+        );
+
+        // An RSS 0.91 feed with its DTD, and an Atom feed with a prefix and authors per entry
+        let old = "<!DOCTYPE rss PUBLIC \"-//Netscape Communications//DTD RSS 0.91//EN\" \"http://my.netscape.com/publish/formats/rss-0.91.dtd\">\n\
+            <rss version=\"0.91\"><channel><title>Old</title><link>http://example.com/</link><description/></channel></rss>";
+        assert_eq!(coerce_feed(&json!(old), &defaults).unwrap()["items"], 0);
+        let prefixed = ATOM_FEED
+            .replace("<feed xmlns=", "<a:feed xmlns:a=")
+            .replace("</feed>", "</a:feed>")
+            .replace("<author><name>Jane Doe</name></author>\n<entry>", "<entry><author><name>Jane</name></author>");
+        let prefixed = ["title", "link", "id", "updated", "published", "summary", "entry", "author", "name"]
+            .iter()
+            .fold(prefixed, |text, name| text.replace(&format!("<{name}"), &format!("<a:{name}")).replace(&format!("</{name}>"), &format!("</a:{name}>")));
+        assert!(validate_feed(&json!(prefixed), &defaults), "{prefixed}");
+    }
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(rfc822_to_rfc3339("Sat, 07 Sep 2002 09:42:31 GMT").as_deref(), Some("2002-09-07T09:42:31Z"));
+        assert_eq!(rfc822_to_rfc3339("7 sep 02 09:42 -0330").as_deref(), Some("2002-09-07T09:42:00-03:30"));
+        assert_eq!(rfc822_to_rfc3339("07 Sep 99 09:42 PDT").as_deref(), Some("1999-09-07T09:42:00-07:00"));
+        for bad in ["", "2002-09-07T09:42:31Z", "Saturday, 07 Sep 2002 09:42:31 GMT", "07 September 2002 09:42 GMT", "07 Sep 2002 9:42 GMT", "07 Sep 2002 09:42 CET", "07 Sep 2002 09:42 0000"] {
+            assert_eq!(rfc822_to_rfc3339(bad), None, "{bad}");
+        }
+        assert!(is_rss_date("29 Feb 2024 23:59:59 +0000"));
+        assert!(!is_rss_date("30 Feb 2024 12:00 GMT"));
+        assert!(!is_rss_date("01 Mar 2024 24:30 GMT"));
+
+        assert_eq!(problem(&RSS.replace("Mon, 04 Mar 2024 09:00:00 GMT", "2024-03-04")), (Some(INVALID_DATE), Some(7)));
+        assert_eq!(problem(&RSS.replace("26 Feb 24", "31 Feb 24")), (Some(INVALID_DATE), Some(17)));
+        assert_eq!(problem(&ATOM_FEED.replace("2024-03-04T09:00:00-05:00", "Mon, 04 Mar 2024 09:00:00 EST")), (Some(INVALID_DATE), Some(14)));
+        assert_eq!(problem(&ATOM_FEED.replace("<updated>2024-03-04T18:30:02Z</updated>\n<author>", "<updated>2024-03-04</updated>\n<author>")), (Some(INVALID_DATE), Some(7)));
+    }
+
+    #[test]
+    fn test_rss() {
+        assert_eq!(problem(&RSS.replace("version=\"2.0\"", "version=\"3.0\"")), (Some(UNSUPPORTED_VERSION), Some(2)));
+        assert_eq!(problem(&RSS.replace("<link>https://example.com/podcast</link>\n", "")), (Some(MISSING_ELEMENT), Some(3)));
+        assert_eq!(problem(&RSS.replace("<title>Episode 2</title>\n", "")), (Some(MISSING_ELEMENT), Some(9)));
+        assert_eq!(problem(&RSS.replace("https://example.com/podcast/2", "example.com/podcast/2")), (Some(INVALID_URL), Some(11)));
+        assert_eq!(problem(&RSS.replace(" length=\"24986239\"", "")), (Some(MISSING_ATTRIBUTE), Some(13)));
+        assert_eq!(problem(&RSS.replace("24986239", "24 MB")), (Some(INVALID_ATTRIBUTE), Some(13)));
+        assert_eq!(problem(&RSS.replace("audio/mpeg", "mp3")), (Some(INVALID_ATTRIBUTE), Some(13)));
+        assert_eq!(problem(&RSS.replace("https://cdn.example.com/ep2.mp3", "ep2.mp3")), (Some(INVALID_URL), Some(13)));
+        // Other namespaces' elements don't stand in for RSS's own
+        assert_eq!(problem(&RSS.replace("<title>Tech Talk</title>", "<itunes:title>Tech Talk</itunes:title>")), (Some(MISSING_ELEMENT), Some(3)));
+    }
+
+    #[test]
+    fn test_atom() {
+        assert_eq!(problem(&ATOM_FEED.replace("<id>urn:uuid:60a76c80-d399-11d9-b93C-0003939e0af6</id>\n", "")), (Some(MISSING_ELEMENT), Some(2)));
+        assert_eq!(problem(&ATOM_FEED.replace("<id>tag:example.com,2024:1</id>\n", "")), (Some(MISSING_ELEMENT), Some(9)));
+        assert_eq!(problem(&ATOM_FEED.replace("<author><name>Jane Doe</name></author>\n", "")), (Some(MISSING_ELEMENT), Some(8)));
+        assert_eq!(problem(&ATOM_FEED.replace("<name>Jane Doe</name>", "<email>jane@example.com</email>")), (Some(MISSING_ELEMENT), Some(8)));
+        assert_eq!(problem(&ATOM_FEED.replace("<link href=\"https://example.com/2024/03/04/launch\"/>\n", "")), (Some(MISSING_ELEMENT), Some(9)));
+        assert!(validate_feed(
+            &json!(ATOM_FEED.replace("<link href=\"https://example.com/2024/03/04/launch\"/>", "<content type=\"html\">&lt;p&gt;Hi&lt;/p&gt;</content>")),
+            &ValidationOptions::default()
+        ));
+        assert_eq!(problem(&ATOM_FEED.replace("<link rel=\"self\" href=\"/feed.atom\"/>", "<link rel=\"self\"/>")), (Some(MISSING_ATTRIBUTE), Some(5)));
+        assert_eq!(problem(&ATOM_FEED.replace("/feed.atom", "/feed atom")), (Some(INVALID_URL), Some(5)));
+        // Atom elements outside the Atom namespace aren't a feed
+        assert_eq!(problem(&ATOM_FEED.replace(" xmlns=\"http://www.w3.org/2005/Atom\"", "")), (Some(NOT_A_FEED), Some(2)));
+    }
+
+    #[test]
+    fn test_xml_and_options() {
+        assert_eq!(problem(""), (None, None));
+        assert_eq!(problem("<html><body>Not a feed</body></html>"), (Some(NOT_A_FEED), Some(1)));
+        assert_eq!(problem(&RSS.replace("</item>\n<item>", "</item>\n</item>")), (Some(xml::MISMATCHED_TAG), Some(15)));
+        assert_eq!(problem(&RSS.replace("Tech Talk", "Tech & Talk")), (Some(xml::INVALID_ENTITY), Some(4)));
+        assert_eq!(problem("Episode 1, https://example.com/1.mp3"), (Some(xml::MALFORMED_XML), Some(1)));
+        assert!(!validate_feed(&json!(42), &ValidationOptions::default()));
+
+        let rss: ValidationOptions = serde_json::from_value(json!({"format": "rss"})).unwrap();
+        assert!(validate_feed(&json!(RSS), &rss));
+        let result = detail_feed_str(ATOM_FEED, &rss);
+        assert_eq!((result.error, result.line), (Some(WRONG_FEED_FORMAT), Some(2)));
+
+        let items: ValidationOptions = serde_json::from_value(json!({"require_items": true})).unwrap();
+        assert!(validate_feed(&json!(RSS), &items));
+        let empty = ATOM_FEED.replace(&ATOM_FEED[ATOM_FEED.find("<entry>").unwrap()..ATOM_FEED.find("</feed>").unwrap()], "");
+        assert!(validate_feed(&json!(empty), &ValidationOptions::default()));
+        assert_eq!(detail_feed_str(&empty, &items).error, Some(MISSING_ELEMENT));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"format": "rdf"})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": RSS, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let broken = RSS.replace("version=\"2.0\"", "version=\"1.0\"");
+        assert_eq!(
+            messages::localize(&detail_feed_str(&broken, &ValidationOptions::default()), &json!(broken), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "unsupported_version",
+                "line": 2,
+                "message": messages::message("unsupported_version", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_feed_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [RSS, ATOM_FEED], "options": {"require_items": true}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [RSS, ATOM_FEED, "", "<rss/>", "<feed/>", "not xml"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// Types for the feed validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The format to accept; detected from the root element when unset (default: null) */
+    format?: "rss" | "atom" | null;
+    /** Reject a feed without items or entries, as a podcast directory would (default: false) */
+    require_items?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    format: "rss" | "atom";
+    version: string;
+    title: string;
+    items: number;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The line the problem was found on, counting from 1 */
+    line?: number;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "malformed_xml" | "mismatched_tag" | "invalid_entity" | "not_a_feed" | "wrong_feed_format" | "unsupported_version" | "missing_element" | "missing_attribute" | "invalid_attribute" | "invalid_date" | "invalid_url" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
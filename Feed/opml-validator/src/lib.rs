@@ -0,0 +1,442 @@
+// ABOUTME: WASM component for OPML outlines, for feed readers that import subscription lists
+// ABOUTME: Checks the XML, the head and body structure and each outline's attributes, with feed URLs checked by the URL validator
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// Parsing XML, shared with the feed and sitemap validators
+#[path = "../../../xml/xml.rs"]
+mod xml;
+
+use xml::{Element, Problem};
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Reject an outline that subscribes to no feeds, as an import would have nothing to do
+    require_feeds: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The line the problem was found on, counting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, line: None }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from((code, line): Problem) -> Self {
+        ValidationResult { error: Some(code), line: Some(line), ..ValidationResult::from(None) }
+    }
+}
+
+// Error codes reported in detailed results, besides the XML parser's
+const NOT_OPML: &str = "not_opml";
+const UNSUPPORTED_VERSION: &str = "unsupported_version";
+const MISSING_ELEMENT: &str = "missing_element";
+const MISSING_ATTRIBUTE: &str = "missing_attribute";
+const INVALID_URL: &str = "invalid_url";
+
+const OPML_VERSIONS: &[&str] = &["1.0", "1.1", "2.0"];
+// The attributes that hold URLs: a feed's, its website's, and a link's or included outline's
+const URL_ATTRIBUTES: &[&str] = &["xmlUrl", "htmlUrl", "url"];
+
+fn is_url(text: &str) -> bool {
+    url_validator::coerce_value(&Value::from(text), &Value::Null).is_some()
+}
+
+// The outlines in one outline or in the body, and all those nested in them. OPML 2.0 requires
+// each to have text; OPML 1.x didn't, so there a title will do. A feed's outline needs an
+// xmlUrl, and a link or an included outline a url; feed URLs are gathered in `feeds`, first
+// appearance first
+fn check_outlines(parent: &Element, version: &str, feeds: &mut Vec<String>) -> Result<usize, Problem> {
+    let mut count = 0;
+    for outline in parent.children(None, "outline") {
+        let named = outline.attribute("text").is_some() || (version != "2.0" && outline.attribute("title").is_some());
+        let required = match outline.attribute("type").map(str::to_ascii_lowercase).as_deref() {
+            Some("rss") => Some("xmlUrl"),
+            Some("link" | "include") => Some("url"),
+            _ => None,
+        };
+        if !named || required.is_some_and(|name| outline.attribute(name).is_none()) {
+            return Err((MISSING_ATTRIBUTE, outline.line));
+        }
+        for name in URL_ATTRIBUTES {
+            if outline.attribute(name).is_some_and(|url| !is_url(url.trim())) {
+                return Err((INVALID_URL, outline.line));
+            }
+        }
+        if let Some(feed) = outline.attribute("xmlUrl").map(str::trim) {
+            if !feeds.iter().any(|known| known == feed) {
+                feeds.push(feed.to_string());
+            }
+        }
+        count += 1 + check_outlines(outline, version, feeds)?;
+    }
+    Ok(count)
+}
+
+// Checks an OPML document: a head, and a body with at least one outline. Returns its version,
+// title, how many outlines it has and the URLs of the feeds they subscribe to
+fn check_opml(text: &str, options: &ValidationOptions) -> Result<Value, Problem> {
+    let root = xml::parse(text)?;
+    if !root.is(None, "opml") {
+        return Err((NOT_OPML, root.line));
+    }
+    let version = root.attribute("version").unwrap_or_default();
+    if !OPML_VERSIONS.contains(&version) {
+        return Err((UNSUPPORTED_VERSION, root.line));
+    }
+    let head = root.child(None, "head").ok_or((MISSING_ELEMENT, root.line))?;
+    let body = root.child(None, "body").ok_or((MISSING_ELEMENT, root.line))?;
+
+    let mut feeds = Vec::new();
+    let outlines = check_outlines(body, version, &mut feeds)?;
+    if outlines == 0 || (options.require_feeds && feeds.is_empty()) {
+        return Err((MISSING_ELEMENT, body.line));
+    }
+    let title = head.child(None, "title").map_or("", |title| title.text.trim());
+    Ok(json!({ "version": version, "title": title, "outlines": outlines, "feeds": feeds }))
+}
+
+fn detail_opml_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no elements, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_opml(text, options) {
+        Ok(summary) => ValidationResult::from(Some(summary)),
+        Err(problem) => ValidationResult::from(problem),
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_opml_str directly
+fn detail_opml(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_opml_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the version, title, number of outlines and feed URLs
+fn coerce_opml(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_opml(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_opml(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_opml(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_opml(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_opml(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_opml(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_opml(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_opml(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_opml(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_opml(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_opml_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPML: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<opml version=\"2.0\">\n<head>\n\
+        <title>My subscriptions</title>\n<dateCreated>Mon, 04 Mar 2024 09:00:00 GMT</dateCreated>\n</head>\n<body>\n\
+        <outline text=\"Tech\" title=\"Tech\">\n\
+        <outline type=\"rss\" text=\"Tech Talk\" xmlUrl=\"https://example.com/podcast.rss\" htmlUrl=\"https://example.com/podcast\"/>\n\
+        <outline type=\"rss\" text=\"Example News\" xmlUrl=\"https://example.com/feed.atom\"/>\n</outline>\n\
+        <outline text=\"Tech Talk, again\" type=\"RSS\" xmlUrl=\" https://example.com/podcast.rss \"/>\n\
+        <outline text=\"About\" type=\"link\" url=\"https://example.com/about\"/>\n\
+        </body>\n</opml>\n";
+
+    fn problem(text: &str) -> (Option<&'static str>, Option<usize>) {
+        let result = detail_opml_str(text, &ValidationOptions::default());
+        (result.error, result.line)
+    }
+
+    #[test]
+    fn test_valid_outlines() {
+        assert_eq!(
+            coerce_opml(&json!(OPML), &ValidationOptions::default()),
+            Some(json!({
+                "version": "2.0",
+                "title": "My subscriptions",
+                "outlines": 5,
+                "feeds": ["https://example.com/podcast.rss", "https://example.com/feed.atom"],
+            }))
+        );
+
+        // OPML 1.0 outlines may have a title rather than text, and the head may be empty
+        let old = "<opml version=\"1.0\"><head/><body><outline title=\"News\" xmlUrl=\"http://example.com/rss\"/></body></opml>";
+        assert_eq!(coerce_opml(&json!(old), &ValidationOptions::default()).unwrap()["feeds"], json!(["http://example.com/rss"]));
+        assert_eq!(problem(&old.replace("1.0", "2.0")), (Some(MISSING_ATTRIBUTE), Some(1)));
+    }
+
+    #[test]
+    fn test_structure() {
+        assert_eq!(problem(&OPML.replace("2.0", "3.0")), (Some(UNSUPPORTED_VERSION), Some(2)));
+        assert_eq!(problem(&OPML.replace(" version=\"2.0\"", "")), (Some(UNSUPPORTED_VERSION), Some(2)));
+        assert_eq!(problem("<opml version=\"2.0\"><body><outline text=\"A\"/></body></opml>"), (Some(MISSING_ELEMENT), Some(1)));
+        assert_eq!(problem("<opml version=\"2.0\">\n<head/>\n</opml>"), (Some(MISSING_ELEMENT), Some(1)));
+        assert_eq!(problem("<opml version=\"2.0\">\n<head/>\n<body>\n</body>\n</opml>"), (Some(MISSING_ELEMENT), Some(3)));
+        assert_eq!(problem("<rss version=\"2.0\"><channel/></rss>"), (Some(NOT_OPML), Some(1)));
+        // An OPML element in a namespace isn't OPML
+        assert_eq!(problem(&OPML.replace("<opml ", "<opml xmlns=\"http://example.com/\" ")), (Some(NOT_OPML), Some(2)));
+    }
+
+    #[test]
+    fn test_outlines() {
+        assert_eq!(problem(&OPML.replace("text=\"Example News\" ", "")), (Some(MISSING_ATTRIBUTE), Some(10)));
+        assert_eq!(problem(&OPML.replace(" xmlUrl=\"https://example.com/feed.atom\"", "")), (Some(MISSING_ATTRIBUTE), Some(10)));
+        assert_eq!(problem(&OPML.replace(" url=\"https://example.com/about\"", "")), (Some(MISSING_ATTRIBUTE), Some(13)));
+        assert_eq!(problem(&OPML.replace("https://example.com/feed.atom", "example.com/feed.atom")), (Some(INVALID_URL), Some(10)));
+        assert_eq!(problem(&OPML.replace("https://example.com/podcast\"", "/podcast\"")), (Some(INVALID_URL), Some(9)));
+        assert_eq!(problem(&OPML.replace("https://example.com/about", "about us")), (Some(INVALID_URL), Some(13)));
+    }
+
+    #[test]
+    fn test_xml_and_options() {
+        assert_eq!(problem(""), (None, None));
+        assert_eq!(problem(&OPML.replace("</outline>\n<outline", "</body>\n<outline")), (Some(xml::MISMATCHED_TAG), Some(11)));
+        assert_eq!(problem(&OPML.replace("Tech Talk\"", "Tech &amp Talk\"")), (Some(xml::INVALID_ENTITY), Some(9)));
+        assert_eq!(problem("https://example.com/podcast.rss"), (Some(xml::MALFORMED_XML), Some(1)));
+        assert!(!validate_opml(&json!(42), &ValidationOptions::default()));
+
+        let feeds: ValidationOptions = serde_json::from_value(json!({"require_feeds": true})).unwrap();
+        assert!(validate_opml(&json!(OPML), &feeds));
+        let links = "<opml version=\"2.0\"><head/><body><outline text=\"About\" type=\"link\" url=\"https://example.com/\"/></body></opml>";
+        assert!(validate_opml(&json!(links), &ValidationOptions::default()));
+        assert_eq!(detail_opml_str(links, &feeds).error, Some(MISSING_ELEMENT));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"require_feeds": "yes"})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": OPML, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let broken = OPML.replace("<opml", "<outline").replace("</opml>", "</outline>");
+        assert_eq!(
+            messages::localize(&detail_opml_str(&broken, &ValidationOptions::default()), &json!(broken), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "not_opml",
+                "line": 2,
+                "message": messages::message("not_opml", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_opml_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [OPML], "options": {"require_feeds": true}})).unwrap();
+        assert_eq!(batch.values.len(), 1);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [OPML, "", "<opml/>", "<opml version=\"2.0\"><head/><body/></opml>", "not xml"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
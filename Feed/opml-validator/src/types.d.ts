@@ -0,0 +1,44 @@
+// Types for the opml validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Reject an outline that subscribes to no feeds, as an import would have nothing to do (default: false) */
+    require_feeds?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    version: "1.0" | "1.1" | "2.0";
+    title: string;
+    outlines: number;
+    feeds: string[];
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The line the problem was found on, counting from 1 */
+    line?: number;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "malformed_xml" | "mismatched_tag" | "invalid_entity" | "not_opml" | "unsupported_version" | "missing_element" | "missing_attribute" | "invalid_url" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
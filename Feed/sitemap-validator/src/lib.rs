@@ -0,0 +1,482 @@
+// ABOUTME: WASM component for sitemap.xml URL sets, for SEO tooling that checks sites' sitemaps
+// ABOUTME: Checks the XML, the protocol's size limits and each URL's loc, lastmod, changefreq and priority
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// Parsing XML, shared with the feed and OPML validators; sitemaps have no attributes to read
+#[path = "../../../xml/xml.rs"]
+#[allow(dead_code)]
+mod xml;
+
+use xml::{Element, Problem};
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The host the sitemap is served from, such as "www.example.com"; search engines ignore
+    // URLs on other hosts
+    host: Option<String>,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The line the problem was found on, counting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, line: None }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from((code, line): Problem) -> Self {
+        ValidationResult { error: Some(code), line: Some(line), ..ValidationResult::from(None) }
+    }
+}
+
+// Error codes reported in detailed results, besides the XML parser's
+const NOT_A_SITEMAP: &str = "not_a_sitemap";
+const SITEMAP_TOO_LARGE: &str = "sitemap_too_large";
+const TOO_MANY_URLS: &str = "too_many_urls";
+const MISSING_ELEMENT: &str = "missing_element";
+const INVALID_URL: &str = "invalid_url";
+const HOST_MISMATCH: &str = "host_mismatch";
+const INVALID_DATE: &str = "invalid_date";
+const INVALID_CHANGEFREQ: &str = "invalid_changefreq";
+const INVALID_PRIORITY: &str = "invalid_priority";
+
+const SITEMAP: Option<&str> = Some("http://www.sitemaps.org/schemas/sitemap/0.9");
+
+// The protocol's limits: 50,000 URLs, 50 MiB uncompressed, and locs shorter than 2,048
+// characters
+const MAX_URLS: usize = 50_000;
+const MAX_BYTES: usize = 50 * 1024 * 1024;
+const MAX_LOC_LENGTH: usize = 2047;
+
+const CHANGEFREQS: &[&str] = &["always", "hourly", "daily", "weekly", "monthly", "yearly", "never"];
+
+// A page's absolute http or https URL, as the URL validator parses it
+fn parse_loc(text: &str) -> Option<Url> {
+    if text.chars().count() > MAX_LOC_LENGTH {
+        return None;
+    }
+    let only_web = json!({ "allowed_schemes": ["http", "https"] });
+    let url = url_validator::coerce_value(&Value::from(text), &only_web)?;
+    Url::parse(url.as_str()?).ok()
+}
+
+// A W3C datetime as search engines read it: a date, or a date and time with a UTC offset,
+// which the date and datetime validators check
+fn is_lastmod(text: &str) -> bool {
+    let value = Value::from(text);
+    if text.contains('T') {
+        datetime_validator::coerce_value(&value, &Value::Null).is_some()
+    } else {
+        date_validator::coerce_value(&value, &Value::Null).is_some()
+    }
+}
+
+// A priority from 0.0 to 1.0, written with a point
+fn is_priority(text: &str) -> bool {
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, "0"));
+    let digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    digits(whole) && digits(fraction) && text.parse::<f64>().is_ok_and(|priority| priority <= 1.0)
+}
+
+// One URL of the set: a loc on the sitemap's host, and optionally a lastmod, changefreq and
+// priority. Elements in other namespaces, such as Google's image and video extensions, are
+// ignored
+fn check_url(url: &Element, options: &ValidationOptions) -> Result<(), Problem> {
+    let loc = url.child(SITEMAP, "loc").ok_or((MISSING_ELEMENT, url.line))?;
+    let parsed = parse_loc(loc.text.trim()).ok_or((INVALID_URL, loc.line))?;
+    if let Some(host) = &options.host {
+        if !parsed.host_str().is_some_and(|loc_host| loc_host.eq_ignore_ascii_case(host.trim())) {
+            return Err((HOST_MISMATCH, loc.line));
+        }
+    }
+    check_text(url, "lastmod", is_lastmod, INVALID_DATE)?;
+    check_text(url, "changefreq", |text| CHANGEFREQS.contains(&text), INVALID_CHANGEFREQ)?;
+    check_text(url, "priority", is_priority, INVALID_PRIORITY)
+}
+
+// Checks the text of a url's optional child
+fn check_text(url: &Element, name: &str, valid: fn(&str) -> bool, code: &'static str) -> Result<(), Problem> {
+    match url.child(SITEMAP, name) {
+        Some(element) if !valid(element.text.trim()) => Err((code, element.line)),
+        _ => Ok(()),
+    }
+}
+
+// Checks a sitemap: a urlset in the sitemap namespace, within the protocol's limits, with at
+// least one url, each with a valid loc. Returns how many URLs it has and its size in bytes
+fn check_sitemap(text: &str, options: &ValidationOptions) -> Result<Value, Problem> {
+    // Checked before parsing, so an oversized file is never read into elements
+    if text.len() > MAX_BYTES {
+        return Err((SITEMAP_TOO_LARGE, 1));
+    }
+    let root = xml::parse(text)?;
+    if !root.is(SITEMAP, "urlset") {
+        return Err((NOT_A_SITEMAP, root.line));
+    }
+    let urls: Vec<&Element> = root.children(SITEMAP, "url").collect();
+    if urls.is_empty() {
+        return Err((MISSING_ELEMENT, root.line));
+    }
+    if urls.len() > MAX_URLS {
+        return Err((TOO_MANY_URLS, urls[MAX_URLS].line));
+    }
+    for url in &urls {
+        check_url(url, options)?;
+    }
+    Ok(json!({ "urls": urls.len(), "bytes": text.len() }))
+}
+
+fn detail_sitemap_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no elements, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_sitemap(text, options) {
+        Ok(summary) => ValidationResult::from(Some(summary)),
+        Err(problem) => ValidationResult::from(problem),
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_sitemap_str directly
+fn detail_sitemap(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_sitemap_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the number of URLs and the size in bytes
+fn coerce_sitemap(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_sitemap(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_sitemap(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_sitemap(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_sitemap(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_sitemap(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_sitemap(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_sitemap(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_sitemap(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_sitemap(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_sitemap(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_sitemap_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SITEMAP_XML: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\" xmlns:image=\"http://www.google.com/schemas/sitemap-image/1.1\">\n\
+        <url>\n<loc>https://www.example.com/</loc>\n<lastmod>2024-03-04</lastmod>\n<changefreq>daily</changefreq>\n<priority>1.0</priority>\n</url>\n\
+        <url>\n<loc>https://www.example.com/catalog?item=12&amp;desc=vacation_hawaii</loc>\n<lastmod>2024-03-04T18:30:02+00:00</lastmod>\n\
+        <image:image><image:loc>https://cdn.example.com/hawaii.jpg</image:loc></image:image>\n</url>\n\
+        <url><loc>http://www.example.com/about</loc><priority>0.5</priority></url>\n\
+        </urlset>\n";
+
+    fn problem(text: &str) -> (Option<&'static str>, Option<usize>) {
+        let result = detail_sitemap_str(text, &ValidationOptions::default());
+        (result.error, result.line)
+    }
+
+    // A urlset with this many URLs, one per line
+    fn urlset(count: usize) -> String {
+        let urls: String = (0..count).map(|n| format!("<url><loc>https://example.com/{n}</loc></url>\n")).collect();
+        format!("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n{urls}</urlset>")
+    }
+
+    #[test]
+    fn test_valid_sitemaps() {
+        assert_eq!(
+            coerce_sitemap(&json!(SITEMAP_XML), &ValidationOptions::default()),
+            Some(json!({"urls": 3, "bytes": SITEMAP_XML.len()}))
+        );
+        assert!(validate_sitemap(&json!(urlset(1)), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_urls() {
+        assert_eq!(problem(&SITEMAP_XML.replace("<loc>https://www.example.com/</loc>\n", "")), (Some(MISSING_ELEMENT), Some(3)));
+        assert_eq!(problem(&SITEMAP_XML.replace("https://www.example.com/<", "/<")), (Some(INVALID_URL), Some(4)));
+        assert_eq!(problem(&SITEMAP_XML.replace("https://www.example.com/<", "ftp://www.example.com/<")), (Some(INVALID_URL), Some(4)));
+        let long = format!("https://www.example.com/{}", "a".repeat(2047 - 24));
+        assert!(validate_sitemap(&json!(SITEMAP_XML.replace("https://www.example.com/<", &format!("{long}<"))), &ValidationOptions::default()));
+        assert_eq!(problem(&SITEMAP_XML.replace("https://www.example.com/<", &format!("{long}a<"))), (Some(INVALID_URL), Some(4)));
+
+        assert_eq!(problem(&SITEMAP_XML.replace("2024-03-04<", "2024-02-30<")), (Some(INVALID_DATE), Some(5)));
+        assert_eq!(problem(&SITEMAP_XML.replace("2024-03-04<", "04/03/2024<")), (Some(INVALID_DATE), Some(5)));
+        assert_eq!(problem(&SITEMAP_XML.replace("18:30:02+00:00", "18:30:02")), (Some(INVALID_DATE), Some(11)));
+        assert_eq!(problem(&SITEMAP_XML.replace("daily", "fortnightly")), (Some(INVALID_CHANGEFREQ), Some(6)));
+        assert_eq!(problem(&SITEMAP_XML.replace("daily", "Daily")), (Some(INVALID_CHANGEFREQ), Some(6)));
+        for bad in ["1.1", "-0.5", "high", "0.", ".5", "1e-1", ""] {
+            assert_eq!(problem(&SITEMAP_XML.replace("1.0<", &format!("{bad}<"))), (Some(INVALID_PRIORITY), Some(7)), "{bad}");
+        }
+        for good in ["0.0", "0", "1", "0.25"] {
+            assert!(validate_sitemap(&json!(SITEMAP_XML.replace("1.0<", &format!("{good}<"))), &ValidationOptions::default()), "{good}");
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        assert!(validate_sitemap(&json!(urlset(MAX_URLS)), &ValidationOptions::default()));
+        assert_eq!(problem(&urlset(MAX_URLS + 1)), (Some(TOO_MANY_URLS), Some(MAX_URLS + 2)));
+
+        let padded = SITEMAP_XML.replace("</urlset>", &format!("<!-- {} --></urlset>", " ".repeat(MAX_BYTES - SITEMAP_XML.len() - 9)));
+        assert_eq!(padded.len(), MAX_BYTES);
+        assert!(validate_sitemap(&json!(padded), &ValidationOptions::default()));
+        assert_eq!(problem(&padded.replace("<!-- ", "<!--  ")), (Some(SITEMAP_TOO_LARGE), Some(1)));
+    }
+
+    #[test]
+    fn test_xml_and_options() {
+        assert_eq!(problem(""), (None, None));
+        assert_eq!(problem("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n</urlset>"), (Some(MISSING_ELEMENT), Some(1)));
+        // A sitemap index, or a urlset outside the sitemap namespace, isn't a URL set
+        assert_eq!(problem("<sitemapindex xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"><sitemap><loc>https://example.com/a.xml</loc></sitemap></sitemapindex>"), (Some(NOT_A_SITEMAP), Some(1)));
+        assert_eq!(problem(&SITEMAP_XML.replace(" xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\"", "")), (Some(NOT_A_SITEMAP), Some(2)));
+        assert_eq!(problem(&SITEMAP_XML.replace("&amp;", "&")), (Some(xml::INVALID_ENTITY), Some(10)));
+        assert_eq!(problem("https://www.example.com/"), (Some(xml::MALFORMED_XML), Some(1)));
+        assert!(!validate_sitemap(&json!(42), &ValidationOptions::default()));
+
+        let host: ValidationOptions = serde_json::from_value(json!({"host": "WWW.example.com"})).unwrap();
+        assert!(validate_sitemap(&json!(SITEMAP_XML), &host));
+        let result = detail_sitemap_str(&SITEMAP_XML.replace("http://www.example.com/about", "http://example.com/about"), &host);
+        assert_eq!((result.error, result.line), (Some(HOST_MISMATCH), Some(14)));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"host": ["example.com"]})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": SITEMAP_XML, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let broken = SITEMAP_XML.replace("weekly", "daily").replace("daily", "sometimes");
+        assert_eq!(
+            messages::localize(&detail_sitemap_str(&broken, &ValidationOptions::default()), &json!(broken), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "invalid_changefreq",
+                "line": 6,
+                "message": messages::message("invalid_changefreq", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_sitemap_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [SITEMAP_XML], "options": {"host": "www.example.com"}})).unwrap();
+        assert_eq!(batch.values.len(), 1);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [SITEMAP_XML, "", "<urlset/>", "not xml"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
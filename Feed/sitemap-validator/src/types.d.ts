@@ -0,0 +1,42 @@
+// Types for the sitemap validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The host the sitemap is served from, such as "www.example.com"; URLs on other hosts are rejected, as search engines ignore them */
+    host?: string;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    urls: number;
+    bytes: number;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The line the problem was found on, counting from 1 */
+    line?: number;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "malformed_xml" | "mismatched_tag" | "invalid_entity" | "not_a_sitemap" | "sitemap_too_large" | "too_many_urls" | "missing_element" | "invalid_url" | "host_mismatch" | "invalid_date" | "invalid_changefreq" | "invalid_priority" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
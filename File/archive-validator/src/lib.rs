@@ -0,0 +1,695 @@
+// ABOUTME: WASM component for ZIP archives, checking their local headers and central directory from the bytes
+// ABOUTME: Flags entries that would escape the extraction directory or expand to huge sizes, without extracting
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// The value type, shared with the other validators that take raw bytes
+#[path = "../../../bytes/bytes.rs"]
+mod bytes;
+
+use bytes::Bytes;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+
+    #[wasm_bindgen(typescript_type = "ValidationOptions")]
+    pub type JsValidationOptions;
+}
+
+#[derive(Deserialize)]
+pub struct ValidationInput {
+    value: Bytes,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Most entries accepted, files and directories alike
+    max_entries: u64,
+    // Largest uncompressed size accepted for one entry, in bytes, as the archive declares it
+    max_entry_size: u64,
+    // Largest uncompressed size accepted for all entries together, in bytes
+    max_total_size: u64,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            max_entries: 10_000,
+            max_entry_size: 1 << 30,
+            max_total_size: 4 << 30,
+        }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize)]
+pub struct BatchInput {
+    values: Vec<Bytes>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The name of the entry that was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+            entry: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const MALFORMED: &str = "malformed";
+const TOO_MANY_ENTRIES: &str = "too_many_entries";
+const ABSOLUTE_PATH: &str = "absolute_path";
+const PATH_TRAVERSAL: &str = "path_traversal";
+const ENTRY_TOO_LARGE: &str = "entry_too_large";
+const ARCHIVE_TOO_LARGE: &str = "archive_too_large";
+
+// Record signatures, and the value a field holds when the real one is in a ZIP64 record
+const LOCAL_HEADER: u32 = 0x0403_4B50;
+const CENTRAL_HEADER: u32 = 0x0201_4B50;
+const END_OF_DIRECTORY: u32 = 0x0605_4B50;
+const ZIP64_END_OF_DIRECTORY: u32 = 0x0606_4B50;
+const ZIP64_LOCATOR: u32 = 0x0706_4B50;
+const ZIP64_EXTRA: u16 = 0x0001;
+const IN_ZIP64: u32 = u32::MAX;
+
+// Little-endian fields at an offset; None past the end of the bytes
+fn u16_at(bytes: &[u8], at: u64) -> Option<u16> {
+    let at = usize::try_from(at).ok()?;
+    Some(u16::from_le_bytes(bytes.get(at..at.checked_add(2)?)?.try_into().ok()?))
+}
+
+fn u32_at(bytes: &[u8], at: u64) -> Option<u32> {
+    let at = usize::try_from(at).ok()?;
+    Some(u32::from_le_bytes(bytes.get(at..at.checked_add(4)?)?.try_into().ok()?))
+}
+
+fn u64_at(bytes: &[u8], at: u64) -> Option<u64> {
+    let at = usize::try_from(at).ok()?;
+    Some(u64::from_le_bytes(bytes.get(at..at.checked_add(8)?)?.try_into().ok()?))
+}
+
+fn slice_at(bytes: &[u8], at: u64, len: u64) -> Option<&[u8]> {
+    let at = usize::try_from(at).ok()?;
+    bytes.get(at..at.checked_add(usize::try_from(len).ok()?)?)
+}
+
+// Where the central directory is and how many entries it holds, from the end of central
+// directory record and, for archives beyond the classic limits, its ZIP64 counterpart
+struct Directory {
+    entries: u64,
+    offset: u64,
+    size: u64,
+}
+
+fn find_directory(bytes: &[u8]) -> Option<Directory> {
+    // The record is 22 bytes followed by a comment of up to 65535, which must run to the end,
+    // so that a signature inside the comment isn't taken for the record
+    let len = bytes.len() as u64;
+    let last = len.checked_sub(22)?;
+    let end = (last.saturating_sub(0xFFFF)..=last).rev().find(|&at| {
+        u32_at(bytes, at) == Some(END_OF_DIRECTORY) && u16_at(bytes, at + 20).map(u64::from) == Some(len - at - 22)
+    })?;
+
+    // Archives split across disks aren't supported
+    if u16_at(bytes, end + 4)? != 0 || u16_at(bytes, end + 6)? != 0 {
+        return None;
+    }
+    let entries = u16_at(bytes, end + 10)?;
+    let size = u32_at(bytes, end + 12)?;
+    let offset = u32_at(bytes, end + 16)?;
+    if entries != u16::MAX && size != IN_ZIP64 && offset != IN_ZIP64 {
+        return Some(Directory { entries: entries.into(), offset: offset.into(), size: size.into() });
+    }
+
+    let locator = end.checked_sub(20)?;
+    if u32_at(bytes, locator)? != ZIP64_LOCATOR {
+        return None;
+    }
+    let record = u64_at(bytes, locator + 8)?;
+    if record >= locator || u32_at(bytes, record)? != ZIP64_END_OF_DIRECTORY {
+        return None;
+    }
+    Some(Directory {
+        entries: u64_at(bytes, record + 32)?,
+        size: u64_at(bytes, record + 40)?,
+        offset: u64_at(bytes, record + 48)?,
+    })
+}
+
+// One central directory entry, with any ZIP64 sizes and offset filled in
+struct Entry<'a> {
+    name: &'a [u8],
+    compressed_size: u64,
+    uncompressed_size: u64,
+    local_offset: u64,
+}
+
+// The entry starting at an offset in the central directory, and the offset of the next
+fn read_entry(bytes: &[u8], at: u64) -> Option<(Entry<'_>, u64)> {
+    if u32_at(bytes, at)? != CENTRAL_HEADER {
+        return None;
+    }
+    let name_len = u64::from(u16_at(bytes, at + 28)?);
+    let extra_len = u64::from(u16_at(bytes, at + 30)?);
+    let comment_len = u64::from(u16_at(bytes, at + 32)?);
+    let name = slice_at(bytes, at + 46, name_len)?;
+    let extra = slice_at(bytes, at + 46 + name_len, extra_len)?;
+    slice_at(bytes, at + 46 + name_len + extra_len, comment_len)?;
+
+    // Fields too large for 32 bits are in the ZIP64 extra field, in this order
+    let mut fields = [u32_at(bytes, at + 24)?, u32_at(bytes, at + 20)?, u32_at(bytes, at + 42)?].map(u64::from);
+    if fields.contains(&IN_ZIP64.into()) {
+        let mut zip64 = zip64_extra(extra)?.chunks_exact(8);
+        for field in fields.iter_mut().filter(|field| **field == u64::from(IN_ZIP64)) {
+            *field = u64::from_le_bytes(zip64.next()?.try_into().ok()?);
+        }
+    }
+    let [uncompressed_size, compressed_size, local_offset] = fields;
+
+    let entry = Entry {
+        name,
+        compressed_size,
+        uncompressed_size,
+        local_offset,
+    };
+    Some((entry, at + 46 + name_len + extra_len + comment_len))
+}
+
+// The data of the ZIP64 extended information field among an entry's extra fields
+fn zip64_extra(mut extra: &[u8]) -> Option<&[u8]> {
+    while extra.len() >= 4 {
+        let id = u16::from_le_bytes([extra[0], extra[1]]);
+        let len = usize::from(u16::from_le_bytes([extra[2], extra[3]]));
+        let data = extra.get(4..4 + len)?;
+        if id == ZIP64_EXTRA {
+            return Some(data);
+        }
+        extra = &extra[4 + len..];
+    }
+    None
+}
+
+// Whether an entry's local header agrees with the central directory about its name, and its
+// data ends before the central directory starts
+fn local_header_matches(bytes: &[u8], entry: &Entry, directory: &Directory) -> bool {
+    let at = entry.local_offset;
+    let matches = || {
+        let name_len = u64::from(u16_at(bytes, at + 26)?);
+        let extra_len = u64::from(u16_at(bytes, at + 28)?);
+        let data_end = (at + 30 + name_len + extra_len).checked_add(entry.compressed_size)?;
+        Some(
+            u32_at(bytes, at)? == LOCAL_HEADER
+                && slice_at(bytes, at + 30, name_len)? == entry.name
+                && data_end <= directory.offset,
+        )
+    };
+    at < directory.offset && matches() == Some(true)
+}
+
+// Names that would be written outside the extraction directory: absolute paths, including
+// Windows drive letters, and paths with ".." segments; extractors on Windows also split at "\"
+fn is_absolute(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    name.starts_with(['/', '\\']) || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+fn is_traversal(name: &str) -> bool {
+    name.split(['/', '\\']).any(|segment| segment == "..")
+}
+
+fn detail_archive(bytes: &[u8], options: &ValidationOptions) -> ValidationResult {
+    let failure = |error, entry: Option<&str>| ValidationResult {
+        error: Some(error),
+        entry: entry.map(str::to_string),
+        ..ValidationResult::from(None)
+    };
+
+    let directory = match find_directory(bytes) {
+        Some(directory) if directory.offset.checked_add(directory.size).is_some_and(|end| end <= bytes.len() as u64) => directory,
+        _ => return failure(MALFORMED, None),
+    };
+    // Checked before the entries are read, so that a huge directory isn't walked
+    if directory.entries > options.max_entries {
+        return failure(TOO_MANY_ENTRIES, None);
+    }
+
+    let mut at = directory.offset;
+    let mut total: u64 = 0;
+    for _ in 0..directory.entries {
+        let (entry, next) = match read_entry(bytes, at) {
+            Some(read) if read.1 <= directory.offset + directory.size => read,
+            _ => return failure(MALFORMED, None),
+        };
+        // Names are UTF-8 or, in old archives, code page 437, which agree on the separators and "."
+        let name = String::from_utf8_lossy(entry.name);
+        if !local_header_matches(bytes, &entry, &directory) {
+            return failure(MALFORMED, Some(&name));
+        }
+        if is_absolute(&name) {
+            return failure(ABSOLUTE_PATH, Some(&name));
+        }
+        if is_traversal(&name) {
+            return failure(PATH_TRAVERSAL, Some(&name));
+        }
+        if entry.uncompressed_size > options.max_entry_size {
+            return failure(ENTRY_TOO_LARGE, Some(&name));
+        }
+        total = total.saturating_add(entry.uncompressed_size);
+        if total > options.max_total_size {
+            return failure(ARCHIVE_TOO_LARGE, Some(&name));
+        }
+        at = next;
+    }
+    if at != directory.offset + directory.size {
+        return failure(MALFORMED, None);
+    }
+
+    ValidationResult::from(Some(json!({ "entries": directory.entries, "uncompressed_size": total })))
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the number of entries and their total uncompressed size, as the archive declares it
+fn coerce_archive(bytes: &[u8], options: &ValidationOptions) -> Option<Value> {
+    let result = detail_archive(bytes, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_archive(bytes: &[u8], options: &ValidationOptions) -> bool {
+    coerce_archive(bytes, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_archive(&Bytes::deserialize(value).ok()?.0, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    let result = match Bytes::deserialize(value) {
+        Ok(bytes) => detail_archive(&bytes.0, &options),
+        Err(_) => ValidationResult::from(None),
+    };
+    serde_json::to_value(result).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_archive(&input_obj.value.0, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_archive(&input_obj.value.0, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid. Every failure of bytes
+    // has its own error code, so the input needn't be kept to choose one
+    let (result, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_archive(&input_obj.value.0, &input_obj.options), input_obj.locale),
+        Err(_) => (ValidationResult::from(None), None),
+    };
+
+    to_js(&messages::localize(&result, &Value::Null, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|bytes| JsValue::from_bool(validate_archive(&bytes.0, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|bytes| {
+            let result = detail_archive(&bytes.0, &batch.options);
+            to_js(&messages::localize(&result, &Value::Null, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for bytes: a Uint8Array is copied straight into WASM memory rather than into an
+// input object, element by element. Options are a separate, optional argument, and options
+// that don't deserialize make the bytes invalid
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_bytes(bytes: &[u8], options: Option<JsValidationOptions>) -> bool {
+    let options = match options {
+        Some(options) => match serde_wasm_bindgen::from_value(options.into()) {
+            Ok(options) => options,
+            Err(_) => return false,
+        },
+        None => ValidationOptions::default(),
+    };
+
+    validate_archive(bytes, &options)
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A ZIP archive of empty stored entries, each declaring an uncompressed size; sizes too
+    // large for 32 bits go in a ZIP64 extra field
+    fn zip(entries: &[(&str, u64)]) -> Vec<u8> {
+        let mut local = Vec::new();
+        let mut central = Vec::new();
+        for (name, size) in entries {
+            let offset = local.len() as u32;
+            let (size32, extra) = match u32::try_from(*size) {
+                Ok(size) if size != u32::MAX => (size, Vec::new()),
+                _ => (u32::MAX, [&1u16.to_le_bytes()[..], &8u16.to_le_bytes(), &size.to_le_bytes()].concat()),
+            };
+
+            local.extend(LOCAL_HEADER.to_le_bytes());
+            local.extend([20, 0, 0, 0, 0, 0, 0, 0, 0x21, 0, 0, 0, 0, 0]);
+            local.extend(0u32.to_le_bytes());
+            local.extend(size32.to_le_bytes());
+            local.extend((name.len() as u16).to_le_bytes());
+            local.extend(0u16.to_le_bytes());
+            local.extend(name.as_bytes());
+
+            central.extend(CENTRAL_HEADER.to_le_bytes());
+            central.extend([20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0x21, 0, 0, 0, 0, 0]);
+            central.extend(0u32.to_le_bytes());
+            central.extend(size32.to_le_bytes());
+            central.extend((name.len() as u16).to_le_bytes());
+            central.extend((extra.len() as u16).to_le_bytes());
+            central.extend([0; 10]);
+            central.extend(offset.to_le_bytes());
+            central.extend(name.as_bytes());
+            central.extend(extra);
+        }
+
+        let mut archive = local;
+        let directory_offset = archive.len() as u32;
+        let directory_size = central.len() as u32;
+        archive.extend(central);
+        archive.extend(END_OF_DIRECTORY.to_le_bytes());
+        archive.extend([0; 4]);
+        archive.extend((entries.len() as u16).to_le_bytes());
+        archive.extend((entries.len() as u16).to_le_bytes());
+        archive.extend(directory_size.to_le_bytes());
+        archive.extend(directory_offset.to_le_bytes());
+        archive.extend(0u16.to_le_bytes());
+        archive
+    }
+
+    #[test]
+    fn test_valid_archives() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_archive(&zip(&[]), &defaults), Some(json!({"entries": 0, "uncompressed_size": 0})));
+        assert_eq!(
+            coerce_archive(&zip(&[("docs/", 0), ("docs/readme.txt", 120), ("photo.jpg", 4000)]), &defaults),
+            Some(json!({"entries": 3, "uncompressed_size": 4120}))
+        );
+        // Names that merely contain dots stay inside the extraction directory
+        assert!(validate_archive(&zip(&[("..config", 1), ("a..b/c...", 1), (".hidden/.", 1)]), &defaults));
+
+        // An archive comment follows the end of central directory record
+        let mut commented = zip(&[("a.txt", 5)]);
+        let len = commented.len();
+        commented[len - 2..].copy_from_slice(&7u16.to_le_bytes());
+        commented.extend(b"comment");
+        assert!(validate_archive(&commented, &defaults));
+
+        // Archives written by other tools
+        for form in description()["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_malformed() {
+        let defaults = ValidationOptions::default();
+        let archive = zip(&[("a.txt", 5), ("b.txt", 5)]);
+
+        assert_eq!(detail_archive(b"", &defaults).error, Some(MALFORMED));
+        assert_eq!(detail_archive(b"%PDF-1.7", &defaults).error, Some(MALFORMED));
+        assert_eq!(detail_archive(&archive[..archive.len() - 1], &defaults).error, Some(MALFORMED));
+        // Bytes after the record that the comment length doesn't account for
+        assert_eq!(detail_archive(&[&archive[..], b"junk"].concat(), &defaults).error, Some(MALFORMED));
+        // A truncated central directory, and one claiming more entries than it holds
+        assert_eq!(detail_archive(&archive[archive.len() - 60..], &defaults).error, Some(MALFORMED));
+        let mut overcounted = archive.clone();
+        let end = overcounted.len() - 22;
+        overcounted[end + 10] = 3;
+        assert_eq!(detail_archive(&overcounted, &defaults).error, Some(MALFORMED));
+
+        // A local header whose name disagrees with the central directory
+        let mut renamed = archive.clone();
+        renamed[30] = b'x';
+        let result = detail_archive(&renamed, &defaults);
+        assert_eq!((result.error, result.entry.as_deref()), (Some(MALFORMED), Some("a.txt")));
+
+        // Archives split across disks
+        let mut spanned = archive.clone();
+        spanned[end + 4] = 1;
+        assert_eq!(detail_archive(&spanned, &defaults).error, Some(MALFORMED));
+    }
+
+    #[test]
+    fn test_dangerous_entries() {
+        let defaults = ValidationOptions::default();
+        let rejected = |name: &str| {
+            let result = detail_archive(&zip(&[("ok.txt", 1), (name, 1)]), &defaults);
+            assert_eq!(result.entry.as_deref(), Some(name));
+            result.error
+        };
+
+        assert_eq!(rejected("../etc/passwd"), Some(PATH_TRAVERSAL));
+        assert_eq!(rejected("docs/../../etc/passwd"), Some(PATH_TRAVERSAL));
+        assert_eq!(rejected("docs\\..\\..\\boot.ini"), Some(PATH_TRAVERSAL));
+        assert_eq!(rejected("docs/.."), Some(PATH_TRAVERSAL));
+        assert_eq!(rejected("/etc/cron.d/job"), Some(ABSOLUTE_PATH));
+        assert_eq!(rejected("\\\\server\\share\\file"), Some(ABSOLUTE_PATH));
+        assert_eq!(rejected("C:\\Windows\\System32\\evil.dll"), Some(ABSOLUTE_PATH));
+        assert_eq!(rejected("c:relative.txt"), Some(ABSOLUTE_PATH));
+    }
+
+    #[test]
+    fn test_options() {
+        let defaults = ValidationOptions::default();
+
+        // Declared sizes are checked without extracting, entry by entry and in total
+        let result = detail_archive(&zip(&[("small", 1), ("bomb.bin", 2 << 30)]), &defaults);
+        assert_eq!((result.error, result.entry.as_deref()), (Some(ENTRY_TOO_LARGE), Some("bomb.bin")));
+        let parts: Vec<(String, u64)> = (0..5).map(|i| (format!("part{i}"), 1 << 30)).collect();
+        let parts: Vec<(&str, u64)> = parts.iter().map(|(name, size)| (name.as_str(), *size)).collect();
+        let result = detail_archive(&zip(&parts), &defaults);
+        assert_eq!((result.error, result.entry.as_deref()), (Some(ARCHIVE_TOO_LARGE), Some("part4")));
+        assert!(validate_archive(&zip(&parts[..4]), &defaults));
+
+        let tight: ValidationOptions =
+            serde_json::from_value(json!({"max_entries": 2, "max_entry_size": 100, "max_total_size": 150})).unwrap();
+        assert!(validate_archive(&zip(&[("a", 100), ("b", 50)]), &tight));
+        assert_eq!(detail_archive(&zip(&[("a", 101)]), &tight).error, Some(ENTRY_TOO_LARGE));
+        assert_eq!(detail_archive(&zip(&[("a", 100), ("b", 51)]), &tight).error, Some(ARCHIVE_TOO_LARGE));
+        let result = detail_archive(&zip(&[("a", 1), ("b", 1), ("c", 1)]), &tight);
+        assert_eq!((result.error, result.entry), (Some(TOO_MANY_ENTRIES), None));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"max_entries": -1})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": [], "options": {"max_size": 1}})).is_err());
+    }
+
+    #[test]
+    fn test_zip64() {
+        // A size beyond 32 bits is read from the entry's ZIP64 extra field
+        let archive = zip(&[("huge.iso", 5 << 30)]);
+        assert_eq!(detail_archive(&archive, &ValidationOptions::default()).error, Some(ENTRY_TOO_LARGE));
+        let roomy: ValidationOptions =
+            serde_json::from_value(json!({"max_entry_size": 8u64 << 30, "max_total_size": 8u64 << 30})).unwrap();
+        assert_eq!(coerce_archive(&archive, &roomy), Some(json!({"entries": 1, "uncompressed_size": 5u64 << 30})));
+
+        // A field marked as being in the extra field, without one, is malformed
+        let mut missing = zip(&[("a", 1)]);
+        let central = missing.len() - 22 - 47;
+        missing[central + 24..central + 28].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(detail_archive(&missing, &roomy).error, Some(MALFORMED));
+    }
+
+    #[test]
+    fn test_input() {
+        // Bytes arrive as arrays of numbers from JSON; anything else is not bytes
+        assert_eq!(coerce_value(&json!(zip(&[])), &Value::Null), Some(json!({"entries": 0, "uncompressed_size": 0})));
+        assert_eq!(coerce_value(&json!([80, 75, 256]), &Value::Null), None);
+        assert_eq!(coerce_value(&json!("PK"), &Value::Null), None);
+        assert_eq!(coerce_value(&json!(zip(&[("a", 2)])), &json!({"max_entry_size": 1})), None);
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [zip(&[]), []], "options": {"max_entries": 1}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_archive(&zip(&[("a", 2)]), &defaults),
+            ValidationResult::from(Some(json!({"entries": 1, "uncompressed_size": 2})))
+        );
+        assert_eq!(
+            messages::localize(&detail_archive(&zip(&[("../x", 1)]), &defaults), &Value::Null, Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "path_traversal",
+                "entry": "../x",
+                "message": messages::message("path_traversal", "en").unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is the default
+        // used here; each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let options = ValidationOptions::deserialize(&json!({ name.as_str(): option["default"] })).unwrap();
+            assert_eq!(serde_json::to_value(&options).unwrap(), serde_json::to_value(ValidationOptions::default()).unwrap());
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The bytes path agrees with the generic path under the default options
+        let samples = [zip(&[]), zip(&[("a", 1)]), zip(&[("/a", 1)]), b"PK".to_vec(), Vec::new()];
+        for bytes in samples {
+            assert_eq!(validate_bytes(&bytes, None), coerce_value(&json!(bytes), &Value::Null).is_some(), "{bytes:?}");
+        }
+    }
+}
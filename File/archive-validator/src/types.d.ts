@@ -0,0 +1,46 @@
+// Types for the archive validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Most entries accepted, files and directories alike (default: 10000) */
+    max_entries?: number;
+    /** Largest uncompressed size accepted for one entry, in bytes, as the archive declares it (default: 1073741824) */
+    max_entry_size?: number;
+    /** Largest uncompressed size accepted for all entries together, in bytes (default: 4294967296) */
+    max_total_size?: number;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = Uint8Array | ArrayBuffer | number[];
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    entries: number;
+    uncompressed_size: number;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The name of the entry that was rejected */
+    entry?: string;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "malformed" | "too_many_entries" | "absolute_path" | "path_traversal" | "entry_too_large" | "archive_too_large" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
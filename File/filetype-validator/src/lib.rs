@@ -0,0 +1,512 @@
+// ABOUTME: WASM component for uploaded files, identified by the magic number in their first bytes
+// ABOUTME: Checks the detected type against an allowlist and against the MIME type the upload declared
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// The value type, shared with the other validators that take raw bytes
+#[path = "../../../bytes/bytes.rs"]
+mod bytes;
+
+use bytes::Bytes;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+
+    #[wasm_bindgen(typescript_type = "ValidationOptions")]
+    pub type JsValidationOptions;
+}
+
+#[derive(Deserialize)]
+pub struct ValidationInput {
+    value: Bytes,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // File types to accept; any recognized type when unset
+    types: Option<Vec<FileType>>,
+    // The MIME type the upload was declared as, such as its Content-Type; the file's contents
+    // must match it
+    declared_type: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileType {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Bmp,
+    Tiff,
+    Pdf,
+    Zip,
+    Gzip,
+    Mp4,
+}
+
+// Brands of ISO base media files that are images or QuickTime movies rather than MP4
+const NON_MP4_BRANDS: [&[u8]; 9] = [b"heic", b"heix", b"hevc", b"hevx", b"mif1", b"msf1", b"avif", b"avis", b"qt  "];
+
+impl FileType {
+    const ALL: [FileType; 10] = [
+        FileType::Png,
+        FileType::Jpeg,
+        FileType::Gif,
+        FileType::Webp,
+        FileType::Bmp,
+        FileType::Tiff,
+        FileType::Pdf,
+        FileType::Zip,
+        FileType::Gzip,
+        FileType::Mp4,
+    ];
+
+    // The MIME types a file of this type may be declared as; the first is its own
+    fn mime_types(self) -> &'static [&'static str] {
+        match self {
+            FileType::Png => &["image/png"],
+            FileType::Jpeg => &["image/jpeg", "image/jpg", "image/pjpeg"],
+            FileType::Gif => &["image/gif"],
+            FileType::Webp => &["image/webp"],
+            FileType::Bmp => &["image/bmp", "image/x-bmp", "image/x-ms-bmp"],
+            FileType::Tiff => &["image/tiff"],
+            FileType::Pdf => &["application/pdf"],
+            // Office documents, EPUB books and Java archives are ZIP files too
+            FileType::Zip => &[
+                "application/zip",
+                "application/x-zip-compressed",
+                "application/epub+zip",
+                "application/java-archive",
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+                "application/vnd.oasis.opendocument.text",
+                "application/vnd.oasis.opendocument.spreadsheet",
+                "application/vnd.oasis.opendocument.presentation",
+            ],
+            FileType::Gzip => &["application/gzip", "application/x-gzip"],
+            FileType::Mp4 => &["video/mp4", "audio/mp4", "application/mp4"],
+        }
+    }
+
+    // Whether the bytes start with this type's magic number
+    fn matches(self, bytes: &[u8]) -> bool {
+        match self {
+            FileType::Png => bytes.starts_with(b"\x89PNG\r\n\x1A\n"),
+            FileType::Jpeg => bytes.starts_with(&[0xFF, 0xD8, 0xFF]),
+            FileType::Gif => bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a"),
+            FileType::Webp => bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP"),
+            // "BM" also starts plenty of text, so the header's reserved fields must be zero too
+            FileType::Bmp => bytes.starts_with(b"BM") && bytes.get(6..10) == Some(&[0; 4]),
+            FileType::Tiff => bytes.starts_with(b"II*\0") || bytes.starts_with(b"MM\0*"),
+            FileType::Pdf => bytes.starts_with(b"%PDF-"),
+            // A local file header, or the end of central directory record of an empty archive
+            FileType::Zip => bytes.starts_with(b"PK\x03\x04") || bytes.starts_with(b"PK\x05\x06"),
+            FileType::Gzip => bytes.starts_with(&[0x1F, 0x8B, 0x08]),
+            // An ISO base media file: a box of type "ftyp" first, whose major brand follows
+            FileType::Mp4 => {
+                bytes.get(4..8) == Some(b"ftyp")
+                    && bytes.get(8..12).is_some_and(|brand| !NON_MP4_BRANDS.contains(&brand))
+            }
+        }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize)]
+pub struct BatchInput {
+    values: Vec<Bytes>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The type the file was recognized as, when it was rejected for its type
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    file_type: Option<FileType>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+            file_type: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const UNRECOGNIZED_TYPE: &str = "unrecognized_type";
+const UNSUPPORTED_TYPE: &str = "unsupported_type";
+const MIME_MISMATCH: &str = "mime_mismatch";
+
+// A declared MIME type without its parameters, in lowercase; an empty declaration or
+// application/octet-stream, which browsers send for files they can't identify, declares nothing
+fn declared_mime(declared: &str) -> Option<String> {
+    let essence = declared.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    (!essence.is_empty() && essence != "application/octet-stream").then_some(essence)
+}
+
+fn detail_filetype(bytes: &[u8], options: &ValidationOptions) -> ValidationResult {
+    let file_type = match FileType::ALL.into_iter().find(|file_type| file_type.matches(bytes)) {
+        Some(file_type) => file_type,
+        None => return ValidationResult { error: Some(UNRECOGNIZED_TYPE), ..ValidationResult::from(None) },
+    };
+    let failure = |error| ValidationResult {
+        error: Some(error),
+        file_type: Some(file_type),
+        ..ValidationResult::from(None)
+    };
+
+    if options.types.as_ref().is_some_and(|types| !types.contains(&file_type)) {
+        return failure(UNSUPPORTED_TYPE);
+    }
+    if let Some(declared) = options.declared_type.as_deref().and_then(declared_mime) {
+        if !file_type.mime_types().contains(&declared.as_str()) {
+            return failure(MIME_MISMATCH);
+        }
+    }
+
+    ValidationResult::from(Some(json!({ "type": file_type, "mime": file_type.mime_types()[0] })))
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the file's type and its MIME type
+fn coerce_filetype(bytes: &[u8], options: &ValidationOptions) -> Option<Value> {
+    let result = detail_filetype(bytes, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_filetype(bytes: &[u8], options: &ValidationOptions) -> bool {
+    coerce_filetype(bytes, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_filetype(&Bytes::deserialize(value).ok()?.0, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    let result = match Bytes::deserialize(value) {
+        Ok(bytes) => detail_filetype(&bytes.0, &options),
+        Err(_) => ValidationResult::from(None),
+    };
+    serde_json::to_value(result).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_filetype(&input_obj.value.0, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_filetype(&input_obj.value.0, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid. Every failure of bytes
+    // has its own error code, so the input needn't be kept to choose one
+    let (result, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_filetype(&input_obj.value.0, &input_obj.options), input_obj.locale),
+        Err(_) => (ValidationResult::from(None), None),
+    };
+
+    to_js(&messages::localize(&result, &Value::Null, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|bytes| JsValue::from_bool(validate_filetype(&bytes.0, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|bytes| {
+            let result = detail_filetype(&bytes.0, &batch.options);
+            to_js(&messages::localize(&result, &Value::Null, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for bytes: a Uint8Array is copied straight into WASM memory rather than into an
+// input object, element by element. Options are a separate, optional argument, and options
+// that don't deserialize make the bytes invalid
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_bytes(bytes: &[u8], options: Option<JsValidationOptions>) -> bool {
+    let options = match options {
+        Some(options) => match serde_wasm_bindgen::from_value(options.into()) {
+            Ok(options) => options,
+            Err(_) => return false,
+        },
+        None => ValidationOptions::default(),
+    };
+
+    validate_filetype(bytes, &options)
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1A\n\0\0\0\x0DIHDR";
+    const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F'];
+    const PDF: &[u8] = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3";
+    const ZIP: &[u8] = b"PK\x03\x04\x14\x00\x00\x00";
+    const MP4: &[u8] = b"\0\0\0\x20ftypisom\0\0\x02\0";
+
+    #[test]
+    fn test_valid_types() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_filetype(PNG, &defaults), Some(json!({"type": "png", "mime": "image/png"})));
+        assert_eq!(coerce_filetype(JPEG, &defaults), Some(json!({"type": "jpeg", "mime": "image/jpeg"})));
+        assert_eq!(coerce_filetype(b"GIF89a\x01\0", &defaults), Some(json!({"type": "gif", "mime": "image/gif"})));
+        assert_eq!(coerce_filetype(b"RIFF\x24\0\0\0WEBPVP8 ", &defaults), Some(json!({"type": "webp", "mime": "image/webp"})));
+        assert_eq!(coerce_filetype(b"BM\x3A\0\0\0\0\0\0\0\x36\0", &defaults), Some(json!({"type": "bmp", "mime": "image/bmp"})));
+        assert_eq!(coerce_filetype(b"II*\0\x08\0\0\0", &defaults), Some(json!({"type": "tiff", "mime": "image/tiff"})));
+        assert_eq!(coerce_filetype(b"MM\0*\0\0\0\x08", &defaults), Some(json!({"type": "tiff", "mime": "image/tiff"})));
+        assert_eq!(coerce_filetype(PDF, &defaults), Some(json!({"type": "pdf", "mime": "application/pdf"})));
+        assert_eq!(coerce_filetype(ZIP, &defaults), Some(json!({"type": "zip", "mime": "application/zip"})));
+        assert_eq!(coerce_filetype(b"PK\x05\x06\0\0\0\0", &defaults), Some(json!({"type": "zip", "mime": "application/zip"})));
+        assert_eq!(coerce_filetype(&[0x1F, 0x8B, 0x08, 0x00], &defaults), Some(json!({"type": "gzip", "mime": "application/gzip"})));
+        assert_eq!(coerce_filetype(MP4, &defaults), Some(json!({"type": "mp4", "mime": "video/mp4"})));
+    }
+
+    #[test]
+    fn test_invalid_types() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(detail_filetype(b"", &defaults).error, Some(UNRECOGNIZED_TYPE));
+        assert_eq!(detail_filetype(b"hello, world", &defaults).error, Some(UNRECOGNIZED_TYPE));
+        // A truncated signature, a RIFF file that isn't WebP, and text that starts with "BM"
+        assert_eq!(detail_filetype(b"\x89PNG", &defaults).error, Some(UNRECOGNIZED_TYPE));
+        assert_eq!(detail_filetype(b"RIFF\x24\0\0\0WAVEfmt ", &defaults).error, Some(UNRECOGNIZED_TYPE));
+        assert_eq!(detail_filetype(b"BMW owners club", &defaults).error, Some(UNRECOGNIZED_TYPE));
+        // HEIF images and QuickTime movies share MP4's container
+        assert_eq!(detail_filetype(b"\0\0\0\x18ftypheic\0\0\0\0", &defaults).error, Some(UNRECOGNIZED_TYPE));
+        assert_eq!(detail_filetype(b"\0\0\0\x14ftypqt  \0\0\0\0", &defaults).error, Some(UNRECOGNIZED_TYPE));
+        // The file type is never taken from the declared type
+        let declared: ValidationOptions = serde_json::from_value(json!({"declared_type": "image/png"})).unwrap();
+        assert_eq!(detail_filetype(b"<svg></svg>", &declared).error, Some(UNRECOGNIZED_TYPE));
+    }
+
+    #[test]
+    fn test_options() {
+        let images: ValidationOptions = serde_json::from_value(json!({"types": ["png", "jpeg"]})).unwrap();
+        assert!(validate_filetype(PNG, &images));
+        assert!(validate_filetype(JPEG, &images));
+        let result = detail_filetype(PDF, &images);
+        assert_eq!((result.error, result.file_type), (Some(UNSUPPORTED_TYPE), Some(FileType::Pdf)));
+
+        // The declared type must be one the contents can be declared as, ignoring case and parameters
+        let png: ValidationOptions = serde_json::from_value(json!({"declared_type": "Image/PNG; charset=binary"})).unwrap();
+        assert!(validate_filetype(PNG, &png));
+        let result = detail_filetype(JPEG, &png);
+        assert_eq!((result.error, result.file_type), (Some(MIME_MISMATCH), Some(FileType::Jpeg)));
+        let jpg: ValidationOptions = serde_json::from_value(json!({"declared_type": "image/jpg"})).unwrap();
+        assert!(validate_filetype(JPEG, &jpg));
+        let docx: ValidationOptions = serde_json::from_value(json!({
+            "declared_type": "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+        }))
+        .unwrap();
+        assert!(validate_filetype(ZIP, &docx));
+        assert_eq!(detail_filetype(PDF, &docx).error, Some(MIME_MISMATCH));
+
+        // Browsers declare files they can't identify as application/octet-stream, which declares nothing
+        let unknown: ValidationOptions = serde_json::from_value(json!({"declared_type": "application/octet-stream"})).unwrap();
+        assert!(validate_filetype(PDF, &unknown));
+        let empty: ValidationOptions = serde_json::from_value(json!({"declared_type": ""})).unwrap();
+        assert!(validate_filetype(PDF, &empty));
+
+        // The allowlist is checked before the declared type
+        let both: ValidationOptions = serde_json::from_value(json!({"types": ["png"], "declared_type": "image/png"})).unwrap();
+        assert_eq!(detail_filetype(JPEG, &both).error, Some(UNSUPPORTED_TYPE));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"types": ["exe"]})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": [37], "options": {"mime": "image/png"}})).is_err());
+    }
+
+    #[test]
+    fn test_input() {
+        // Bytes arrive as arrays of numbers from JSON; anything else is not bytes
+        assert_eq!(coerce_value(&json!(PDF), &Value::Null), Some(json!({"type": "pdf", "mime": "application/pdf"})));
+        assert_eq!(coerce_value(&json!([37, 256]), &Value::Null), None);
+        assert_eq!(coerce_value(&json!("%PDF-1.7"), &Value::Null), None);
+        assert_eq!(coerce_value(&json!(PNG), &json!({"types": ["pdf"]})), None);
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [PNG, []], "options": {"types": ["png"]}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_filetype(PNG, &defaults),
+            ValidationResult::from(Some(json!({"type": "png", "mime": "image/png"})))
+        );
+        let declared: ValidationOptions = serde_json::from_value(json!({"declared_type": "image/png"})).unwrap();
+        assert_eq!(
+            messages::localize(&detail_filetype(PDF, &declared), &Value::Null, Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "mime_mismatch",
+                "type": "pdf",
+                "message": messages::message("mime_mismatch", "en").unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The bytes path agrees with the generic path under the default options
+        let samples: [&[u8]; 5] = [PNG, JPEG, MP4, b"\x89PNG", b""];
+        for bytes in samples {
+            assert_eq!(validate_bytes(bytes, None), coerce_value(&json!(bytes), &Value::Null).is_some(), "{bytes:?}");
+        }
+    }
+}
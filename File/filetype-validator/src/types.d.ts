@@ -0,0 +1,44 @@
+// Types for the filetype validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** File types to accept; any recognized type when unset */
+    types?: ("png" | "jpeg" | "gif" | "webp" | "bmp" | "tiff" | "pdf" | "zip" | "gzip" | "mp4")[];
+    /** The MIME type the upload was declared as, such as its Content-Type; the contents must match it, and application/octet-stream declares nothing (default: null) */
+    declared_type?: string | null;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = Uint8Array | ArrayBuffer | number[];
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    type: "png" | "jpeg" | "gif" | "webp" | "bmp" | "tiff" | "pdf" | "zip" | "gzip" | "mp4";
+    mime: string;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The type the file was recognized as, when it was rejected for its type */
+    type?: "png" | "jpeg" | "gif" | "webp" | "bmp" | "tiff" | "pdf" | "zip" | "gzip" | "mp4";
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "unrecognized_type" | "unsupported_type" | "mime_mismatch" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
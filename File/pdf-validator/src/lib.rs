@@ -0,0 +1,1146 @@
+// ABOUTME: WASM component for PDF documents, checking their header, end marker and cross-reference data
+// ABOUTME: Reads the page count from the page tree and detects encryption, for document-intake limits
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// The value type, shared with the other validators that take raw bytes
+#[path = "../../../bytes/bytes.rs"]
+mod bytes;
+
+use bytes::Bytes;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+
+    #[wasm_bindgen(typescript_type = "ValidationOptions")]
+    pub type JsValidationOptions;
+}
+
+#[derive(Deserialize)]
+pub struct ValidationInput {
+    value: Bytes,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Most pages accepted, as the page tree counts them; any number when unset
+    max_pages: Option<u64>,
+    // Accept encrypted documents, whose contents can't be read without their password
+    allow_encrypted: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { max_pages: None, allow_encrypted: true }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize)]
+pub struct BatchInput {
+    values: Vec<Bytes>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const MISSING_HEADER: &str = "missing_header";
+const MISSING_EOF: &str = "missing_eof";
+const INVALID_XREF: &str = "invalid_xref";
+const INVALID_PAGE_TREE: &str = "invalid_page_tree";
+const ENCRYPTED: &str = "encrypted";
+const TOO_MANY_PAGES: &str = "too_many_pages";
+
+// Readers accept a header within the first 1024 bytes, and an end marker within the last 1024
+const MARKER_WINDOW: usize = 1024;
+// Limits on crafted files: nesting of arrays and dictionaries, references followed in a chain,
+// cross-reference sections, and the size of a decoded stream
+const MAX_DEPTH: usize = 64;
+const MAX_INDIRECTION: usize = 16;
+const MAX_SECTIONS: usize = 256;
+const MAX_DECODED: usize = 16 << 20;
+
+// The parts of PDF's object syntax that the structure is made of; strings are kept only so
+// that they can be skipped, and a stream keeps its dictionary and where its undecoded data is
+#[derive(Debug, Clone, PartialEq)]
+enum Object {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Real,
+    String,
+    Name(Vec<u8>),
+    Array(Vec<Object>),
+    Dictionary(Dictionary),
+    Reference(u32, u16),
+    Stream(Dictionary, Range<usize>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Dictionary(Vec<(Vec<u8>, Object)>);
+
+impl Dictionary {
+    fn get(&self, key: &[u8]) -> Option<&Object> {
+        self.0.iter().find(|(name, _)| name == key).map(|(_, object)| object)
+    }
+
+    fn integer(&self, key: &[u8]) -> Option<i64> {
+        match self.get(key)? {
+            Object::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn is_type(&self, name: &[u8]) -> bool {
+        matches!(self.get(b"Type"), Some(Object::Name(type_name)) if type_name == name)
+    }
+}
+
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, 0 | b'\t' | b'\n' | 0x0C | b'\r' | b' ')
+}
+
+fn is_delimiter(byte: u8) -> bool {
+    matches!(byte, b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%')
+}
+
+// Reads objects and keywords from a position in the file
+struct Lexer<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    // Offsets come from the file, so one past its end reads as the end
+    fn at(bytes: &'a [u8], pos: usize) -> Self {
+        Lexer { bytes, pos: pos.min(bytes.len()) }
+    }
+
+    // Whitespace and comments, which run to the end of the line
+    fn skip_whitespace(&mut self) {
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            if is_whitespace(byte) {
+                self.pos += 1;
+            } else if byte == b'%' {
+                while self.bytes.get(self.pos).is_some_and(|&byte| byte != b'\r' && byte != b'\n') {
+                    self.pos += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    // A run of regular characters: a number, a keyword, or a name after its slash
+    fn token(&mut self) -> &'a [u8] {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.bytes.get(self.pos).is_some_and(|&byte| !is_whitespace(byte) && !is_delimiter(byte)) {
+            self.pos += 1;
+        }
+        &self.bytes[start..self.pos]
+    }
+
+    // Consumes a keyword, or leaves the position alone
+    fn keyword(&mut self, keyword: &[u8]) -> bool {
+        let start = self.pos;
+        if self.token() == keyword {
+            return true;
+        }
+        self.pos = start;
+        false
+    }
+
+    // Consumes an integer, or leaves the position alone
+    fn integer(&mut self) -> Option<i64> {
+        let start = self.pos;
+        let integer = std::str::from_utf8(self.token()).ok().and_then(|token| token.parse().ok());
+        if integer.is_none() {
+            self.pos = start;
+        }
+        integer
+    }
+
+    fn object(&mut self, depth: usize) -> Option<Object> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        self.skip_whitespace();
+        match *self.bytes.get(self.pos)? {
+            b'/' => {
+                self.pos += 1;
+                Some(Object::Name(self.token().to_vec()))
+            }
+            b'(' => self.literal_string(),
+            b'<' if self.bytes.get(self.pos + 1) == Some(&b'<') => {
+                self.pos += 2;
+                self.dictionary(depth).map(Object::Dictionary)
+            }
+            b'<' => {
+                let end = self.bytes[self.pos..].iter().position(|&byte| byte == b'>')?;
+                let hex = &self.bytes[self.pos + 1..self.pos + end];
+                self.pos += end + 1;
+                hex.iter().all(|&byte| byte.is_ascii_hexdigit() || is_whitespace(byte)).then_some(Object::String)
+            }
+            b'[' => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_whitespace();
+                    if self.bytes.get(self.pos) == Some(&b']') {
+                        self.pos += 1;
+                        return Some(Object::Array(items));
+                    }
+                    items.push(self.object(depth + 1)?);
+                }
+            }
+            _ => self.simple_object(),
+        }
+    }
+
+    fn dictionary(&mut self, depth: usize) -> Option<Dictionary> {
+        let mut entries = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.bytes[self.pos..].starts_with(b">>") {
+                self.pos += 2;
+                return Some(Dictionary(entries));
+            }
+            let key = match self.object(depth + 1)? {
+                Object::Name(key) => key,
+                _ => return None,
+            };
+            entries.push((key, self.object(depth + 1)?));
+        }
+    }
+
+    // A string in parentheses, which may contain balanced parentheses and backslash escapes
+    fn literal_string(&mut self) -> Option<Object> {
+        let mut nesting = 0;
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            self.pos += 1;
+            match byte {
+                b'\\' => self.pos += 1,
+                b'(' => nesting += 1,
+                b')' if nesting == 1 => return Some(Object::String),
+                b')' => nesting -= 1,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // Numbers, booleans, null and references, which are two integers followed by "R"
+    fn simple_object(&mut self) -> Option<Object> {
+        let token = self.token();
+        match token {
+            b"true" => return Some(Object::Bool(true)),
+            b"false" => return Some(Object::Bool(false)),
+            b"null" => return Some(Object::Null),
+            _ => {}
+        }
+        let text = std::str::from_utf8(token).ok()?;
+        if let Ok(number) = text.parse::<i64>() {
+            let start = self.pos;
+            if let (Ok(number), Some(generation)) = (u32::try_from(number), self.integer()) {
+                if let (Ok(generation), true) = (u16::try_from(generation), self.keyword(b"R")) {
+                    return Some(Object::Reference(number, generation));
+                }
+            }
+            self.pos = start;
+            return Some(Object::Integer(number));
+        }
+        let real = !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit() || matches!(byte, b'.' | b'-' | b'+'));
+        real.then_some(Object::Real)
+    }
+}
+
+// Where an object is, from the cross-reference data
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum XrefEntry {
+    Free,
+    Offset(usize, u16),
+    Compressed { stream: u32, index: usize },
+}
+
+// A document's cross-reference data merged across its sections, newest first, and the newest
+// trailer
+struct Document<'a> {
+    bytes: &'a [u8],
+    xref: HashMap<u32, XrefEntry>,
+    trailer: Dictionary,
+}
+
+impl<'a> Document<'a> {
+    // Reads the sections from the one startxref points to, following /Prev back to the first
+    fn read(bytes: &'a [u8], eof: usize) -> Option<Self> {
+        let startxref = find_last(&bytes[..eof], b"startxref")?;
+        let mut lexer = Lexer::at(bytes, startxref + b"startxref".len());
+        let mut next = Some(usize::try_from(lexer.integer()?).ok()?);
+
+        let mut document = Document { bytes, xref: HashMap::new(), trailer: Dictionary(Vec::new()) };
+        let mut trailer = None;
+        let mut seen = HashSet::new();
+        while let Some(offset) = next {
+            if !seen.insert(offset) || seen.len() > MAX_SECTIONS {
+                return None;
+            }
+            let section = document.read_section(offset)?;
+            // Hybrid files keep their compressed objects in a stream beside the table
+            if let Some(stream) = section.integer(b"XRefStm") {
+                document.read_section(usize::try_from(stream).ok()?)?;
+            }
+            next = match section.integer(b"Prev") {
+                Some(prev) => Some(usize::try_from(prev).ok()?),
+                None => None,
+            };
+            trailer.get_or_insert(section);
+        }
+        document.trailer = trailer?;
+        Some(document)
+    }
+
+    // Reads a classic table or a cross-reference stream, keeping entries that newer sections
+    // haven't already given, and returns its trailer dictionary
+    fn read_section(&mut self, offset: usize) -> Option<Dictionary> {
+        let mut lexer = Lexer::at(self.bytes, offset);
+        if !lexer.keyword(b"xref") {
+            return self.read_xref_stream(offset);
+        }
+
+        loop {
+            if lexer.keyword(b"trailer") {
+                return match lexer.object(0)? {
+                    Object::Dictionary(trailer) => Some(trailer),
+                    _ => None,
+                };
+            }
+            let start = u32::try_from(lexer.integer()?).ok()?;
+            let count = u32::try_from(lexer.integer()?).ok()?;
+            for number in start..start.checked_add(count)? {
+                let offset = usize::try_from(lexer.integer()?).ok()?;
+                let generation = u16::try_from(lexer.integer()?).ok()?;
+                let entry = match lexer.token() {
+                    b"n" => XrefEntry::Offset(offset, generation),
+                    b"f" => XrefEntry::Free,
+                    _ => return None,
+                };
+                self.xref.entry(number).or_insert(entry);
+            }
+        }
+    }
+
+    fn read_xref_stream(&mut self, offset: usize) -> Option<Dictionary> {
+        let (dictionary, data) = match self.indirect_object(offset, 0)? {
+            Object::Stream(dictionary, data) if dictionary.is_type(b"XRef") => (dictionary, data),
+            _ => return None,
+        };
+        let data = decode_stream(&dictionary, &self.bytes[data])?;
+
+        let widths = match dictionary.get(b"W")? {
+            Object::Array(widths) => widths
+                .iter()
+                .map(|width| match width {
+                    Object::Integer(width @ 0..=8) => Some(*width as usize),
+                    _ => None,
+                })
+                .collect::<Option<Vec<usize>>>()?,
+            _ => return None,
+        };
+        let [type_width, field_width, index_width] = widths[..] else { return None };
+        let row_width = type_width + field_width + index_width;
+        if row_width == 0 {
+            return None;
+        }
+
+        let size = dictionary.integer(b"Size")?;
+        let ranges = match dictionary.get(b"Index") {
+            None => vec![0, size],
+            Some(Object::Array(index)) => index
+                .iter()
+                .map(|bound| match bound {
+                    Object::Integer(bound) => Some(*bound),
+                    _ => None,
+                })
+                .collect::<Option<Vec<i64>>>()?,
+            Some(_) => return None,
+        };
+        if ranges.len() % 2 != 0 {
+            return None;
+        }
+
+        let mut rows = data.chunks_exact(row_width);
+        for range in ranges.chunks_exact(2) {
+            let start = u32::try_from(range[0]).ok()?;
+            let count = u32::try_from(range[1]).ok()?;
+            for number in start..start.checked_add(count)? {
+                let row = rows.next()?;
+                let (kind, rest) = row.split_at(type_width);
+                let (field, index) = rest.split_at(field_width);
+                // A type field of width zero means every entry is in use
+                let kind = if type_width == 0 { 1 } else { big_endian(kind) };
+                let entry = match kind {
+                    0 => XrefEntry::Free,
+                    1 => XrefEntry::Offset(usize::try_from(big_endian(field)).ok()?, u16::try_from(big_endian(index)).ok()?),
+                    2 => XrefEntry::Compressed {
+                        stream: u32::try_from(big_endian(field)).ok()?,
+                        index: usize::try_from(big_endian(index)).ok()?,
+                    },
+                    // Later versions may add types, which readers treat as null objects
+                    _ => XrefEntry::Free,
+                };
+                self.xref.entry(number).or_insert(entry);
+            }
+        }
+        Some(dictionary)
+    }
+
+    // Whether every entry in use points at its object: "N G obj" at the offset, or an object
+    // stream that is itself in use
+    fn check_xref(&self) -> bool {
+        self.xref.iter().all(|(&number, entry)| match *entry {
+            XrefEntry::Free => true,
+            XrefEntry::Offset(offset, generation) => {
+                let mut lexer = Lexer::at(self.bytes, offset);
+                lexer.integer() == Some(number.into())
+                    && lexer.integer() == Some(generation.into())
+                    && lexer.keyword(b"obj")
+            }
+            XrefEntry::Compressed { stream, .. } => matches!(self.xref.get(&stream), Some(XrefEntry::Offset(..))),
+        })
+    }
+
+    // The object at an offset: "N G obj", the object, and for a stream its data, whose
+    // /Length may itself be a reference
+    fn indirect_object(&self, offset: usize, depth: usize) -> Option<Object> {
+        let mut lexer = Lexer::at(self.bytes, offset);
+        lexer.integer()?;
+        lexer.integer()?;
+        if !lexer.keyword(b"obj") {
+            return None;
+        }
+        let object = lexer.object(0)?;
+        let dictionary = match object {
+            Object::Dictionary(dictionary) if lexer.keyword(b"stream") => dictionary,
+            object => return Some(object),
+        };
+
+        // The data starts after the end of line that follows the keyword
+        let mut start = lexer.pos;
+        if self.bytes.get(start) == Some(&b'\r') {
+            start += 1;
+        }
+        if self.bytes.get(start) == Some(&b'\n') {
+            start += 1;
+        }
+        let length = match dictionary.get(b"Length")? {
+            Object::Integer(length) => *length,
+            reference @ Object::Reference(..) => match self.resolve(reference.clone(), depth + 1)? {
+                Object::Integer(length) => length,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let end = start.checked_add(usize::try_from(length).ok()?)?;
+        if end > self.bytes.len() || !Lexer::at(self.bytes, end).keyword(b"endstream") {
+            return None;
+        }
+        Some(Object::Stream(dictionary, start..end))
+    }
+
+    // The object a reference points to, followed through any chain of references; other
+    // objects are their own value
+    fn resolve(&self, mut object: Object, depth: usize) -> Option<Object> {
+        for _ in 0..MAX_INDIRECTION {
+            let Object::Reference(number, _) = object else { return Some(object) };
+            if depth > MAX_INDIRECTION {
+                return None;
+            }
+            object = match *self.xref.get(&number)? {
+                XrefEntry::Free => Object::Null,
+                XrefEntry::Offset(offset, _) => self.indirect_object(offset, depth)?,
+                XrefEntry::Compressed { stream, index } => self.compressed_object(stream, index, number, depth + 1)?,
+            };
+        }
+        None
+    }
+
+    // An object stored in an object stream: the stream starts with pairs of object numbers and
+    // offsets from /First, one for each of its /N objects
+    fn compressed_object(&self, stream: u32, index: usize, number: u32, depth: usize) -> Option<Object> {
+        let XrefEntry::Offset(offset, _) = *self.xref.get(&stream)? else { return None };
+        let (dictionary, data) = match self.indirect_object(offset, depth)? {
+            Object::Stream(dictionary, data) if dictionary.is_type(b"ObjStm") => (dictionary, data),
+            _ => return None,
+        };
+        let data = decode_stream(&dictionary, &self.bytes[data])?;
+        let first = usize::try_from(dictionary.integer(b"First")?).ok()?;
+        if index >= usize::try_from(dictionary.integer(b"N")?).ok()? {
+            return None;
+        }
+
+        let mut header = Lexer::at(&data, 0);
+        for _ in 0..index {
+            header.integer()?;
+            header.integer()?;
+        }
+        if header.integer()? != i64::from(number) {
+            return None;
+        }
+        let offset = first.checked_add(usize::try_from(header.integer()?).ok()?)?;
+        Lexer::at(&data, offset).object(0)
+    }
+}
+
+// The last occurrence of a marker, as its offset
+fn find_last(bytes: &[u8], marker: &[u8]) -> Option<usize> {
+    bytes.windows(marker.len()).rposition(|window| window == marker)
+}
+
+fn big_endian(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |value, &byte| value << 8 | u64::from(byte))
+}
+
+// A stream's data with its filters undone; only FlateDecode, with or without a PNG predictor,
+// is used for cross-reference and object streams in practice
+fn decode_stream(dictionary: &Dictionary, data: &[u8]) -> Option<Vec<u8>> {
+    let (filter, params) = match (dictionary.get(b"Filter"), dictionary.get(b"DecodeParms")) {
+        (None, _) => return Some(data.to_vec()),
+        (Some(Object::Name(filter)), params) => (filter, params),
+        (Some(Object::Array(filters)), params) => match (&filters[..], params) {
+            ([Object::Name(filter)], Some(Object::Array(params))) => (filter, params.first()),
+            ([Object::Name(filter)], params) => (filter, params),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if filter != b"FlateDecode" {
+        return None;
+    }
+    let inflated = miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(data, MAX_DECODED).ok()?;
+    match params {
+        None | Some(Object::Null) => Some(inflated),
+        Some(Object::Dictionary(params)) => unpredict(inflated, params),
+        Some(_) => None,
+    }
+}
+
+// Undoes a PNG predictor: each row starts with the filter used on it, and is predicted from
+// the bytes to its left and in the row above
+fn unpredict(data: Vec<u8>, params: &Dictionary) -> Option<Vec<u8>> {
+    let predictor = params.integer(b"Predictor").unwrap_or(1);
+    if predictor == 1 {
+        return Some(data);
+    }
+    if !(10..=15).contains(&predictor) {
+        return None;
+    }
+    let parameter = |key: &[u8]| match params.integer(key) {
+        None => Some(1),
+        Some(value) => usize::try_from(value).ok().filter(|value| (1..=1 << 16).contains(value)),
+    };
+    let colors = parameter(b"Colors")?;
+    let bits = match params.integer(b"BitsPerComponent") {
+        None => 8,
+        Some(bits @ (1 | 2 | 4 | 8 | 16)) => bits as usize,
+        Some(_) => return None,
+    };
+    let columns = parameter(b"Columns")?;
+    let pixel = (colors * bits).div_ceil(8);
+    let row_len = (colors * bits).checked_mul(columns).filter(|&bits| bits <= MAX_DECODED * 8)?.div_ceil(8);
+
+    let mut decoded = Vec::with_capacity(data.len());
+    let mut previous = vec![0u8; row_len];
+    for chunk in data.chunks(row_len + 1) {
+        let (&filter, row) = chunk.split_first()?;
+        let mut row = row.to_vec();
+        row.resize(row_len, 0);
+        for i in 0..row_len {
+            let left = if i >= pixel { row[i - pixel] } else { 0 };
+            let up = previous[i];
+            let up_left = if i >= pixel { previous[i - pixel] } else { 0 };
+            let prediction = match filter {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((u16::from(left) + u16::from(up)) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => return None,
+            };
+            row[i] = row[i].wrapping_add(prediction);
+        }
+        decoded.extend_from_slice(&row);
+        previous = row;
+    }
+    Some(decoded)
+}
+
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = i16::from(left) + i16::from(up) - i16::from(up_left);
+    let (to_left, to_up, to_up_left) =
+        ((estimate - i16::from(left)).abs(), (estimate - i16::from(up)).abs(), (estimate - i16::from(up_left)).abs());
+    if to_left <= to_up && to_left <= to_up_left {
+        left
+    } else if to_up <= to_up_left {
+        up
+    } else {
+        up_left
+    }
+}
+
+// The number of pages, from the /Count of the page tree's root: the trailer's /Root is the
+// catalog, whose /Pages is the root node
+fn page_count(document: &Document) -> Option<u64> {
+    let resolve = |object: Option<&Object>| document.resolve(object?.clone(), 0);
+    let catalog = match resolve(document.trailer.get(b"Root"))? {
+        Object::Dictionary(catalog) if catalog.is_type(b"Catalog") => catalog,
+        _ => return None,
+    };
+    let pages = match resolve(catalog.get(b"Pages"))? {
+        Object::Dictionary(pages) if pages.is_type(b"Pages") => pages,
+        _ => return None,
+    };
+    if !matches!(resolve(pages.get(b"Kids"))?, Object::Array(_)) {
+        return None;
+    }
+    match resolve(pages.get(b"Count"))? {
+        Object::Integer(count) => u64::try_from(count).ok(),
+        _ => None,
+    }
+}
+
+fn detail_pdf(bytes: &[u8], options: &ValidationOptions) -> ValidationResult {
+    let failure = |error| ValidationResult { error: Some(error), ..ValidationResult::from(None) };
+
+    // "%PDF-" and a version such as 1.7 or 2.0. Readers take offsets in the file to count
+    // from the header, so that anything before it can be ignored
+    let header = bytes[..bytes.len().min(MARKER_WINDOW)].windows(8).enumerate().find_map(|(start, window)| match window {
+        [b'%', b'P', b'D', b'F', b'-', major, b'.', minor] if major.is_ascii_digit() && minor.is_ascii_digit() => {
+            Some((start, format!("{}.{}", *major as char, *minor as char)))
+        }
+        _ => None,
+    });
+    let (bytes, version) = match header {
+        Some((start, version)) => (&bytes[start..], version),
+        None => return failure(MISSING_HEADER),
+    };
+    let tail = bytes.len().saturating_sub(MARKER_WINDOW);
+    let eof = match find_last(&bytes[tail..], b"%%EOF") {
+        Some(eof) => tail + eof,
+        None => return failure(MISSING_EOF),
+    };
+
+    let document = match Document::read(bytes, eof) {
+        Some(document) if document.check_xref() => document,
+        _ => return failure(INVALID_XREF),
+    };
+    let encrypted = document.trailer.get(b"Encrypt").is_some();
+    if encrypted && !options.allow_encrypted {
+        return failure(ENCRYPTED);
+    }
+
+    // Object streams are encrypted along with the rest of an encrypted document, so its page
+    // tree may be out of reach; its page count is then unknown, and can't be held to max_pages
+    let pages = match page_count(&document) {
+        Some(pages) => Some(pages),
+        None if encrypted && options.max_pages.is_none() => None,
+        None if encrypted => return failure(ENCRYPTED),
+        None => return failure(INVALID_PAGE_TREE),
+    };
+    if pages.zip(options.max_pages).is_some_and(|(pages, max_pages)| pages > max_pages) {
+        return failure(TOO_MANY_PAGES);
+    }
+
+    ValidationResult::from(Some(json!({ "version": version, "pages": pages, "encrypted": encrypted })))
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the document's version, page count and whether it is encrypted
+fn coerce_pdf(bytes: &[u8], options: &ValidationOptions) -> Option<Value> {
+    let result = detail_pdf(bytes, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_pdf(bytes: &[u8], options: &ValidationOptions) -> bool {
+    coerce_pdf(bytes, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_pdf(&Bytes::deserialize(value).ok()?.0, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    let result = match Bytes::deserialize(value) {
+        Ok(bytes) => detail_pdf(&bytes.0, &options),
+        Err(_) => ValidationResult::from(None),
+    };
+    serde_json::to_value(result).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_pdf(&input_obj.value.0, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_pdf(&input_obj.value.0, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid. Every failure of bytes
+    // has its own error code, so the input needn't be kept to choose one
+    let (result, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_pdf(&input_obj.value.0, &input_obj.options), input_obj.locale),
+        Err(_) => (ValidationResult::from(None), None),
+    };
+
+    to_js(&messages::localize(&result, &Value::Null, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|bytes| JsValue::from_bool(validate_pdf(&bytes.0, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|bytes| {
+            let result = detail_pdf(&bytes.0, &batch.options);
+            to_js(&messages::localize(&result, &Value::Null, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for bytes: a Uint8Array is copied straight into WASM memory rather than into an
+// input object, element by element. Options are a separate, optional argument, and options
+// that don't deserialize make the bytes invalid
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_bytes(bytes: &[u8], options: Option<JsValidationOptions>) -> bool {
+    let options = match options {
+        Some(options) => match serde_wasm_bindgen::from_value(options.into()) {
+            Ok(options) => options,
+            Err(_) => return false,
+        },
+        None => ValidationOptions::default(),
+    };
+
+    validate_pdf(bytes, &options)
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A catalog, a page tree with one page, and the page
+    const OBJECTS: [&str; 3] = [
+        "<< /Type /Catalog /Pages 2 0 R >>",
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents (a (nested) string\\)) >>",
+    ];
+
+    fn object(out: &mut Vec<u8>, number: usize, object: &str) -> usize {
+        let offset = out.len();
+        out.extend(format!("{number} 0 obj\n{object}\nendobj\n").bytes());
+        offset
+    }
+
+    fn stream(out: &mut Vec<u8>, number: usize, dictionary: &str, data: &[u8]) -> usize {
+        let offset = out.len();
+        out.extend(format!("{number} 0 obj\n<< {dictionary} /Length {} >>\nstream\n", data.len()).bytes());
+        out.extend_from_slice(data);
+        out.extend(b"\nendstream\nendobj\n");
+        offset
+    }
+
+    // A document with a classic cross-reference table, numbering the objects from 1
+    fn pdf(objects: &[&str], trailer: &str) -> Vec<u8> {
+        let mut out = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let offsets: Vec<usize> = objects.iter().enumerate().map(|(i, o)| object(&mut out, i + 1, o)).collect();
+        let xref = out.len();
+        out.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).bytes());
+        for offset in offsets {
+            out.extend(format!("{offset:010} 00000 n \n").bytes());
+        }
+        out.extend(format!("trailer\n<< /Size {} {trailer} >>\nstartxref\n{xref}\n%%EOF\n", objects.len() + 1).bytes());
+        out
+    }
+
+    // The document with the first occurrence of some text replaced
+    fn replace(bytes: &[u8], from: &str, to: &str) -> Vec<u8> {
+        let start = bytes.windows(from.len()).position(|window| window == from.as_bytes()).unwrap();
+        [&bytes[..start], to.as_bytes(), &bytes[start + from.len()..]].concat()
+    }
+
+    // An incremental update replacing one object, whose table links back to the last one
+    fn update(mut out: Vec<u8>, number: usize, replacement: &str) -> Vec<u8> {
+        let startxref = find_last(&out, b"startxref").unwrap();
+        let prev = Lexer::at(&out, startxref + 9).integer().unwrap();
+        let offset = object(&mut out, number, replacement);
+        let xref = out.len();
+        out.extend(format!("xref\n{number} 1\n{offset:010} 00000 n \n").bytes());
+        out.extend(format!("trailer\n<< /Size 4 /Root 1 0 R /Prev {prev} >>\nstartxref\n{xref}\n%%EOF\n").bytes());
+        out
+    }
+
+    // A document with a cross-reference stream, numbering the objects kept in an object stream
+    // from 1 and the plain ones after them; the rows can be compressed with the PNG Up
+    // predictor, as writers usually do
+    fn xref_stream_pdf(compressed: &[&str], objects: &[&str], flate: bool) -> Vec<u8> {
+        let row = |kind: u8, field: usize, index: usize| [kind, (field >> 8) as u8, field as u8, index as u8];
+        let object_stream = compressed.len() + objects.len() + 1;
+        let mut out = b"%PDF-1.5\n".to_vec();
+        let mut rows = vec![row(0, 0, 255)];
+        rows.extend((0..compressed.len()).map(|i| row(2, object_stream, i)));
+        for (i, o) in objects.iter().enumerate() {
+            rows.push(row(1, object(&mut out, compressed.len() + i + 1, o), 0));
+        }
+        if !compressed.is_empty() {
+            let (mut header, mut body) = (String::new(), String::new());
+            for (i, o) in compressed.iter().enumerate() {
+                header += &format!("{} {} ", i + 1, body.len());
+                body += &format!("{o}\n");
+            }
+            let dictionary = format!("/Type /ObjStm /N {} /First {}", compressed.len(), header.len());
+            rows.push(row(1, stream(&mut out, object_stream, &dictionary, (header + &body).as_bytes()), 0));
+        }
+        let number = rows.len();
+        let xref = out.len();
+        rows.push(row(1, xref, 0));
+
+        let dictionary = format!("/Type /XRef /Size {} /W [1 2 1] /Root 1 0 R", rows.len());
+        if flate {
+            let mut previous = [0u8; 4];
+            let mut predicted = Vec::new();
+            for row in &rows {
+                predicted.push(2);
+                predicted.extend(row.iter().zip(previous).map(|(byte, above)| byte.wrapping_sub(above)));
+                previous = *row;
+            }
+            let data = miniz_oxide::deflate::compress_to_vec_zlib(&predicted, 6);
+            let dictionary = format!("{dictionary} /Filter /FlateDecode /DecodeParms << /Predictor 12 /Columns 4 >>");
+            stream(&mut out, number, &dictionary, &data);
+        } else {
+            stream(&mut out, number, &dictionary, &rows.concat());
+        }
+        out.extend(format!("startxref\n{xref}\n%%EOF\n").bytes());
+        out
+    }
+
+    #[test]
+    fn test_valid_documents() {
+        let defaults = ValidationOptions::default();
+        let single = json!({"version": "1.7", "pages": 1, "encrypted": false});
+
+        assert_eq!(coerce_pdf(&pdf(&OBJECTS, "/Root 1 0 R"), &defaults), Some(single.clone()));
+        // An update's objects replace the originals, and older tables are still checked
+        let updated = update(pdf(&OBJECTS, "/Root 1 0 R"), 2, "<< /Type /Pages /Kids [3 0 R 3 0 R] /Count 2 >>");
+        assert_eq!(coerce_pdf(&updated, &defaults), Some(json!({"version": "1.7", "pages": 2, "encrypted": false})));
+        // The count may be an indirect object
+        let indirect = pdf(&[OBJECTS[0], "<< /Type /Pages /Kids [3 0 R] /Count 4 0 R >>", OBJECTS[2], "1"], "/Root 1 0 R");
+        assert_eq!(coerce_pdf(&indirect, &defaults), Some(json!({"version": "1.7", "pages": 1, "encrypted": false})));
+
+        // Cross-reference streams, plain or compressed, and objects kept in an object stream
+        let version = json!({"version": "1.5", "pages": 1, "encrypted": false});
+        assert_eq!(coerce_pdf(&xref_stream_pdf(&[], &OBJECTS, false), &defaults), Some(version.clone()));
+        assert_eq!(coerce_pdf(&xref_stream_pdf(&[], &OBJECTS, true), &defaults), Some(version.clone()));
+        assert_eq!(coerce_pdf(&xref_stream_pdf(&OBJECTS[..2], &OBJECTS[2..], false), &defaults), Some(version.clone()));
+        assert_eq!(coerce_pdf(&xref_stream_pdf(&OBJECTS[..2], &OBJECTS[2..], true), &defaults), Some(version));
+    }
+
+    #[test]
+    fn test_markers() {
+        let defaults = ValidationOptions::default();
+        let document = pdf(&OBJECTS, "/Root 1 0 R");
+
+        assert_eq!(detail_pdf(b"", &defaults).error, Some(MISSING_HEADER));
+        assert_eq!(detail_pdf(b"%!PS-Adobe-3.0\n%%EOF\n", &defaults).error, Some(MISSING_HEADER));
+        assert_eq!(detail_pdf(b"%PDF-x.y\n%%EOF\n", &defaults).error, Some(MISSING_HEADER));
+        // Readers allow some junk before the header and after the end marker, but not much
+        let prefixed = [&b"\xEF\xBB\xBFjunk\n"[..], &document].concat();
+        assert_eq!(coerce_pdf(&prefixed, &defaults).map(|value| value["pages"].clone()), Some(json!(1)));
+        let prefixed = [&[b' '; MARKER_WINDOW][..], &document].concat();
+        assert_eq!(detail_pdf(&prefixed, &defaults).error, Some(MISSING_HEADER));
+        let suffixed = [&document[..], &[0; 100]].concat();
+        assert!(validate_pdf(&suffixed, &defaults));
+        let suffixed = [&document[..], &[0; MARKER_WINDOW]].concat();
+        assert_eq!(detail_pdf(&suffixed, &defaults).error, Some(MISSING_EOF));
+
+        // A truncated upload loses its end marker
+        assert_eq!(detail_pdf(&document[..document.len() - 10], &defaults).error, Some(MISSING_EOF));
+        assert_eq!(detail_pdf(b"%PDF-1.7\n", &defaults).error, Some(MISSING_EOF));
+    }
+
+    #[test]
+    fn test_xref() {
+        let defaults = ValidationOptions::default();
+        let document = pdf(&OBJECTS, "/Root 1 0 R");
+
+        // startxref must point at a table or stream, and every entry in use at its object
+        let startxref = find_last(&document, b"startxref").unwrap();
+        let xref = Lexer::at(&document, startxref + 9).integer().unwrap();
+        let moved = replace(&document, &format!("startxref\n{xref}"), &format!("startxref\n{}", xref + 1));
+        assert_eq!(detail_pdf(&moved, &defaults).error, Some(INVALID_XREF));
+        let shifted = replace(&document, "<< /Type /Catalog", "<</Type/Catalog");
+        assert_eq!(detail_pdf(&shifted, &defaults).error, Some(INVALID_XREF));
+        let renumbered = replace(&document, "2 0 obj", "5 0 obj");
+        assert_eq!(detail_pdf(&renumbered, &defaults).error, Some(INVALID_XREF));
+        assert_eq!(detail_pdf(b"%PDF-1.7\n%%EOF\n", &defaults).error, Some(INVALID_XREF));
+
+        // A /Prev that loops back is not followed forever
+        let looped = replace(&document, "/Root 1 0 R", &format!("/Root 1 0 R /Prev {xref}"));
+        assert_eq!(detail_pdf(&looped, &defaults).error, Some(INVALID_XREF));
+        // Nor one that points outside the file
+        let dangling = replace(&document, "/Root 1 0 R", "/Root 1 0 R /Prev 999999");
+        assert_eq!(detail_pdf(&dangling, &defaults).error, Some(INVALID_XREF));
+
+        // A corrupt cross-reference stream
+        let stream = xref_stream_pdf(&[], &OBJECTS, true);
+        let corrupt: Vec<u8> = stream.iter().map(|&byte| if byte == b'W' { b'V' } else { byte }).collect();
+        assert_eq!(detail_pdf(&corrupt, &defaults).error, Some(INVALID_XREF));
+    }
+
+    #[test]
+    fn test_page_tree() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(detail_pdf(&pdf(&OBJECTS, ""), &defaults).error, Some(INVALID_PAGE_TREE));
+        assert_eq!(detail_pdf(&pdf(&OBJECTS, "/Root 3 0 R"), &defaults).error, Some(INVALID_PAGE_TREE));
+        assert_eq!(detail_pdf(&pdf(&OBJECTS, "/Root 9 0 R"), &defaults).error, Some(INVALID_PAGE_TREE));
+        let broken = [OBJECTS[0], "<< /Type /Pages /Kids [3 0 R] /Count -1 >>", OBJECTS[2]];
+        assert_eq!(detail_pdf(&pdf(&broken, "/Root 1 0 R"), &defaults).error, Some(INVALID_PAGE_TREE));
+        let broken = [OBJECTS[0], "<< /Type /Pages /Count 1 >>", OBJECTS[2]];
+        assert_eq!(detail_pdf(&pdf(&broken, "/Root 1 0 R"), &defaults).error, Some(INVALID_PAGE_TREE));
+        let broken = ["<< /Type /Catalog /Pages 3 0 R >>", OBJECTS[1], OBJECTS[2]];
+        assert_eq!(detail_pdf(&pdf(&broken, "/Root 1 0 R"), &defaults).error, Some(INVALID_PAGE_TREE));
+        // References that refer to each other are followed only so far
+        let cycle = ["<< /Type /Catalog /Pages 2 0 R >>", "3 0 R", "2 0 R"];
+        assert_eq!(detail_pdf(&pdf(&cycle, "/Root 1 0 R"), &defaults).error, Some(INVALID_PAGE_TREE));
+    }
+
+    #[test]
+    fn test_options() {
+        let defaults = ValidationOptions::default();
+        let encrypted = pdf(&OBJECTS, "/Root 1 0 R /Encrypt << /Filter /Standard /V 2 /R 3 >> /ID [<0123> <4567>]");
+
+        assert_eq!(coerce_pdf(&encrypted, &defaults), Some(json!({"version": "1.7", "pages": 1, "encrypted": true})));
+        let plain: ValidationOptions = serde_json::from_value(json!({"allow_encrypted": false})).unwrap();
+        assert_eq!(detail_pdf(&encrypted, &plain).error, Some(ENCRYPTED));
+        assert!(validate_pdf(&pdf(&OBJECTS, "/Root 1 0 R"), &plain));
+
+        // An encrypted document's page tree may be unreadable; its page count is then unknown,
+        // and it can't be shown to be within max_pages
+        let unreadable = ["(encrypted)", OBJECTS[1], OBJECTS[2]];
+        let unreadable = pdf(&unreadable, "/Root 1 0 R /Encrypt 9 0 R");
+        assert_eq!(coerce_pdf(&unreadable, &defaults), Some(json!({"version": "1.7", "pages": null, "encrypted": true})));
+        let limited: ValidationOptions = serde_json::from_value(json!({"max_pages": 10})).unwrap();
+        assert_eq!(detail_pdf(&unreadable, &limited).error, Some(ENCRYPTED));
+
+        let updated = update(pdf(&OBJECTS, "/Root 1 0 R"), 2, "<< /Type /Pages /Kids [3 0 R 3 0 R] /Count 2 >>");
+        let one: ValidationOptions = serde_json::from_value(json!({"max_pages": 1})).unwrap();
+        assert!(validate_pdf(&pdf(&OBJECTS, "/Root 1 0 R"), &one));
+        assert_eq!(detail_pdf(&updated, &one).error, Some(TOO_MANY_PAGES));
+        let none: ValidationOptions = serde_json::from_value(json!({"max_pages": 0})).unwrap();
+        assert_eq!(detail_pdf(&pdf(&OBJECTS, "/Root 1 0 R"), &none).error, Some(TOO_MANY_PAGES));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"max_pages": -1})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": [37], "options": {"pages": 1}})).is_err());
+    }
+
+    #[test]
+    fn test_input() {
+        // Bytes arrive as arrays of numbers from JSON; anything else is not bytes
+        let document = pdf(&OBJECTS, "/Root 1 0 R");
+        assert_eq!(coerce_value(&json!(document), &Value::Null), Some(json!({"version": "1.7", "pages": 1, "encrypted": false})));
+        assert_eq!(coerce_value(&json!([37, 256]), &Value::Null), None);
+        assert_eq!(coerce_value(&json!("%PDF-1.7\n%%EOF\n"), &Value::Null), None);
+        assert_eq!(coerce_value(&json!(document), &json!({"max_pages": 0})), None);
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [document, []], "options": {"max_pages": 5}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_pdf(&pdf(&OBJECTS, "/Root 1 0 R"), &defaults),
+            ValidationResult::from(Some(json!({"version": "1.7", "pages": 1, "encrypted": false})))
+        );
+        assert_eq!(
+            messages::localize(&detail_pdf(b"%PDF-1.7\n", &defaults), &Value::Null, Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "missing_eof",
+                "message": messages::message("missing_eof", "en").unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The bytes path agrees with the generic path under the default options
+        let document = pdf(&OBJECTS, "/Root 1 0 R");
+        let samples: [&[u8]; 4] = [&document, &document[..100], b"%PDF-1.7\n%%EOF\n", b""];
+        for bytes in samples {
+            assert_eq!(validate_bytes(bytes, None), coerce_value(&json!(bytes), &Value::Null).is_some(), "{bytes:?}");
+        }
+    }
+}
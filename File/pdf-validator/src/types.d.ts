@@ -0,0 +1,43 @@
+// Types for the pdf validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The most pages the document may have, counted from its page tree (default: null) */
+    max_pages?: number | null;
+    /** Whether to accept an encrypted document; its page tree may be unreadable, so its page count is then null (default: true) */
+    allow_encrypted?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = Uint8Array | ArrayBuffer | number[];
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    version: string;
+    pages: number | null;
+    encrypted: boolean;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "missing_header" | "missing_eof" | "invalid_xref" | "invalid_page_tree" | "encrypted" | "too_many_pages" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,380 @@
+// ABOUTME: WASM component for stock ticker symbol validation
+// ABOUTME: Provides validate and coerce functions for tickers with optional exchange prefixes (NASDAQ:AAPL) or suffixes (VOD.L)
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Only accept tickers qualified by one of these exchanges, such as ["NASDAQ", "NYSE", "LSE"]
+    exchanges: Option<Vec<String>>,
+    // Reject tickers that don't name their exchange
+    require_exchange: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// Exchange suffixes in the dotted style of VOD.L, and the exchange each one stands for
+const SUFFIXES: &[(&str, &str)] = &[
+    ("L", "LSE"), ("IL", "LSE"), ("T", "TSE"), ("HK", "HKEX"), ("TO", "TSX"), ("V", "TSXV"),
+    ("AX", "ASX"), ("NZ", "NZX"), ("PA", "EPA"), ("AS", "AMS"), ("BR", "EBR"), ("LS", "ELI"),
+    ("DE", "XETRA"), ("F", "FRA"), ("MI", "BIT"), ("MC", "BME"), ("SW", "SIX"), ("VI", "VIE"),
+    ("ST", "STO"), ("OL", "OSL"), ("CO", "CPH"), ("HE", "HEL"), ("IR", "ISE"), ("SS", "SSE"),
+    ("SZ", "SZSE"), ("KS", "KRX"), ("TW", "TWSE"), ("SI", "SGX"), ("NS", "NSE"), ("BO", "BSE"),
+    ("SA", "B3"), ("MX", "BMV"), ("JO", "JSE"),
+];
+
+// Share classes are one or two letters after a dot or hyphen, as in BRK.B or RDS-A
+const MAX_BASE_LENGTH: usize = 10;
+const MAX_CLASS_LENGTH: usize = 2;
+
+fn is_exchange_code(text: &str) -> bool {
+    (2..=10).contains(&text.len()) && text.bytes().all(|b| b.is_ascii_uppercase())
+}
+
+// The symbol without exchange: a base of letters and digits with an optional share class
+fn is_symbol(text: &str) -> bool {
+    let (base, class) = match text.split_once(['.', '-']) {
+        Some((base, class)) => (base, Some(class)),
+        None => (text, None),
+    };
+
+    let base_valid = (1..=MAX_BASE_LENGTH).contains(&base.len())
+        && base.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit());
+    let class_valid = class.is_none_or(|class| {
+        (1..=MAX_CLASS_LENGTH).contains(&class.len()) && class.bytes().all(|b| b.is_ascii_uppercase())
+    });
+    base_valid && class_valid
+}
+
+// Splits off an exchange given as a prefix (NASDAQ:AAPL) or a known suffix (VOD.L)
+fn parse_ticker(text: &str) -> Option<(String, Option<String>)> {
+    let text = text.trim().to_ascii_uppercase();
+
+    if let Some((exchange, symbol)) = text.split_once(':') {
+        return (is_exchange_code(exchange) && is_symbol(symbol)).then(|| (symbol.to_string(), Some(exchange.to_string())));
+    }
+
+    let suffixed = text.rsplit_once('.').and_then(|(symbol, suffix)| {
+        let (_, exchange) = SUFFIXES.iter().find(|(known, _)| *known == suffix)?;
+        Some((symbol.to_string(), Some(exchange.to_string())))
+    });
+    match suffixed {
+        Some((symbol, exchange)) if is_symbol(&symbol) => Some((symbol, exchange)),
+        _ => is_symbol(&text).then_some((text, None)),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns {symbol, exchange} in uppercase, with exchange null when the ticker doesn't name one
+fn coerce_ticker_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let (symbol, exchange) = parse_ticker(text)?;
+
+    match (&exchange, &options.exchanges) {
+        (None, _) if options.require_exchange => return None,
+        (Some(exchange), Some(allowed)) if !allowed.iter().any(|a| a.eq_ignore_ascii_case(exchange)) => return None,
+        _ => {},
+    }
+
+    Some(serde_json::json!({ "symbol": symbol, "exchange": exchange }))
+}
+
+// Only strings can be valid; the fast path calls coerce_ticker_str directly
+fn coerce_ticker(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_ticker_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_ticker(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_ticker(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_ticker(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_ticker(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_ticker(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_ticker(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_ticker(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_ticker(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_ticker(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_ticker_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_tickers() {
+        let defaults = ValidationOptions::default();
+
+        for ticker in ["AAPL", "F", "BRK.B", "RDS-A", "NASDAQ:AAPL", "VOD.L", "7203.T", "0700.HK", "aapl", " MSFT ", "LSE:RDS-A"] {
+            assert!(validate_ticker(&json!(ticker), &defaults), "{}", ticker);
+        }
+    }
+
+    #[test]
+    fn test_invalid_tickers() {
+        let defaults = ValidationOptions::default();
+
+        for value in [
+            json!(""), json!("AAPL:"), json!(":AAPL"), json!("N:AAPL"), json!("NASDAQ:"), json!("TOOLONGTICKER"),
+            json!("BRK.ABC"), json!("BRK..B"), json!("AA PL"), json!("VOD.L.L.L"), json!("AAPL$"), json!(42),
+        ] {
+            assert!(!validate_ticker(&value, &defaults), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_exchanges() {
+        let us = options(json!({"exchanges": ["NASDAQ", "NYSE"]}));
+        let qualified = options(json!({"require_exchange": true}));
+
+        assert!(validate_ticker(&json!("NASDAQ:AAPL"), &us));
+        assert!(validate_ticker(&json!("nyse:brk.b"), &us));
+        assert!(validate_ticker(&json!("AAPL"), &us));
+        assert!(!validate_ticker(&json!("VOD.L"), &us));
+        assert!(!validate_ticker(&json!("LSE:VOD"), &us));
+
+        assert!(validate_ticker(&json!("VOD.L"), &options(json!({"exchanges": ["lse"]}))));
+        assert!(validate_ticker(&json!("VOD.L"), &qualified));
+        assert!(!validate_ticker(&json!("AAPL"), &qualified));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_ticker(&json!("nasdaq:aapl"), &defaults), Some(json!({"symbol": "AAPL", "exchange": "NASDAQ"})));
+        assert_eq!(coerce_ticker(&json!("VOD.L"), &defaults), Some(json!({"symbol": "VOD", "exchange": "LSE"})));
+        assert_eq!(coerce_ticker(&json!("BRK.B"), &defaults), Some(json!({"symbol": "BRK.B", "exchange": null})));
+        assert_eq!(coerce_ticker(&json!("RDS-A.L"), &defaults), Some(json!({"symbol": "RDS-A", "exchange": "LSE"})));
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "AAPL"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "AAPL", "options": {"exchanges": ["NYSE"], "require_exchange": true}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "AAPL", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_ticker(&json!("0700.HK"), &defaults)),
+            ValidationResult { valid: true, value: json!({"symbol": "0700", "exchange": "HKEX"}) }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_ticker(&json!("NASDAQ:"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["AAPL", "brk.b", "123456789012", ""] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,437 @@
+// ABOUTME: WASM component for altitude and depth validation
+// ABOUTME: Provides validate and coerce functions for heights in meters or feet with plausibility bounds
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Unit for bare numbers and for the coerced value: "m" (default) or "ft"
+    unit: Unit,
+    // Treat the value as a depth below sea level, positive downwards
+    depth: bool,
+    // Lowest accepted value in `unit`, replacing the plausibility bound
+    min: Option<f64>,
+    // Highest accepted value in `unit`, replacing the plausibility bound
+    max: Option<f64>,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when a specific reason was detected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Unit {
+    #[default]
+    M,
+    Ft,
+}
+
+const FEET_PER_METER: f64 = 1.0 / 0.3048;
+
+// Plausibility bounds in meters: the deepest ocean trench, and the edge of space
+const DEEPEST_METERS: f64 = 11_000.0;
+const HIGHEST_METERS: f64 = 100_000.0;
+
+const OUT_OF_RANGE: &str = messages::ErrorCode::OutOfRange.as_str();
+
+impl Unit {
+    // How many of this unit make up one meter
+    fn per_meter(self) -> f64 {
+        match self {
+            Unit::M => 1.0,
+            Unit::Ft => FEET_PER_METER,
+        }
+    }
+}
+
+// Splits a trailing unit such as "m", "metres", "ft" or "'" off a number
+fn parse_measurement(text: &str) -> Option<(f64, Option<Unit>)> {
+    let text = text.trim();
+    let split = text.find(|c: char| c.is_alphabetic() || c == '\'').unwrap_or(text.len());
+    let (number, unit) = (text[..split].trim_end(), text[split..].to_ascii_lowercase());
+
+    let unit = match unit.as_str() {
+        "" => None,
+        "m" | "meter" | "meters" | "metre" | "metres" => Some(Unit::M),
+        "ft" | "foot" | "feet" | "'" => Some(Unit::Ft),
+        _ => return None,
+    };
+
+    // Rust's float parser accepts "inf" and "nan", which are not measurements
+    let value = number.parse::<f64>().ok().filter(|n| n.is_finite())?;
+    Some((value, unit))
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the value as a number in the configured unit
+fn coerce_altitude(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    match value {
+        Value::Number(n) => coerce_measurement(n.as_f64()?, None, options),
+        Value::String(s) => coerce_altitude_str(s, options),
+        _ => None,
+    }
+}
+
+// The string half of coerce_altitude; the fast path calls it directly
+fn coerce_altitude_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let (number, unit) = parse_measurement(text)?;
+    coerce_measurement(number, unit, options)
+}
+
+// Converts a measurement to the configured unit and checks it against the bounds; a number
+// without a unit is taken to be in the configured unit already
+fn coerce_measurement(number: f64, unit: Option<Unit>, options: &ValidationOptions) -> Option<Value> {
+    let converted = number / unit.unwrap_or(options.unit).per_meter() * options.unit.per_meter();
+
+    let (low, high) = if options.depth { (0.0, DEEPEST_METERS) } else { (-DEEPEST_METERS, HIGHEST_METERS) };
+    let min = options.min.unwrap_or(low * options.unit.per_meter());
+    let max = options.max.unwrap_or(high * options.unit.per_meter());
+    if !(min..=max).contains(&converted) {
+        return None;
+    }
+
+    // Unit conversion leaves float noise, so results are rounded to the nearest thousandth
+    let rounded = (converted * 1000.0).round() / 1000.0;
+    serde_json::Number::from_f64(rounded).map(Value::Number)
+}
+
+// Detailed validation; a measurement outside the bounds gets the out_of_range error code
+fn detail_altitude(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let mut result = ValidationResult::from(coerce_altitude(value, options));
+    let measured = match value {
+        Value::Number(_) => true,
+        Value::String(s) => parse_measurement(s).is_some(),
+        _ => false,
+    };
+    if !result.valid && measured {
+        result.error = Some(OUT_OF_RANGE);
+    }
+    result
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_altitude(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_altitude(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_altitude(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_altitude(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_altitude(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_altitude(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_altitude(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_altitude(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_altitude(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_altitude_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Fast path for a JS number with the default options, as validate_str is for strings
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_f64(value: f64) -> bool {
+    coerce_measurement(value, None, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_altitudes() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_altitude(&json!(0), &defaults));
+        assert!(validate_altitude(&json!(8848.86), &defaults));
+        assert!(validate_altitude(&json!(-430), &defaults)); // Dead Sea shore
+        assert!(validate_altitude(&json!("35000 ft"), &defaults));
+        assert!(validate_altitude(&json!("1200m"), &defaults));
+        assert!(validate_altitude(&json!(" 300 metres "), &defaults));
+        assert!(validate_altitude(&json!("5280'"), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_altitudes() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_altitude(&json!(-12000), &defaults)); // Deeper than any ocean
+        assert!(!validate_altitude(&json!(150000), &defaults));
+        assert!(!validate_altitude(&json!("400000 ft"), &defaults));
+        assert!(!validate_altitude(&json!("100 furlongs"), &defaults));
+        assert!(!validate_altitude(&json!("ft"), &defaults));
+        assert!(!validate_altitude(&json!("inf"), &defaults));
+        assert!(!validate_altitude(&json!(""), &defaults));
+        assert!(!validate_altitude(&json!(true), &defaults));
+    }
+
+    #[test]
+    fn test_units_and_bounds() {
+        let feet = options(json!({"unit": "ft"}));
+        let depth = options(json!({"depth": true}));
+        let airfield = options(json!({"min": 0, "max": 5000}));
+
+        assert!(validate_altitude(&json!(320000), &feet)); // Just under 100 km
+        assert!(!validate_altitude(&json!(320000), &ValidationOptions::default()));
+
+        assert!(validate_altitude(&json!(10900), &depth));
+        assert!(!validate_altitude(&json!(-5), &depth));
+        assert!(!validate_altitude(&json!(12000), &depth));
+
+        assert!(validate_altitude(&json!("4000 m"), &airfield));
+        assert!(!validate_altitude(&json!("20000 ft"), &airfield)); // 6096 m
+        assert!(!validate_altitude(&json!(-1), &airfield));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"unit": "km"})).is_err());
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+        let feet = options(json!({"unit": "ft"}));
+
+        assert_eq!(coerce_altitude(&json!("1000 ft"), &defaults), Some(json!(304.8)));
+        assert_eq!(coerce_altitude(&json!("304.8 m"), &feet), Some(json!(1000.0)));
+        assert_eq!(coerce_altitude(&json!(1500), &feet), Some(json!(1500.0)));
+        assert_eq!(coerce_altitude(&json!("abc"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 100})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 100, "options": {"unit": "ft", "max": 45000}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 100, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_altitude(&json!("10 ft"), &defaults)),
+            ValidationResult { valid: true, value: json!(3.048), error: None }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_altitude(&json!(-20000), &defaults)),
+            ValidationResult { valid: false, value: Value::Null, error: None }
+        );
+
+        // Measurements outside the bounds say so; anything else has no specific reason
+        assert_eq!(detail_altitude(&json!(-20000), &defaults).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_altitude(&json!("400000 ft"), &defaults).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_altitude(&json!(-5), &options(json!({"depth": true}))).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_altitude(&json!("100 furlongs"), &defaults).error, None);
+        assert_eq!(detail_altitude(&json!(true), &defaults).error, None);
+        assert_eq!(detail_altitude(&json!("10 ft"), &defaults).error, None);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["8848 m", "29032 ft", "100", "9000 km", "high"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+        for number in [8848.0, -500.0, 100000.0, f64::NAN] {
+            assert_eq!(validate_f64(number), coerce_value(&Value::from(number), &Value::Null).is_some(), "{number}");
+        }
+    }
+}
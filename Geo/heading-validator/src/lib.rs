@@ -0,0 +1,387 @@
+// ABOUTME: WASM component for compass heading validation
+// ABOUTME: Provides validate and coerce functions for headings in degrees from 0 up to 360
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Accept fractional degrees such as 271.5
+    decimal: bool,
+    // Accept 360 for north, as aviation headings and runway numbers do
+    allow_360: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when a specific reason was detected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+const OUT_OF_RANGE: &str = messages::ErrorCode::OutOfRange.as_str();
+
+// A number of degrees, optionally zero-padded ("045") and followed by a degree sign
+fn parse_degrees(text: &str) -> Option<f64> {
+    let number = text.trim();
+    let number = number.strip_suffix('°').unwrap_or(number).trim_end();
+
+    // Only plain decimal notation: no signs, exponents, "inf" or "nan"
+    if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return None;
+    }
+    number.parse::<f64>().ok()
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the heading as a number of degrees
+fn coerce_heading(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let degrees = match value {
+        Value::Number(n) => n.as_f64()?,
+        Value::String(s) => parse_degrees(s)?,
+        _ => return None,
+    };
+    coerce_degrees(degrees, options)
+}
+
+// Checks a number of degrees against the options; shared with the fast paths
+fn coerce_degrees(degrees: f64, options: &ValidationOptions) -> Option<Value> {
+    if degrees.fract() != 0.0 && !options.decimal {
+        return None;
+    }
+
+    let in_range = if options.allow_360 { (0.0..=360.0).contains(&degrees) } else { (0.0..360.0).contains(&degrees) };
+    if !in_range {
+        return None;
+    }
+
+    if degrees.fract() == 0.0 {
+        Some(Value::from(degrees as u64))
+    } else {
+        serde_json::Number::from_f64(degrees).map(Value::Number)
+    }
+}
+
+// Detailed validation; a number of degrees off the compass gets the out_of_range error code
+fn detail_heading(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let mut result = ValidationResult::from(coerce_heading(value, options));
+    let degrees = match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => parse_degrees(s),
+        _ => None,
+    };
+    if !result.valid && degrees.is_some_and(|degrees| degrees.fract() == 0.0 || options.decimal) {
+        result.error = Some(OUT_OF_RANGE);
+    }
+    result
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_heading(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_heading(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_heading(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_heading(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_heading(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_heading(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_heading(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_heading(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_heading(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    parse_degrees(value).and_then(|degrees| coerce_degrees(degrees, &ValidationOptions::default())).is_some()
+}
+
+// Fast path for a JS number with the default options, as validate_str is for strings
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_f64(value: f64) -> bool {
+    coerce_degrees(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_headings() {
+        let defaults = ValidationOptions::default();
+
+        for heading in [json!(0), json!(90), json!(359), json!("045"), json!("270°"), json!(" 180 "), json!(90.0)] {
+            assert!(validate_heading(&heading, &defaults), "{}", heading);
+        }
+    }
+
+    #[test]
+    fn test_invalid_headings() {
+        let defaults = ValidationOptions::default();
+
+        for heading in [
+            json!(360), json!(-1), json!(400), json!(90.5), json!("-90"), json!("+90"), json!("1e2"),
+            json!("north"), json!(""), json!("°"), json!(null), json!(true),
+        ] {
+            assert!(!validate_heading(&heading, &defaults), "{}", heading);
+        }
+    }
+
+    #[test]
+    fn test_decimal_and_360() {
+        let decimal = options(json!({"decimal": true}));
+        let aviation = options(json!({"allow_360": true}));
+
+        assert!(validate_heading(&json!(271.5), &decimal));
+        assert!(validate_heading(&json!("359.99°"), &decimal));
+        assert!(!validate_heading(&json!(360), &decimal));
+
+        assert!(validate_heading(&json!(360), &aviation));
+        assert!(validate_heading(&json!("360"), &aviation));
+        assert!(!validate_heading(&json!(360.5), &options(json!({"decimal": true, "allow_360": true}))));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_heading(&json!("045°"), &defaults), Some(json!(45)));
+        assert_eq!(coerce_heading(&json!(90.0), &defaults), Some(json!(90)));
+        assert_eq!(coerce_heading(&json!("12.25"), &options(json!({"decimal": true}))), Some(json!(12.25)));
+        assert_eq!(coerce_heading(&json!(361), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 90})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 90, "options": {"decimal": true, "allow_360": true}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 90, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_heading(&json!("090"), &defaults)),
+            ValidationResult { valid: true, value: json!(90), error: None }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_heading(&json!(360), &defaults)),
+            ValidationResult { valid: false, value: Value::Null, error: None }
+        );
+
+        // Degrees off the compass say so; fractions without the decimal option and anything
+        // else have no specific reason
+        assert_eq!(detail_heading(&json!(360), &defaults).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_heading(&json!("400°"), &defaults).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_heading(&json!(-10), &defaults).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_heading(&json!(12.5), &defaults).error, None);
+        assert_eq!(detail_heading(&json!("north"), &defaults).error, None);
+        assert_eq!(detail_heading(&json!(90), &defaults).error, None);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["045", "270°", "360", "12.5", "-10"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+        for number in [45.0, 359.0, 360.0, 12.5, -1.0] {
+            assert_eq!(validate_f64(number), coerce_value(&Value::from(number), &Value::Null).is_some(), "{number}");
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// Types for the heading validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Accept fractional degrees such as 271.5 (default: false) */
+    decimal?: boolean;
+    /** Accept 360 for north, as aviation headings and runway numbers do (default: false) */
+    allow_360?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = number | string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = number;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "out_of_range" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,577 @@
+// ABOUTME: WASM component for ARIA role and attribute validation
+// ABOUTME: Provides validate and coerce functions to check roles and aria-* attributes against WAI-ARIA 1.2
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// Concrete (non-abstract) roles from the WAI-ARIA 1.2 role table
+const ROLES: &[&str] = &[
+    "alert", "alertdialog", "application", "article", "banner", "blockquote",
+    "button", "caption", "cell", "checkbox", "code", "columnheader", "combobox",
+    "complementary", "contentinfo", "definition", "deletion", "dialog",
+    "directory", "document", "emphasis", "feed", "figure", "form", "generic",
+    "grid", "gridcell", "group", "heading", "img", "insertion", "link", "list",
+    "listbox", "listitem", "log", "main", "marquee", "math", "menu", "menubar",
+    "menuitem", "menuitemcheckbox", "menuitemradio", "meter", "navigation",
+    "none", "note", "option", "paragraph", "presentation", "progressbar",
+    "radio", "radiogroup", "region", "row", "rowgroup", "rowheader",
+    "scrollbar", "search", "searchbox", "separator", "slider", "spinbutton",
+    "status", "strong", "subscript", "superscript", "switch", "tab", "table",
+    "tablist", "tabpanel", "term", "textbox", "time", "timer", "toolbar",
+    "tooltip", "tree", "treegrid", "treeitem",
+];
+
+// Value types from the WAI-ARIA 1.2 "Value" column of each state and property
+enum AttributeType {
+    TrueFalse,
+    TrueFalseUndefined,
+    Tristate,
+    IdReference,
+    IdReferenceList,
+    Integer,
+    Number,
+    String,
+    Token(&'static [&'static str]),
+    TokenList(&'static [&'static str]),
+}
+
+fn attribute_type(name: &str) -> Option<AttributeType> {
+    use AttributeType::*;
+
+    let kind = match name {
+        "aria-activedescendant" => IdReference,
+        "aria-atomic" => TrueFalse,
+        "aria-autocomplete" => Token(&["inline", "list", "both", "none"]),
+        "aria-braillelabel" => String,
+        "aria-brailleroledescription" => String,
+        "aria-busy" => TrueFalse,
+        "aria-checked" => Tristate,
+        "aria-colcount" => Integer,
+        "aria-colindex" => Integer,
+        "aria-colindextext" => String,
+        "aria-colspan" => Integer,
+        "aria-controls" => IdReferenceList,
+        "aria-current" => Token(&["page", "step", "location", "date", "time", "true", "false"]),
+        "aria-describedby" => IdReferenceList,
+        "aria-description" => String,
+        "aria-details" => IdReference,
+        "aria-disabled" => TrueFalse,
+        "aria-dropeffect" => TokenList(&["copy", "execute", "link", "move", "none", "popup"]),
+        "aria-errormessage" => IdReference,
+        "aria-expanded" => TrueFalseUndefined,
+        "aria-flowto" => IdReferenceList,
+        "aria-grabbed" => TrueFalseUndefined,
+        "aria-haspopup" => Token(&["false", "true", "menu", "listbox", "tree", "grid", "dialog"]),
+        "aria-hidden" => TrueFalseUndefined,
+        "aria-invalid" => Token(&["grammar", "false", "spelling", "true"]),
+        "aria-keyshortcuts" => String,
+        "aria-label" => String,
+        "aria-labelledby" => IdReferenceList,
+        "aria-level" => Integer,
+        "aria-live" => Token(&["assertive", "off", "polite"]),
+        "aria-modal" => TrueFalse,
+        "aria-multiline" => TrueFalse,
+        "aria-multiselectable" => TrueFalse,
+        "aria-orientation" => Token(&["horizontal", "undefined", "vertical"]),
+        "aria-owns" => IdReferenceList,
+        "aria-placeholder" => String,
+        "aria-posinset" => Integer,
+        "aria-pressed" => Tristate,
+        "aria-readonly" => TrueFalse,
+        "aria-relevant" => TokenList(&["additions", "all", "removals", "text"]),
+        "aria-required" => TrueFalse,
+        "aria-roledescription" => String,
+        "aria-rowcount" => Integer,
+        "aria-rowindex" => Integer,
+        "aria-rowindextext" => String,
+        "aria-rowspan" => Integer,
+        "aria-selected" => TrueFalseUndefined,
+        "aria-setsize" => Integer,
+        "aria-sort" => Token(&["ascending", "descending", "none", "other"]),
+        "aria-valuemax" => Number,
+        "aria-valuemin" => Number,
+        "aria-valuenow" => Number,
+        "aria-valuetext" => String,
+        _ => return None,
+    };
+
+    Some(kind)
+}
+
+// A role attribute is a space-separated list of fallback roles; every token must be a concrete role
+// Returns the list lowercased and joined with single spaces
+fn coerce_role(text: &str) -> Option<String> {
+    let tokens: Vec<String> = text
+        .split_ascii_whitespace()
+        .map(|token| token.to_ascii_lowercase())
+        .collect();
+
+    if tokens.is_empty() || !tokens.iter().all(|token| ROLES.contains(&token.as_str())) {
+        return None;
+    }
+
+    Some(tokens.join(" "))
+}
+
+// Returns the canonical JSON form of an attribute value: booleans for true/false states,
+// numbers for numeric properties and lowercased tokens for enumerated values
+fn coerce_attribute_value(kind: &AttributeType, value: &Value) -> Option<Value> {
+    use AttributeType::*;
+
+    // Booleans and numbers are accepted directly where the type allows them
+    let text = match value {
+        Value::String(s) => s.trim().to_ascii_lowercase(),
+        Value::Bool(b) => {
+            let word = if *b { "true" } else { "false" };
+            return match kind {
+                TrueFalse | TrueFalseUndefined | Tristate => Some(Value::Bool(*b)),
+                Token(tokens) if tokens.contains(&word) => Some(Value::from(word)),
+                _ => None,
+            };
+        }
+        Value::Number(n) => {
+            return match kind {
+                Integer if n.is_i64() || n.is_u64() => Some(Value::Number(n.clone())),
+                Number => Some(Value::Number(n.clone())),
+                _ => None,
+            };
+        }
+        _ => return None,
+    };
+
+    match kind {
+        TrueFalse | TrueFalseUndefined | Tristate => match text.as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            "undefined" if !matches!(kind, TrueFalse) => Some(Value::from(text)),
+            "mixed" if matches!(kind, Tristate) => Some(Value::from(text)),
+            _ => None,
+        },
+        // ID references are case-sensitive, so only surrounding whitespace is normalized
+        IdReference => {
+            let id = value.as_str()?.trim();
+            (!id.is_empty() && !id.contains(char::is_whitespace)).then(|| Value::from(id))
+        },
+        IdReferenceList => {
+            let ids: Vec<&str> = value.as_str()?.split_ascii_whitespace().collect();
+            (!ids.is_empty()).then(|| Value::from(ids.join(" ")))
+        },
+        Integer => text.parse::<i64>().ok().map(Value::from),
+        Number => text
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        // String values are free-form, so the original (untrimmed) string is kept as is
+        String => Some(value.clone()),
+        Token(tokens) => tokens.contains(&text.as_str()).then(|| Value::from(text)),
+        TokenList(tokens) => {
+            let items: Vec<&str> = text.split_ascii_whitespace().collect();
+            (!items.is_empty() && items.iter().all(|t| tokens.contains(t)))
+                .then(|| Value::from(items.join(" ")))
+        }
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the normalized role list, attribute name or attribute map, or None when invalid
+fn coerce_aria(value: &Value, _options: &ValidationOptions) -> Option<Value> {
+    match value {
+        // A bare string is either an aria-* attribute name or a role attribute value
+        Value::String(s) => coerce_aria_str(s),
+
+        // An object maps attribute names (role or aria-*) to their values
+        Value::Object(map) => {
+            if map.is_empty() {
+                return None;
+            }
+
+            let mut normalized = serde_json::Map::new();
+            for (name, attr_value) in map {
+                let name = name.to_ascii_lowercase();
+                let coerced = if name == "role" {
+                    coerce_role(attr_value.as_str()?).map(Value::from)
+                } else {
+                    coerce_attribute_value(&attribute_type(&name)?, attr_value)
+                };
+                normalized.insert(name, coerced?);
+            }
+
+            Some(Value::Object(normalized))
+        },
+
+        // All other types cannot describe ARIA markup
+        _ => None,
+    }
+}
+
+// The string half of coerce_aria; the fast path calls it directly
+fn coerce_aria_str(text: &str) -> Option<Value> {
+    let trimmed = text.trim().to_ascii_lowercase();
+    if trimmed.starts_with("aria-") {
+        attribute_type(&trimmed).map(|_| Value::from(trimmed))
+    } else {
+        coerce_role(&trimmed).map(Value::from)
+    }
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_aria(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_aria(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_aria(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_aria(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_aria(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_aria(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_aria(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_aria(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_aria(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_aria_str(value).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_roles() {
+        assert!(validate_aria(&json!("button"), &ValidationOptions::default()));
+        assert!(validate_aria(&json!("navigation"), &ValidationOptions::default()));
+        assert!(validate_aria(&json!("treegrid"), &ValidationOptions::default()));
+        assert!(validate_aria(&json!("  dialog  "), &ValidationOptions::default())); // With whitespace
+        assert!(validate_aria(&json!("Button"), &ValidationOptions::default())); // Case-insensitive
+
+        // Fallback role lists
+        assert!(validate_aria(&json!("switch checkbox"), &ValidationOptions::default()));
+        assert!(validate_aria(&json!("region\tgroup"), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_invalid_roles() {
+        assert!(!validate_aria(&json!(""), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!("   "), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!("buton"), &ValidationOptions::default())); // Typo
+        assert!(!validate_aria(&json!("button fancy"), &ValidationOptions::default())); // Unknown fallback
+
+        // Abstract roles must not be used by authors
+        assert!(!validate_aria(&json!("widget"), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!("landmark"), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!("roletype"), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_attribute_names() {
+        assert!(validate_aria(&json!("aria-label"), &ValidationOptions::default()));
+        assert!(validate_aria(&json!("aria-describedby"), &ValidationOptions::default()));
+        assert!(validate_aria(&json!("ARIA-HIDDEN"), &ValidationOptions::default()));
+
+        assert!(!validate_aria(&json!("aria-"), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!("aria-lable"), &ValidationOptions::default())); // Typo
+        assert!(!validate_aria(&json!("aria-role"), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_attribute_maps() {
+        assert!(validate_aria(&json!({"role": "checkbox", "aria-checked": "mixed"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-checked": true}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-expanded": "undefined"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-hidden": false}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-level": 2, "role": "heading"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-level": "3"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-valuenow": 42.5}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-valuemin": "-10"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-labelledby": "title subtitle"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-activedescendant": "option-3"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-live": "Polite"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-relevant": "additions text"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-current": "page"}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-current": true}), &ValidationOptions::default()));
+        assert!(validate_aria(&json!({"aria-label": ""}), &ValidationOptions::default())); // Strings are free-form
+    }
+
+    #[test]
+    fn test_invalid_attribute_maps() {
+        assert!(!validate_aria(&json!({}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"role": "fancy"}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"role": 1}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-lable": "Close"}), &ValidationOptions::default())); // Unknown attribute
+        assert!(!validate_aria(&json!({"class": "btn"}), &ValidationOptions::default())); // Not an ARIA attribute
+        assert!(!validate_aria(&json!({"aria-checked": "maybe"}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-modal": "mixed"}), &ValidationOptions::default())); // Tristate only on checked/pressed
+        assert!(!validate_aria(&json!({"aria-level": 2.5}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-level": "two"}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-valuenow": "NaN"}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-activedescendant": "two ids"}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-controls": "   "}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-live": "loud"}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-relevant": "additions everything"}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-label": null}), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!({"aria-sort": true}), &ValidationOptions::default())); // No boolean token in aria-sort
+    }
+
+    #[test]
+    fn test_other_types() {
+        assert!(!validate_aria(&json!(null), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!(true), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!(42), &ValidationOptions::default()));
+        assert!(!validate_aria(&json!(["button"]), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_options() {
+        // Options are optional and may be empty
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "button"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "button", "options": {}})).is_ok());
+
+        // Unknown options are rejected so typos never silently change behaviour
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "button", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_coerce() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(coerce_aria(&json!(" Switch  checkbox "), &options), Some(json!("switch checkbox")));
+        assert_eq!(coerce_aria(&json!("ARIA-Label"), &options), Some(json!("aria-label")));
+        assert_eq!(
+            coerce_aria(&json!({"Role": "Heading", "aria-level": "2", "aria-hidden": "FALSE"}), &options),
+            Some(json!({"role": "heading", "aria-level": 2, "aria-hidden": false}))
+        );
+        assert_eq!(
+            coerce_aria(&json!({"aria-checked": "Mixed", "aria-labelledby": " a  b ", "aria-label": " Close "}), &options),
+            Some(json!({"aria-checked": "mixed", "aria-labelledby": "a b", "aria-label": " Close "}))
+        );
+        assert_eq!(coerce_aria(&json!({"aria-current": true}), &options), Some(json!({"aria-current": "true"})));
+
+        assert_eq!(coerce_aria(&json!({"aria-level": "two"}), &options), None);
+        assert_eq!(coerce_aria(&json!("widget"), &options), None);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_aria(&json!("Button"), &options)),
+            ValidationResult { valid: true, value: json!("button") }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_aria(&json!("widget"), &options)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["button", 1, "aria-label"]})).unwrap();
+        assert_eq!(batch.values.len(), 3);
+        assert!(serde_json::from_value::<BatchInput>(json!({"values": [], "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["button", "aria-label", "ARIA-Hidden", "aria-bogus", "notarole"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
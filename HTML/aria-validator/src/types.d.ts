@@ -0,0 +1,34 @@
+// Types for the aria validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** The validator takes no options; any option makes every value invalid */
+export type ValidationOptions = Record<string, never>;
+
+/** A value accepted by the validator */
+export type ValidationValue = string | Record<string, unknown>;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string | Record<string, unknown>;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,589 @@
+// ABOUTME: WASM component for delimited lists such as tags, CC lists and CSV cells holding several values
+// ABOUTME: Splits a string on a configurable delimiter and validates every item with another validator
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+mod split;
+
+use split::Syntax;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// The locales that write decimals with a comma, shared with the float validator
+#[path = "../../../mode/mode.rs"]
+#[allow(dead_code)]
+mod mode;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Separator between items; defaults to the locale's list separator, or "," without a locale
+    delimiter: Option<Delimiter>,
+    // BCP 47 language tag, such as "de-DE", whose list separator is the default delimiter
+    locale: Option<String>,
+    // Character around an item that contains the delimiter; null turns quoting off
+    quote: Option<char>,
+    // Keep the whitespace around unquoted items, which is trimmed by default
+    preserve_whitespace: bool,
+    // Drop empty items, such as the one after a trailing delimiter, instead of validating them
+    skip_empty_items: bool,
+    // Datatype every item must have, such as "email" or "integer"; any item passes when unset
+    item_type: Option<String>,
+    // Options passed to the item type's validator for each item
+    item_options: Value,
+    // Reject items whose canonical value repeats an earlier item's
+    unique: bool,
+    // Bounds on the number of items, counted after empty ones are skipped
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    // Vocabulary the items' canonical values must come from
+    allowed_values: Option<Vec<Value>>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            delimiter: None,
+            locale: None,
+            quote: Some('"'),
+            preserve_whitespace: false,
+            skip_empty_items: false,
+            item_type: None,
+            item_options: Value::Null,
+            unique: false,
+            min_items: None,
+            max_items: None,
+            allowed_values: None,
+        }
+    }
+}
+
+// A non-empty delimiter
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub struct Delimiter(String);
+
+impl TryFrom<String> for Delimiter {
+    type Error = &'static str;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        if text.is_empty() {
+            return Err("empty delimiter");
+        }
+        Ok(Delimiter(text))
+    }
+}
+
+impl From<Delimiter> for String {
+    fn from(delimiter: Delimiter) -> Self {
+        delimiter.0
+    }
+}
+
+// The list separator for a locale: a semicolon where decimals are written with a comma, as
+// spreadsheets do for those locales
+fn locale_delimiter(locale: &str) -> &'static str {
+    if mode::decimal_comma(locale) {
+        ";"
+    } else {
+        ","
+    }
+}
+
+impl ValidationOptions {
+    fn syntax(&self) -> Syntax<'_> {
+        let delimiter = match (&self.delimiter, &self.locale) {
+            (Some(delimiter), _) => delimiter.0.as_str(),
+            (None, Some(locale)) => locale_delimiter(locale),
+            (None, None) => ",",
+        };
+        Syntax { delimiter, quote: self.quote, trim: !self.preserve_whitespace }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Outcome for each item, in order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    items: Vec<ItemResult>,
+    // Why the value was rejected, when the list itself could not be split or checked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            items: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+impl ValidationResult {
+    fn error(code: &'static str) -> Self {
+        ValidationResult { error: Some(code), ..ValidationResult::from(None) }
+    }
+}
+
+// Outcome for a single item: the text it was split into, and the verdict on it
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ItemResult {
+    input: String,
+    valid: bool,
+    value: Value,
+    // Why an item that passed its item type was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+// Error codes reported in detailed results, for the list and for individual items
+const UNKNOWN_ITEM_TYPE: &str = "unknown_item_type";
+const TOO_FEW_ITEMS: &str = "too_few_items";
+const TOO_MANY_ITEMS: &str = "too_many_items";
+const DUPLICATE_ITEM: &str = "duplicate_item";
+const DISALLOWED_VALUE: &str = "disallowed_value";
+
+// The set constraints on an item that passed its item type, given the canonical values before it
+fn check_item(value: &Value, earlier: &[Option<Value>], options: &ValidationOptions) -> Option<&'static str> {
+    if options.allowed_values.as_ref().is_some_and(|allowed| !allowed.contains(value)) {
+        return Some(DISALLOWED_VALUE);
+    }
+    if options.unique && earlier.iter().flatten().any(|other| other == value) {
+        return Some(DUPLICATE_ITEM);
+    }
+    None
+}
+
+// Detailed validation: splits the list and coerces each item, keeping every item's outcome
+fn detail_list_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    let coerce_item = match options.item_type.as_deref() {
+        Some(name) => match validator_registry::lookup(name) {
+            Some(coerce) => Some(coerce),
+            None => return ValidationResult::error(UNKNOWN_ITEM_TYPE),
+        },
+        None => None,
+    };
+
+    let syntax = options.syntax();
+    // A blank string is an empty list rather than a list of one empty item
+    let mut inputs = if text.trim().is_empty() && syntax.trim {
+        Vec::new()
+    } else {
+        match split::split(text, &syntax) {
+            Ok(inputs) => inputs,
+            Err(error) => return ValidationResult::error(error.code()),
+        }
+    };
+    if options.skip_empty_items {
+        inputs.retain(|input| !input.is_empty());
+    }
+
+    let coerced: Vec<Option<Value>> = inputs
+        .iter()
+        .map(|input| {
+            let item = Value::String(input.clone());
+            match coerce_item {
+                Some(coerce) => coerce(&item, &options.item_options),
+                None => Some(item),
+            }
+        })
+        .collect();
+
+    let items: Vec<ItemResult> = inputs
+        .into_iter()
+        .enumerate()
+        .map(|(index, input)| {
+            let Some(value) = &coerced[index] else {
+                return ItemResult { input, valid: false, value: Value::Null, error: None };
+            };
+            match check_item(value, &coerced[..index], options) {
+                Some(code) => ItemResult { input, valid: false, value: Value::Null, error: Some(code) },
+                None => ItemResult { input, valid: true, value: value.clone(), error: None },
+            }
+        })
+        .collect();
+
+    let count_error = if options.min_items.is_some_and(|min| items.len() < min) {
+        Some(TOO_FEW_ITEMS)
+    } else if options.max_items.is_some_and(|max| items.len() > max) {
+        Some(TOO_MANY_ITEMS)
+    } else {
+        None
+    };
+    if count_error.is_some() || !items.iter().all(|item| item.valid) {
+        return ValidationResult { items, error: count_error, ..ValidationResult::from(None) };
+    }
+    let coerced = items.iter().map(|item| item.value.clone()).collect();
+    ValidationResult { items, ..ValidationResult::from(Some(Value::Array(coerced))) }
+}
+
+// Only strings can be valid; the fast path calls detail_list_str directly
+fn detail_list(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_list_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the items as a JSON array of their canonical values
+fn coerce_list(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_list(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_list(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_list(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_list(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_list(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_list(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_list(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_list(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_list(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_list(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_list_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_lists() {
+        let emails = options(json!({"item_type": "email"}));
+
+        assert!(validate_list(&json!("ada@example.com, grace@example.com"), &emails));
+        assert!(validate_list(&json!("ada@example.com"), &emails));
+        assert!(validate_list(&json!(""), &emails));
+        assert!(validate_list(&json!("1, 2 ,3"), &options(json!({"item_type": "integer"}))));
+        assert!(validate_list(&json!("rust, wasm, \"a, b\""), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_invalid_lists() {
+        let emails = options(json!({"item_type": "email"}));
+
+        assert!(!validate_list(&json!("ada@example.com, grace"), &emails));
+        assert!(!validate_list(&json!("ada@example.com,"), &emails));
+        assert!(!validate_list(&json!(["ada@example.com"]), &emails));
+        assert!(!validate_list(&json!(null), &ValidationOptions::default()));
+        assert!(!validate_list(&json!("\"unclosed, x"), &ValidationOptions::default()));
+        assert!(!validate_list(&json!("a"), &options(json!({"item_type": "nonsense"}))));
+    }
+
+    #[test]
+    fn test_delimiter_and_locale() {
+        let floats = json!({"item_type": "float"});
+
+        let semicolons = options(json!({"item_type": "float", "delimiter": ";"}));
+        assert_eq!(coerce_list(&json!("1.5; 2"), &semicolons), Some(json!([1.5, 2.0])));
+
+        // A German list separates with semicolons, leaving commas to the numbers
+        let german = options(json!({"locale": "de-DE"}));
+        assert_eq!(coerce_list(&json!("1,5;2,25"), &german), Some(json!(["1,5", "2,25"])));
+        let english = options(json!({"locale": "en_US", "item_type": "float"}));
+        assert_eq!(coerce_list(&json!("1.5,2"), &english), Some(json!([1.5, 2.0])));
+
+        // An explicit delimiter wins over the locale's
+        let piped = options(json!({"locale": "fr", "delimiter": "|", "item_type": "float"}));
+        assert_eq!(coerce_list(&json!("1|2"), &piped), Some(json!([1.0, 2.0])));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"delimiter": ""})).is_err());
+        assert!(coerce_value(&json!("1"), &floats).is_some());
+    }
+
+    #[test]
+    fn test_quoting_and_whitespace() {
+        let defaults = ValidationOptions::default();
+        assert_eq!(coerce_list(&json!(r#"a, "b, c", "d""e""#), &defaults), Some(json!(["a", "b, c", "d\"e"])));
+
+        let unquoted = options(json!({"quote": null}));
+        assert_eq!(coerce_list(&json!(r#""a, b""#), &unquoted), Some(json!(["\"a", "b\""])));
+        let single = options(json!({"quote": "'"}));
+        assert_eq!(coerce_list(&json!("'a, b', c"), &single), Some(json!(["a, b", "c"])));
+
+        let preserved = options(json!({"preserve_whitespace": true}));
+        assert_eq!(coerce_list(&json!(" a , b"), &preserved), Some(json!([" a ", " b"])));
+
+        let skipping = options(json!({"skip_empty_items": true, "item_type": "integer"}));
+        assert_eq!(coerce_list(&json!("1,,2,"), &skipping), Some(json!([1, 2])));
+    }
+
+    #[test]
+    fn test_set_constraints() {
+        let tags = options(json!({
+            "unique": true,
+            "max_items": 3,
+            "allowed_values": ["rust", "wasm", "web", "cli"],
+        }));
+        assert_eq!(coerce_list(&json!("rust, wasm"), &tags), Some(json!(["rust", "wasm"])));
+        assert!(validate_list(&json!(""), &tags));
+        assert!(!validate_list(&json!("rust, go"), &tags));
+        assert!(!validate_list(&json!("rust, wasm, rust"), &tags));
+        assert!(!validate_list(&json!("rust, wasm, web, cli"), &tags));
+
+        let at_least_two = options(json!({"min_items": 2, "skip_empty_items": true}));
+        assert!(validate_list(&json!("a,b"), &at_least_two));
+        assert!(!validate_list(&json!("a,,"), &at_least_two));
+
+        // Uniqueness and the vocabulary apply to canonical values
+        let integers = options(json!({"item_type": "integer", "unique": true, "allowed_values": [1, 2, 3]}));
+        assert!(validate_list(&json!("1, 2, 3"), &integers));
+        assert!(!validate_list(&json!("1, 01"), &integers));
+        assert!(!validate_list(&json!("1, 4"), &integers));
+    }
+
+    #[test]
+    fn test_set_constraints_detailed() {
+        let tags = options(json!({"unique": true, "allowed_values": ["a", "b"]}));
+        let result = detail_list(&json!("a, c, a"), &tags);
+        assert_eq!(result.error, None);
+        assert_eq!(
+            result.items.iter().map(|item| item.error).collect::<Vec<_>>(),
+            vec![None, Some(DISALLOWED_VALUE), Some(DUPLICATE_ITEM)]
+        );
+
+        let bounded = options(json!({"min_items": 2, "max_items": 3}));
+        assert_eq!(detail_list(&json!("a"), &bounded).error, Some(TOO_FEW_ITEMS));
+        assert_eq!(detail_list(&json!("a,b,c,d"), &bounded).error, Some(TOO_MANY_ITEMS));
+        assert_eq!(detail_list(&json!("a,b,c,d"), &bounded).items.len(), 4);
+    }
+
+    #[test]
+    fn test_item_options() {
+        let v4 = options(json!({"item_type": "ip", "item_options": {"version": 4}}));
+
+        assert!(validate_list(&json!("192.168.0.1, 10.0.0.1"), &v4));
+        assert!(!validate_list(&json!("192.168.0.1, ::1"), &v4));
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a", "options": {"delimiter": ";", "quote": "'"}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a", "options": {"quote": "ab"}})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let integers = options(json!({"item_type": "integer"}));
+
+        assert_eq!(
+            detail_list(&json!("1, x"), &integers),
+            ValidationResult {
+                valid: false,
+                value: Value::Null,
+                items: vec![
+                    ItemResult { input: "1".to_string(), valid: true, value: json!(1), error: None },
+                    ItemResult { input: "x".to_string(), valid: false, value: Value::Null, error: None },
+                ],
+                error: None,
+            }
+        );
+        assert_eq!(detail_list(&json!("1, 2"), &integers).value, json!([1, 2]));
+        assert_eq!(detail_list(&json!("\"1"), &integers).error, Some("unterminated_quote"));
+        assert_eq!(detail_list(&json!("\"1\"2"), &integers).error, Some("text_after_quote"));
+        assert_eq!(detail_list(&json!("1"), &options(json!({"item_type": "list"}))).error, Some(UNKNOWN_ITEM_TYPE));
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["a, b, c", "single", "", ",,"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
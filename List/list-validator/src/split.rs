@@ -0,0 +1,139 @@
+// ABOUTME: Splits a delimited string into items, honouring quotes and optionally trimming whitespace
+// ABOUTME: Follows CSV conventions: a quoted item may contain the delimiter, and a doubled quote is a literal one
+
+// How a list is written
+pub struct Syntax<'a> {
+    pub delimiter: &'a str,
+    pub quote: Option<char>,
+    pub trim: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SplitError {
+    // A quoted item is never closed
+    UnterminatedQuote,
+    // Something other than whitespace follows a closing quote before the next delimiter
+    TextAfterQuote,
+}
+
+impl SplitError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SplitError::UnterminatedQuote => "unterminated_quote",
+            SplitError::TextAfterQuote => "text_after_quote",
+        }
+    }
+}
+
+// Splits text into its items; there is always at least one, possibly empty
+pub fn split(text: &str, syntax: &Syntax) -> Result<Vec<String>, SplitError> {
+    let mut items = Vec::new();
+    let mut rest = text;
+    loop {
+        let (item, remainder) = next_item(rest, syntax)?;
+        items.push(item);
+        match remainder {
+            Some(remainder) => rest = remainder,
+            None => return Ok(items),
+        }
+    }
+}
+
+// Reads one item, returning it and the text after its delimiter (None after the last item)
+fn next_item<'t>(text: &'t str, syntax: &Syntax) -> Result<(String, Option<&'t str>), SplitError> {
+    let start = skip_whitespace(text, syntax.delimiter);
+
+    if let Some(quote) = syntax.quote.filter(|quote| start.starts_with(*quote)) {
+        let (item, after) = quoted(&start[quote.len_utf8()..], quote)?;
+        let after = skip_whitespace(after, syntax.delimiter);
+        return match after.strip_prefix(syntax.delimiter) {
+            Some(remainder) => Ok((item, Some(remainder))),
+            None if after.is_empty() => Ok((item, None)),
+            None => Err(SplitError::TextAfterQuote),
+        };
+    }
+
+    let (item, remainder) = match text.find(syntax.delimiter) {
+        Some(at) => (&text[..at], Some(&text[at + syntax.delimiter.len()..])),
+        None => (text, None),
+    };
+    let item = if syntax.trim { item.trim() } else { item };
+    Ok((item.to_string(), remainder))
+}
+
+// The content of a quoted item, whose opening quote has been consumed, and the text after it
+pub fn quoted(text: &str, quote: char) -> Result<(String, &str), SplitError> {
+    let mut item = String::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((at, c)) = chars.next() {
+        if c != quote {
+            item.push(c);
+        } else if chars.next_if(|(_, next)| *next == quote).is_some() {
+            item.push(quote);
+        } else {
+            return Ok((item, &text[at + quote.len_utf8()..]));
+        }
+    }
+    Err(SplitError::UnterminatedQuote)
+}
+
+// Leading whitespace, stopping short of a delimiter that is itself whitespace (such as a tab)
+pub fn skip_whitespace<'t>(mut text: &'t str, delimiter: &str) -> &'t str {
+    while let Some(c) = text.chars().next().filter(|c| c.is_whitespace()) {
+        if text.starts_with(delimiter) {
+            break;
+        }
+        text = &text[c.len_utf8()..];
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: Syntax = Syntax { delimiter: ",", quote: Some('"'), trim: true };
+
+    fn items(text: &str, syntax: &Syntax) -> Vec<String> {
+        split(text, syntax).unwrap()
+    }
+
+    #[test]
+    fn test_unquoted() {
+        assert_eq!(items("a,b,c", &CSV), vec!["a", "b", "c"]);
+        assert_eq!(items(" a , b ,c ", &CSV), vec!["a", "b", "c"]);
+        assert_eq!(items("a,,b,", &CSV), vec!["a", "", "b", ""]);
+        assert_eq!(items("", &CSV), vec![""]);
+
+        let untrimmed = Syntax { trim: false, ..CSV };
+        assert_eq!(items(" a , b", &untrimmed), vec![" a ", " b"]);
+    }
+
+    #[test]
+    fn test_quoted() {
+        assert_eq!(items(r#""a,b",c"#, &CSV), vec!["a,b", "c"]);
+        assert_eq!(items(r#" "  padded  " , x"#, &CSV), vec!["  padded  ", "x"]);
+        assert_eq!(items(r#""say ""hi""",x"#, &CSV), vec![r#"say "hi""#, "x"]);
+        assert_eq!(items(r#""""#, &CSV), vec![""]);
+        assert_eq!(items(r#"a"b,c"#, &CSV), vec![r#"a"b"#, "c"]);
+
+        assert_eq!(split(r#""open,x"#, &CSV), Err(SplitError::UnterminatedQuote));
+        assert_eq!(split(r#""a"b,c"#, &CSV), Err(SplitError::TextAfterQuote));
+
+        let unquoted = Syntax { quote: None, ..CSV };
+        assert_eq!(items(r#""a,b""#, &unquoted), vec![r#""a"#, r#"b""#]);
+    }
+
+    #[test]
+    fn test_delimiters() {
+        let semicolon = Syntax { delimiter: ";", ..CSV };
+        assert_eq!(items("1,5; 2,25", &semicolon), vec!["1,5", "2,25"]);
+
+        let tab = Syntax { delimiter: "\t", ..CSV };
+        assert_eq!(items("a\t\tb", &tab), vec!["a", "", "b"]);
+        assert_eq!(items("a \t \"b\"", &tab), vec!["a", "b"]);
+
+        let pipes = Syntax { delimiter: " | ", quote: Some('\''), trim: true };
+        assert_eq!(items("a | 'b | c' | d", &pipes), vec!["a", "b | c", "d"]);
+    }
+}
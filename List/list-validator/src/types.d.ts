@@ -0,0 +1,59 @@
+// Types for the list validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Separator between items, any non-empty string; without it, locale decides (default: ",") */
+    delimiter?: string;
+    /** BCP 47 language tag whose list separator is the default delimiter */
+    locale?: string;
+    /** Character that quotes an item containing the delimiter; null turns quoting off (default: "\"") */
+    quote?: string | null;
+    /** Keep the whitespace around unquoted items instead of trimming it (default: false) */
+    preserve_whitespace?: boolean;
+    /** Drop empty items instead of validating them (default: false) */
+    skip_empty_items?: boolean;
+    /** Datatype every item must have; without it any item passes */
+    item_type?: string;
+    /** Options passed to the item type's validator for each item */
+    item_options?: unknown;
+    /** Reject items whose canonical value repeats an earlier item's (default: false) */
+    unique?: boolean;
+    /** Fewest items allowed, counted after empty items are skipped */
+    min_items?: number;
+    /** Most items allowed */
+    max_items?: number;
+    /** Canonical values the items must come from */
+    allowed_values?: unknown[];
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = unknown[];
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Outcome for each item, in order */
+    items?: { input: string; valid: boolean; value: unknown; error?: "duplicate_item" | "disallowed_value" | "empty_value" | "invalid"; message?: string }[];
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "unknown_item_type" | "too_few_items" | "too_many_items" | "unterminated_quote" | "text_after_quote" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,355 @@
+// ABOUTME: WASM component for multipart boundary delimiters, for upload proxies and debugging tools
+// ABOUTME: Checks the RFC 2046 rules: 1 to 70 characters from a limited set, not ending in a space
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Shortest boundary accepted; a short boundary is more likely to turn up inside a part's
+    // content
+    min_length: usize,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { min_length: 1 }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const INVALID_LENGTH: &str = "invalid_length";
+const INVALID_CHARACTER: &str = "invalid_character";
+const TRAILING_SPACE: &str = "trailing_space";
+
+// RFC 2046 bchars: the characters that survive mail gateways unchanged
+fn is_bchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b" '()+_,-./:=?".contains(&b)
+}
+
+// The boundary rules; a boundary copied from a Content-Type header may keep its quotes, which
+// are not part of it
+fn check_boundary(text: &str, options: &ValidationOptions) -> Result<String, &'static str> {
+    let boundary = text
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(text);
+
+    if boundary.len() < options.min_length.max(1) || boundary.len() > 70 {
+        return Err(INVALID_LENGTH);
+    }
+    if !boundary.bytes().all(is_bchar) {
+        return Err(INVALID_CHARACTER);
+    }
+    if boundary.ends_with(' ') {
+        return Err(TRAILING_SPACE);
+    }
+
+    Ok(boundary.to_string())
+}
+
+// Detailed validation; only strings can be valid, and a string's problem is reported
+fn detail_boundary(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str().map(|text| check_boundary(text, options)) {
+        Some(Ok(boundary)) => ValidationResult::from(Some(Value::String(boundary))),
+        Some(Err(code)) => ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the boundary without its quotes; whitespace is part of a boundary, so it is neither
+// trimmed nor ignored
+fn coerce_boundary(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    check_boundary(value.as_str()?, options).ok().map(Value::String)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_boundary(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_boundary(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_boundary(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_boundary(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_boundary(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_boundary(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_boundary(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_boundary(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_boundary(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    check_boundary(value, &ValidationOptions::default()).is_ok()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error(boundary: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_boundary(&json!(boundary), options).error
+    }
+
+    #[test]
+    fn test_valid_boundaries() {
+        let defaults = ValidationOptions::default();
+
+        for boundary in ["----WebKitFormBoundary7MA4YWxkTrZu0gW", "simple boundary", "a", "'()+_,-./:=?", &"x".repeat(70)] {
+            assert!(validate_boundary(&json!(boundary), &defaults), "{boundary}");
+        }
+        assert_eq!(coerce_boundary(&json!("\"simple boundary\""), &defaults), Some(json!("simple boundary")));
+        assert_eq!(coerce_boundary(&json!(" leading"), &defaults), Some(json!(" leading")));
+    }
+
+    #[test]
+    fn test_invalid_boundaries() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(error("", &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error("\"\"", &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error(&"x".repeat(71), &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error("a;b", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("a\"b", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("naïve", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("tab\there", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("boundary ", &defaults), Some(TRAILING_SPACE));
+        assert_eq!(error("\"boundary \"", &defaults), Some(TRAILING_SPACE));
+
+        // Only strings can be boundaries, and other values have no specific reason
+        assert_eq!(detail_boundary(&json!(123), &defaults), ValidationResult::from(None));
+    }
+
+    #[test]
+    fn test_options() {
+        let long: ValidationOptions = serde_json::from_value(json!({"min_length": 16})).unwrap();
+        assert_eq!(error("short", &long), Some(INVALID_LENGTH));
+        assert!(validate_boundary(&json!("----boundary1234"), &long));
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "abc"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "abc", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_boundary(&json!("frontier"), &defaults),
+            ValidationResult { valid: true, value: json!("frontier"), error: None }
+        );
+        assert_eq!(
+            detail_boundary(&json!("front;ier"), &defaults),
+            ValidationResult { valid: false, value: Value::Null, error: Some(INVALID_CHARACTER) }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["abc", 1], "options": {"min_length": 2}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["frontier", "\"simple boundary\"", "boundary ", "a;b", ""] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
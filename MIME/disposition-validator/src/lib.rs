@@ -0,0 +1,538 @@
+// ABOUTME: WASM component for Content-Disposition header values, for upload proxies and debugging tools
+// ABOUTME: Parses the type and parameters (RFC 6266), decoding RFC 5987 filename* values, into an object
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Disposition types to accept, ignoring case; any type when unset
+    types: Option<Vec<String>>,
+    // Reject values without a filename or filename* parameter
+    require_filename: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const MALFORMED: &str = "malformed";
+const DUPLICATE_PARAMETER: &str = "duplicate_parameter";
+const INVALID_EXT_VALUE: &str = "invalid_ext_value";
+const UNSUPPORTED_CHARSET: &str = "unsupported_charset";
+const UNSUPPORTED_TYPE: &str = "unsupported_type";
+const MISSING_FILENAME: &str = "missing_filename";
+
+// The parts of a Content-Disposition value
+#[derive(Debug, PartialEq)]
+struct Disposition {
+    // Lowercased, as disposition types are case-insensitive
+    kind: String,
+    // Lowercased names with their values unquoted and decoded, in the order given
+    params: Vec<(String, String)>,
+}
+
+impl Disposition {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    // filename* carries the real name where it can't be written in ASCII, so it wins over the
+    // fallback in filename
+    fn filename(&self) -> Option<&str> {
+        self.param("filename*").or_else(|| self.param("filename"))
+    }
+
+    fn to_value(&self) -> Value {
+        let parameters: Map<String, Value> =
+            self.params.iter().map(|(key, value)| (key.clone(), Value::from(value.as_str()))).collect();
+
+        json!({
+            "type": self.kind,
+            "filename": self.filename(),
+            "parameters": parameters,
+        })
+    }
+}
+
+// RFC 7230 tokens, as used for disposition types and parameter names and values
+fn is_token(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+// RFC 5987 attr-char: the characters an ext-value may hold without percent-encoding
+fn is_attr_char(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b)
+}
+
+// The rest of a quoted string after its opening quote: the unescaped text, and what follows
+// the closing quote
+fn parse_quoted(text: &str) -> Result<(String, &str), &'static str> {
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &text[i + 1..])),
+            '\\' => value.push(chars.next().ok_or(MALFORMED)?.1),
+            c if c.is_control() && c != '\t' => return Err(MALFORMED),
+            c => value.push(c),
+        }
+    }
+    Err(MALFORMED)
+}
+
+// An RFC 5987 ext-value, charset'language'percent-encoded-text; UTF-8 and ISO-8859-1 are the
+// charsets every recipient must support, and the only ones decoded
+fn decode_ext_value(text: &str) -> Result<String, &'static str> {
+    let mut parts = text.splitn(3, '\'');
+    let (charset, language, encoded) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(charset), Some(language), Some(encoded)) => (charset, language, encoded),
+        _ => return Err(INVALID_EXT_VALUE),
+    };
+    if !is_token(charset) || !language.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+        return Err(INVALID_EXT_VALUE);
+    }
+
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit));
+            let hex = hex.and_then(|hex| std::str::from_utf8(hex).ok()).ok_or(INVALID_EXT_VALUE)?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| INVALID_EXT_VALUE)?);
+            i += 3;
+        } else if is_attr_char(bytes[i]) {
+            decoded.push(bytes[i]);
+            i += 1;
+        } else {
+            return Err(INVALID_EXT_VALUE);
+        }
+    }
+
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(decoded).map_err(|_| INVALID_EXT_VALUE)
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Ok(decoded.into_iter().map(char::from).collect())
+    } else {
+        Err(UNSUPPORTED_CHARSET)
+    }
+}
+
+// disposition-type *( ";" parameter ), where a parameter is name=token, name="quoted string"
+// or name*=ext-value
+fn parse_disposition(text: &str) -> Result<Disposition, &'static str> {
+    let end = text.find(';').unwrap_or(text.len());
+    let kind = text[..end].trim();
+    if !is_token(kind) {
+        return Err(MALFORMED);
+    }
+
+    let mut disposition = Disposition { kind: kind.to_ascii_lowercase(), params: Vec::new() };
+    let mut rest = &text[end..];
+    while let Some(parameter) = rest.strip_prefix(';') {
+        let (name, value) = parameter.split_once('=').ok_or(MALFORMED)?;
+        let name = name.trim().to_ascii_lowercase();
+        if !is_token(&name) {
+            return Err(MALFORMED);
+        }
+
+        let value = value.trim_start();
+        let (value, after) = match value.strip_prefix('"') {
+            Some(quoted) if !name.ends_with('*') => parse_quoted(quoted)?,
+            _ => {
+                let end = value.find(';').unwrap_or(value.len());
+                let raw = value[..end].trim_end();
+                let decoded = match name.ends_with('*') {
+                    true => decode_ext_value(raw)?,
+                    false if is_token(raw) => raw.to_string(),
+                    false => return Err(MALFORMED),
+                };
+                (decoded, &value[end..])
+            },
+        };
+
+        rest = after.trim_start();
+        if !rest.is_empty() && !rest.starts_with(';') {
+            return Err(MALFORMED);
+        }
+        if disposition.param(&name).is_some() {
+            return Err(DUPLICATE_PARAMETER);
+        }
+        disposition.params.push((name, value));
+    }
+
+    Ok(disposition)
+}
+
+// The first option the disposition breaks, if any
+fn check_options(disposition: &Disposition, options: &ValidationOptions) -> Option<&'static str> {
+    if let Some(types) = &options.types {
+        if !types.iter().any(|kind| kind.eq_ignore_ascii_case(&disposition.kind)) {
+            return Some(UNSUPPORTED_TYPE);
+        }
+    }
+    if options.require_filename && disposition.filename().is_none() {
+        return Some(MISSING_FILENAME);
+    }
+    None
+}
+
+fn detail_disposition_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    let disposition = match parse_disposition(text.trim()) {
+        Ok(disposition) => disposition,
+        Err(code) => return ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+    };
+
+    match check_options(&disposition, options) {
+        Some(code) => ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+        None => ValidationResult::from(Some(disposition.to_value())),
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_disposition_str directly
+fn detail_disposition(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_disposition_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the type, the filename and every parameter, decoded
+fn coerce_disposition(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_disposition(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_disposition(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_disposition(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_disposition(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_disposition(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_disposition(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_disposition(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_disposition(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_disposition(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_disposition(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_disposition_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_disposition(&json!(text), options).error
+    }
+
+    #[test]
+    fn test_parse() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            coerce_disposition(&json!("Attachment; FileName=\"report 2024.pdf\""), &defaults),
+            Some(json!({"type": "attachment", "filename": "report 2024.pdf", "parameters": {"filename": "report 2024.pdf"}}))
+        );
+        assert_eq!(
+            coerce_disposition(&json!("inline"), &defaults),
+            Some(json!({"type": "inline", "filename": null, "parameters": {}}))
+        );
+        assert_eq!(
+            coerce_disposition(&json!("form-data; name=upload; filename=\"a \\\"quoted\\\" name.txt\""), &defaults),
+            Some(json!({
+                "type": "form-data",
+                "filename": "a \"quoted\" name.txt",
+                "parameters": {"name": "upload", "filename": "a \"quoted\" name.txt"},
+            }))
+        );
+
+        // Semicolons inside quotes don't end the parameter
+        let parsed = parse_disposition("attachment; filename=\"a;b.txt\"; size=42").unwrap();
+        assert_eq!(parsed.params, vec![("filename".to_string(), "a;b.txt".to_string()), ("size".to_string(), "42".to_string())]);
+    }
+
+    #[test]
+    fn test_ext_values() {
+        let defaults = ValidationOptions::default();
+
+        // filename* wins over the ASCII fallback
+        let value =
+            coerce_disposition(&json!("attachment; filename=\"naive.txt\"; filename*=UTF-8''na%C3%AFve%20file.txt"), &defaults)
+                .unwrap();
+        assert_eq!(value["filename"], "naïve file.txt");
+        assert_eq!(value["parameters"]["filename"], "naive.txt");
+
+        assert_eq!(decode_ext_value("iso-8859-1'en'%A3%20rates"), Ok("£ rates".to_string()));
+        assert_eq!(decode_ext_value("utf-8''plain.txt"), Ok("plain.txt".to_string()));
+
+        assert_eq!(error("attachment; filename*=na%C3%AFve.txt", &defaults), Some(INVALID_EXT_VALUE));
+        assert_eq!(error("attachment; filename*=UTF-8''bad%2", &defaults), Some(INVALID_EXT_VALUE));
+        assert_eq!(error("attachment; filename*=UTF-8''%FF", &defaults), Some(INVALID_EXT_VALUE));
+        assert_eq!(error("attachment; filename*=UTF-8''two words", &defaults), Some(INVALID_EXT_VALUE));
+        assert_eq!(error("attachment; filename*=\"UTF-8''quoted\"", &defaults), Some(INVALID_EXT_VALUE));
+        assert_eq!(error("attachment; filename*=Shift_JIS''%82%A0", &defaults), Some(UNSUPPORTED_CHARSET));
+    }
+
+    #[test]
+    fn test_invalid_dispositions() {
+        let defaults = ValidationOptions::default();
+
+        for text in [
+            "",
+            "; filename=a.txt",
+            "attach ment",
+            "attachment; filename",
+            "attachment; filename=two words.txt",
+            "attachment; filename=\"unterminated",
+            "attachment; filename=\"a.txt\" extra",
+            "attachment;",
+            "attachment; =a.txt",
+        ] {
+            assert_eq!(error(text, &defaults), Some(MALFORMED), "{text}");
+        }
+        assert_eq!(error("attachment; filename=a.txt; FILENAME=b.txt", &defaults), Some(DUPLICATE_PARAMETER));
+
+        // Only strings can be valid, and other values have no specific reason
+        assert_eq!(detail_disposition(&json!(123), &defaults), ValidationResult::from(None));
+    }
+
+    #[test]
+    fn test_options() {
+        let types: ValidationOptions = serde_json::from_value(json!({"types": ["Attachment", "inline"]})).unwrap();
+        assert!(validate_disposition(&json!("attachment"), &types));
+        assert_eq!(error("form-data; name=a", &types), Some(UNSUPPORTED_TYPE));
+
+        let filename: ValidationOptions = serde_json::from_value(json!({"require_filename": true})).unwrap();
+        assert_eq!(error("attachment", &filename), Some(MISSING_FILENAME));
+        assert!(validate_disposition(&json!("attachment; filename*=UTF-8''a.txt"), &filename));
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "inline", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_disposition(&json!("inline"), &defaults),
+            ValidationResult { valid: true, value: json!({"type": "inline", "filename": null, "parameters": {}}), error: None }
+        );
+        assert_eq!(
+            detail_disposition(&json!("inline; inline"), &defaults),
+            ValidationResult { valid: false, value: Value::Null, error: Some(MALFORMED) }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput =
+            serde_json::from_value(json!({"values": ["inline", 1], "options": {"require_filename": true}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["inline", " attachment; filename=a.txt ", "attachment;", "", "x; a*=UTF-8''%FF"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,810 @@
+// ABOUTME: WASM component for HLS playlists (extended M3U, .m3u8), for video platforms that take stream uploads
+// ABOUTME: Checks tags and their attribute lists and the URIs they name, telling master playlists from media playlists
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The kind of playlist to accept; detected from its tags when unset
+    playlist: Option<Playlist>,
+    // Accept URIs relative to the playlist's own, such as "segment1.ts"; when false every URI
+    // must be an absolute URL
+    relative_uris: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { playlist: None, relative_uris: true }
+    }
+}
+
+// The two kinds of playlist: a master playlist lists the variant streams of a presentation,
+// and a media playlist the segments of one of them
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Playlist {
+    Master,
+    Media,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The line the problem was found on, counting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, line: None }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from((code, line): Problem) -> Self {
+        ValidationResult { error: Some(code), line: Some(line), ..ValidationResult::from(None) }
+    }
+}
+
+// Error codes reported in detailed results
+const MISSING_EXTM3U: &str = "missing_extm3u";
+const INVALID_TAG: &str = "invalid_tag";
+const DUPLICATE_TAG: &str = "duplicate_tag";
+const INVALID_ATTRIBUTE: &str = "invalid_attribute";
+const MISSING_ATTRIBUTE: &str = "missing_attribute";
+const MISSING_URI: &str = "missing_uri";
+const UNEXPECTED_URI: &str = "unexpected_uri";
+const INVALID_URI: &str = "invalid_uri";
+const MIXED_PLAYLIST: &str = "mixed_playlist";
+const WRONG_PLAYLIST_TYPE: &str = "wrong_playlist_type";
+const MISSING_TARGET_DURATION: &str = "missing_target_duration";
+const SEGMENT_TOO_LONG: &str = "segment_too_long";
+
+// Relative URIs are resolved against this before the URL validator sees them; the .invalid
+// domain is reserved, so it never names a real host
+const BASE: &str = "https://playlist.invalid/";
+
+// Why a playlist was rejected, and the line the problem was found on
+type Problem = (&'static str, usize);
+
+// What follows a tag's name and colon
+#[derive(Clone, Copy)]
+enum Form {
+    // Nothing, and no colon, as in #EXT-X-ENDLIST
+    Empty,
+    // A decimal integer, as in #EXT-X-TARGETDURATION:10
+    Integer,
+    // An attribute list, with the attributes that must be in it
+    Attributes(&'static [&'static str]),
+    // Any text, which the tag's own rules check
+    Text,
+}
+
+// A tag from RFC 8216: the kind of playlist it belongs to (None for both), what follows its
+// name, and whether it may appear only once
+struct Tag {
+    name: &'static str,
+    playlist: Option<Playlist>,
+    form: Form,
+    once: bool,
+}
+
+const TAGS: &[Tag] = &[
+    Tag { name: "EXT-X-VERSION", playlist: None, form: Form::Integer, once: true },
+    Tag { name: "EXT-X-INDEPENDENT-SEGMENTS", playlist: None, form: Form::Empty, once: true },
+    Tag { name: "EXT-X-START", playlist: None, form: Form::Attributes(&["TIME-OFFSET"]), once: true },
+    Tag { name: "EXT-X-TARGETDURATION", playlist: Some(Playlist::Media), form: Form::Integer, once: true },
+    Tag { name: "EXT-X-MEDIA-SEQUENCE", playlist: Some(Playlist::Media), form: Form::Integer, once: true },
+    Tag { name: "EXT-X-DISCONTINUITY-SEQUENCE", playlist: Some(Playlist::Media), form: Form::Integer, once: true },
+    Tag { name: "EXT-X-PLAYLIST-TYPE", playlist: Some(Playlist::Media), form: Form::Text, once: true },
+    Tag { name: "EXT-X-I-FRAMES-ONLY", playlist: Some(Playlist::Media), form: Form::Empty, once: true },
+    Tag { name: "EXT-X-ENDLIST", playlist: Some(Playlist::Media), form: Form::Empty, once: true },
+    Tag { name: "EXTINF", playlist: Some(Playlist::Media), form: Form::Text, once: false },
+    Tag { name: "EXT-X-BYTERANGE", playlist: Some(Playlist::Media), form: Form::Text, once: false },
+    Tag { name: "EXT-X-DISCONTINUITY", playlist: Some(Playlist::Media), form: Form::Empty, once: false },
+    Tag { name: "EXT-X-GAP", playlist: Some(Playlist::Media), form: Form::Empty, once: false },
+    Tag { name: "EXT-X-KEY", playlist: Some(Playlist::Media), form: Form::Attributes(&["METHOD"]), once: false },
+    Tag { name: "EXT-X-MAP", playlist: Some(Playlist::Media), form: Form::Attributes(&["URI"]), once: false },
+    Tag { name: "EXT-X-PROGRAM-DATE-TIME", playlist: Some(Playlist::Media), form: Form::Text, once: false },
+    Tag { name: "EXT-X-DATERANGE", playlist: Some(Playlist::Media), form: Form::Attributes(&["ID", "START-DATE"]), once: false },
+    Tag { name: "EXT-X-STREAM-INF", playlist: Some(Playlist::Master), form: Form::Attributes(&["BANDWIDTH"]), once: false },
+    Tag { name: "EXT-X-I-FRAME-STREAM-INF", playlist: Some(Playlist::Master), form: Form::Attributes(&["BANDWIDTH", "URI"]), once: false },
+    Tag { name: "EXT-X-MEDIA", playlist: Some(Playlist::Master), form: Form::Attributes(&["TYPE", "GROUP-ID", "NAME"]), once: false },
+    Tag { name: "EXT-X-SESSION-DATA", playlist: Some(Playlist::Master), form: Form::Attributes(&["DATA-ID"]), once: false },
+    Tag { name: "EXT-X-SESSION-KEY", playlist: Some(Playlist::Master), form: Form::Attributes(&["METHOD", "URI"]), once: false },
+];
+
+// The types of attribute values
+enum Kind {
+    Integer,
+    Hex,
+    Float,
+    SignedFloat,
+    Quoted,
+    // A quoted string, or NONE unquoted
+    QuotedOrNone,
+    Resolution,
+    OneOf(&'static [&'static str]),
+}
+
+const YES_NO: Kind = Kind::OneOf(&["YES", "NO"]);
+
+// The attributes RFC 8216 defines, and the types of their values; attributes not listed,
+// such as a client's own X- attributes, need only be well formed
+const ATTRIBUTES: &[(&str, Kind)] = &[
+    ("BANDWIDTH", Kind::Integer),
+    ("AVERAGE-BANDWIDTH", Kind::Integer),
+    ("CODECS", Kind::Quoted),
+    ("RESOLUTION", Kind::Resolution),
+    ("FRAME-RATE", Kind::Float),
+    ("HDCP-LEVEL", Kind::OneOf(&["TYPE-0", "NONE"])),
+    ("AUDIO", Kind::Quoted),
+    ("VIDEO", Kind::Quoted),
+    ("SUBTITLES", Kind::Quoted),
+    ("CLOSED-CAPTIONS", Kind::QuotedOrNone),
+    ("URI", Kind::Quoted),
+    ("TYPE", Kind::OneOf(&["AUDIO", "VIDEO", "SUBTITLES", "CLOSED-CAPTIONS"])),
+    ("GROUP-ID", Kind::Quoted),
+    ("LANGUAGE", Kind::Quoted),
+    ("ASSOC-LANGUAGE", Kind::Quoted),
+    ("NAME", Kind::Quoted),
+    ("DEFAULT", YES_NO),
+    ("AUTOSELECT", YES_NO),
+    ("FORCED", YES_NO),
+    ("INSTREAM-ID", Kind::Quoted),
+    ("CHARACTERISTICS", Kind::Quoted),
+    ("CHANNELS", Kind::Quoted),
+    ("METHOD", Kind::OneOf(&["NONE", "AES-128", "SAMPLE-AES"])),
+    ("IV", Kind::Hex),
+    ("KEYFORMAT", Kind::Quoted),
+    ("KEYFORMATVERSIONS", Kind::Quoted),
+    ("BYTERANGE", Kind::Quoted),
+    ("DATA-ID", Kind::Quoted),
+    ("VALUE", Kind::Quoted),
+    ("TIME-OFFSET", Kind::SignedFloat),
+    ("PRECISE", YES_NO),
+    ("ID", Kind::Quoted),
+    ("CLASS", Kind::Quoted),
+    ("START-DATE", Kind::Quoted),
+    ("END-DATE", Kind::Quoted),
+    ("DURATION", Kind::Float),
+    ("PLANNED-DURATION", Kind::Float),
+    ("END-ON-NEXT", YES_NO),
+];
+
+// One NAME=value pair of an attribute list, and whether its value was quoted
+struct Attribute<'a> {
+    name: &'a str,
+    value: &'a str,
+    quoted: bool,
+}
+
+fn is_digits(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit())
+}
+
+// A decimal integer from 0 to 2^64 - 1
+fn is_integer(text: &str) -> bool {
+    is_digits(text) && text.parse::<u64>().is_ok()
+}
+
+fn is_float(text: &str) -> bool {
+    match text.split_once('.') {
+        Some((whole, fraction)) => is_digits(whole) && is_digits(fraction),
+        None => is_digits(text),
+    }
+}
+
+// An attribute list: NAME=value pairs separated by commas, each value a quoted string or
+// text without quotes, commas or whitespace. None when it's malformed or names an attribute
+// twice
+fn parse_attributes(text: &str) -> Option<Vec<Attribute<'_>>> {
+    let mut attributes: Vec<Attribute> = Vec::new();
+    let mut rest = text;
+    loop {
+        let (name, after) = rest.split_once('=')?;
+        let well_formed = !name.is_empty() && name.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'-');
+        if !well_formed || attributes.iter().any(|attribute| attribute.name == name) {
+            return None;
+        }
+        let (value, quoted, after) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let (value, after) = quoted.split_once('"')?;
+                (value, true, after)
+            }
+            None => {
+                let (value, after) = after.split_at(after.find(',').unwrap_or(after.len()));
+                if value.is_empty() || value.contains(['"', ' ', '\t']) {
+                    return None;
+                }
+                (value, false, after)
+            }
+        };
+        attributes.push(Attribute { name, value, quoted });
+        match after.strip_prefix(',') {
+            Some(next) => rest = next,
+            None if after.is_empty() => return Some(attributes),
+            None => return None,
+        }
+    }
+}
+
+// Whether an attribute's value has the type its name calls for
+fn check_attribute(attribute: &Attribute) -> bool {
+    let Some((_, kind)) = ATTRIBUTES.iter().find(|(name, _)| *name == attribute.name) else {
+        return true;
+    };
+    let value = attribute.value;
+    match kind {
+        Kind::Quoted => attribute.quoted,
+        Kind::QuotedOrNone => attribute.quoted || value == "NONE",
+        _ if attribute.quoted => false,
+        Kind::Integer => is_integer(value),
+        Kind::Hex => {
+            let digits = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X"));
+            digits.is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_hexdigit()))
+        }
+        Kind::Float => is_float(value),
+        Kind::SignedFloat => is_float(value.strip_prefix('-').unwrap_or(value)),
+        Kind::Resolution => value.split_once('x').is_some_and(|(width, height)| is_integer(width) && is_integer(height)),
+        Kind::OneOf(values) => values.contains(&value),
+    }
+}
+
+// A URI, which the URL validator checks: relative ones once resolved against the playlist's
+// location. A key's URI may use its DRM system's own scheme, such as FairPlay's skd:, which
+// the URL validator doesn't know, so those need only parse
+fn check_uri(text: &str, key: bool, options: &ValidationOptions) -> bool {
+    if text.is_empty() || text.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return false;
+    }
+    let url = match Url::parse(text) {
+        Ok(url) if key && !matches!(url.scheme(), "http" | "https" | "data") => return true,
+        Ok(url) => url,
+        Err(url::ParseError::RelativeUrlWithoutBase) if options.relative_uris => {
+            match Url::parse(BASE).and_then(|base| base.join(text)) {
+                Ok(url) => url,
+                Err(_) => return false,
+            }
+        }
+        Err(_) => return false,
+    };
+    url_validator::coerce_value(&Value::from(url.as_str()), &Value::Null).is_some()
+}
+
+// Checks a playlist line by line. Returns the kind of playlist with its variant streams and
+// renditions, or its segments, their total duration and whether the playlist has ended
+fn check_playlist(text: &str, options: &ValidationOptions) -> Result<Value, Problem> {
+    let mut lines = text.lines().enumerate().map(|(index, line)| (index + 1, line.trim_end()));
+    if lines.next().map(|(_, line)| line) != Some("#EXTM3U") {
+        return Err((MISSING_EXTM3U, 1));
+    }
+
+    // The first line with a tag that belongs to each kind of playlist
+    let (mut master, mut media) = (None, None);
+    // The line of the #EXTINF or #EXT-X-STREAM-INF still waiting for its URI
+    let mut pending: Option<usize> = None;
+    let mut seen: Vec<&str> = Vec::new();
+    let (mut variants, mut renditions) = (0, 0);
+    let mut segments: Vec<(f64, usize)> = Vec::new();
+    let (mut target_duration, mut ended) = (None, false);
+
+    for (line, text) in lines {
+        if text.is_empty() {
+            continue;
+        }
+        // A URI line, naming the segment or variant stream the tags before it describe
+        let Some(tag_text) = text.strip_prefix('#') else {
+            if pending.take().is_none() {
+                return Err((UNEXPECTED_URI, line));
+            }
+            if !check_uri(text, false, options) {
+                return Err((INVALID_URI, line));
+            }
+            continue;
+        };
+        let (name, value) = match tag_text.split_once(':') {
+            Some((name, value)) => (name, Some(value)),
+            None => (tag_text, None),
+        };
+        // Comments, and tags from later versions or other vendors, which players ignore
+        let Some(tag) = TAGS.iter().find(|tag| tag.name == name) else {
+            continue;
+        };
+        if tag.once && seen.contains(&name) {
+            return Err((DUPLICATE_TAG, line));
+        }
+        seen.push(name);
+        match tag.playlist {
+            Some(Playlist::Master) => master = master.or(Some(line)),
+            Some(Playlist::Media) => media = media.or(Some(line)),
+            None => {}
+        }
+
+        let attributes = match (tag.form, value) {
+            (Form::Empty, None) => Vec::new(),
+            (Form::Integer, Some(value)) if is_integer(value) => Vec::new(),
+            (Form::Text, Some(value)) if !value.is_empty() => Vec::new(),
+            (Form::Attributes(required), Some(value)) => {
+                let attributes = parse_attributes(value)
+                    .filter(|attributes| attributes.iter().all(check_attribute))
+                    .ok_or((INVALID_ATTRIBUTE, line))?;
+                if !required.iter().all(|name| attributes.iter().any(|attribute| attribute.name == *name)) {
+                    return Err((MISSING_ATTRIBUTE, line));
+                }
+                attributes
+            }
+            (Form::Attributes(_), None) => return Err((MISSING_ATTRIBUTE, line)),
+            _ => return Err((INVALID_TAG, line)),
+        };
+        let attribute = |name: &str| attributes.iter().find(|attribute| attribute.name == name).map(|attribute| attribute.value);
+        if attribute("URI").is_some_and(|uri| !check_uri(uri, matches!(name, "EXT-X-KEY" | "EXT-X-SESSION-KEY"), options)) {
+            return Err((INVALID_URI, line));
+        }
+
+        match name {
+            "EXTINF" | "EXT-X-STREAM-INF" => {
+                if let Some(pending) = pending.replace(line) {
+                    return Err((MISSING_URI, pending));
+                }
+            }
+            _ => {}
+        }
+        match name {
+            // The segment's duration in seconds, then an optional title after the comma
+            "EXTINF" => {
+                let duration = value.and_then(|value| value.split_once(',')).map(|(duration, _)| duration);
+                match duration.filter(|duration| is_float(duration)).and_then(|duration| duration.parse().ok()) {
+                    Some(duration) => segments.push((duration, line)),
+                    None => return Err((INVALID_TAG, line)),
+                }
+            }
+            "EXT-X-STREAM-INF" => variants += 1,
+            "EXT-X-MEDIA" => {
+                renditions += 1;
+                // Closed captions travel in the video stream, named by INSTREAM-ID, not a URI
+                if attribute("TYPE") == Some("CLOSED-CAPTIONS") {
+                    if attribute("URI").is_some() {
+                        return Err((INVALID_ATTRIBUTE, line));
+                    }
+                    if attribute("INSTREAM-ID").is_none() {
+                        return Err((MISSING_ATTRIBUTE, line));
+                    }
+                }
+            }
+            // Every method but NONE needs the key's URI
+            "EXT-X-KEY" if attribute("METHOD") != Some("NONE") && attribute("URI").is_none() => {
+                return Err((MISSING_ATTRIBUTE, line));
+            }
+            "EXT-X-SESSION-KEY" if attribute("METHOD") == Some("NONE") => return Err((INVALID_ATTRIBUTE, line)),
+            "EXT-X-TARGETDURATION" => target_duration = value.and_then(|value| value.parse::<u64>().ok()),
+            "EXT-X-PLAYLIST-TYPE" if !matches!(value, Some("EVENT" | "VOD")) => return Err((INVALID_TAG, line)),
+            // A length in bytes, and an optional offset after an @
+            "EXT-X-BYTERANGE" => {
+                let range = value.unwrap_or_default();
+                let (length, offset) = range.split_once('@').unwrap_or((range, "0"));
+                if !is_integer(length) || !is_integer(offset) {
+                    return Err((INVALID_TAG, line));
+                }
+            }
+            "EXT-X-ENDLIST" => ended = true,
+            _ => {}
+        }
+    }
+    if let Some(line) = pending {
+        return Err((MISSING_URI, line));
+    }
+
+    let playlist = match (master, media) {
+        (Some(master), Some(media)) => return Err((MIXED_PLAYLIST, master.max(media))),
+        (Some(_), None) => Playlist::Master,
+        (None, _) => Playlist::Media,
+    };
+    if options.playlist.is_some_and(|expected| expected != playlist) {
+        return Err((WRONG_PLAYLIST_TYPE, master.or(media).unwrap_or(1)));
+    }
+    if playlist == Playlist::Master {
+        return Ok(json!({ "playlist": playlist, "variants": variants, "renditions": renditions }));
+    }
+
+    let target_duration = target_duration.ok_or((MISSING_TARGET_DURATION, 1))?;
+    // Each segment's duration, rounded to the nearest second, is at most the target duration
+    if let Some((_, line)) = segments.iter().find(|(duration, _)| duration.round() > target_duration as f64) {
+        return Err((SEGMENT_TOO_LONG, *line));
+    }
+    let duration = (segments.iter().map(|(duration, _)| duration).sum::<f64>() * 1000.0).round() / 1000.0;
+    Ok(json!({ "playlist": playlist, "segments": segments.len(), "duration": duration, "ended": ended }))
+}
+
+fn detail_playlist_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no tags, and gets the shared code for an empty value
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_playlist(text, options) {
+        Ok(summary) => ValidationResult::from(Some(summary)),
+        Err(problem) => ValidationResult::from(problem),
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_playlist_str directly
+fn detail_playlist(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_playlist_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the kind of playlist, with its variant streams and renditions or its segments
+// and their total duration
+fn coerce_playlist(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_playlist(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_playlist(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_playlist(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_playlist(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_playlist(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_playlist(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_playlist(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_playlist(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_playlist(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_playlist(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_playlist_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTER: &str = "#EXTM3U\n#EXT-X-VERSION:6\n#EXT-X-INDEPENDENT-SEGMENTS\n\
+        #EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID=\"aac\",NAME=\"English\",LANGUAGE=\"en\",DEFAULT=YES,AUTOSELECT=YES,URI=\"audio/en.m3u8\"\n\
+        #EXT-X-MEDIA:TYPE=CLOSED-CAPTIONS,GROUP-ID=\"cc\",NAME=\"English\",INSTREAM-ID=\"CC1\"\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=1280000,AVERAGE-BANDWIDTH=1000000,CODECS=\"avc1.4d401f,mp4a.40.2\",RESOLUTION=1280x720,FRAME-RATE=29.970,AUDIO=\"aac\",CLOSED-CAPTIONS=\"cc\"\n\
+        720p/index.m3u8\n\
+        #EXT-X-STREAM-INF:BANDWIDTH=2560000,RESOLUTION=1920x1080,AUDIO=\"aac\",CLOSED-CAPTIONS=NONE\n\
+        https://cdn.example.com/1080p/index.m3u8\n\
+        #EXT-X-I-FRAME-STREAM-INF:BANDWIDTH=86000,URI=\"720p/iframes.m3u8\"\n";
+
+    const MEDIA: &str = "#EXTM3U\r\n#EXT-X-VERSION:3\r\n#EXT-X-TARGETDURATION:10\r\n#EXT-X-MEDIA-SEQUENCE:0\r\n#EXT-X-PLAYLIST-TYPE:VOD\r\n\
+        #EXT-X-KEY:METHOD=AES-128,URI=\"https://keys.example.com/key1\",IV=0x1f2e3d4c5b6a79880716253443526170\r\n\
+        # Encoded on 2024-03-14\r\n\
+        #EXTINF:9.009,\r\nsegment0.ts\r\n\
+        #EXTINF:9.009,Interview\r\n#EXT-X-BYTERANGE:75232@0\r\nsegment1.ts\r\n\
+        \r\n#EXTINF:3.003,\r\n/media/segment2.ts\r\n#EXT-X-ENDLIST\r\n";
+
+    fn detail(text: &str) -> ValidationResult {
+        detail_playlist_str(text, &ValidationOptions::default())
+    }
+
+    fn problem(text: &str) -> (Option<&'static str>, Option<usize>) {
+        let result = detail(text);
+        (result.error, result.line)
+    }
+
+    #[test]
+    fn test_valid_playlists() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_playlist(&json!(MASTER), &defaults), Some(json!({"playlist": "master", "variants": 2, "renditions": 2})));
+        assert_eq!(
+            coerce_playlist(&json!(MEDIA), &defaults),
+            Some(json!({"playlist": "media", "segments": 3, "duration": 21.021, "ended": true}))
+        );
+
+        // A live playlist that hasn't ended, with unknown tags, a client's own attributes and
+        // a FairPlay key
+        let live = "\u{feff}#EXTM3U\n#EXT-X-TARGETDURATION:6\n#EXT-X-SERVER-CONTROL:CAN-BLOCK-RELOAD=YES\n\
+            #EXT-X-KEY:METHOD=SAMPLE-AES,URI=\"skd://key42\",KEYFORMAT=\"com.apple.streamingkeydelivery\"\n\
+            #EXT-X-DATERANGE:ID=\"ad1\",START-DATE=\"2024-03-14T10:00:00Z\",DURATION=30.0,X-AD-ID=\"1234\"\n\
+            #EXT-X-PROGRAM-DATE-TIME:2024-03-14T10:00:00.000Z\n#EXTINF:6.4,\n//cdn.example.com/live/1.ts\n";
+        assert_eq!(coerce_playlist(&json!(live), &defaults), Some(json!({"playlist": "media", "segments": 1, "duration": 6.4, "ended": false})));
+        // A media playlist may have no segments yet
+        assert!(validate_playlist(&json!("#EXTM3U\n#EXT-X-TARGETDURATION:4\n"), &defaults));
+    }
+
+    #[test]
+    fn test_tags() {
+        assert_eq!(detail("").error, None);
+        assert_eq!(problem("#EXT-X-VERSION:3\n#EXTM3U\n"), (Some(MISSING_EXTM3U), Some(1)));
+        assert_eq!(problem(&MEDIA.replace("TARGETDURATION:10", "TARGETDURATION:10.5")), (Some(INVALID_TAG), Some(3)));
+        assert_eq!(problem(&MEDIA.replace("PLAYLIST-TYPE:VOD", "PLAYLIST-TYPE:LIVE")), (Some(INVALID_TAG), Some(5)));
+        assert_eq!(problem(&MEDIA.replace("#EXTINF:9.009,\r\nsegment0", "#EXTINF:9.009\r\nsegment0")), (Some(INVALID_TAG), Some(8)));
+        assert_eq!(problem(&MEDIA.replace("75232@0", "75232@")), (Some(INVALID_TAG), Some(11)));
+        assert_eq!(problem(&MEDIA.replace("#EXT-X-ENDLIST", "#EXT-X-ENDLIST:YES")), (Some(INVALID_TAG), Some(16)));
+        assert_eq!(problem(&MEDIA.replace("#EXT-X-VERSION:3", "#EXT-X-MEDIA-SEQUENCE:0")), (Some(DUPLICATE_TAG), Some(4)));
+
+        // Every #EXTINF and #EXT-X-STREAM-INF is followed by its URI, and only they are
+        assert_eq!(problem(&MEDIA.replace("segment1.ts\r\n", "")), (Some(MISSING_URI), Some(10)));
+        assert_eq!(problem(&MEDIA.replace("/media/segment2.ts\r\n", "")), (Some(MISSING_URI), Some(14)));
+        assert_eq!(problem(&MEDIA.replace("# Encoded", "Encoded")), (Some(UNEXPECTED_URI), Some(7)));
+        assert_eq!(problem(&MASTER.replace("720p/index.m3u8\n", "")), (Some(MISSING_URI), Some(6)));
+
+        assert_eq!(problem(&MEDIA.replace("#EXT-X-TARGETDURATION:10\r\n", "")), (Some(MISSING_TARGET_DURATION), Some(1)));
+        assert_eq!(problem(&MEDIA.replace("#EXTINF:3.003", "#EXTINF:10.5")), (Some(SEGMENT_TOO_LONG), Some(14)));
+        assert!(validate_playlist(&json!(MEDIA.replace("#EXTINF:3.003", "#EXTINF:10.49")), &ValidationOptions::default()));
+        assert!(!validate_playlist(&json!(42), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_attributes() {
+        let attributes = parse_attributes("BANDWIDTH=1280000,CODECS=\"avc1.4d401f,mp4a.40.2\",X-NOTE=\"\"").unwrap();
+        let pairs: Vec<_> = attributes.iter().map(|attribute| (attribute.name, attribute.value, attribute.quoted)).collect();
+        assert_eq!(pairs, vec![("BANDWIDTH", "1280000", false), ("CODECS", "avc1.4d401f,mp4a.40.2", true), ("X-NOTE", "", true)]);
+        for bad in ["", "BANDWIDTH", "BANDWIDTH=", "bandwidth=1", "A=1,", "A=1,A=2", "A=\"open", "A=\"x\"y", "A=1 2"] {
+            assert!(parse_attributes(bad).is_none(), "{bad}");
+        }
+
+        let stream = |attributes: &str| problem(&MASTER.replace("BANDWIDTH=2560000,RESOLUTION=1920x1080", attributes));
+        assert_eq!(stream("BANDWIDTH=2560000,RESOLUTION=1920x1080"), (None, None));
+        assert_eq!(stream("BANDWIDTH=\"2560000\""), (Some(INVALID_ATTRIBUTE), Some(8)));
+        assert_eq!(stream("BANDWIDTH=2560000,RESOLUTION=1920*1080"), (Some(INVALID_ATTRIBUTE), Some(8)));
+        assert_eq!(stream("BANDWIDTH=2560000,HDCP-LEVEL=TYPE-2"), (Some(INVALID_ATTRIBUTE), Some(8)));
+        assert_eq!(stream("BANDWIDTH=2560000, RESOLUTION=1920x1080"), (Some(INVALID_ATTRIBUTE), Some(8)));
+        assert_eq!(stream("RESOLUTION=1920x1080"), (Some(MISSING_ATTRIBUTE), Some(8)));
+
+        assert_eq!(problem(&MASTER.replace("INSTREAM-ID=\"CC1\"", "URI=\"cc.m3u8\"")), (Some(INVALID_ATTRIBUTE), Some(5)));
+        assert_eq!(problem(&MASTER.replace(",NAME=\"English\",LANGUAGE", ",LANGUAGE")), (Some(MISSING_ATTRIBUTE), Some(4)));
+        assert_eq!(problem(&MEDIA.replace(",IV=0x1f2e", ",IV=0xzz2e")), (Some(INVALID_ATTRIBUTE), Some(6)));
+        assert_eq!(problem(&MEDIA.replace(",URI=\"https://keys.example.com/key1\"", "")), (Some(MISSING_ATTRIBUTE), Some(6)));
+        assert!(validate_playlist(&json!(MEDIA.replace("METHOD=AES-128,URI=\"https://keys.example.com/key1\",IV=0x1f2e3d4c5b6a79880716253443526170", "METHOD=NONE")), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_uris() {
+        assert_eq!(problem(&MEDIA.replace("segment0.ts", "segment 0.ts")), (Some(INVALID_URI), Some(9)));
+        assert_eq!(problem(&MEDIA.replace("/media/segment2.ts", "ftp://")), (Some(INVALID_URI), Some(15)));
+        assert_eq!(problem(&MEDIA.replace("https://keys.example.com/key1", "gopher://keys.example.com/key1")), (None, None));
+        assert_eq!(problem(&MEDIA.replace("https://keys.example.com/key1", "https://")), (Some(INVALID_URI), Some(6)));
+        assert_eq!(problem(&MASTER.replace("https://cdn.example.com/", "skd://cdn.example.com/")), (Some(INVALID_URI), Some(9)));
+        assert_eq!(problem(&MASTER.replace("audio/en.m3u8", "")), (Some(INVALID_URI), Some(4)));
+
+        let absolute: ValidationOptions = serde_json::from_value(json!({"relative_uris": false})).unwrap();
+        assert_eq!(detail_playlist_str(MASTER, &absolute).line, Some(4));
+        let master = MASTER.replace("\"audio/", "\"https://cdn.example.com/audio/").replace("\n720p/", "\nhttps://cdn.example.com/720p/");
+        assert!(validate_playlist(&json!(master.replace("\"720p/", "\"https://cdn.example.com/720p/")), &absolute));
+    }
+
+    #[test]
+    fn test_playlist_types() {
+        let mixed = MASTER.replace("#EXT-X-INDEPENDENT-SEGMENTS", "#EXT-X-TARGETDURATION:10");
+        assert_eq!(problem(&mixed), (Some(MIXED_PLAYLIST), Some(4)));
+
+        let master: ValidationOptions = serde_json::from_value(json!({"playlist": "master"})).unwrap();
+        let media: ValidationOptions = serde_json::from_value(json!({"playlist": "media"})).unwrap();
+        assert!(validate_playlist(&json!(MASTER), &master));
+        assert!(validate_playlist(&json!(MEDIA), &media));
+        let result = detail_playlist_str(MEDIA, &master);
+        assert_eq!((result.error, result.line), (Some(WRONG_PLAYLIST_TYPE), Some(3)));
+        assert_eq!(detail_playlist_str(MASTER, &media).error, Some(WRONG_PLAYLIST_TYPE));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"playlist": "variant"})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": MEDIA, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        assert_eq!(
+            messages::localize(&detail(&MEDIA.replace("#EXTM3U", "#EXTM3U8")), &json!(MEDIA), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "missing_extm3u",
+                "line": 1,
+                "message": messages::message("missing_extm3u", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail("  "), &json!("  "), Some("en"))["error"], "empty_value");
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [MASTER, MEDIA], "options": {"playlist": "media"}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [MASTER, MEDIA, "", "#EXTM3U\n", "#EXTM3U\nsegment.ts\n", "not a playlist"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,50 @@
+// Types for the m3u8 validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The kind of playlist to accept; detected from its tags when unset (default: null) */
+    playlist?: "master" | "media" | null;
+    /** Accept URIs relative to the playlist's own, such as "segment0.ts"; when false every URI must be an absolute URL (default: true) */
+    relative_uris?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    playlist: "master";
+    variants: number;
+    renditions: number;
+} | {
+    playlist: "media";
+    segments: number;
+    duration: number;
+    ended: boolean;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The line the problem was found on, counting from 1 */
+    line?: number;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "missing_extm3u" | "invalid_tag" | "duplicate_tag" | "invalid_attribute" | "missing_attribute" | "missing_uri" | "unexpected_uri" | "invalid_uri" | "mixed_playlist" | "wrong_playlist_type" | "missing_target_duration" | "segment_too_long" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
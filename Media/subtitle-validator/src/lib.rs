@@ -0,0 +1,593 @@
+// ABOUTME: WASM component for SRT and WebVTT subtitle files, for video platforms that take subtitle uploads
+// ABOUTME: Checks cue numbering, timestamp format and ordering, and cue settings, warning about overlapping cues
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The format to accept; detected from the WEBVTT header when unset
+    format: Option<Format>,
+    // Require SRT cue numbers to count up from 1 without gaps, rather than be any numbers
+    sequential_numbers: bool,
+    // Accept cues that overlap in time, such as two speakers at once, with a warning; when
+    // false an overlap is an error
+    allow_overlaps: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { format: None, sequential_numbers: true, allow_overlaps: true }
+    }
+}
+
+// The subtitle formats, by their usual file extensions
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Srt,
+    Vtt,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The line the problem was found on, counting from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    // Concerns that don't make the value invalid, such as overlapping cues
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+            line: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from((code, line): Problem) -> Self {
+        ValidationResult { error: Some(code), line: Some(line), ..ValidationResult::from(None) }
+    }
+}
+
+// Error codes reported in detailed results; overlapping cues are a warning unless overlaps
+// aren't allowed
+const MISSING_WEBVTT_HEADER: &str = "missing_webvtt_header";
+const INVALID_CUE_NUMBER: &str = "invalid_cue_number";
+const INVALID_CUE_TIMING: &str = "invalid_cue_timing";
+const INVALID_CUE_SETTING: &str = "invalid_cue_setting";
+const MISSING_CUE_TEXT: &str = "missing_cue_text";
+const END_BEFORE_START: &str = "end_before_start";
+const CUE_OUT_OF_ORDER: &str = "cue_out_of_order";
+const OVERLAPPING_CUES: &str = "overlapping_cues";
+
+// WebVTT cue settings, each written name:value after the timing
+const CUE_SETTINGS: &[&str] = &["vertical", "line", "position", "size", "align", "region"];
+
+// Why a file was rejected, and the line the problem was found on
+type Problem = (&'static str, usize);
+
+// A cue's start and end, in milliseconds, and the line of its timing
+struct Cue {
+    start: u64,
+    end: u64,
+    line: usize,
+}
+
+// The file's lines grouped into blocks separated by blank lines, each with the number of its
+// first line; a line of only spaces counts as blank, as players treat it
+fn blocks(text: &str) -> Vec<(usize, Vec<&str>)> {
+    let mut blocks: Vec<(usize, Vec<&str>)> = Vec::new();
+    let mut open = false;
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            open = false;
+        } else if open {
+            if let Some((_, lines)) = blocks.last_mut() {
+                lines.push(line);
+            }
+        } else {
+            blocks.push((index + 1, vec![line]));
+            open = true;
+        }
+    }
+    blocks
+}
+
+fn two_digits_below_60(text: &str) -> Option<u64> {
+    let value = text.parse().ok().filter(|&value| value < 60)?;
+    (text.len() == 2 && text.bytes().all(|b| b.is_ascii_digit())).then_some(value)
+}
+
+// A timestamp in milliseconds: hours of two or more digits, which WebVTT may leave out, then
+// minutes, seconds, and milliseconds after a comma in SRT and a point in WebVTT
+fn parse_timestamp(text: &str, format: Format) -> Option<u64> {
+    let separator = match format {
+        Format::Srt => ',',
+        Format::Vtt => '.',
+    };
+    let (clock, millis) = text.split_once(separator)?;
+    if millis.len() != 3 || !millis.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let parts: Vec<&str> = clock.split(':').collect();
+    let (hours, minutes, seconds) = match (format, parts.as_slice()) {
+        (_, [hours, minutes, seconds]) => {
+            if hours.len() < 2 || hours.len() > 9 || !hours.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            (hours.parse::<u64>().ok()?, *minutes, *seconds)
+        }
+        (Format::Vtt, [minutes, seconds]) => (0, *minutes, *seconds),
+        _ => return None,
+    };
+    let (minutes, seconds) = (two_digits_below_60(minutes)?, two_digits_below_60(seconds)?);
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1000 + millis.parse::<u64>().ok()?)
+}
+
+// A timing line: the start, "-->" and the end, then WebVTT's cue settings or the coordinates
+// some SRT files carry. Returns the cue's start and end, and whatever follows them
+fn parse_timing(line: &str, format: Format) -> Option<(u64, u64, &str)> {
+    let (start, rest) = line.split_once("-->")?;
+    let rest = rest.trim_start();
+    let (end, after) = rest.split_at(rest.find([' ', '\t']).unwrap_or(rest.len()));
+    Some((parse_timestamp(start.trim(), format)?, parse_timestamp(end, format)?, after))
+}
+
+// WebVTT cue settings: known names, each with a value, and none twice
+fn check_settings(settings: &str) -> bool {
+    let mut seen = Vec::new();
+    settings.split_whitespace().all(|setting| match setting.split_once(':') {
+        Some((name, value)) if CUE_SETTINGS.contains(&name) && !value.is_empty() && !seen.contains(&name) => {
+            seen.push(name);
+            true
+        }
+        _ => false,
+    })
+}
+
+// An SRT file: blocks of a cue number, a timing line and one or more lines of text
+fn parse_srt(text: &str, options: &ValidationOptions) -> Result<Vec<Cue>, Problem> {
+    let mut cues = Vec::new();
+    for (line, block) in blocks(text) {
+        let number = block[0].trim().parse::<u64>().ok();
+        let expected = cues.len() as u64 + 1;
+        if number.is_none() || (options.sequential_numbers && number != Some(expected)) {
+            return Err((INVALID_CUE_NUMBER, line));
+        }
+        let (start, end, _) = match block.get(1).and_then(|timing| parse_timing(timing, Format::Srt)) {
+            Some(timing) => timing,
+            None => return Err((INVALID_CUE_TIMING, line + 1)),
+        };
+        if block.len() < 3 {
+            return Err((MISSING_CUE_TEXT, line));
+        }
+        cues.push(Cue { start, end, line: line + 1 });
+    }
+    Ok(cues)
+}
+
+// A WebVTT file: a WEBVTT header, then cues, each an optional identifier, a timing line with
+// any settings, and text, which may be empty; NOTE, STYLE and REGION blocks are skipped
+fn parse_vtt(text: &str) -> Result<Vec<Cue>, Problem> {
+    let blocks = blocks(text);
+    let header = blocks.first().filter(|(line, _)| *line == 1).map(|(_, lines)| lines[0]);
+    let signature = header.and_then(|header| header.strip_prefix("WEBVTT"));
+    if !signature.is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return Err((MISSING_WEBVTT_HEADER, 1));
+    }
+
+    let mut cues = Vec::new();
+    for (line, block) in blocks.into_iter().skip(1) {
+        let keyword = block[0].split([' ', '\t']).next().unwrap_or_default();
+        if matches!(keyword, "NOTE" | "STYLE" | "REGION") && !block[0].contains("-->") {
+            continue;
+        }
+        // The timing comes first, or after the cue's identifier
+        let offset = if block[0].contains("-->") { 0 } else { 1 };
+        let (start, end, settings) = match block.get(offset).and_then(|timing| parse_timing(timing, Format::Vtt)) {
+            Some(timing) => timing,
+            None => return Err((INVALID_CUE_TIMING, line + offset)),
+        };
+        if !check_settings(settings) {
+            return Err((INVALID_CUE_SETTING, line + offset));
+        }
+        cues.push(Cue { start, end, line: line + offset });
+    }
+    Ok(cues)
+}
+
+// Checks the cues' timing: each ends after it starts, and starts no earlier than the one
+// before it. Returns the warnings, for cues that start before an earlier one ends
+fn check_cues(cues: &[Cue], options: &ValidationOptions) -> Result<Vec<&'static str>, Problem> {
+    let mut warnings = Vec::new();
+    let (mut previous_start, mut latest_end) = (0, 0);
+    for cue in cues {
+        if cue.end <= cue.start {
+            return Err((END_BEFORE_START, cue.line));
+        }
+        if cue.start < previous_start {
+            return Err((CUE_OUT_OF_ORDER, cue.line));
+        }
+        if cue.start < latest_end {
+            if !options.allow_overlaps {
+                return Err((OVERLAPPING_CUES, cue.line));
+            }
+            if warnings.is_empty() {
+                warnings.push(OVERLAPPING_CUES);
+            }
+        }
+        previous_start = cue.start;
+        latest_end = latest_end.max(cue.end);
+    }
+    Ok(warnings)
+}
+
+fn detail_subtitle_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no cues, and gets the shared code for an empty value
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    let format = match options.format {
+        Some(format) => format,
+        None if text.starts_with("WEBVTT") => Format::Vtt,
+        None => Format::Srt,
+    };
+    let cues = match format {
+        Format::Srt => parse_srt(text, options),
+        Format::Vtt => parse_vtt(text),
+    };
+    let (cues, warnings) = match cues.and_then(|cues| check_cues(&cues, options).map(|warnings| (cues, warnings))) {
+        Ok(checked) => checked,
+        Err(problem) => return ValidationResult::from(problem),
+    };
+
+    let duration = cues.iter().map(|cue| cue.end).max().unwrap_or(0) as f64 / 1000.0;
+    ValidationResult { warnings, ..ValidationResult::from(Some(json!({ "format": format, "cues": cues.len(), "duration": duration }))) }
+}
+
+// Only strings can be valid; the fast path calls detail_subtitle_str directly
+fn detail_subtitle(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_subtitle_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the format, the number of cues and when the last one ends, in seconds
+fn coerce_subtitle(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_subtitle(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_subtitle(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_subtitle(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_subtitle(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_subtitle(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_subtitle(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_subtitle(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_subtitle(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_subtitle(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_subtitle(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_subtitle_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRT: &str = "1\r\n00:00:01,000 --> 00:00:03,500\r\nHello there.\r\n\r\n2\r\n00:00:04,000 --> 00:00:06,250\r\nGeneral Kenobi!\r\nYou are a bold one.\r\n";
+
+    const VTT: &str = "WEBVTT - Episode 1\n\nNOTE Translated by Jane\n\nSTYLE\n::cue { color: yellow }\n\n\
+        intro\n00:01.000 --> 00:03.500 align:start position:10%\nHello <b>there</b>.\n\n\
+        00:00:04.000 --> 00:00:06.250\n- General Kenobi!\n";
+
+    fn detail(text: &str) -> ValidationResult {
+        detail_subtitle_str(text, &ValidationOptions::default())
+    }
+
+    #[test]
+    fn test_valid_files() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_subtitle(&json!(SRT), &defaults), Some(json!({"format": "srt", "cues": 2, "duration": 6.25})));
+        assert_eq!(coerce_subtitle(&json!(VTT), &defaults), Some(json!({"format": "vtt", "cues": 2, "duration": 6.25})));
+
+        // A byte order mark, LF line breaks, blank lines of spaces, SRT coordinates and long
+        // running times
+        let srt = "\u{feff}1\n00:00:01,000 --> 00:00:02,000 X1:40 X2:600 Y1:20 Y2:50\nHi\n   \n2\n100:00:00,000 --> 100:00:01,000\nLater\n";
+        assert_eq!(coerce_subtitle(&json!(srt), &defaults).unwrap()["duration"], json!(360001.0));
+        // A WebVTT file may have no cues, and cues may have no text
+        assert_eq!(coerce_subtitle(&json!("WEBVTT"), &defaults), Some(json!({"format": "vtt", "cues": 0, "duration": 0.0})));
+        assert!(validate_subtitle(&json!("WEBVTT\n\n00:01.000 --> 00:02.000\n"), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        assert_eq!(detail("").error, None);
+        let problem = |text: &str| {
+            let result = detail(text);
+            (result.error, result.line)
+        };
+
+        assert_eq!(problem(&SRT.replace("2\r\n", "3\r\n")), (Some(INVALID_CUE_NUMBER), Some(5)));
+        assert_eq!(problem(&SRT.replace("1\r\n", "one\r\n")), (Some(INVALID_CUE_NUMBER), Some(1)));
+        assert_eq!(problem(&SRT.replace("00:00:04,000", "00:00:04.000")), (Some(INVALID_CUE_TIMING), Some(6)));
+        assert_eq!(problem(&SRT.replace("00:00:03,500", "0:00:03,500")), (Some(INVALID_CUE_TIMING), Some(2)));
+        assert_eq!(problem(&SRT.replace("00:00:03,500", "00:00:63,500")), (Some(INVALID_CUE_TIMING), Some(2)));
+        assert_eq!(problem(&SRT.replace(" --> ", " -> ")), (Some(INVALID_CUE_TIMING), Some(2)));
+        assert_eq!(problem(&SRT.replace("Hello there.\r\n", "")), (Some(MISSING_CUE_TEXT), Some(1)));
+        assert_eq!(problem(&SRT.replace("00:00:03,500", "00:00:01,000")), (Some(END_BEFORE_START), Some(2)));
+        assert_eq!(problem(&SRT.replace("00:00:04,000 --> 00:00:06,250", "00:00:00,500 --> 00:00:06,250")), (Some(CUE_OUT_OF_ORDER), Some(6)));
+
+        assert_eq!(problem(&VTT.replace("WEBVTT - Episode 1", "WEBVTT- Episode 1")), (Some(MISSING_WEBVTT_HEADER), Some(1)));
+        assert_eq!(problem(&SRT.replace("1\r\n", "WEBVTT\r\n\r\n")).0, Some(INVALID_CUE_TIMING));
+        assert_eq!(problem(&VTT.replace("00:01.000", "00:01,000")), (Some(INVALID_CUE_TIMING), Some(9)));
+        assert_eq!(problem(&VTT.replace("00:01.000", "1:01.000")), (Some(INVALID_CUE_TIMING), Some(9)));
+        assert_eq!(problem(&VTT.replace("intro\n00:01.000 --> 00:03.500 align:start position:10%\n", "intro\n")), (Some(INVALID_CUE_TIMING), Some(9)));
+        assert_eq!(problem(&VTT.replace("align:start", "color:red")), (Some(INVALID_CUE_SETTING), Some(9)));
+        assert_eq!(problem(&VTT.replace("align:start", "position:20%")), (Some(INVALID_CUE_SETTING), Some(9)));
+        assert!(!validate_subtitle(&json!(42), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let overlapping = SRT.replace("00:00:03,500", "00:00:05,000");
+        let result = detail(&overlapping);
+        assert_eq!((result.valid, result.warnings), (true, vec![OVERLAPPING_CUES]));
+        assert!(detail(SRT).warnings.is_empty());
+
+        let no_overlaps: ValidationOptions = serde_json::from_value(json!({"allow_overlaps": false})).unwrap();
+        let result = detail_subtitle_str(&overlapping, &no_overlaps);
+        assert_eq!((result.error, result.line), (Some(OVERLAPPING_CUES), Some(6)));
+        assert!(validate_subtitle(&json!(SRT), &no_overlaps));
+    }
+
+    #[test]
+    fn test_options() {
+        // Numbering may have gaps when it needn't be sequential
+        let gaps: ValidationOptions = serde_json::from_value(json!({"sequential_numbers": false})).unwrap();
+        assert!(validate_subtitle(&json!(SRT.replace("2\r\n", "7\r\n")), &gaps));
+        assert!(!validate_subtitle(&json!(SRT.replace("2\r\n", "x\r\n")), &gaps));
+
+        // A format given is never second-guessed
+        let srt: ValidationOptions = serde_json::from_value(json!({"format": "srt"})).unwrap();
+        let vtt: ValidationOptions = serde_json::from_value(json!({"format": "vtt"})).unwrap();
+        assert!(validate_subtitle(&json!(SRT), &srt));
+        assert_eq!(detail_subtitle_str(VTT, &srt).error, Some(INVALID_CUE_NUMBER));
+        assert_eq!(detail_subtitle_str(SRT, &vtt).error, Some(MISSING_WEBVTT_HEADER));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"format": "ass"})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": SRT, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        assert_eq!(
+            messages::localize(&detail(&SRT.replace("00:00:03,500", "00:00:00,500")), &json!(SRT), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "end_before_start",
+                "line": 2,
+                "message": messages::message("end_before_start", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail("  "), &json!("  "), Some("en"))["error"], "empty_value");
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [SRT, VTT], "options": {"format": "srt"}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [SRT, VTT, "", "WEBVTT\n\nnot a cue\n", "not subtitles"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
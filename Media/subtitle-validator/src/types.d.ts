@@ -0,0 +1,49 @@
+// Types for the subtitle validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The format to accept; detected from the WEBVTT header when unset (default: null) */
+    format?: "srt" | "vtt" | null;
+    /** Require SRT cue numbers to count up from 1 without gaps (default: true) */
+    sequential_numbers?: boolean;
+    /** Accept cues that overlap in time, such as two speakers at once, with an overlapping_cues warning; when false an overlap is an error (default: true) */
+    allow_overlaps?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    format: "srt" | "vtt";
+    cues: number;
+    duration: number;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The line the problem was found on, counting from 1 */
+    line?: number;
+    /** Concerns that don't make the value invalid */
+    warnings?: "overlapping_cues"[];
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "missing_webvtt_header" | "invalid_cue_number" | "invalid_cue_timing" | "invalid_cue_setting" | "missing_cue_text" | "end_before_start" | "cue_out_of_order" | "overlapping_cues" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,465 @@
+// ABOUTME: WASM component for IP address and CIDR validation
+// ABOUTME: Provides validate and coerce functions for IPv4 and IPv6 addresses with version and range restrictions
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Which address family to accept: 4, 6 or "any" (default)
+    version: IpVersion,
+    // Accept CIDR notation (address/prefix length)
+    allow_cidr: bool,
+    // Reject RFC 1918 private IPv4 ranges and IPv6 unique local addresses
+    reject_private: bool,
+    // Reject 127.0.0.0/8 and ::1
+    reject_loopback: bool,
+    // Reject 224.0.0.0/4 and ff00::/8
+    reject_multicast: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// Accepts 4, 6, "4", "6" or "any", since the version reads naturally as either a number or a string
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+#[serde(try_from = "Value", into = "Value")]
+pub enum IpVersion {
+    #[default]
+    Any,
+    V4,
+    V6,
+}
+
+impl TryFrom<Value> for IpVersion {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Number(n) if n.as_u64() == Some(4) => Ok(IpVersion::V4),
+            Value::Number(n) if n.as_u64() == Some(6) => Ok(IpVersion::V6),
+            Value::String(s) if s == "4" => Ok(IpVersion::V4),
+            Value::String(s) if s == "6" => Ok(IpVersion::V6),
+            Value::String(s) if s == "any" => Ok(IpVersion::Any),
+            other => Err(format!("unknown IP version {}", other)),
+        }
+    }
+}
+
+impl From<IpVersion> for Value {
+    fn from(version: IpVersion) -> Value {
+        match version {
+            IpVersion::Any => Value::from("any"),
+            IpVersion::V4 => Value::from(4),
+            IpVersion::V6 => Value::from(6),
+        }
+    }
+}
+
+fn is_private(addr: &IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(v4) => v4.is_private(),
+        // fc00::/7 unique local addresses
+        IpAddr::V6(v6) => (v6.segments()[0] & 0xfe00) == 0xfc00,
+    }
+}
+
+// IPv4-mapped IPv6 addresses (::ffff:a.b.c.d) are judged by the IPv4 address they carry
+fn effective_address(addr: IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(addr, IpAddr::V4),
+        v4 => v4,
+    }
+}
+
+// A prefix length: plain decimal digits, no sign or leading zeros, within the address width
+fn parse_prefix(text: &str, max: u8) -> Option<u8> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) || (text.len() > 1 && text.starts_with('0')) {
+        return None;
+    }
+    text.parse::<u8>().ok().filter(|&prefix| prefix <= max)
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the address in its standard textual form (compressed lowercase for IPv6)
+fn coerce_ip_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let (address_text, prefix_text) = match text.split_once('/') {
+        Some((address, prefix)) if options.allow_cidr => (address, Some(prefix)),
+        Some(_) => return None,
+        None => (text, None),
+    };
+
+    let addr: IpAddr = match options.version {
+        IpVersion::Any => address_text.parse().ok()?,
+        IpVersion::V4 => IpAddr::V4(address_text.parse::<Ipv4Addr>().ok()?),
+        IpVersion::V6 => IpAddr::V6(address_text.parse::<Ipv6Addr>().ok()?),
+    };
+
+    let checked = effective_address(addr);
+    if (options.reject_private && is_private(&checked))
+        || (options.reject_loopback && checked.is_loopback())
+        || (options.reject_multicast && checked.is_multicast())
+    {
+        return None;
+    }
+
+    match prefix_text {
+        Some(prefix) => {
+            let width = if addr.is_ipv4() { 32 } else { 128 };
+            Some(Value::String(format!("{}/{}", addr, parse_prefix(prefix, width)?)))
+        },
+        None => Some(Value::String(addr.to_string())),
+    }
+}
+
+// Only strings can be valid; the fast path calls coerce_ip_str directly
+fn coerce_ip(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_ip_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_ip(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_ip(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_ip(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_ip(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_ip(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_ip(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_ip(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_ip(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_ip(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_ip_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_addresses() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_ip(&json!("192.168.1.1"), &defaults));
+        assert!(validate_ip(&json!("0.0.0.0"), &defaults));
+        assert!(validate_ip(&json!("255.255.255.255"), &defaults));
+        assert!(validate_ip(&json!("::1"), &defaults));
+        assert!(validate_ip(&json!("::"), &defaults));
+        assert!(validate_ip(&json!("2001:db8::8a2e:370:7334"), &defaults));
+        assert!(validate_ip(&json!("2001:0DB8:0000:0000:0000:0000:0000:0001"), &defaults));
+        assert!(validate_ip(&json!("::ffff:192.0.2.1"), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_addresses() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_ip(&json!(""), &defaults));
+        assert!(!validate_ip(&json!("256.0.0.1"), &defaults));
+        assert!(!validate_ip(&json!("1.2.3"), &defaults));
+        assert!(!validate_ip(&json!("1.2.3.4.5"), &defaults));
+        assert!(!validate_ip(&json!("01.2.3.4"), &defaults)); // Leading zeros are ambiguous (octal)
+        assert!(!validate_ip(&json!(" 1.2.3.4"), &defaults));
+        assert!(!validate_ip(&json!("2001:db8::1::1"), &defaults));
+        assert!(!validate_ip(&json!("2001:db8:g::1"), &defaults));
+        assert!(!validate_ip(&json!("fe80::1%eth0"), &defaults));
+        assert!(!validate_ip(&json!("10.0.0.0/8"), &defaults)); // CIDR not enabled
+        assert!(!validate_ip(&json!(16843009), &defaults));
+    }
+
+    #[test]
+    fn test_version() {
+        let v4 = options(json!({"version": 4}));
+        let v6 = options(json!({"version": "6"}));
+
+        assert!(validate_ip(&json!("10.0.0.1"), &v4));
+        assert!(!validate_ip(&json!("::1"), &v4));
+        assert!(validate_ip(&json!("::1"), &v6));
+        assert!(!validate_ip(&json!("10.0.0.1"), &v6));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"version": "any"})).is_ok());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"version": 5})).is_err());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"version": "ipv4"})).is_err());
+    }
+
+    #[test]
+    fn test_cidr() {
+        let cidr = options(json!({"allow_cidr": true}));
+
+        assert!(validate_ip(&json!("10.0.0.0/8"), &cidr));
+        assert!(validate_ip(&json!("0.0.0.0/0"), &cidr));
+        assert!(validate_ip(&json!("192.168.1.1/32"), &cidr));
+        assert!(validate_ip(&json!("2001:db8::/32"), &cidr));
+        assert!(validate_ip(&json!("::/128"), &cidr));
+        assert!(validate_ip(&json!("10.0.0.1"), &cidr));
+
+        assert!(!validate_ip(&json!("10.0.0.0/33"), &cidr));
+        assert!(!validate_ip(&json!("2001:db8::/129"), &cidr));
+        assert!(!validate_ip(&json!("10.0.0.0/"), &cidr));
+        assert!(!validate_ip(&json!("10.0.0.0/08"), &cidr));
+        assert!(!validate_ip(&json!("10.0.0.0/+8"), &cidr));
+        assert!(!validate_ip(&json!("10.0.0.0/8/8"), &cidr));
+    }
+
+    #[test]
+    fn test_range_restrictions() {
+        let private = options(json!({"reject_private": true}));
+        let loopback = options(json!({"reject_loopback": true}));
+        let multicast = options(json!({"reject_multicast": true}));
+
+        for addr in ["10.1.2.3", "172.16.0.1", "172.31.255.255", "192.168.0.1", "fd00::1", "::ffff:10.0.0.1"] {
+            assert!(!validate_ip(&json!(addr), &private), "{}", addr);
+        }
+        assert!(validate_ip(&json!("172.32.0.1"), &private));
+        assert!(validate_ip(&json!("8.8.8.8"), &private));
+
+        assert!(!validate_ip(&json!("127.0.0.1"), &loopback));
+        assert!(!validate_ip(&json!("127.255.0.1"), &loopback));
+        assert!(!validate_ip(&json!("::1"), &loopback));
+        assert!(validate_ip(&json!("10.0.0.1"), &loopback));
+
+        assert!(!validate_ip(&json!("224.0.0.1"), &multicast));
+        assert!(!validate_ip(&json!("239.255.255.255"), &multicast));
+        assert!(!validate_ip(&json!("ff02::1"), &multicast));
+        assert!(validate_ip(&json!("223.255.255.255"), &multicast));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+        let cidr = options(json!({"allow_cidr": true}));
+
+        assert_eq!(coerce_ip(&json!("2001:0DB8:0000:0000:0000:0000:0000:0001"), &defaults), Some(json!("2001:db8::1")));
+        assert_eq!(coerce_ip(&json!("192.168.1.1"), &defaults), Some(json!("192.168.1.1")));
+        assert_eq!(coerce_ip(&json!("2001:DB8::/32"), &cidr), Some(json!("2001:db8::/32")));
+        assert_eq!(coerce_ip(&json!("999.1.1.1"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "::1"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "::1", "options": {"version": 6, "allow_cidr": true}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "::1", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_ip(&json!("::FFFF:192.0.2.1"), &defaults)),
+            ValidationResult { valid: true, value: json!("::ffff:192.0.2.1") }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_ip(&json!("1.2.3"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["192.168.1.1", "::1", "256.0.0.1", "localhost"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// Types for the ip validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Accept only IPv4 or IPv6 addresses (default: "any") */
+    version?: "any" | 4 | 6 | "4" | "6";
+    /** Accept CIDR notation such as 10.0.0.0/8 (default: false) */
+    allow_cidr?: boolean;
+    /** Reject RFC 1918 private IPv4 ranges and IPv6 unique local addresses (default: false) */
+    reject_private?: boolean;
+    /** Reject 127.0.0.0/8 and ::1 (default: false) */
+    reject_loopback?: boolean;
+    /** Reject 224.0.0.0/4 and ff00::/8 (default: false) */
+    reject_multicast?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,551 @@
+// ABOUTME: WASM component for exact decimal numbers, such as amounts of money, read without binary float rounding
+// ABOUTME: Enforces SQL-style precision and scale and limits, and coerces to a canonical decimal string
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Most digits in all, before and after the point, as in SQL's DECIMAL(precision, scale)
+    precision: Option<u32>,
+    // Digits after the point: values with more are rejected unless the extra digits are zeros,
+    // and the canonical string is padded to this many, so with 2, "12.5" is "12.50"
+    scale: Option<u32>,
+    // Lowest and highest accepted values, themselves included, as decimal strings or numbers
+    min: Option<Decimal>,
+    max: Option<Decimal>,
+    // Accept JSON numbers, which are binary floats by the time they arrive, as the shortest
+    // decimal that reads back as the same float, so 0.1 is "0.1"; strings are always exact
+    allow_numbers: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { precision: None, scale: None, min: None, max: None, allow_numbers: true }
+    }
+}
+
+impl ValidationOptions {
+    // The decimal at the scale the options ask for, or why it isn't accepted: outside min and
+    // max, or with more digits than allowed
+    fn check(&self, decimal: Decimal) -> Result<Decimal, &'static str> {
+        let below = self.min.as_ref().is_some_and(|min| decimal.compare(min) == Ordering::Less);
+        let above = self.max.as_ref().is_some_and(|max| decimal.compare(max) == Ordering::Greater);
+        if below || above {
+            return Err(OUT_OF_RANGE);
+        }
+        let decimal = match self.scale {
+            Some(scale) => decimal.rescale(scale as usize).ok_or(TOO_MANY_DECIMAL_PLACES)?,
+            None => decimal,
+        };
+        if self.precision.is_some_and(|precision| decimal.precision() > precision as usize) {
+            return Err(TOO_MANY_DIGITS);
+        }
+        Ok(decimal)
+    }
+}
+
+// An exact decimal number: its digits without leading zeros, empty for zero, and how many of
+// them come after the point. Trailing zeros are kept, so "12.50" has a scale of 2
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(try_from = "Value", into = "String")]
+pub struct Decimal {
+    negative: bool,
+    digits: String,
+    scale: usize,
+}
+
+impl Decimal {
+    // Digits before the point and after it, as SQL counts a DECIMAL's precision
+    fn precision(&self) -> usize {
+        self.digits.len().max(self.scale)
+    }
+
+    // The digits as an integer at a larger scale, without leading zeros
+    fn magnitude(&self, scale: usize) -> String {
+        let mut digits = self.digits.clone();
+        digits.extend(std::iter::repeat_n('0', scale - self.scale));
+        digits.trim_start_matches('0').to_string()
+    }
+
+    // Orders two decimals by value, whatever their scales, with -0 equal to 0
+    fn compare(&self, other: &Decimal) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        let (a, b) = (self.magnitude(scale), other.magnitude(scale));
+        let by_magnitude = a.len().cmp(&b.len()).then_with(|| a.cmp(&b));
+        match (self.negative && !a.is_empty(), other.negative && !b.is_empty()) {
+            (false, false) => by_magnitude,
+            (true, true) => by_magnitude.reverse(),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+        }
+    }
+
+    // The same value with exactly `scale` digits after the point, padded with zeros; None when
+    // that would drop digits other than trailing zeros, since nothing is ever rounded
+    fn rescale(self, scale: usize) -> Option<Decimal> {
+        if scale >= self.scale {
+            let digits = format!("{}{}", self.digits, "0".repeat(scale - self.scale));
+            return Some(Decimal { digits: digits.trim_start_matches('0').to_string(), scale, ..self });
+        }
+        let (kept, dropped) = self.digits.split_at(self.digits.len().saturating_sub(self.scale - scale));
+        dropped.bytes().all(|b| b == b'0').then(|| Decimal { digits: kept.to_string(), scale, ..self })
+    }
+}
+
+// Most that an exponent may move the point, so that "1e999999" can't make a huge string
+const MAX_EXPONENT: i64 = 1000;
+
+// A decimal written as a string: an optional sign, digits with an optional point, which may
+// come first or last (".5", "5."), and an optional exponent ("1.5e3"), around any whitespace
+fn parse_decimal(text: &str) -> Option<Decimal> {
+    let text = text.trim();
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => {
+            let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            if digits.is_empty() || digits.len() > 6 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            (mantissa, exponent.parse::<i64>().ok().filter(|exponent| exponent.abs() <= MAX_EXPONENT)?)
+        }
+        None => (unsigned, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !all_digits(integer) || !all_digits(fraction) {
+        return None;
+    }
+
+    // The exponent moves the point, and past the last digit adds zeros
+    let scale = fraction.len() as i64 - exponent;
+    let digits = format!("{integer}{fraction}{}", "0".repeat((-scale).max(0) as usize));
+    Some(Decimal { negative, digits: digits.trim_start_matches('0').to_string(), scale: scale.max(0) as usize })
+}
+
+impl TryFrom<Value> for Decimal {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let text = match value {
+            Value::String(text) => text,
+            Value::Number(number) => number.to_string(),
+            _ => return Err("expected a decimal string or number".to_string()),
+        };
+        parse_decimal(&text).ok_or_else(|| format!("invalid decimal: {text:?}"))
+    }
+}
+
+impl From<Decimal> for String {
+    fn from(decimal: Decimal) -> String {
+        decimal.to_string()
+    }
+}
+
+// The canonical form: a minus sign only for values below zero, one zero before the point when
+// there are no other digits there, and the scale's digits after it
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = format!("{:0>width$}", self.digits, width = self.scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale);
+        let sign = if self.negative && !self.digits.is_empty() { "-" } else { "" };
+        match fraction {
+            "" => write!(f, "{sign}{integer}"),
+            fraction => write!(f, "{sign}{integer}.{fraction}"),
+        }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a decimal outside min and max or with too many digits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None }
+    }
+}
+
+// Error codes reported in detailed results; the first two are shared with the float validator
+const OUT_OF_RANGE: &str = messages::ErrorCode::OutOfRange.as_str();
+const TOO_MANY_DECIMAL_PLACES: &str = "too_many_decimal_places";
+const TOO_MANY_DIGITS: &str = "too_many_digits";
+
+fn detail_decimal_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    let decimal = match parse_decimal(text) {
+        Some(decimal) => decimal,
+        None => return ValidationResult::from(None),
+    };
+    match options.check(decimal) {
+        Ok(decimal) => ValidationResult::from(Some(Value::from(decimal.to_string()))),
+        Err(error) => ValidationResult { error: Some(error), ..ValidationResult::from(None) },
+    }
+}
+
+// Strings, and numbers unless allow_numbers is off; the fast path calls detail_decimal_str
+// directly
+fn detail_decimal(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value {
+        Value::String(text) => detail_decimal_str(text, options),
+        Value::Number(number) if options.allow_numbers => detail_decimal_str(&number.to_string(), options),
+        _ => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the canonical decimal string for a valid input
+fn coerce_decimal(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_decimal(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_decimal(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_decimal(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_decimal(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_decimal(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_decimal(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_decimal(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_decimal(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_decimal(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_decimal(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_decimal_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(options: Value) -> ValidationOptions {
+        serde_json::from_value(options).unwrap()
+    }
+
+    fn coerce(value: Value) -> Option<Value> {
+        coerce_decimal(&value, &ValidationOptions::default())
+    }
+
+    #[test]
+    fn test_canonical_strings() {
+        assert_eq!(coerce(json!("12.50")), Some(json!("12.50")));
+        assert_eq!(coerce(json!(" +007.5 ")), Some(json!("7.5")));
+        assert_eq!(coerce(json!(".5")), Some(json!("0.5")));
+        assert_eq!(coerce(json!("5.")), Some(json!("5")));
+        assert_eq!(coerce(json!("-0.00")), Some(json!("0.00")));
+        assert_eq!(coerce(json!("1.5e3")), Some(json!("1500")));
+        assert_eq!(coerce(json!("1.50E-3")), Some(json!("0.00150")));
+        assert_eq!(coerce(json!("-12e+1")), Some(json!("-120")));
+
+        // Digits far beyond what an f64 or i128 holds are kept exactly
+        let long = "123456789012345678901234567890.000000000000000000000000000001";
+        assert_eq!(coerce(json!(long)), Some(json!(long)));
+        assert_eq!(coerce(json!("0.1")), Some(json!("0.1")));
+
+        for bad in ["", " ", "-", ".", "1.2.3", "1,000", "1e", "1e+", "e5", "++1", "0x10", "NaN", "Infinity", "1e1001", "١٢"] {
+            assert_eq!(coerce(json!(bad)), None, "{bad}");
+        }
+        assert_eq!(coerce(json!(true)), None);
+        assert_eq!(coerce(json!(null)), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(coerce(json!(42)), Some(json!("42")));
+        assert_eq!(coerce(json!(0.1)), Some(json!("0.1")));
+        assert_eq!(coerce(json!(-2.5)), Some(json!("-2.5")));
+        assert_eq!(coerce(json!(1e21)), Some(json!("1000000000000000000000")));
+
+        let strings_only = options(json!({"allow_numbers": false}));
+        assert_eq!(coerce_decimal(&json!(0.1), &strings_only), None);
+        assert_eq!(coerce_decimal(&json!("0.1"), &strings_only), Some(json!("0.1")));
+    }
+
+    #[test]
+    fn test_precision_and_scale() {
+        // DECIMAL(5, 2): up to 999.99
+        let money = options(json!({"precision": 5, "scale": 2}));
+        let detail = |text: &str| detail_decimal(&json!(text), &money);
+        assert_eq!(detail("12.5").value, json!("12.50"));
+        assert_eq!(detail("999.99").value, json!("999.99"));
+        assert_eq!(detail("-0.01").value, json!("-0.01"));
+        assert_eq!(detail("7").value, json!("7.00"));
+        // Extra zeros are dropped, but nothing is rounded
+        assert_eq!(detail("12.5000").value, json!("12.50"));
+        assert_eq!(detail("12.505").error, Some(TOO_MANY_DECIMAL_PLACES));
+        assert_eq!(detail("0.001").error, Some(TOO_MANY_DECIMAL_PLACES));
+        assert_eq!(detail("1000").error, Some(TOO_MANY_DIGITS));
+        assert_eq!(detail("1000.00").error, Some(TOO_MANY_DIGITS));
+        assert_eq!(detail("abc").error, None);
+
+        // Without a scale, precision counts the digits as written
+        let precise = options(json!({"precision": 3}));
+        assert!(validate_decimal(&json!("1.23"), &precise));
+        assert!(validate_decimal(&json!("0.005"), &precise));
+        assert!(!validate_decimal(&json!("1.230"), &precise));
+        assert!(!validate_decimal(&json!("0.0005"), &precise));
+
+        let whole = options(json!({"scale": 0}));
+        assert_eq!(coerce_decimal(&json!("42.000"), &whole), Some(json!("42")));
+        assert_eq!(coerce_decimal(&json!("4.2e1"), &whole), Some(json!("42")));
+        assert_eq!(coerce_decimal(&json!("0.5"), &whole), None);
+    }
+
+    #[test]
+    fn test_range() {
+        let positive = options(json!({"min": "0.01", "max": 1000}));
+        assert!(validate_decimal(&json!("0.01"), &positive));
+        assert!(validate_decimal(&json!("1000.000"), &positive));
+        assert!(validate_decimal(&json!("999.9999999999999999999"), &positive));
+        assert_eq!(detail_decimal(&json!("0.00999999999999999999"), &positive).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_decimal(&json!("1000.0000000000000000001"), &positive).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_decimal(&json!("-5"), &positive).error, Some(OUT_OF_RANGE));
+
+        let negative = options(json!({"min": "-10.5", "max": "-0"}));
+        assert!(validate_decimal(&json!("-10.50"), &negative));
+        assert!(validate_decimal(&json!("0"), &negative));
+        assert!(!validate_decimal(&json!("-10.51"), &negative));
+        assert!(!validate_decimal(&json!("0.001"), &negative));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"min": "ten"})).is_err());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"max": true})).is_err());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"scale": -1})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "1", "options": {"round": true}})).is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let compare = |a: &str, b: &str| parse_decimal(a).unwrap().compare(&parse_decimal(b).unwrap());
+        assert_eq!(compare("1.50", "1.5"), Ordering::Equal);
+        assert_eq!(compare("-0", "0.000"), Ordering::Equal);
+        assert_eq!(compare("0.1", "0.09"), Ordering::Greater);
+        assert_eq!(compare("-2", "-10"), Ordering::Greater);
+        assert_eq!(compare("-1", "0.5"), Ordering::Less);
+        assert_eq!(compare("1e3", "999.999"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let money = options(json!({"scale": 2}));
+        assert_eq!(
+            messages::localize(&detail_decimal(&json!("9.999"), &money), &json!("9.999"), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "too_many_decimal_places",
+                "message": messages::message("too_many_decimal_places", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_decimal(&json!(""), &money), &json!(""), Some("en"))["error"], "empty_value");
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["1.5", 2], "options": {"scale": 2}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in ["12.50", " -0.5 ", "1e3", "", "1,000", "abc"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
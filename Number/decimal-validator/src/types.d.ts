@@ -0,0 +1,45 @@
+// Types for the decimal validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Most digits in all, before and after the point, as in SQL's DECIMAL(precision, scale) (default: null) */
+    precision?: number | null;
+    /** Digits after the point: values with more are rejected unless the extra digits are zeros, and the canonical string is padded to this many (default: null) */
+    scale?: number | null;
+    /** Lowest accepted value, itself included, as a decimal string or number (default: null) */
+    min?: string | number | null;
+    /** Highest accepted value, itself included, as a decimal string or number (default: null) */
+    max?: string | number | null;
+    /** Accept JSON numbers, read as the shortest decimal that reads back as the same float, so 0.1 is "0.1"; strings are always exact (default: true) */
+    allow_numbers?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string | number;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "out_of_range" | "too_many_decimal_places" | "too_many_digits" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -1,67 +1,408 @@
 // ABOUTME: WASM component for floating point number validation
-// ABOUTME: Provides a validate function to check if a value is a valid floating point number
+// ABOUTME: Provides validate and coerce functions to check if a value is a valid floating point number
+
+use std::borrow::Cow;
 
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// The strictness mode shared with the other primitive validators
+#[path = "../../../mode/mode.rs"]
+mod mode;
+
+use mode::Mode;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct ValidationInput {
     value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
 }
 
-// Internal validation logic that can be tested without WASM
-fn validate_float(value: &Value) -> bool {
-    match value {
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // How far to go to read a value: "strict", "lenient" or "coerce"
+    mode: Mode,
+    // Lowest and highest accepted values, themselves included
+    min: Option<f64>,
+    max: Option<f64>,
+    // Most digits accepted after the decimal point, such as 2 for amounts of money
+    max_decimal_places: Option<u32>,
+    // Most significant digits accepted, not counting leading or trailing zeros
+    max_significant_digits: Option<u32>,
+    // Decimal separator of numbers written as strings; defaults to the locale's, or "."
+    // without a locale
+    decimal_separator: Option<DecimalSeparator>,
+    // BCP 47 language tag, such as "de-DE", whose decimal separator is the default
+    locale: Option<String>,
+    // Accept strings with a trailing percent sign, such as "45%", read as a fraction (0.45) or
+    // as the number written (45); without it they're rejected
+    percent: Option<Percent>,
+}
+
+// The character between a number's integer and fractional parts
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecimalSeparator {
+    #[serde(rename = ".")]
+    Point,
+    #[serde(rename = ",")]
+    Comma,
+}
+
+impl ValidationOptions {
+    fn decimal_separator(&self) -> DecimalSeparator {
+        match (self.decimal_separator, &self.locale) {
+            (Some(separator), _) => separator,
+            (None, Some(locale)) if mode::decimal_comma(locale) => DecimalSeparator::Comma,
+            _ => DecimalSeparator::Point,
+        }
+    }
+
+    // A number written as a string, rewritten with a decimal point: with a decimal comma, the
+    // comma and the points or spaces grouping thousands trade places, so that "1.234,56" reads
+    // as "1,234.56" and "3,14" as "3.14"
+    fn with_decimal_point<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self.decimal_separator() {
+            DecimalSeparator::Point => Cow::Borrowed(text),
+            DecimalSeparator::Comma => Cow::Owned(
+                text.trim()
+                    .chars()
+                    .map(|c| match c {
+                        ',' => '.',
+                        '.' | ' ' | '\u{a0}' | '\u{202f}' => ',',
+                        c => c,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    // A string without the percent sign it ends with, if percentages are accepted and it has
+    // one; a space may come before the sign, as in "3.5 %"
+    fn strip_percent<'a>(&self, text: &'a str) -> (&'a str, bool) {
+        match text.trim_end().strip_suffix('%') {
+            Some(number) if self.percent.is_some() => (number, true),
+            _ => (text, false),
+        }
+    }
+
+    // Why a finite number isn't accepted, if it isn't: outside min and max, or written with
+    // more digits than allowed
+    fn check(&self, number: f64) -> Option<&'static str> {
+        if self.min.is_some_and(|min| number < min) || self.max.is_some_and(|max| number > max) {
+            return Some(OUT_OF_RANGE);
+        }
+        let (significant_digits, decimal_places) = precision(number);
+        if self.max_decimal_places.is_some_and(|max| decimal_places > max) {
+            return Some(TOO_MANY_DECIMAL_PLACES);
+        }
+        if self.max_significant_digits.is_some_and(|max| significant_digits > max) {
+            return Some(TOO_MANY_SIGNIFICANT_DIGITS);
+        }
+        None
+    }
+}
+
+// What a percentage such as "45%" is read as
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Percent {
+    // 0.45, as spreadsheets store it
+    Fraction,
+    // 45, the number without its sign
+    Number,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a number outside min and max or with too many digits
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const OUT_OF_RANGE: &str = messages::ErrorCode::OutOfRange.as_str();
+const TOO_MANY_DECIMAL_PLACES: &str = "too_many_decimal_places";
+const TOO_MANY_SIGNIFICANT_DIGITS: &str = "too_many_significant_digits";
+
+// The number a value stands for, before its range and digits are checked
+fn parse_float(value: &Value, options: &ValidationOptions) -> Option<f64> {
+    match (value, options.mode) {
         // Direct number
-        Value::Number(n) => {
+        (Value::Number(n), _) => {
             // Accept any finite number (integer or float)
-            // Reject NaN and infinite values
-            n.is_f64() || n.is_i64() || n.is_u64()
+            // JSON numbers can never be NaN or infinite
+            n.as_f64()
         },
-        // String that might contain a float
-        Value::String(s) => {
-            // Trim whitespace
-            let trimmed = s.trim();
-            
-            // Check for empty string
-            if trimmed.is_empty() {
-                return false;
-            }
-            
-            // Check for special values we want to reject
-            let lower = trimmed.to_lowercase();
-            if lower == "nan" || lower == "infinity" || lower == "-infinity" || 
-               lower == "inf" || lower == "-inf" || lower == "+inf" {
-                return false;
+        // String that might contain a float, in the decimal separator's notation and perhaps a
+        // percentage. Coercive parsing drops thousands separators first, and so does lenient
+        // parsing once a decimal comma is configured, since the points grouping "1.234,56"
+        // can't then be mistaken for a decimal point
+        (Value::String(s), Mode::Lenient | Mode::Coerce) => {
+            let (text, percentage) = options.strip_percent(s);
+            let text = options.with_decimal_point(text);
+            let number = if options.mode == Mode::Coerce || options.decimal_separator() == DecimalSeparator::Comma {
+                mode::strip_grouping(&text).and_then(|s| parse_float_str(&s))?
+            } else {
+                parse_float_str(&text)?
+            };
+            // Shifting the decimal point, rather than dividing by 100, gives the f64 nearest the
+            // fraction, so "3.5%" is exactly 0.035
+            match (percentage, options.percent) {
+                (true, Some(Percent::Fraction)) => format!("{number}e-2").parse().ok(),
+                _ => Some(number),
             }
-            
-            // Try parsing as f64
-            match trimmed.parse::<f64>() {
-                Ok(f) => {
-                    // Check that the parsed value is finite (not NaN or infinite)
-                    f.is_finite()
-                },
-                Err(_) => false,
-            }
-        },
-        // All other types are not floating point numbers
-        _ => false,
+        }
+        // All other types are not floating point numbers, and strict mode accepts nothing else
+        _ => None,
     }
 }
 
-#[wasm_bindgen]
-pub fn validate(input: JsValue) -> bool {
+// A number's significant digits and decimal places, as its shortest form that reads back as
+// the same f64 writes them, so that 2.50 and "2.5" both have one decimal place
+fn precision(number: f64) -> (u32, u32) {
+    let scientific = format!("{:e}", number.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let significant_digits = mantissa.bytes().filter(u8::is_ascii_digit).count() as i32;
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    (significant_digits as u32, (significant_digits - 1 - exponent).max(0) as u32)
+}
+
+// Detailed validation; a number outside min and max, or with more digits than allowed, is
+// reported with its own error code
+fn detail_float(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let number = match parse_float(value, options) {
+        Some(number) => number,
+        None => return ValidationResult::from(None),
+    };
+    match options.check(number) {
+        Some(error) => ValidationResult { error: Some(error), ..ValidationResult::from(None) },
+        None => ValidationResult::from(serde_json::Number::from_f64(number).map(Value::Number)),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the number as a JSON float for a valid input, or None when the value is not a finite
+// number or isn't accepted by the options
+fn coerce_float(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_float(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Floats written as strings, shared with the validate_str fast path
+fn parse_float_str(text: &str) -> Option<f64> {
+    // Trim whitespace
+    let trimmed = text.trim();
+
+    // Check for empty string
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    // Check for special values we want to reject
+    let lower = trimmed.to_lowercase();
+    if lower == "nan" || lower == "infinity" || lower == "-infinity" ||
+       lower == "inf" || lower == "-inf" || lower == "+inf" {
+        return None;
+    }
+
+    // Try parsing as f64, keeping only finite values (not NaN or infinite)
+    trimmed.parse::<f64>().ok().filter(|f| f.is_finite())
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_float(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_float(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_float(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_float(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
     // Parse the input object
-    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input) {
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
         Ok(val) => val,
         Err(_) => return false,
     };
     
-    validate_float(&input_obj.value)
+    validate_float(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_float(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_float(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_float(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_float(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    parse_float_str(value).is_some()
+}
+
+// Fast path for a JS number with the default options, as validate_str is for strings
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_f64(value: f64) -> bool {
+    // NaN and the infinities have no JSON representation
+    value.is_finite()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)] // 3.14 and friends are test data, not stand-ins for PI
 mod tests {
     use super::*;
     use serde_json::json;
@@ -69,95 +410,326 @@ mod tests {
     #[test]
     fn test_valid_floats() {
         // Direct float numbers
-        assert!(validate_float(&json!(3.14)));
-        assert!(validate_float(&json!(0.0)));
-        assert!(validate_float(&json!(-3.14)));
-        assert!(validate_float(&json!(1.23e10))); // Scientific notation
-        assert!(validate_float(&json!(-1.23e-10))); // Negative scientific notation
-        assert!(validate_float(&json!(0.1)));
-        assert!(validate_float(&json!(-0.1)));
+        assert!(validate_float(&json!(3.14), &ValidationOptions::default()));
+        assert!(validate_float(&json!(0.0), &ValidationOptions::default()));
+        assert!(validate_float(&json!(-3.14), &ValidationOptions::default()));
+        assert!(validate_float(&json!(1.23e10), &ValidationOptions::default())); // Scientific notation
+        assert!(validate_float(&json!(-1.23e-10), &ValidationOptions::default())); // Negative scientific notation
+        assert!(validate_float(&json!(0.1), &ValidationOptions::default()));
+        assert!(validate_float(&json!(-0.1), &ValidationOptions::default()));
         
         // Integers are also valid floats
-        assert!(validate_float(&json!(42)));
-        assert!(validate_float(&json!(0)));
-        assert!(validate_float(&json!(-42)));
-        assert!(validate_float(&json!(1000000)));
+        assert!(validate_float(&json!(42), &ValidationOptions::default()));
+        assert!(validate_float(&json!(0), &ValidationOptions::default()));
+        assert!(validate_float(&json!(-42), &ValidationOptions::default()));
+        assert!(validate_float(&json!(1000000), &ValidationOptions::default()));
         
         // String representations of floats
-        assert!(validate_float(&json!("3.14")));
-        assert!(validate_float(&json!("0.0")));
-        assert!(validate_float(&json!("-3.14")));
-        assert!(validate_float(&json!("1.23e10")));
-        assert!(validate_float(&json!("-1.23e-10")));
-        assert!(validate_float(&json!("  3.14  "))); // With whitespace
-        assert!(validate_float(&json!("\t-0.5\n"))); // With tabs and newlines
+        assert!(validate_float(&json!("3.14"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("0.0"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("-3.14"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("1.23e10"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("-1.23e-10"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("  3.14  "), &ValidationOptions::default())); // With whitespace
+        assert!(validate_float(&json!("\t-0.5\n"), &ValidationOptions::default())); // With tabs and newlines
         
         // String representations of integers (valid as floats)
-        assert!(validate_float(&json!("42")));
-        assert!(validate_float(&json!("0")));
-        assert!(validate_float(&json!("-42")));
-        assert!(validate_float(&json!("+3.14"))); // With plus sign
+        assert!(validate_float(&json!("42"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("0"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("-42"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("+3.14"), &ValidationOptions::default())); // With plus sign
     }
 
     #[test]
     fn test_invalid_floats() {
         // Special float values that we reject
-        assert!(!validate_float(&json!("NaN")));
-        assert!(!validate_float(&json!("nan")));
-        assert!(!validate_float(&json!("Infinity")));
-        assert!(!validate_float(&json!("infinity")));
-        assert!(!validate_float(&json!("-Infinity")));
-        assert!(!validate_float(&json!("+Infinity")));
-        assert!(!validate_float(&json!("inf")));
-        assert!(!validate_float(&json!("-inf")));
-        assert!(!validate_float(&json!("+inf")));
+        assert!(!validate_float(&json!("NaN"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("nan"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("Infinity"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("infinity"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("-Infinity"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("+Infinity"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("inf"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("-inf"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("+inf"), &ValidationOptions::default()));
         
         // Invalid string formats
-        assert!(!validate_float(&json!(""))); // Empty string
-        assert!(!validate_float(&json!("   "))); // Only whitespace
-        assert!(!validate_float(&json!("not a number")));
-        assert!(!validate_float(&json!("12.34.56"))); // Multiple decimal points
-        assert!(!validate_float(&json!("12abc")));
-        assert!(!validate_float(&json!("abc12")));
-        assert!(!validate_float(&json!("3.14.159"))); // Multiple decimals
-        assert!(!validate_float(&json!("--3.14"))); // Double negative
-        assert!(!validate_float(&json!("3.14-"))); // Trailing negative
+        assert!(!validate_float(&json!(""), &ValidationOptions::default())); // Empty string
+        assert!(!validate_float(&json!("   "), &ValidationOptions::default())); // Only whitespace
+        assert!(!validate_float(&json!("not a number"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("12.34.56"), &ValidationOptions::default())); // Multiple decimal points
+        assert!(!validate_float(&json!("12abc"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("abc12"), &ValidationOptions::default()));
+        assert!(!validate_float(&json!("3.14.159"), &ValidationOptions::default())); // Multiple decimals
+        assert!(!validate_float(&json!("--3.14"), &ValidationOptions::default())); // Double negative
+        assert!(!validate_float(&json!("3.14-"), &ValidationOptions::default())); // Trailing negative
         
         // Other JSON types
-        assert!(!validate_float(&json!(true)));
-        assert!(!validate_float(&json!(false)));
-        assert!(!validate_float(&json!(null)));
-        assert!(!validate_float(&json!([])));
-        assert!(!validate_float(&json!({})));
-        assert!(!validate_float(&json!({"number": 3.14})));
+        assert!(!validate_float(&json!(true), &ValidationOptions::default()));
+        assert!(!validate_float(&json!(false), &ValidationOptions::default()));
+        assert!(!validate_float(&json!(null), &ValidationOptions::default()));
+        assert!(!validate_float(&json!([]), &ValidationOptions::default()));
+        assert!(!validate_float(&json!({}), &ValidationOptions::default()));
+        assert!(!validate_float(&json!({"number": 3.14}), &ValidationOptions::default()));
     }
     
     #[test]
     fn test_edge_cases() {
         // Very small numbers
-        assert!(validate_float(&json!(1e-308))); // Near minimum positive double
-        assert!(validate_float(&json!(-1e-308)));
-        assert!(validate_float(&json!("1e-308")));
+        assert!(validate_float(&json!(1e-308), &ValidationOptions::default())); // Near minimum positive double
+        assert!(validate_float(&json!(-1e-308), &ValidationOptions::default()));
+        assert!(validate_float(&json!("1e-308"), &ValidationOptions::default()));
         
         // Very large numbers
-        assert!(validate_float(&json!(1e308))); // Near maximum double
-        assert!(validate_float(&json!(-1e308)));
-        assert!(validate_float(&json!("1e308")));
+        assert!(validate_float(&json!(1e308), &ValidationOptions::default())); // Near maximum double
+        assert!(validate_float(&json!(-1e308), &ValidationOptions::default()));
+        assert!(validate_float(&json!("1e308"), &ValidationOptions::default()));
         
         // Zero in various forms
-        assert!(validate_float(&json!(0.0)));
-        assert!(validate_float(&json!(-0.0)));
-        assert!(validate_float(&json!("0.0")));
-        assert!(validate_float(&json!("-0.0")));
-        assert!(validate_float(&json!("0")));
-        assert!(validate_float(&json!("-0")));
-        assert!(validate_float(&json!("00.00"))); // Leading/trailing zeros
+        assert!(validate_float(&json!(0.0), &ValidationOptions::default()));
+        assert!(validate_float(&json!(-0.0), &ValidationOptions::default()));
+        assert!(validate_float(&json!("0.0"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("-0.0"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("0"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("-0"), &ValidationOptions::default()));
+        assert!(validate_float(&json!("00.00"), &ValidationOptions::default())); // Leading/trailing zeros
         
         // Scientific notation variations
-        assert!(validate_float(&json!("1E10"))); // Capital E
-        assert!(validate_float(&json!("1e+10"))); // Explicit plus in exponent
-        assert!(validate_float(&json!(".5"))); // No leading zero
-        assert!(validate_float(&json!("5."))); // No trailing zero
-        assert!(validate_float(&json!("-.5"))); // Negative without leading zero
+        assert!(validate_float(&json!("1E10"), &ValidationOptions::default())); // Capital E
+        assert!(validate_float(&json!("1e+10"), &ValidationOptions::default())); // Explicit plus in exponent
+        assert!(validate_float(&json!(".5"), &ValidationOptions::default())); // No leading zero
+        assert!(validate_float(&json!("5."), &ValidationOptions::default())); // No trailing zero
+        assert!(validate_float(&json!("-.5"), &ValidationOptions::default())); // Negative without leading zero
+    }
+
+    #[test]
+    fn test_options() {
+        // Options are optional and may be empty
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 2.5})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 2.5, "options": {}})).is_ok());
+
+        // Unknown options are rejected so typos never silently change behaviour
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 2.5, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_modes() {
+        let strict: ValidationOptions = serde_json::from_value(json!({"mode": "strict"})).unwrap();
+        let coerce: ValidationOptions = serde_json::from_value(json!({"mode": "coerce"})).unwrap();
+
+        assert_eq!(coerce_float(&json!(3.5), &strict), Some(json!(3.5)));
+        assert_eq!(coerce_float(&json!(42), &strict), Some(json!(42.0)));
+        assert_eq!(coerce_float(&json!("3.5"), &strict), None);
+
+        assert_eq!(coerce_float(&json!("1,234.5"), &coerce), Some(json!(1234.5)));
+        assert_eq!(coerce_float(&json!("-1_000e-3"), &coerce), Some(json!(-1.0)));
+        assert_eq!(coerce_float(&json!(" .5 "), &coerce), Some(json!(0.5)));
+        assert_eq!(coerce_float(&json!("3,14"), &coerce), None);
+        assert_eq!(coerce_float(&json!("NaN"), &coerce), None);
+        assert_eq!(coerce_float(&json!(true), &coerce), None);
+    }
+
+    #[test]
+    fn test_decimal_separator() {
+        let comma: ValidationOptions = serde_json::from_value(json!({"decimal_separator": ","})).unwrap();
+        let german: ValidationOptions = serde_json::from_value(json!({"locale": "de-DE", "mode": "coerce"})).unwrap();
+        let french: ValidationOptions = serde_json::from_value(json!({"locale": "fr-FR", "mode": "coerce"})).unwrap();
+
+        assert_eq!(coerce_float(&json!("3,14"), &comma), Some(json!(3.14)));
+        assert_eq!(coerce_float(&json!(" -0,5 "), &comma), Some(json!(-0.5)));
+        assert_eq!(coerce_float(&json!("42"), &comma), Some(json!(42.0)));
+        assert_eq!(coerce_float(&json!(2.5), &comma), Some(json!(2.5)));
+        // A point is then a thousands separator, dropped in lenient mode too when the grouping
+        // is well formed; a lone point with two digits after it is neither
+        assert_eq!(coerce_float(&json!("1.234,56"), &comma), Some(json!(1234.56)));
+        assert_eq!(coerce_float(&json!("1.234.567"), &comma), Some(json!(1234567.0)));
+        assert_eq!(coerce_float(&json!("12.34,5"), &comma), None);
+        assert_eq!(coerce_float(&json!("3.14"), &comma), None);
+        let lenient_german = serde_json::from_value(json!({"locale": "de-DE"})).unwrap();
+        assert_eq!(coerce_float(&json!("1.234,56"), &lenient_german), Some(json!(1234.56)));
+        // Without a decimal comma, lenient mode still leaves grouping to coercion
+        assert_eq!(coerce_float(&json!("1,234.5"), &ValidationOptions::default()), None);
+        assert_eq!(coerce_float(&json!("1.234,56"), &german), Some(json!(1234.56)));
+        assert_eq!(coerce_float(&json!("1.234.567"), &german), Some(json!(1234567.0)));
+        assert_eq!(coerce_float(&json!("3.14"), &german), None);
+        assert_eq!(coerce_float(&json!("1\u{202f}234,5"), &french), Some(json!(1234.5)));
+        assert_eq!(coerce_float(&json!("1 234,5"), &french), Some(json!(1234.5)));
+
+        // Locales that write a decimal point, and an explicit separator, override nothing else
+        let english: ValidationOptions = serde_json::from_value(json!({"locale": "en-GB"})).unwrap();
+        assert_eq!(coerce_float(&json!("3.14"), &english), Some(json!(3.14)));
+        assert_eq!(coerce_float(&json!("3,14"), &english), None);
+        let point: ValidationOptions = serde_json::from_value(json!({"locale": "de", "decimal_separator": "."})).unwrap();
+        assert_eq!(coerce_float(&json!("3.14"), &point), Some(json!(3.14)));
+
+        // Range and digit limits apply to the number read
+        let money: ValidationOptions = serde_json::from_value(json!({"locale": "it", "max": 100, "max_decimal_places": 2})).unwrap();
+        assert_eq!(coerce_float(&json!("99,99"), &money), Some(json!(99.99)));
+        assert_eq!(detail_float(&json!("99,999"), &money).error, Some(TOO_MANY_DECIMAL_PLACES));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"decimal_separator": ";"})).is_err());
+    }
+
+    #[test]
+    fn test_percent() {
+        let fraction: ValidationOptions = serde_json::from_value(json!({"percent": "fraction"})).unwrap();
+        let number: ValidationOptions = serde_json::from_value(json!({"percent": "number"})).unwrap();
+
+        assert_eq!(coerce_float(&json!("45%"), &fraction), Some(json!(0.45)));
+        assert_eq!(coerce_float(&json!("3.5 %"), &fraction), Some(json!(0.035)));
+        assert_eq!(coerce_float(&json!(" -12.5% "), &fraction), Some(json!(-0.125)));
+        assert_eq!(coerce_float(&json!("0.1%"), &fraction), Some(json!(0.001)));
+        assert_eq!(coerce_float(&json!("45%"), &number), Some(json!(45.0)));
+        assert_eq!(coerce_float(&json!("3.5 %"), &number), Some(json!(3.5)));
+        // Plain numbers are still numbers
+        assert_eq!(coerce_float(&json!("45"), &fraction), Some(json!(45.0)));
+        assert_eq!(coerce_float(&json!(0.45), &fraction), Some(json!(0.45)));
+
+        for bad in ["%", "45%%", "% 45", "4%5", "abc%"] {
+            assert_eq!(coerce_float(&json!(bad), &fraction), None, "{bad}");
+        }
+        assert_eq!(coerce_float(&json!("45%"), &ValidationOptions::default()), None);
+        let strict: ValidationOptions = serde_json::from_value(json!({"percent": "fraction", "mode": "strict"})).unwrap();
+        assert_eq!(coerce_float(&json!("45%"), &strict), None);
+
+        // With the other options: separators, and limits on the value returned
+        let german: ValidationOptions = serde_json::from_value(json!({"percent": "fraction", "locale": "de", "mode": "coerce"})).unwrap();
+        assert_eq!(coerce_float(&json!("1.250,5 %"), &german), Some(json!(12.505)));
+        let french: ValidationOptions = serde_json::from_value(json!({"percent": "fraction", "locale": "fr"})).unwrap();
+        assert_eq!(coerce_float(&json!("12,5\u{202f}%"), &french), Some(json!(0.125)));
+        let share: ValidationOptions = serde_json::from_value(json!({"percent": "fraction", "min": 0, "max": 1})).unwrap();
+        assert!(validate_float(&json!("100%"), &share));
+        assert_eq!(detail_float(&json!("101%"), &share).error, Some(OUT_OF_RANGE));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"percent": true})).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_range() {
+        let amount: ValidationOptions = serde_json::from_value(json!({"min": 0, "max": 10000})).unwrap();
+
+        assert_eq!(coerce_float(&json!(0), &amount), Some(json!(0.0)));
+        assert_eq!(coerce_float(&json!("10000"), &amount), Some(json!(10000.0)));
+        assert_eq!(coerce_float(&json!(" 9999.99 "), &amount), Some(json!(9999.99)));
+        assert_eq!(detail_float(&json!(-0.01), &amount).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_float(&json!("10000.5"), &amount).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_float(&json!("abc"), &amount).error, None);
+
+        // Either bound may be left open, and bounds may be fractional
+        let ratio: ValidationOptions = serde_json::from_value(json!({"max": 0.5})).unwrap();
+        assert!(validate_float(&json!(-1e300), &ratio));
+        assert!(!validate_float(&json!(0.500001), &ratio));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"min": "0"})).is_err());
+    }
+
+    #[test]
+    fn test_precision() {
+        assert_eq!(precision(0.0), (1, 0));
+        assert_eq!(precision(2.5), (2, 1));
+        assert_eq!(precision(-1234.56), (6, 2));
+        assert_eq!(precision(0.05), (1, 2));
+        assert_eq!(precision(1500.0), (2, 0));
+        assert_eq!(precision(1e21), (1, 0));
+        assert_eq!(precision(1.5e-7), (2, 8));
+        // 0.1 + 0.2 isn't 0.3 in binary, and takes all of an f64's digits to tell apart from it
+        assert_eq!(precision(0.1 + 0.2), (17, 17));
+
+        // Two decimal places, between 0 and 10000, for an amount of money; trailing zeros are
+        // not digits of the value
+        let money: ValidationOptions =
+            serde_json::from_value(json!({"min": 0, "max": 10000, "max_decimal_places": 2})).unwrap();
+        assert_eq!(coerce_float(&json!("19.99"), &money), Some(json!(19.99)));
+        assert_eq!(coerce_float(&json!("19.90"), &money), Some(json!(19.9)));
+        assert_eq!(coerce_float(&json!("19.900"), &money), Some(json!(19.9)));
+        assert_eq!(coerce_float(&json!(20), &money), Some(json!(20.0)));
+        assert_eq!(detail_float(&json!(19.999), &money).error, Some(TOO_MANY_DECIMAL_PLACES));
+        assert_eq!(detail_float(&json!(0.1 + 0.2), &money).error, Some(TOO_MANY_DECIMAL_PLACES));
+        // The range is checked first
+        assert_eq!(detail_float(&json!(-0.001), &money).error, Some(OUT_OF_RANGE));
+
+        let measurement: ValidationOptions = serde_json::from_value(json!({"max_significant_digits": 3})).unwrap();
+        assert!(validate_float(&json!(0.00123), &measurement));
+        assert!(validate_float(&json!("1.20e6"), &measurement));
+        assert!(validate_float(&json!(-999), &measurement));
+        assert_eq!(detail_float(&json!(1234), &measurement).error, Some(TOO_MANY_SIGNIFICANT_DIGITS));
+        assert_eq!(detail_float(&json!("0.001234"), &measurement).error, Some(TOO_MANY_SIGNIFICANT_DIGITS));
+
+        let whole: ValidationOptions = serde_json::from_value(json!({"max_decimal_places": 0})).unwrap();
+        assert!(validate_float(&json!("1e3"), &whole));
+        assert!(!validate_float(&json!(0.5), &whole));
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"max_decimal_places": -1})).is_err());
+    }
+
+    #[test]
+    fn test_coerce() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(coerce_float(&json!(3.14), &options), Some(json!(3.14)));
+        assert_eq!(coerce_float(&json!(42), &options), Some(json!(42.0)));
+        assert_eq!(coerce_float(&json!(" -0.5 "), &options), Some(json!(-0.5)));
+        assert_eq!(coerce_float(&json!("1e3"), &options), Some(json!(1000.0)));
+        assert_eq!(coerce_float(&json!(".5"), &options), Some(json!(0.5)));
+
+        assert_eq!(coerce_float(&json!("NaN"), &options), None);
+        assert_eq!(coerce_float(&json!("1e400"), &options), None); // Overflows to infinity
+        assert_eq!(coerce_float(&json!("abc"), &options), None);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_float(&json!(" 2.5 "), &options)),
+            ValidationResult { valid: true, value: json!(2.5), error: None }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_float(&json!("NaN"), &options)),
+            ValidationResult { valid: false, value: Value::Null, error: None }
+        );
+        let price: ValidationOptions = serde_json::from_value(json!({"max": 100, "max_decimal_places": 2})).unwrap();
+        assert_eq!(
+            messages::localize(&detail_float(&json!("9.999"), &price), &json!("9.999"), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "too_many_decimal_places",
+                "message": messages::message("too_many_decimal_places", "en").unwrap(),
+            })
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [1.5, "x", "2"]})).unwrap();
+        assert_eq!(batch.values.len(), 3);
+        assert!(serde_json::from_value::<BatchInput>(json!({"values": [], "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["3.14", " -2 ", "1e10", "NaN", "abc"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+        for number in [3.14, -2.0, f64::INFINITY, f64::NAN] {
+            assert_eq!(validate_f64(number), coerce_value(&Value::from(number), &Value::Null).is_some(), "{number}");
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// Types for the float validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** How far to go to read a value: "strict" takes only JSON numbers, "lenient" also numbers written as strings, and "coerce" also thousands separators ("1,234.5") (default: "lenient") */
+    mode?: "strict" | "lenient" | "coerce";
+    /** Lowest accepted value, itself included (default: null) */
+    min?: number | null;
+    /** Highest accepted value, itself included (default: null) */
+    max?: number | null;
+    /** Most digits accepted after the decimal point, such as 2 for amounts of money; trailing zeros don't count (default: null) */
+    max_decimal_places?: number | null;
+    /** Most significant digits accepted, not counting leading or trailing zeros (default: null) */
+    max_significant_digits?: number | null;
+    /** Decimal separator of numbers written as strings; with ",", points and spaces group thousands ("1.234,56"). Defaults to the locale's, or "." without a locale (default: null) */
+    decimal_separator?: "." | "," | null;
+    /** BCP 47 language tag, such as "de-DE", whose decimal separator is the default (default: null) */
+    locale?: string | null;
+    /** Accept strings with a trailing percent sign, such as "45%" or "3.5 %", read as a fraction (0.45) with "fraction" or as the number written (45) with "number"; null rejects them (default: null) */
+    percent?: "fraction" | "number" | null;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = number | string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = number;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "out_of_range" | "too_many_decimal_places" | "too_many_significant_digits" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -1,63 +1,662 @@
 // ABOUTME: WASM component for integer validation
-// ABOUTME: Provides a validate function to check if a value is a valid integer
+// ABOUTME: Provides validate and coerce functions to check if a value is a valid integer
 
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Number, Value};
+use std::cmp::Ordering;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// The strictness mode shared with the other primitive validators; its locale helper is for
+// the float and list validators
+#[path = "../../../mode/mode.rs"]
+#[allow(dead_code)]
+mod mode;
+
+use mode::Mode;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
 
 #[derive(Deserialize, Serialize)]
 pub struct ValidationInput {
     value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
 }
 
-// Internal validation logic that can be tested without WASM
-fn validate_integer(value: &Value) -> bool {
-    match value {
-        // Direct integer number
-        Value::Number(n) => {
-            // Check if it's an integer (not a float)
-            n.is_i64() || n.is_u64()
-        },
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // How far to go to read a value: "strict", "lenient" or "coerce"
+    mode: Mode,
+    // Lowest accepted value, itself included unless exclusive_min is set
+    min: Option<i64>,
+    // Highest accepted value, itself included unless exclusive_max is set
+    max: Option<i64>,
+    exclusive_min: bool,
+    exclusive_max: bool,
+    // Radixes strings may be written in: unprefixed digits are read in the first one, and
+    // "0x", "0o" and "0b" literals are accepted when 16, 8 or 2 is listed
+    radixes: Radixes,
+    // Thousands separators accepted in lenient and coerce modes, such as [",", "_"] or [".", " "]
+    // for European exports; digits must be grouped in threes
+    separators: Vec<Separator>,
+    // Accept integers of any size, returned as decimal strings so that no precision is lost
+    bigint: bool,
+    // Most digits, leading zeros aside, a string may be written with in bigint mode; longer ones
+    // are out of range rather than converted, which takes time in the square of their length
+    max_digits: usize,
+    // Fixed-width type the value must fit, such as "u8" or "i32", checked along with min and max
+    width: Option<Width>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            mode: Mode::default(),
+            min: None,
+            max: None,
+            exclusive_min: false,
+            exclusive_max: false,
+            radixes: Radixes(vec![Radix(10)]),
+            separators: Vec::new(),
+            bigint: false,
+            max_digits: DEFAULT_MAX_DIGITS,
+            width: None,
+        }
+    }
+}
+
+// Enough for any 128-bit ID or token amount, and quick to convert from any radix
+const DEFAULT_MAX_DIGITS: usize = 4096;
+
+// A radix integers may be written in: 2, 8, 10 or 16
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct Radix(u32);
+
+impl TryFrom<u32> for Radix {
+    type Error = String;
+
+    fn try_from(radix: u32) -> Result<Self, Self::Error> {
+        match radix {
+            2 | 8 | 10 | 16 => Ok(Radix(radix)),
+            _ => Err(format!("unsupported radix {radix}, expected 2, 8, 10 or 16")),
+        }
+    }
+}
+
+impl From<Radix> for u32 {
+    fn from(radix: Radix) -> u32 {
+        radix.0
+    }
+}
+
+// The radixes option: at least one radix, since with none every string would be rejected, a
+// mistake that would otherwise look like every value being invalid
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(try_from = "Vec<Radix>", into = "Vec<Radix>")]
+pub struct Radixes(Vec<Radix>);
+
+impl TryFrom<Vec<Radix>> for Radixes {
+    type Error = &'static str;
+
+    fn try_from(radixes: Vec<Radix>) -> Result<Self, Self::Error> {
+        if radixes.is_empty() {
+            return Err("radixes must list at least one radix");
+        }
+        Ok(Radixes(radixes))
+    }
+}
+
+impl From<Radixes> for Vec<Radix> {
+    fn from(radixes: Radixes) -> Vec<Radix> {
+        radixes.0
+    }
+}
+
+// A fixed-width integer type, as in a typed database column or protobuf field; i128 and u128
+// need the bigint option for values beyond u64
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Width {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    I128,
+    U128,
+}
+
+impl Width {
+    // The lowest and highest values of the type
+    fn range(self) -> (i128, u128) {
+        match self {
+            Width::I8 => (i8::MIN.into(), i8::MAX as u128),
+            Width::U8 => (0, u8::MAX.into()),
+            Width::I16 => (i16::MIN.into(), i16::MAX as u128),
+            Width::U16 => (0, u16::MAX.into()),
+            Width::I32 => (i32::MIN.into(), i32::MAX as u128),
+            Width::U32 => (0, u32::MAX.into()),
+            Width::I64 => (i64::MIN.into(), i64::MAX as u128),
+            Width::U64 => (0, u64::MAX.into()),
+            Width::I128 => (i128::MIN, i128::MAX as u128),
+            Width::U128 => (0, u128::MAX),
+        }
+    }
+
+    // The most digits, leading zeros aside, a value of the type is written with in a radix, so
+    // that longer literals can be rejected before they're converted
+    fn digits(self, radix: u32) -> usize {
+        let (min, max) = self.range();
+        max.max(min.unsigned_abs()).ilog(u128::from(radix)) as usize + 1
+    }
+
+    // Whether an integer, as a decimal string, fits the type
+    fn fits(self, decimal: &str) -> bool {
+        let (min, max) = self.range();
+        compare_decimal(decimal, &min.to_string()).is_ge() && compare_decimal(decimal, &max.to_string()).is_le()
+    }
+}
+
+impl ValidationOptions {
+    // Whether an integer lies within min and max; u64 values beyond i64 are compared exactly
+    fn in_range(&self, number: &Number) -> bool {
+        let value = match (number.as_i64(), number.as_u64()) {
+            (Some(n), _) => i128::from(n),
+            (None, Some(n)) => i128::from(n),
+            (None, None) => return false,
+        };
+        self.in_bounds(|bound| value.cmp(&i128::from(bound)))
+    }
+
+    // Whether a value lies within min and max, given how it compares with a bound
+    fn in_bounds(&self, compare: impl Fn(i64) -> Ordering) -> bool {
+        let above_min = match self.min.map(&compare) {
+            Some(ordering) if self.exclusive_min => ordering.is_gt(),
+            Some(ordering) => ordering.is_ge(),
+            None => true,
+        };
+        let below_max = match self.max.map(&compare) {
+            Some(ordering) if self.exclusive_max => ordering.is_lt(),
+            Some(ordering) => ordering.is_le(),
+            None => true,
+        };
+        above_min && below_max
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is an integer outside min and max
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error code for integers outside min and max
+const OUT_OF_RANGE: &str = messages::ErrorCode::OutOfRange.as_str();
+
+// The integer a value stands for, before the range is checked
+fn parse_integer(value: &Value, options: &ValidationOptions) -> Option<Number> {
+    match (value, options.mode) {
+        // Direct integer number; JSON numbers keep integers exact, so this doesn't go through f64
+        (Value::Number(n), _) if n.is_i64() || n.is_u64() => Some(n.clone()),
+        // Coercive parsing also takes whole floats such as 42.0
+        (Value::Number(n), Mode::Coerce) => n.as_f64().and_then(parse_integer_f64),
         // String that might contain an integer
-        Value::String(s) => {
-            // Try to parse as integer
-            // First trim whitespace
-            let trimmed = s.trim();
-            
-            // Check for empty string
-            if trimmed.is_empty() {
-                return false;
-            }
-            
-            // Try parsing as i64 (handles negative integers)
-            if trimmed.parse::<i64>().is_ok() {
-                return true;
-            }
-            
-            // Try parsing as u64 (handles very large positive integers)
-            if trimmed.parse::<u64>().is_ok() {
-                return true;
-            }
-            
-            false
+        (Value::String(s), Mode::Lenient | Mode::Coerce) => parse_integer_text(s, options),
+        // All other types are not integers, and strict mode accepts nothing else
+        _ => None,
+    }
+}
+
+// A thousands separator: comma, period, underscore, apostrophe, or a space, no-break space or
+// narrow no-break space
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(try_from = "char", into = "char")]
+pub struct Separator(char);
+
+impl TryFrom<char> for Separator {
+    type Error = String;
+
+    fn try_from(separator: char) -> Result<Self, Self::Error> {
+        match separator {
+            ',' | '.' | '_' | '\'' | ' ' | '\u{a0}' | '\u{202f}' => Ok(Separator(separator)),
+            _ => Err(format!("unsupported thousands separator {separator:?}")),
+        }
+    }
+}
+
+impl From<Separator> for char {
+    fn from(separator: Separator) -> char {
+        separator.0
+    }
+}
+
+// The integer a value stands for, as coerce returns it, and whether it lies within min and max
+// and fits the width
+fn read_integer(value: &Value, options: &ValidationOptions) -> Option<(Value, bool)> {
+    if options.bigint {
+        // Too long to convert, so out of range whatever its value; the value isn't returned
+        let Some(digits) = parse_bigint(value, options)? else {
+            return Some((Value::Null, false));
+        };
+        let in_range = options.in_bounds(|bound| compare_decimal(&digits, &bound.to_string()))
+            && options.width.is_none_or(|width| width.fits(&digits));
+        Some((Value::String(digits), in_range))
+    } else {
+        let number = parse_integer(value, options)?;
+        let in_range = options.in_range(&number) && options.width.is_none_or(|width| width.fits(&number.to_string()));
+        Some((Value::Number(number), in_range))
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the integer as a JSON number (or, in bigint mode, a decimal string) for a valid
+// input, or None when the value is not an integer or is outside min and max
+fn coerce_integer(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    read_integer(value, options).filter(|(_, in_range)| *in_range).map(|(value, _)| value)
+}
+
+// Detailed validation; an integer outside min and max is reported as out of range
+fn detail_integer(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match read_integer(value, options) {
+        Some((_, false)) => ValidationResult { error: Some(OUT_OF_RANGE), ..ValidationResult::from(None) },
+        parsed => ValidationResult::from(parsed.map(|(value, _)| value)),
+    }
+}
+
+// Integers written as strings, shared with the validate_str fast path
+fn parse_integer_str(text: &str) -> Option<Number> {
+    // First trim whitespace
+    let trimmed = text.trim();
+
+    // Check for empty string
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    // Try parsing as i64 (handles negative integers)
+    if let Ok(i) = trimmed.parse::<i64>() {
+        return Some(Number::from(i));
+    }
+
+    // Try parsing as u64 (handles very large positive integers)
+    trimmed.parse::<u64>().ok().map(Number::from)
+}
+
+// Coercive parsing of strings: thousands separators are dropped, and whole numbers written
+// with a fraction or an exponent ("42.0", "1e3") are accepted
+fn parse_integer_coercive(text: &str) -> Option<Number> {
+    let text = mode::strip_grouping(text)?;
+    parse_integer_str(&text).or_else(|| text.parse::<f64>().ok().and_then(parse_integer_f64))
+}
+
+// How a string in lenient or coerce mode writes its integer, given the configured radixes
+enum Literal<'a> {
+    // Unprefixed digits in radix 10, read by the mode's own parsing
+    Decimal,
+    // Digits in another radix, checked, without their sign or prefix
+    Radix { negative: bool, radix: u32, digits: &'a str },
+}
+
+// "0xFF", "-0o755" and "0b1010" are read in their radix when it is listed, and other strings in
+// the first radix, so that radixes [16] reads a bare "ff" as 255
+fn literal<'a>(text: &'a str, radixes: &[Radix]) -> Option<Literal<'a>> {
+    let trimmed = text.trim();
+    let (negative, unsigned) = match trimmed.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let prefixed = [(16, "0x"), (8, "0o"), (2, "0b")].into_iter().find_map(|(radix, prefix)| {
+        let digits = unsigned.get(2..).filter(|_| unsigned[..2].eq_ignore_ascii_case(prefix))?;
+        radixes.contains(&Radix(radix)).then_some((radix, digits))
+    });
+    let (radix, digits) = match prefixed {
+        Some(prefixed) => prefixed,
+        None => match radixes.first()? {
+            Radix(10) => return Some(Literal::Decimal),
+            Radix(radix) => (*radix, unsigned),
         },
-        // All other types are not integers
-        _ => false,
+    };
+
+    // from_str_radix would also take a second sign, so the digits are checked here
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
     }
+    Some(Literal::Radix { negative, radix, digits })
 }
 
-#[wasm_bindgen]
-pub fn validate(input: JsValue) -> bool {
+// Strings in the configured radixes; decimal digits go through parse_integer_decimal
+fn parse_integer_text(text: &str, options: &ValidationOptions) -> Option<Number> {
+    let (negative, radix, digits) = match literal(text, &options.radixes.0)? {
+        Literal::Decimal => return parse_integer_decimal(text, options),
+        Literal::Radix { negative, radix, digits } => (negative, radix, digits),
+    };
+    let magnitude = i128::try_from(u128::from_str_radix(digits, radix).ok()?).ok()?;
+    let value = if negative { -magnitude } else { magnitude };
+    match i64::try_from(value) {
+        Ok(n) => Some(Number::from(n)),
+        Err(_) => u64::try_from(value).ok().map(Number::from),
+    }
+}
+
+// Decimal strings in lenient or coerce mode, also accepting the configured thousands separators
+fn parse_integer_decimal(text: &str, options: &ValidationOptions) -> Option<Number> {
+    let grouped = || ungroup_integer(text, &options.separators).and_then(|digits| parse_integer_str(&digits));
+    match options.mode {
+        Mode::Coerce => grouped().or_else(|| parse_integer_coercive(text)),
+        _ => parse_integer_str(text).or_else(grouped),
+    }
+}
+
+// Digits grouped in threes by one of the separators, with the separators removed: "1.000.000"
+// for [".", " "], but not "1.00.000", "1000.000" or a mix such as "1,000.000"
+fn ungroup_integer(text: &str, separators: &[Separator]) -> Option<String> {
+    let text = text.trim();
+    let (sign, unsigned) = match text.strip_prefix(['-', '+']) {
+        Some(unsigned) => (&text[..1], unsigned),
+        None => ("", text),
+    };
+    let separator = unsigned.chars().find(|c| !c.is_ascii_digit())?;
+    if !separators.contains(&Separator(separator)) {
+        return None;
+    }
+
+    let groups: Vec<&str> = unsigned.split(separator).collect();
+    let grouped = (1..=3).contains(&groups[0].len())
+        && groups[1..].iter().all(|group| group.len() == 3)
+        && groups.iter().all(|group| group.bytes().all(|b| b.is_ascii_digit()));
+
+    grouped.then(|| format!("{sign}{}", groups.concat()))
+}
+
+// The integer a value stands for in bigint mode, as a decimal string without leading zeros, or
+// None inside when a string has more digits than max_digits or the width allow.
+// Strings of digits may be up to max_digits long; anything else is read as in the other modes
+fn parse_bigint(value: &Value, options: &ValidationOptions) -> Option<Option<String>> {
+    let text = match (value, options.mode) {
+        (Value::String(text), Mode::Lenient | Mode::Coerce) => parse_bigint_text(text, options),
+        _ => None,
+    };
+    text.or_else(|| parse_integer(value, options).map(|n| Some(n.to_string())))
+}
+
+fn parse_bigint_text(text: &str, options: &ValidationOptions) -> Option<Option<String>> {
+    match literal(text, &options.radixes.0)? {
+        Literal::Radix { negative, radix, digits } => {
+            let digits = digits.trim_start_matches('0');
+            let limit = options.width.map_or(options.max_digits, |width| width.digits(radix).min(options.max_digits));
+            Some((digits.len() <= limit).then(|| signed_decimal(negative, &radix_to_decimal(digits, radix))))
+        }
+        Literal::Decimal => {
+            let grouped = ungroup_integer(text, &options.separators);
+            let coerced = (options.mode == Mode::Coerce).then(|| mode::strip_grouping(text)).flatten();
+            let parsed = [Some(text.trim().to_string()), grouped, coerced].into_iter().flatten().find_map(|candidate| {
+                let (negative, digits) = match candidate.strip_prefix('-') {
+                    Some(digits) => (true, digits),
+                    None => (false, candidate.strip_prefix('+').unwrap_or(&candidate)),
+                };
+                let decimal = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit());
+                decimal.then(|| signed_decimal(negative, digits))
+            });
+            parsed.map(|digits| Some(digits).filter(|digits| digits.trim_start_matches('-').len() <= options.max_digits))
+        }
+    }
+}
+
+// Digits with their leading zeros removed and the sign in front, with zero unsigned
+fn signed_decimal(negative: bool, digits: &str) -> String {
+    match digits.trim_start_matches('0') {
+        "" => "0".to_string(),
+        digits if negative => format!("-{digits}"),
+        digits => digits.to_string(),
+    }
+}
+
+// Checked digits in radix 2, 8 or 16 written in decimal, through base 10^9 limbs, least
+// significant first
+fn radix_to_decimal(digits: &str, radix: u32) -> String {
+    const LIMB: u64 = 1_000_000_000;
+
+    let mut limbs: Vec<u64> = vec![0];
+    for digit in digits.chars().filter_map(|c| c.to_digit(radix)) {
+        let mut carry = u64::from(digit);
+        for limb in limbs.iter_mut() {
+            let value = *limb * u64::from(radix) + carry;
+            *limb = value % LIMB;
+            carry = value / LIMB;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+
+    let mut decimal = limbs.last().map(u64::to_string).unwrap_or_default();
+    for limb in limbs.iter().rev().skip(1) {
+        decimal.push_str(&format!("{limb:09}"));
+    }
+    decimal
+}
+
+// Orders two decimal strings as signed_decimal writes them: by sign, then by length, then digit by digit
+fn compare_decimal(a: &str, b: &str) -> Ordering {
+    let magnitude = |a: &str, b: &str| a.len().cmp(&b.len()).then_with(|| a.cmp(b));
+    match (a.strip_prefix('-'), b.strip_prefix('-')) {
+        (Some(a), Some(b)) => magnitude(b, a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => magnitude(a, b),
+    }
+}
+
+// JS numbers are all f64; whole ones within the i64 or u64 range are integers
+fn parse_integer_f64(number: f64) -> Option<Number> {
+    const TWO_POW_63: f64 = 9_223_372_036_854_775_808.0;
+
+    if !number.is_finite() || number.fract() != 0.0 {
+        return None;
+    }
+    if (-TWO_POW_63..TWO_POW_63).contains(&number) {
+        Some(Number::from(number as i64))
+    } else if (0.0..2.0 * TWO_POW_63).contains(&number) {
+        Some(Number::from(number as u64))
+    } else {
+        None
+    }
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_integer(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_integer(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_integer(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_integer(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
     // Parse the input object
-    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input) {
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
         Ok(val) => val,
         Err(_) => return false,
     };
     
-    validate_integer(&input_obj.value)
+    validate_integer(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_integer(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_integer(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_integer(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_integer(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    parse_integer_str(value).is_some()
+}
+
+// Fast path for a JS number with the default options, as validate_str is for strings
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_f64(value: f64) -> bool {
+    parse_integer_f64(value).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)] // 3.14 and friends are test data, not stand-ins for PI
 mod tests {
     use super::*;
     use serde_json::json;
@@ -65,81 +664,420 @@ mod tests {
     #[test]
     fn test_valid_integers() {
         // Direct integer numbers
-        assert!(validate_integer(&json!(42)));
-        assert!(validate_integer(&json!(0)));
-        assert!(validate_integer(&json!(-42)));
-        assert!(validate_integer(&json!(1000000)));
-        assert!(validate_integer(&json!(-1000000)));
+        assert!(validate_integer(&json!(42), &ValidationOptions::default()));
+        assert!(validate_integer(&json!(0), &ValidationOptions::default()));
+        assert!(validate_integer(&json!(-42), &ValidationOptions::default()));
+        assert!(validate_integer(&json!(1000000), &ValidationOptions::default()));
+        assert!(validate_integer(&json!(-1000000), &ValidationOptions::default()));
         
         // Very large integers (within i64/u64 range)
-        assert!(validate_integer(&json!(9223372036854775807i64))); // i64::MAX
-        assert!(validate_integer(&json!(-9223372036854775808i64))); // i64::MIN
+        assert!(validate_integer(&json!(9223372036854775807i64), &ValidationOptions::default())); // i64::MAX
+        assert!(validate_integer(&json!(-9223372036854775808i64), &ValidationOptions::default())); // i64::MIN
         
         // String representations of integers
-        assert!(validate_integer(&json!("42")));
-        assert!(validate_integer(&json!("0")));
-        assert!(validate_integer(&json!("-42")));
-        assert!(validate_integer(&json!("1000000")));
-        assert!(validate_integer(&json!("-1000000")));
-        assert!(validate_integer(&json!("  42  "))); // With whitespace
-        assert!(validate_integer(&json!("\t-100\n"))); // With tabs and newlines
+        assert!(validate_integer(&json!("42"), &ValidationOptions::default()));
+        assert!(validate_integer(&json!("0"), &ValidationOptions::default()));
+        assert!(validate_integer(&json!("-42"), &ValidationOptions::default()));
+        assert!(validate_integer(&json!("1000000"), &ValidationOptions::default()));
+        assert!(validate_integer(&json!("-1000000"), &ValidationOptions::default()));
+        assert!(validate_integer(&json!("  42  "), &ValidationOptions::default())); // With whitespace
+        assert!(validate_integer(&json!("\t-100\n"), &ValidationOptions::default())); // With tabs and newlines
     }
 
     #[test]
     fn test_invalid_integers() {
         // Floating point numbers
-        assert!(!validate_integer(&json!(42.5)));
-        assert!(!validate_integer(&json!(0.1)));
-        assert!(!validate_integer(&json!(-3.14)));
-        assert!(!validate_integer(&json!(1.0e10))); // Scientific notation with decimal
+        assert!(!validate_integer(&json!(42.5), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!(0.1), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!(-3.14), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!(1.0e10), &ValidationOptions::default())); // Scientific notation with decimal
         
         // String representations of floats
-        assert!(!validate_integer(&json!("42.5")));
-        assert!(!validate_integer(&json!("0.1")));
-        assert!(!validate_integer(&json!("-3.14")));
-        assert!(!validate_integer(&json!("1.23e10")));
+        assert!(!validate_integer(&json!("42.5"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("0.1"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("-3.14"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("1.23e10"), &ValidationOptions::default()));
         
         // Invalid string formats
-        assert!(!validate_integer(&json!(""))); // Empty string
-        assert!(!validate_integer(&json!("   "))); // Only whitespace
-        assert!(!validate_integer(&json!("not a number")));
-        assert!(!validate_integer(&json!("12abc")));
-        assert!(!validate_integer(&json!("abc12")));
-        assert!(!validate_integer(&json!("12.34.56")));
-        assert!(!validate_integer(&json!("--42"))); // Double negative
-        assert!(!validate_integer(&json!("42-"))); // Trailing negative
+        assert!(!validate_integer(&json!(""), &ValidationOptions::default())); // Empty string
+        assert!(!validate_integer(&json!("   "), &ValidationOptions::default())); // Only whitespace
+        assert!(!validate_integer(&json!("not a number"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("12abc"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("abc12"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("12.34.56"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("--42"), &ValidationOptions::default())); // Double negative
+        assert!(!validate_integer(&json!("42-"), &ValidationOptions::default())); // Trailing negative
         
         // Other JSON types
-        assert!(!validate_integer(&json!(true)));
-        assert!(!validate_integer(&json!(false)));
-        assert!(!validate_integer(&json!(null)));
-        assert!(!validate_integer(&json!([])));
-        assert!(!validate_integer(&json!({})));
-        assert!(!validate_integer(&json!({"number": 42})));
+        assert!(!validate_integer(&json!(true), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!(false), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!(null), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!([]), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!({}), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!({"number": 42}), &ValidationOptions::default()));
     }
     
     #[test]
     fn test_edge_cases() {
         // Zero in various forms
-        assert!(validate_integer(&json!(0)));
-        assert!(validate_integer(&json!("0")));
-        assert!(validate_integer(&json!("-0")));
-        assert!(validate_integer(&json!("00"))); // Leading zeros
+        assert!(validate_integer(&json!(0), &ValidationOptions::default()));
+        assert!(validate_integer(&json!("0"), &ValidationOptions::default()));
+        assert!(validate_integer(&json!("-0"), &ValidationOptions::default()));
+        assert!(validate_integer(&json!("00"), &ValidationOptions::default())); // Leading zeros
         
         // Numbers that look like octals/hex but parsed as decimal
-        assert!(validate_integer(&json!("0123"))); // Parses as 123
+        assert!(validate_integer(&json!("0123"), &ValidationOptions::default())); // Parses as 123
         
         // Very large numbers as strings
-        assert!(validate_integer(&json!("9223372036854775807"))); // i64::MAX
-        assert!(validate_integer(&json!("-9223372036854775808"))); // i64::MIN
+        assert!(validate_integer(&json!("9223372036854775807"), &ValidationOptions::default())); // i64::MAX
+        assert!(validate_integer(&json!("-9223372036854775808"), &ValidationOptions::default())); // i64::MIN
         
         // Numbers with + sign are actually valid in string parsing
-        assert!(validate_integer(&json!("+42"))); // Plus sign is supported by Rust's parse
+        assert!(validate_integer(&json!("+42"), &ValidationOptions::default())); // Plus sign is supported by Rust's parse
         
         // Special float values as strings
-        assert!(!validate_integer(&json!("NaN")));
-        assert!(!validate_integer(&json!("Infinity")));
-        assert!(!validate_integer(&json!("-Infinity")));
-        assert!(!validate_integer(&json!("inf")));
+        assert!(!validate_integer(&json!("NaN"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("Infinity"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("-Infinity"), &ValidationOptions::default()));
+        assert!(!validate_integer(&json!("inf"), &ValidationOptions::default()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_options() {
+        // Options are optional and may be empty
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 42})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 42, "options": {}})).is_ok());
+
+        // Unknown options are rejected so typos never silently change behaviour
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": 42, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_range() {
+        let age: ValidationOptions = serde_json::from_value(json!({"min": 0, "max": 150})).unwrap();
+        assert_eq!(coerce_integer(&json!(0), &age), Some(json!(0)));
+        assert_eq!(coerce_integer(&json!(" 150 "), &age), Some(json!(150)));
+        assert_eq!(coerce_integer(&json!(151), &age), None);
+        assert_eq!(coerce_integer(&json!(-1), &age), None);
+        assert_eq!(coerce_integer(&json!(u64::MAX), &age), None);
+
+        let exclusive: ValidationOptions =
+            serde_json::from_value(json!({"min": 0, "max": 65536, "exclusive_min": true, "exclusive_max": true})).unwrap();
+        assert_eq!(coerce_integer(&json!(1), &exclusive), Some(json!(1)));
+        assert_eq!(coerce_integer(&json!(65535), &exclusive), Some(json!(65535)));
+        assert_eq!(coerce_integer(&json!(0), &exclusive), None);
+        assert_eq!(coerce_integer(&json!(65536), &exclusive), None);
+
+        // Either bound may be left open, and values beyond i64 are compared exactly
+        let positive: ValidationOptions = serde_json::from_value(json!({"min": 1})).unwrap();
+        assert_eq!(coerce_integer(&json!(u64::MAX), &positive), Some(json!(u64::MAX)));
+        let capped: ValidationOptions = serde_json::from_value(json!({"max": i64::MAX})).unwrap();
+        assert_eq!(coerce_integer(&json!(i64::MAX as u64 + 1), &capped), None);
+
+        // Only integers that are out of range are reported as such
+        assert_eq!(detail_integer(&json!(200), &age).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_integer(&json!("200"), &age).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_integer(&json!("abc"), &age).error, None);
+        assert_eq!(detail_integer(&json!(42), &age).error, None);
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"min": 1.5})).is_err());
+    }
+
+    #[test]
+    fn test_radixes() {
+        let literals: ValidationOptions = serde_json::from_value(json!({"radixes": [10, 16, 8, 2]})).unwrap();
+        assert_eq!(coerce_integer(&json!("0xFF"), &literals), Some(json!(255)));
+        assert_eq!(coerce_integer(&json!(" 0o755 "), &literals), Some(json!(493)));
+        assert_eq!(coerce_integer(&json!("0B1010"), &literals), Some(json!(10)));
+        assert_eq!(coerce_integer(&json!("-0x10"), &literals), Some(json!(-16)));
+        assert_eq!(coerce_integer(&json!("42"), &literals), Some(json!(42)));
+        assert_eq!(coerce_integer(&json!("0xFFFFFFFFFFFFFFFF"), &literals), Some(json!(u64::MAX)));
+        assert_eq!(coerce_integer(&json!("0x10000000000000000"), &literals), None);
+        assert_eq!(coerce_integer(&json!("0x"), &literals), None);
+        assert_eq!(coerce_integer(&json!("0xG"), &literals), None);
+        assert_eq!(coerce_integer(&json!("0o8"), &literals), None);
+        assert_eq!(coerce_integer(&json!("0x-1"), &literals), None);
+
+        // Prefixes are only read for the listed radixes, and the default is decimal alone
+        let hex_literals: ValidationOptions = serde_json::from_value(json!({"radixes": [10, 16]})).unwrap();
+        assert_eq!(coerce_integer(&json!("0x1f"), &hex_literals), Some(json!(31)));
+        assert_eq!(coerce_integer(&json!("0b1010"), &hex_literals), None);
+        assert_eq!(coerce_integer(&json!("0xFF"), &ValidationOptions::default()), None);
+
+        // Bare digits are read in the first radix
+        let hex: ValidationOptions = serde_json::from_value(json!({"radixes": [16]})).unwrap();
+        assert_eq!(coerce_integer(&json!("ff"), &hex), Some(json!(255)));
+        assert_eq!(coerce_integer(&json!("0xff"), &hex), Some(json!(255)));
+        assert_eq!(coerce_integer(&json!("10"), &hex), Some(json!(16)));
+        assert_eq!(coerce_integer(&json!(10), &hex), Some(json!(10)));
+
+        // Strict mode still takes only numbers, and the range applies to the decimal value
+        let strict: ValidationOptions = serde_json::from_value(json!({"mode": "strict", "radixes": [16]})).unwrap();
+        assert_eq!(coerce_integer(&json!("ff"), &strict), None);
+        let byte: ValidationOptions = serde_json::from_value(json!({"radixes": [10, 16], "max": 255})).unwrap();
+        assert_eq!(coerce_integer(&json!("0xFF"), &byte), Some(json!(255)));
+        assert_eq!(detail_integer(&json!("0x100"), &byte).error, Some(OUT_OF_RANGE));
+
+        // Decimal digits keep coercive parsing
+        let coerce: ValidationOptions = serde_json::from_value(json!({"mode": "coerce", "radixes": [10, 16]})).unwrap();
+        assert_eq!(coerce_integer(&json!("1,234"), &coerce), Some(json!(1234)));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"radixes": [3]})).is_err());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"radixes": ["hex"]})).is_err());
+        // An empty list would reject every string, so it's invalid options instead
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"radixes": []})).is_err());
+        assert_eq!(coerce_value(&json!("42"), &json!({"radixes": []})), None);
+        assert!(detail_value(&json!("42"), &json!({"radixes": []})).is_none());
+    }
+
+    #[test]
+    fn test_separators() {
+        let grouped: ValidationOptions = serde_json::from_value(json!({"separators": [",", "_"]})).unwrap();
+        assert_eq!(coerce_integer(&json!("1,000,000"), &grouped), Some(json!(1000000)));
+        assert_eq!(coerce_integer(&json!(" -1_000_000 "), &grouped), Some(json!(-1000000)));
+        assert_eq!(coerce_integer(&json!("999"), &grouped), Some(json!(999)));
+        assert_eq!(coerce_integer(&json!("1,00,000"), &grouped), None);
+        assert_eq!(coerce_integer(&json!("1000,000"), &grouped), None);
+        assert_eq!(coerce_integer(&json!(",100"), &grouped), None);
+        assert_eq!(coerce_integer(&json!("1,000,"), &grouped), None);
+        assert_eq!(coerce_integer(&json!("1,000_000"), &grouped), None);
+        assert_eq!(coerce_integer(&json!("1.000.000"), &grouped), None);
+
+        // European exports group with periods or spaces
+        let european: ValidationOptions = serde_json::from_value(json!({"separators": [".", " ", "\u{a0}"]})).unwrap();
+        assert_eq!(coerce_integer(&json!("1.000.000"), &european), Some(json!(1000000)));
+        assert_eq!(coerce_integer(&json!("1 000 000"), &european), Some(json!(1000000)));
+        assert_eq!(coerce_integer(&json!("1\u{a0}000"), &european), Some(json!(1000)));
+        assert_eq!(coerce_integer(&json!("1.5"), &european), None);
+        assert_eq!(coerce_integer(&json!("1,000"), &european), None);
+
+        // Without the option lenient mode takes plain digits only, and ranges see the grouped value
+        assert_eq!(coerce_integer(&json!("1,000"), &ValidationOptions::default()), None);
+        let capped: ValidationOptions = serde_json::from_value(json!({"separators": ["'"], "max": 1000})).unwrap();
+        assert_eq!(coerce_integer(&json!("1'000"), &capped), Some(json!(1000)));
+        assert_eq!(detail_integer(&json!("1'001"), &capped).error, Some(OUT_OF_RANGE));
+
+        // In coerce mode the configured separators come first, so "1.000" is a thousand rather than one
+        let coerce: ValidationOptions = serde_json::from_value(json!({"mode": "coerce", "separators": ["."]})).unwrap();
+        assert_eq!(coerce_integer(&json!("1.000"), &coerce), Some(json!(1000)));
+        assert_eq!(coerce_integer(&json!("1,234"), &coerce), Some(json!(1234)));
+        assert_eq!(coerce_integer(&json!("42.0"), &coerce), Some(json!(42)));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"separators": ["-"]})).is_err());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"separators": [",,"]})).is_err());
+    }
+
+    #[test]
+    fn test_bigint() {
+        let bigint: ValidationOptions = serde_json::from_value(json!({"bigint": true})).unwrap();
+        let u128_max = "340282366920938463463374607431768211455";
+
+        assert_eq!(coerce_integer(&json!(u128_max), &bigint), Some(json!(u128_max)));
+        assert_eq!(coerce_integer(&json!(" -000123456789012345678901234567890 "), &bigint), Some(json!("-123456789012345678901234567890")));
+        assert_eq!(coerce_integer(&json!("+0"), &bigint), Some(json!("0")));
+        assert_eq!(coerce_integer(&json!("-0"), &bigint), Some(json!("0")));
+        // Every value comes back as a string, small ones and JSON numbers included
+        assert_eq!(coerce_integer(&json!("42"), &bigint), Some(json!("42")));
+        assert_eq!(coerce_integer(&json!(u64::MAX), &bigint), Some(json!("18446744073709551615")));
+        assert_eq!(coerce_integer(&json!(-7), &bigint), Some(json!("-7")));
+
+        assert_eq!(coerce_integer(&json!("12345678901234567890123.5"), &bigint), None);
+        assert_eq!(coerce_integer(&json!("1234567890123456789012a"), &bigint), None);
+        assert_eq!(coerce_integer(&json!("--1"), &bigint), None);
+        assert_eq!(coerce_integer(&json!("-"), &bigint), None);
+        assert_eq!(coerce_integer(&json!(""), &bigint), None);
+        assert_eq!(coerce_integer(&json!(1.5), &bigint), None);
+        // Without bigint, values beyond u64 are still rejected
+        assert_eq!(coerce_integer(&json!(u128_max), &ValidationOptions::default()), None);
+
+        // The other options apply as in the other modes
+        let options = |extra: Value| -> ValidationOptions {
+            let mut options = json!({"bigint": true});
+            options.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(options).unwrap()
+        };
+        let hex = options(json!({"radixes": [10, 16]}));
+        assert_eq!(coerce_integer(&json!("0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF"), &hex), Some(json!(u128_max)));
+        assert_eq!(coerce_integer(&json!("-0x10000000000000000"), &hex), Some(json!("-18446744073709551616")));
+        assert_eq!(coerce_integer(&json!("0x0"), &hex), Some(json!("0")));
+        let grouped = options(json!({"separators": [","]}));
+        assert_eq!(coerce_integer(&json!("1,000,000,000,000,000,000,000"), &grouped), Some(json!("1000000000000000000000")));
+        let coerce = options(json!({"mode": "coerce"}));
+        assert_eq!(coerce_integer(&json!("99_999_999_999_999_999_999"), &coerce), Some(json!("99999999999999999999")));
+        assert_eq!(coerce_integer(&json!("1e3"), &coerce), Some(json!("1000")));
+        let strict = options(json!({"mode": "strict"}));
+        assert_eq!(coerce_integer(&json!(u128_max), &strict), None);
+        assert_eq!(coerce_integer(&json!(42), &strict), Some(json!("42")));
+
+        // Bounds are compared exactly
+        let positive = options(json!({"min": 0, "exclusive_min": true}));
+        assert_eq!(coerce_integer(&json!(u128_max), &positive), Some(json!(u128_max)));
+        assert_eq!(detail_integer(&json!("-99999999999999999999"), &positive).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_integer(&json!("0"), &positive).error, Some(OUT_OF_RANGE));
+        let capped = options(json!({"max": i64::MAX}));
+        assert_eq!(coerce_integer(&json!("9223372036854775807"), &capped), Some(json!("9223372036854775807")));
+        assert_eq!(detail_integer(&json!("9223372036854775808"), &capped).error, Some(OUT_OF_RANGE));
+        let negative = options(json!({"min": -10, "max": -5}));
+        assert_eq!(coerce_integer(&json!("-10"), &negative), Some(json!("-10")));
+        assert_eq!(coerce_integer(&json!("-11"), &negative), None);
+        assert_eq!(coerce_integer(&json!("-4"), &negative), None);
+
+        // Long strings are out of range before they're converted, so they can't take long
+        let hex = options(json!({"radixes": [16]}));
+        let long = format!("0x{}", "F".repeat(100_000));
+        let started = std::time::Instant::now();
+        assert_eq!(detail_integer(&json!(long), &hex).error, Some(OUT_OF_RANGE));
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        assert!(coerce_integer(&json!("F".repeat(DEFAULT_MAX_DIGITS)), &hex).is_some());
+        assert_eq!(coerce_integer(&json!(format!("{}FF", "0".repeat(100_000))), &hex), Some(json!("255")));
+        let short = options(json!({"max_digits": 5}));
+        assert_eq!(coerce_integer(&json!("-00099999"), &short), Some(json!("-99999")));
+        assert_eq!(detail_integer(&json!("100000"), &short).error, Some(OUT_OF_RANGE));
+
+        // With a width, a literal with more digits than the type holds isn't converted either
+        let u128_hex = options(json!({"radixes": [16], "width": "u128"}));
+        assert_eq!(coerce_integer(&json!(format!("0x{}{}", "0".repeat(100_000), "F".repeat(32))), &u128_hex), Some(json!(u128_max)));
+        assert_eq!(detail_integer(&json!(format!("0x1{}", "0".repeat(32))), &u128_hex).error, Some(OUT_OF_RANGE));
+        let i8_binary = options(json!({"radixes": [2], "width": "i8"}));
+        assert_eq!(coerce_integer(&json!("-10000000"), &i8_binary), Some(json!("-128")));
+        assert_eq!(detail_integer(&json!("10000000"), &i8_binary).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_integer(&json!("100000000"), &i8_binary).error, Some(OUT_OF_RANGE));
+    }
+
+    #[test]
+    fn test_width() {
+        let width = |width: &str| -> ValidationOptions { serde_json::from_value(json!({ "width": width })).unwrap() };
+
+        assert_eq!(coerce_integer(&json!(255), &width("u8")), Some(json!(255)));
+        assert_eq!(coerce_integer(&json!("0"), &width("u8")), Some(json!(0)));
+        assert_eq!(detail_integer(&json!(256), &width("u8")).error, Some(OUT_OF_RANGE));
+        assert_eq!(detail_integer(&json!(-1), &width("u8")).error, Some(OUT_OF_RANGE));
+        assert_eq!(coerce_integer(&json!(-128), &width("i8")), Some(json!(-128)));
+        assert_eq!(detail_integer(&json!(128), &width("i8")).error, Some(OUT_OF_RANGE));
+        assert_eq!(coerce_integer(&json!(-32768), &width("i16")), Some(json!(-32768)));
+        assert_eq!(detail_integer(&json!(65536), &width("u16")).error, Some(OUT_OF_RANGE));
+        assert_eq!(coerce_integer(&json!(i32::MIN), &width("i32")), Some(json!(i32::MIN)));
+        assert_eq!(detail_integer(&json!(i64::from(i32::MAX) + 1), &width("i32")).error, Some(OUT_OF_RANGE));
+        assert_eq!(coerce_integer(&json!(u32::MAX), &width("u32")), Some(json!(u32::MAX)));
+        assert_eq!(detail_integer(&json!(u64::MAX), &width("i64")).error, Some(OUT_OF_RANGE));
+        assert_eq!(coerce_integer(&json!(u64::MAX), &width("u64")), Some(json!(u64::MAX)));
+        assert_eq!(detail_integer(&json!(i64::MIN), &width("u64")).error, Some(OUT_OF_RANGE));
+
+        // Values that aren't integers are invalid rather than out of range
+        assert_eq!(detail_integer(&json!("abc"), &width("u8")).error, None);
+
+        // The width and min and max must all hold
+        let port: ValidationOptions = serde_json::from_value(json!({"width": "u16", "min": 1})).unwrap();
+        assert_eq!(coerce_integer(&json!(65535), &port), Some(json!(65535)));
+        assert_eq!(coerce_integer(&json!(0), &port), None);
+        assert_eq!(coerce_integer(&json!(65536), &port), None);
+
+        // 128-bit types take their full range in bigint mode
+        let u128_bigint: ValidationOptions = serde_json::from_value(json!({"width": "u128", "bigint": true})).unwrap();
+        assert_eq!(coerce_integer(&json!(u128::MAX.to_string()), &u128_bigint), Some(json!(u128::MAX.to_string())));
+        assert_eq!(coerce_integer(&json!("340282366920938463463374607431768211456"), &u128_bigint), None);
+        assert_eq!(coerce_integer(&json!("-1"), &u128_bigint), None);
+        let i128_bigint: ValidationOptions = serde_json::from_value(json!({"width": "i128", "bigint": true})).unwrap();
+        assert_eq!(coerce_integer(&json!(i128::MIN.to_string()), &i128_bigint), Some(json!(i128::MIN.to_string())));
+        assert_eq!(coerce_integer(&json!(i128::MAX.to_string()), &i128_bigint), Some(json!(i128::MAX.to_string())));
+        assert_eq!(coerce_integer(&json!((i128::MAX as u128 + 1).to_string()), &i128_bigint), None);
+        let i8_bigint: ValidationOptions = serde_json::from_value(json!({"width": "i8", "bigint": true})).unwrap();
+        assert_eq!(coerce_integer(&json!("-128"), &i8_bigint), Some(json!("-128")));
+        assert_eq!(detail_integer(&json!("99999999999999999999999"), &i8_bigint).error, Some(OUT_OF_RANGE));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"width": "u7"})).is_err());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"width": "U8"})).is_err());
+    }
+
+    #[test]
+    fn test_modes() {
+        let strict: ValidationOptions = serde_json::from_value(json!({"mode": "strict"})).unwrap();
+        let coerce: ValidationOptions = serde_json::from_value(json!({"mode": "coerce"})).unwrap();
+
+        assert_eq!(coerce_integer(&json!(42), &strict), Some(json!(42)));
+        assert_eq!(coerce_integer(&json!("42"), &strict), None);
+        assert_eq!(coerce_integer(&json!(42.0), &strict), None);
+
+        assert_eq!(coerce_integer(&json!(42.0), &coerce), Some(json!(42)));
+        assert_eq!(coerce_integer(&json!(" 1,234,567 "), &coerce), Some(json!(1234567)));
+        assert_eq!(coerce_integer(&json!("-1_000"), &coerce), Some(json!(-1000)));
+        assert_eq!(coerce_integer(&json!("42.0"), &coerce), Some(json!(42)));
+        assert_eq!(coerce_integer(&json!("1e3"), &coerce), Some(json!(1000)));
+        assert_eq!(coerce_integer(&json!("18446744073709551615"), &coerce), Some(json!(u64::MAX)));
+
+        // Fractions are never rounded, and separators must group by thousands
+        assert_eq!(coerce_integer(&json!(42.5), &coerce), None);
+        assert_eq!(coerce_integer(&json!("42.5"), &coerce), None);
+        assert_eq!(coerce_integer(&json!("1,5"), &coerce), None);
+        assert_eq!(coerce_integer(&json!("inf"), &coerce), None);
+        assert_eq!(coerce_integer(&json!(true), &coerce), None);
+    }
+
+    #[test]
+    fn test_coerce() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(coerce_integer(&json!(42), &options), Some(json!(42)));
+        assert_eq!(coerce_integer(&json!(" 42 "), &options), Some(json!(42)));
+        assert_eq!(coerce_integer(&json!("+7"), &options), Some(json!(7)));
+        assert_eq!(coerce_integer(&json!("-0"), &options), Some(json!(0)));
+        assert_eq!(coerce_integer(&json!("0123"), &options), Some(json!(123)));
+        assert_eq!(coerce_integer(&json!("18446744073709551615"), &options), Some(json!(u64::MAX)));
+
+        assert_eq!(coerce_integer(&json!(42.5), &options), None);
+        assert_eq!(coerce_integer(&json!("4 2"), &options), None);
+        assert_eq!(coerce_integer(&json!(true), &options), None);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(
+            detail_integer(&json!(" 42 "), &options),
+            ValidationResult { valid: true, value: json!(42), error: None }
+        );
+        assert_eq!(
+            detail_integer(&json!("4.2"), &options),
+            ValidationResult { valid: false, value: Value::Null, error: None }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [1, "x", "2"]})).unwrap();
+        assert_eq!(batch.values.len(), 3);
+        assert!(serde_json::from_value::<BatchInput>(json!({"values": [], "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["42", " -7 ", "18446744073709551615", "1.5", "abc"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+        // JS has only f64 numbers, so whole ones count as integers
+        assert!(validate_f64(42.0));
+        assert!(validate_f64(-7.0));
+        assert!(validate_f64(1e19));
+        assert!(!validate_f64(1.5));
+        assert!(!validate_f64(1e20));
+        assert!(!validate_f64(f64::NAN));
+        assert!(!validate_f64(f64::INFINITY));
+    }
+}
@@ -0,0 +1,55 @@
+// Types for the integer validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** How far to go to read a value: "strict" takes only JSON integers, "lenient" also integers written as strings, and "coerce" also whole floats (42.0, "1e3") and thousands separators ("1,234") (default: "lenient") */
+    mode?: "strict" | "lenient" | "coerce";
+    /** Lowest accepted value, itself included unless exclusive_min is set */
+    min?: number;
+    /** Highest accepted value, itself included unless exclusive_max is set */
+    max?: number;
+    /** Reject min itself (default: false) */
+    exclusive_min?: boolean;
+    /** Reject max itself (default: false) */
+    exclusive_max?: boolean;
+    /** Radixes strings may be written in; bare digits use the first, and 0x, 0o and 0b literals are accepted when 16, 8 or 2 is listed (default: [10]) */
+    radixes?: (2 | 8 | 10 | 16)[];
+    /** Thousands separators accepted in lenient and coerce modes; digits must be grouped in threes (default: []) */
+    separators?: ("," | "." | "_" | "'" | " " | "\u00a0" | "\u202f")[];
+    /** Accept integers of any size, such as 128-bit IDs, and return every value as a decimal string so that no precision is lost (default: false) */
+    bigint?: boolean;
+    /** Most digits, leading zeros aside, a string may be written with in bigint mode; longer strings are out of range rather than converted (default: 4096) */
+    max_digits?: number;
+    /** Fixed-width integer type the value must fit, such as "u8" or "i32", checked along with min and max; values beyond u64 need bigint (default: null) */
+    width?: "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "i128" | "u128" | null;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = number | string;
+
+/** The canonical form of a valid value, as coerce returns it: a number, or a decimal string when the bigint option is set */
+export type CanonicalValue = number | string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "out_of_range" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
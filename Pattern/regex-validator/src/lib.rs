@@ -0,0 +1,378 @@
+// ABOUTME: WASM component for regular expression validation
+// ABOUTME: Provides validate and coerce functions matching values against a caller-supplied pattern, caching compiled patterns
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use regex::{Regex, RegexBuilder};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The regular expression, in Rust regex syntax
+    pattern: Option<String>,
+    // Any of "i" (case-insensitive), "m" (multi-line), "s" (dot matches newline) and "x" (verbose)
+    flags: String,
+    // Accept a match anywhere in the value instead of requiring the whole value to match
+    partial_match: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// Compiled patterns are kept between calls, keyed by anchoring, flags and pattern text
+const CACHE_CAPACITY: usize = 64;
+
+// Bounds compilation of hostile patterns; matching itself is always linear time
+const COMPILED_SIZE_LIMIT: usize = 1 << 20;
+
+thread_local! {
+    static CACHE: RefCell<HashMap<(bool, String, String), Regex>> = RefCell::new(HashMap::new());
+}
+
+fn compile(pattern: &str, flags: &str, partial_match: bool) -> Option<Regex> {
+    // Anchoring wraps the pattern in a group so alternations cannot escape it; in verbose
+    // mode the group is closed on a new line so a trailing comment cannot swallow it
+    let source = match (partial_match, flags.contains('x')) {
+        (true, _) => pattern.to_string(),
+        (false, false) => format!("^(?:{})$", pattern),
+        (false, true) => format!("^(?:{}\n)$", pattern),
+    };
+
+    let mut builder = RegexBuilder::new(&source);
+    builder.size_limit(COMPILED_SIZE_LIMIT);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            _ => return None,
+        };
+    }
+    builder.build().ok()
+}
+
+// Returns the compiled pattern, compiling and caching it on first use
+fn cached_regex(pattern: &str, flags: &str, partial_match: bool) -> Option<Regex> {
+    let key = (partial_match, flags.to_string(), pattern.to_string());
+    if let Some(regex) = CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+        return Some(regex);
+    }
+
+    let regex = compile(pattern, flags, partial_match)?;
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        // Patterns rarely vary much, so a full cache is simply emptied rather than tracking recency
+        if cache.len() >= CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(key, regex.clone());
+    });
+    Some(regex)
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the value unchanged when it matches; a missing or malformed pattern matches nothing
+fn coerce_pattern(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let text = value.as_str()?;
+    let regex = cached_regex(options.pattern.as_deref()?, &options.flags, options.partial_match)?;
+    regex.is_match(text).then(|| value.clone())
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_pattern(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_pattern(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_pattern(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_pattern(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_pattern(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_pattern(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_pattern(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_pattern(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_pattern(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pattern(options: Value) -> ValidationOptions {
+        serde_json::from_value(options).unwrap()
+    }
+
+    #[test]
+    fn test_full_match() {
+        let postcode = pattern(json!({"pattern": "[A-Z]{1,2}[0-9][A-Z0-9]? [0-9][A-Z]{2}"}));
+
+        assert!(validate_pattern(&json!("SW1A 1AA"), &postcode));
+        assert!(validate_pattern(&json!("M1 1AE"), &postcode));
+        assert!(!validate_pattern(&json!("Flat 2, SW1A 1AA"), &postcode)); // Whole value must match
+        assert!(!validate_pattern(&json!("sw1a 1aa"), &postcode));
+        assert!(!validate_pattern(&json!(42), &postcode));
+
+        // Alternations stay inside the anchors
+        let choice = pattern(json!({"pattern": "cat|dog"}));
+        assert!(validate_pattern(&json!("dog"), &choice));
+        assert!(!validate_pattern(&json!("catapult"), &choice));
+    }
+
+    #[test]
+    fn test_partial_match() {
+        let digits = pattern(json!({"pattern": "[0-9]+", "partial_match": true}));
+
+        assert!(validate_pattern(&json!("order 66"), &digits));
+        assert!(!validate_pattern(&json!("no digits"), &digits));
+    }
+
+    #[test]
+    fn test_flags() {
+        assert!(validate_pattern(&json!("HELLO"), &pattern(json!({"pattern": "hello", "flags": "i"}))));
+        assert!(validate_pattern(&json!("a\nb"), &pattern(json!({"pattern": "a.b", "flags": "s"}))));
+        assert!(!validate_pattern(&json!("a\nb"), &pattern(json!({"pattern": "a.b"}))));
+        assert!(validate_pattern(&json!("one\ntwo"), &pattern(json!({"pattern": "^two$", "flags": "m", "partial_match": true}))));
+        assert!(validate_pattern(&json!("abc"), &pattern(json!({"pattern": "a b c  # spaced out", "flags": "x"}))));
+
+        assert!(!validate_pattern(&json!("hello"), &pattern(json!({"pattern": "hello", "flags": "g"}))));
+    }
+
+    #[test]
+    fn test_bad_patterns() {
+        assert!(!validate_pattern(&json!("anything"), &ValidationOptions::default()));
+        assert!(!validate_pattern(&json!("(("), &pattern(json!({"pattern": "(("}))));
+        assert!(!validate_pattern(&json!("aaaa"), &pattern(json!({"pattern": "(a)\\1"})))); // No backreferences
+        assert!(!validate_pattern(&json!("a"), &pattern(json!({"pattern": "a{100000}{100000}"})))); // Too large
+    }
+
+    #[test]
+    fn test_cache() {
+        let options = pattern(json!({"pattern": "x+y"}));
+        assert!(validate_pattern(&json!("xxy"), &options));
+        assert!(validate_pattern(&json!("xy"), &options));
+
+        let key = (false, String::new(), "x+y".to_string());
+        assert!(CACHE.with(|cache| cache.borrow().contains_key(&key)));
+
+        // The same pattern text with different flags is a separate entry
+        assert!(validate_pattern(&json!("XY"), &pattern(json!({"pattern": "x+y", "flags": "i"}))));
+        assert!(!validate_pattern(&json!("XY"), &options));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let options = pattern(json!({"pattern": "[a-z]+"}));
+
+        assert_eq!(coerce_pattern(&json!("abc"), &options), Some(json!("abc")));
+        assert_eq!(coerce_pattern(&json!("ABC"), &options), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a", "options": {"pattern": "a", "flags": "i"}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a", "options": {"regex": "a"}})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let options = pattern(json!({"pattern": "[0-9]{3}"}));
+
+        assert_eq!(
+            ValidationResult::from(coerce_pattern(&json!("123"), &options)),
+            ValidationResult { valid: true, value: json!("123") }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_pattern(&json!("1234"), &options)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+}
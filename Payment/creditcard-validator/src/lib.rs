@@ -0,0 +1,429 @@
+// ABOUTME: WASM component for payment card number validation
+// ABOUTME: Provides validate and coerce functions applying the Luhn checksum and per-brand length rules
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Only accept cards of these brands, e.g. ["visa", "mastercard"]
+    brands: Option<Vec<Brand>>,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Brand {
+    Visa,
+    Mastercard,
+    Amex,
+    Discover,
+    Diners,
+    Jcb,
+    Unionpay,
+    Maestro,
+}
+
+struct BrandRule {
+    brand: Brand,
+    // Inclusive ranges of the leading digits, compared at the width of the range bounds
+    prefixes: &'static [(u32, u32)],
+    lengths: &'static [usize],
+}
+
+// Checked in order, so the narrower ranges come before brands that overlap them
+const BRAND_RULES: &[BrandRule] = &[
+    BrandRule { brand: Brand::Amex, prefixes: &[(34, 34), (37, 37)], lengths: &[15] },
+    BrandRule { brand: Brand::Diners, prefixes: &[(300, 305), (36, 36), (38, 39)], lengths: &[14, 15, 16, 17, 18, 19] },
+    BrandRule { brand: Brand::Jcb, prefixes: &[(3528, 3589)], lengths: &[16, 17, 18, 19] },
+    BrandRule { brand: Brand::Visa, prefixes: &[(4, 4)], lengths: &[13, 16, 19] },
+    BrandRule { brand: Brand::Mastercard, prefixes: &[(51, 55), (2221, 2720)], lengths: &[16] },
+    BrandRule {
+        brand: Brand::Discover,
+        prefixes: &[(6011, 6011), (644, 649), (65, 65), (622126, 622925)],
+        lengths: &[16, 17, 18, 19],
+    },
+    BrandRule { brand: Brand::Unionpay, prefixes: &[(62, 62), (81, 81)], lengths: &[16, 17, 18, 19] },
+    BrandRule { brand: Brand::Maestro, prefixes: &[(50, 50), (56, 58), (6, 6)], lengths: &[12, 13, 14, 15, 16, 17, 18, 19] },
+];
+
+// Digits of the number, allowing spaces and hyphens between groups
+fn strip_separators(text: &str) -> Option<String> {
+    let digits: String = text.chars().filter(|c| *c != ' ' && *c != '-').collect();
+    (!digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())).then_some(digits)
+}
+
+fn luhn_valid(digits: &str) -> bool {
+    // Double every second digit from the right, subtracting 9 when that exceeds 9
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| {
+            let digit = u32::from(b - b'0');
+            match i % 2 {
+                1 if digit > 4 => digit * 2 - 9,
+                1 => digit * 2,
+                _ => digit,
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn has_prefix(digits: &str, (low, high): (u32, u32)) -> bool {
+    let width = low.to_string().len();
+    digits
+        .get(..width)
+        .and_then(|prefix| prefix.parse::<u32>().ok())
+        .is_some_and(|prefix| (low..=high).contains(&prefix))
+}
+
+// The brand whose number ranges and lengths fit these digits
+fn detect_brand(digits: &str) -> Option<Brand> {
+    BRAND_RULES
+        .iter()
+        .find(|rule| rule.prefixes.iter().any(|&range| has_prefix(digits, range)) && rule.lengths.contains(&digits.len()))
+        .map(|rule| rule.brand)
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns { number, brand } with the separators removed
+fn coerce_card_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let digits = strip_separators(text.trim())?;
+    if !luhn_valid(&digits) {
+        return None;
+    }
+
+    let brand = detect_brand(&digits)?;
+    if options.brands.as_ref().is_some_and(|allowed| !allowed.contains(&brand)) {
+        return None;
+    }
+
+    Some(serde_json::json!({ "number": digits, "brand": brand }))
+}
+
+// Only strings can be valid; the fast path calls coerce_card_str directly
+fn coerce_card(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_card_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_card(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_card(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_card(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_card(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_card(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_card(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_card(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_card(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_card(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_card_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn brands(list: Value) -> ValidationOptions {
+        serde_json::from_value(json!({ "brands": list })).unwrap()
+    }
+
+    #[test]
+    fn test_luhn() {
+        assert!(luhn_valid("4111111111111111"));
+        assert!(luhn_valid("79927398713"));
+        assert!(!luhn_valid("4111111111111112"));
+        assert!(!luhn_valid("79927398710"));
+    }
+
+    #[test]
+    fn test_brands() {
+        assert_eq!(detect_brand("4111111111111111"), Some(Brand::Visa));
+        assert_eq!(detect_brand("4222222222222"), Some(Brand::Visa));
+        assert_eq!(detect_brand("5555555555554444"), Some(Brand::Mastercard));
+        assert_eq!(detect_brand("2223003122003222"), Some(Brand::Mastercard));
+        assert_eq!(detect_brand("378282246310005"), Some(Brand::Amex));
+        assert_eq!(detect_brand("6011111111111117"), Some(Brand::Discover));
+        assert_eq!(detect_brand("36227206271667"), Some(Brand::Diners));
+        assert_eq!(detect_brand("3530111333300000"), Some(Brand::Jcb));
+        assert_eq!(detect_brand("6200000000000005"), Some(Brand::Unionpay));
+        assert_eq!(detect_brand("6759649826438453"), Some(Brand::Maestro));
+
+        assert_eq!(detect_brand("37828224631000"), None); // Amex is always 15 digits
+        assert_eq!(detect_brand("9111111111111111"), None);
+    }
+
+    #[test]
+    fn test_valid_numbers() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_card(&json!("4111111111111111"), &defaults));
+        assert!(validate_card(&json!("4111 1111 1111 1111"), &defaults));
+        assert!(validate_card(&json!("3782-822463-10005"), &defaults));
+        assert!(validate_card(&json!(" 5555 5555 5555 4444 "), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_numbers() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_card(&json!("4111111111111112"), &defaults)); // Bad checksum
+        assert!(!validate_card(&json!("411111111111111"), &defaults)); // Wrong length for Visa
+        assert!(!validate_card(&json!("4111.1111.1111.1111"), &defaults));
+        assert!(!validate_card(&json!("4111 1111 1111 111a"), &defaults));
+        assert!(!validate_card(&json!(""), &defaults));
+        assert!(!validate_card(&json!("0000000000000000"), &defaults)); // Passes Luhn, no brand
+        assert!(!validate_card(&json!(4111111111111111u64), &defaults));
+    }
+
+    #[test]
+    fn test_brand_allow_list() {
+        let visa_only = brands(json!(["visa"]));
+
+        assert!(validate_card(&json!("4111111111111111"), &visa_only));
+        assert!(!validate_card(&json!("5555555555554444"), &visa_only));
+        assert!(validate_card(&json!("5555555555554444"), &brands(json!(["visa", "mastercard"]))));
+        assert!(!validate_card(&json!("4111111111111111"), &brands(json!([]))));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"brands": ["laser"]})).is_err());
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            coerce_card(&json!("3782 822463 10005"), &defaults),
+            Some(json!({"number": "378282246310005", "brand": "amex"}))
+        );
+        assert_eq!(coerce_card(&json!("4111111111111112"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "4111111111111111"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "4111111111111111", "options": {"brands": ["visa"]}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "4111111111111111", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_card(&json!("4111-1111-1111-1111"), &defaults)),
+            ValidationResult { valid: true, value: json!({"number": "4111111111111111", "brand": "visa"}) }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_card(&json!("1234"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["4111111111111111", "4111 1111 1111 1112", "card"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,385 @@
+// ABOUTME: WASM component for payment card expiry date validation
+// ABOUTME: Provides validate and coerce functions for MM/YY and MM/YYYY expiry dates, optionally checked against a supplied date
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Reject cards that have expired by this date: "YYYY-MM", "YYYY-MM-DD" or an RFC 3339 timestamp.
+    // WASM has no clock of its own, so the caller supplies "now"
+    valid_at: Option<YearMonth>,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// A calendar month; ordering compares the year first
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct YearMonth {
+    year: u32,
+    month: u32,
+}
+
+impl TryFrom<String> for YearMonth {
+    type Error = String;
+
+    // Only the year and month of a date or timestamp matter to an expiry check
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let parsed = text.get(..7).zip(text.get(7..)).and_then(|(head, rest)| {
+            let (year, month) = head.split_once('-')?;
+            if year.len() != 4 || !(rest.is_empty() || rest.starts_with('-')) {
+                return None;
+            }
+            Some(YearMonth { year: digits(year)?, month: digits(month).filter(|m| (1..=12).contains(m))? })
+        });
+        parsed.ok_or_else(|| format!("invalid date {}", text))
+    }
+}
+
+impl From<YearMonth> for String {
+    fn from(date: YearMonth) -> Self {
+        format!("{:04}-{:02}", date.year, date.month)
+    }
+}
+
+fn digits(text: &str) -> Option<u32> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+// MM/YY or MM/YYYY; a hyphen also separates, and spaces around the separator are allowed
+fn parse_expiry(text: &str) -> Option<YearMonth> {
+    let (month, year) = text.trim().split_once(['/', '-'])?;
+    let (month, year) = (month.trim_end(), year.trim_start());
+
+    if month.len() != 2 {
+        return None;
+    }
+    let month = digits(month).filter(|m| (1..=12).contains(m))?;
+
+    // Two-digit years are in the 2000s: cards are issued for a few years at most
+    let year = match year.len() {
+        2 => 2000 + digits(year)?,
+        4 => digits(year)?,
+        _ => return None,
+    };
+
+    Some(YearMonth { year, month })
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the expiry as {month, year}, with the year in full
+fn coerce_expiry_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let expiry = parse_expiry(text)?;
+
+    // A card stays valid through the last day of its expiry month
+    if options.valid_at.is_some_and(|now| expiry < now) {
+        return None;
+    }
+
+    Some(serde_json::json!({ "month": expiry.month, "year": expiry.year }))
+}
+
+// Only strings can be valid; the fast path calls coerce_expiry_str directly
+fn coerce_expiry(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_expiry_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_expiry(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_expiry(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_expiry(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_expiry(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_expiry(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_expiry(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_expiry(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_expiry(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_expiry(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_expiry_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_expiries() {
+        let defaults = ValidationOptions::default();
+
+        for text in ["12/25", "01/2030", "06 / 27", "09-26", " 11/2031 ", "12/99"] {
+            assert!(validate_expiry(&json!(text), &defaults), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_invalid_expiries() {
+        let defaults = ValidationOptions::default();
+
+        for value in [
+            json!("13/25"), json!("00/25"), json!("1/25"), json!("12/5"), json!("12/025"),
+            json!("1225"), json!("12.25"), json!("ab/cd"), json!("12/+5"), json!(""), json!(1225),
+        ] {
+            assert!(!validate_expiry(&value, &defaults), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_valid_at() {
+        let now = options(json!({"valid_at": "2025-06-15T10:30:00Z"}));
+
+        assert!(validate_expiry(&json!("06/25"), &now)); // Valid through the end of June
+        assert!(validate_expiry(&json!("07/25"), &now));
+        assert!(validate_expiry(&json!("01/2026"), &now));
+        assert!(!validate_expiry(&json!("05/25"), &now));
+        assert!(!validate_expiry(&json!("12/2024"), &now));
+
+        assert!(validate_expiry(&json!("03/24"), &options(json!({"valid_at": "2024-03"}))));
+        assert!(validate_expiry(&json!("03/24"), &options(json!({"valid_at": "2024-03-31"}))));
+
+        for bad in ["2024", "2024-13", "24-03-01", "2024-03x", "next month"] {
+            assert!(serde_json::from_value::<ValidationOptions>(json!({"valid_at": bad})).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_expiry(&json!("12/25"), &defaults), Some(json!({"month": 12, "year": 2025})));
+        assert_eq!(coerce_expiry(&json!("01 / 2030"), &defaults), Some(json!({"month": 1, "year": 2030})));
+        assert_eq!(coerce_expiry(&json!("13/25"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "12/25"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "12/25", "options": {"valid_at": "2025-01-01"}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "12/25", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let now = options(json!({"valid_at": "2025-06"}));
+
+        assert_eq!(
+            ValidationResult::from(coerce_expiry(&json!("08/27"), &now)),
+            ValidationResult { valid: true, value: json!({"month": 8, "year": 2027}) }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_expiry(&json!("08/24"), &now)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["12/99", "13/30", "soon"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,339 @@
+// ABOUTME: WASM component for ISO 11649 structured creditor reference (RF reference) validation
+// ABOUTME: Provides validate and coerce functions for RF references such as RF18 5390 0754 7034, checked with ISO 7064 mod 97-10
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// RF, two check digits, then 1 to 21 alphanumeric characters of the creditor's own reference
+const MIN_LENGTH: usize = 5;
+const MAX_LENGTH: usize = 25;
+
+// ISO 7064 mod 97-10, as for IBANs: move the first four characters to the end, replace
+// letters with 10..35, and the result must leave a remainder of 1
+fn mod97_valid(reference: &str) -> bool {
+    let rearranged = reference[4..].chars().chain(reference[..4].chars());
+    let remainder = rearranged.fold(0u32, |remainder, c| {
+        let value = c.to_digit(36).unwrap_or(0);
+        let width = if value >= 10 { 100 } else { 10 };
+        (remainder * width + value) % 97
+    });
+    remainder == 1
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the reference in its electronic format: uppercase with no spaces
+fn coerce_reference_str(text: &str, _options: &ValidationOptions) -> Option<Value> {
+    // The paper format groups the reference in fours separated by spaces
+    let reference: String = text.chars().filter(|c| *c != ' ').collect::<String>().to_ascii_uppercase();
+
+    if !reference.is_ascii()
+        || !(MIN_LENGTH..=MAX_LENGTH).contains(&reference.len())
+        || !reference.starts_with("RF")
+        || !reference[2..4].bytes().all(|b| b.is_ascii_digit())
+        || !reference[4..].bytes().all(|b| b.is_ascii_alphanumeric())
+    {
+        return None;
+    }
+
+    mod97_valid(&reference).then_some(Value::String(reference))
+}
+
+// Only strings can be valid; the fast path calls coerce_reference_str directly
+fn coerce_reference(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_reference_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_reference(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_reference(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_reference(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_reference(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_reference(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_reference(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_reference(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_reference(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_reference(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_reference_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_references() {
+        let defaults = ValidationOptions::default();
+
+        for reference in ["RF18 5390 0754 7034", "RF185390075470 34", "RF18539007547034", "rf18 5390 0754 7034", "RF712348231", "RF18000000000539007547034"] {
+            assert!(validate_reference(&json!(reference), &defaults), "{}", reference);
+        }
+    }
+
+    #[test]
+    fn test_invalid_references() {
+        let defaults = ValidationOptions::default();
+
+        for value in [
+            json!("RF19 5390 0754 7034"), // Wrong check digits
+            json!("RF18 5390 0754 7035"),
+            json!("RF18"),
+            json!("RF18 0000 0000 0539 0075 4703 4X"), // Longer than 25 characters
+            json!("XX18 5390 0754 7034"),
+            json!("RFAB 5390 0754 7034"),
+            json!("RF18-5390-0754-7034"),
+            json!("RF18 5390 0754 703é"),
+            json!("RF1é 5390 0754 7034"),
+            json!(""),
+            json!(18539007547034u64),
+        ] {
+            assert!(!validate_reference(&value, &defaults), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_check_digits() {
+        // Each valid reference has exactly one pair of check digits
+        let valid = (0..100).filter(|n| mod97_valid(&format!("RF{:02}539007547034", n))).collect::<Vec<_>>();
+        assert_eq!(valid, vec![18]);
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(coerce_reference(&json!("rf18 5390 0754 7034"), &defaults), Some(json!("RF18539007547034")));
+        assert_eq!(coerce_reference(&json!("RF71 2348 231"), &defaults), Some(json!("RF712348231")));
+        assert_eq!(coerce_reference(&json!("RF00 0000"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "RF712348231"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "RF712348231", "options": {}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "RF712348231", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_reference(&json!("RF71 2348 231"), &defaults)),
+            ValidationResult { valid: true, value: json!("RF712348231") }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_reference(&json!("RF72 2348 231"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["RF18539007547034", "RF00539007547034", "RF"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,474 @@
+// ABOUTME: WASM component for phone number validation
+// ABOUTME: Provides validate and coerce functions for E.164 and national phone numbers, normalizing to E.164
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // ISO 3166-1 alpha-2 country used to interpret numbers written without a + prefix
+    default_country: Option<String>,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+struct Country {
+    code: &'static str,
+    calling_code: &'static str,
+    // National prefix dialled before the number within the country, dropped in E.164
+    trunk_prefix: &'static str,
+    // Length range of the national significant number
+    min_length: usize,
+    max_length: usize,
+}
+
+const fn country(code: &'static str, calling_code: &'static str, trunk_prefix: &'static str, min_length: usize, max_length: usize) -> Country {
+    Country { code, calling_code, trunk_prefix, min_length, max_length }
+}
+
+const COUNTRIES: &[Country] = &[
+    country("US", "1", "1", 10, 10),
+    country("CA", "1", "1", 10, 10),
+    country("GB", "44", "0", 9, 10),
+    country("IE", "353", "0", 7, 9),
+    country("FR", "33", "0", 9, 9),
+    country("DE", "49", "0", 6, 13),
+    country("NL", "31", "0", 9, 9),
+    country("BE", "32", "0", 8, 9),
+    country("CH", "41", "0", 9, 9),
+    country("AT", "43", "0", 4, 13),
+    country("ES", "34", "", 9, 9),
+    country("PT", "351", "", 9, 9),
+    country("IT", "39", "", 6, 11),
+    country("SE", "46", "0", 7, 9),
+    country("NO", "47", "", 8, 8),
+    country("DK", "45", "", 8, 8),
+    country("FI", "358", "0", 5, 12),
+    country("PL", "48", "", 9, 9),
+    country("RU", "7", "8", 10, 10),
+    country("AU", "61", "0", 9, 9),
+    country("NZ", "64", "0", 8, 10),
+    country("JP", "81", "0", 9, 10),
+    country("KR", "82", "0", 8, 10),
+    country("CN", "86", "0", 7, 11),
+    country("HK", "852", "", 8, 8),
+    country("SG", "65", "", 8, 8),
+    country("IN", "91", "0", 10, 10),
+    country("ZA", "27", "0", 9, 9),
+    country("BR", "55", "0", 10, 11),
+    country("MX", "52", "", 10, 10),
+];
+
+// E.164 allows at most 15 digits including the country code
+const MAX_E164_DIGITS: usize = 15;
+const MIN_E164_DIGITS: usize = 7;
+
+// Splits a trailing extension written as "ext. 123", "x123", "#123" or ";ext=123"
+fn split_extension(text: &str) -> Option<(&str, Option<&str>)> {
+    let lower = text.to_ascii_lowercase();
+    for marker in [";ext=", "extension", "ext.", "ext", "x", "#"] {
+        if let Some(index) = lower.rfind(marker) {
+            let extension = text[index + marker.len()..].trim();
+            if !extension.is_empty() && extension.len() <= 7 && extension.bytes().all(|b| b.is_ascii_digit()) {
+                return Some((&text[..index], Some(extension)));
+            }
+            return None;
+        }
+    }
+    Some((text, None))
+}
+
+// Digits of the number, allowing spaces, hyphens, dots and one pair of parentheses as separators
+fn strip_separators(text: &str) -> Option<String> {
+    let mut digits = String::new();
+    let mut parentheses = 0;
+    let mut open = false;
+
+    for c in text.chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            ' ' | '-' | '.' => {},
+            '(' if !open && parentheses == 0 => open = true,
+            ')' if open => {
+                open = false;
+                parentheses += 1;
+            },
+            _ => return None,
+        }
+    }
+
+    (!open && !digits.is_empty()).then_some(digits)
+}
+
+fn country_for_digits(digits: &str) -> Option<&'static Country> {
+    COUNTRIES
+        .iter()
+        .filter(|country| digits.starts_with(country.calling_code))
+        .max_by_key(|country| country.calling_code.len())
+}
+
+fn valid_national_length(country: &Country, national: &str) -> bool {
+    (country.min_length..=country.max_length).contains(&national.len())
+}
+
+// Converts a number written in international form (+ prefix) to E.164 digits
+fn international_digits(digits: &str) -> Option<String> {
+    if digits.starts_with('0') || !(MIN_E164_DIGITS..=MAX_E164_DIGITS).contains(&digits.len()) {
+        return None;
+    }
+
+    // Numbers for countries we know are held to that country's national length
+    if let Some(country) = country_for_digits(digits) {
+        if !valid_national_length(country, &digits[country.calling_code.len()..]) {
+            return None;
+        }
+    }
+    Some(digits.to_string())
+}
+
+// Converts a number written in national form to E.164 digits, dropping the trunk prefix
+fn national_digits(digits: &str, country_code: &str) -> Option<String> {
+    let country = COUNTRIES.iter().find(|country| country.code.eq_ignore_ascii_case(country_code))?;
+
+    let national = match digits.strip_prefix(country.trunk_prefix) {
+        Some(stripped) if !country.trunk_prefix.is_empty() && valid_national_length(country, stripped) => stripped,
+        _ => digits,
+    };
+
+    valid_national_length(country, national).then(|| format!("{}{}", country.calling_code, national))
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the E.164 form (+ and digits), with any extension appended as ";ext="
+fn coerce_phone_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let (number, extension) = split_extension(text.trim())?;
+    let number = number.trim();
+
+    let e164 = match number.strip_prefix('+') {
+        Some(rest) => international_digits(&strip_separators(rest)?)?,
+        None => national_digits(&strip_separators(number)?, options.default_country.as_deref()?)?,
+    };
+
+    Some(Value::String(match extension {
+        Some(extension) => format!("+{};ext={}", e164, extension),
+        None => format!("+{}", e164),
+    }))
+}
+
+// Only strings can be valid; the fast path calls coerce_phone_str directly
+fn coerce_phone(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_phone_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_phone(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_phone(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_phone(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_phone(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_phone(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_phone(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_phone(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_phone(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_phone(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_phone_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn country(code: &str) -> ValidationOptions {
+        serde_json::from_value(json!({"default_country": code})).unwrap()
+    }
+
+    #[test]
+    fn test_e164() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_phone(&json!("+14155550123"), &defaults));
+        assert!(validate_phone(&json!("+442079460958"), &defaults));
+        assert!(validate_phone(&json!("+1 (415) 555-0123"), &defaults));
+        assert!(validate_phone(&json!("+44 20 7946 0958"), &defaults));
+        assert!(validate_phone(&json!("+380 44 123 4567"), &defaults)); // Not in the country table
+
+        assert!(!validate_phone(&json!("4155550123"), &defaults)); // National form needs a default country
+        assert!(!validate_phone(&json!("+0123456789"), &defaults));
+        assert!(!validate_phone(&json!("+1415555012"), &defaults)); // Too short for NANP
+        assert!(!validate_phone(&json!("+1234567890123456"), &defaults)); // Over 15 digits
+        assert!(!validate_phone(&json!("+12345"), &defaults));
+        assert!(!validate_phone(&json!("+1 415 555 0123 abc"), &defaults));
+        assert!(!validate_phone(&json!("+1 (415 555-0123"), &defaults));
+        assert!(!validate_phone(&json!("+1 (415) (555) 0123"), &defaults));
+        assert!(!validate_phone(&json!(""), &defaults));
+        assert!(!validate_phone(&json!(14155550123u64), &defaults));
+    }
+
+    #[test]
+    fn test_national_formats() {
+        let us = country("US");
+        let gb = country("gb");
+        let it = country("IT");
+
+        assert!(validate_phone(&json!("(415) 555-0123"), &us));
+        assert!(validate_phone(&json!("415.555.0123"), &us));
+        assert!(validate_phone(&json!("1-415-555-0123"), &us));
+        assert!(validate_phone(&json!("+44 20 7946 0958"), &us)); // International form still works
+        assert!(!validate_phone(&json!("555-0123"), &us));
+
+        assert!(validate_phone(&json!("020 7946 0958"), &gb));
+        assert!(validate_phone(&json!("06 1234 5678"), &it)); // Italian numbers keep their leading zero
+
+        assert!(!validate_phone(&json!("020 7946 0958"), &country("XX")));
+    }
+
+    #[test]
+    fn test_extensions() {
+        let us = country("US");
+
+        assert!(validate_phone(&json!("+1 415 555 0123 ext. 42"), &us));
+        assert!(validate_phone(&json!("(415) 555-0123 x42"), &us));
+        assert!(validate_phone(&json!("415-555-0123 #42"), &us));
+        assert!(validate_phone(&json!("+14155550123;ext=42"), &us));
+
+        assert!(!validate_phone(&json!("415-555-0123 ext."), &us));
+        assert!(!validate_phone(&json!("415-555-0123 x12345678"), &us));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let us = country("US");
+
+        assert_eq!(coerce_phone(&json!("(415) 555-0123"), &us), Some(json!("+14155550123")));
+        assert_eq!(coerce_phone(&json!("1 415 555 0123"), &us), Some(json!("+14155550123")));
+        assert_eq!(coerce_phone(&json!("020 7946 0958"), &country("GB")), Some(json!("+442079460958")));
+        assert_eq!(coerce_phone(&json!("8 (495) 123-45-67"), &country("RU")), Some(json!("+74951234567")));
+        assert_eq!(coerce_phone(&json!("+1 415 555 0123 ext. 42"), &us), Some(json!("+14155550123;ext=42")));
+        assert_eq!(coerce_phone(&json!("555-0123"), &us), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "+14155550123"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "4155550123", "options": {"default_country": "US"}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "+14155550123", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_phone(&json!("+1 415-555-0123"), &defaults)),
+            ValidationResult { valid: true, value: json!("+14155550123") }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_phone(&json!("call me"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["+14155552671", "(415) 555-2671", "123", "phone"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
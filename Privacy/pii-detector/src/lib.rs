@@ -0,0 +1,606 @@
+// ABOUTME: WASM component that scans free text, such as comments, for personal data before it's posted
+// ABOUTME: Finds email addresses, phone numbers, Luhn-valid card numbers and national IDs, reporting their types and spans
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The kinds of personal data to look for, such as ["email", "credit_card"]; all when unset
+    types: Option<Vec<PiiType>>,
+    // ISO 3166-1 alpha-2 country for phone numbers written without a + prefix, such as "US";
+    // only international numbers are found when unset
+    default_country: Option<String>,
+}
+
+// The kinds of personal data the detector finds
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiType {
+    Email,
+    Phone,
+    CreditCard,
+    // A US Social Security number or a UK National Insurance number
+    NationalId,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The personal data found, in the order it appears
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    findings: Vec<Finding>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, findings: Vec::new() }
+    }
+}
+
+// Something in the text that looks like personal data: its type, and where it starts and ends
+// in UTF-16 code units, as JavaScript counts them, so that text.slice(start, end) is the match
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Finding {
+    #[serde(rename = "type")]
+    kind: PiiType,
+    start: usize,
+    end: usize,
+    // The country that issues a national ID, "US" or "GB"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    country: Option<&'static str>,
+}
+
+// Error codes reported in detailed results
+const CONTAINS_PII: &str = "contains_pii";
+
+// The longest stretch of digits and separators tried as a single number; a card number with
+// a space between every digit is shorter
+const MAX_NUMBER_LENGTH: usize = 40;
+
+// Numbers without a + prefix are only taken for phone numbers with at least this many digits,
+// so that dates such as 2024-06-01 and other short numbers aren't
+const MIN_NATIONAL_PHONE_DIGITS: usize = 9;
+
+// A byte span of the text, the type found there and, for a national ID, its country
+type Span = (usize, usize, PiiType, Option<&'static str>);
+
+fn wanted(options: &ValidationOptions, kind: PiiType) -> bool {
+    options.types.as_ref().is_none_or(|types| types.contains(&kind))
+}
+
+// Whether the byte at an index continues a word, so that a match next to it would be part of
+// something longer
+fn joins_word(text: &str, index: usize) -> bool {
+    text.as_bytes().get(index).is_some_and(|byte| byte.is_ascii_alphanumeric() || *byte == b'_' || *byte >= 0x80)
+}
+
+// Email addresses: around each "@", the longest run of the characters addresses are commonly
+// written with, that the email validator accepts
+fn email_spans(text: &str) -> Vec<Span> {
+    let bytes = text.as_bytes();
+    let local = |byte: u8| byte.is_ascii_alphanumeric() || b"._%+-".contains(&byte);
+    let domain = |byte: u8| byte.is_ascii_alphanumeric() || b".-".contains(&byte);
+    let mut spans = Vec::new();
+    for (at, _) in text.match_indices('@') {
+        let mut start = at;
+        while start > 0 && local(bytes[start - 1]) {
+            start -= 1;
+        }
+        let mut end = at + 1;
+        while end < bytes.len() && domain(bytes[end]) {
+            end += 1;
+        }
+        // Sentences end in a period, and addresses don't
+        while start < at && bytes[start] == b'.' {
+            start += 1;
+        }
+        while end > at + 1 && b".-".contains(&bytes[end - 1]) {
+            end -= 1;
+        }
+        let overlaps = spans.last().is_some_and(|&(_, previous_end, _, _): &Span| start < previous_end);
+        if !overlaps && email_validator::coerce_value(&Value::from(&text[start..end]), &Value::Null).is_some() {
+            spans.push((start, end, PiiType::Email, None));
+        }
+    }
+    spans
+}
+
+// Whether a number is written as a US Social Security number, "123-45-6789", in a range the
+// Social Security Administration assigns from: no 000, 666 or 9xx area, 00 group or 0000 serial
+fn is_ssn(number: &str) -> bool {
+    let bytes = number.as_bytes();
+    let shaped = bytes.len() == 11 && bytes.iter().enumerate().all(|(index, byte)| if index == 3 || index == 6 { *byte == b'-' } else { byte.is_ascii_digit() });
+    shaped && !matches!(&number[..3], "000" | "666") && !number.starts_with('9') && &number[4..6] != "00" && &number[7..] != "0000"
+}
+
+// The type of personal data a number is, if any: a card number that passes the Luhn check and
+// fits a brand, a Social Security number, or a phone number the phone validator accepts
+fn classify_number(number: &str, options: &ValidationOptions) -> Option<(PiiType, Option<&'static str>)> {
+    let value = Value::from(number);
+    if wanted(options, PiiType::CreditCard) && creditcard_validator::coerce_value(&value, &Value::Null).is_some() {
+        return Some((PiiType::CreditCard, None));
+    }
+    if wanted(options, PiiType::NationalId) && is_ssn(number) {
+        return Some((PiiType::NationalId, Some("US")));
+    }
+    let digits = number.bytes().filter(u8::is_ascii_digit).count();
+    let international = number.starts_with('+');
+    if wanted(options, PiiType::Phone) && (international || digits >= MIN_NATIONAL_PHONE_DIGITS) {
+        let phone_options = match &options.default_country {
+            Some(country) => json!({ "default_country": country }),
+            None => Value::Null,
+        };
+        if phone_validator::coerce_value(&value, &phone_options).is_some() {
+            return Some((PiiType::Phone, None));
+        }
+    }
+    None
+}
+
+// Stretches of text that may be numbers: digits with single spaces, hyphens or dots, and
+// parentheses, between them, perhaps after a "+", and not run into a word on either side
+fn number_runs(text: &str) -> Vec<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut runs = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let starts = match bytes[index] {
+            b'0'..=b'9' => true,
+            b'+' | b'(' => bytes[index + 1..].iter().take(2).any(u8::is_ascii_digit),
+            _ => false,
+        };
+        if !starts || (index > 0 && joins_word(text, index - 1)) {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        let mut end = index + 1;
+        while end < bytes.len() {
+            match bytes[end] {
+                b'0'..=b'9' | b'(' | b')' => {}
+                b' ' | b'-' | b'.' if !b" -.".contains(&bytes[end - 1]) => {}
+                _ => break,
+            }
+            end += 1;
+        }
+        // Back to the last digit or closing parenthesis
+        while end > start && !matches!(bytes[end - 1], b'0'..=b'9' | b')') {
+            end -= 1;
+        }
+        if end > start && !joins_word(text, end) {
+            runs.push((start, end));
+        }
+        index = end.max(start + 1);
+    }
+    runs
+}
+
+// Card, Social Security and phone numbers: within each stretch that may be numbers, from each
+// space-separated piece, the longest stretch to the end of a later piece that is one, so that
+// numbers listed one after another are found one by one
+fn number_spans(text: &str, options: &ValidationOptions) -> Vec<Span> {
+    let mut spans = Vec::new();
+    for (start, end) in number_runs(text) {
+        let mut pieces = vec![start];
+        pieces.extend(text[start..end].match_indices(' ').map(|(index, _)| start + index + 1));
+        let ends: Vec<usize> = pieces[1..].iter().map(|piece| piece - 1).chain([end]).collect();
+        let mut first = 0;
+        while first < pieces.len() {
+            let found = (first..pieces.len()).rev().filter(|&last| ends[last] - pieces[first] <= MAX_NUMBER_LENGTH).find_map(|last| {
+                let number = &text[pieces[first]..ends[last]];
+                classify_number(number, options).map(|(kind, country)| (last, (pieces[first], ends[last], kind, country)))
+            });
+            match found {
+                Some((last, span)) => {
+                    spans.push(span);
+                    first = last + 1;
+                }
+                None => first += 1,
+            }
+        }
+    }
+    spans
+}
+
+// Whether a National Insurance number's two-letter prefix can be issued: neither letter D, F,
+// I, Q, U or V, the second not O, and not BG, GB, KN, NK, NT, TN or ZZ
+fn is_nino_prefix(first: u8, second: u8) -> bool {
+    let prefix = [first, second];
+    !b"DFIQUV".contains(&first) && !b"DFIQUVO".contains(&second) && !matches!(&prefix, b"BG" | b"GB" | b"KN" | b"NK" | b"NT" | b"TN" | b"ZZ")
+}
+
+// UK National Insurance numbers, "QQ123456C" or "QQ 12 34 56 C", in capitals
+fn nino_spans(text: &str) -> Vec<Span> {
+    let bytes = text.as_bytes();
+    let mut spans = Vec::new();
+    let mut index = 0;
+    while index + 9 <= bytes.len() {
+        if (index > 0 && joins_word(text, index - 1)) || !bytes[index].is_ascii_uppercase() {
+            index += 1;
+            continue;
+        }
+        // The compact and the spaced form, as the letters, digits and spaces they're made of
+        let found = ["LLDDDDDDS", "LL DD DD DD S"].into_iter().find(|shape| {
+            let candidate = &bytes[index..];
+            candidate.len() >= shape.len()
+                && shape.bytes().zip(candidate).all(|(expected, &byte)| match expected {
+                    b'L' => byte.is_ascii_uppercase(),
+                    b'D' => byte.is_ascii_digit(),
+                    b'S' => (b'A'..=b'D').contains(&byte),
+                    _ => byte == b' ',
+                })
+                && !joins_word(text, index + shape.len())
+        });
+        match found {
+            Some(shape) if is_nino_prefix(bytes[index], bytes[index + 1]) => {
+                spans.push((index, index + shape.len(), PiiType::NationalId, Some("GB")));
+                index += shape.len();
+            }
+            _ => index += 1,
+        }
+    }
+    spans
+}
+
+// The personal data in a text, in order: email addresses first, then numbers and National
+// Insurance numbers outside them. Addresses are found even when they aren't looked for, so
+// that the digits in one aren't taken for a number
+fn scan(text: &str, options: &ValidationOptions) -> Vec<Finding> {
+    let emails = email_spans(text);
+    let mut others = number_spans(text, options);
+    if wanted(options, PiiType::NationalId) {
+        others.extend(nino_spans(text));
+    }
+    let mut spans: Vec<Span> = others.into_iter().filter(|&(start, end, _, _)| emails.iter().all(|&(email_start, email_end, _, _)| end <= email_start || start >= email_end)).collect();
+    if wanted(options, PiiType::Email) {
+        spans.extend(emails);
+    }
+    spans.sort_by_key(|&(start, _, _, _)| start);
+
+    // Byte offsets to UTF-16 ones
+    let utf16 = |offset: usize| text[..offset].encode_utf16().count();
+    spans.into_iter().map(|(start, end, kind, country)| Finding { kind, start: utf16(start), end: utf16(end), country }).collect()
+}
+
+fn detail_pii_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank text gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    let findings = scan(text, options);
+    if findings.is_empty() {
+        return ValidationResult::from(Some(Value::from(text)));
+    }
+    ValidationResult { error: Some(CONTAINS_PII), findings, ..ValidationResult::from(None) }
+}
+
+// Only strings can be valid; the fast path calls detail_pii_str directly
+fn detail_pii(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_pii_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the text unchanged when it holds no personal data
+fn coerce_pii(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_pii(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_pii(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_pii(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_pii(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_pii(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_pii(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_pii(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_pii(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_pii(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_pii(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_pii_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(options: Value) -> ValidationOptions {
+        serde_json::from_value(options).unwrap()
+    }
+
+    // The type, matched text and country of each finding
+    fn found(text: &str, options: &ValidationOptions) -> Vec<(PiiType, String, Option<&'static str>)> {
+        let units: Vec<u16> = text.encode_utf16().collect();
+        let slice = |finding: &Finding| String::from_utf16(&units[finding.start..finding.end]).unwrap();
+        scan(text, options).iter().map(|finding| (finding.kind, slice(finding), finding.country)).collect()
+    }
+
+    #[test]
+    fn test_emails() {
+        let default = ValidationOptions::default();
+        assert_eq!(
+            found("Mail me at jane.doe+shop@Example.com. Or (bob@example.org)!", &default),
+            vec![(PiiType::Email, "jane.doe+shop@Example.com".to_string(), None), (PiiType::Email, "bob@example.org".to_string(), None)]
+        );
+        // Handles and addresses without a top-level domain aren't addresses the validator accepts
+        assert!(found("Thanks @jane, see user@localhost or a@b", &default).is_empty());
+    }
+
+    #[test]
+    fn test_numbers() {
+        let default = ValidationOptions::default();
+        assert_eq!(found("Card 4111 1111 1111 1111, exp 12/27", &default), vec![(PiiType::CreditCard, "4111 1111 1111 1111".to_string(), None)]);
+        assert_eq!(found("4111-1111-1111-1111", &default), vec![(PiiType::CreditCard, "4111-1111-1111-1111".to_string(), None)]);
+        // The Luhn check rules out most long numbers
+        assert!(found("Tracking 4111 1111 1111 1112", &default).is_empty());
+        assert!(found("ref4111111111111111", &default).is_empty());
+
+        assert_eq!(found("SSN: 123-45-6789.", &default), vec![(PiiType::NationalId, "123-45-6789".to_string(), Some("US"))]);
+        assert!(found("000-12-3456 666-12-3456 912-34-5678 123-00-4567 123-45-0000", &default).is_empty());
+
+        assert_eq!(found("Call +44 20 7946 0958 today", &default), vec![(PiiType::Phone, "+44 20 7946 0958".to_string(), None)]);
+        // Numbers without a + are only phone numbers in the default country
+        assert!(found("Call (415) 555-2671", &default).is_empty());
+        let us = options(json!({"default_country": "US"}));
+        assert_eq!(found("Call (415) 555-2671", &us), vec![(PiiType::Phone, "(415) 555-2671".to_string(), None)]);
+        assert!(found("On 2024-06-01 we shipped 12 items for 19.99", &us).is_empty());
+
+        // Numbers listed one after another are found one by one
+        assert_eq!(
+            found("415-555-2671 4111 1111 1111 1111 123-45-6789", &us),
+            vec![
+                (PiiType::Phone, "415-555-2671".to_string(), None),
+                (PiiType::CreditCard, "4111 1111 1111 1111".to_string(), None),
+                (PiiType::NationalId, "123-45-6789".to_string(), Some("US")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_national_insurance() {
+        let default = ValidationOptions::default();
+        assert_eq!(found("NI number AB123456C", &default), vec![(PiiType::NationalId, "AB123456C".to_string(), Some("GB"))]);
+        assert!(found("NI: QQ 12 34 56 A?", &default).is_empty());
+        assert_eq!(found("NI: JG 12 34 56 A?", &default), vec![(PiiType::NationalId, "JG 12 34 56 A".to_string(), Some("GB"))]);
+        assert!(found("GB123456A BG123456A AO123456A AB123456E XAB123456C", &default).is_empty());
+    }
+
+    #[test]
+    fn test_types() {
+        let text = "jane@example.com, 4111 1111 1111 1111, AB123456C";
+        let cards = options(json!({"types": ["credit_card"]}));
+        assert_eq!(found(text, &cards), vec![(PiiType::CreditCard, "4111 1111 1111 1111".to_string(), None)]);
+        // An email address's digits aren't a number of their own
+        assert!(found("4111111111111111@example.com", &cards).is_empty());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"types": ["passport"]})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        // Spans count UTF-16 code units, as JavaScript does
+        let text = "📞 +44 20 7946 0958";
+        assert_eq!(
+            messages::localize(&detail_pii_str(text, &ValidationOptions::default()), &json!(text), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "contains_pii",
+                "findings": [{"type": "phone", "start": 3, "end": 19}],
+                "message": messages::message("contains_pii", "en").unwrap(),
+            })
+        );
+        assert_eq!(coerce_pii(&json!("Nice, thanks!"), &ValidationOptions::default()), Some(json!("Nice, thanks!")));
+        assert_eq!(messages::localize(&detail_pii_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+        assert!(!validate_pii(&json!(42), &ValidationOptions::default()));
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["Call 020 7946 0958", "Lovely"], "options": {"default_country": "GB"}})).unwrap();
+        let valid: Vec<bool> = batch.values.iter().map(|value| validate_pii(value, &batch.options)).collect();
+        assert_eq!(valid, vec![false, true]);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in ["Nice work", "mail jane@example.com", "", "+44 20 7946 0958", "AB123456C"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// Types for the pii validator's exports, added to the .d.ts that wasm-bindgen generates
+
+export type PiiType = "email" | "phone" | "credit_card" | "national_id";
+
+/** Something in the text that looks like personal data; start and end count UTF-16 code units, as JavaScript does, so that text.slice(start, end) is the match */
+export interface Finding {
+    type: PiiType;
+    start: number;
+    end: number;
+    /** The country that issues a national ID, "US" or "GB" */
+    country?: "US" | "GB";
+}
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The kinds of personal data to look for, such as ["email", "credit_card"]; all when unset */
+    types?: PiiType[];
+    /** ISO 3166-1 alpha-2 country for phone numbers written without a + prefix, such as "US"; only international numbers are found when unset */
+    default_country?: string;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The personal data found, in the order it appears */
+    findings?: Finding[];
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "contains_pii" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,418 @@
+// ABOUTME: WASM component for record (JSON object) validation
+// ABOUTME: Validates each field of an object with the validator its datatype names, returning per-field results
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Field name → datatype name, or {type, required, options}
+    fields: BTreeMap<String, FieldSpec>,
+    // Reject fields that have no entry in `fields`; by default they pass through unchanged
+    reject_unknown_fields: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Outcome for each declared field, plus any rejected undeclared ones
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    fields: BTreeMap<String, FieldResult>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            fields: BTreeMap::new(),
+        }
+    }
+}
+
+// Error codes reported for individual fields in detailed results
+const MISSING_FIELD: &str = "missing_field";
+const UNKNOWN_TYPE: &str = "unknown_type";
+const UNKNOWN_FIELD: &str = "unknown_field";
+
+// How one field is validated: either just a datatype name, or the full form
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum FieldSpec {
+    Type(String),
+    Rule(FieldRule),
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldRule {
+    #[serde(rename = "type")]
+    type_name: String,
+    // Fail when the field is absent or null
+    #[serde(default)]
+    required: bool,
+    // Options passed to the datatype's validator
+    #[serde(default)]
+    options: Value,
+}
+
+impl FieldSpec {
+    fn rule(&self) -> (&str, bool, &Value) {
+        match self {
+            FieldSpec::Type(type_name) => (type_name, false, &Value::Null),
+            FieldSpec::Rule(rule) => (&rule.type_name, rule.required, &rule.options),
+        }
+    }
+}
+
+// Outcome for a single field, shaped like a validator's own detailed result
+#[derive(Serialize, Debug, PartialEq)]
+pub struct FieldResult {
+    valid: bool,
+    value: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl FieldResult {
+    fn error(code: &'static str) -> Self {
+        FieldResult { valid: false, value: Value::Null, error: Some(code) }
+    }
+}
+
+fn check_field(spec: &FieldSpec, value: Option<&Value>) -> FieldResult {
+    let (type_name, required, options) = spec.rule();
+    let Some(coerce) = validator_registry::lookup(type_name) else {
+        return FieldResult::error(UNKNOWN_TYPE);
+    };
+
+    // Forms commonly send null for an empty field, so it counts as absent
+    match value.filter(|value| !value.is_null()) {
+        Some(value) => {
+            let coerced = coerce(value, options);
+            FieldResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None }
+        },
+        None if required => FieldResult::error(MISSING_FIELD),
+        None => FieldResult { valid: true, value: Value::Null, error: None },
+    }
+}
+
+// Detailed validation: every declared field is checked, and so are undeclared ones when
+// reject_unknown_fields is set; the record is valid when all of them are
+fn detail_record(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let Some(record) = value.as_object() else {
+        return ValidationResult::from(None);
+    };
+
+    let mut fields: BTreeMap<String, FieldResult> = options
+        .fields
+        .iter()
+        .map(|(name, spec)| (name.clone(), check_field(spec, record.get(name))))
+        .collect();
+    if options.reject_unknown_fields {
+        for name in record.keys().filter(|name| !options.fields.contains_key(*name)) {
+            fields.insert(name.clone(), FieldResult::error(UNKNOWN_FIELD));
+        }
+    }
+
+    if !fields.values().all(|field| field.valid) {
+        return ValidationResult { fields, ..ValidationResult::from(None) };
+    }
+
+    // Declared fields take their canonical values; absent optional ones stay absent
+    let mut coerced = record.clone();
+    for (name, field) in &fields {
+        if coerced.contains_key(name) {
+            coerced.insert(name.clone(), field.value.clone());
+        }
+    }
+    ValidationResult { fields, ..ValidationResult::from(Some(Value::Object(coerced))) }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the record with every declared field replaced by its canonical value
+fn coerce_record(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_record(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_record(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_record(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_record(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_record(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_record(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_record(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_record(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_record(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_record(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn signup() -> ValidationOptions {
+        options(json!({"fields": {
+            "email": {"type": "email", "required": true},
+            "age": "integer",
+            "website": {"type": "url", "options": {}},
+        }}))
+    }
+
+    #[test]
+    fn test_valid_records() {
+        let signup = signup();
+
+        assert!(validate_record(&json!({"email": "ada@example.com", "age": "36"}), &signup));
+        assert!(validate_record(&json!({"email": "ada@example.com", "age": null}), &signup));
+        assert!(validate_record(&json!({"email": "ada@example.com", "newsletter": "yes"}), &signup));
+        assert!(validate_record(&json!({}), &ValidationOptions::default()));
+    }
+
+    #[test]
+    fn test_invalid_records() {
+        let signup = signup();
+
+        assert!(!validate_record(&json!({"age": 36}), &signup));
+        assert!(!validate_record(&json!({"email": null}), &signup));
+        assert!(!validate_record(&json!({"email": "ada@example.com", "age": "old"}), &signup));
+        assert!(!validate_record(&json!([{"email": "ada@example.com"}]), &signup));
+        assert!(!validate_record(&json!("email=ada@example.com"), &signup));
+    }
+
+    #[test]
+    fn test_unknown_fields_and_types() {
+        let strict = options(json!({"fields": {"age": "integer"}, "reject_unknown_fields": true}));
+        let result = detail_record(&json!({"age": 36, "nickname": "Ada"}), &strict);
+
+        assert!(!result.valid);
+        assert_eq!(result.fields["age"], FieldResult { valid: true, value: json!(36), error: None });
+        assert_eq!(result.fields["nickname"], FieldResult::error(UNKNOWN_FIELD));
+
+        let typo = options(json!({"fields": {"age": "integr"}}));
+        assert_eq!(detail_record(&json!({"age": 36}), &typo).fields["age"], FieldResult::error(UNKNOWN_TYPE));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"fields": {"age": {"type": "integer", "min": 0}}})).is_err());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"fields": {"age": 7}})).is_err());
+    }
+
+    #[test]
+    fn test_coerce() {
+        assert_eq!(
+            coerce_record(&json!({"email": "Ada@EXAMPLE.com", "age": " 36 ", "extra": 1}), &signup()),
+            Some(json!({"email": "Ada@example.com", "age": 36, "extra": 1}))
+        );
+        assert_eq!(coerce_record(&json!({"age": 36}), &signup()), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": {}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": {}, "options": {"fields": {"a": "text"}}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": {}, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let result = detail_record(&json!({"age": "x", "website": "https://example.com"}), &signup());
+
+        assert_eq!(serde_json::to_value(&result).unwrap(), json!({
+            "valid": false,
+            "value": null,
+            "fields": {
+                "age": {"valid": false, "value": null},
+                "email": {"valid": false, "value": null, "error": "missing_field"},
+                "website": {"valid": true, "value": "https://example.com/"},
+            },
+        }));
+        assert_eq!(
+            detail_record(&json!(42), &signup()),
+            ValidationResult { valid: false, value: Value::Null, fields: BTreeMap::new() }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+}
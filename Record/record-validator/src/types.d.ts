@@ -0,0 +1,43 @@
+// Types for the record validator's exports, added to the .d.ts that wasm-bindgen generates
+
+export type FieldSpec = string | { type: string; required?: boolean; options?: unknown };
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Field name to datatype name (an optional field), or { type, required, options } */
+    fields?: Record<string, FieldSpec>;
+    /** Reject fields that have no entry in fields (default: false) */
+    reject_unknown_fields?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = Record<string, unknown>;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = Record<string, unknown>;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Outcome for each field present, plus any missing required ones */
+    fields?: Record<string, { valid: boolean; value: unknown; error?: "missing_field" | "unknown_type" | "unknown_field" | "empty_value" | "invalid"; message?: string }>;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
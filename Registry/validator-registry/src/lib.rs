@@ -0,0 +1,216 @@
+// ABOUTME: Registry of the datatype validators, looked up by datatype name
+// ABOUTME: Lets composite validators such as the array validator delegate to the others' internal logic
+
+use serde_json::Value;
+
+// A validator's dispatch entry point: the value and its options as JSON, returning the
+// canonical value, or None when the value (or the options) are invalid
+pub type Coerce = fn(&Value, &Value) -> Option<Value>;
+
+// A validator's detailed entry point: the value and its options as JSON, returning the
+// detailed result as JSON, before any message is added, or None when the options are invalid
+pub type Detail = fn(&Value, &Value) -> Option<Value>;
+
+// A validator's capability metadata, as its describe export returns it
+pub type Describe = fn() -> Value;
+
+// Every validator that can be named as a datatype; each has a feature of the same name, so
+// that builds needing only a few of them can compile the rest out
+const VALIDATORS: &[(&str, Coerce, Detail, Describe)] = &[
+    #[cfg(feature = "text")]
+    ("text", text_validator::coerce_value, text_validator::detail_value, text_validator::description),
+    #[cfg(feature = "url")]
+    ("url", url_validator::coerce_value, url_validator::detail_value, url_validator::description),
+    #[cfg(feature = "email")]
+    ("email", email_validator::coerce_value, email_validator::detail_value, email_validator::description),
+    #[cfg(feature = "phone")]
+    ("phone", phone_validator::coerce_value, phone_validator::detail_value, phone_validator::description),
+    #[cfg(feature = "ip")]
+    ("ip", ip_validator::coerce_value, ip_validator::detail_value, ip_validator::description),
+    #[cfg(feature = "uuid")]
+    ("uuid", uuid_validator::coerce_value, uuid_validator::detail_value, uuid_validator::description),
+    #[cfg(feature = "regex")]
+    ("regex", regex_validator::coerce_value, regex_validator::detail_value, regex_validator::description),
+    #[cfg(feature = "boolean")]
+    ("boolean", boolean_validator::coerce_value, boolean_validator::detail_value, boolean_validator::description),
+    #[cfg(feature = "integer")]
+    ("integer", integer_validator::coerce_value, integer_validator::detail_value, integer_validator::description),
+    #[cfg(feature = "float")]
+    ("float", floatingpoint_validator::coerce_value, floatingpoint_validator::detail_value, floatingpoint_validator::description),
+    #[cfg(feature = "datetime")]
+    ("datetime", datetime_validator::coerce_value, datetime_validator::detail_value, datetime_validator::description),
+    #[cfg(feature = "date")]
+    ("date", date_validator::coerce_value, date_validator::detail_value, date_validator::description),
+    #[cfg(feature = "time")]
+    ("time", time_validator::coerce_value, time_validator::detail_value, time_validator::description),
+    #[cfg(feature = "period")]
+    ("period", period_validator::coerce_value, period_validator::detail_value, period_validator::description),
+    #[cfg(feature = "aria")]
+    ("aria", aria_validator::coerce_value, aria_validator::detail_value, aria_validator::description),
+    #[cfg(feature = "creditcard")]
+    ("creditcard", creditcard_validator::coerce_value, creditcard_validator::detail_value, creditcard_validator::description),
+    #[cfg(feature = "fontfamily")]
+    ("fontfamily", fontfamily_validator::coerce_value, fontfamily_validator::detail_value, fontfamily_validator::description),
+    #[cfg(feature = "color")]
+    ("color", color_validator::coerce_value, color_validator::detail_value, color_validator::description),
+    #[cfg(feature = "contrast")]
+    ("contrast", contrast_validator::coerce_value, contrast_validator::detail_value, contrast_validator::description),
+    #[cfg(feature = "altitude")]
+    ("altitude", altitude_validator::coerce_value, altitude_validator::detail_value, altitude_validator::description),
+    #[cfg(feature = "heading")]
+    ("heading", heading_validator::coerce_value, heading_validator::detail_value, heading_validator::description),
+    #[cfg(feature = "expiry")]
+    ("expiry", expiry_validator::coerce_value, expiry_validator::detail_value, expiry_validator::description),
+    #[cfg(feature = "cvv")]
+    ("cvv", cvv_validator::coerce_value, cvv_validator::detail_value, cvv_validator::description),
+    #[cfg(feature = "rfreference")]
+    ("rfreference", rfreference_validator::coerce_value, rfreference_validator::detail_value, rfreference_validator::description),
+    #[cfg(feature = "ticker")]
+    ("ticker", ticker_validator::coerce_value, ticker_validator::detail_value, ticker_validator::description),
+    #[cfg(feature = "connstring")]
+    ("connstring", connstring_validator::coerce_value, connstring_validator::detail_value, connstring_validator::description),
+    #[cfg(feature = "mailserver")]
+    ("mailserver", mailserver_validator::coerce_value, mailserver_validator::detail_value, mailserver_validator::description),
+    #[cfg(feature = "webhook")]
+    ("webhook", webhook_validator::coerce_value, webhook_validator::detail_value, webhook_validator::description),
+    #[cfg(feature = "s3bucket")]
+    ("s3bucket", s3bucket_validator::coerce_value, s3bucket_validator::detail_value, s3bucket_validator::description),
+    #[cfg(feature = "s3key")]
+    ("s3key", s3key_validator::coerce_value, s3key_validator::detail_value, s3key_validator::description),
+    #[cfg(feature = "flagkey")]
+    ("flagkey", flagkey_validator::coerce_value, flagkey_validator::detail_value, flagkey_validator::description),
+    #[cfg(feature = "boundary")]
+    ("boundary", boundary_validator::coerce_value, boundary_validator::detail_value, boundary_validator::description),
+    #[cfg(feature = "disposition")]
+    ("disposition", disposition_validator::coerce_value, disposition_validator::detail_value, disposition_validator::description),
+    #[cfg(feature = "charset")]
+    ("charset", charset_validator::coerce_value, charset_validator::detail_value, charset_validator::description),
+    #[cfg(feature = "encodingsniff")]
+    ("encodingsniff", encodingsniff_validator::coerce_value, encodingsniff_validator::detail_value, encodingsniff_validator::description),
+    #[cfg(feature = "filetype")]
+    ("filetype", filetype_validator::coerce_value, filetype_validator::detail_value, filetype_validator::description),
+    #[cfg(feature = "archive")]
+    ("archive", archive_validator::coerce_value, archive_validator::detail_value, archive_validator::description),
+    #[cfg(feature = "pdf")]
+    ("pdf", pdf_validator::coerce_value, pdf_validator::detail_value, pdf_validator::description),
+    #[cfg(feature = "vcard")]
+    ("vcard", vcard_validator::coerce_value, vcard_validator::detail_value, vcard_validator::description),
+    #[cfg(feature = "ics")]
+    ("ics", ics_validator::coerce_value, ics_validator::detail_value, ics_validator::description),
+    #[cfg(feature = "subtitle")]
+    ("subtitle", subtitle_validator::coerce_value, subtitle_validator::detail_value, subtitle_validator::description),
+    #[cfg(feature = "m3u8")]
+    ("m3u8", m3u8_validator::coerce_value, m3u8_validator::detail_value, m3u8_validator::description),
+    #[cfg(feature = "decimal")]
+    ("decimal", decimal_validator::coerce_value, decimal_validator::detail_value, decimal_validator::description),
+    #[cfg(feature = "feed")]
+    ("feed", feed_validator::coerce_value, feed_validator::detail_value, feed_validator::description),
+    #[cfg(feature = "opml")]
+    ("opml", opml_validator::coerce_value, opml_validator::detail_value, opml_validator::description),
+    #[cfg(feature = "sitemap")]
+    ("sitemap", sitemap_validator::coerce_value, sitemap_validator::detail_value, sitemap_validator::description),
+    #[cfg(feature = "securitytxt")]
+    ("securitytxt", securitytxt_validator::coerce_value, securitytxt_validator::detail_value, securitytxt_validator::description),
+    #[cfg(feature = "authrecord")]
+    ("authrecord", authrecord_validator::coerce_value, authrecord_validator::detail_value, authrecord_validator::description),
+    #[cfg(feature = "header")]
+    ("header", header_validator::coerce_value, header_validator::detail_value, header_validator::description),
+    #[cfg(feature = "placeholder")]
+    ("placeholder", placeholder_validator::coerce_value, placeholder_validator::detail_value, placeholder_validator::description),
+    #[cfg(feature = "formatstring")]
+    ("formatstring", formatstring_validator::coerce_value, formatstring_validator::detail_value, formatstring_validator::description),
+    #[cfg(feature = "urn")]
+    ("urn", urn_validator::coerce_value, urn_validator::detail_value, urn_validator::description),
+    #[cfg(feature = "pii")]
+    ("pii", pii_detector::coerce_value, pii_detector::detail_value, pii_detector::description),
+    #[cfg(feature = "uritemplate")]
+    ("uritemplate", uritemplate_validator::coerce_value, uritemplate_validator::detail_value, uritemplate_validator::description),
+    #[cfg(feature = "magnet")]
+    ("magnet", magnet_validator::coerce_value, magnet_validator::detail_value, magnet_validator::description),
+    #[cfg(feature = "secret")]
+    ("secret", secret_detector::coerce_value, secret_detector::detail_value, secret_detector::description),
+];
+
+// Finds a validator by datatype name; names are case-sensitive, as option values are elsewhere
+pub fn lookup(name: &str) -> Option<Coerce> {
+    VALIDATORS.iter().find(|(known, ..)| *known == name).map(|(_, coerce, ..)| *coerce)
+}
+
+// Finds the detailed entry point of the validator with the datatype name
+pub fn lookup_detail(name: &str) -> Option<Detail> {
+    VALIDATORS.iter().find(|(known, ..)| *known == name).map(|(_, _, detail, _)| *detail)
+}
+
+// The capability metadata of the validator with the datatype name
+pub fn describe(name: &str) -> Option<Value> {
+    VALIDATORS.iter().find(|(known, ..)| *known == name).map(|(.., describe)| describe())
+}
+
+// The datatype names that lookup accepts
+pub fn names() -> impl Iterator<Item = &'static str> {
+    VALIDATORS.iter().map(|(name, ..)| *name)
+}
+
+// The tests name validators from across the table
+#[cfg(all(test, feature = "all"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_lookup() {
+        let integer = lookup("integer").unwrap();
+        assert_eq!(integer(&json!("42"), &Value::Null), Some(json!(42)));
+        assert_eq!(integer(&json!("4.2"), &Value::Null), None);
+
+        let text = lookup("text").unwrap();
+        assert_eq!(text(&json!("hello"), &Value::Null), Some(json!("hello")));
+        assert_eq!(text(&json!(42), &Value::Null), None);
+
+        assert!(lookup("Integer").is_none());
+        assert!(lookup("array").is_none());
+        assert!(lookup("").is_none());
+    }
+
+    #[test]
+    fn test_options_are_forwarded() {
+        let uuid = lookup("uuid").unwrap();
+        let nil = json!("00000000-0000-0000-0000-000000000000");
+
+        assert!(uuid(&nil, &json!({})).is_some());
+        assert!(uuid(&nil, &json!({"reject_nil": true})).is_none());
+        assert!(uuid(&nil, &json!({"strict": true})).is_none());
+        assert!(uuid(&nil, &json!("reject_nil")).is_none());
+    }
+
+    #[test]
+    fn test_lookup_detail() {
+        let integer = lookup_detail("integer").unwrap();
+        assert_eq!(integer(&json!("42"), &Value::Null), Some(json!({"valid": true, "value": 42})));
+        assert_eq!(integer(&json!("1000"), &json!({"max": 10})), Some(json!({"valid": false, "value": null, "error": "out_of_range"})));
+
+        // Options that don't deserialize give no result rather than an invalid one
+        assert_eq!(integer(&json!("42"), &json!({"maximum": 10})), None);
+        assert_eq!(integer(&Value::Null, &json!({"maximum": 10})), None);
+        assert!(lookup_detail("array").is_none());
+    }
+
+    #[test]
+    fn test_names() {
+        let names: Vec<&str> = names().collect();
+
+        assert_eq!(names.len(), VALIDATORS.len());
+        assert!(names.iter().all(|name| lookup(name).is_some() && lookup_detail(name).is_some()));
+        assert!(names.contains(&"boolean") && names.contains(&"float") && names.contains(&"url"));
+    }
+
+    #[test]
+    fn test_describe() {
+        // Each validator describes itself under the name it is registered as
+        for name in names() {
+            assert_eq!(describe(name).unwrap()["datatype"], name);
+        }
+        assert_eq!(describe("float").unwrap()["name"], "floatingpoint-validator");
+        assert!(describe("array").is_none());
+    }
+}
@@ -0,0 +1,651 @@
+// ABOUTME: WASM component for security.txt files (RFC 9116), for tools that publish or audit vulnerability disclosure contacts
+// ABOUTME: Checks field syntax, the required Contact and Expires, and URIs and dates with the URL and datetime validators
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The current time, as an RFC 3339 timestamp, which the file must expire after; WASM has no
+    // clock of its own, so the caller supplies "now"
+    now: Option<Instant>,
+    // The URL the file was fetched from, which must be one of its Canonical URIs when it lists any
+    url: Option<String>,
+}
+
+// A moment in time, as seconds since 1970 in UTC, and the timestamp it was written as
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(try_from = "String", into = "String")]
+pub struct Instant {
+    seconds: i64,
+    text: String,
+}
+
+impl TryFrom<String> for Instant {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        match instant(&text) {
+            Some(seconds) => Ok(Instant { seconds, text }),
+            None => Err(format!("invalid timestamp {}", text)),
+        }
+    }
+}
+
+impl From<Instant> for String {
+    fn from(instant: Instant) -> Self {
+        instant.text
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The line the problem was found on, counting from 1; a missing field has none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    // The field at fault, when there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, line: None, field: None }
+    }
+}
+
+impl From<Problem> for ValidationResult {
+    fn from(problem: Problem) -> Self {
+        ValidationResult { error: Some(problem.code), line: problem.line, field: problem.field, ..ValidationResult::from(None) }
+    }
+}
+
+// Error codes reported in detailed results
+const MALFORMED_LINE: &str = "malformed_line";
+const MALFORMED_SIGNATURE: &str = "malformed_signature";
+const MISSING_FIELD: &str = "missing_field";
+const DUPLICATE_FIELD: &str = "duplicate_field";
+const INVALID_URL: &str = "invalid_url";
+const INSECURE_SCHEME: &str = "insecure_scheme";
+const INVALID_DATE: &str = "invalid_date";
+const EXPIRED: &str = "expired";
+const INVALID_LANGUAGE: &str = "invalid_language";
+const CANONICAL_MISMATCH: &str = "canonical_mismatch";
+
+// Why a file was rejected: an error code, the line it was found on and the field at fault,
+// where there are those
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    code: &'static str,
+    line: Option<usize>,
+    field: Option<&'static str>,
+}
+
+// What a field's value is
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Uri,
+    Date,
+    Languages,
+}
+
+// The fields RFC 9116 defines, whose names are case-insensitive; other fields are extensions,
+// and are ignored
+struct Field {
+    name: &'static str,
+    kind: Kind,
+    // Whether it may appear only once; the others may be repeated
+    once: bool,
+    required: bool,
+}
+
+const FIELDS: &[Field] = &[
+    Field { name: "Acknowledgments", kind: Kind::Uri, once: false, required: false },
+    Field { name: "Canonical", kind: Kind::Uri, once: false, required: false },
+    Field { name: "Contact", kind: Kind::Uri, once: false, required: true },
+    Field { name: "CSAF", kind: Kind::Uri, once: false, required: false },
+    Field { name: "Encryption", kind: Kind::Uri, once: false, required: false },
+    Field { name: "Expires", kind: Kind::Date, once: true, required: true },
+    Field { name: "Hiring", kind: Kind::Uri, once: false, required: false },
+    Field { name: "Policy", kind: Kind::Uri, once: false, required: false },
+    Field { name: "Preferred-Languages", kind: Kind::Languages, once: true, required: false },
+];
+
+// The lines of an OpenPGP cleartext signature (RFC 4880) around a signed file
+const SIGNED_MESSAGE: &str = "-----BEGIN PGP SIGNED MESSAGE-----";
+const SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----";
+const SIGNATURE_END: &str = "-----END PGP SIGNATURE-----";
+
+// Days from 1970-01-01 to a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// An RFC 3339 timestamp, checked by the datetime validator, as seconds since 1970 in UTC;
+// fractions of a second are dropped
+fn instant(text: &str) -> Option<i64> {
+    let canonical = datetime_validator::coerce_value(&Value::from(text.trim()), &Value::Null)?;
+    let canonical = canonical.as_str()?.to_ascii_uppercase();
+    let number = |range: std::ops::Range<usize>| canonical.get(range)?.parse::<i64>().ok();
+    let days = days_from_civil(number(0..4)?, number(5..7)?, number(8..10)?);
+    let seconds = days * 86_400 + number(11..13)? * 3_600 + number(14..16)? * 60 + number(17..19)?;
+    let offset = match canonical[19..].trim_start_matches(|c: char| c == '.' || c.is_ascii_digit()) {
+        "Z" => 0,
+        offset => {
+            let minutes = offset[1..3].parse::<i64>().ok()? * 60 + offset[4..6].parse::<i64>().ok()?;
+            if offset.starts_with('-') { -minutes * 60 } else { minutes * 60 }
+        }
+    };
+    Some(seconds - offset)
+}
+
+// A line of the file and its number, counting from 1
+type Line<'a> = (usize, &'a str);
+
+// The lines of the file, numbered from 1, and whether it was signed. A signed file's lines are
+// those between the armor headers and the signature, with their dash-escaping removed; the
+// signature itself isn't verified
+fn body_lines(text: &str) -> Result<(Vec<Line<'_>>, bool), Problem> {
+    let lines: Vec<Line> = text.lines().enumerate().map(|(index, line)| (index + 1, line)).collect();
+    let Some(&(start, _)) = lines.iter().find(|(_, line)| !line.trim().is_empty()).filter(|(_, line)| line.trim_end() == SIGNED_MESSAGE) else {
+        return Ok((lines, false));
+    };
+    let malformed = |line: usize| Problem { code: MALFORMED_SIGNATURE, line: Some(line), field: None };
+
+    // Armor headers, such as "Hash: SHA256", end at a blank line
+    let mut rest = lines[start..].iter().skip_while(|(_, line)| !line.trim().is_empty());
+    rest.next().ok_or(malformed(start))?;
+    let mut body = Vec::new();
+    for &(number, line) in rest.by_ref() {
+        if line.trim_end() == SIGNATURE {
+            let ended = lines[number..].iter().any(|(_, line)| line.trim_end() == SIGNATURE_END);
+            return if ended { Ok((body, true)) } else { Err(malformed(number)) };
+        }
+        if line.starts_with('-') && !line.starts_with("- ") {
+            return Err(malformed(number));
+        }
+        body.push((number, line.strip_prefix("- ").unwrap_or(line)));
+    }
+    Err(malformed(lines.len()))
+}
+
+// A URI as the URL validator canonicalizes it, with any scheme, such as mailto:, tel: or
+// openpgp4fpr:, except that web URIs must be https
+fn check_uri(text: &str) -> Result<Value, &'static str> {
+    let any_scheme = json!({ "allow_any_scheme": true });
+    let uri = url_validator::coerce_value(&Value::from(text), &any_scheme).ok_or(INVALID_URL)?;
+    match uri.as_str() {
+        Some(uri) if uri.starts_with("http:") => Err(INSECURE_SCHEME),
+        _ => Ok(uri),
+    }
+}
+
+// The date the file's contents expire, which must be later than now when it's known
+fn check_expires(text: &str, options: &ValidationOptions) -> Result<Value, &'static str> {
+    let seconds = instant(text).ok_or(INVALID_DATE)?;
+    if options.now.as_ref().is_some_and(|now| seconds <= now.seconds) {
+        return Err(EXPIRED);
+    }
+    datetime_validator::coerce_value(&Value::from(text), &Value::Null).ok_or(INVALID_DATE)
+}
+
+// A comma-separated list of language tags (RFC 5646), such as "en, fr-CA"; each subtag is one
+// to eight letters or digits, and the first is letters
+fn check_languages(text: &str) -> Result<Value, &'static str> {
+    let is_tag = |tag: &str| {
+        let mut subtags = tag.split('-');
+        let primary = subtags.next().unwrap_or_default();
+        (1..=8).contains(&primary.len())
+            && primary.bytes().all(|b| b.is_ascii_alphabetic())
+            && subtags.all(|subtag| (1..=8).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphanumeric()))
+    };
+    let tags: Vec<&str> = text.split(',').map(str::trim).collect();
+    if !tags.iter().all(|tag| is_tag(tag)) {
+        return Err(INVALID_LANGUAGE);
+    }
+    Ok(json!(tags))
+}
+
+// Field names are visible ASCII characters other than the colon, with no space before the colon
+fn is_field_name(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_graphic() && b != b':')
+}
+
+// The canonical form of a URL to compare Canonical URIs with; one the URL validator can't read
+// is compared as written
+fn canonical_url(text: &str) -> String {
+    check_uri(text.trim()).ok().and_then(|uri| uri.as_str().map(String::from)).unwrap_or_else(|| text.trim().to_string())
+}
+
+// Checks a security.txt file: "Name: value" lines, comments and blank lines, with a Contact and
+// an Expires, perhaps inside a cleartext signature. Returns the fields RFC 9116 defines, keyed
+// by their names in lowercase with underscores, and whether the file was signed
+fn check_securitytxt(text: &str, options: &ValidationOptions) -> Result<Value, Problem> {
+    let (lines, signed) = body_lines(text)?;
+    let mut found: Vec<Vec<(usize, Value)>> = FIELDS.iter().map(|_| Vec::new()).collect();
+    for (line, text) in lines {
+        if text.trim().is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let malformed = Problem { code: MALFORMED_LINE, line: Some(line), field: None };
+        let (name, value) = text.split_once(':').filter(|(name, _)| is_field_name(name)).ok_or(malformed)?;
+        let Some(index) = FIELDS.iter().position(|field| field.name.eq_ignore_ascii_case(name)) else {
+            continue;
+        };
+        let field = &FIELDS[index];
+        let problem = |code| Problem { code, line: Some(line), field: Some(field.name) };
+        if field.once && !found[index].is_empty() {
+            return Err(problem(DUPLICATE_FIELD));
+        }
+        let value = match field.kind {
+            Kind::Uri => check_uri(value.trim()),
+            Kind::Date => check_expires(value.trim(), options),
+            Kind::Languages => check_languages(value),
+        };
+        found[index].push((line, value.map_err(problem)?));
+    }
+
+    for (field, values) in FIELDS.iter().zip(&found) {
+        if field.required && values.is_empty() {
+            return Err(Problem { code: MISSING_FIELD, line: None, field: Some(field.name) });
+        }
+    }
+    let canonical = &found[FIELDS.iter().position(|field| field.name == "Canonical").unwrap_or_default()];
+    if let (Some(url), Some((line, _))) = (&options.url, canonical.first()) {
+        if !canonical.iter().any(|(_, uri)| uri.as_str() == Some(canonical_url(url).as_str())) {
+            return Err(Problem { code: CANONICAL_MISMATCH, line: Some(*line), field: Some("Canonical") });
+        }
+    }
+
+    let mut summary = serde_json::Map::new();
+    for (field, values) in FIELDS.iter().zip(found) {
+        let key = field.name.to_ascii_lowercase().replace('-', "_");
+        let mut values = values.into_iter().map(|(_, value)| value);
+        let value = if field.once { values.next().unwrap_or(Value::Null) } else { Value::Array(values.collect()) };
+        summary.insert(key, value);
+    }
+    summary.insert("signed".to_string(), Value::Bool(signed));
+    Ok(Value::Object(summary))
+}
+
+fn detail_securitytxt_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank value has no fields, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_securitytxt(text, options) {
+        Ok(summary) => ValidationResult::from(Some(summary)),
+        Err(problem) => ValidationResult::from(problem),
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_securitytxt_str directly
+fn detail_securitytxt(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_securitytxt_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the fields RFC 9116 defines, with URIs and dates in canonical form
+fn coerce_securitytxt(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_securitytxt(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_securitytxt(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_securitytxt(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_securitytxt(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_securitytxt(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_securitytxt(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_securitytxt(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_securitytxt(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_securitytxt(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_securitytxt(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_securitytxt_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECURITY_TXT: &str = "# Our security address\n\
+        Contact: mailto:security@example.com\n\
+        Contact: https://example.com/security/report\n\
+        Expires: 2030-01-01T00:00:00.000Z\n\
+        Encryption: openpgp4fpr:5f2de5521c63a801ab59ccb603d49de44b29100f\n\
+        Preferred-Languages: en, fr-CA\n\
+        Canonical: https://example.com/.well-known/security.txt\n\
+        Policy: https://example.com/security/policy\n\
+        X-Bug-Bounty: yes\n";
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn problem(text: &str) -> (Option<&'static str>, Option<usize>, Option<&'static str>) {
+        let result = detail_securitytxt_str(text, &ValidationOptions::default());
+        (result.error, result.line, result.field)
+    }
+
+    #[test]
+    fn test_valid_files() {
+        assert_eq!(
+            coerce_securitytxt(&json!(SECURITY_TXT), &ValidationOptions::default()),
+            Some(json!({
+                "acknowledgments": [],
+                "canonical": ["https://example.com/.well-known/security.txt"],
+                "contact": ["mailto:security@example.com", "https://example.com/security/report"],
+                "csaf": [],
+                "encryption": ["openpgp4fpr:5f2de5521c63a801ab59ccb603d49de44b29100f"],
+                "expires": "2030-01-01T00:00:00.000Z",
+                "hiring": [],
+                "policy": ["https://example.com/security/policy"],
+                "preferred_languages": ["en", "fr-CA"],
+                "signed": false,
+            }))
+        );
+
+        // Field names are case-insensitive, and CRLF line endings are fine
+        let minimal = "contact: tel:+1-201-555-0123\r\nEXPIRES: 2030-06-30T12:00:00+02:00\r\n";
+        let summary = coerce_securitytxt(&json!(minimal), &ValidationOptions::default()).unwrap();
+        assert_eq!((summary["contact"].clone(), summary["preferred_languages"].clone()), (json!(["tel:+1-201-555-0123"]), Value::Null));
+    }
+
+    #[test]
+    fn test_signed() {
+        let signed = format!(
+            "-----BEGIN PGP SIGNED MESSAGE-----\nHash: SHA256\n\n{SECURITY_TXT}- # a dash-escaped comment\n\
+             -----BEGIN PGP SIGNATURE-----\n\niHUEARYIAB0WIQSsP2kEdoKDVFpSg6u3rK+YCkjapwUCY9qRaQAKCRC3rK+YCkja\n-----END PGP SIGNATURE-----\n"
+        );
+        assert_eq!(coerce_securitytxt(&json!(signed), &ValidationOptions::default()).unwrap()["signed"], true);
+
+        assert_eq!(problem(&signed.replace("-----END PGP SIGNATURE-----\n", "")).0, Some(MALFORMED_SIGNATURE));
+        assert_eq!(problem(&signed.replace("Hash: SHA256\n\n", "Hash: SHA256\n")).0, Some(MALFORMED_SIGNATURE));
+        assert_eq!(problem(&signed.replace("- #", "-#")), (Some(MALFORMED_SIGNATURE), Some(13), None));
+        // The fields must still be inside the signed part
+        let unsigned = signed.replace("Expires: 2030-01-01T00:00:00.000Z\n", "");
+        assert_eq!(problem(&format!("{unsigned}Expires: 2030-01-01T00:00:00Z\n")), (Some(MISSING_FIELD), None, Some("Expires")));
+    }
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(problem(&SECURITY_TXT.replace("Contact: mailto:security@example.com\nContact: https://example.com/security/report\n", "")), (Some(MISSING_FIELD), None, Some("Contact")));
+        assert_eq!(problem(&SECURITY_TXT.replace("Expires: 2030-01-01T00:00:00.000Z\n", "")), (Some(MISSING_FIELD), None, Some("Expires")));
+        assert_eq!(problem(&format!("{SECURITY_TXT}Expires: 2031-01-01T00:00:00Z\n")), (Some(DUPLICATE_FIELD), Some(10), Some("Expires")));
+        assert_eq!(problem(&format!("{SECURITY_TXT}Preferred-Languages: de\n")), (Some(DUPLICATE_FIELD), Some(10), Some("Preferred-Languages")));
+
+        assert_eq!(problem(&SECURITY_TXT.replace("https://example.com/security/report", "http://example.com/security/report")), (Some(INSECURE_SCHEME), Some(3), Some("Contact")));
+        assert_eq!(problem(&SECURITY_TXT.replace("mailto:security@example.com", "security@example.com")), (Some(INVALID_URL), Some(2), Some("Contact")));
+        assert_eq!(problem(&SECURITY_TXT.replace("2030-01-01T00:00:00.000Z", "Tue, 1 Jan 2030 00:00:00 GMT")), (Some(INVALID_DATE), Some(4), Some("Expires")));
+        assert_eq!(problem(&SECURITY_TXT.replace("2030-01-01T00:00:00.000Z", "2030-01-01")), (Some(INVALID_DATE), Some(4), Some("Expires")));
+        assert_eq!(problem(&SECURITY_TXT.replace("en, fr-CA", "en, français")), (Some(INVALID_LANGUAGE), Some(6), Some("Preferred-Languages")));
+        assert_eq!(problem(&SECURITY_TXT.replace("en, fr-CA", "en,,fr")), (Some(INVALID_LANGUAGE), Some(6), Some("Preferred-Languages")));
+
+        assert_eq!(problem(&SECURITY_TXT.replace("Contact: mailto", "Contact : mailto")), (Some(MALFORMED_LINE), Some(2), None));
+        assert_eq!(problem(&SECURITY_TXT.replace("# Our security address", "Our security address")), (Some(MALFORMED_LINE), Some(1), None));
+        assert_eq!(problem(&SECURITY_TXT.replace("Policy:", " Policy:")), (Some(MALFORMED_LINE), Some(8), None));
+    }
+
+    #[test]
+    fn test_expiry() {
+        assert_eq!(instant("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(instant("2000-03-01T00:00:00Z"), Some(951_868_800));
+        assert_eq!(instant("2024-02-29T23:59:59.9-01:30"), Some(1_709_251_199 + 5_400));
+        assert_eq!(instant("2024-02-30T00:00:00Z"), None);
+
+        let now = options(json!({"now": "2029-12-31T23:00:00-02:00"}));
+        let result = detail_securitytxt_str(SECURITY_TXT, &now);
+        assert_eq!((result.error, result.line, result.field), (Some(EXPIRED), Some(4), Some("Expires")));
+        let now = options(json!({"now": "2029-12-31T23:59:59Z"}));
+        assert!(validate_securitytxt(&json!(SECURITY_TXT), &now));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"now": "yesterday"})).is_err());
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"now": "2024-01-01"})).is_err());
+    }
+
+    #[test]
+    fn test_canonical() {
+        let fetched = options(json!({"url": "HTTPS://Example.com/.well-known/security.txt"}));
+        assert!(validate_securitytxt(&json!(SECURITY_TXT), &fetched));
+        let elsewhere = options(json!({"url": "https://example.com/security.txt"}));
+        let result = detail_securitytxt_str(SECURITY_TXT, &elsewhere);
+        assert_eq!((result.error, result.line, result.field), (Some(CANONICAL_MISMATCH), Some(7), Some("Canonical")));
+
+        // A file without Canonical fields doesn't say where it belongs
+        let uncanonical = SECURITY_TXT.replace("Canonical: https://example.com/.well-known/security.txt\n", "");
+        assert!(validate_securitytxt(&json!(uncanonical), &elsewhere));
+        assert_eq!(problem(&SECURITY_TXT.replace("Canonical: https", "Canonical: http")), (Some(INSECURE_SCHEME), Some(7), Some("Canonical")));
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": SECURITY_TXT, "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let broken = SECURITY_TXT.replace("Expires", "Expiry");
+        assert_eq!(
+            messages::localize(&detail_securitytxt_str(&broken, &ValidationOptions::default()), &json!(broken), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "missing_field",
+                "field": "Expires",
+                "message": messages::message("missing_field", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_securitytxt_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+        assert!(!validate_securitytxt(&json!(42), &ValidationOptions::default()));
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [SECURITY_TXT], "options": {"now": "2024-01-01T00:00:00Z"}})).unwrap();
+        assert_eq!(batch.values.len(), 1);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [SECURITY_TXT, "", "Contact: mailto:a@example.com", "not a field"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
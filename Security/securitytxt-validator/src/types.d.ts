@@ -0,0 +1,54 @@
+// Types for the securitytxt validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The current time, as an RFC 3339 timestamp, which Expires must be later than; WASM has no clock, so the caller supplies it */
+    now?: string;
+    /** The URL the file was fetched from, which must be one of its Canonical URIs when it lists any */
+    url?: string;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    acknowledgments: string[];
+    canonical: string[];
+    contact: string[];
+    csaf: string[];
+    encryption: string[];
+    expires: string;
+    hiring: string[];
+    policy: string[];
+    preferred_languages: string[] | null;
+    signed: boolean;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The line the problem was found on, counting from 1; a missing field has none */
+    line?: number;
+    /** The field at fault, when there is one */
+    field?: string;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "malformed_line" | "malformed_signature" | "missing_field" | "duplicate_field" | "invalid_url" | "insecure_scheme" | "invalid_date" | "expired" | "invalid_language" | "canonical_mismatch" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,384 @@
+// ABOUTME: WASM component for S3 bucket names, for cloud-storage configuration forms
+// ABOUTME: Checks the naming rules: length, charset, no IP-like names, no adjacent periods, reserved affixes
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Accept periods, which break virtual-hosted-style requests over https and Transfer
+    // Acceleration
+    allow_periods: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { allow_periods: true }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const INVALID_LENGTH: &str = "invalid_length";
+const INVALID_CHARACTER: &str = "invalid_character";
+const INVALID_BOUNDARY: &str = "invalid_boundary";
+const ADJACENT_PERIODS: &str = "adjacent_periods";
+const IP_ADDRESS: &str = "ip_address";
+const RESERVED_PREFIX: &str = "reserved_prefix";
+const RESERVED_SUFFIX: &str = "reserved_suffix";
+const PERIOD_NOT_ALLOWED: &str = "period_not_allowed";
+
+// Affixes AWS reserves for punycode, its own tooling, access point aliases, Multi-Region
+// Access Points, directory buckets and table buckets
+const RESERVED_PREFIXES: &[&str] = &["xn--", "sthree-", "amzn-s3-demo-"];
+const RESERVED_SUFFIXES: &[&str] = &["-s3alias", "--ol-s3", ".mrap", "--x-s3", "--table-s3"];
+
+// Four dot-separated runs of digits, whether or not they make a valid address
+fn is_ip_like(name: &str) -> bool {
+    let labels: Vec<&str> = name.split('.').collect();
+    labels.len() == 4 && labels.iter().all(|label| !label.is_empty() && label.bytes().all(|b| b.is_ascii_digit()))
+}
+
+// The naming rules for general purpose buckets, checked in order so that each name gets the
+// most basic of its problems
+fn check_bucket(text: &str, options: &ValidationOptions) -> Result<String, &'static str> {
+    let name = text.trim();
+
+    if !(3..=63).contains(&name.len()) {
+        return Err(INVALID_LENGTH);
+    }
+    if !name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'.' || b == b'-') {
+        return Err(INVALID_CHARACTER);
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphanumeric()) || !name.ends_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err(INVALID_BOUNDARY);
+    }
+    if name.contains("..") {
+        return Err(ADJACENT_PERIODS);
+    }
+    if is_ip_like(name) {
+        return Err(IP_ADDRESS);
+    }
+    if RESERVED_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) {
+        return Err(RESERVED_PREFIX);
+    }
+    if RESERVED_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)) {
+        return Err(RESERVED_SUFFIX);
+    }
+    if !options.allow_periods && name.contains('.') {
+        return Err(PERIOD_NOT_ALLOWED);
+    }
+
+    Ok(name.to_string())
+}
+
+// Detailed validation; only strings can be valid, and a string's problem is reported
+fn detail_bucket(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str().map(|text| check_bucket(text, options)) {
+        Some(Ok(name)) => ValidationResult::from(Some(Value::String(name))),
+        Some(Err(code)) => ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the name without surrounding whitespace; bucket names are case-sensitive, so
+// uppercase letters are rejected rather than lowercased
+fn coerce_bucket(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    check_bucket(value.as_str()?, options).ok().map(Value::String)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_bucket(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_bucket(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_bucket(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_bucket(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_bucket(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_bucket(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_bucket(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_bucket(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_bucket(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    check_bucket(value, &ValidationOptions::default()).is_ok()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error(name: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_bucket(&json!(name), options).error
+    }
+
+    #[test]
+    fn test_valid_buckets() {
+        let defaults = ValidationOptions::default();
+
+        for name in ["my-bucket", "abc", "logs.example.com", "2024-backups", "a1b", &"a".repeat(63)] {
+            assert!(validate_bucket(&json!(name), &defaults), "{name}");
+        }
+        assert_eq!(coerce_bucket(&json!("  my-bucket "), &defaults), Some(json!("my-bucket")));
+    }
+
+    #[test]
+    fn test_invalid_buckets() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(error("ab", &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error(&"a".repeat(64), &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error("", &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error("My-Bucket", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("my_bucket", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("my bucket", &defaults), Some(INVALID_CHARACTER));
+        assert_eq!(error("-bucket", &defaults), Some(INVALID_BOUNDARY));
+        assert_eq!(error("bucket.", &defaults), Some(INVALID_BOUNDARY));
+        assert_eq!(error("my..bucket", &defaults), Some(ADJACENT_PERIODS));
+        assert_eq!(error("192.168.5.4", &defaults), Some(IP_ADDRESS));
+        assert_eq!(error("999.1.1.1", &defaults), Some(IP_ADDRESS));
+        assert_eq!(error("xn--bucket", &defaults), Some(RESERVED_PREFIX));
+        assert_eq!(error("sthree-bucket", &defaults), Some(RESERVED_PREFIX));
+        assert_eq!(error("bucket-s3alias", &defaults), Some(RESERVED_SUFFIX));
+        assert_eq!(error("bucket--x-s3", &defaults), Some(RESERVED_SUFFIX));
+
+        // Only strings can be bucket names, and other values have no specific reason
+        assert_eq!(detail_bucket(&json!(123), &defaults), ValidationResult::from(None));
+        assert!(validate_bucket(&json!("1.2.3"), &defaults));
+    }
+
+    #[test]
+    fn test_options() {
+        let no_periods: ValidationOptions = serde_json::from_value(json!({"allow_periods": false})).unwrap();
+        assert_eq!(error("logs.example.com", &no_periods), Some(PERIOD_NOT_ALLOWED));
+        assert!(validate_bucket(&json!("logs-example-com"), &no_periods));
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "abc"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "abc", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_bucket(&json!("my-bucket"), &defaults),
+            ValidationResult { valid: true, value: json!("my-bucket"), error: None }
+        );
+        assert_eq!(
+            detail_bucket(&json!("My-Bucket"), &defaults),
+            ValidationResult { valid: false, value: Value::Null, error: Some(INVALID_CHARACTER) }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["abc", 1], "options": {"allow_periods": false}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["my-bucket", " logs.example.com ", "My-Bucket", "10.0.0.1", "ab"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// Types for the s3bucket validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Accept periods, which break virtual-hosted-style requests over https and Transfer Acceleration (default: true) */
+    allow_periods?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "invalid_length" | "invalid_character" | "invalid_boundary" | "adjacent_periods" | "ip_address" | "reserved_prefix" | "reserved_suffix" | "period_not_allowed" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,364 @@
+// ABOUTME: WASM component for S3 object key names, for cloud-storage configuration forms
+// ABOUTME: Checks the key's length in bytes and rejects the characters AWS says to avoid or handle specially
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Only accept the characters AWS lists as safe: ASCII letters and digits, "/" and !-_.*'()
+    safe_only: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const INVALID_LENGTH: &str = "invalid_length";
+const CONTROL_CHARACTER: &str = "control_character";
+const DISCOURAGED_CHARACTER: &str = "discouraged_character";
+const RELATIVE_SEGMENT: &str = "relative_segment";
+const SPECIAL_CHARACTER: &str = "special_character";
+
+// Keys are at most 1024 bytes of UTF-8
+const MAX_KEY_BYTES: usize = 1024;
+
+// Characters AWS recommends avoiding in keys, since clients and URLs mangle them
+const DISCOURAGED: &[char] = &['\\', '{', '}', '^', '%', '`', '[', ']', '"', '<', '>', '~', '#', '|'];
+
+// Punctuation AWS lists as safe, besides the "/" that delimits prefixes
+const SAFE_PUNCTUATION: &[char] = &['/', '!', '-', '_', '.', '*', '\'', '(', ')'];
+
+// Checked in order so that each key gets the most basic of its problems; the key is used
+// exactly as given, since whitespace is significant in a key
+fn check_key(key: &str, options: &ValidationOptions) -> Result<String, &'static str> {
+    if key.is_empty() || key.len() > MAX_KEY_BYTES {
+        return Err(INVALID_LENGTH);
+    }
+    if key.chars().any(char::is_control) {
+        return Err(CONTROL_CHARACTER);
+    }
+    if key.contains(DISCOURAGED) {
+        return Err(DISCOURAGED_CHARACTER);
+    }
+    // S3 stores "." and ".." segments as they are, but consoles and SDKs that treat keys as
+    // paths resolve them, reaching a different object or failing
+    if key.split('/').any(|segment| segment == "." || segment == "..") {
+        return Err(RELATIVE_SEGMENT);
+    }
+    if options.safe_only && !key.chars().all(|c| c.is_ascii_alphanumeric() || SAFE_PUNCTUATION.contains(&c)) {
+        return Err(SPECIAL_CHARACTER);
+    }
+
+    Ok(key.to_string())
+}
+
+// Detailed validation; only strings can be valid, and a string's problem is reported
+fn detail_key(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str().map(|key| check_key(key, options)) {
+        Some(Ok(key)) => ValidationResult::from(Some(Value::String(key))),
+        Some(Err(code)) => ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// A valid key is its own canonical value
+fn coerce_key(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    check_key(value.as_str()?, options).ok().map(Value::String)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_key(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_key(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_key(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_key(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_key(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_key(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_key(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_key(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_key(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    check_key(value, &ValidationOptions::default()).is_ok()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error(key: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_key(&json!(key), options).error
+    }
+
+    #[test]
+    fn test_valid_keys() {
+        let defaults = ValidationOptions::default();
+
+        for key in ["photos/2024/beach.jpg", "report (final).pdf", "a", "données/été.csv", "logs/", "a&b=c;d", &"k".repeat(1024)] {
+            assert!(validate_key(&json!(key), &defaults), "{key}");
+        }
+
+        // Whitespace is part of the key
+        assert_eq!(coerce_key(&json!(" notes.txt "), &defaults), Some(json!(" notes.txt ")));
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(error("", &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error(&"k".repeat(1025), &defaults), Some(INVALID_LENGTH));
+        // The limit is in bytes, not characters
+        assert_eq!(error(&"é".repeat(513), &defaults), Some(INVALID_LENGTH));
+        assert_eq!(error("line\nbreak", &defaults), Some(CONTROL_CHARACTER));
+        assert_eq!(error("nul\u{0}", &defaults), Some(CONTROL_CHARACTER));
+        assert_eq!(error("c1\u{85}", &defaults), Some(CONTROL_CHARACTER));
+        for key in ["back\\slash", "50%", "a{b}", "hash#tag", "pipe|d", "tilde~", "<tag>", "quote\"d", "car^et", "tick`"] {
+            assert_eq!(error(key, &defaults), Some(DISCOURAGED_CHARACTER), "{key}");
+        }
+        assert_eq!(error("../secrets", &defaults), Some(RELATIVE_SEGMENT));
+        assert_eq!(error("a/./b", &defaults), Some(RELATIVE_SEGMENT));
+        assert_eq!(error(".", &defaults), Some(RELATIVE_SEGMENT));
+        assert!(validate_key(&json!("a/.hidden/..b"), &defaults));
+
+        assert_eq!(detail_key(&json!(42), &defaults), ValidationResult::from(None));
+    }
+
+    #[test]
+    fn test_options() {
+        let safe: ValidationOptions = serde_json::from_value(json!({"safe_only": true})).unwrap();
+
+        assert!(validate_key(&json!("photos/2024/beach_(1).jpg"), &safe));
+        assert_eq!(error("my file.txt", &safe), Some(SPECIAL_CHARACTER));
+        assert_eq!(error("a&b", &safe), Some(SPECIAL_CHARACTER));
+        assert_eq!(error("été.csv", &safe), Some(SPECIAL_CHARACTER));
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "a", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            detail_key(&json!("docs/readme.md"), &defaults),
+            ValidationResult { valid: true, value: json!("docs/readme.md"), error: None }
+        );
+        assert_eq!(
+            detail_key(&json!("100%.txt"), &defaults),
+            ValidationResult { valid: false, value: Value::Null, error: Some(DISCOURAGED_CHARACTER) }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["a", 1], "options": {"safe_only": true}})).unwrap();
+        assert_eq!(batch.values.len(), 2);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["photos/beach.jpg", "my file.txt", "50%", "../x", ""] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,1060 @@
+// ABOUTME: WASM component for printf and brace format strings, for localization platforms checking translations
+// ABOUTME: Parses printf, Rust and Python placeholders and checks them against the arguments, or a translation against its source
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+mod markup;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The placeholder syntax
+    style: Style,
+    // The kinds of the arguments the string is formatted with, in order, such as ["string",
+    // "integer"]; with named_arguments, the whole signature. Not checked when neither is set
+    arguments: Option<Vec<Kind>>,
+    // The kinds of the named arguments, such as {"count": "integer"}
+    named_arguments: Option<BTreeMap<String, Kind>>,
+    // Whether arguments without a placeholder are allowed, as when a translation leaves a
+    // count out
+    allow_unused: bool,
+    // The string the value translates; its placeholders are checked against the arguments,
+    // and the value must take the same ones, keep its HTML tags and add no control characters
+    source: Option<String>,
+}
+
+// The placeholder syntaxes format strings are parsed in
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Style {
+    // C's and POSIX's %s, %1$d and %.*f, and Python's %(name)s
+    #[default]
+    Printf,
+    // Rust's {}, {0}, {name:>8.2} and {:?}
+    Rust,
+    // Python's str.format, {}, {0}, {user.name} and {:,.2f}
+    Python,
+}
+
+// The kinds of value an argument may be, as far as placeholders tell them apart; "any" is
+// for placeholders that format whatever they're given
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Integer,
+    Float,
+    String,
+    Char,
+    Pointer,
+    Any,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // Where the placeholder at fault starts, counting lines and characters from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    // The argument at fault: its position, counting from 0, or its name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    argument: Option<Value>,
+    // The HTML tag at fault, such as "<b>" or "</a>"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    // Every problem with a translation, each as a result of its own, the first of which the
+    // fields above repeat
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    issues: Vec<ValidationResult>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+            line: None,
+            column: None,
+            argument: None,
+            tag: None,
+            issues: Vec::new(),
+        }
+    }
+}
+
+// An error code, the byte offset of the placeholder it was found at, if it's about one, and
+// the argument it's about
+type Problem = (&'static str, Option<usize>, Option<Argument>);
+
+// Error codes reported in detailed results
+const INVALID_PLACEHOLDER: &str = "invalid_placeholder";
+const UNSAFE_CONVERSION: &str = "unsafe_conversion";
+const MIXED_NUMBERING: &str = "mixed_numbering";
+const UNKNOWN_ARGUMENT: &str = "unknown_argument";
+const UNUSED_ARGUMENT: &str = "unused_argument";
+const ARGUMENT_TYPE_MISMATCH: &str = "argument_type_mismatch";
+const INVALID_SOURCE: &str = "invalid_source";
+
+// An argument a placeholder formats: by position, counting from 0, or by name
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Argument {
+    Position(usize),
+    Name(String),
+}
+
+impl From<Argument> for Value {
+    fn from(argument: Argument) -> Self {
+        match argument {
+            Argument::Position(position) => Value::from(position),
+            Argument::Name(name) => Value::from(name),
+        }
+    }
+}
+
+// Where a placeholder takes its argument from: the next one in order, or one it names
+enum Reference {
+    Next,
+    Explicit(Argument),
+}
+
+// An argument a placeholder takes, and the kind of value it formats it as
+type Use = (Reference, Kind);
+
+// A placeholder's argument, the kind of value it formats, and where it starts; a width or
+// precision given by an argument is a placeholder of its own, for an integer
+struct Placeholder {
+    argument: Argument,
+    kind: Kind,
+    offset: usize,
+}
+
+// Numbers placeholders' arguments, taking the next position for those that don't name one.
+// printf and Python can't mix the two, nor printf positions and names; Rust can
+struct Numbering {
+    next: usize,
+    implicit: bool,
+    explicit: bool,
+    named: bool,
+}
+
+impl Numbering {
+    fn new() -> Self {
+        Numbering { next: 0, implicit: false, explicit: false, named: false }
+    }
+
+    fn resolve(&mut self, reference: Reference) -> Argument {
+        match reference {
+            Reference::Next => {
+                self.implicit = true;
+                self.next += 1;
+                Argument::Position(self.next - 1)
+            }
+            Reference::Explicit(argument) => {
+                match argument {
+                    Argument::Position(_) => self.explicit = true,
+                    Argument::Name(_) => self.named = true,
+                }
+                argument
+            }
+        }
+    }
+}
+
+// The argument a placeholder names: a number, or an identifier (Unicode letters, digits and
+// "_", not starting with a digit)
+fn argument_name(text: &str) -> Option<Argument> {
+    if !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return text.parse().ok().map(Argument::Position);
+    }
+    let mut chars = text.chars();
+    let first = chars.next()?;
+    ((first.is_alphabetic() || first == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')).then(|| Argument::Name(text.to_string()))
+}
+
+// Splits off the longest prefix of ASCII digits
+fn digits(text: &str) -> (&str, &str) {
+    text.split_at(text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len()))
+}
+
+// A printf argument position, "n$", counting from 1, or None when there isn't one
+fn printf_position(text: &str) -> Result<Option<(usize, &str)>, &'static str> {
+    let (number, rest) = digits(text);
+    match rest.strip_prefix('$') {
+        Some(rest) if !number.is_empty() => match number.parse::<usize>() {
+            Ok(position) if position > 0 => Ok(Some((position - 1, rest))),
+            _ => Err(INVALID_PLACEHOLDER),
+        },
+        _ => Ok(None),
+    }
+}
+
+// The kind of value a printf conversion formats; %n writes to memory rather than formatting
+// anything, and is refused
+fn printf_kind(conversion: char) -> Result<Kind, &'static str> {
+    match conversion {
+        'd' | 'i' | 'o' | 'u' | 'x' | 'X' => Ok(Kind::Integer),
+        'e' | 'E' | 'f' | 'F' | 'g' | 'G' | 'a' | 'A' => Ok(Kind::Float),
+        's' => Ok(Kind::String),
+        'c' => Ok(Kind::Char),
+        'p' => Ok(Kind::Pointer),
+        // Python's repr() and ascii() conversions format anything
+        'r' => Ok(Kind::Any),
+        'n' => Err(UNSAFE_CONVERSION),
+        _ => Err(INVALID_PLACEHOLDER),
+    }
+}
+
+// Reads a width or precision: digits, or "*" for an argument, perhaps at a position "*n$"
+fn printf_count<'a>(text: &'a str, references: &mut Vec<Use>) -> Result<&'a str, &'static str> {
+    match text.strip_prefix('*') {
+        Some(rest) => match printf_position(rest)? {
+            Some((position, rest)) => {
+                references.push((Reference::Explicit(Argument::Position(position)), Kind::Integer));
+                Ok(rest)
+            }
+            None => {
+                references.push((Reference::Next, Kind::Integer));
+                Ok(rest)
+            }
+        },
+        None => Ok(digits(text).1),
+    }
+}
+
+// A printf conversion after its "%": C's and POSIX's, "%[n$][flags][width][.precision]
+// [length]conversion", or Python's, which may take a mapping key, "%(name)s". Returns the
+// arguments it takes, a width and precision from "*" before the value, and its length
+fn printf_conversion(spec: &str) -> Result<(Vec<Use>, usize), &'static str> {
+    let mut references = Vec::new();
+    let mut rest = spec;
+    let mut value = Reference::Next;
+    if let Some(key) = rest.strip_prefix('(') {
+        let end = key.find(')').ok_or(INVALID_PLACEHOLDER)?;
+        value = Reference::Explicit(Argument::Name(key[..end].to_string()));
+        rest = &key[end + 1..];
+    } else if let Some((position, after)) = printf_position(rest)? {
+        value = Reference::Explicit(Argument::Position(position));
+        rest = after;
+    }
+    rest = rest.trim_start_matches(['-', '+', ' ', '#', '0', '\'', 'I']);
+    rest = printf_count(rest, &mut references)?;
+    if let Some(precision) = rest.strip_prefix('.') {
+        rest = printf_count(precision, &mut references)?;
+    }
+    // Python's mapping keys can't be mixed with "*"
+    if matches!(value, Reference::Explicit(Argument::Name(_))) && !references.is_empty() {
+        return Err(INVALID_PLACEHOLDER);
+    }
+    for length in ["hh", "ll", "h", "l", "j", "z", "t", "L", "q"] {
+        if let Some(after) = rest.strip_prefix(length) {
+            rest = after;
+            break;
+        }
+    }
+    let conversion = rest.chars().next().ok_or(INVALID_PLACEHOLDER)?;
+    references.push((value, printf_kind(conversion)?));
+    Ok((references, spec.len() - rest.len() + conversion.len_utf8()))
+}
+
+// The placeholders of a printf format string; "%%" is a literal percent sign
+fn printf_placeholders(text: &str) -> Result<Vec<Placeholder>, Problem> {
+    let mut placeholders = Vec::new();
+    let mut numbering = Numbering::new();
+    let mut position = 0;
+    while let Some(index) = text[position..].find('%') {
+        let offset = position + index;
+        let spec = &text[offset + 1..];
+        if spec.starts_with('%') {
+            position = offset + 2;
+            continue;
+        }
+        let (references, length) = printf_conversion(spec).map_err(|code| (code, Some(offset), None))?;
+        for (reference, kind) in references {
+            let argument = numbering.resolve(reference);
+            placeholders.push(Placeholder { argument, kind, offset });
+        }
+        if (numbering.implicit && numbering.explicit) || (numbering.named && (numbering.implicit || numbering.explicit)) {
+            return Err((MIXED_NUMBERING, Some(offset), None));
+        }
+        position = offset + 1 + length;
+    }
+    Ok(placeholders)
+}
+
+// The end of a replacement field that starts at a "{", where "{{" and "}}" are literal braces
+// outside fields; Python's fields may hold fields of their own in their format specs
+fn field_end(text: &str, start: usize, nesting: bool) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in text[start..].char_indices() {
+        match c {
+            '{' if depth == 0 || nesting => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + index);
+                }
+            }
+            '{' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+// The fields of a brace format string, with their byte offsets; "{{" and "}}" are literal
+// braces, and any other lone brace is a broken placeholder
+fn brace_fields(text: &str, nesting: bool) -> Result<Vec<(usize, &str)>, Problem> {
+    let mut fields = Vec::new();
+    let mut position = 0;
+    while let Some(index) = text[position..].find(['{', '}']) {
+        let offset = position + index;
+        let doubled = |brace: &str| text[offset..].starts_with(brace);
+        if doubled("{{") || doubled("}}") {
+            position = offset + 2;
+            continue;
+        }
+        if text[offset..].starts_with('}') {
+            return Err((INVALID_PLACEHOLDER, Some(offset), None));
+        }
+        let end = field_end(text, offset, nesting).ok_or((INVALID_PLACEHOLDER, Some(offset), None))?;
+        fields.push((offset, &text[offset + 1..end]));
+        position = end + 1;
+    }
+    Ok(fields)
+}
+
+// Splits the fill and alignment off a format spec: an alignment character, perhaps after
+// any fill character
+fn strip_alignment<'a>(spec: &'a str, alignments: &str) -> &'a str {
+    let mut chars = spec.chars();
+    match (chars.next(), chars.next()) {
+        (Some(fill), Some(align)) if alignments.contains(align) => &spec[fill.len_utf8() + 1..],
+        (Some(align), _) if alignments.contains(align) => &spec[1..],
+        _ => spec,
+    }
+}
+
+// A Rust width or precision: a number, or an argument's position or name followed by "$"
+fn rust_count<'a>(text: &'a str, references: &mut Vec<Use>) -> Result<&'a str, &'static str> {
+    let end = text.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(text.len());
+    match text[end..].strip_prefix('$') {
+        Some(rest) => {
+            let argument = argument_name(&text[..end]).ok_or(INVALID_PLACEHOLDER)?;
+            references.push((Reference::Explicit(argument), Kind::Integer));
+            Ok(rest)
+        }
+        None => Ok(digits(text).1),
+    }
+}
+
+// A Rust field, "{argument:spec}", where the spec is "[[fill]align][sign]['#']['0'][width]
+// ['.' precision][type]" and the precision may be "*", taking the next argument before the
+// value's. Returns the arguments it takes
+fn rust_field(field: &str) -> Result<Vec<Use>, &'static str> {
+    let (name, spec) = field.split_once(':').unwrap_or((field, ""));
+    let value = match name {
+        "" => Reference::Next,
+        name => Reference::Explicit(argument_name(name).ok_or(INVALID_PLACEHOLDER)?),
+    };
+    let mut references = Vec::new();
+    let mut rest = strip_alignment(spec, "<^>");
+    rest = rest.strip_prefix(['+', '-']).unwrap_or(rest);
+    rest = rest.strip_prefix('#').unwrap_or(rest);
+    if rest.starts_with('0') && !rest[1..].starts_with('$') {
+        rest = &rest[1..];
+    }
+    rest = rust_count(rest, &mut references)?;
+    if let Some(precision) = rest.strip_prefix('.') {
+        rest = match precision.strip_prefix('*') {
+            Some(after) => {
+                references.push((Reference::Next, Kind::Integer));
+                after
+            }
+            None => rust_count(precision, &mut references)?,
+        };
+    }
+    let kind = match rest {
+        "" | "?" | "x?" | "X?" => Kind::Any,
+        "x" | "X" | "o" | "b" => Kind::Integer,
+        "e" | "E" => Kind::Float,
+        "p" => Kind::Pointer,
+        _ => return Err(INVALID_PLACEHOLDER),
+    };
+    references.push((value, kind));
+    Ok(references)
+}
+
+// A Python field, "{name!conversion:spec}", where the name may be followed by attributes and
+// indexes, as in "{user.name}" or "{0[key]}", and the spec is "[[fill]align][sign]['z']['#']
+// ['0'][width][grouping]['.' precision][type]", unless it holds fields of its own. Returns the
+// arguments it takes, its own first, with the specs of those it holds
+fn python_field(field: &str) -> Result<(Vec<Use>, &str), &'static str> {
+    let name_end = field.find(['!', ':']).unwrap_or(field.len());
+    let (path, rest) = field.split_at(name_end);
+    let name_length = path.find(['.', '[']).unwrap_or(path.len());
+    let (name, mut accessors) = path.split_at(name_length);
+    while !accessors.is_empty() {
+        accessors = match accessors.strip_prefix('.') {
+            Some(attribute) => {
+                let end = attribute.find(['.', '[']).unwrap_or(attribute.len());
+                if !matches!(argument_name(&attribute[..end]), Some(Argument::Name(_))) {
+                    return Err(INVALID_PLACEHOLDER);
+                }
+                &attribute[end..]
+            }
+            None => {
+                let index = accessors.strip_prefix('[').ok_or(INVALID_PLACEHOLDER)?;
+                let end = index.find(']').filter(|&end| end > 0).ok_or(INVALID_PLACEHOLDER)?;
+                &index[end + 1..]
+            }
+        };
+    }
+    let value = match name {
+        "" => Reference::Next,
+        name => Reference::Explicit(argument_name(name).ok_or(INVALID_PLACEHOLDER)?),
+    };
+    let (conversion, spec) = match rest.strip_prefix('!') {
+        Some(conversion) => {
+            let (conversion, spec) = conversion.split_at(conversion.find(':').unwrap_or(conversion.len()));
+            if !matches!(conversion, "r" | "s" | "a") {
+                return Err(INVALID_PLACEHOLDER);
+            }
+            (true, spec.strip_prefix(':').unwrap_or(spec))
+        }
+        None => (false, rest.strip_prefix(':').unwrap_or(rest)),
+    };
+    // A spec with fields of its own is only known once they're filled in
+    let kind = if spec.contains('{') {
+        Kind::Any
+    } else {
+        let mut rest = strip_alignment(spec, "<>=^");
+        rest = rest.strip_prefix(['+', '-', ' ']).unwrap_or(rest);
+        for flag in ['z', '#', '0'] {
+            rest = rest.strip_prefix(flag).unwrap_or(rest);
+        }
+        rest = digits(rest).1;
+        rest = rest.strip_prefix([',', '_']).unwrap_or(rest);
+        if let Some(precision) = rest.strip_prefix('.') {
+            let (number, after) = digits(precision);
+            if number.is_empty() {
+                return Err(INVALID_PLACEHOLDER);
+            }
+            rest = after;
+        }
+        match rest {
+            "" => Kind::Any,
+            "b" | "c" | "d" | "o" | "x" | "X" => Kind::Integer,
+            "e" | "E" | "f" | "F" | "g" | "G" | "%" => Kind::Float,
+            // Locale-aware, for either kind of number
+            "n" => Kind::Any,
+            "s" => Kind::String,
+            _ => return Err(INVALID_PLACEHOLDER),
+        }
+    };
+    // After a conversion, or through attributes and indexes, the argument itself can be anything
+    let kind = match kind {
+        _ if conversion && !matches!(kind, Kind::Any | Kind::String) => return Err(INVALID_PLACEHOLDER),
+        _ if conversion || path.len() > name.len() => Kind::Any,
+        kind => kind,
+    };
+    Ok((vec![(value, kind)], spec))
+}
+
+// The placeholders of a Rust or Python brace format string
+fn brace_placeholders(text: &str, style: Style) -> Result<Vec<Placeholder>, Problem> {
+    let mut placeholders = Vec::new();
+    let mut numbering = Numbering::new();
+    for (offset, field) in brace_fields(text, style == Style::Python)? {
+        let invalid = |code| (code, Some(offset), None);
+        let references = match style {
+            Style::Python => {
+                let (mut references, spec) = python_field(field).map_err(invalid)?;
+                // Fields in a spec, as in "{:{width}}", take arguments after the field's own
+                for (_, nested) in brace_fields(spec, false)? {
+                    references.extend(python_field(nested).map_err(invalid)?.0.into_iter().map(|(reference, _)| (reference, Kind::Any)));
+                }
+                references
+            }
+            _ => rust_field(field).map_err(invalid)?,
+        };
+        for (reference, kind) in references {
+            let argument = numbering.resolve(reference);
+            placeholders.push(Placeholder { argument, kind, offset });
+        }
+        if style == Style::Python && numbering.implicit && numbering.explicit {
+            return Err((MIXED_NUMBERING, Some(offset), None));
+        }
+    }
+    Ok(placeholders)
+}
+
+// Whether a placeholder for one kind of value can format an argument of another; "any" fits
+// everything
+fn fits(expected: Kind, used: Kind) -> bool {
+    expected == Kind::Any || used == Kind::Any || expected == used
+}
+
+// The placeholders of a format string in a style
+fn placeholders(text: &str, style: Style) -> Result<Vec<Placeholder>, Problem> {
+    match style {
+        Style::Printf => printf_placeholders(text),
+        style => brace_placeholders(text, style),
+    }
+}
+
+// The kind each argument is used as, across the placeholders that take it: the most specific
+// one, since "%1$d" and "%1$s" can't both format the same argument
+fn usage(placeholders: &[Placeholder]) -> Result<BTreeMap<Argument, Kind>, Problem> {
+    let mut usage = BTreeMap::new();
+    for placeholder in placeholders {
+        let kind = usage.entry(placeholder.argument.clone()).or_insert(Kind::Any);
+        if !fits(*kind, placeholder.kind) {
+            return Err((ARGUMENT_TYPE_MISMATCH, Some(placeholder.offset), Some(placeholder.argument.clone())));
+        }
+        if *kind == Kind::Any {
+            *kind = placeholder.kind;
+        }
+    }
+    Ok(usage)
+}
+
+// The argument signature a format string implies, in the form the options take, with
+// positional arguments no placeholder takes as "any"
+fn signature(usage: &BTreeMap<Argument, Kind>) -> Value {
+    let mut arguments = Vec::new();
+    let mut named_arguments = BTreeMap::new();
+    // Positions come first, in order
+    for (argument, &kind) in usage {
+        match argument {
+            Argument::Position(position) => {
+                arguments.resize(position + 1, Kind::Any);
+                arguments[*position] = kind;
+            }
+            Argument::Name(name) => {
+                named_arguments.insert(name.clone(), kind);
+            }
+        }
+    }
+    json!({ "arguments": arguments, "named_arguments": named_arguments })
+}
+
+// When the options give the arguments a string is formatted with, checks that each
+// placeholder's argument is one of them and of a kind it can format, and that every argument
+// has a placeholder
+fn check_arguments(placeholders: &[Placeholder], options: &ValidationOptions) -> Result<(), Problem> {
+    if options.arguments.is_none() && options.named_arguments.is_none() {
+        return Ok(());
+    }
+    let positional = options.arguments.as_deref().unwrap_or_default();
+    let named = options.named_arguments.clone().unwrap_or_default();
+    for placeholder in placeholders {
+        let expected = match &placeholder.argument {
+            Argument::Position(position) => positional.get(*position),
+            Argument::Name(name) => named.get(name),
+        };
+        let problem = |code| Err((code, Some(placeholder.offset), Some(placeholder.argument.clone())));
+        match expected {
+            None => return problem(UNKNOWN_ARGUMENT),
+            Some(&kind) if !fits(kind, placeholder.kind) => return problem(ARGUMENT_TYPE_MISMATCH),
+            Some(_) => {}
+        }
+    }
+    if !options.allow_unused {
+        let used = |argument: Argument| placeholders.iter().any(|placeholder| placeholder.argument == argument);
+        let unused = (0..positional.len()).map(Argument::Position).chain(named.into_keys().map(Argument::Name)).find(|argument| !used(argument.clone()));
+        if let Some(argument) = unused {
+            return Err((UNUSED_ARGUMENT, None, Some(argument)));
+        }
+    }
+    Ok(())
+}
+
+// Checks a format string's placeholders, and them against the arguments when the options give
+// them. Returns the signature the string implies
+fn check_format(text: &str, options: &ValidationOptions) -> Result<Value, Problem> {
+    let placeholders = placeholders(text, options.style)?;
+    let usage = usage(&placeholders)?;
+    check_arguments(&placeholders, options)?;
+    Ok(signature(&usage))
+}
+
+// Checks a translation against the string it translates. The source's placeholders are
+// checked against the arguments, as check_format does; then each of the translation's must
+// take an argument the source's take, as a kind they can format, and unless allow_unused is
+// set it must take every one. It must keep the source's HTML tags, in any order, and add no
+// control characters. Returns the signature the translation implies, or every problem with it
+// in the order they turn up, those about no place in it last
+fn check_translation(text: &str, source: &str, options: &ValidationOptions) -> Result<Value, Vec<ValidationResult>> {
+    let expected = placeholders(source, options.style)
+        .and_then(|placeholders| {
+            check_arguments(&placeholders, options)?;
+            usage(&placeholders)
+        })
+        .map_err(|_| vec![problem_result(text, (INVALID_SOURCE, None, None))])?;
+    let placeholders = placeholders(text, options.style).map_err(|problem| vec![problem_result(text, problem)])?;
+    let used = usage(&placeholders).map_err(|problem| vec![problem_result(text, problem)])?;
+
+    let mut issues = Vec::new();
+    let mut reported = Vec::new();
+    for placeholder in &placeholders {
+        let code = match expected.get(&placeholder.argument) {
+            None => UNKNOWN_ARGUMENT,
+            Some(&kind) if !fits(kind, placeholder.kind) => ARGUMENT_TYPE_MISMATCH,
+            Some(_) => continue,
+        };
+        // Once for each argument, at its first placeholder
+        if !reported.contains(&placeholder.argument) {
+            reported.push(placeholder.argument.clone());
+            issues.push(problem_result(text, (code, Some(placeholder.offset), Some(placeholder.argument.clone()))));
+        }
+    }
+    if !options.allow_unused {
+        let unused = expected.keys().filter(|argument| !used.contains_key(*argument));
+        issues.extend(unused.map(|argument| problem_result(text, (UNUSED_ARGUMENT, None, Some(argument.clone())))));
+    }
+    for (code, offset, tag) in markup::tag_problems(source, text) {
+        issues.push(ValidationResult { tag: Some(tag), ..problem_result(text, (code, offset, None)) });
+    }
+    let controls = markup::introduced_controls(source, text).into_iter();
+    issues.extend(controls.map(|offset| problem_result(text, (markup::CONTROL_CHARACTER, Some(offset), None))));
+
+    if issues.is_empty() {
+        return Ok(signature(&used));
+    }
+    issues.sort_by_key(|issue| (issue.line.is_none(), issue.line, issue.column));
+    Err(issues)
+}
+
+// A failed result for a problem, placing it by line and column
+fn problem_result(text: &str, (code, offset, argument): Problem) -> ValidationResult {
+    let position = offset.map(|offset| line_and_column(text, offset));
+    ValidationResult {
+        error: Some(code),
+        line: position.map(|(line, _)| line),
+        column: position.map(|(_, column)| column),
+        argument: argument.map(Value::from),
+        ..ValidationResult::from(None)
+    }
+}
+
+// The line and column of a byte offset, counting from 1, with columns in characters
+fn line_and_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
+fn detail_format_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank string has nothing to format, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match &options.source {
+        Some(source) => match check_translation(text, source, options) {
+            Ok(signature) => ValidationResult::from(Some(signature)),
+            Err(issues) => {
+                let first = issues[0].clone();
+                ValidationResult { issues, ..first }
+            }
+        },
+        None => match check_format(text, options) {
+            Ok(signature) => ValidationResult::from(Some(signature)),
+            Err(problem) => problem_result(text, problem),
+        },
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_format_str directly
+fn detail_format(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_format_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the argument signature the string implies
+fn coerce_format(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_format(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_format(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_format(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_format(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_format(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_format(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_format(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_format(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_format(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_format(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_format_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(options: Value) -> ValidationOptions {
+        serde_json::from_value(options).unwrap()
+    }
+
+    fn problem(text: &str, options: &ValidationOptions) -> (Option<&'static str>, Option<usize>, Option<usize>, Option<Value>) {
+        let result = detail_format_str(text, options);
+        (result.error, result.line, result.column, result.argument)
+    }
+
+    fn error(text: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_format_str(text, options).error
+    }
+
+    fn signature(text: &str, options: &ValidationOptions) -> Value {
+        coerce_format(&json!(text), options).unwrap_or(Value::Null)
+    }
+
+    #[test]
+    fn test_printf() {
+        let printf = ValidationOptions::default();
+        assert_eq!(signature("%s has %d new messages (100%%)", &printf), json!({"arguments": ["string", "integer"], "named_arguments": {}}));
+        assert_eq!(signature("%-*.*f|%lld|%c|%p|%#010x", &printf), json!({"arguments": ["integer", "integer", "float", "integer", "char", "pointer", "integer"], "named_arguments": {}}));
+        assert_eq!(signature("%2$s a envoyé %1$d fichiers, %1$d", &printf), json!({"arguments": ["integer", "string"], "named_arguments": {}}));
+        assert_eq!(signature("%(count)d items in %(folder)s", &printf), json!({"arguments": [], "named_arguments": {"count": "integer", "folder": "string"}}));
+        assert_eq!(signature("%3$s", &printf), json!({"arguments": ["any", "any", "string"], "named_arguments": {}}));
+
+        assert_eq!(problem("Done\n%s wrote %n bytes", &printf), (Some(UNSAFE_CONVERSION), Some(2), Some(10), None));
+        assert_eq!(problem("%1$s and %s", &printf), (Some(MIXED_NUMBERING), Some(1), Some(10), None));
+        assert_eq!(error("%(name)s and %s", &printf), Some(MIXED_NUMBERING));
+        assert_eq!(error("100%", &printf), Some(INVALID_PLACEHOLDER));
+        assert_eq!(error("%y", &printf), Some(INVALID_PLACEHOLDER));
+        assert_eq!(error("%0$s", &printf), Some(INVALID_PLACEHOLDER));
+        assert_eq!(error("%(name", &printf), Some(INVALID_PLACEHOLDER));
+        assert_eq!(problem("%1$d and %1$s", &printf), (Some(ARGUMENT_TYPE_MISMATCH), Some(1), Some(10), Some(json!(0))));
+    }
+
+    #[test]
+    fn test_brace_styles() {
+        let rust = options(json!({"style": "rust"}));
+        assert_eq!(signature("{} has {count} items, {0:>8.2} {:x} {{literal}}", &rust), json!({"arguments": ["any", "integer"], "named_arguments": {"count": "any"}}));
+        assert_eq!(signature("{:.*} {:width$} {:1$}", &rust), json!({"arguments": ["integer", "integer", "any", "any"], "named_arguments": {"width": "integer"}}));
+        assert_eq!(signature("{:?} {0:#x?} {:e}", &rust), json!({"arguments": ["any", "float"], "named_arguments": {}}));
+        assert_eq!(problem("Hi {name", &rust), (Some(INVALID_PLACEHOLDER), Some(1), Some(4), None));
+        assert_eq!(error("Hi name}", &rust), Some(INVALID_PLACEHOLDER));
+        assert_eq!(error("{:s}", &rust), Some(INVALID_PLACEHOLDER));
+        assert_eq!(error("{my-name}", &rust), Some(INVALID_PLACEHOLDER));
+
+        let python = options(json!({"style": "python"}));
+        assert_eq!(signature("{} of {}: {:,.2f} {!r:>10}", &python), json!({"arguments": ["any", "any", "float", "any"], "named_arguments": {}}));
+        assert_eq!(signature("{user.name} has {0[items]} and {count:d}", &python), json!({"arguments": ["any"], "named_arguments": {"count": "integer", "user": "any"}}));
+        assert_eq!(signature("{:{width}.{precision}f}", &python), json!({"arguments": ["any"], "named_arguments": {"precision": "any", "width": "any"}}));
+        assert_eq!(error("{} and {0}", &python), Some(MIXED_NUMBERING));
+        assert_eq!(error("{!x}", &python), Some(INVALID_PLACEHOLDER));
+        assert_eq!(error("{!r:d}", &python), Some(INVALID_PLACEHOLDER));
+        assert_eq!(error("{user.}", &python), Some(INVALID_PLACEHOLDER));
+        assert_eq!(error("{0:d} {0:s}", &python), Some(ARGUMENT_TYPE_MISMATCH));
+    }
+
+    #[test]
+    fn test_signature() {
+        let expected = options(json!({"arguments": ["string", "integer"]}));
+        assert!(validate_format(&json!("%2$d fichiers de %1$s"), &expected));
+        assert_eq!(problem("%s has %f", &expected), (Some(ARGUMENT_TYPE_MISMATCH), Some(1), Some(8), Some(json!(1))));
+        assert_eq!(problem("%s has %d %s", &expected), (Some(UNKNOWN_ARGUMENT), Some(1), Some(11), Some(json!(2))));
+        assert_eq!(problem("%s has new messages", &expected), (Some(UNUSED_ARGUMENT), None, None, Some(json!(1))));
+        assert!(validate_format(&json!("%s has new messages"), &options(json!({"arguments": ["string", "integer"], "allow_unused": true}))));
+
+        let named = options(json!({"style": "python", "named_arguments": {"count": "integer", "name": "any"}}));
+        assert!(validate_format(&json!("{name} : {count:d}"), &named));
+        assert_eq!(error("{name} : {total}", &named), Some(UNKNOWN_ARGUMENT));
+        assert_eq!(detail_format_str("{name}", &named).argument, Some(json!("count")));
+        assert_eq!(error("{}", &named), Some(UNKNOWN_ARGUMENT));
+
+        // A string's signature checks its translations
+        let source = signature("{count:d} files in {folder}", &options(json!({"style": "python"})));
+        let mut translation = source.clone();
+        translation["style"] = json!("python");
+        assert!(validate_format(&json!("{folder} contient {count:d} fichiers"), &options(translation.clone())));
+        assert_eq!(error("{folder} contient {count:.1f} fichiers", &options(translation)), Some(ARGUMENT_TYPE_MISMATCH));
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"arguments": ["number"]})).is_err());
+    }
+
+    #[test]
+    fn test_translation() {
+        let source = options(json!({"source": "<b>%s</b> has %d new messages"}));
+        // Tags may wrap other words in a translation
+        assert!(validate_format(&json!("%s a <b>%d</b> nouveaux messages"), &source));
+        assert_eq!(signature("<b>%1$s</b> a %2$d nouveaux messages", &source), json!({"arguments": ["string", "integer"], "named_arguments": {}}));
+
+        let result = detail_format_str("%s a\u{7} %f nouveaux <i>messages</i> %3$s", &source);
+        assert_eq!((result.error, result.line, result.column), (Some(MIXED_NUMBERING), Some(1), Some(35)));
+        assert_eq!(result.issues.len(), 1);
+
+        let result = detail_format_str("<b>%1$s</b>\u{7} a %2$f <i>nouveaux</i> messages %3$s", &source);
+        let issues: Vec<_> = result.issues.iter().map(|issue| (issue.error, issue.column, issue.argument.clone(), issue.tag.clone())).collect();
+        assert_eq!(
+            issues,
+            vec![
+                (Some(markup::CONTROL_CHARACTER), Some(12), None, None),
+                (Some(ARGUMENT_TYPE_MISMATCH), Some(16), Some(json!(1)), None),
+                (Some(markup::EXTRA_HTML_TAG), Some(21), None, Some("<i>".to_string())),
+                (Some(markup::EXTRA_HTML_TAG), Some(32), None, Some("</i>".to_string())),
+                (Some(UNKNOWN_ARGUMENT), Some(46), Some(json!(2)), None),
+            ]
+        );
+        assert_eq!((result.error, result.column), (Some(markup::CONTROL_CHARACTER), Some(12)));
+
+        // A dropped argument is allowed only with allow_unused; a dropped tag never is
+        let result = detail_format_str("%s a des messages", &source);
+        let issues: Vec<_> = result.issues.iter().map(|issue| (issue.error, issue.line, issue.argument.clone(), issue.tag.clone())).collect();
+        assert_eq!(
+            issues,
+            vec![
+                (Some(UNUSED_ARGUMENT), None, Some(json!(1)), None),
+                (Some(markup::MISSING_HTML_TAG), None, None, Some("<b>".to_string())),
+                (Some(markup::MISSING_HTML_TAG), None, None, Some("</b>".to_string())),
+            ]
+        );
+        let lenient = options(json!({"source": "<b>%s</b> has %d new messages", "allow_unused": true}));
+        assert!(validate_format(&json!("<b>%s</b> a des messages"), &lenient));
+
+        // The source is checked against the arguments, as the value is without one
+        let python = options(json!({"style": "python", "source": "{count:d} files in {folder}", "named_arguments": {"count": "integer", "folder": "string"}}));
+        assert!(validate_format(&json!("{folder} contient {count} fichiers"), &python));
+        assert_eq!(error("{folder} contient {count:.1f} fichiers", &python), Some(ARGUMENT_TYPE_MISMATCH));
+        let python = options(json!({"style": "python", "source": "{count:d} files", "named_arguments": {"total": "integer"}}));
+        assert_eq!(error("{count} fichiers", &python), Some(INVALID_SOURCE));
+    }
+
+    #[test]
+    fn test_detailed() {
+        assert_eq!(
+            messages::localize(&detail_format_str("%d%n", &ValidationOptions::default()), &json!("%d%n"), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "unsafe_conversion",
+                "line": 1,
+                "column": 3,
+                "message": messages::message("unsafe_conversion", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_format_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+        assert!(!validate_format(&json!(42), &ValidationOptions::default()));
+
+        // Each of a translation's issues gets its own message
+        let source = options(json!({"source": "<b>%d</b> files"}));
+        let result = messages::localize(&detail_format_str("%d fichiers", &source), &json!("%d fichiers"), Some("fr"));
+        assert_eq!(result["error"], "missing_html_tag");
+        assert_eq!(result["issues"][1]["message"], messages::message("missing_html_tag", "fr").unwrap());
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["%d", "%s"], "options": {"arguments": ["string"]}})).unwrap();
+        let valid: Vec<bool> = batch.values.iter().map(|value| validate_format(value, &batch.options)).collect();
+        assert_eq!(valid, vec![false, true]);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in ["%s has %d", "100%%", "", "%n", "%1$s %s"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// ABOUTME: Compares the markup around a translation's placeholders with its source string's
+// ABOUTME: Finds HTML tags the translation drops or adds, and control characters it introduces
+
+// The codes for a source's tag the translation lacks, a tag the source lacks, and a control
+// character the source doesn't have
+pub const MISSING_HTML_TAG: &str = "missing_html_tag";
+pub const EXTRA_HTML_TAG: &str = "extra_html_tag";
+pub const CONTROL_CHARACTER: &str = "control_character";
+
+// The HTML tags in a string, with their byte offsets, as "<b>", "</b>" or "<br/>": names in
+// lower case, without their attributes, which translations may localize, as in an href. A "<"
+// not followed by a name, as in "a < b", isn't a tag
+fn tags(text: &str) -> Vec<(usize, String)> {
+    let mut tags = Vec::new();
+    let mut position = 0;
+    while let Some(index) = text[position..].find('<') {
+        let offset = position + index;
+        position = offset + 1;
+        let rest = &text[offset + 1..];
+        let (closing, rest) = match rest.strip_prefix('/') {
+            Some(rest) => ("/", rest),
+            None => ("", rest),
+        };
+        let length = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == ':')).unwrap_or(rest.len());
+        let name = &rest[..length];
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            continue;
+        }
+        let Some(end) = rest[length..].find('>') else {
+            continue;
+        };
+        let attributes = &rest[length..length + end];
+        if !attributes.is_empty() && !attributes.starts_with(|c: char| c.is_ascii_whitespace() || c == '/') {
+            continue;
+        }
+        let self_closing = if attributes.trim_end().ends_with('/') { "/" } else { "" };
+        tags.push((offset, format!("<{closing}{}{self_closing}>", name.to_ascii_lowercase())));
+        position = offset + 1 + closing.len() + length + end + 1;
+    }
+    tags
+}
+
+// The tags the translation lacks, or has more of than the source, in either order, since a
+// translation may move them; each missing tag once for every time it's missing, without an
+// offset, and each extra tag at the offset it turns up at
+pub fn tag_problems(source: &str, translation: &str) -> Vec<(&'static str, Option<usize>, String)> {
+    let mut expected: Vec<String> = tags(source).into_iter().map(|(_, tag)| tag).collect();
+    let mut problems = Vec::new();
+    for (offset, tag) in tags(translation) {
+        match expected.iter().position(|candidate| *candidate == tag) {
+            Some(index) => {
+                expected.remove(index);
+            }
+            None => problems.push((EXTRA_HTML_TAG, Some(offset), tag)),
+        }
+    }
+    problems.extend(expected.into_iter().map(|tag| (MISSING_HTML_TAG, None, tag)));
+    problems
+}
+
+// The byte offsets of the control characters in the translation beyond those in the source,
+// such as a stray escape or bell, or a line break in a one-line string
+pub fn introduced_controls(source: &str, translation: &str) -> Vec<usize> {
+    let mut available: Vec<char> = source.chars().filter(|c| c.is_control()).collect();
+    let mut offsets = Vec::new();
+    for (offset, c) in translation.char_indices().filter(|(_, c)| c.is_control()) {
+        match available.iter().position(|&candidate| candidate == c) {
+            Some(index) => {
+                available.remove(index);
+            }
+            None => offsets.push(offset),
+        }
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags() {
+        let tags: Vec<String> = tags("<b>Bold</B> <a href=\"/x\">link</a><br/> <br /> a < b, <3, <x-icon> <b").into_iter().map(|(_, tag)| tag).collect();
+        assert_eq!(tags, ["<b>", "</b>", "<a>", "</a>", "<br/>", "<br/>", "<x-icon>"]);
+
+        assert!(tag_problems("<b>%s</b> <a href=\"/en\">help</a>", "<a href=\"/fr\">aide</a> <b>%s</b>").is_empty());
+        assert_eq!(
+            tag_problems("<b>%s</b> files", "%s <i>fichiers</b>"),
+            vec![(EXTRA_HTML_TAG, Some(3), "<i>".to_string()), (MISSING_HTML_TAG, None, "<b>".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_controls() {
+        assert!(introduced_controls("Line one\nLine two", "Ligne un\nLigne deux").is_empty());
+        assert_eq!(introduced_controls("Save", "Enregis\u{7}trer\n"), vec![7, 12]);
+        assert_eq!(introduced_controls("a\tb", "a\t\tb"), vec![2]);
+    }
+}
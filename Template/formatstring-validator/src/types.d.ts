@@ -0,0 +1,59 @@
+// Types for the formatstring validator's exports, added to the .d.ts that wasm-bindgen generates
+
+export type ArgumentKind = "integer" | "float" | "string" | "char" | "pointer" | "any";
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The placeholder syntax: C's and POSIX's printf, with Python's %(name)s, Rust's format! or Python's str.format (default: "printf") */
+    style?: "printf" | "rust" | "python";
+    /** The kinds of the arguments the string is formatted with, in order, such as ["string", "integer"]; with named_arguments, the whole signature. Not checked when neither is set */
+    arguments?: ArgumentKind[];
+    /** The kinds of the named arguments, such as {"count": "integer"} */
+    named_arguments?: Record<string, ArgumentKind>;
+    /** Allow arguments that no placeholder takes, as when a translation leaves a count out (default: false) */
+    allow_unused?: boolean;
+    /** The string the value translates; its placeholders are checked against the arguments, and the value must take the same ones, keep its HTML tags, in any order, and add no control characters */
+    source?: string;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it: the argument signature the string implies */
+export type CanonicalValue = {
+    arguments: ArgumentKind[];
+    named_arguments: Record<string, ArgumentKind>;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Where the placeholder at fault starts, counting lines and characters from 1 */
+    line?: number;
+    column?: number;
+    /** The argument at fault: its position, counting from 0, or its name */
+    argument?: number | string;
+    /** The HTML tag at fault, such as "<b>" or "</a>" */
+    tag?: string;
+    /** Every problem with a translation, each as a result of its own, the first of which the fields above repeat */
+    issues?: ValidationResult[];
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "invalid_placeholder" | "unsafe_conversion" | "mixed_numbering" | "unknown_argument" | "unused_argument" | "argument_type_mismatch" | "invalid_source" | "missing_html_tag" | "extra_html_tag" | "control_character" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
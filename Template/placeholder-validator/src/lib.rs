@@ -0,0 +1,1182 @@
+// ABOUTME: WASM component for Handlebars and Jinja templates, for notification template editors
+// ABOUTME: Checks that placeholder delimiters balance and don't nest, that blocks close in order, and that variables are on an allowlist
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The template language
+    syntax: Syntax,
+    // Variables templates may use, such as ["user", "order.total"]; a name covers its
+    // properties, so "user" allows "user.name". Any when unset
+    variables: Option<Vec<String>>,
+}
+
+// The template languages placeholders are checked in
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Syntax {
+    // {{name}}, {{#each items}}...{{/each}} and {{! comments }}, as Mustache writes them too
+    #[default]
+    Handlebars,
+    // {{ name }}, {% for item in items %}...{% endfor %} and {# comments #}
+    Jinja,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // Where the problem was found, counting lines and characters from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    // The unknown variable, or the block that isn't closed or closes the wrong one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, line: None, column: None, name: None }
+    }
+}
+
+// An error code, the byte offset in the template it was found at, and the variable or block
+// it's about
+type Problem = (&'static str, usize, Option<String>);
+
+// Error codes reported in detailed results
+const UNBALANCED_DELIMITER: &str = "unbalanced_delimiter";
+const NESTED_DELIMITER: &str = "nested_delimiter";
+const INVALID_EXPRESSION: &str = "invalid_expression";
+const UNKNOWN_VARIABLE: &str = "unknown_variable";
+const MISMATCHED_BLOCK: &str = "mismatched_block";
+
+// What a tag holds
+#[derive(Clone, Copy, PartialEq)]
+enum TagKind {
+    Comment,
+    // A placeholder, or a Handlebars block tag
+    Expression,
+    // The triple-stash Handlebars placeholder, {{{name}}}, which can't open or close a block
+    Unescaped,
+    // A Jinja statement, {% ... %}
+    Statement,
+}
+
+// Each syntax's opening delimiters, where a longer one takes precedence over a shorter one it
+// starts with, and the closing delimiter and contents of each
+const HANDLEBARS_TAGS: &[(&str, &str, TagKind)] = &[
+    ("{{!--", "--}}", TagKind::Comment),
+    ("{{!", "}}", TagKind::Comment),
+    ("{{{", "}}}", TagKind::Unescaped),
+    ("{{", "}}", TagKind::Expression),
+];
+const JINJA_TAGS: &[(&str, &str, TagKind)] = &[
+    ("{#", "#}", TagKind::Comment),
+    ("{%", "%}", TagKind::Statement),
+    ("{{", "}}", TagKind::Expression),
+];
+
+// Handlebars' built-in helpers; each and with change the context that names are looked up
+// in, as a block named after a variable, such as {{#user}}, does
+const HANDLEBARS_HELPERS: &[&str] = &["each", "if", "log", "lookup", "unless", "with"];
+
+// Characters a Handlebars name can't contain
+const HANDLEBARS_PUNCTUATION: &str = "!\"#%&'()*+,./;<=>@[\\]^`{|}~";
+
+// Words that aren't variables in Jinja expressions, and the global functions Jinja provides
+const JINJA_KEYWORDS: &[&str] = &["False", "None", "True", "and", "else", "false", "if", "in", "is", "none", "not", "or", "recursive", "true"];
+const JINJA_GLOBALS: &[&str] = &["cycler", "dict", "joiner", "lipsum", "namespace", "range", "self", "super"];
+
+// The Jinja statements that open blocks, closed by "end" and their name, and those of them that
+// scope the variables set within them
+const JINJA_BLOCKS: &[&str] = &["autoescape", "block", "call", "filter", "for", "if", "macro", "set", "with"];
+const JINJA_SCOPES: &[&str] = &["call", "for", "macro", "with"];
+
+// A block opened and not yet closed
+struct Block {
+    name: String,
+    offset: usize,
+    // The names the block binds, such as a for loop's targets or Handlebars block parameters
+    bound: Vec<String>,
+    // Whether names within the block are looked up in a Handlebars context other than the
+    // template's, as in {{#each items}}
+    nested: bool,
+}
+
+// What's known while walking a template: the blocks open, innermost last, the names bound at
+// the top level, and the variables used so far
+struct Walk<'a> {
+    options: &'a ValidationOptions,
+    blocks: Vec<Block>,
+    bound: Vec<String>,
+    variables: Vec<String>,
+}
+
+impl Walk<'_> {
+    fn is_bound(&self, name: &str) -> bool {
+        self.bound.iter().chain(self.blocks.iter().flat_map(|block| &block.bound)).any(|bound| bound == name)
+    }
+
+    // Records a variable of the template's own context, such as "user.name", after checking it
+    // against the allowlist; a listed name covers its properties, and a listed property its
+    // object
+    fn use_variable(&mut self, path: String, offset: usize) -> Result<(), Problem> {
+        if let Some(allowed) = &self.options.variables {
+            let covers = |listed: &String| {
+                let prefix = |long: &str, short: &str| long.strip_prefix(short).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'));
+                prefix(&path, listed) || prefix(listed, &path)
+            };
+            if !allowed.iter().any(covers) {
+                return Err((UNKNOWN_VARIABLE, offset, Some(path)));
+            }
+        }
+        if !self.variables.contains(&path) {
+            self.variables.push(path);
+        }
+        Ok(())
+    }
+
+    fn open(&mut self, name: &str, offset: usize, bound: Vec<String>, nested: bool) {
+        self.blocks.push(Block { name: name.to_string(), offset, bound, nested });
+    }
+
+    // Closes the innermost block, which must be the one named
+    fn close(&mut self, name: &str, tag: &str, offset: usize) -> Result<(), Problem> {
+        match self.blocks.pop() {
+            Some(block) if block.name == name => Ok(()),
+            _ => Err((MISMATCHED_BLOCK, offset, Some(tag.to_string()))),
+        }
+    }
+
+    // The innermost block, which an else tag must be in, one of those named
+    fn innermost(&mut self, names: &[&str], tag: &str, offset: usize) -> Result<&mut Block, Problem> {
+        match self.blocks.last_mut() {
+            Some(block) if names.is_empty() || names.contains(&block.name.as_str()) => Ok(block),
+            _ => Err((MISMATCHED_BLOCK, offset, Some(tag.to_string()))),
+        }
+    }
+}
+
+// A token of a Handlebars expression
+#[derive(Clone, PartialEq)]
+enum Token<'a> {
+    Path(&'a str),
+    Literal,
+    Open,
+    Close,
+    Equals,
+    Pipe,
+}
+
+// A Handlebars path, such as "user.name", "../title", "this" or "@root.user", split into its
+// segments, with "[...]" segments unwrapped
+struct HandlebarsPath {
+    data: bool,
+    ups: usize,
+    this: bool,
+    segments: Vec<String>,
+}
+
+// Splits a Handlebars path, if it is one, such as "user.name", "user/name", "../title",
+// "./name", "this.name", "@index" or "items.[0]"
+fn handlebars_path(word: &str) -> Option<HandlebarsPath> {
+    let (data, mut rest) = match word.strip_prefix('@') {
+        Some(rest) => (true, rest),
+        None => (false, word),
+    };
+    let mut path = HandlebarsPath { data, ups: 0, this: false, segments: Vec::new() };
+    while let Some(after) = rest.strip_prefix("../") {
+        path.ups += 1;
+        rest = after;
+    }
+    if let Some(after) = rest.strip_prefix("./") {
+        path.this = true;
+        rest = after;
+    }
+    if rest.is_empty() {
+        return (path.ups > 0 || path.this).then_some(path);
+    }
+    loop {
+        let (segment, after) = match rest.strip_prefix('[') {
+            Some(literal) => {
+                let end = literal.find(']')?;
+                (&literal[..end], &literal[end + 1..])
+            }
+            None => {
+                let end = rest.find(['.', '/']).unwrap_or(rest.len());
+                let segment = &rest[..end];
+                if segment.is_empty() || segment.chars().any(|c| c.is_whitespace() || HANDLEBARS_PUNCTUATION.contains(c)) {
+                    return None;
+                }
+                (segment, &rest[end..])
+            }
+        };
+        if segment == "this" && path.segments.is_empty() && !path.data {
+            path.this = true;
+        } else {
+            path.segments.push(segment.to_string());
+        }
+        if after.is_empty() {
+            return Some(path);
+        }
+        rest = after.strip_prefix(['.', '/'])?;
+        if rest.is_empty() {
+            return None;
+        }
+    }
+}
+
+// The tokens of a Handlebars expression: paths, string, number and keyword literals,
+// parentheses around subexpressions, the "=" of hash arguments and the "|" around block
+// parameters
+fn handlebars_tokens(content: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = content.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let (token, length) = match c {
+            '"' | '\'' => {
+                let end = rest[1..].find(c)?;
+                (Token::Literal, end + 2)
+            }
+            '(' => (Token::Open, 1),
+            ')' => (Token::Close, 1),
+            '=' => (Token::Equals, 1),
+            '|' => (Token::Pipe, 1),
+            _ => {
+                // A word runs to whitespace or punctuation, though "[...]" segments may hold either
+                let mut end = 0;
+                while let Some(c) = rest[end..].chars().next() {
+                    if c == '[' {
+                        end += rest[end..].find(']')? + 1;
+                    } else if c.is_whitespace() || "()=|".contains(c) {
+                        break;
+                    } else {
+                        end += c.len_utf8();
+                    }
+                }
+                let word = &rest[..end];
+                let number = word.starts_with(|c: char| c.is_ascii_digit() || c == '-') && word.parse::<f64>().is_ok();
+                if number || matches!(word, "true" | "false" | "null" | "undefined") {
+                    (Token::Literal, end)
+                } else {
+                    handlebars_path(word)?;
+                    (Token::Path(word), end)
+                }
+            }
+        };
+        tokens.push(token);
+        rest = rest[length..].trim_start();
+    }
+    Some(tokens)
+}
+
+// Checks a path used as a value: a variable of the template's context is checked against the
+// allowlist, while names bound by block parameters, data such as @index, and names looked up
+// in a nested context, such as an each block's items, can't be
+fn handlebars_variable(walk: &mut Walk, word: &str, offset: usize) -> Result<(), Problem> {
+    let Some(path) = handlebars_path(word) else {
+        return Err((INVALID_EXPRESSION, offset, None));
+    };
+    let segments = if path.data {
+        match path.segments.split_first() {
+            Some((first, rest)) if first == "root" => rest,
+            _ => return Ok(()),
+        }
+    } else {
+        let depth = walk.blocks.iter().filter(|block| block.nested).count();
+        let local = path.ups == 0 && !path.this && walk.is_bound(&path.segments[0]);
+        if local || depth > path.ups {
+            return Ok(());
+        }
+        &path.segments[..]
+    };
+    if segments.is_empty() {
+        return Ok(());
+    }
+    walk.use_variable(segments.join("."), offset)
+}
+
+// Checks the parameters of a helper call, and of the subexpressions among them; the helper's
+// own name, the first token of a subexpression and the keys of hash arguments aren't values
+fn handlebars_params(walk: &mut Walk, tokens: &[Token], offset: usize) -> Result<(), Problem> {
+    let mut depth = 0usize;
+    for (index, token) in tokens.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|index| &tokens[index]);
+        match token {
+            Token::Open => depth += 1,
+            Token::Close => depth = depth.checked_sub(1).ok_or((INVALID_EXPRESSION, offset, None))?,
+            Token::Path(_) if previous == Some(&Token::Open) => {}
+            Token::Path(_) if tokens.get(index + 1) == Some(&Token::Equals) => {}
+            Token::Path(word) => handlebars_variable(walk, word, offset)?,
+            Token::Equals if !matches!(previous, Some(Token::Path(_))) || !matches!(tokens.get(index + 1), Some(Token::Path(_) | Token::Literal | Token::Open)) => {
+                return Err((INVALID_EXPRESSION, offset, None));
+            }
+            Token::Pipe => return Err((INVALID_EXPRESSION, offset, None)),
+            Token::Literal | Token::Equals => {}
+        }
+    }
+    if depth > 0 {
+        return Err((INVALID_EXPRESSION, offset, None));
+    }
+    Ok(())
+}
+
+// A mustache or block's tokens, with its block parameters, "as |item index|", split off
+fn block_params<'a>(tokens: Vec<Token<'a>>, offset: usize) -> Result<(Vec<Token<'a>>, Vec<String>), Problem> {
+    let Some(start) = tokens.windows(2).position(|pair| pair == [Token::Path("as"), Token::Pipe]) else {
+        return Ok((tokens, Vec::new()));
+    };
+    if tokens.len() < start + 4 || tokens.last() != Some(&Token::Pipe) {
+        return Err((INVALID_EXPRESSION, offset, None));
+    }
+    let mut names = Vec::new();
+    for token in &tokens[start + 2..tokens.len() - 1] {
+        match token {
+            Token::Path(name) if handlebars_path(name).is_some_and(|path| path.segments.len() == 1 && !path.data && path.ups == 0 && !path.this) => {
+                names.push(name.to_string());
+            }
+            _ => return Err((INVALID_EXPRESSION, offset, None)),
+        }
+    }
+    Ok((tokens[..start].to_vec(), names))
+}
+
+// A Handlebars expression's tokens, of which there must be some
+fn nonempty_tokens(content: &str, offset: usize) -> Result<Vec<Token<'_>>, Problem> {
+    handlebars_tokens(content).filter(|tokens| !tokens.is_empty()).ok_or((INVALID_EXPRESSION, offset, None))
+}
+
+// Checks a Handlebars tag: a placeholder, a block's opening or closing tag, an else, a partial
+// or a decorator. A block named after a variable, such as {{#user}}, is a section, which looks
+// up names in that variable as each and with do
+fn handlebars_tag(walk: &mut Walk, content: &str, kind: TagKind, offset: usize) -> Result<(), Problem> {
+    let content = content.trim_start_matches('~').trim_end_matches('~').trim();
+    let tokens = |text| nonempty_tokens(text, offset);
+    if kind == TagKind::Unescaped {
+        let tokens = tokens(content)?;
+        return handlebars_params(walk, &tokens, offset);
+    }
+    let (sigil, rest) = match content.chars().next() {
+        Some(sigil @ ('#' | '^' | '/' | '>' | '&' | '*')) => (Some(sigil), content[1..].trim_start()),
+        _ => (None, content),
+    };
+    match sigil {
+        Some('#') if rest.starts_with(['>', '*']) => {
+            // A partial block, {{#> layout}}, or an inline partial, {{#*inline "name"}}
+            let tokens = tokens(&rest[1..])?;
+            let name = match tokens[0] {
+                Token::Path(name) => name,
+                _ => return Err((INVALID_EXPRESSION, offset, None)),
+            };
+            if rest.starts_with('>') {
+                handlebars_params(walk, &tokens[1..], offset)?;
+            }
+            walk.open(name, offset, Vec::new(), false);
+        }
+        Some('#') => {
+            let (tokens, bound) = block_params(tokens(rest)?, offset)?;
+            let Some(&Token::Path(name)) = tokens.first() else {
+                return Err((INVALID_EXPRESSION, offset, None));
+            };
+            let section = tokens.len() == 1 && !HANDLEBARS_HELPERS.contains(&name);
+            if section {
+                handlebars_variable(walk, name, offset)?;
+            } else {
+                handlebars_params(walk, &tokens[1..], offset)?;
+            }
+            walk.open(name, offset, bound, section || matches!(name, "each" | "with"));
+        }
+        // An inverted section, {{^items}}, or else, {{^}}
+        Some('^') if !rest.is_empty() => {
+            let Some(&[Token::Path(name)]) = tokens(rest).ok().as_deref() else {
+                return Err((INVALID_EXPRESSION, offset, None));
+            };
+            handlebars_variable(walk, name, offset)?;
+            walk.open(name, offset, Vec::new(), false);
+        }
+        Some('^') => handlebars_else(walk, &[], offset)?,
+        Some('/') => {
+            let Some(&[Token::Path(name)]) = tokens(rest).ok().as_deref() else {
+                return Err((INVALID_EXPRESSION, offset, None));
+            };
+            walk.close(name, &format!("/{name}"), offset)?;
+        }
+        Some('>') => {
+            // A partial's name, or a subexpression that gives it, isn't a variable
+            let tokens = tokens(rest)?;
+            let params = if tokens[0] == Token::Open { &tokens[..] } else { &tokens[1..] };
+            handlebars_params(walk, params, offset)?;
+        }
+        Some('&') => handlebars_params(walk, &tokens(rest)?, offset)?,
+        // Decorators, {{*decorator}}, are left to the code that registers them
+        Some(_) => {}
+        None => {
+            let tokens = tokens(rest)?;
+            match tokens[..] {
+                [Token::Path("else"), ..] => handlebars_else(walk, &tokens[1..], offset)?,
+                [Token::Path(word)] => handlebars_variable(walk, word, offset)?,
+                [Token::Open, ..] => handlebars_params(walk, &tokens, offset)?,
+                [Token::Path(_) | Token::Literal, ..] => handlebars_params(walk, &tokens[1..], offset)?,
+                _ => return Err((INVALID_EXPRESSION, offset, None)),
+            }
+        }
+    }
+    Ok(())
+}
+
+// An else, perhaps chained with another helper as in {{else if other}}; its part of the block
+// is rendered in the block's outer context, without its block parameters
+fn handlebars_else(walk: &mut Walk, chained: &[Token], offset: usize) -> Result<(), Problem> {
+    let block = walk.innermost(&[], "else", offset)?;
+    block.nested = false;
+    block.bound.clear();
+    if chained.is_empty() {
+        return Ok(());
+    }
+    if !matches!(chained[0], Token::Path(_)) {
+        return Err((INVALID_EXPRESSION, offset, None));
+    }
+    handlebars_params(walk, &chained[1..], offset)
+}
+
+// A token of a Jinja expression: a name, a string or number, or an operator
+#[derive(Clone, Copy, PartialEq)]
+enum JinjaToken<'a> {
+    Name(&'a str),
+    Literal,
+    Operator(&'a str),
+}
+
+// The tokens of a Jinja expression
+fn jinja_tokens(content: &str) -> Option<Vec<JinjaToken<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = content.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let word = |rest: &str, allowed: fn(char) -> bool| rest.find(|c: char| !allowed(c)).unwrap_or(rest.len());
+        let (token, length) = if c.is_alphabetic() || c == '_' {
+            let length = word(rest, |c| c.is_alphanumeric() || c == '_');
+            (JinjaToken::Name(&rest[..length]), length)
+        } else if c.is_ascii_digit() {
+            (JinjaToken::Literal, word(rest, |c| c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+        } else if c == '"' || c == '\'' {
+            // Strings may escape their quote with a backslash
+            let mut escaped = false;
+            let end = rest[1..].find(|next: char| {
+                let closes = next == c && !escaped;
+                escaped = next == '\\' && !escaped;
+                closes
+            })?;
+            (JinjaToken::Literal, end + 2)
+        } else {
+            let length = match rest.get(..2) {
+                Some("**" | "//" | "==" | "!=" | "<=" | ">=") => 2,
+                _ if "+-*/%~<>=()[]{}.,:|".contains(c) => 1,
+                _ => return None,
+            };
+            (JinjaToken::Operator(&rest[..length]), length)
+        };
+        tokens.push(token);
+        rest = rest[length..].trim_start();
+    }
+    Some(tokens)
+}
+
+// Checks the variables a Jinja expression uses, and that its brackets balance. Attributes,
+// filters, tests, keyword arguments and global functions aren't variables, and a variable's
+// path runs through its attributes, as in "user.address.city"
+fn jinja_expression(walk: &mut Walk, tokens: &[JinjaToken], offset: usize) -> Result<(), Problem> {
+    if tokens.is_empty() {
+        return Err((INVALID_EXPRESSION, offset, None));
+    }
+    let mut brackets = Vec::new();
+    for (index, &token) in tokens.iter().enumerate() {
+        let previous = |back: usize| index.checked_sub(back).map(|index| tokens[index]);
+        let next = tokens.get(index + 1).copied();
+        match token {
+            JinjaToken::Operator(open @ ("(" | "[" | "{")) => brackets.push(open),
+            JinjaToken::Operator(close @ (")" | "]" | "}")) => {
+                let open = brackets.pop();
+                if [("(", ")"), ("[", "]"), ("{", "}")].iter().all(|&pair| pair != (open.unwrap_or_default(), close)) {
+                    return Err((INVALID_EXPRESSION, offset, None));
+                }
+            }
+            JinjaToken::Name(name) => {
+                let attribute = matches!(previous(1), Some(JinjaToken::Operator("." | "|")));
+                let test = previous(1) == Some(JinjaToken::Name("is"))
+                    || (previous(1) == Some(JinjaToken::Name("not")) && previous(2) == Some(JinjaToken::Name("is")));
+                let argument = next == Some(JinjaToken::Operator("="));
+                let global = next == Some(JinjaToken::Operator("(")) && JINJA_GLOBALS.contains(&name);
+                if attribute || test || argument || global || JINJA_KEYWORDS.contains(&name) || walk.is_bound(name) {
+                    continue;
+                }
+                let mut path = name.to_string();
+                for pair in tokens[index + 1..].chunks(2) {
+                    match pair {
+                        [JinjaToken::Operator("."), JinjaToken::Name(attribute)] => path = format!("{path}.{attribute}"),
+                        _ => break,
+                    }
+                }
+                walk.use_variable(path, offset)?;
+            }
+            JinjaToken::Literal | JinjaToken::Operator(_) => {}
+        }
+    }
+    if !brackets.is_empty() {
+        return Err((INVALID_EXPRESSION, offset, None));
+    }
+    Ok(())
+}
+
+// The names in a Jinja target list, such as a for loop's "key, value" or "(a, b)"
+fn jinja_targets(tokens: &[JinjaToken], offset: usize) -> Result<Vec<String>, Problem> {
+    let mut names = Vec::new();
+    for token in tokens {
+        match token {
+            JinjaToken::Name(name) if !JINJA_KEYWORDS.contains(name) => names.push(name.to_string()),
+            JinjaToken::Operator("," | "(" | ")") => {}
+            _ => return Err((INVALID_EXPRESSION, offset, None)),
+        }
+    }
+    if names.is_empty() {
+        return Err((INVALID_EXPRESSION, offset, None));
+    }
+    Ok(names)
+}
+
+// Splits tokens at the first of a name or operator outside brackets
+fn jinja_split<'a, 'b>(tokens: &'b [JinjaToken<'a>], at: JinjaToken) -> Option<(&'b [JinjaToken<'a>], &'b [JinjaToken<'a>])> {
+    let mut depth = 0i32;
+    let index = tokens.iter().position(|&token| {
+        match token {
+            JinjaToken::Operator("(" | "[" | "{") => depth += 1,
+            JinjaToken::Operator(")" | "]" | "}") => depth -= 1,
+            _ => {}
+        }
+        depth == 0 && token == at
+    })?;
+    Some((&tokens[..index], &tokens[index + 1..]))
+}
+
+// Binds names where a set, import or macro statement puts them: in the innermost loop, macro,
+// call or with block, or else at the top level
+fn jinja_bind(walk: &mut Walk, names: Vec<String>) {
+    match walk.blocks.iter_mut().rev().find(|block| JINJA_SCOPES.contains(&block.name.as_str())) {
+        Some(block) => block.bound.extend(names),
+        None => walk.bound.extend(names),
+    }
+}
+
+// The parameters of a macro or call block, "(a, b=1)", after checking their defaults
+fn jinja_parameters(walk: &mut Walk, tokens: &[JinjaToken], offset: usize) -> Result<Vec<String>, Problem> {
+    let inner = match tokens {
+        [JinjaToken::Operator("("), inner @ .., JinjaToken::Operator(")")] => inner,
+        _ => return Err((INVALID_EXPRESSION, offset, None)),
+    };
+    let mut names = Vec::new();
+    let mut rest = inner;
+    while !rest.is_empty() {
+        let (parameter, after) = jinja_split(rest, JinjaToken::Operator(",")).unwrap_or((rest, &[]));
+        match parameter {
+            [JinjaToken::Name(name)] => names.push(name.to_string()),
+            [JinjaToken::Name(name), JinjaToken::Operator("="), default @ ..] => {
+                jinja_expression(walk, default, offset)?;
+                names.push(name.to_string());
+            }
+            _ => return Err((INVALID_EXPRESSION, offset, None)),
+        }
+        rest = after;
+    }
+    Ok(names)
+}
+
+// Checks a Jinja statement, {% ... %}: opening, continuing or closing a block, setting a
+// variable, or including, extending or importing another template
+fn jinja_statement(walk: &mut Walk, content: &str, offset: usize) -> Result<(), Problem> {
+    let content = content.trim_start_matches(['-', '+']).trim_end_matches(['-', '+']);
+    let tokens = jinja_tokens(content).ok_or((INVALID_EXPRESSION, offset, None))?;
+    let Some((&JinjaToken::Name(tag), rest)) = tokens.split_first() else {
+        return Err((INVALID_EXPRESSION, offset, None));
+    };
+    match tag {
+        "if" => {
+            jinja_expression(walk, rest, offset)?;
+            walk.open(tag, offset, Vec::new(), false);
+        }
+        "elif" => {
+            walk.innermost(&["if"], tag, offset)?;
+            jinja_expression(walk, rest, offset)?;
+        }
+        // A for loop's else runs when there's nothing to loop over, without the loop's variables
+        "else" => walk.innermost(&["if", "for"], tag, offset)?.bound.clear(),
+        "for" => {
+            let (targets, rest) = jinja_split(rest, JinjaToken::Name("in")).ok_or((INVALID_EXPRESSION, offset, None))?;
+            let mut bound = jinja_targets(targets, offset)?;
+            bound.push("loop".to_string());
+            let rest = rest.strip_suffix(&[JinjaToken::Name("recursive")]).unwrap_or(rest);
+            let (iterable, condition) = jinja_split(rest, JinjaToken::Name("if")).unwrap_or((rest, &[]));
+            jinja_expression(walk, iterable, offset)?;
+            walk.open(tag, offset, bound, false);
+            if !condition.is_empty() {
+                jinja_expression(walk, condition, offset)?;
+            }
+        }
+        "set" => match jinja_split(rest, JinjaToken::Operator("=")) {
+            Some((targets, value)) => {
+                jinja_expression(walk, value, offset)?;
+                // Setting an attribute, as of a namespace, uses the object rather than binding it
+                if let [JinjaToken::Name(_), JinjaToken::Operator("."), JinjaToken::Name(_)] = targets {
+                    jinja_expression(walk, &targets[..1], offset)?;
+                } else {
+                    let names = jinja_targets(targets, offset)?;
+                    jinja_bind(walk, names);
+                }
+            }
+            // A block set, {% set name %}...{% endset %}, perhaps through filters
+            None => {
+                let (target, _) = jinja_split(rest, JinjaToken::Operator("|")).unwrap_or((rest, &[]));
+                let names = jinja_targets(target, offset)?;
+                jinja_bind(walk, names);
+                walk.open(tag, offset, Vec::new(), false);
+            }
+        },
+        "with" => {
+            let mut bound = Vec::new();
+            let mut rest = rest;
+            while !rest.is_empty() {
+                let (assignment, after) = jinja_split(rest, JinjaToken::Operator(",")).unwrap_or((rest, &[]));
+                let [JinjaToken::Name(name), JinjaToken::Operator("="), value @ ..] = assignment else {
+                    return Err((INVALID_EXPRESSION, offset, None));
+                };
+                jinja_expression(walk, value, offset)?;
+                bound.push(name.to_string());
+                rest = after;
+            }
+            walk.open(tag, offset, bound, false);
+        }
+        "macro" => {
+            let Some((&JinjaToken::Name(name), parameters)) = rest.split_first() else {
+                return Err((INVALID_EXPRESSION, offset, None));
+            };
+            let mut bound = jinja_parameters(walk, parameters, offset)?;
+            bound.extend(["varargs", "kwargs", "caller"].map(String::from));
+            jinja_bind(walk, vec![name.to_string()]);
+            walk.open(tag, offset, bound, false);
+        }
+        "call" => {
+            // The caller's own parameters, if any, come before the macro call
+            let (bound, call) = match rest.first() {
+                Some(JinjaToken::Operator("(")) => {
+                    let mut depth = 0;
+                    let end = rest.iter().position(|token| {
+                        match token {
+                            JinjaToken::Operator("(") => depth += 1,
+                            JinjaToken::Operator(")") => depth -= 1,
+                            _ => {}
+                        }
+                        depth == 0
+                    });
+                    let end = end.ok_or((INVALID_EXPRESSION, offset, None))?;
+                    (jinja_parameters(walk, &rest[..=end], offset)?, &rest[end + 1..])
+                }
+                _ => (Vec::new(), rest),
+            };
+            jinja_expression(walk, call, offset)?;
+            walk.open(tag, offset, bound, false);
+        }
+        "autoescape" => {
+            jinja_expression(walk, rest, offset)?;
+            walk.open(tag, offset, Vec::new(), false);
+        }
+        // Block and filter names aren't variables
+        "block" | "filter" if !rest.is_empty() => walk.open(tag, offset, Vec::new(), false),
+        "include" | "extends" => {
+            // Their context and missing-template modifiers aren't part of the template's name
+            let modifiers = ["ignore", "missing", "with", "without", "context"];
+            let end = rest.iter().position(|token| matches!(token, JinjaToken::Name(word) if modifiers.contains(word))).unwrap_or(rest.len());
+            jinja_expression(walk, &rest[..end], offset)?;
+        }
+        "import" => {
+            let (template, alias) = jinja_split(rest, JinjaToken::Name("as")).ok_or((INVALID_EXPRESSION, offset, None))?;
+            jinja_expression(walk, template, offset)?;
+            let names = jinja_targets(&alias[..alias.len().min(1)], offset)?;
+            jinja_bind(walk, names);
+        }
+        "from" => {
+            let (template, imports) = jinja_split(rest, JinjaToken::Name("import")).ok_or((INVALID_EXPRESSION, offset, None))?;
+            jinja_expression(walk, template, offset)?;
+            let mut names = Vec::new();
+            for import in imports.split(|&token| token == JinjaToken::Operator(",")) {
+                match import {
+                    [JinjaToken::Name(name)] | [JinjaToken::Name(_), JinjaToken::Name("as"), JinjaToken::Name(name)] => names.push(name.to_string()),
+                    [JinjaToken::Name(name), JinjaToken::Name("with" | "without"), JinjaToken::Name("context")] => names.push(name.to_string()),
+                    _ => return Err((INVALID_EXPRESSION, offset, None)),
+                }
+            }
+            jinja_bind(walk, names);
+        }
+        _ => match tag.strip_prefix("end") {
+            Some(name) if JINJA_BLOCKS.contains(&name) => walk.close(name, tag, offset)?,
+            _ => return Err((INVALID_EXPRESSION, offset, Some(tag.to_string()))),
+        },
+    }
+    Ok(())
+}
+
+// The offset just past the {% endraw %} that closes a Jinja raw block
+fn endraw(text: &str, from: usize) -> Option<usize> {
+    let mut position = from;
+    while let Some(index) = text[position..].find("{%") {
+        let start = position + index + 2;
+        let end = start + text[start..].find("%}")?;
+        if text[start..end].trim_matches(['-', '+']).trim() == "endraw" {
+            return Some(end + 2);
+        }
+        position = start;
+    }
+    None
+}
+
+// Checks literal text between tags for closing delimiters without opening ones, as in
+// "{name}}"
+fn check_text(text: &str, offset: usize, tags: &[(&str, &str, TagKind)]) -> Result<(), Problem> {
+    let stray = tags.iter().filter_map(|(_, close, _)| text.find(close)).min();
+    match stray {
+        Some(index) => Err((UNBALANCED_DELIMITER, offset + index, None)),
+        None => Ok(()),
+    }
+}
+
+// Checks a template: that its delimiters balance, without one tag inside another, that its
+// blocks nest, and that its expressions parse and use only the allowed variables. Returns
+// the variables of the template's own context that it uses, in order
+fn check_template(text: &str, options: &ValidationOptions) -> Result<Vec<String>, Problem> {
+    let tags = match options.syntax {
+        Syntax::Handlebars => HANDLEBARS_TAGS,
+        Syntax::Jinja => JINJA_TAGS,
+    };
+    let mut walk = Walk { options, blocks: Vec::new(), bound: Vec::new(), variables: Vec::new() };
+    let mut position = 0;
+    loop {
+        let next = text[position..].match_indices('{').find_map(|(index, _)| {
+            let start = position + index;
+            tags.iter().find(|(open, _, _)| text[start..].starts_with(open)).map(|&tag| (start, tag))
+        });
+        let Some((start, (open, close, kind))) = next else {
+            check_text(&text[position..], position, tags)?;
+            break;
+        };
+        check_text(&text[position..start], position, tags)?;
+        // Handlebars leaves a mustache escaped with a backslash, \{{name}}, as it is
+        let escaped = options.syntax == Syntax::Handlebars && text[..start].ends_with('\\') && !text[..start].ends_with("\\\\");
+        let inner = start + open.len();
+        let end = inner + text[inner..].find(close).ok_or((UNBALANCED_DELIMITER, start, None))?;
+        position = end + close.len();
+        if escaped || kind == TagKind::Comment {
+            continue;
+        }
+        let content = &text[inner..end];
+        if let Some(index) = tags.iter().filter_map(|(open, _, _)| content.find(open)).min() {
+            return Err((NESTED_DELIMITER, inner + index, None));
+        }
+        match kind {
+            TagKind::Statement if content.trim_matches(['-', '+']).trim() == "raw" => {
+                // A raw block's contents are text, even where they look like tags
+                position = endraw(text, position).ok_or((MISMATCHED_BLOCK, start, Some("raw".to_string())))?;
+            }
+            TagKind::Statement => jinja_statement(&mut walk, content, start)?,
+            TagKind::Expression if options.syntax == Syntax::Jinja => {
+                let content = content.trim_start_matches(['-', '+']).trim_end_matches(['-', '+']);
+                let tokens = jinja_tokens(content).ok_or((INVALID_EXPRESSION, start, None))?;
+                jinja_expression(&mut walk, &tokens, start)?;
+            }
+            _ => handlebars_tag(&mut walk, content, kind, start)?,
+        }
+    }
+    match walk.blocks.pop() {
+        Some(block) => Err((MISMATCHED_BLOCK, block.offset, Some(block.name))),
+        None => Ok(walk.variables),
+    }
+}
+
+// The line and column of a byte offset, counting from 1, with columns in characters
+fn line_and_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
+fn detail_placeholder_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank template has nothing to fill in, and gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_template(text, options) {
+        Ok(variables) => ValidationResult::from(Some(json!({ "variables": variables }))),
+        Err((code, offset, name)) => {
+            let (line, column) = line_and_column(text, offset);
+            ValidationResult { error: Some(code), line: Some(line), column: Some(column), name, ..ValidationResult::from(None) }
+        }
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_placeholder_str directly
+fn detail_placeholder(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_placeholder_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the variables the template uses
+fn coerce_placeholder(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_placeholder(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_placeholder(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_placeholder(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_placeholder(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_placeholder(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_placeholder(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_placeholder(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_placeholder(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_placeholder(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_placeholder(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_placeholder_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(options: Value) -> ValidationOptions {
+        serde_json::from_value(options).unwrap()
+    }
+
+    fn problem(text: &str, options: &ValidationOptions) -> (Option<&'static str>, Option<usize>, Option<usize>, Option<String>) {
+        let result = detail_placeholder_str(text, options);
+        (result.error, result.line, result.column, result.name)
+    }
+
+    fn error(text: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_placeholder_str(text, options).error
+    }
+
+    fn variables(text: &str, options: &ValidationOptions) -> Value {
+        coerce_placeholder(&json!(text), options).map_or(Value::Null, |value| value["variables"].clone())
+    }
+
+    #[test]
+    fn test_delimiters() {
+        let handlebars = ValidationOptions::default();
+        assert_eq!(problem("Hi {{name},\nthanks", &handlebars), (Some(UNBALANCED_DELIMITER), Some(1), Some(4), None));
+        assert_eq!(problem("Hi {name}}", &handlebars), (Some(UNBALANCED_DELIMITER), Some(1), Some(9), None));
+        assert_eq!(error("{{{name}}", &handlebars), Some(UNBALANCED_DELIMITER));
+        assert_eq!(problem("Hi {{first {{last}} }}", &handlebars), (Some(NESTED_DELIMITER), Some(1), Some(12), None));
+        // Comments, escaped mustaches and lone braces are text
+        assert!(validate_placeholder(&json!("{{!-- {{old}} --}}{{! note }}\\{{literal}} {a} {"), &handlebars));
+
+        let jinja = options(json!({"syntax": "jinja"}));
+        assert_eq!(error("{% if x }", &jinja), Some(UNBALANCED_DELIMITER));
+        assert_eq!(error("{{ x %}", &jinja), Some(UNBALANCED_DELIMITER));
+        assert_eq!(error("{% if {{ x }} %}{% endif %}", &jinja), Some(NESTED_DELIMITER));
+        assert_eq!(error("100%} done", &jinja), Some(UNBALANCED_DELIMITER));
+        assert!(validate_placeholder(&json!("{# {{ draft }} #}{% raw %}{{ shown as is }} {% if %}{% endraw %}"), &jinja));
+        assert_eq!(error("{% raw %}{{ x }}", &jinja), Some(MISMATCHED_BLOCK));
+    }
+
+    #[test]
+    fn test_handlebars() {
+        let handlebars = ValidationOptions::default();
+        assert_eq!(variables("Hi {{user.first_name}}, {{~ order.id ~}} {{{footer}}}", &handlebars), json!(["user.first_name", "order.id", "footer"]));
+        assert_eq!(
+            variables("{{#if (eq status \"paid\")}}{{formatCurrency total currency=currency}}{{else if refunded}}{{/if}}", &handlebars),
+            json!(["status", "total", "currency", "refunded"])
+        );
+        // Names within each and with blocks, and sections, are looked up in their items
+        assert_eq!(variables("{{#each items}}{{name}} {{../title}} {{@index}}{{else}}{{empty}}{{/each}}", &handlebars), json!(["items", "title", "empty"]));
+        assert_eq!(variables("{{#each items as |item i|}}{{item.name}}{{i}}{{/each}}{{#user}}{{email}}{{/user}}", &handlebars), json!(["items", "user"]));
+        assert_eq!(variables("{{#with user}}{{@root.site}}{{this.name}}{{/with}}{{> footer year=year}}", &handlebars), json!(["user", "site", "year"]));
+
+        assert_eq!(problem("{{#if a}}\n{{#each b}}{{/if}}{{/each}}", &handlebars), (Some(MISMATCHED_BLOCK), Some(2), Some(12), Some("/if".to_string())));
+        assert_eq!(problem("{{#if a}}{{#each b}}{{/each}}", &handlebars), (Some(MISMATCHED_BLOCK), Some(1), Some(1), Some("if".to_string())));
+        assert_eq!(error("{{/if}}", &handlebars), Some(MISMATCHED_BLOCK));
+        assert_eq!(error("{{else}}", &handlebars), Some(MISMATCHED_BLOCK));
+        assert_eq!(error("{{}}", &handlebars), Some(INVALID_EXPRESSION));
+        assert_eq!(error("{{user name=}}", &handlebars), Some(INVALID_EXPRESSION));
+        assert_eq!(error("{{first name)}}", &handlebars), Some(INVALID_EXPRESSION));
+        assert_eq!(error("{{\"unterminated}}", &handlebars), Some(INVALID_EXPRESSION));
+        assert_eq!(error("{{{#if a}}}", &handlebars), Some(INVALID_EXPRESSION));
+        assert_eq!(error("{{#each items as |item}}{{/each}}", &handlebars), Some(INVALID_EXPRESSION));
+    }
+
+    #[test]
+    fn test_jinja() {
+        let jinja = options(json!({"syntax": "jinja"}));
+        assert_eq!(
+            variables("Hi {{- user.name | default('there') | title -}}, {{ order['id'] }} {{ 'x' if vip is defined else total * 1.2 }}", &jinja),
+            json!(["user.name", "order", "vip", "total"])
+        );
+        let template = "{% for item in items if item.qty %}{{ loop.index }} {{ item.name }}{% else %}{{ empty }}{% endfor %}\
+            {% set greeting = 'Hi ' ~ user.name %}{{ greeting }}\
+            {% macro row(label, value=none) %}{{ label }}{{ caller() }}{% endmacro %}{{ row('a') }}\
+            {% with total = order.total %}{{ total }}{% endwith %}{% for n in range(3) %}{{ n }}{% endfor %}";
+        assert_eq!(variables(template, &jinja), json!(["items", "empty", "user.name", "order.total"]));
+
+        assert_eq!(problem("{% if a %}\n{% for b in c %}{% endif %}", &jinja), (Some(MISMATCHED_BLOCK), Some(2), Some(17), Some("endif".to_string())));
+        assert_eq!(error("{% for x in xs %}", &jinja), Some(MISMATCHED_BLOCK));
+        assert_eq!(error("{% elif a %}", &jinja), Some(MISMATCHED_BLOCK));
+        assert_eq!(error("{% for x in xs %}{% elif a %}{% endfor %}", &jinja), Some(MISMATCHED_BLOCK));
+        assert_eq!(error("{{ }}", &jinja), Some(INVALID_EXPRESSION));
+        assert_eq!(error("{{ price(1 }}", &jinja), Some(INVALID_EXPRESSION));
+        assert_eq!(error("{{ a ; b }}", &jinja), Some(INVALID_EXPRESSION));
+        assert_eq!(error("{% iff x %}", &jinja), Some(INVALID_EXPRESSION));
+        assert_eq!(error("{% for in xs %}{% endfor %}", &jinja), Some(INVALID_EXPRESSION));
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let handlebars = options(json!({"variables": ["user", "order.total", "items"]}));
+        assert!(validate_placeholder(&json!("{{user.name}} {{order.total}} {{#with order}}{{id}}{{/with}} {{#each items}}{{sku}}{{/each}}"), &handlebars));
+        assert_eq!(problem("Hi {{user.name}}\n{{order.id}}", &handlebars), (Some(UNKNOWN_VARIABLE), Some(2), Some(1), Some("order.id".to_string())));
+        assert_eq!(error("{{#if coupon}}{{/if}}", &handlebars), Some(UNKNOWN_VARIABLE));
+        assert_eq!(error("{{users}}", &handlebars), Some(UNKNOWN_VARIABLE));
+
+        let jinja = options(json!({"syntax": "jinja", "variables": ["user"]}));
+        assert!(validate_placeholder(&json!("{% for o in user.orders %}{{ o.id }}{% endfor %}{% set n = user.name %}{{ n }}"), &jinja));
+        assert_eq!(error("{{ username }}", &jinja), Some(UNKNOWN_VARIABLE));
+        assert_eq!(error("{% for o in orders %}{% endfor %}", &jinja), Some(UNKNOWN_VARIABLE));
+
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"syntax": "liquid"})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        assert_eq!(
+            messages::localize(&detail_placeholder_str("{{#if a}}", &ValidationOptions::default()), &json!("{{#if a}}"), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "mismatched_block",
+                "line": 1,
+                "column": 1,
+                "name": "if",
+                "message": messages::message("mismatched_block", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_placeholder_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+        assert!(!validate_placeholder(&json!(42), &ValidationOptions::default()));
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["{{ a }}", "{{ b }}"], "options": {"variables": ["b"]}})).unwrap();
+        let valid: Vec<bool> = batch.values.iter().map(|value| validate_placeholder(value, &batch.options)).collect();
+        assert_eq!(valid, vec![false, true]);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in ["Hi {{name}}", "{{#if a}}{{/if}}", "", "{{a", "{{#each}}"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// Types for the placeholder validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The template language: Handlebars, whose placeholders Mustache writes the same way, or Jinja (default: "handlebars") */
+    syntax?: "handlebars" | "jinja";
+    /** Variables templates may use, such as ["user", "order.total"]; a name covers its properties, so "user" allows "user.name". Any when unset */
+    variables?: string[];
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    variables: string[];
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Where the problem was found, counting lines and characters from 1 */
+    line?: number;
+    column?: number;
+    /** The unknown variable, or the block that isn't closed or closes the wrong one */
+    name?: string;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "unbalanced_delimiter" | "nested_delimiter" | "invalid_expression" | "unknown_variable" | "mismatched_block" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -1,60 +1,488 @@
 // ABOUTME: WASM component for text validation
-// ABOUTME: Provides a validate function to check if a string contains valid text
+// ABOUTME: Provides validate and coerce functions to check if a string contains valid text
 
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use unicode_segmentation::UnicodeSegmentation;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// The strictness mode shared with the other primitive validators; its number helpers are
+// for the number validators
+#[path = "../../../mode/mode.rs"]
+#[allow(dead_code)]
+mod mode;
+
+use mode::Mode;
+
+// Script hidden in text that's rendered as HTML, for the safe_html option
+mod safehtml;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
 
 #[derive(Deserialize, Serialize)]
 pub struct ValidationInput {
-    value: String,
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
 }
 
-// Internal validation logic that can be tested without WASM
-fn validate_text(text: &str) -> bool {
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // How far to go to read a value: "strict", "lenient" or "coerce"
+    mode: Mode,
+    // For text rendered as HTML with little sanitization: "reject" or "warn" about script tags,
+    // event-handler attributes and javascript: URLs in it; not checked when unset
+    safe_html: Option<SafeHtml>,
+    // The fewest and most units of length the text may have, counted in `unit`
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    // What min_length and max_length count: "bytes" of UTF-8, "chars" (Unicode code points) or
+    // "graphemes", the characters a reader sees, so that an emoji or a letter with combining
+    // accents counts once
+    unit: LengthUnit,
+}
+
+// The units a text's length is counted in
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LengthUnit {
+    Bytes,
+    Chars,
+    #[default]
+    Graphemes,
+}
+
+// What the safe_html option does with text that would run script when rendered
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SafeHtml {
+    // The text is invalid, with the first kind of script found as the error
+    Reject,
+    // The text is valid, with each kind of script found as a warning
+    Warn,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when its length or the safe_html option rejected it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // Script the safe_html option warns about, which doesn't make the value invalid
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<&'static str>,
+}
+
+impl From<Option<String>> for ValidationResult {
+    fn from(coerced: Option<String>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.map_or(Value::Null, Value::String),
+            error: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Text has no alternative spellings, so a valid input is its own canonical value
+fn coerce_text(text: &str, options: &ValidationOptions) -> Option<String> {
     if text.is_empty() {
-        return false;
+        return None;
     }
     
     // Check if text contains valid UTF-8 and has at least one non-whitespace character
-    !text.trim().is_empty() && text.chars().all(|c| {
+    let valid = !text.trim().is_empty() && text.chars().all(|c| {
         // Allow printable characters, whitespace, and common control characters
         c.is_ascii_graphic() || c.is_whitespace() || 
         (c as u32 >= 0x20 && c as u32 != 0x7F) // Non-control characters
-    })
+    });
+
+    let rejected = options.safe_html == Some(SafeHtml::Reject) && !safehtml::hazards(text).is_empty();
+    (valid && !rejected && length_error(text, options).is_none()).then(|| text.to_string())
 }
 
-#[wasm_bindgen]
-pub fn validate(input: JsValue) -> bool {
+// Error codes for text outside the length bounds in the options
+const TOO_SHORT: &str = "too_short";
+const TOO_LONG: &str = "too_long";
+
+// Why a text's length, in the options' unit, is outside their bounds, if it is
+fn length_error(text: &str, options: &ValidationOptions) -> Option<&'static str> {
+    if options.min_length.is_none() && options.max_length.is_none() {
+        return None;
+    }
+    let length = match options.unit {
+        LengthUnit::Bytes => text.len(),
+        LengthUnit::Chars => text.chars().count(),
+        LengthUnit::Graphemes => text.graphemes(true).count(),
+    };
+    if options.min_length.is_some_and(|min| length < min) {
+        Some(TOO_SHORT)
+    } else if options.max_length.is_some_and(|max| length > max) {
+        Some(TOO_LONG)
+    } else {
+        None
+    }
+}
+
+// The text a value is read as: only ever a string in strict and lenient mode; coercive mode
+// also takes numbers and booleans, as JSON writes them
+fn read_text(value: &Value, options: &ValidationOptions) -> Option<String> {
+    match (value, options.mode) {
+        (Value::String(text), _) => Some(text.clone()),
+        (Value::Number(_) | Value::Bool(_), Mode::Coerce) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn coerce_text_value(value: &Value, options: &ValidationOptions) -> Option<String> {
+    coerce_text(&read_text(value, options)?, options)
+}
+
+// The detailed outcome for a value: text the safe_html option rejects has the script found in
+// it as the error, and text it warns about as warnings. Blank text isn't reported as too short
+fn detail_text(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let Some(text) = read_text(value, options) else {
+        return ValidationResult::from(None);
+    };
+    let hazards = match options.safe_html {
+        Some(_) => safehtml::hazards(&text),
+        None => Vec::new(),
+    };
+    match coerce_text(&text, options) {
+        Some(text) => ValidationResult { warnings: hazards, ..ValidationResult::from(Some(text)) },
+        None if text.trim().is_empty() => ValidationResult::from(None),
+        None => {
+            let unsafe_html = hazards.first().copied().filter(|_| options.safe_html == Some(SafeHtml::Reject));
+            ValidationResult { error: unsafe_html.or_else(|| length_error(&text, options)), ..ValidationResult::from(None) }
+        }
+    }
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_text(text: &str, options: &ValidationOptions) -> bool {
+    coerce_text(text, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_text_value(value, &options).map(Value::String)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_text(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
     // Parse the input object
-    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input) {
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
         Ok(val) => val,
         Err(_) => return false,
     };
     
-    validate_text(&input_obj.value)
+    coerce_text_value(&input_obj.value, &input_obj.options).is_some()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_text_value(&input_obj.value, &input_obj.options) {
+        Some(text) => JsValue::from_str(&text),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_text(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(coerce_text_value(value, &batch.options).is_some()))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_text(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    validate_text(value, &ValidationOptions::default())
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_valid_text() {
-        assert!(validate_text("Hello, World!"));
-        assert!(validate_text("Test 123"));
-        assert!(validate_text("Multi\nline\ntext"));
+        assert!(validate_text("Hello, World!", &ValidationOptions::default()));
+        assert!(validate_text("Test 123", &ValidationOptions::default()));
+        assert!(validate_text("Multi\nline\ntext", &ValidationOptions::default()));
     }
 
     #[test]
     fn test_invalid_text() {
-        assert!(!validate_text(""));
-        assert!(!validate_text("   "));
-        assert!(!validate_text("\t\n\r"));
+        assert!(!validate_text("", &ValidationOptions::default()));
+        assert!(!validate_text("   ", &ValidationOptions::default()));
+        assert!(!validate_text("\t\n\r", &ValidationOptions::default()));
     }
 
     #[test]
     fn test_special_characters() {
-        assert!(validate_text("Special: @#$%^&*()"));
-        assert!(validate_text("Émoji text")); // Non-ASCII but valid
+        assert!(validate_text("Special: @#$%^&*()", &ValidationOptions::default()));
+        assert!(validate_text("Émoji text", &ValidationOptions::default())); // Non-ASCII but valid
+    }
+
+    #[test]
+    fn test_options() {
+        // Options are optional and may be empty
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "Hello"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "Hello", "options": {}})).is_ok());
+
+        // Unknown options are rejected so typos never silently change behaviour
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "Hello", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_modes() {
+        let strict: ValidationOptions = serde_json::from_value(json!({"mode": "strict"})).unwrap();
+        let coerce: ValidationOptions = serde_json::from_value(json!({"mode": "coerce"})).unwrap();
+        let defaults = ValidationOptions::default();
+
+        // Strings are text in every mode; numbers and booleans only when coercing
+        assert_eq!(coerce_text_value(&json!("Hello"), &strict), Some("Hello".to_string()));
+        assert_eq!(coerce_text_value(&json!(42), &strict), None);
+        assert_eq!(coerce_text_value(&json!(42), &defaults), None);
+        assert_eq!(coerce_text_value(&json!(42), &coerce), Some("42".to_string()));
+        assert_eq!(coerce_text_value(&json!(2.5), &coerce), Some("2.5".to_string()));
+        assert_eq!(coerce_text_value(&json!(false), &coerce), Some("false".to_string()));
+        assert_eq!(coerce_text_value(&json!(null), &coerce), None);
+        assert_eq!(coerce_text_value(&json!(["a"]), &coerce), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_coerce() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(coerce_text("Hello", &options), Some("Hello".to_string()));
+        assert_eq!(coerce_text("  padded  ", &options), Some("  padded  ".to_string()));
+        assert_eq!(coerce_text("   ", &options), None);
+    }
+
+    #[test]
+    fn test_detailed() {
+        let options = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_text("Hello", &options)),
+            ValidationResult { valid: true, value: json!("Hello"), error: None, warnings: Vec::new() }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_text("   ", &options)),
+            ValidationResult { valid: false, value: Value::Null, error: None, warnings: Vec::new() }
+        );
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["Hello", 1, "  "]})).unwrap();
+        assert_eq!(batch.values.len(), 3);
+        assert!(serde_json::from_value::<BatchInput>(json!({"values": [], "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_safe_html() {
+        let reject: ValidationOptions = serde_json::from_value(json!({"safe_html": "reject"})).unwrap();
+        let warn: ValidationOptions = serde_json::from_value(json!({"safe_html": "warn"})).unwrap();
+        let text = "Nice post! <img src=x onerror=alert(1)> <a href=javascript:void(0)>more</a>";
+
+        // Unchecked without the option
+        assert!(validate_text(text, &ValidationOptions::default()));
+
+        assert!(!validate_text(text, &reject));
+        assert!(validate_text("Nice post! <b>Really</b> [more](https://example.com)", &reject));
+        assert_eq!(
+            messages::localize(&detail_text(&json!(text), &reject), &json!(text), Some("en")),
+            json!({"valid": false, "value": null, "error": "event_handler", "message": messages::message("event_handler", "en").unwrap()})
+        );
+        assert_eq!(detail_text(&json!("   "), &reject).error, None);
+
+        let result = detail_text(&json!(text), &warn);
+        assert!(result.valid);
+        assert_eq!(result.warnings, [safehtml::EVENT_HANDLER, safehtml::JAVASCRIPT_URL]);
+        assert_eq!(coerce_value(&json!("<script>x</script>"), &json!({"safe_html": "warn"})), Some(json!("<script>x</script>")));
+        assert_eq!(coerce_value(&json!("<script>x</script>"), &json!({"safe_html": "reject"})), None);
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"safe_html": "strip"})).is_err());
+
+        // Non-ASCII attribute names are safe in both modes
+        assert!(validate_text("<a aé=1>x</a>", &reject));
+        assert!(detail_text(&json!("<a aé=1>x</a>"), &warn).warnings.is_empty());
+    }
+
+    #[test]
+    fn test_lengths() {
+        let options = |options: Value| serde_json::from_value::<ValidationOptions>(options).unwrap();
+        let tweet = options(json!({"max_length": 6}));
+
+        // A family emoji and a letter with two combining accents are one grapheme each, but
+        // several code points and more bytes
+        let text = "Hi 👨\u{200d}👩\u{200d}👧 a\u{301}\u{323}";
+        assert!(validate_text(text, &tweet));
+        assert!(!validate_text(text, &options(json!({"max_length": 11, "unit": "chars"}))));
+        assert!(validate_text(text, &options(json!({"max_length": 12, "unit": "chars"}))));
+        assert!(!validate_text(text, &options(json!({"max_length": 26, "unit": "bytes"}))));
+        assert!(validate_text(text, &options(json!({"max_length": 27, "unit": "bytes"}))));
+
+        assert_eq!(detail_text(&json!("Hello, world"), &tweet).error, Some(TOO_LONG));
+        assert_eq!(detail_text(&json!("Hi"), &options(json!({"min_length": 3}))).error, Some(TOO_SHORT));
+        assert_eq!(detail_text(&json!("   "), &options(json!({"min_length": 3}))).error, None);
+        // Padding counts, as the text is kept as written, and coerced numbers are measured too
+        assert!(!validate_text(" Hey ", &options(json!({"max_length": 4}))));
+        assert_eq!(coerce_value(&json!(123456), &json!({"mode": "coerce", "max_length": 5})), None);
+        assert!(serde_json::from_value::<ValidationOptions>(json!({"unit": "words"})).is_err());
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["Hello", "  padded  ", "   ", ""] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
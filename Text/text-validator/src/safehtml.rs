@@ -0,0 +1,201 @@
+// ABOUTME: Finds the markup in free text that runs script when the text is rendered as HTML
+// ABOUTME: Script tags, event-handler attributes, and javascript: URLs in attributes or Markdown links
+
+use serde_json::{json, Value};
+
+// The codes for a <script> tag, an on* attribute such as onerror, and a javascript: URL
+pub const SCRIPT_TAG: &str = "script_tag";
+pub const EVENT_HANDLER: &str = "event_handler";
+pub const JAVASCRIPT_URL: &str = "javascript_url";
+
+// The named character references that spell out a URL scheme in an attribute, as in
+// "javascript&colon;", or hide it, as "&Tab;" does; numeric references are decoded too
+const NAMED_REFERENCES: &[(&str, char)] = &[
+    ("colon", ':'),
+    ("tab", '\t'),
+    ("newline", '\n'),
+    ("lpar", '('),
+    ("rpar", ')'),
+    ("amp", '&'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("lt", '<'),
+    ("gt", '>'),
+];
+
+// Decodes the character references in an attribute value as a browser does, where the
+// semicolon after a numeric one is optional
+fn decode_references(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(index) = rest.find('&') {
+        decoded.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+        let numeric = rest.strip_prefix('#').map(|number| match number.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16, rest.len() - hex.len()),
+            None => (number, 10, 1),
+        });
+        if let Some((digits, radix, prefix)) = numeric {
+            let length = digits.find(|c: char| !c.is_digit(radix)).unwrap_or(digits.len());
+            if let Some(c) = u32::from_str_radix(&digits[..length], radix).ok().and_then(char::from_u32) {
+                decoded.push(c);
+                rest = &rest[prefix + length..];
+                rest = rest.strip_prefix(';').unwrap_or(rest);
+                continue;
+            }
+        }
+        let named = NAMED_REFERENCES.iter().find(|(name, _)| rest.get(..name.len() + 1).is_some_and(|start| start.eq_ignore_ascii_case(&format!("{name};"))));
+        match named {
+            Some((name, c)) => {
+                decoded.push(*c);
+                rest = &rest[name.len() + 1..];
+            }
+            None => decoded.push('&'),
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+// Whether a link target is a javascript: URL. The URL validator reads it as a browser does,
+// dropping the spaces around it and the tabs and line breaks inside it, and putting the scheme
+// in lower case
+fn is_javascript_url(target: &str) -> bool {
+    let canonical = url_validator::coerce_value(&Value::from(target), &json!({ "allow_any_scheme": true }));
+    canonical.as_ref().and_then(Value::as_str).is_some_and(|url| url.starts_with("javascript:"))
+}
+
+// The tags in a text, as each one's name and attributes, an attribute's value decoded and
+// empty when it has none. A "<" not followed by a letter, as in "a < b", doesn't start a tag,
+// and a tag that isn't closed runs to the end of the text, since a page may close it
+fn tags(text: &str) -> Vec<(&str, Vec<(&str, String)>)> {
+    let bytes = text.as_bytes();
+    let mut tags = Vec::new();
+    let mut position = 0;
+    while let Some(index) = text[position..].find('<') {
+        let start = position + index + 1;
+        position = start;
+        if !bytes.get(start).is_some_and(u8::is_ascii_alphabetic) {
+            continue;
+        }
+        let name_end = start + text[start..].find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>').unwrap_or(text.len() - start);
+        let mut attributes = Vec::new();
+        let mut index = name_end;
+        while index < bytes.len() && bytes[index] != b'>' {
+            if bytes[index].is_ascii_whitespace() || bytes[index] == b'/' {
+                index += 1;
+                continue;
+            }
+            let name_start = index;
+            while index < bytes.len() && !(bytes[index].is_ascii_whitespace() || b"/>=".contains(&bytes[index])) {
+                index += 1;
+            }
+            let name = &text[name_start..index];
+            while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+                index += 1;
+            }
+            let mut value = "";
+            if bytes.get(index) == Some(&b'=') {
+                index += 1;
+                while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+                    index += 1;
+                }
+                // A quoted value ends at its closing quote, which is skipped, and an unquoted one
+                // at a space or the end of the tag
+                let (value_start, value_end, next) = match bytes.get(index) {
+                    Some(&quote @ (b'"' | b'\'')) => {
+                        let end = text[index + 1..].find(char::from(quote)).map_or(text.len(), |length| index + 1 + length);
+                        (index + 1, end, (end + 1).min(text.len()))
+                    }
+                    _ => {
+                        let end = index + text[index..].find(|c: char| c.is_ascii_whitespace() || c == '>').unwrap_or(text.len() - index);
+                        (index, end, end)
+                    }
+                };
+                value = &text[value_start..value_end];
+                index = next;
+            }
+            attributes.push((name, decode_references(value)));
+        }
+        tags.push((&text[start..name_end], attributes));
+        position = index.max(start);
+    }
+    tags
+}
+
+// The targets of Markdown links and images, "[text](target)", which renderers turn into
+// href and src attributes
+fn markdown_targets(text: &str) -> impl Iterator<Item = &str> {
+    text.match_indices("](").map(move |(index, _)| {
+        let rest = &text[index + 2..];
+        let rest = rest.trim_start();
+        &rest[..rest.find(|c: char| c.is_whitespace() || c == ')').unwrap_or(rest.len())]
+    })
+}
+
+// The kinds of script the text would run if rendered as HTML, each once, in the order they
+// turn up: tags first, then Markdown links
+pub fn hazards(text: &str) -> Vec<&'static str> {
+    let mut found = Vec::new();
+    let mut report = |code| {
+        if !found.contains(&code) {
+            found.push(code);
+        }
+    };
+    for (name, attributes) in tags(text) {
+        if name.eq_ignore_ascii_case("script") {
+            report(SCRIPT_TAG);
+        }
+        for (attribute, value) in attributes {
+            // Compared as bytes, since a name such as "aé" has no character boundary after its
+            // second byte
+            let name = attribute.as_bytes();
+            let handler = name.len() > 2 && name[..2].eq_ignore_ascii_case(b"on") && name[2..].iter().all(u8::is_ascii_alphabetic);
+            if handler {
+                report(EVENT_HANDLER);
+            } else if is_javascript_url(&value) {
+                report(JAVASCRIPT_URL);
+            }
+        }
+    }
+    if markdown_targets(text).any(|target| is_javascript_url(&decode_references(target))) {
+        report(JAVASCRIPT_URL);
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags() {
+        let tags = tags("a < b <b>bold</b> <a href=\"/x?a=1&amp;b=2\" title='it\"s' data-x=y hidden>link</a> <img src=x onerror=alert(1)");
+        let names: Vec<&str> = tags.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["b", "a", "img"]);
+        assert_eq!(tags[1].1, [("href", "/x?a=1&b=2".to_string()), ("title", "it\"s".to_string()), ("data-x", "y".to_string()), ("hidden", String::new())]);
+        assert_eq!(tags[2].1, [("src", "x".to_string()), ("onerror", "alert(1)".to_string())]);
+    }
+
+    #[test]
+    fn test_references() {
+        assert_eq!(decode_references("javascript&colon;alert&lpar;1&rpar;"), "javascript:alert(1)");
+        assert_eq!(decode_references("&#106;ava&#x53;cript&#58alert(1)"), "javaScript:alert(1)");
+        assert_eq!(decode_references("java&Tab;script:x &unknown; & &#;"), "java\tscript:x &unknown; & &#;");
+    }
+
+    #[test]
+    fn test_hazards() {
+        assert!(hazards("I <3 <b>HTML</b>, and 2 < 3 > 1; see [docs](https://example.com/docs)").is_empty());
+        assert_eq!(hazards("<SCRIPT src=//evil.example></SCRIPT>"), [SCRIPT_TAG]);
+        assert_eq!(hazards("<svg/onload=alert(1)>"), [EVENT_HANDLER]);
+        assert_eq!(hazards("<a href=\" JaVa&#09;ScRiPt:alert(1)\">x</a> <img src=x onerror=y>"), [JAVASCRIPT_URL, EVENT_HANDLER]);
+        assert_eq!(hazards("[click me](javascript:alert(document.cookie))"), [JAVASCRIPT_URL]);
+        // Attributes that merely start with "on" aren't handlers, and text about javascript:
+        // URLs isn't one
+        assert!(hazards("<input one-time=\"1\"> the javascript: scheme").is_empty());
+        // Names with non-ASCII characters, even straddling the "on", are read without panicking
+        assert!(hazards("<a aé=1 é=2 oé=3 onclíck=4>").is_empty());
+        assert_eq!(hazards("<a aé=1 onclick=2>"), [EVENT_HANDLER]);
+    }
+}
@@ -0,0 +1,47 @@
+// Types for the text validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** How far to go to read a value: "strict" and "lenient" take only strings, and "coerce" also numbers and booleans, as JSON writes them (default: "lenient") */
+    mode?: "strict" | "lenient" | "coerce";
+    /** For text rendered as HTML with little sanitization: "reject" makes text with a <script> tag, an event-handler attribute such as onerror, or a javascript: URL in an attribute or a Markdown link invalid, and "warn" reports them as warnings; not checked when unset */
+    safe_html?: "reject" | "warn";
+    /** The fewest units of length the text may have, counted in unit */
+    min_length?: number;
+    /** The most units of length the text may have, counted in unit, such as 280 for a post */
+    max_length?: number;
+    /** What min_length and max_length count: "bytes" of UTF-8, "chars" (Unicode code points), or "graphemes", the characters a reader sees, so that an emoji or a letter with combining accents counts once (default: "graphemes") */
+    unit?: "bytes" | "chars" | "graphemes";
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string | number | boolean;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string;
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Script the safe_html option warns about, which doesn't make the value invalid */
+    warnings?: ("script_tag" | "event_handler" | "javascript_url")[];
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "too_short" | "too_long" | "script_tag" | "event_handler" | "javascript_url" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
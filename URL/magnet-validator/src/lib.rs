@@ -0,0 +1,520 @@
+// ABOUTME: WASM component for BitTorrent magnet links, such as magnet:?xt=urn:btih:...&tr=udp://tracker.example.org:1337
+// ABOUTME: Checks the exact topic's info hash, hex or base32, v1 or v2, and that each tracker is a well-formed URL
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Reject links without a tracker, which only the DHT and peer exchange can resolve
+    require_trackers: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // The parameter the problem was found in, as written, such as "xt" or "tr.2"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameter: Option<String>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, parameter: None }
+    }
+}
+
+// An error code and the parameter it was found in, when there is one
+type Problem = (&'static str, Option<String>);
+
+// Error codes reported in detailed results
+const BAD_SCHEME: &str = messages::ErrorCode::BadScheme.as_str();
+const MALFORMED_PARAMETER: &str = "malformed_parameter";
+const MISSING_EXACT_TOPIC: &str = "missing_exact_topic";
+const INVALID_INFO_HASH: &str = "invalid_info_hash";
+const INVALID_TRACKER: &str = "invalid_tracker";
+const MISSING_TRACKER: &str = "missing_tracker";
+
+// The schemes trackers announce over: HTTP(S), UDP (BEP 15), and WebSockets for WebTorrent
+const TRACKER_SCHEMES: &[&str] = &["http", "https", "udp", "ws", "wss"];
+
+// The multihash prefix of a BitTorrent v2 info hash: SHA-256, 32 bytes
+const SHA256_MULTIHASH: &str = "1220";
+
+// An exact topic: a BitTorrent v1 or v2 info hash, in lower-case hex, or a URN of another
+// network, such as "urn:sha1:" or "urn:ed2k:", which isn't checked
+enum Topic {
+    V1(String),
+    V2(String),
+    Other,
+}
+
+// Decodes %XX escapes, as in the parameters of a magnet link
+fn percent_decode(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+            decoded.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+// Decodes RFC 4648 base32, ignoring case and without padding; None for a character outside
+// its alphabet
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in text.bytes() {
+        let value = match c.to_ascii_uppercase() {
+            c @ b'A'..=b'Z' => c - b'A',
+            c @ b'2'..=b'7' => c - b'2' + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5 | u32::from(value)) & 0xfff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+// Reads an exact topic. A v1 info hash, after "urn:btih:", is the 20 bytes of a SHA-1 hash
+// as 40 hex digits or 32 base32 characters; a v2 one, after "urn:btmh:", a SHA-256 multihash
+// as hex: "1220" and 64 digits
+fn exact_topic(topic: &str) -> Result<Topic, &'static str> {
+    let after = |prefix: &str| topic.get(..prefix.len()).filter(|start| start.eq_ignore_ascii_case(prefix)).map(|_| &topic[prefix.len()..]);
+    let is_hex = |text: &str, length| text.len() == length && text.bytes().all(|byte| byte.is_ascii_hexdigit());
+    if let Some(hash) = after("urn:btih:") {
+        if is_hex(hash, 40) {
+            return Ok(Topic::V1(hash.to_ascii_lowercase()));
+        }
+        let decoded = base32_decode(hash).filter(|_| hash.len() == 32);
+        return decoded.map(|bytes| Topic::V1(hex(&bytes))).ok_or(INVALID_INFO_HASH);
+    }
+    if let Some(hash) = after("urn:btmh:") {
+        let valid = is_hex(hash, 68) && hash.starts_with(SHA256_MULTIHASH);
+        return valid.then(|| Topic::V2(hash.to_ascii_lowercase())).ok_or(INVALID_INFO_HASH);
+    }
+    Ok(Topic::Other)
+}
+
+// A tracker's canonical URL: an HTTP(S), UDP or WebSocket URL with a host, checked and
+// normalized by the URL validator. UDP has no default port, so a UDP tracker needs one
+fn tracker(text: &str) -> Option<String> {
+    let canonical = url_validator::coerce_value(&Value::from(text), &json!({ "allowed_schemes": TRACKER_SCHEMES }))?;
+    let mut url = Url::parse(canonical.as_str()?).ok()?;
+    // The url crate lower-cases the hosts of the schemes it knows, which UDP isn't
+    if let Some(host) = url.host_str().map(str::to_ascii_lowercase) {
+        url.set_host(Some(&host)).ok()?;
+    }
+    let has_port = url.scheme() != "udp" || url.port().is_some();
+    (url.host().is_some() && has_port).then(|| url.to_string())
+}
+
+// Checks a magnet link: "magnet:?" and "&"-separated parameters, with at least one BitTorrent
+// exact topic (xt), and trackers (tr) that are well-formed URLs. Parameters may be numbered,
+// as "xt.1" and "tr.2" are, and those it doesn't know are left alone. Returns the info hashes,
+// the first of each version, the display name (dn), the length (xl) and the trackers
+fn check_magnet(text: &str, options: &ValidationOptions) -> Result<Value, Problem> {
+    let rest = text.get(..7).filter(|scheme| scheme.eq_ignore_ascii_case("magnet:")).map(|_| &text[7..]).ok_or((BAD_SCHEME, None))?;
+    let query = match rest.strip_prefix('?') {
+        Some(query) => query,
+        None if rest.is_empty() => return Err((MISSING_EXACT_TOPIC, None)),
+        None => return Err((MALFORMED_PARAMETER, None)),
+    };
+
+    let (mut v1, mut v2, mut name, mut size) = (None, None, None, None);
+    let mut trackers: Vec<String> = Vec::new();
+    for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+        let (key, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+        let problem = |code| (code, Some(key.to_string()));
+        let decoded = percent_decode(value).ok_or_else(|| problem(MALFORMED_PARAMETER))?;
+        match key.split('.').next().unwrap_or_default() {
+            "xt" => match exact_topic(&decoded).map_err(problem)? {
+                Topic::V1(hash) => v1 = v1.or(Some(hash)),
+                Topic::V2(hash) => v2 = v2.or(Some(hash)),
+                Topic::Other => {}
+            },
+            // Names are often written with "+" for a space, as in a form's query string
+            "dn" => {
+                let decoded = percent_decode(&value.replace('+', " ")).ok_or_else(|| problem(MALFORMED_PARAMETER))?;
+                name = name.or(Some(decoded));
+            }
+            "xl" => {
+                let length = decoded.parse::<u64>().ok().filter(|_| decoded.bytes().all(|byte| byte.is_ascii_digit()));
+                size = size.or(Some(length.ok_or_else(|| problem(MALFORMED_PARAMETER))?));
+            }
+            "tr" => {
+                let url = tracker(&decoded).ok_or_else(|| problem(INVALID_TRACKER))?;
+                if !trackers.contains(&url) {
+                    trackers.push(url);
+                }
+            }
+            _ => {}
+        }
+    }
+    if v1.is_none() && v2.is_none() {
+        return Err((MISSING_EXACT_TOPIC, None));
+    }
+    if options.require_trackers && trackers.is_empty() {
+        return Err((MISSING_TRACKER, None));
+    }
+
+    let mut canonical = serde_json::Map::new();
+    let fields = [("info_hash", v1.map(Value::from)), ("info_hash_v2", v2.map(Value::from)), ("name", name.map(Value::from)), ("size", size.map(Value::from))];
+    canonical.extend(fields.into_iter().filter_map(|(field, value)| Some((field.to_string(), value?))));
+    canonical.insert("trackers".to_string(), Value::from(trackers));
+    Ok(Value::Object(canonical))
+}
+
+fn detail_magnet_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank link gets the shared code for an empty value
+    let text = text.trim();
+    if text.is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_magnet(text, options) {
+        Ok(canonical) => ValidationResult::from(Some(canonical)),
+        Err((code, parameter)) => ValidationResult { error: Some(code), parameter, ..ValidationResult::from(None) },
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_magnet_str directly
+fn detail_magnet(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_magnet_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the link's info hashes, name, size and trackers
+fn coerce_magnet(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_magnet(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_magnet(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_magnet(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_magnet(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_magnet(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_magnet(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_magnet(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_magnet(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_magnet(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_magnet(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_magnet_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "c12fe1c06bba254a9dc9f519b335aa7c1367a88a";
+
+    fn options(options: Value) -> ValidationOptions {
+        serde_json::from_value(options).unwrap()
+    }
+
+    fn coerce(text: &str) -> Option<Value> {
+        coerce_magnet(&json!(text), &ValidationOptions::default())
+    }
+
+    fn problem(text: &str, options: &ValidationOptions) -> (Option<&'static str>, Option<String>) {
+        let result = detail_magnet_str(text, options);
+        (result.error, result.parameter)
+    }
+
+    #[test]
+    fn test_info_hash() {
+        assert_eq!(coerce(&format!("magnet:?xt=urn:btih:{}", HASH.to_uppercase())), Some(json!({"info_hash": HASH, "trackers": []})));
+        assert_eq!(coerce("MAGNET:?xt=URN:BTIH:yex6dqdlxisuvhoj6um3gnnkpqjwpkek"), Some(json!({"info_hash": HASH, "trackers": []})));
+        let v2 = "1220caf1e1c30e81cb361b9ee167c4aa64228a7fa4fa9f6105232b28ad099f3a302e";
+        assert_eq!(
+            coerce(&format!("magnet:?xt=urn:btih:{HASH}&xt=urn:btmh:{}&xl=1048576", v2.to_uppercase())),
+            Some(json!({"info_hash": HASH, "info_hash_v2": v2, "size": 1048576, "trackers": []}))
+        );
+        // Other networks' topics are left alone, beside a BitTorrent one
+        assert!(coerce(&format!("magnet:?xt.1=urn:sha1:YNCKHTQCWBTRNJIV4WNAE52SJUQCZO5C&xt.2=urn:btih:{HASH}")).is_some());
+
+        let default = ValidationOptions::default();
+        let missing = (Some(MISSING_EXACT_TOPIC), None);
+        assert_eq!(problem("magnet:", &default), missing);
+        assert_eq!(problem("magnet:?dn=file", &default), missing);
+        assert_eq!(problem("magnet:?xt=urn:sha1:YNCKHTQCWBTRNJIV4WNAE52SJUQCZO5C", &default), missing);
+        let invalid = (Some(INVALID_INFO_HASH), Some("xt".to_string()));
+        assert_eq!(problem(&format!("magnet:?xt=urn:btih:{}", &HASH[1..]), &default), invalid);
+        assert_eq!(problem(&format!("magnet:?xt=urn:btih:{}g", &HASH[1..]), &default), invalid);
+        assert_eq!(problem("magnet:?xt=urn:btih:YEX6DQDLXISUVHOJ6UM3GNNKPQJWPKE1", &default), invalid);
+        assert_eq!(problem(&format!("magnet:?xt=urn:btmh:1114{HASH}"), &default), invalid);
+        assert_eq!(problem(&format!("magnet:?xt=urn:btih:{HASH}&xl=-1"), &default), (Some(MALFORMED_PARAMETER), Some("xl".to_string())));
+        assert_eq!(problem(&format!("magnet:?xt=urn:btih:{HASH}&dn=100%"), &default), (Some(MALFORMED_PARAMETER), Some("dn".to_string())));
+        assert_eq!(problem(&format!("magnet:xt=urn:btih:{HASH}"), &default), (Some(MALFORMED_PARAMETER), None));
+        assert_eq!(problem(&format!("https://example.com/?xt=urn:btih:{HASH}"), &default), (Some(BAD_SCHEME), None));
+    }
+
+    #[test]
+    fn test_trackers() {
+        let link = format!(
+            "magnet:?xt=urn:btih:{HASH}&dn=Big+Buck%20Bunny&tr=udp%3A%2F%2FTracker.Example.org%3A1337%2Fannounce&tr.1=https://tracker.example.com/announce&tr=wss://tracker.example.net&tr=udp://tracker.example.org:1337/announce&x.pe=203.0.113.5:6881"
+        );
+        assert_eq!(
+            coerce(&link),
+            Some(json!({
+                "info_hash": HASH,
+                "name": "Big Buck Bunny",
+                "trackers": ["udp://tracker.example.org:1337/announce", "https://tracker.example.com/announce", "wss://tracker.example.net/"],
+            }))
+        );
+
+        let default = ValidationOptions::default();
+        for tracker in ["udp://tracker.example.org/announce", "ftp://tracker.example.org/", "http://", "tracker.example.org:80", "udp%3A%2F%2F"] {
+            assert_eq!(problem(&format!("magnet:?xt=urn:btih:{HASH}&tr.3={tracker}"), &default), (Some(INVALID_TRACKER), Some("tr.3".to_string())), "{tracker}");
+        }
+
+        let require = options(json!({"require_trackers": true}));
+        assert_eq!(problem(&format!("magnet:?xt=urn:btih:{HASH}"), &require), (Some(MISSING_TRACKER), None));
+        assert!(validate_magnet(&json!(format!("magnet:?xt=urn:btih:{HASH}&tr=http://tracker.example.org/announce")), &require));
+    }
+
+    #[test]
+    fn test_detailed() {
+        let link = format!("magnet:?xt=urn:btih:{HASH}&tr=gopher://tracker.example.org");
+        assert_eq!(
+            messages::localize(&detail_magnet_str(&link, &ValidationOptions::default()), &json!(link), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "invalid_tracker",
+                "parameter": "tr",
+                "message": messages::message("invalid_tracker", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_magnet_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+        assert!(!validate_magnet(&json!(42), &ValidationOptions::default()));
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": [format!("magnet:?xt=urn:btih:{HASH}"), format!("magnet:?xt=urn:btih:{HASH}&tr=udp://tracker.example.org:1337")], "options": {"require_trackers": true}})).unwrap();
+        let valid: Vec<bool> = batch.values.iter().map(|value| validate_magnet(value, &batch.options)).collect();
+        assert_eq!(valid, vec![false, true]);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in [format!("magnet:?xt=urn:btih:{HASH}"), format!("magnet:?xt=urn:btih:{HASH}&tr=x"), "".to_string(), "magnet:?dn=x".to_string()] {
+            assert_eq!(validate_str(&text), coerce_value(&Value::from(text.as_str()), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// Types for the magnet validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Reject links without a tracker, which only the DHT and peer exchange can resolve (default: false) */
+    require_trackers?: boolean;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    info_hash?: string;
+    info_hash_v2?: string;
+    name?: string;
+    size?: number;
+    trackers: string[];
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** The parameter the problem was found in, as written, such as "xt" or "tr.2" */
+    parameter?: string;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "bad_scheme" | "malformed_parameter" | "missing_exact_topic" | "invalid_info_hash" | "invalid_tracker" | "missing_tracker" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
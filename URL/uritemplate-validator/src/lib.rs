@@ -0,0 +1,479 @@
+// ABOUTME: WASM component for RFC 6570 URI templates, such as https://api.example.com/users/{id}{?fields*}
+// ABOUTME: Checks literals, balanced braces, operators, variable names and modifiers, and the level a template needs
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // The highest RFC 6570 level the expanding library supports, from 1 to 4
+    level: u8,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { level: 4 }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    // Where the problem was found, counting characters from 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None, column: None }
+    }
+}
+
+// An error code and the byte offset it was found at
+type Problem = (&'static str, usize);
+
+// Error codes reported in detailed results
+const UNBALANCED_DELIMITER: &str = "unbalanced_delimiter";
+const INVALID_EXPRESSION: &str = "invalid_expression";
+const INVALID_OPERATOR: &str = "invalid_operator";
+const INVALID_VARIABLE_MODIFIER: &str = "invalid_variable_modifier";
+const INVALID_LITERAL: &str = "invalid_literal";
+const UNSUPPORTED_LEVEL: &str = "unsupported_level";
+
+// Operators and the level that introduced them: reserved and fragment expansion at level 2,
+// labels, path segments, path parameters and queries at level 3
+const OPERATORS: &[(char, u8)] = &[('+', 2), ('#', 2), ('.', 3), ('/', 3), (';', 3), ('?', 3), ('&', 3)];
+
+// Operators RFC 6570 reserves for future extensions
+const RESERVED_OPERATORS: &str = "=,!@|";
+
+// Characters a literal can't hold: besides controls and spaces, those URIs never hold, and the
+// braces and "|" templates keep for themselves
+const EXCLUDED_LITERALS: &str = " \"'<>\\^`{|}";
+
+// Whether a text starts with a percent-encoding, "%" and two hex digits
+fn is_pct_encoded(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() >= 3 && bytes[0] == b'%' && bytes[1].is_ascii_hexdigit() && bytes[2].is_ascii_hexdigit()
+}
+
+// Whether a variable name is well-formed: letters, digits, "_" and percent-encodings, with
+// single dots between them
+fn is_varname(name: &str) -> bool {
+    let mut rest = name;
+    let mut after_dot = true;
+    while let Some(c) = rest.chars().next() {
+        let length = match c {
+            '%' if is_pct_encoded(rest) => 3,
+            '.' if !after_dot => 1,
+            c if c.is_ascii_alphanumeric() || c == '_' => 1,
+            _ => return false,
+        };
+        after_dot = c == '.';
+        rest = &rest[length..];
+    }
+    !after_dot
+}
+
+// Checks an expression's body, between its braces, which starts at the given byte offset;
+// returns the names of its variables and the level it needs: 1 for a single variable, 2 with
+// "+" or "#", 3 with the other operators or several variables, and 4 with modifiers
+fn check_expression(body: &str, offset: usize) -> Result<(Vec<&str>, u8), Problem> {
+    let first = body.chars().next().ok_or((INVALID_EXPRESSION, offset - 1))?;
+    if RESERVED_OPERATORS.contains(first) {
+        return Err((INVALID_OPERATOR, offset));
+    }
+    let (list, mut level) = match OPERATORS.iter().find(|(operator, _)| *operator == first) {
+        Some(&(_, level)) => (&body[1..], level),
+        None => (body, 1),
+    };
+    let list_offset = offset + body.len() - list.len();
+    let mut names = Vec::new();
+    let mut position = list_offset;
+    for varspec in list.split(',') {
+        let name_length = varspec.find([':', '*']).unwrap_or(varspec.len());
+        let (name, modifier) = varspec.split_at(name_length);
+        if !is_varname(name) {
+            return Err((INVALID_EXPRESSION, position));
+        }
+        // An explode, or a prefix of 1 to 9999 characters
+        let valid_modifier = match modifier.strip_prefix(':') {
+            Some(length) => (1..=4).contains(&length.len()) && !length.starts_with('0') && length.bytes().all(|byte| byte.is_ascii_digit()),
+            None => modifier.is_empty() || modifier == "*",
+        };
+        if !valid_modifier {
+            return Err((INVALID_VARIABLE_MODIFIER, position + name_length));
+        }
+        if !modifier.is_empty() {
+            level = 4;
+        }
+        names.push(name);
+        position += varspec.len() + 1;
+    }
+    if names.len() > 1 {
+        level = level.max(3);
+    }
+    Ok((names, level))
+}
+
+// Checks a URI template: literals of the characters URIs hold, with valid percent-encodings,
+// and expressions in braces. Returns the variables it uses, in order of first use, and the
+// level it needs
+fn check_template(text: &str, options: &ValidationOptions) -> Result<(Vec<String>, u8), Problem> {
+    let mut variables: Vec<String> = Vec::new();
+    let mut needed = 1;
+    let mut position = 0;
+    while let Some(c) = text[position..].chars().next() {
+        match c {
+            '{' => {
+                let body_start = position + 1;
+                let end = text[body_start..].find(['{', '}']).map(|index| body_start + index);
+                let end = end.filter(|&end| text[end..].starts_with('}')).ok_or((UNBALANCED_DELIMITER, position))?;
+                let (names, level) = check_expression(&text[body_start..end], body_start)?;
+                if level > options.level {
+                    return Err((UNSUPPORTED_LEVEL, position));
+                }
+                needed = needed.max(level);
+                for name in names {
+                    if !variables.iter().any(|variable| variable == name) {
+                        variables.push(name.to_string());
+                    }
+                }
+                position = end + 1;
+            }
+            '}' => return Err((UNBALANCED_DELIMITER, position)),
+            '%' if !is_pct_encoded(&text[position..]) => return Err((INVALID_LITERAL, position)),
+            c if c.is_control() || EXCLUDED_LITERALS.contains(c) => return Err((INVALID_LITERAL, position)),
+            c => position += c.len_utf8(),
+        }
+    }
+    Ok((variables, needed))
+}
+
+fn detail_uritemplate_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    // A blank template gets the shared code for an empty value
+    if text.trim().is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_template(text, options) {
+        Ok((variables, level)) => ValidationResult::from(Some(json!({ "variables": variables, "level": level }))),
+        Err((code, offset)) => ValidationResult { error: Some(code), column: Some(text[..offset].chars().count() + 1), ..ValidationResult::from(None) },
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_uritemplate_str directly
+fn detail_uritemplate(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_uritemplate_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the variables the template uses and the level it needs
+fn coerce_uritemplate(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_uritemplate(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_uritemplate(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_uritemplate(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_uritemplate(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_uritemplate(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_uritemplate(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_uritemplate(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_uritemplate(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_uritemplate(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_uritemplate(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_uritemplate_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(options: Value) -> ValidationOptions {
+        serde_json::from_value(options).unwrap()
+    }
+
+    fn error(text: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_uritemplate_str(text, options).error
+    }
+
+    #[test]
+    fn test_syntax() {
+        let default = ValidationOptions::default();
+        assert_eq!(
+            coerce_uritemplate(&json!("https://api.example.com/users/{id}{?fields*,id}"), &default),
+            Some(json!({"variables": ["id", "fields"], "level": 4}))
+        );
+        assert_eq!(coerce_uritemplate(&json!("/caf%C3%A9/{user.name,count}"), &default), Some(json!({"variables": ["user.name", "count"], "level": 3})));
+        assert_eq!(coerce_uritemplate(&json!("no expressions"), &default), None);
+        assert_eq!(coerce_uritemplate(&json!("/static/page"), &default), Some(json!({"variables": [], "level": 1})));
+        assert!(validate_uritemplate(&json!("{var:9999}{%41b}"), &default));
+
+        assert_eq!(error("/users/{id", &default), Some(UNBALANCED_DELIMITER));
+        assert_eq!(error("/users/{id{name}}", &default), Some(UNBALANCED_DELIMITER));
+        assert_eq!(error("/users/id}", &default), Some(UNBALANCED_DELIMITER));
+        assert_eq!(error("/users/{}", &default), Some(INVALID_EXPRESSION));
+        assert_eq!(error("/users/{+}", &default), Some(INVALID_EXPRESSION));
+        assert_eq!(error("/users/{a,,b}", &default), Some(INVALID_EXPRESSION));
+        assert_eq!(error("/users/{user-id}", &default), Some(INVALID_EXPRESSION));
+        assert_eq!(error("/users/{a..b}", &default), Some(INVALID_EXPRESSION));
+        assert_eq!(error("/users/{=id}", &default), Some(INVALID_OPERATOR));
+        assert_eq!(error("/users/{|id}", &default), Some(INVALID_OPERATOR));
+        assert_eq!(error("/users/{id:0}", &default), Some(INVALID_VARIABLE_MODIFIER));
+        assert_eq!(error("/users/{id:10000}", &default), Some(INVALID_VARIABLE_MODIFIER));
+        assert_eq!(error("/users/{id*:3}", &default), Some(INVALID_VARIABLE_MODIFIER));
+        assert_eq!(error("/users/{id:}", &default), Some(INVALID_VARIABLE_MODIFIER));
+        assert_eq!(error("/users/<id>", &default), Some(INVALID_LITERAL));
+        assert_eq!(error("/users/100%", &default), Some(INVALID_LITERAL));
+        assert_eq!(error("/users/a b", &default), Some(INVALID_LITERAL));
+
+        // Columns count characters from 1
+        assert_eq!(detail_uritemplate_str("/café/{id", &default).column, Some(7));
+        assert_eq!(detail_uritemplate_str("/x/{id:0}", &default).column, Some(7));
+    }
+
+    #[test]
+    fn test_levels() {
+        let level = |text: &str| coerce_uritemplate(&json!(text), &ValidationOptions::default()).map(|value| value["level"].clone());
+        assert_eq!(level("/users/{id}"), Some(json!(1)));
+        assert_eq!(level("{+base}/files{#section}"), Some(json!(2)));
+        assert_eq!(level("/search{?q}"), Some(json!(3)));
+        assert_eq!(level("{+a,b}"), Some(json!(3)));
+        assert_eq!(level("{/path*}"), Some(json!(4)));
+
+        let two = options(json!({"level": 2}));
+        assert!(validate_uritemplate(&json!("{+base}/users/{id}"), &two));
+        assert_eq!(error("/search{?q}", &two), Some(UNSUPPORTED_LEVEL));
+        assert_eq!(error("/users/{id*}", &two), Some(UNSUPPORTED_LEVEL));
+        // Syntax errors are reported as such, whatever the level
+        assert_eq!(error("/users/{id:0}", &options(json!({"level": 1}))), Some(INVALID_VARIABLE_MODIFIER));
+    }
+
+    #[test]
+    fn test_detailed() {
+        assert_eq!(
+            messages::localize(&detail_uritemplate_str("/users/{id", &ValidationOptions::default()), &json!("/users/{id"), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "unbalanced_delimiter",
+                "column": 8,
+                "message": messages::message("unbalanced_delimiter", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_uritemplate_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+        assert!(!validate_uritemplate(&json!(42), &ValidationOptions::default()));
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["/users/{id}", "{?q}"], "options": {"level": 1}})).unwrap();
+        let valid: Vec<bool> = batch.values.iter().map(|value| validate_uritemplate(value, &batch.options)).collect();
+        assert_eq!(valid, vec![true, false]);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in ["/users/{id}", "{?fields*}", "", "/users/{id", "{=x}"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
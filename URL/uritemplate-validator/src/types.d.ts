@@ -0,0 +1,42 @@
+// Types for the uritemplate validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** The highest RFC 6570 level the expanding library supports: 1 for simple {var} expressions, 2 adds {+var} and {#var}, 3 adds several variables per expression and the . / ; ? & operators, and 4 adds :prefix and * modifiers (default: 4) */
+    level?: number;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = {
+    variables: string[];
+    level: number;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Where the problem was found, counting characters from 1 */
+    column?: number;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "unbalanced_delimiter" | "invalid_expression" | "invalid_operator" | "invalid_variable_modifier" | "invalid_literal" | "unsupported_level" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,362 @@
+// ABOUTME: EXIF metadata for image payloads embedded in data: URLs: GPS coordinates and timestamps
+// ABOUTME: Reads the TIFF structure and reports values that can't be right as warnings, never as errors
+
+use serde::{Deserialize, Serialize};
+
+// Warning codes; metadata is easily edited or corrupted, so it never makes an image invalid
+pub const INVALID_EXIF: &str = "invalid_exif";
+pub const GPS_OUT_OF_RANGE: &str = "gps_out_of_range";
+pub const IMPLAUSIBLE_TIMESTAMP: &str = "implausible_timestamp";
+
+// No photograph older than the first surviving one can have been taken
+const EARLIEST_YEAR: u32 = 1826;
+
+// Tags in the main image directory and the directories it points to
+const DATE_TIME: u16 = 0x0132;
+const EXIF_POINTER: u16 = 0x8769;
+const GPS_POINTER: u16 = 0x8825;
+const DATE_TIME_ORIGINAL: u16 = 0x9003;
+const DATE_TIME_DIGITIZED: u16 = 0x9004;
+const GPS_LATITUDE_REF: u16 = 1;
+const GPS_LATITUDE: u16 = 2;
+const GPS_LONGITUDE_REF: u16 = 3;
+const GPS_LONGITUDE: u16 = 4;
+
+// Directories hold a few dozen entries; anything near this is corrupt
+const MAX_ENTRIES: usize = 1024;
+
+// A date and time, to the second; ordering compares the year first
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct Timestamp {
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+}
+
+impl TryFrom<String> for Timestamp {
+    type Error = String;
+
+    // "YYYY-MM-DD" or an RFC 3339 timestamp; EXIF times have no offset, so any offset or
+    // fraction of a second is ignored
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        let parsed = text.get(..10).and_then(|date| {
+            let time = match text.get(10..) {
+                Some("") => "00:00:00",
+                Some(rest) => rest.strip_prefix(['T', 't', ' ']).and_then(|time| time.get(..8))?,
+                None => return None,
+            };
+            Timestamp::parse(&date.replace('-', ":"), time)
+        });
+        parsed.ok_or_else(|| format!("invalid timestamp {}", text))
+    }
+}
+
+impl From<Timestamp> for String {
+    fn from(timestamp: Timestamp) -> Self {
+        let Timestamp { year, month, day, hour, minute, second } = timestamp;
+        format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}")
+    }
+}
+
+impl Timestamp {
+    // A date as YYYY:MM:DD and a time as HH:MM:SS, the fields of an EXIF timestamp
+    fn parse(date: &str, time: &str) -> Option<Timestamp> {
+        let fields = |text: &str, widths: [usize; 3]| -> Option<Vec<u32>> {
+            let parts: Vec<&str> = text.split(':').collect();
+            let sound = parts.len() == 3
+                && parts.iter().zip(widths).all(|(part, width)| part.len() == width && part.bytes().all(|b| b.is_ascii_digit()));
+            sound.then(|| parts.iter().map(|part| part.parse().unwrap_or(0)).collect())
+        };
+        let (date, time) = (fields(date, [4, 2, 2])?, fields(time, [2, 2, 2])?);
+        let timestamp = Timestamp { year: date[0], month: date[1], day: date[2], hour: time[0], minute: time[1], second: time[2] };
+
+        let year = timestamp.year;
+        let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days = match timestamp.month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        let real = (1..=12).contains(&timestamp.month)
+            && (1..=days).contains(&timestamp.day)
+            && timestamp.hour < 24
+            && timestamp.minute < 60
+            && timestamp.second < 60;
+        real.then_some(timestamp)
+    }
+}
+
+// The TIFF structure EXIF is stored in: a byte order mark, then directories of 12-byte entries
+struct Tiff<'a> {
+    bytes: &'a [u8],
+    big_endian: bool,
+}
+
+// An entry's tag and field type, how many values it holds, and where the entry starts
+struct Entry {
+    tag: u16,
+    kind: u16,
+    count: usize,
+    pos: usize,
+}
+
+impl<'a> Tiff<'a> {
+    fn u16(&self, pos: usize) -> Option<u16> {
+        let b = self.bytes.get(pos..)?.get(..2)?;
+        Some(if self.big_endian { u16::from_be_bytes([b[0], b[1]]) } else { u16::from_le_bytes([b[0], b[1]]) })
+    }
+
+    fn u32(&self, pos: usize) -> Option<u32> {
+        let b = self.bytes.get(pos..)?.get(..4)?;
+        Some(if self.big_endian { u32::from_be_bytes([b[0], b[1], b[2], b[3]]) } else { u32::from_le_bytes([b[0], b[1], b[2], b[3]]) })
+    }
+
+    // The entries of the directory at an offset
+    fn directory(&self, offset: usize) -> Option<Vec<Entry>> {
+        let count = usize::from(self.u16(offset)?);
+        if count > MAX_ENTRIES || offset.checked_add(2 + count * 12)? > self.bytes.len() {
+            return None;
+        }
+        (0..count)
+            .map(|i| {
+                let pos = offset + 2 + i * 12;
+                Some(Entry { tag: self.u16(pos)?, kind: self.u16(pos + 2)?, count: self.u32(pos + 4)? as usize, pos })
+            })
+            .collect()
+    }
+
+    // Where an entry's value starts: inside the entry when it fits in four bytes, otherwise at
+    // the offset the entry holds; the value must lie within the data
+    fn start(&self, entry: &Entry) -> Option<usize> {
+        let size = match entry.kind {
+            // BYTE, ASCII, SBYTE and UNDEFINED
+            1 | 2 | 6 | 7 => 1,
+            // SHORT and SSHORT
+            3 | 8 => 2,
+            // LONG, SLONG and FLOAT
+            4 | 9 | 11 => 4,
+            // RATIONAL, SRATIONAL and DOUBLE
+            5 | 10 | 12 => 8,
+            _ => return None,
+        };
+        let length = entry.count.checked_mul(size)?;
+        let start = if length <= 4 { entry.pos + 8 } else { self.u32(entry.pos + 8)? as usize };
+        (start.checked_add(length)? <= self.bytes.len()).then_some(start)
+    }
+
+    // An ASCII value, without the NUL it ends with
+    fn ascii(&self, entry: &Entry) -> Option<&'a str> {
+        if entry.kind != 2 {
+            return None;
+        }
+        let start = self.start(entry)?;
+        std::str::from_utf8(&self.bytes[start..start + entry.count]).ok().map(|text| text.trim_end_matches('\0'))
+    }
+
+    // A RATIONAL value, as numerator and denominator pairs
+    fn rationals(&self, entry: &Entry) -> Option<Vec<(u32, u32)>> {
+        if entry.kind != 5 {
+            return None;
+        }
+        let start = self.start(entry)?;
+        (0..entry.count).map(|i| Some((self.u32(start + i * 8)?, self.u32(start + i * 8 + 4)?))).collect()
+    }
+
+    // The directory a pointer entry points to, if there is one
+    fn pointed(&self, directory: &[Entry], tag: u16) -> Option<Option<Vec<Entry>>> {
+        let Some(entry) = directory.iter().find(|entry| entry.tag == tag) else {
+            return Some(None);
+        };
+        let offset = match entry.kind {
+            4 | 13 if entry.count == 1 => self.u32(entry.pos + 8)? as usize,
+            _ => return None,
+        };
+        self.directory(offset).map(Some)
+    }
+}
+
+// Whether a timestamp could be when the photo was taken: a real date and time after photography
+// began, and not after the time given. Cameras write blanks or zeros when they don't know
+fn plausible(text: &str, taken_before: Option<Timestamp>) -> bool {
+    if text.trim_matches(|c: char| c == ' ' || c == ':' || c == '0').is_empty() {
+        return true;
+    }
+    let timestamp = match text.split_once(' ') {
+        Some((date, time)) => Timestamp::parse(date, time),
+        None => None,
+    };
+    timestamp.is_some_and(|timestamp| timestamp.year >= EARLIEST_YEAR && taken_before.is_none_or(|before| timestamp <= before))
+}
+
+// Whether a coordinate's reference and its degrees, minutes and seconds are in range: N or S
+// and up to 90 degrees for a latitude, E or W and up to 180 for a longitude
+fn in_range(reference: Option<&str>, position: &[(u32, u32)], references: [&str; 2], limit: u32) -> bool {
+    if reference.is_some_and(|reference| !references.contains(&reference.trim())) {
+        return false;
+    }
+    let [degrees, minutes, seconds] = match position {
+        [degrees, minutes, seconds] if position.iter().all(|&(_, denominator)| denominator != 0) => {
+            [degrees, minutes, seconds].map(|&(numerator, denominator)| f64::from(numerator) / f64::from(denominator))
+        }
+        _ => return false,
+    };
+    minutes < 60.0 && seconds < 60.0 && degrees + minutes / 60.0 + seconds / 3600.0 <= f64::from(limit)
+}
+
+// Reads the metadata and returns the warnings for it: whether it is malformed, its GPS position
+// out of range, or any of its timestamps implausible
+pub fn warnings(bytes: &[u8], taken_before: Option<Timestamp>) -> Vec<&'static str> {
+    let checked = || -> Option<Vec<&'static str>> {
+        let big_endian = match bytes.get(..4)? {
+            b"II*\0" => false,
+            b"MM\0*" => true,
+            _ => return None,
+        };
+        let tiff = Tiff { bytes, big_endian };
+        let main = tiff.directory(tiff.u32(4)? as usize)?;
+        let exif = tiff.pointed(&main, EXIF_POINTER)?.unwrap_or_default();
+        let gps = tiff.pointed(&main, GPS_POINTER)?.unwrap_or_default();
+
+        let mut warnings = Vec::new();
+        let timestamps = main
+            .iter()
+            .filter(|entry| entry.tag == DATE_TIME)
+            .chain(exif.iter().filter(|entry| matches!(entry.tag, DATE_TIME_ORIGINAL | DATE_TIME_DIGITIZED)));
+        for entry in timestamps {
+            if !plausible(tiff.ascii(entry)?, taken_before) && !warnings.contains(&IMPLAUSIBLE_TIMESTAMP) {
+                warnings.push(IMPLAUSIBLE_TIMESTAMP);
+            }
+        }
+
+        let find = |tag: u16| gps.iter().find(|entry| entry.tag == tag);
+        let coordinates = [(GPS_LATITUDE_REF, GPS_LATITUDE, ["N", "S"], 90), (GPS_LONGITUDE_REF, GPS_LONGITUDE, ["E", "W"], 180)];
+        for (reference_tag, position_tag, references, limit) in coordinates {
+            let reference = match find(reference_tag) {
+                Some(entry) => Some(tiff.ascii(entry)?),
+                None => None,
+            };
+            if let Some(entry) = find(position_tag) {
+                if !in_range(reference, &tiff.rationals(entry)?, references, limit) && !warnings.contains(&GPS_OUT_OF_RANGE) {
+                    warnings.push(GPS_OUT_OF_RANGE);
+                }
+            }
+        }
+        Some(warnings)
+    };
+    checked().unwrap_or_else(|| vec![INVALID_EXIF])
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // A GPS reference and its degrees, minutes and seconds as rationals
+    pub type Coordinate<'a> = (&'a str, [(u32, u32); 3]);
+
+    // Builds little-endian EXIF: a main directory with DateTime and pointers to an Exif directory
+    // with DateTimeOriginal and a GPS directory with the given position
+    pub fn exif(date_time: &str, latitude: Coordinate, longitude: Coordinate) -> Vec<u8> {
+        let entry = |tag: u16, kind: u16, count: u32, value: u32| -> Vec<u8> {
+            [tag.to_le_bytes().to_vec(), kind.to_le_bytes().to_vec(), count.to_le_bytes().to_vec(), value.to_le_bytes().to_vec()].concat()
+        };
+        let short_ascii = |text: &str| -> u32 {
+            let mut bytes = [0u8; 4];
+            bytes[..text.len()].copy_from_slice(text.as_bytes());
+            u32::from_le_bytes(bytes)
+        };
+        let rationals = |values: [(u32, u32); 3]| -> Vec<u8> { values.iter().flat_map(|&(n, d)| [n.to_le_bytes(), d.to_le_bytes()].concat()).collect() };
+
+        // Main directory at 8 (3 entries), Exif directory at 50 (1 entry), GPS directory at 68
+        // (4 entries), then the data: the date (20 bytes) at 122 and the positions at 142 and 166
+        let date = format!("{date_time}\0");
+        let mut bytes = b"II*\0\x08\0\0\0".to_vec();
+        bytes.extend(3u16.to_le_bytes());
+        bytes.extend(entry(DATE_TIME, 2, date.len() as u32, 122));
+        bytes.extend(entry(EXIF_POINTER, 4, 1, 50));
+        bytes.extend(entry(GPS_POINTER, 4, 1, 68));
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend(1u16.to_le_bytes());
+        bytes.extend(entry(DATE_TIME_ORIGINAL, 2, date.len() as u32, 122));
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend(4u16.to_le_bytes());
+        bytes.extend(entry(GPS_LATITUDE_REF, 2, 2, short_ascii(latitude.0)));
+        bytes.extend(entry(GPS_LATITUDE, 5, 3, 142));
+        bytes.extend(entry(GPS_LONGITUDE_REF, 2, 2, short_ascii(longitude.0)));
+        bytes.extend(entry(GPS_LONGITUDE, 5, 3, 166));
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend(format!("{date:\0<20}").bytes());
+        bytes.extend(rationals(latitude.1));
+        bytes.extend(rationals(longitude.1));
+        bytes
+    }
+
+    const LONDON: (Coordinate, Coordinate) =
+        (("N", [(51, 1), (30, 1), (2600, 100)]), ("W", [(0, 1), (7, 1), (3960, 100)]));
+
+    #[test]
+    fn test_timestamps() {
+        let (latitude, longitude) = LONDON;
+        assert!(warnings(&exif("2024:03:14 15:30:00", latitude, longitude), None).is_empty());
+        // Unknown times are written as blanks or zeros
+        assert!(warnings(&exif("0000:00:00 00:00:00", latitude, longitude), None).is_empty());
+        assert!(warnings(&exif("    :  :     :  :  ", latitude, longitude), None).is_empty());
+
+        for implausible in ["2024:02:30 12:00:00", "2024:03:14 25:00:00", "1801:01:01 00:00:00", "14/03/2024 15:30"] {
+            assert_eq!(warnings(&exif(implausible, latitude, longitude), None), vec![IMPLAUSIBLE_TIMESTAMP], "{implausible}");
+        }
+
+        let now = Timestamp::try_from("2024-03-14T16:00:00Z".to_string()).unwrap();
+        assert!(warnings(&exif("2024:03:14 15:30:00", latitude, longitude), Some(now)).is_empty());
+        assert_eq!(warnings(&exif("2024:03:15 09:00:00", latitude, longitude), Some(now)), vec![IMPLAUSIBLE_TIMESTAMP]);
+
+        assert_eq!(String::from(Timestamp::try_from("2024-03-14".to_string()).unwrap()), "2024-03-14T00:00:00");
+        for bad in ["2024-03", "2024-13-01", "2024-03-14 noon", "yesterday"] {
+            assert!(Timestamp::try_from(bad.to_string()).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_gps() {
+        let (latitude, longitude) = LONDON;
+        let date = "2024:03:14 15:30:00";
+        assert!(warnings(&exif(date, ("S", [(90, 1), (0, 1), (0, 1)]), ("E", [(180, 1), (0, 1), (0, 1)])), None).is_empty());
+        // Degrees as a single decimal value
+        assert!(warnings(&exif(date, ("N", [(515072, 10000), (0, 1), (0, 1)]), longitude), None).is_empty());
+
+        for (latitude, longitude) in [
+            (("N", [(91, 1), (0, 1), (0, 1)]), longitude),
+            (("N", [(90, 1), (0, 1), (1, 1)]), longitude),
+            (("N", [(51, 1), (60, 1), (0, 1)]), longitude),
+            (("N", [(51, 1), (30, 1), (0, 0)]), longitude),
+            (("X", [(51, 1), (30, 1), (0, 1)]), longitude),
+            (latitude, ("N", [(0, 1), (7, 1), (0, 1)])),
+            (latitude, ("W", [(181, 1), (0, 1), (0, 1)])),
+        ] {
+            assert_eq!(warnings(&exif(date, latitude, longitude), None), vec![GPS_OUT_OF_RANGE], "{latitude:?} {longitude:?}");
+        }
+
+        // Both problems are reported, once each
+        let both = exif("2099:01:01 00:00:00", ("N", [(99, 1), (0, 1), (0, 1)]), ("W", [(200, 1), (0, 1), (0, 1)]));
+        let now = Timestamp::try_from("2030-01-01".to_string()).unwrap();
+        assert_eq!(warnings(&both, Some(now)), vec![IMPLAUSIBLE_TIMESTAMP, GPS_OUT_OF_RANGE]);
+    }
+
+    #[test]
+    fn test_malformed() {
+        let (latitude, longitude) = LONDON;
+        let good = exif("2024:03:14 15:30:00", latitude, longitude);
+        assert_eq!(warnings(b"not exif", None), vec![INVALID_EXIF]);
+        assert_eq!(warnings(&good[..100], None), vec![INVALID_EXIF]); // Data cut short
+        // A pointer past the end
+        let mut dangling = good.clone();
+        dangling[30] = 0xFF;
+        assert_eq!(warnings(&dangling, None), vec![INVALID_EXIF]);
+        // Big-endian EXIF with an empty main directory
+        assert!(warnings(b"MM\0*\0\0\0\x08\0\0\0\0\0\0", None).is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+// ABOUTME: Homograph checks for internationalized host names, such as "pаypal.com" with a Cyrillic "а"
+// ABOUTME: Flags labels that mix scripts, or are written wholly in Cyrillic or Greek letters that look Latin
+
+use url::{Host, Url};
+
+// The error and warning code for a host name that could pass for another
+pub const CONFUSABLE_HOST: &str = "confusable_host";
+
+// The scripts host names are written in, as far as telling homographs apart needs; digits,
+// hyphens and combining marks belong to none, and scripts not listed are Other
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Thai,
+    Hangul,
+    Hiragana,
+    Katakana,
+    Bopomofo,
+    Han,
+    Other,
+}
+
+// The scripts each character's block belongs to, by code point
+const BLOCKS: &[(u32, u32, Script)] = &[
+    (0x0041, 0x005a, Script::Latin),
+    (0x0061, 0x007a, Script::Latin),
+    (0x00c0, 0x024f, Script::Latin),
+    (0x0370, 0x03ff, Script::Greek),
+    (0x0400, 0x052f, Script::Cyrillic),
+    (0x0530, 0x058f, Script::Armenian),
+    (0x0590, 0x05ff, Script::Hebrew),
+    (0x0600, 0x06ff, Script::Arabic),
+    (0x0750, 0x077f, Script::Arabic),
+    (0x08a0, 0x08ff, Script::Arabic),
+    (0x0900, 0x097f, Script::Devanagari),
+    (0x0e00, 0x0e7f, Script::Thai),
+    (0x1100, 0x11ff, Script::Hangul),
+    (0x1c80, 0x1c8f, Script::Cyrillic),
+    (0x1e00, 0x1eff, Script::Latin),
+    (0x1f00, 0x1fff, Script::Greek),
+    (0x2c60, 0x2c7f, Script::Latin),
+    (0x2de0, 0x2dff, Script::Cyrillic),
+    (0x3005, 0x3007, Script::Han),
+    (0x3040, 0x309f, Script::Hiragana),
+    (0x30a0, 0x30ff, Script::Katakana),
+    (0x3100, 0x312f, Script::Bopomofo),
+    (0x3130, 0x318f, Script::Hangul),
+    (0x31a0, 0x31bf, Script::Bopomofo),
+    (0x31f0, 0x31ff, Script::Katakana),
+    (0x3400, 0x4dbf, Script::Han),
+    (0x4e00, 0x9fff, Script::Han),
+    (0xa640, 0xa69f, Script::Cyrillic),
+    (0xa720, 0xa7ff, Script::Latin),
+    (0xab30, 0xab6f, Script::Latin),
+    (0xac00, 0xd7af, Script::Hangul),
+    (0xf900, 0xfaff, Script::Han),
+    (0x20000, 0x2fa1f, Script::Han),
+];
+
+// The scripts that go together in Japanese, Chinese and Korean names, which may also use Latin
+// letters, as UTS #39's Highly Restrictive level allows
+const JAPANESE: &[Script] = &[Script::Latin, Script::Han, Script::Hiragana, Script::Katakana];
+const CHINESE: &[Script] = &[Script::Latin, Script::Han, Script::Bopomofo];
+const KOREAN: &[Script] = &[Script::Latin, Script::Han, Script::Hangul];
+
+// Cyrillic and Greek letters that look like Latin ones; a label written only in these passes for
+// a Latin one, as "аррӏе" does for "apple"
+const CYRILLIC_LOOKALIKES: &str = "аьсԁеһіјӏорԛѕԝхуѵ";
+const GREEK_LOOKALIKES: &str = "αικνορυχ";
+
+// Top-level domains of countries that write in Cyrillic or Greek, where a label in those
+// letters is expected
+const CYRILLIC_TLDS: &[&str] = &["bg", "by", "kg", "kz", "mk", "mn", "rs", "ru", "su", "ua", "uz"];
+const GREEK_TLDS: &[&str] = &["cy", "gr"];
+
+// The script of a character, or None for one that belongs to none: digits, hyphens, combining
+// marks and the Japanese prolonged sound mark
+fn script(c: char) -> Option<Script> {
+    let code = u32::from(c);
+    if c.is_ascii_digit() || c == '-' || (0x0300..=0x036f).contains(&code) || c == '\u{30fc}' {
+        return None;
+    }
+    let block = BLOCKS.iter().find(|(start, end, _)| (*start..=*end).contains(&code));
+    Some(block.map_or(Script::Other, |&(_, _, script)| script))
+}
+
+// Whether a label mixes scripts in a way no language does
+fn mixes_scripts(label: &str) -> bool {
+    let mut scripts: Vec<Script> = label.chars().filter_map(script).collect();
+    scripts.sort();
+    scripts.dedup();
+    let within = |allowed: &[Script]| scripts.iter().all(|script| allowed.contains(script));
+    scripts.len() > 1 && !(within(JAPANESE) || within(CHINESE) || within(KOREAN))
+}
+
+// Whether a label is written wholly in Cyrillic or Greek letters that look Latin, under a
+// top-level domain where Latin letters would be expected
+fn passes_for_latin(label: &str, tld: &str) -> bool {
+    let letters: Vec<char> = label.chars().filter(|&c| script(c).is_some()).collect();
+    let all_of = |lookalikes: &str| !letters.is_empty() && letters.iter().all(|&c| lookalikes.contains(c));
+    (all_of(CYRILLIC_LOOKALIKES) && !CYRILLIC_TLDS.contains(&tld)) || (all_of(GREEK_LOOKALIKES) && !GREEK_TLDS.contains(&tld))
+}
+
+// Whether the URL's host name could pass for another: one of its labels mixes scripts, or is
+// written in Cyrillic or Greek letters that look Latin. Host names are punycode in canonical
+// URLs, so only those with "xn--" labels need decoding
+pub fn is_confusable(url: &Url) -> bool {
+    let Some(Host::Domain(domain)) = url.host() else {
+        return false;
+    };
+    if !domain.split('.').any(|label| label.starts_with("xn--")) {
+        return false;
+    }
+    let (unicode, _) = idna::domain_to_unicode(domain);
+    let labels: Vec<&str> = unicode.trim_end_matches('.').split('.').collect();
+    // A top-level domain in its own script leaves Latin lookalikes unremarkable
+    let tld = labels.last().copied().unwrap_or_default();
+    let tld = if tld.is_ascii() { tld } else { "" };
+    labels.iter().any(|label| mixes_scripts(label) || (!tld.is_empty() && passes_for_latin(label, tld)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confusable(url: &str) -> bool {
+        is_confusable(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_mixed_scripts() {
+        // A Cyrillic "а" among Latin letters, written in Unicode or in punycode
+        assert!(confusable("https://pаypal.com/"));
+        assert!(confusable("https://xn--pypal-4ve.com/"));
+        assert!(confusable("https://www.gοogle.com/"));
+        assert!(confusable("https://exаmple.рф/"));
+
+        assert!(!confusable("https://paypal.com/"));
+        assert!(!confusable("https://bücher.example/"));
+        assert!(!confusable("https://münchen-2024.de/"));
+        assert!(!confusable("https://пример.рф/"));
+        assert!(!confusable("https://例え.jp/"));
+        assert!(!confusable("https://ソニーbank.jp/"));
+        assert!(!confusable("https://한국abc.kr/"));
+        assert!(!confusable("https://192.0.2.1/"));
+    }
+
+    #[test]
+    fn test_lookalikes() {
+        // Wholly Cyrillic or Greek labels that pass for Latin ones
+        assert!(confusable("https://аррӏе.com/"));
+        assert!(confusable("https://ехсоn.com/"));
+        assert!(confusable("https://νικο.com/"));
+
+        // Under a top-level domain that writes in those scripts, they're expected
+        assert!(!confusable("https://орех.ru/"));
+        assert!(!confusable("https://οικος.gr/"));
+        assert!(!confusable("https://орех.рф/"));
+        // Letters without a Latin lookalike give the label away
+        assert!(!confusable("https://москва.com/"));
+    }
+}
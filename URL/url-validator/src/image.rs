@@ -0,0 +1,394 @@
+// ABOUTME: Content sniffing for image payloads embedded in data: URLs
+// ABOUTME: Checks that decoded bytes carry the signature and structure of their declared image type
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Ico,
+    Svg,
+}
+
+impl ImageFormat {
+    // Maps a declared media type (type/subtype, already lowercased) to the format it promises
+    pub fn from_media_type(media_type: &str) -> Option<ImageFormat> {
+        match media_type {
+            "image/png" => Some(ImageFormat::Png),
+            "image/jpeg" | "image/jpg" | "image/pjpeg" => Some(ImageFormat::Jpeg),
+            "image/gif" => Some(ImageFormat::Gif),
+            "image/webp" => Some(ImageFormat::Webp),
+            "image/x-icon" | "image/vnd.microsoft.icon" => Some(ImageFormat::Ico),
+            "image/svg+xml" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+
+    // Returns true when the bytes really are an image of this format
+    pub fn matches(self, bytes: &[u8]) -> bool {
+        match self {
+            ImageFormat::Png => parse_png(bytes).is_some(),
+            ImageFormat::Jpeg => parse_jpeg(bytes).is_some(),
+            ImageFormat::Gif => parse_gif(bytes).is_some(),
+            ImageFormat::Webp => is_webp(bytes),
+            ImageFormat::Ico => is_ico(bytes),
+            ImageFormat::Svg => is_svg(bytes),
+        }
+    }
+
+    // Returns the EXIF metadata (a TIFF structure) embedded in a JPEG APP1 segment, a PNG eXIf
+    // chunk or a WebP EXIF chunk; other formats, and images without any, report None
+    pub fn exif(self, bytes: &[u8]) -> Option<&[u8]> {
+        match self {
+            ImageFormat::Png => png_exif(bytes),
+            ImageFormat::Jpeg => jpeg_exif(bytes),
+            ImageFormat::Webp => webp_exif(bytes),
+            _ => None,
+        }
+    }
+
+    // Returns (width, height) in pixels as declared by the image header
+    // Only PNG, JPEG and GIF headers are parsed; other formats report None
+    pub fn dimensions(self, bytes: &[u8]) -> Option<(u32, u32)> {
+        match self {
+            ImageFormat::Png => parse_png(bytes),
+            ImageFormat::Jpeg => parse_jpeg(bytes),
+            ImageFormat::Gif => parse_gif(bytes),
+            _ => None,
+        }
+    }
+}
+
+fn read_u16_le(bytes: &[u8], pos: usize) -> Option<u16> {
+    bytes.get(pos..pos + 2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u16_be(bytes: &[u8], pos: usize) -> Option<u16> {
+    bytes.get(pos..pos + 2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_u32_le(bytes: &[u8], pos: usize) -> Option<u32> {
+    bytes.get(pos..pos + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u32_be(bytes: &[u8], pos: usize) -> Option<u32> {
+    bytes.get(pos..pos + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+// Signature, then a chain of length-prefixed chunks starting with IHDR and ending with IEND
+// Returns the dimensions from IHDR when the structure is sound
+fn parse_png(bytes: &[u8]) -> Option<(u32, u32)> {
+    if !bytes.starts_with(PNG_SIGNATURE) {
+        return None;
+    }
+
+    let mut pos = PNG_SIGNATURE.len();
+    let mut first = true;
+    loop {
+        let length = read_u32_be(bytes, pos)? as usize;
+        let chunk_type = bytes.get(pos + 4..pos + 8)?;
+        if first && (chunk_type != b"IHDR" || length != 13) {
+            return None;
+        }
+
+        // Chunk data is followed by a four byte CRC
+        let end = match (pos + 12).checked_add(length) {
+            Some(end) if end <= bytes.len() => end,
+            _ => return None,
+        };
+        if chunk_type == b"IEND" {
+            // IHDR starts with the width and height as big-endian u32s
+            let start = PNG_SIGNATURE.len() + 8;
+            return Some((read_u32_be(bytes, start)?, read_u32_be(bytes, start + 4)?));
+        }
+
+        pos = end;
+        first = false;
+    }
+}
+
+// SOI marker, marker segments up to a frame header and start of scan, then EOI
+// Returns the dimensions from the frame header when the structure is sound
+fn parse_jpeg(bytes: &[u8]) -> Option<(u32, u32)> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) || !bytes.ends_with(&[0xFF, 0xD9]) {
+        return None;
+    }
+
+    let mut pos = 2;
+    let mut frame = None;
+    loop {
+        if bytes.get(pos) != Some(&0xFF) {
+            return None;
+        }
+
+        // Markers may be preceded by any number of 0xFF fill bytes
+        let mut marker_pos = pos + 1;
+        while bytes.get(marker_pos) == Some(&0xFF) {
+            marker_pos += 1;
+        }
+        let marker = *bytes.get(marker_pos)?;
+
+        // Standalone markers carry no length
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            pos = marker_pos + 1;
+            continue;
+        }
+        if marker == 0xD9 {
+            return None;
+        }
+
+        let length = match read_u16_be(bytes, marker_pos + 1) {
+            Some(length) if length >= 2 => length as usize,
+            _ => return None,
+        };
+
+        // SOF0-SOF15, excluding DHT (C4), JPG (C8) and DAC (CC): precision, height, width
+        if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            let height = read_u16_be(bytes, marker_pos + 4)?;
+            let width = read_u16_be(bytes, marker_pos + 6)?;
+            frame = Some((u32::from(width), u32::from(height)));
+        }
+
+        // Start of scan: entropy-coded data runs until the trailing EOI
+        if marker == 0xDA {
+            return frame.filter(|_| marker_pos + 1 + length <= bytes.len() - 2);
+        }
+
+        pos = marker_pos + 1 + length;
+    }
+}
+
+// The eXIf chunk, which must come before the image data
+fn png_exif(bytes: &[u8]) -> Option<&[u8]> {
+    let mut pos = PNG_SIGNATURE.len();
+    loop {
+        let length = read_u32_be(bytes, pos)? as usize;
+        let chunk_type = bytes.get(pos + 4..pos + 8)?;
+        if chunk_type == b"IDAT" || chunk_type == b"IEND" {
+            return None;
+        }
+        let data = bytes.get(pos + 8..(pos + 8).checked_add(length)?)?;
+        if chunk_type == b"eXIf" {
+            return Some(data);
+        }
+        pos += 12 + length;
+    }
+}
+
+// The APP1 segment that starts with "Exif\0\0", among the marker segments before the scan
+fn jpeg_exif(bytes: &[u8]) -> Option<&[u8]> {
+    let mut pos = 2;
+    loop {
+        if bytes.get(pos) != Some(&0xFF) {
+            return None;
+        }
+        let mut marker_pos = pos + 1;
+        while bytes.get(marker_pos) == Some(&0xFF) {
+            marker_pos += 1;
+        }
+        let marker = *bytes.get(marker_pos)?;
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            pos = marker_pos + 1;
+            continue;
+        }
+        if marker == 0xDA || marker == 0xD9 {
+            return None;
+        }
+
+        let length = read_u16_be(bytes, marker_pos + 1)? as usize;
+        let segment = bytes.get(marker_pos + 3..marker_pos + 1 + length)?;
+        if marker == 0xE1 {
+            if let Some(exif) = segment.strip_prefix(b"Exif\0\0") {
+                return Some(exif);
+            }
+        }
+        pos = marker_pos + 1 + length;
+    }
+}
+
+// The EXIF chunk among the RIFF chunks, each padded to an even length; some writers keep the
+// JPEG "Exif\0\0" prefix
+fn webp_exif(bytes: &[u8]) -> Option<&[u8]> {
+    let mut pos = 12;
+    loop {
+        let chunk_type = bytes.get(pos..pos + 4)?;
+        let length = read_u32_le(bytes, pos + 4)? as usize;
+        let data = bytes.get(pos + 8..(pos + 8).checked_add(length)?)?;
+        if chunk_type == b"EXIF" {
+            return Some(data.strip_prefix(b"Exif\0\0").unwrap_or(data));
+        }
+        pos += 8 + length + length % 2;
+    }
+}
+
+// GIF87a/GIF89a header, a logical screen descriptor and the trailer byte
+// Returns the logical screen dimensions when the structure is sound
+fn parse_gif(bytes: &[u8]) -> Option<(u32, u32)> {
+    let valid = (bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a"))
+        && bytes.len() > 13
+        && bytes.last() == Some(&0x3B);
+    if !valid {
+        return None;
+    }
+
+    Some((u32::from(read_u16_le(bytes, 6)?), u32::from(read_u16_le(bytes, 8)?)))
+}
+
+// RIFF container whose declared size fits the payload and whose form type is WEBP
+fn is_webp(bytes: &[u8]) -> bool {
+    if !bytes.starts_with(b"RIFF") || bytes.get(8..12) != Some(b"WEBP") {
+        return false;
+    }
+
+    let riff_size = match read_u32_le(bytes, 4) {
+        Some(size) => u64::from(size),
+        None => return false,
+    };
+
+    riff_size + 8 <= bytes.len() as u64
+        && matches!(bytes.get(12..16), Some(b"VP8 ") | Some(b"VP8L") | Some(b"VP8X"))
+}
+
+// ICONDIR header followed by one directory entry per image, each pointing inside the payload
+fn is_ico(bytes: &[u8]) -> bool {
+    if read_u16_le(bytes, 0) != Some(0) || !matches!(read_u16_le(bytes, 2), Some(1) | Some(2)) {
+        return false;
+    }
+
+    let count = match read_u16_le(bytes, 4) {
+        Some(count) if count > 0 => count as usize,
+        _ => return false,
+    };
+
+    (0..count).all(|i| {
+        let entry = 6 + i * 16;
+        match (read_u32_le(bytes, entry + 8), read_u32_le(bytes, entry + 12)) {
+            (Some(size), Some(offset)) => {
+                let end = u64::from(offset) + u64::from(size);
+                size > 0 && offset as usize >= 6 + count * 16 && end <= bytes.len() as u64
+            }
+            _ => false,
+        }
+    })
+}
+
+// UTF-8 XML whose root element is <svg>, allowing a prolog of declarations and comments
+fn is_svg(bytes: &[u8]) -> bool {
+    let text = match std::str::from_utf8(bytes) {
+        Ok(text) => text.strip_prefix('\u{FEFF}').unwrap_or(text),
+        Err(_) => return false,
+    };
+
+    let mut rest = text.trim_start();
+    loop {
+        let terminator = if rest.starts_with("<?") {
+            "?>"
+        } else if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<!") {
+            ">"
+        } else {
+            break;
+        };
+
+        rest = match rest.find(terminator) {
+            Some(end) => rest[end + terminator.len()..].trim_start(),
+            None => return false,
+        };
+    }
+
+    match rest.strip_prefix("<svg") {
+        Some(after) => {
+            after.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                && (rest.contains("</svg>") || rest.contains("/>"))
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 1x1 PNG
+    const PNG_1X1: &[u8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F, 0x15, 0xC4,
+        0x89, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x44, 0x41, 0x54, 0x78, 0xDA, 0x63, 0x64, 0x60, 0xF8, 0x5F,
+        0x0F, 0x00, 0x02, 0x87, 0x01, 0x80, 0xEB, 0x47, 0xBA, 0x92, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45,
+        0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    #[test]
+    fn test_png() {
+        assert_eq!(parse_png(PNG_1X1), Some((1, 1)));
+        assert_eq!(parse_png(&PNG_1X1[..40]), None); // Truncated before IEND
+        assert_eq!(parse_png(b"\x89PNG\r\n\x1a\n"), None); // Signature only
+        assert_eq!(parse_png(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn test_jpeg() {
+        // SOI, SOF0 (640x480, one component), SOS (no components), one byte of scan data, EOI
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xC0, 0x00, 0x0B, 0x08, 0x01, 0xE0, 0x02, 0x80, 0x01, 0x01, 0x11, 0x00,
+            0xFF, 0xDA, 0x00, 0x02, 0x00, 0xFF, 0xD9,
+        ];
+        assert_eq!(parse_jpeg(&jpeg), Some((640, 480)));
+        assert_eq!(parse_jpeg(&jpeg[..jpeg.len() - 2]), None); // Missing EOI
+        assert_eq!(parse_jpeg(&[0xFF, 0xD8, 0xFF, 0xD9]), None); // No frame or scan
+    }
+
+    #[test]
+    fn test_exif() {
+        // A JPEG with an APP0 segment, then an APP1 segment holding a minimal TIFF header
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xE1, 0x00, 0x10, b'E', b'x', b'i', b'f', 0x00, 0x00,
+            b'I', b'I', 0x2A, 0x00, 0x08, 0x00, 0x00, 0x00, 0xFF, 0xDA, 0x00, 0x02, 0x00, 0xFF, 0xD9,
+        ];
+        assert_eq!(ImageFormat::Jpeg.exif(&jpeg), Some(&b"II*\0\x08\0\0\0"[..]));
+        assert_eq!(ImageFormat::Jpeg.exif(&[0xFF, 0xD8, 0xFF, 0xDA, 0x00, 0x02, 0xFF, 0xD9]), None);
+        assert_eq!(ImageFormat::Jpeg.exif(&jpeg[..14]), None); // Segment cut short
+
+        let mut png = PNG_1X1[..33].to_vec();
+        png.extend_from_slice(b"\0\0\0\x04eXIfMM\0*\0\0\0\0");
+        png.extend_from_slice(&PNG_1X1[33..]);
+        assert_eq!(ImageFormat::Png.exif(&png), Some(&b"MM\0*"[..]));
+        assert_eq!(ImageFormat::Png.exif(PNG_1X1), None);
+
+        let webp = b"RIFF\x1a\0\0\0WEBPVP8L\x01\0\0\0\0\0EXIF\x04\0\0\0II*\0";
+        assert_eq!(ImageFormat::Webp.exif(webp), Some(&b"II*\0"[..]));
+        assert_eq!(ImageFormat::Gif.exif(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn test_gif_webp_ico() {
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&[0x20, 0x00, 0x10, 0x00, 0x00, 0x00, 0x00, 0x3B]);
+        assert_eq!(parse_gif(&gif), Some((32, 16)));
+        assert_eq!(parse_gif(b"GIF89a;"), None);
+
+        let webp = b"RIFF\x0c\x00\x00\x00WEBPVP8L\x00\x00\x00\x00";
+        assert!(is_webp(webp));
+        assert!(!is_webp(b"RIFF\xff\x00\x00\x00WEBPVP8L"));
+
+        let mut ico = vec![0, 0, 1, 0, 1, 0];
+        ico.extend_from_slice(&[1, 1, 0, 0, 1, 0, 32, 0, 4, 0, 0, 0, 22, 0, 0, 0]);
+        ico.extend_from_slice(&[0xAA; 4]);
+        assert!(is_ico(&ico));
+        assert!(!is_ico(&ico[..24])); // Image data cut short
+    }
+
+    #[test]
+    fn test_svg() {
+        assert!(is_svg(b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>"));
+        assert!(is_svg(b"<?xml version=\"1.0\"?>\n<!-- icon -->\n<svg/>"));
+        assert!(is_svg("\u{FEFF}<!DOCTYPE svg><svg viewBox=\"0 0 1 1\"></svg>".as_bytes()));
+        assert!(!is_svg(b"<html><svg></svg></html>"));
+        assert!(!is_svg(b"<svgfoo></svgfoo>"));
+        assert!(!is_svg(&[0xFF, 0xFE, 0x00]));
+    }
+}
@@ -0,0 +1,117 @@
+// ABOUTME: Well-known URIs (RFC 8615): paths under /.well-known/, and the suffixes IANA has registered there
+// ABOUTME: A suffix missing from the registry is a warning rather than an error, as new ones are registered often
+
+use url::Url;
+
+// The error code for a URL that isn't a well-known URI, and the warning code for a suffix that
+// isn't in the registry
+pub const NOT_WELL_KNOWN: &str = "not_well_known";
+pub const UNREGISTERED_SUFFIX: &str = "unregistered_suffix";
+
+const PREFIX: &str = "/.well-known/";
+
+// The permanent and provisional entries of IANA's Well-Known URIs registry, sorted for binary
+// search: https://www.iana.org/assignments/well-known-uris/
+const REGISTERED: &[&str] = &[
+    "acme-challenge",
+    "amphtml",
+    "api-catalog",
+    "appspecific",
+    "ashrae",
+    "assetlinks.json",
+    "brski",
+    "caldav",
+    "carddav",
+    "change-password",
+    "cmp",
+    "coap",
+    "core",
+    "csaf",
+    "csaf-aggregator",
+    "csvm",
+    "did-configuration.json",
+    "did.json",
+    "dnt",
+    "dnt-policy.txt",
+    "dots",
+    "ecips",
+    "edhoc",
+    "est",
+    "genid",
+    "gpc.json",
+    "hoba",
+    "host-meta",
+    "host-meta.json",
+    "http-opportunistic",
+    "idp-proxy",
+    "jmap",
+    "keybase.txt",
+    "lnurlp",
+    "looking-glass",
+    "masque",
+    "matrix",
+    "mercure",
+    "mta-sts.txt",
+    "mud",
+    "ni",
+    "nodeinfo",
+    "nostr.json",
+    "oauth-authorization-server",
+    "oauth-protected-resource",
+    "openid-configuration",
+    "openid-federation",
+    "openorg",
+    "oslc",
+    "pki-validation",
+    "posh",
+    "privacy-sandbox-attestations.json",
+    "pvd",
+    "reload-config",
+    "repute-template",
+    "resourcesync",
+    "security.txt",
+    "stun-key",
+    "tdmrep.json",
+    "thread",
+    "time",
+    "timezone",
+    "traffic-advice",
+    "trust.txt",
+    "uma2-configuration",
+    "void",
+    "webfinger",
+    "wot",
+];
+
+// The suffix of a well-known URI: the path segment after /.well-known/, which can't be empty
+pub fn suffix(url: &Url) -> Option<&str> {
+    let suffix = url.path().strip_prefix(PREFIX)?.split('/').next().unwrap_or_default();
+    (!suffix.is_empty()).then_some(suffix)
+}
+
+pub fn is_registered(suffix: &str) -> bool {
+    REGISTERED.binary_search(&suffix).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_sorted() {
+        assert!(REGISTERED.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_suffix() {
+        let url = |text: &str| Url::parse(text).unwrap();
+        assert_eq!(suffix(&url("https://example.com/.well-known/security.txt")), Some("security.txt"));
+        assert_eq!(suffix(&url("https://example.com/.well-known/acme-challenge/LoqXcYV8q5ONbJQxbmR7SCTNo3tiAXDfowyjxAjEuX0")), Some("acme-challenge"));
+        assert_eq!(suffix(&url("https://example.com/.well-known/")), None);
+        assert_eq!(suffix(&url("https://example.com/.well-known")), None);
+        assert_eq!(suffix(&url("https://example.com/docs/.well-known/security.txt")), None);
+        assert_eq!(suffix(&url("mailto:security@example.com")), None);
+        assert!(is_registered("webfinger"));
+        assert!(!is_registered("Security.txt"));
+    }
+}
@@ -0,0 +1,511 @@
+// ABOUTME: WASM component for RFC 8141 URNs, such as urn:isbn:0451450523 or urn:uuid:...
+// ABOUTME: Checks the namespace identifier, the namespace-specific string and the r, q and f components
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Namespace identifiers to accept, such as ["isbn", "uuid"], ignoring case; any when unset
+    namespaces: Option<Vec<String>>,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected, when it is a string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult { valid: coerced.is_some(), value: coerced.unwrap_or(Value::Null), error: None }
+    }
+}
+
+// Error codes reported in detailed results; a value without the urn: scheme gets the shared
+// bad_scheme
+const BAD_SCHEME: &str = messages::ErrorCode::BadScheme.as_str();
+const INVALID_NID: &str = "invalid_nid";
+const INVALID_NSS: &str = "invalid_nss";
+const INVALID_URN_COMPONENT: &str = "invalid_urn_component";
+const DISALLOWED_NAMESPACE: &str = "disallowed_namespace";
+
+// Whether a character may appear unencoded in an NSS or a component: RFC 3986's pchar, less
+// the "%" that starts a percent-encoding
+fn is_pchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-._~!$&'()*+,;=:@".contains(c)
+}
+
+// Checks a part of a URN made of pchars, the characters in `extra` and percent-encodings, and
+// that it starts with a pchar when `leading` is set. Returns it with the hex digits of its
+// percent-encodings in upper case, as RFC 8141 compares them
+fn normalize_part(text: &str, extra: &str, leading: bool) -> Option<String> {
+    if leading && !text.starts_with(|c: char| is_pchar(c) || c == '%') {
+        return None;
+    }
+    let mut normalized = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            let hex: String = chars.by_ref().take(2).collect();
+            if hex.len() != 2 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            normalized.push('%');
+            normalized.push_str(&hex.to_ascii_uppercase());
+        } else if is_pchar(c) || extra.contains(c) {
+            normalized.push(c);
+        } else {
+            return None;
+        }
+    }
+    Some(normalized)
+}
+
+// Whether a namespace identifier is well-formed: 2 to 32 letters, digits and hyphens, starting
+// and ending with a letter or digit
+fn is_nid(nid: &str) -> bool {
+    let alphanumeric = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+    (2..=32).contains(&nid.len())
+        && alphanumeric(nid.chars().next())
+        && alphanumeric(nid.chars().last())
+        && nid.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+// Whether an ISBN, with or without hyphens between its groups, has a correct check digit:
+// ISBN-10's modulo 11, where "X" is 10, or ISBN-13's modulo 10
+fn is_isbn(isbn: &str) -> bool {
+    if isbn.starts_with('-') || isbn.ends_with('-') || isbn.contains("--") {
+        return false;
+    }
+    let digits: Vec<char> = isbn.chars().filter(|&c| c != '-').collect();
+    let value = |c: char| c.to_digit(10);
+    match digits.len() {
+        10 => {
+            let check = match digits[9] {
+                'X' | 'x' => Some(10),
+                c => value(c),
+            };
+            let body: Option<Vec<u32>> = digits[..9].iter().map(|&c| value(c)).collect();
+            match (body, check) {
+                (Some(body), Some(check)) => {
+                    let sum: u32 = body.iter().enumerate().map(|(index, digit)| (10 - index as u32) * digit).sum::<u32>() + check;
+                    sum.is_multiple_of(11)
+                }
+                _ => false,
+            }
+        }
+        13 => match digits.iter().map(|&c| value(c)).collect::<Option<Vec<u32>>>() {
+            Some(digits) => digits.iter().enumerate().map(|(index, digit)| if index.is_multiple_of(2) { *digit } else { digit * 3 }).sum::<u32>().is_multiple_of(10),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+// The NSS of a namespace this validator knows the syntax of, checked and in canonical form:
+// for "uuid", RFC 9562's hyphenated UUID, checked by the UUID validator and in lower case; for
+// "isbn", an ISBN with a correct check digit, and "X" in upper case. Others are left as they are
+fn namespace_nss(nid: &str, nss: &str) -> Result<String, &'static str> {
+    match nid {
+        "uuid" => {
+            let hyphenated = nss.len() == 36 && nss.char_indices().all(|(index, c)| matches!(index, 8 | 13 | 18 | 23) == (c == '-'));
+            match uuid_validator::coerce_value(&Value::from(nss), &Value::Null) {
+                Some(Value::String(uuid)) if hyphenated => Ok(uuid),
+                _ => Err(INVALID_NSS),
+            }
+        }
+        "isbn" if is_isbn(nss) => Ok(nss.to_ascii_uppercase()),
+        "isbn" => Err(INVALID_NSS),
+        _ => Ok(nss.to_string()),
+    }
+}
+
+// Checks a URN, "urn:NID:NSS", perhaps followed by an r-component "?+...", a q-component
+// "?=..." and an f-component "#...", in that order. Returns it with the scheme and namespace
+// identifier in lower case and percent-encodings' hex digits in upper case, as RFC 8141
+// compares URNs; the components, which it doesn't compare, are kept
+fn check_urn(text: &str, options: &ValidationOptions) -> Result<String, &'static str> {
+    let scheme = text.get(..4).ok_or(BAD_SCHEME)?;
+    if !scheme.eq_ignore_ascii_case("urn:") {
+        return Err(BAD_SCHEME);
+    }
+    let (nid, rest) = text[4..].split_once(':').ok_or(INVALID_NID)?;
+    if !is_nid(nid) {
+        return Err(INVALID_NID);
+    }
+    let nid = nid.to_ascii_lowercase();
+    if let Some(namespaces) = &options.namespaces {
+        if !namespaces.iter().any(|namespace| namespace.eq_ignore_ascii_case(&nid)) {
+            return Err(DISALLOWED_NAMESPACE);
+        }
+    }
+
+    let (rest, fragment) = match rest.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (rest, None),
+    };
+    let (nss, components) = rest.split_at(rest.find('?').unwrap_or(rest.len()));
+    let nss = normalize_part(nss, "/", true).filter(|nss| !nss.is_empty()).ok_or(INVALID_NSS)?;
+    let mut urn = format!("urn:{nid}:{}", namespace_nss(&nid, &nss)?);
+
+    // The r-component runs to the q-component, which runs to the end; both may hold "?"
+    let (resolution, query) = match components.strip_prefix("?+") {
+        Some(rest) => match rest.split_once("?=") {
+            Some((resolution, query)) => (Some(resolution), Some(query)),
+            None => (Some(rest), None),
+        },
+        None if components.is_empty() => (None, None),
+        None => (None, Some(components.strip_prefix("?=").ok_or(INVALID_URN_COMPONENT)?)),
+    };
+    for (delimiter, component) in [("?+", resolution), ("?=", query)] {
+        if let Some(component) = component {
+            urn.push_str(delimiter);
+            urn.push_str(&normalize_part(component, "/?", true).ok_or(INVALID_URN_COMPONENT)?);
+        }
+    }
+    if let Some(fragment) = fragment {
+        urn.push('#');
+        urn.push_str(&normalize_part(fragment, "/?", false).ok_or(INVALID_URN_COMPONENT)?);
+    }
+    Ok(urn)
+}
+
+fn detail_urn_str(text: &str, options: &ValidationOptions) -> ValidationResult {
+    let text = text.trim();
+    // A blank value gets the shared code for an empty value
+    if text.is_empty() {
+        return ValidationResult::from(None);
+    }
+    match check_urn(text, options) {
+        Ok(urn) => ValidationResult::from(Some(Value::from(urn))),
+        Err(code) => ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+    }
+}
+
+// Only strings can be valid; the fast path calls detail_urn_str directly
+fn detail_urn(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    match value.as_str() {
+        Some(text) => detail_urn_str(text, options),
+        None => ValidationResult::from(None),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the URN in the form RFC 8141 compares
+fn coerce_urn(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_urn(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_urn(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_urn(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_urn(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_urn(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_urn(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_urn(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_urn(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_urn(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_urn(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    detail_urn_str(value, &ValidationOptions::default()).valid
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(options: Value) -> ValidationOptions {
+        serde_json::from_value(options).unwrap()
+    }
+
+    fn coerce(text: &str) -> Option<Value> {
+        coerce_urn(&json!(text), &ValidationOptions::default())
+    }
+
+    fn error(text: &str, options: &ValidationOptions) -> Option<&'static str> {
+        detail_urn_str(text, options).error
+    }
+
+    #[test]
+    fn test_syntax() {
+        assert_eq!(coerce("URN:IETF:rfc:8141"), Some(json!("urn:ietf:rfc:8141")));
+        assert_eq!(coerce(" urn:example:a%2fb/c:d@e "), Some(json!("urn:example:a%2Fb/c:d@e")));
+        assert_eq!(coerce("urn:urn-7:informal"), Some(json!("urn:urn-7:informal")));
+
+        let default = ValidationOptions::default();
+        assert_eq!(error("https://example.com/", &default), Some(BAD_SCHEME));
+        assert_eq!(error("urn", &default), Some(BAD_SCHEME));
+        assert_eq!(error("urn:isbn", &default), Some(INVALID_NID));
+        assert_eq!(error("urn:a:thing", &default), Some(INVALID_NID));
+        assert_eq!(error("urn:-ab:thing", &default), Some(INVALID_NID));
+        assert_eq!(error("urn:a_b:thing", &default), Some(INVALID_NID));
+        assert_eq!(error(&format!("urn:{}:thing", "a".repeat(33)), &default), Some(INVALID_NID));
+        assert_eq!(error("urn:example:", &default), Some(INVALID_NSS));
+        assert_eq!(error("urn:example:/path", &default), Some(INVALID_NSS));
+        assert_eq!(error("urn:example:a b", &default), Some(INVALID_NSS));
+        assert_eq!(error("urn:example:100%", &default), Some(INVALID_NSS));
+        assert_eq!(error("urn:example:a%zz", &default), Some(INVALID_NSS));
+    }
+
+    #[test]
+    fn test_components() {
+        assert_eq!(
+            coerce("urn:example:weather?=op=map&lat=39.56&lon=-104.85#tomorrow"),
+            Some(json!("urn:example:weather?=op=map&lat=39.56&lon=-104.85#tomorrow"))
+        );
+        assert_eq!(coerce("urn:nbn:de:bvb:19-146642?+CCResolve:cc=uk?=a?b/c"), Some(json!("urn:nbn:de:bvb:19-146642?+CCResolve:cc=uk?=a?b/c")));
+        assert_eq!(coerce("urn:example:a#"), Some(json!("urn:example:a#")));
+        assert_eq!(coerce("urn:example:a#/b?c%3f"), Some(json!("urn:example:a#/b?c%3F")));
+
+        let default = ValidationOptions::default();
+        assert_eq!(error("urn:example:a?b", &default), Some(INVALID_URN_COMPONENT));
+        assert_eq!(error("urn:example:a?+", &default), Some(INVALID_URN_COMPONENT));
+        assert_eq!(error("urn:example:a?=", &default), Some(INVALID_URN_COMPONENT));
+        assert_eq!(error("urn:example:a?+?=q", &default), Some(INVALID_URN_COMPONENT));
+        assert_eq!(error("urn:example:a?+/r", &default), Some(INVALID_URN_COMPONENT));
+        assert_eq!(error("urn:example:a#b#c", &default), Some(INVALID_URN_COMPONENT));
+    }
+
+    #[test]
+    fn test_namespaces() {
+        assert_eq!(coerce("URN:UUID:F81D4FAE-7DEC-11D0-A765-00A0C91E6BF6"), Some(json!("urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bf6")));
+        assert_eq!(coerce("urn:isbn:0-8044-2957-x"), Some(json!("urn:isbn:0-8044-2957-X")));
+        assert_eq!(coerce("urn:isbn:978-0-306-40615-7"), Some(json!("urn:isbn:978-0-306-40615-7")));
+
+        let default = ValidationOptions::default();
+        assert_eq!(error("urn:uuid:f81d4fae7dec11d0a76500a0c91e6bf6", &default), Some(INVALID_NSS));
+        assert_eq!(error("urn:uuid:{f81d4fae-7dec-11d0-a765-00a0c91e6bf6}", &default), Some(INVALID_NSS));
+        assert_eq!(error("urn:uuid:f81d4fae-7dec-11d0-a765-00a0c91e6bfg", &default), Some(INVALID_NSS));
+        assert_eq!(error("urn:isbn:0451450524", &default), Some(INVALID_NSS));
+        assert_eq!(error("urn:isbn:978-0-306-40615-8", &default), Some(INVALID_NSS));
+        assert_eq!(error("urn:isbn:0--306-40615-X", &default), Some(INVALID_NSS));
+
+        let restricted = options(json!({"namespaces": ["ISBN", "uuid"]}));
+        assert!(validate_urn(&json!("urn:isbn:0451450523"), &restricted));
+        assert_eq!(error("urn:ietf:rfc:8141", &restricted), Some(DISALLOWED_NAMESPACE));
+        assert_eq!(error("urn:isbnx:1", &restricted), Some(DISALLOWED_NAMESPACE));
+    }
+
+    #[test]
+    fn test_detailed() {
+        assert_eq!(
+            messages::localize(&detail_urn_str("urn:isbn:0451450524", &ValidationOptions::default()), &json!("urn:isbn:0451450524"), Some("en")),
+            json!({
+                "valid": false,
+                "value": null,
+                "error": "invalid_nss",
+                "message": messages::message("invalid_nss", "en").unwrap(),
+            })
+        );
+        assert_eq!(messages::localize(&detail_urn_str(" ", &ValidationOptions::default()), &json!(" "), Some("en"))["error"], "empty_value");
+        assert!(!validate_urn(&json!(42), &ValidationOptions::default()));
+
+        // Batches carry a list of values and share one set of options
+        let batch: BatchInput = serde_json::from_value(json!({"values": ["urn:ietf:rfc:8141", "urn:isbn:0451450523"], "options": {"namespaces": ["isbn"]}})).unwrap();
+        let valid: Vec<bool> = batch.values.iter().map(|value| validate_urn(value, &batch.options)).collect();
+        assert_eq!(valid, vec![false, true]);
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+
+        // The example forms are valid
+        for form in description["forms"].as_array().unwrap() {
+            assert!(coerce_value(form, &Value::Null).is_some(), "{form}");
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast path agrees with the generic path under the default options
+        for text in ["urn:ietf:rfc:8141", "urn:isbn:0451450523", "", "urn:x:y", "mailto:a@example.com"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,432 @@
+// ABOUTME: WASM component for webhook endpoints: a URL validator preset with an optional signing secret
+// ABOUTME: Requires https, rejects credentials and hosts that reach internal networks, and checks secret entropy
+
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// Private addresses and internal names, shared with the URL validator
+#[path = "../../../hosts/hosts.rs"]
+mod hosts;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Reject configurations without a signing secret
+    require_secret: bool,
+    // Least estimated entropy of the secret, in bits
+    min_secret_bits: u32,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions { require_secret: false, min_secret_bits: 80 }
+    }
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+    // Why the value was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+            error: None,
+        }
+    }
+}
+
+// Error codes reported in detailed results
+const INVALID_URL: &str = "invalid_url";
+const INSECURE_SCHEME: &str = "insecure_scheme";
+const CREDENTIALS_IN_URL: &str = "credentials_in_url";
+const MISSING_SECRET: &str = "missing_secret";
+const WEAK_SECRET: &str = "weak_secret";
+
+// Estimated bits of entropy: the length times the Shannon entropy of the character
+// frequencies, which underestimates short random strings but gives repeated and
+// low-variety secrets ("aaaa...", "abcabc...") the low score they deserve
+fn secret_bits(secret: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in secret.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    let length = secret.chars().count() as f64;
+    let per_char: f64 = counts
+        .values()
+        .map(|count| {
+            let p = *count as f64 / length;
+            -p * p.log2()
+        })
+        .sum();
+    per_char * length
+}
+
+// The URL is canonicalized by the URL validator, then must be https without credentials, and
+// its host must not point into a private network
+fn check_url(text: &str) -> Result<String, &'static str> {
+    let canonical = url_validator::coerce_value(&Value::from(text.trim()), &Value::Null).ok_or(INVALID_URL)?;
+    let url = canonical.as_str().and_then(|canonical| Url::parse(canonical).ok()).ok_or(INVALID_URL)?;
+
+    if url.scheme() != "https" {
+        return Err(INSECURE_SCHEME);
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err(CREDENTIALS_IN_URL);
+    }
+    match url.host().map(|host| hosts::host_error(&host)) {
+        Some(Some(code)) => Err(code),
+        Some(None) => Ok(url.to_string()),
+        None => Err(INVALID_URL),
+    }
+}
+
+// A URL string, or { url, secret } for a configuration with a signing secret
+fn detail_webhook(value: &Value, options: &ValidationOptions) -> ValidationResult {
+    let (url, secret) = match value {
+        Value::String(url) => (url.as_str(), None),
+        Value::Object(config) => match (config.get("url").and_then(Value::as_str), config.get("secret")) {
+            (Some(url), None | Some(Value::Null)) => (url, None),
+            (Some(url), Some(Value::String(secret))) => (url, Some(secret.as_str())),
+            _ => return ValidationResult::from(None),
+        },
+        _ => return ValidationResult::from(None),
+    };
+
+    let url = match check_url(url) {
+        Ok(url) => url,
+        Err(code) => return ValidationResult { error: Some(code), ..ValidationResult::from(None) },
+    };
+    let error = match secret {
+        None if options.require_secret => Some(MISSING_SECRET),
+        Some(secret) if secret_bits(secret) < f64::from(options.min_secret_bits) => Some(WEAK_SECRET),
+        _ => None,
+    };
+    if error.is_some() {
+        return ValidationResult { error, ..ValidationResult::from(None) };
+    }
+
+    // The canonical form follows the input's: a URL alone, or the configuration
+    match value {
+        Value::String(_) => ValidationResult::from(Some(Value::String(url))),
+        _ => ValidationResult::from(Some(json!({ "url": url, "secret": secret }))),
+    }
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the canonical URL, or { url, secret } when a configuration object was given
+fn coerce_webhook(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    let result = detail_webhook(value, options);
+    result.valid.then_some(result.value)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_webhook(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_webhook(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_webhook(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(detail_webhook(value, &options)).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_webhook(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_webhook(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (detail_webhook(&input_obj.value, &input_obj.options), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_webhook(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = detail_webhook(value, &batch.options);
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    check_url(value).is_ok()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hosts::{INTERNAL_HOST, PRIVATE_ADDRESS};
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn error(value: Value, options: &ValidationOptions) -> Option<&'static str> {
+        detail_webhook(&value, options).error
+    }
+
+    #[test]
+    fn test_urls() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            coerce_webhook(&json!(" HTTPS://Hooks.Example.com:443/notify "), &defaults),
+            Some(json!("https://hooks.example.com/notify"))
+        );
+        assert!(validate_webhook(&json!("https://93.184.216.34/hook"), &defaults));
+        assert!(validate_webhook(&json!("https://[2606:2800:220:1::]/hook"), &defaults));
+
+        assert_eq!(error(json!("not a url"), &defaults), Some(INVALID_URL));
+        assert_eq!(error(json!("http://hooks.example.com/notify"), &defaults), Some(INSECURE_SCHEME));
+        assert_eq!(error(json!("ftp://hooks.example.com/notify"), &defaults), Some(INSECURE_SCHEME));
+        assert_eq!(error(json!("https://user:pw@hooks.example.com/"), &defaults), Some(CREDENTIALS_IN_URL));
+        assert_eq!(error(json!("https://token@hooks.example.com/"), &defaults), Some(CREDENTIALS_IN_URL));
+        assert_eq!(detail_webhook(&json!(42), &defaults), ValidationResult::from(None));
+    }
+
+    #[test]
+    fn test_ssrf_hosts() {
+        let defaults = ValidationOptions::default();
+
+        for address in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "[::1]",
+            "[fd00::1]",
+            "[fe80::1]",
+            "[::ffff:127.0.0.1]",
+        ] {
+            assert_eq!(error(json!(format!("https://{address}/hook")), &defaults), Some(PRIVATE_ADDRESS), "{address}");
+        }
+
+        // Alternative IPv4 spellings are normalized by the URL parser before the check
+        assert_eq!(error(json!("https://2130706433/"), &defaults), Some(PRIVATE_ADDRESS));
+        assert_eq!(error(json!("https://0x7f.1/"), &defaults), Some(PRIVATE_ADDRESS));
+
+        for host in ["localhost", "api.localhost", "printer.local", "metadata.google.internal", "intranet", "router.home.arpa"] {
+            assert_eq!(error(json!(format!("https://{host}/hook")), &defaults), Some(INTERNAL_HOST), "{host}");
+        }
+        assert!(validate_webhook(&json!("https://local.example.com/hook"), &defaults));
+    }
+
+    #[test]
+    fn test_secrets() {
+        let defaults = ValidationOptions::default();
+        let strong = "9f86d081884c7d659a2feaa0c55ad015";
+
+        assert_eq!(
+            coerce_webhook(&json!({"url": "https://hooks.example.com/", "secret": strong}), &defaults),
+            Some(json!({"url": "https://hooks.example.com/", "secret": strong}))
+        );
+        assert_eq!(
+            coerce_webhook(&json!({"url": "https://hooks.example.com/"}), &defaults),
+            Some(json!({"url": "https://hooks.example.com/", "secret": null}))
+        );
+        assert_eq!(error(json!({"url": "https://hooks.example.com/", "secret": "password123"}), &defaults), Some(WEAK_SECRET));
+        assert_eq!(error(json!({"url": "https://hooks.example.com/", "secret": "a".repeat(64)}), &defaults), Some(WEAK_SECRET));
+        assert_eq!(error(json!({"url": "http://hooks.example.com/", "secret": strong}), &defaults), Some(INSECURE_SCHEME));
+        assert!(!validate_webhook(&json!({"url": "https://hooks.example.com/", "secret": 42}), &defaults));
+        assert!(!validate_webhook(&json!({"secret": strong}), &defaults));
+
+        assert!(secret_bits("aaaaaaaa") == 0.0);
+        assert!(secret_bits(strong) > 100.0);
+    }
+
+    #[test]
+    fn test_options() {
+        let required = options(json!({"require_secret": true}));
+        assert_eq!(error(json!("https://hooks.example.com/"), &required), Some(MISSING_SECRET));
+        assert_eq!(error(json!({"url": "https://hooks.example.com/", "secret": null}), &required), Some(MISSING_SECRET));
+
+        let lenient = options(json!({"min_secret_bits": 0}));
+        assert!(validate_webhook(&json!({"url": "https://hooks.example.com/", "secret": "password123"}), &lenient));
+
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "x", "options": {"min_secret_bits": -1}})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "x", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["https://hooks.example.com/notify", "http://hooks.example.com/", "https://10.0.0.1/", "junk"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// Types for the webhook validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the validator; an unknown option makes every value invalid */
+export interface ValidationOptions {
+    /** Reject configurations without a signing secret (default: false) */
+    require_secret?: boolean;
+    /** Least estimated entropy of the secret, in bits (default: 80) */
+    min_secret_bits?: number;
+}
+
+/** A value accepted by the validator */
+export type ValidationValue = string | Record<string, unknown>;
+
+/** The canonical form of a valid value, as coerce returns it */
+export type CanonicalValue = string | {
+    url: string;
+    secret: string | null;
+};
+
+export interface ValidationInput {
+    value: ValidationValue;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: ValidationValue[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected; present on every failure when the input names a locale */
+    error?: "invalid_url" | "insecure_scheme" | "credentials_in_url" | "private_address" | "internal_host" | "missing_secret" | "weak_secret" | "empty_value" | "invalid";
+    /** The error's message, when the input names a locale */
+    message?: string;
+}
@@ -0,0 +1,392 @@
+// ABOUTME: WASM component for UUID validation
+// ABOUTME: Provides validate and coerce functions for canonical, braced and URN-form UUIDs with version constraints
+
+use wasm_bindgen::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Component Model bindings; their export names can't be linked into a native library, so
+// outside wasm32 they are only compiled for tests
+#[cfg(all(feature = "component", any(target_arch = "wasm32", test)))]
+#[path = "../../../wit/component.rs"]
+mod component;
+
+// Shared error codes and their messages in each locale, added to detailed results when the
+// input names a locale; each validator reports only some of the codes
+#[path = "../../../messages/messages.rs"]
+#[allow(dead_code)]
+mod messages;
+
+// TypeScript declarations for the input, options and detailed result, so that the generated
+// .d.ts types the exports below instead of leaving them as `any`
+#[cfg(feature = "exports")]
+#[wasm_bindgen(typescript_custom_section)]
+const TYPESCRIPT_TYPES: &str = include_str!("types.d.ts");
+
+// The exports' arguments, named after the declarations in types.d.ts; they are plain JS
+// values, deserialized as before
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "ValidationInput")]
+    pub type JsValidationInput;
+
+    #[wasm_bindgen(typescript_type = "BatchInput")]
+    pub type JsBatchInput;
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ValidationInput {
+    value: Value,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Per-call configuration; unknown options are rejected rather than silently ignored
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidationOptions {
+    // Require this UUID version (1-8), which also requires the RFC 9562 variant
+    version: Option<u8>,
+    // Reject the all-zero nil UUID
+    reject_nil: bool,
+}
+
+// Input for the batch exports: many values sharing one set of options
+#[derive(Deserialize, Serialize)]
+pub struct BatchInput {
+    values: Vec<Value>,
+    #[serde(default)]
+    options: ValidationOptions,
+    // Language for the error messages in detailed results, such as "en" or "fr-CA"
+    #[serde(default)]
+    locale: Option<String>,
+}
+
+// Detailed outcome for a single value: validity plus the canonical value (null when invalid)
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ValidationResult {
+    valid: bool,
+    value: Value,
+}
+
+impl From<Option<Value>> for ValidationResult {
+    fn from(coerced: Option<Value>) -> Self {
+        ValidationResult {
+            valid: coerced.is_some(),
+            value: coerced.unwrap_or(Value::Null),
+        }
+    }
+}
+
+// Strips the braced or URN wrapper, leaving the hyphenated 8-4-4-4-12 form
+fn unwrap_uuid(text: &str) -> &str {
+    if let Some(inner) = text.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+        return inner;
+    }
+    match text.get(..9) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("urn:uuid:") => &text[9..],
+        _ => text,
+    }
+}
+
+// Parses the hyphenated form into its 16 bytes
+fn parse_uuid(text: &str) -> Option<[u8; 16]> {
+    let groups: Vec<&str> = text.split('-').collect();
+    let lengths: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+    if lengths != [8, 4, 4, 4, 12] {
+        return None;
+    }
+
+    let hex: String = groups.concat();
+    let mut bytes = [0u8; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let pair = hex.get(i * 2..i * 2 + 2)?;
+        if !pair.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        *byte = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+// Internal coercion logic that can be tested without WASM
+// Returns the lowercase hyphenated form, whichever wrapper the input used
+fn coerce_uuid_str(text: &str, options: &ValidationOptions) -> Option<Value> {
+    let text = text.trim();
+    let bytes = parse_uuid(unwrap_uuid(text))?;
+
+    if options.reject_nil && bytes == [0u8; 16] {
+        return None;
+    }
+
+    if let Some(version) = options.version {
+        // The version is the high nibble of byte 6; the variant is the top two bits of byte 8
+        if bytes[6] >> 4 != version || bytes[8] >> 6 != 0b10 {
+            return None;
+        }
+    }
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Some(Value::String(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..]
+    )))
+}
+
+// Only strings can be valid; the fast path calls coerce_uuid_str directly
+fn coerce_uuid(value: &Value, options: &ValidationOptions) -> Option<Value> {
+    coerce_uuid_str(value.as_str()?, options)
+}
+
+// Internal validation logic that can be tested without WASM
+fn validate_uuid(value: &Value, options: &ValidationOptions) -> bool {
+    coerce_uuid(value, options).is_some()
+}
+
+// Entry point for crates that dispatch to this validator by name; options arrive as JSON
+// and, as with the WASM exports, options that don't deserialize make the value invalid
+pub fn coerce_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    coerce_uuid(value, &options)
+}
+
+// Detailed counterpart of coerce_value: the result validate_detailed reports, before any
+// message is added, or None when the options don't deserialize
+pub fn detail_value(value: &Value, options: &Value) -> Option<Value> {
+    let options = match options {
+        Value::Null => ValidationOptions::default(),
+        options => ValidationOptions::deserialize(options).ok()?,
+    };
+    serde_json::to_value(ValidationResult::from(coerce_uuid(value, &options))).ok()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate(input: JsValidationInput) -> bool {
+    // Parse the input object
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return false,
+    };
+
+    validate_uuid(&input_obj.value, &input_obj.options)
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "CanonicalValue | null"))]
+pub fn coerce(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input has no canonical value
+    let input_obj: ValidationInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return JsValue::NULL,
+    };
+
+    match coerce_uuid(&input_obj.value, &input_obj.options) {
+        Some(value) => to_js(&value),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult"))]
+pub fn validate_detailed(input: JsValidationInput) -> JsValue {
+    // Parse the input object; unparseable input is reported as invalid
+    let (result, value, locale) = match serde_wasm_bindgen::from_value::<ValidationInput>(input.into()) {
+        Ok(input_obj) => (ValidationResult::from(coerce_uuid(&input_obj.value, &input_obj.options)), input_obj.value, input_obj.locale),
+        Err(_) => (ValidationResult::from(None), Value::Null, None),
+    };
+
+    to_js(&messages::localize(&result, &value, locale.as_deref()))
+}
+
+// Validates a whole column of values in one call; wasm-bindgen cannot return Vec<bool>,
+// so each result is a JS boolean
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "boolean[]"))]
+pub fn validate_batch(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| JsValue::from_bool(validate_uuid(value, &batch.options)))
+        .collect()
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen(unchecked_return_type = "ValidationResult[]"))]
+pub fn validate_batch_detailed(input: JsBatchInput) -> Vec<JsValue> {
+    // Parse the batch object; an unparseable batch yields no results
+    let batch: BatchInput = match serde_wasm_bindgen::from_value(input.into()) {
+        Ok(val) => val,
+        Err(_) => return Vec::new(),
+    };
+
+    batch
+        .values
+        .iter()
+        .map(|value| {
+            let result = ValidationResult::from(coerce_uuid(value, &batch.options));
+            to_js(&messages::localize(&result, value, batch.locale.as_deref()))
+        })
+        .collect()
+}
+
+// Fast path for a string with the default options, skipping the deserialization of an input
+// object into a Value
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn validate_str(value: &str) -> bool {
+    coerce_uuid_str(value, &ValidationOptions::default()).is_some()
+}
+
+// Capability metadata for form builders and other tooling: the accepted input types, example
+// forms, supported options and a JSON Schema fragment for the canonical value
+pub fn description() -> Value {
+    let mut description: Value =
+        serde_json::from_str(include_str!("description.json")).expect("description.json is valid JSON");
+    description["name"] = Value::from(env!("CARGO_PKG_NAME"));
+    description["version"] = Value::from(env!("CARGO_PKG_VERSION"));
+    description
+}
+
+#[cfg_attr(feature = "exports", wasm_bindgen)]
+pub fn describe() -> JsValue {
+    to_js(&description())
+}
+
+// Serializes to plain JSON-compatible JS values (objects rather than Maps)
+fn to_js<T: Serialize>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn options(value: Value) -> ValidationOptions {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_uuids() {
+        let defaults = ValidationOptions::default();
+
+        assert!(validate_uuid(&json!("123e4567-e89b-12d3-a456-426614174000"), &defaults));
+        assert!(validate_uuid(&json!("F47AC10B-58CC-4372-A567-0E02B2C3D479"), &defaults));
+        assert!(validate_uuid(&json!("{f47ac10b-58cc-4372-a567-0e02b2c3d479}"), &defaults));
+        assert!(validate_uuid(&json!("urn:uuid:f47ac10b-58cc-4372-a567-0e02b2c3d479"), &defaults));
+        assert!(validate_uuid(&json!("URN:UUID:f47ac10b-58cc-4372-a567-0e02b2c3d479"), &defaults));
+        assert!(validate_uuid(&json!("00000000-0000-0000-0000-000000000000"), &defaults));
+        assert!(validate_uuid(&json!("ffffffff-ffff-ffff-ffff-ffffffffffff"), &defaults));
+    }
+
+    #[test]
+    fn test_invalid_uuids() {
+        let defaults = ValidationOptions::default();
+
+        assert!(!validate_uuid(&json!(""), &defaults));
+        assert!(!validate_uuid(&json!("f47ac10b58cc4372a5670e02b2c3d479"), &defaults)); // No hyphens
+        assert!(!validate_uuid(&json!("f47ac10b-58cc-4372-a567-0e02b2c3d47"), &defaults)); // Short
+        assert!(!validate_uuid(&json!("f47ac10b-58cc-4372-a567-0e02b2c3d4790"), &defaults)); // Long
+        assert!(!validate_uuid(&json!("f47ac10b-58cc-4372-a567-0e02b2c3d47g"), &defaults));
+        assert!(!validate_uuid(&json!("f47ac10b-58cc4-372-a567-0e02b2c3d479"), &defaults)); // Misplaced hyphen
+        assert!(!validate_uuid(&json!("{f47ac10b-58cc-4372-a567-0e02b2c3d479"), &defaults));
+        assert!(!validate_uuid(&json!("urn:uuid:{f47ac10b-58cc-4372-a567-0e02b2c3d479}"), &defaults));
+        assert!(!validate_uuid(&json!("+47ac10b-58cc-4372-a567-0e02b2c3d479"), &defaults));
+        assert!(!validate_uuid(&json!(42), &defaults));
+    }
+
+    #[test]
+    fn test_version() {
+        let v1 = options(json!({"version": 1}));
+        let v4 = options(json!({"version": 4}));
+        let v7 = options(json!({"version": 7}));
+
+        assert!(validate_uuid(&json!("123e4567-e89b-12d3-a456-426614174000"), &v1));
+        assert!(validate_uuid(&json!("f47ac10b-58cc-4372-a567-0e02b2c3d479"), &v4));
+        assert!(validate_uuid(&json!("018f3c1e-7a2b-7c3d-8e4f-5a6b7c8d9e0f"), &v7));
+
+        assert!(!validate_uuid(&json!("f47ac10b-58cc-4372-a567-0e02b2c3d479"), &v7));
+        assert!(!validate_uuid(&json!("f47ac10b-58cc-4372-c567-0e02b2c3d479"), &v4)); // Microsoft variant
+        assert!(!validate_uuid(&json!("00000000-0000-0000-0000-000000000000"), &v4));
+    }
+
+    #[test]
+    fn test_reject_nil() {
+        let reject_nil = options(json!({"reject_nil": true}));
+
+        assert!(!validate_uuid(&json!("00000000-0000-0000-0000-000000000000"), &reject_nil));
+        assert!(!validate_uuid(&json!("{00000000-0000-0000-0000-000000000000}"), &reject_nil));
+        assert!(validate_uuid(&json!("f47ac10b-58cc-4372-a567-0e02b2c3d479"), &reject_nil));
+    }
+
+    #[test]
+    fn test_coerce() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            coerce_uuid(&json!("{F47AC10B-58CC-4372-A567-0E02B2C3D479}"), &defaults),
+            Some(json!("f47ac10b-58cc-4372-a567-0e02b2c3d479"))
+        );
+        assert_eq!(
+            coerce_uuid(&json!(" urn:uuid:F47AC10B-58CC-4372-A567-0E02B2C3D479 "), &defaults),
+            Some(json!("f47ac10b-58cc-4372-a567-0e02b2c3d479"))
+        );
+        assert_eq!(coerce_uuid(&json!("not-a-uuid"), &defaults), None);
+    }
+
+    #[test]
+    fn test_options() {
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "x"})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "x", "options": {"version": 4}})).is_ok());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "x", "options": {"version": "v4"}})).is_err());
+        assert!(serde_json::from_value::<ValidationInput>(json!({"value": "x", "options": {"strict": true}})).is_err());
+    }
+
+    #[test]
+    fn test_detailed() {
+        let defaults = ValidationOptions::default();
+
+        assert_eq!(
+            ValidationResult::from(coerce_uuid(&json!("F47AC10B-58CC-4372-A567-0E02B2C3D479"), &defaults)),
+            ValidationResult { valid: true, value: json!("f47ac10b-58cc-4372-a567-0e02b2c3d479") }
+        );
+        assert_eq!(
+            ValidationResult::from(coerce_uuid(&json!("nope"), &defaults)),
+            ValidationResult { valid: false, value: Value::Null }
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = description();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+
+        // Every documented option is accepted under its name, and its default is a valid value;
+        // each is declared in the TypeScript options too
+        for (name, option) in description["options"]["properties"].as_object().unwrap() {
+            assert!(include_str!("types.d.ts").contains(&format!("    {name}?: ")), "{name}");
+            let value = option.get("default").cloned().unwrap_or(Value::Null);
+            if let Err(error) = ValidationOptions::deserialize(&json!({ name.as_str(): value })) {
+                assert!(option.get("default").is_none(), "{name}: {error}");
+                assert!(!error.to_string().contains("unknown field"), "{name}: {error}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // The fast paths agree with the generic path under the default options
+        for text in ["550e8400-e29b-41d4-a716-446655440000", "550e8400e29b41d4a716446655440000", "not-a-uuid"] {
+            assert_eq!(validate_str(text), coerce_value(&Value::from(text), &Value::Null).is_some(), "{text}");
+        }
+    }
+}
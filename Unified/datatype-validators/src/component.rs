@@ -0,0 +1,51 @@
+// ABOUTME: Component Model bindings for the all-in-one validator, generated from wit/validator.wit
+// ABOUTME: Exports the datatype-validators world, selecting the validator by datatype name per call
+
+wit_bindgen::generate!({ world: "datatype-validators", path: "../../wit" });
+
+use exports::datatype_validators::validator::validators::Guest;
+use serde_json::Value;
+
+struct Component;
+
+impl Guest for Component {
+    fn validate(type_name: String, input: String) -> bool {
+        Self::coerce(type_name, input).is_some()
+    }
+
+    fn coerce(type_name: String, input: String) -> Option<String> {
+        let input: Value = serde_json::from_str(&input).ok()?;
+        let options = input.get("options").unwrap_or(&Value::Null);
+        super::coerce_value(&type_name, input.get("value")?, options).map(|value| value.to_string())
+    }
+
+    fn types() -> Vec<String> {
+        super::type_names().map(String::from).collect()
+    }
+
+    fn describe() -> String {
+        super::description().to_string()
+    }
+}
+
+export!(Component);
+
+#[cfg(all(test, feature = "all"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_input() {
+        assert_eq!(Component::coerce("integer".to_string(), r#"{"value": "7"}"#.to_string()), Some("7".to_string()));
+        assert_eq!(Component::coerce("integer".to_string(), "not json".to_string()), None);
+        assert_eq!(Component::coerce("integer".to_string(), "{}".to_string()), None);
+        assert!(!Component::validate("Integer".to_string(), r#"{"value": "7"}"#.to_string()));
+    }
+
+    #[test]
+    fn test_describe() {
+        let description: Value = serde_json::from_str(&Component::describe()).unwrap();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+        assert_eq!(description["types"].as_object().unwrap().len(), Component::types().len());
+    }
+}
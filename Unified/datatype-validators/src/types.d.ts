@@ -0,0 +1,33 @@
+// Types for the unified validator's exports, added to the .d.ts that wasm-bindgen generates
+
+/** Options for the named datatype's validator; an unknown option makes every value invalid */
+export type ValidationOptions = Record<string, unknown>;
+
+export interface ValidationInput {
+    value: unknown;
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+export interface BatchInput {
+    values: unknown[];
+    options?: ValidationOptions;
+    /** Language for the error messages in detailed results, such as "en" or "fr-CA" */
+    locale?: string;
+}
+
+/** The canonical form of a valid value, which depends on the datatype */
+export type CanonicalValue = unknown;
+
+/** A detailed result: validity plus the canonical value (null when invalid) */
+export interface ValidationResult {
+    valid: boolean;
+    value: CanonicalValue | null;
+    /** Why the value was rejected: "unknown_type", or one of the named validator's codes; present on every failure when the input names a locale */
+    error?: string;
+    /** The error's message, when the input names a locale */
+    message?: string;
+    /** The named validator's other fields, such as the line a problem was found on or its warnings */
+    [detail: string]: unknown;
+}
@@ -444,11 +444,24 @@ class Validator {
         this.addHeapObject = addHeapObject;
         this.getObject = getObject;
         this.takeObject = takeObject;
+
+        // Copies a string into WASM memory, returning its (pointer, length) for &str parameters
+        this.passString = (arg) => {
+            const ptr = passStringToWasm(arg, this.wasmInstance.exports.__wbindgen_malloc, this.wasmInstance.exports.__wbindgen_realloc);
+            return [ptr, WASM_VECTOR_LEN];
+        };
+
+        // Copies bytes into WASM memory, returning their (pointer, length) for &[u8] parameters
+        this.passBytes = (arg) => {
+            const ptr = this.wasmInstance.exports.__wbindgen_malloc(arg.length, 1) >>> 0;
+            new Uint8Array(this.wasmInstance.exports.memory.buffer).set(arg, ptr);
+            return [ptr, arg.length];
+        };
         
         return imports;
     }
 
-    async validate(value) {
+    async validate(value, options) {
         await this.ensureLoaded();
 
         if (!this.wasmInstance.exports.validate) {
@@ -456,8 +469,8 @@ class Validator {
         }
 
         try {
-            // Create the input object with value property
-            const input = { value };
+            // Create the input object with value and (optional) options properties
+            const input = options === undefined ? { value } : { value, options };
             
             // Add the object to the heap for WASM to access
             const inputIdx = this.addHeapObject(input);
@@ -473,8 +486,158 @@ class Validator {
         }
     }
 
-    async assert(value) {
-        const isValid = await this.validate(value);
+    async coerce(value, options) {
+        await this.ensureLoaded();
+
+        if (!this.wasmInstance.exports.coerce) {
+            throw new Error('WASM module does not export a coerce function');
+        }
+
+        try {
+            // Create the input object with value and (optional) options properties
+            const input = options === undefined ? { value } : { value, options };
+            const inputIdx = this.addHeapObject(input);
+
+            // The coerce function returns a heap index for the normalized value (null when invalid)
+            const resultIdx = this.wasmInstance.exports.coerce(inputIdx);
+            return this.takeObject(resultIdx);
+        } catch (error) {
+            console.error('Coercion error:', error);
+            return null;
+        }
+    }
+
+    async toBool(value, options) {
+        await this.ensureLoaded();
+
+        if (!this.wasmInstance.exports.to_bool) {
+            throw new Error('WASM module does not export a to_bool function');
+        }
+
+        // Create the input object with value and (optional) options properties
+        const input = options === undefined ? { value } : { value, options };
+        const inputIdx = this.addHeapObject(input);
+
+        // Returns a heap index for true, false or null when the value is not a boolean
+        const resultIdx = this.wasmInstance.exports.to_bool(inputIdx);
+        return this.takeObject(resultIdx);
+    }
+
+    async validateDetailed(value, options, locale) {
+        await this.ensureLoaded();
+
+        if (!this.wasmInstance.exports.validate_detailed) {
+            throw new Error('WASM module does not export a validate_detailed function');
+        }
+
+        // Create the input object with value and (optional) options properties; a locale adds
+        // an error code and message to failed results
+        const input = options === undefined ? { value } : { value, options };
+        if (locale !== undefined) {
+            input.locale = locale;
+        }
+        const inputIdx = this.addHeapObject(input);
+
+        // Returns a heap index for a { valid, value } result object
+        const resultIdx = this.wasmInstance.exports.validate_detailed(inputIdx);
+        return this.takeObject(resultIdx);
+    }
+
+    async validateStr(value) {
+        await this.ensureLoaded();
+
+        if (!this.wasmInstance.exports.validate_str) {
+            throw new Error('WASM module does not export a validate_str function');
+        }
+
+        // The string is passed as (pointer, length) rather than as an input object; WASM frees it
+        const [ptr, len] = this.passString(value);
+        return this.wasmInstance.exports.validate_str(ptr, len) !== 0;
+    }
+
+    async validateF64(value) {
+        await this.ensureLoaded();
+
+        if (!this.wasmInstance.exports.validate_f64) {
+            throw new Error('WASM module does not export a validate_f64 function');
+        }
+
+        // Numbers cross the boundary as plain f64 arguments
+        return this.wasmInstance.exports.validate_f64(value) !== 0;
+    }
+
+    async validateBytes(bytes, options) {
+        await this.ensureLoaded();
+
+        if (!this.wasmInstance.exports.validate_bytes) {
+            throw new Error('WASM module does not export a validate_bytes function');
+        }
+
+        // The bytes are passed as (pointer, length), like validateStr's string, and the options
+        // as a heap index, or 0 when there are none
+        const [ptr, len] = this.passBytes(bytes instanceof Uint8Array ? bytes : new Uint8Array(bytes));
+        const optionsIdx = options === undefined || options === null ? 0 : this.addHeapObject(options);
+        return this.wasmInstance.exports.validate_bytes(ptr, len, optionsIdx) !== 0;
+    }
+
+    async describe() {
+        await this.ensureLoaded();
+
+        if (!this.wasmInstance.exports.describe) {
+            throw new Error('WASM module does not export a describe function');
+        }
+
+        // Returns a heap index for the validator's capability metadata
+        return this.takeObject(this.wasmInstance.exports.describe());
+    }
+
+    async validateBatch(values, options) {
+        return this.callBatchExport('validate_batch', values, options);
+    }
+
+    async validateBatchDetailed(values, options, locale) {
+        return this.callBatchExport('validate_batch_detailed', values, options, locale);
+    }
+
+    async callBatchExport(name, values, options, locale) {
+        await this.ensureLoaded();
+
+        const exports = this.wasmInstance.exports;
+        if (!exports[name]) {
+            throw new Error(`WASM module does not export a ${name} function`);
+        }
+
+        // Batch exports take every value in one call, sharing a single options object
+        const input = options === undefined ? { values } : { values, options };
+        if (locale !== undefined) {
+            input.locale = locale;
+        }
+        const inputIdx = this.addHeapObject(input);
+
+        // Vec<JsValue> results are returned through a stack slot holding (pointer, length)
+        const retptr = exports.__wbindgen_add_to_stack_pointer(-16);
+        try {
+            exports[name](retptr, inputIdx);
+
+            const view = new DataView(exports.memory.buffer);
+            const ptr = view.getInt32(retptr, true);
+            const len = view.getInt32(retptr + 4, true);
+
+            // The vector holds one heap index per result
+            const results = [];
+            for (let i = 0; i < len; i++) {
+                results.push(this.takeObject(view.getUint32(ptr + 4 * i, true)));
+            }
+            exports.__wbindgen_free(ptr, len * 4, 4);
+
+            return results;
+        } finally {
+            exports.__wbindgen_add_to_stack_pointer(16);
+        }
+    }
+
+    async assert(value, options) {
+        const isValid = await this.validate(value, options);
         
         if (!isValid) {
             throw new ValidationError(
@@ -5,6 +5,23 @@
 
 set -e  # Exit on any error
 
+# ./build.sh --combined [datatype,...] builds a single size-optimized module with the named
+# validators (every one by default) instead of one module per validator
+if [ "$1" = "--combined" ]; then
+    features="exports,${2:-all}"
+    echo "📦 Building combined validator module (features: $features)..."
+    cargo build -p datatype-validators --target wasm32-unknown-unknown --profile wasm-size \
+        --no-default-features --features "$features"
+    mkdir -p build/Combined
+    cp target/wasm32-unknown-unknown/wasm-size/datatype_validators.wasm build/Combined/index.wasm
+    if command -v wasm-opt >/dev/null; then
+        wasm-opt -Oz build/Combined/index.wasm -o build/Combined/index.wasm
+    fi
+    echo "✅ Build complete!"
+    ls -lh build/Combined/index.wasm | awk '{print "  • " $9 ": " $5}'
+    exit 0
+fi
+
 echo "🔨 Building all validators..."
 
 # Create build directory structure
@@ -18,63 +35,515 @@ mkdir -p build/FloatingPoint
 mkdir -p build/DateTime
 mkdir -p build/Date
 mkdir -p build/Time
+mkdir -p build/ARIA
+mkdir -p build/Contrast
+mkdir -p build/Email
+mkdir -p build/UUID
+mkdir -p build/FontFamily
+mkdir -p build/IP
+mkdir -p build/Phone
+mkdir -p build/CreditCard
+mkdir -p build/Color
+mkdir -p build/Period
+mkdir -p build/Regex
+mkdir -p build/Altitude
+mkdir -p build/Heading
+mkdir -p build/Array
+mkdir -p build/Expiry
+mkdir -p build/Record
+mkdir -p build/CVV
+mkdir -p build/Combinator
+mkdir -p build/RFReference
+mkdir -p build/Ticker
+mkdir -p build/Unified
+mkdir -p build/List
+mkdir -p build/KV
+mkdir -p build/ConnString
+mkdir -p build/MailServer
+mkdir -p build/Webhook
+mkdir -p build/S3Bucket
+mkdir -p build/S3Key
+mkdir -p build/FlagKey
+mkdir -p build/Boundary
+mkdir -p build/Disposition
+mkdir -p build/Charset
+mkdir -p build/EncodingSniff
+mkdir -p build/FileType
+mkdir -p build/Archive
+mkdir -p build/PDF
+mkdir -p build/VCard
+mkdir -p build/ICS
+mkdir -p build/Subtitle
+mkdir -p build/M3U8
+mkdir -p build/Decimal
+mkdir -p build/Feed
+mkdir -p build/OPML
+mkdir -p build/Sitemap
+mkdir -p build/SecurityTxt
+mkdir -p build/AuthRecord
+mkdir -p build/Header
+mkdir -p build/Placeholder
+mkdir -p build/FormatString
+mkdir -p build/URN
+mkdir -p build/PII
+mkdir -p build/URITemplate
+mkdir -p build/Magnet
+mkdir -p build/Secret
 
 # Text Validator
 echo "📝 Building Text validator..."
 cd Text/text-validator
 cargo build --target wasm32-unknown-unknown --release
-cp target/wasm32-unknown-unknown/release/text_validator.wasm ../../build/Text/index.wasm
+cp ../../target/wasm32-unknown-unknown/release/text_validator.wasm ../../build/Text/index.wasm
 cd ../..
 
 # URL Validator
 echo "🔗 Building URL validator..."
 cd URL/url-validator
 cargo build --target wasm32-unknown-unknown --release
-cp target/wasm32-unknown-unknown/release/url_validator.wasm ../../build/URL/index.wasm
+cp ../../target/wasm32-unknown-unknown/release/url_validator.wasm ../../build/URL/index.wasm
 cd ../..
 
 # Boolean Validator
 echo "✓ Building Boolean validator..."
 cd Boolean/boolean-validator
 cargo build --target wasm32-unknown-unknown --release
-cp target/wasm32-unknown-unknown/release/boolean_validator.wasm ../../build/Boolean/index.wasm
+cp ../../target/wasm32-unknown-unknown/release/boolean_validator.wasm ../../build/Boolean/index.wasm
 cd ../..
 
 # Integer Validator
 echo "🔢 Building Integer validator..."
 cd Number/integer-validator
 cargo build --target wasm32-unknown-unknown --release
-cp target/wasm32-unknown-unknown/release/integer_validator.wasm ../../build/Integer/index.wasm
+cp ../../target/wasm32-unknown-unknown/release/integer_validator.wasm ../../build/Integer/index.wasm
 cd ../..
 
 # FloatingPoint Validator
 echo "🔢 Building FloatingPoint validator..."
 cd Number/floatingpoint-validator
 cargo build --target wasm32-unknown-unknown --release
-cp target/wasm32-unknown-unknown/release/floatingpoint_validator.wasm ../../build/FloatingPoint/index.wasm
+cp ../../target/wasm32-unknown-unknown/release/floatingpoint_validator.wasm ../../build/FloatingPoint/index.wasm
 cd ../..
 
 # DateTime Validator
 echo "📅 Building DateTime validator..."
 cd DateTime/datetime-validator
 cargo build --target wasm32-unknown-unknown --release
-cp target/wasm32-unknown-unknown/release/datetime_validator.wasm ../../build/DateTime/index.wasm
+cp ../../target/wasm32-unknown-unknown/release/datetime_validator.wasm ../../build/DateTime/index.wasm
 cd ../..
 
 # Date Validator
 echo "📆 Building Date validator..."
 cd DateTime/date-validator
 cargo build --target wasm32-unknown-unknown --release
-cp target/wasm32-unknown-unknown/release/date_validator.wasm ../../build/Date/index.wasm
+cp ../../target/wasm32-unknown-unknown/release/date_validator.wasm ../../build/Date/index.wasm
 cd ../..
 
 # Time Validator
 echo "🕐 Building Time validator..."
 cd DateTime/time-validator
 cargo build --target wasm32-unknown-unknown --release
-cp target/wasm32-unknown-unknown/release/time_validator.wasm ../../build/Time/index.wasm
+cp ../../target/wasm32-unknown-unknown/release/time_validator.wasm ../../build/Time/index.wasm
+cd ../..
+
+# ARIA Validator
+echo "♿ Building ARIA validator..."
+cd HTML/aria-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/aria_validator.wasm ../../build/ARIA/index.wasm
+cd ../..
+
+# Contrast Validator
+echo "🎨 Building Contrast validator..."
+cd Color/contrast-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/contrast_validator.wasm ../../build/Contrast/index.wasm
+cd ../..
+
+# Email Validator
+echo "📧 Building Email validator..."
+cd Email/email-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/email_validator.wasm ../../build/Email/index.wasm
+cd ../..
+
+# UUID Validator
+echo "🆔 Building UUID validator..."
+cd UUID/uuid-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/uuid_validator.wasm ../../build/UUID/index.wasm
+cd ../..
+
+# FontFamily Validator
+echo "🔤 Building FontFamily validator..."
+cd CSS/fontfamily-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/fontfamily_validator.wasm ../../build/FontFamily/index.wasm
+cd ../..
+
+# IP Validator
+echo "🌐 Building IP validator..."
+cd Network/ip-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/ip_validator.wasm ../../build/IP/index.wasm
+cd ../..
+
+# Phone Validator
+echo "📞 Building Phone validator..."
+cd Phone/phone-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/phone_validator.wasm ../../build/Phone/index.wasm
+cd ../..
+
+# CreditCard Validator
+echo "💳 Building CreditCard validator..."
+cd Payment/creditcard-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/creditcard_validator.wasm ../../build/CreditCard/index.wasm
+cd ../..
+
+# Color Validator
+echo "🎨 Building Color validator..."
+cd Color/color-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/color_validator.wasm ../../build/Color/index.wasm
+cd ../..
+
+# Period Validator
+echo "🗓️ Building Period validator..."
+cd DateTime/period-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/period_validator.wasm ../../build/Period/index.wasm
+cd ../..
+
+# Regex Validator
+echo "🔍 Building Regex validator..."
+cd Pattern/regex-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/regex_validator.wasm ../../build/Regex/index.wasm
+cd ../..
+
+# Altitude Validator
+echo "⛰️  Building Altitude validator..."
+cd Geo/altitude-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/altitude_validator.wasm ../../build/Altitude/index.wasm
+cd ../..
+
+# Heading Validator
+echo "🧭 Building Heading validator..."
+cd Geo/heading-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/heading_validator.wasm ../../build/Heading/index.wasm
+cd ../..
+
+# Array Validator
+echo "📚 Building Array validator..."
+cd Collection/array-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/array_validator.wasm ../../build/Array/index.wasm
+cd ../..
+
+# Expiry Validator
+echo "📅 Building Expiry validator..."
+cd Payment/expiry-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/expiry_validator.wasm ../../build/Expiry/index.wasm
+cd ../..
+
+# Record Validator
+echo "🗂️  Building Record validator..."
+cd Record/record-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/record_validator.wasm ../../build/Record/index.wasm
+cd ../..
+
+# CVV Validator
+echo "🔐 Building CVV validator..."
+cd Payment/cvv-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/cvv_validator.wasm ../../build/CVV/index.wasm
+cd ../..
+
+# Combinator Validator
+echo "🧩 Building Combinator validator..."
+cd Combinator/combinator-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/combinator_validator.wasm ../../build/Combinator/index.wasm
+cd ../..
+
+# RFReference Validator
+echo "🧾 Building RFReference validator..."
+cd Payment/rfreference-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/rfreference_validator.wasm ../../build/RFReference/index.wasm
+cd ../..
+
+# Ticker Validator
+echo "📈 Building Ticker validator..."
+cd Finance/ticker-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/ticker_validator.wasm ../../build/Ticker/index.wasm
+cd ../..
+
+# Unified Validator (every validator in one module)
+echo "📦 Building Unified validator..."
+cd Unified/datatype-validators
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/datatype_validators.wasm ../../build/Unified/index.wasm
+cd ../..
+
+# List Validator
+echo "🏷️  Building List validator..."
+cd List/list-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/list_validator.wasm ../../build/List/index.wasm
+cd ../..
+
+# KV Validator
+echo "🔑 Building KV validator..."
+cd Config/kv-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/kv_validator.wasm ../../build/KV/index.wasm
+cd ../..
+
+# ConnString Validator
+echo "🔌 Building ConnString validator..."
+cd Config/connstring-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/connstring_validator.wasm ../../build/ConnString/index.wasm
+cd ../..
+
+# MailServer Validator
+echo "📮 Building MailServer validator..."
+cd Config/mailserver-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/mailserver_validator.wasm ../../build/MailServer/index.wasm
+cd ../..
+
+# Webhook Validator
+echo "🪝 Building Webhook validator..."
+cd URL/webhook-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/webhook_validator.wasm ../../build/Webhook/index.wasm
+cd ../..
+
+# S3Bucket Validator
+echo "🪣 Building S3Bucket validator..."
+cd Storage/s3bucket-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/s3bucket_validator.wasm ../../build/S3Bucket/index.wasm
 cd ../..
 
+# S3Key Validator
+echo "🔑 Building S3Key validator..."
+cd Storage/s3key-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/s3key_validator.wasm ../../build/S3Key/index.wasm
+cd ../..
+
+# FlagKey Validator
+echo "🚩 Building FlagKey validator..."
+cd Config/flagkey-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/flagkey_validator.wasm ../../build/FlagKey/index.wasm
+cd ../..
+
+# Boundary Validator
+echo "✉️ Building Boundary validator..."
+cd MIME/boundary-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/boundary_validator.wasm ../../build/Boundary/index.wasm
+cd ../..
+
+# Disposition Validator
+echo "📎 Building Disposition validator..."
+cd MIME/disposition-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/disposition_validator.wasm ../../build/Disposition/index.wasm
+cd ../..
+
+# Charset Validator
+echo "🔤 Building Charset validator..."
+cd Encoding/charset-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/charset_validator.wasm ../../build/Charset/index.wasm
+cd ../..
+
+# EncodingSniff Validator
+echo "🔍 Building EncodingSniff validator..."
+cd Encoding/encodingsniff-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/encodingsniff_validator.wasm ../../build/EncodingSniff/index.wasm
+cd ../..
+
+# FileType Validator
+echo "📁 Building FileType validator..."
+cd File/filetype-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/filetype_validator.wasm ../../build/FileType/index.wasm
+cd ../..
+
+# Archive Validator
+echo "🗜️ Building Archive validator..."
+cd File/archive-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/archive_validator.wasm ../../build/Archive/index.wasm
+cd ../..
+
+# PDF Validator
+echo "📄 Building PDF validator..."
+cd File/pdf-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/pdf_validator.wasm ../../build/PDF/index.wasm
+cd ../..
+
+# VCard Validator
+echo "📇 Building VCard validator..."
+cd Contact/vcard-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/vcard_validator.wasm ../../build/VCard/index.wasm
+cd ../..
+
+# ICS Validator
+echo "📅 Building ICS validator..."
+cd Calendar/ics-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/ics_validator.wasm ../../build/ICS/index.wasm
+cd ../..
+
+# Subtitle Validator
+echo "💬 Building Subtitle validator..."
+cd Media/subtitle-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/subtitle_validator.wasm ../../build/Subtitle/index.wasm
+cd ../..
+
+# M3U8 Validator
+echo "📺 Building M3U8 validator..."
+cd Media/m3u8-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/m3u8_validator.wasm ../../build/M3U8/index.wasm
+cd ../..
+
+# Decimal Validator
+echo "🔢 Building Decimal validator..."
+cd Number/decimal-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/decimal_validator.wasm ../../build/Decimal/index.wasm
+cd ../..
+
+# Feed Validator
+echo "📰 Building Feed validator..."
+cd Feed/feed-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/feed_validator.wasm ../../build/Feed/index.wasm
+cd ../..
+
+# OPML Validator
+echo "🗂️ Building OPML validator..."
+cd Feed/opml-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/opml_validator.wasm ../../build/OPML/index.wasm
+cd ../..
+
+# Sitemap Validator
+echo "🗺️ Building Sitemap validator..."
+cd Feed/sitemap-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/sitemap_validator.wasm ../../build/Sitemap/index.wasm
+cd ../..
+
+# SecurityTxt Validator
+echo "🔐 Building SecurityTxt validator..."
+cd Security/securitytxt-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/securitytxt_validator.wasm ../../build/SecurityTxt/index.wasm
+cd ../..
+
+# AuthRecord Validator
+echo "🔏 Building AuthRecord validator..."
+cd Email/authrecord-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/authrecord_validator.wasm ../../build/AuthRecord/index.wasm
+cd ../..
+
+# Header Validator
+echo "📨 Building Header validator..."
+cd Email/header-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/header_validator.wasm ../../build/Header/index.wasm
+cd ../..
+
+# Placeholder Validator
+echo "🧩 Building Placeholder validator..."
+cd Template/placeholder-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/placeholder_validator.wasm ../../build/Placeholder/index.wasm
+cd ../..
+
+# FormatString Validator
+echo "🔣 Building FormatString validator..."
+cd Template/formatstring-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/formatstring_validator.wasm ../../build/FormatString/index.wasm
+cd ../..
+
+# URN Validator
+echo "🏷️ Building URN validator..."
+cd URL/urn-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/urn_validator.wasm ../../build/URN/index.wasm
+cd ../..
+
+# PII Validator
+echo "🕵️ Building PII validator..."
+cd Privacy/pii-detector
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/pii_detector.wasm ../../build/PII/index.wasm
+cd ../..
+
+# URITemplate Validator
+echo "🧷 Building URITemplate validator..."
+cd URL/uritemplate-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/uritemplate_validator.wasm ../../build/URITemplate/index.wasm
+cd ../..
+
+# Magnet Validator
+echo "🧲 Building Magnet validator..."
+cd URL/magnet-validator
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/magnet_validator.wasm ../../build/Magnet/index.wasm
+cd ../..
+
+# Secret Validator
+echo "🔑 Building Secret validator..."
+cd Security/secret-detector
+cargo build --target wasm32-unknown-unknown --release
+cp ../../target/wasm32-unknown-unknown/release/secret_detector.wasm ../../build/Secret/index.wasm
+cd ../..
+
+# Component Model builds, for hosts such as wasmtime and wasm-compose; each validator
+# crate with a "component" feature is built for wasm32-wasip2 without its wasm-bindgen exports
+if rustup target list --installed 2>/dev/null | grep -q '^wasm32-wasip2$'; then
+    echo "🧱 Building Component Model validators..."
+    mkdir -p build/components
+    for manifest in $(grep -l '^component = ' */*/Cargo.toml); do
+        crate=$(dirname "$manifest")
+        name=$(basename "$crate")
+        # Composite validators only reach every datatype through their "all" feature
+        features=component
+        if grep -q '^all = ' "$manifest"; then
+            features=component,all
+        fi
+        (cd "$crate" && cargo build --target wasm32-wasip2 --release --no-default-features --features "$features")
+        cp "target/wasm32-wasip2/release/${name//-/_}.wasm" "build/components/$name.wasm"
+    done
+else
+    echo "⏭️  Skipping Component Model builds (rustup target add wasm32-wasip2 to enable)"
+fi
+
 echo "✅ Build complete!"
 echo ""
 echo "📊 Build summary:"
@@ -86,6 +555,63 @@ echo "  • FloatingPoint validator → build/FloatingPoint/index.wasm"
 echo "  • DateTime validator   → build/DateTime/index.wasm"
 echo "  • Date validator       → build/Date/index.wasm"
 echo "  • Time validator       → build/Time/index.wasm"
+echo "  • ARIA validator       → build/ARIA/index.wasm"
+echo "  • Contrast validator   → build/Contrast/index.wasm"
+echo "  • Email validator       → build/Email/index.wasm"
+echo "  • UUID validator        → build/UUID/index.wasm"
+echo "  • FontFamily validator  → build/FontFamily/index.wasm"
+echo "  • IP validator          → build/IP/index.wasm"
+echo "  • Phone validator       → build/Phone/index.wasm"
+echo "  • CreditCard validator  → build/CreditCard/index.wasm"
+echo "  • Color validator       → build/Color/index.wasm"
+echo "  • Period validator      → build/Period/index.wasm"
+echo "  • Regex validator       → build/Regex/index.wasm"
+echo "  • Altitude validator    → build/Altitude/index.wasm"
+echo "  • Heading validator     → build/Heading/index.wasm"
+echo "  • Array validator       → build/Array/index.wasm"
+echo "  • Expiry validator      → build/Expiry/index.wasm"
+echo "  • Record validator      → build/Record/index.wasm"
+echo "  • CVV validator         → build/CVV/index.wasm"
+echo "  • Combinator validator  → build/Combinator/index.wasm"
+echo "  • RFReference validator  → build/RFReference/index.wasm"
+echo "  • Ticker validator      → build/Ticker/index.wasm"
+echo "  • Unified validator     → build/Unified/index.wasm"
+echo "  • List validator        → build/List/index.wasm"
+echo "  • KV validator          → build/KV/index.wasm"
+echo "  • ConnString validator  → build/ConnString/index.wasm"
+echo "  • MailServer validator  → build/MailServer/index.wasm"
+echo "  • Webhook validator     → build/Webhook/index.wasm"
+echo "  • S3Bucket validator    → build/S3Bucket/index.wasm"
+echo "  • S3Key validator       → build/S3Key/index.wasm"
+echo "  • FlagKey validator     → build/FlagKey/index.wasm"
+echo "  • Boundary validator    → build/Boundary/index.wasm"
+echo "  • Disposition validator → build/Disposition/index.wasm"
+echo "  • Charset validator     → build/Charset/index.wasm"
+echo "  • EncodingSniff validator → build/EncodingSniff/index.wasm"
+echo "  • FileType validator    → build/FileType/index.wasm"
+echo "  • Archive validator     → build/Archive/index.wasm"
+echo "  • PDF validator         → build/PDF/index.wasm"
+echo "  • VCard validator       → build/VCard/index.wasm"
+echo "  • ICS validator         → build/ICS/index.wasm"
+echo "  • Subtitle validator    → build/Subtitle/index.wasm"
+echo "  • M3U8 validator        → build/M3U8/index.wasm"
+echo "  • Decimal validator     → build/Decimal/index.wasm"
+echo "  • Feed validator        → build/Feed/index.wasm"
+echo "  • OPML validator        → build/OPML/index.wasm"
+echo "  • Sitemap validator     → build/Sitemap/index.wasm"
+echo "  • SecurityTxt validator → build/SecurityTxt/index.wasm"
+echo "  • AuthRecord validator  → build/AuthRecord/index.wasm"
+echo "  • Header validator      → build/Header/index.wasm"
+echo "  • Placeholder validator → build/Placeholder/index.wasm"
+echo "  • FormatString validator → build/FormatString/index.wasm"
+echo "  • URN validator         → build/URN/index.wasm"
+echo "  • PII validator         → build/PII/index.wasm"
+echo "  • URITemplate validator → build/URITemplate/index.wasm"
+echo "  • Magnet validator      → build/Magnet/index.wasm"
+echo "  • Secret validator      → build/Secret/index.wasm"
+if [ -d build/components ]; then
+    echo "  • Components           → build/components/*.wasm"
+fi
 echo ""
 
 # Show file sizes
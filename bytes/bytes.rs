@@ -0,0 +1,57 @@
+// ABOUTME: Raw bytes as validator input: a Uint8Array or ArrayBuffer from JS, or an array of numbers in JSON
+// ABOUTME: Included through #[path] by the encoding sniff, file type and archive validators as their value type
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+
+// Raw bytes: a Uint8Array or ArrayBuffer from JS, or an array of numbers from 0 to 255 in JSON
+#[derive(Debug, PartialEq)]
+pub struct Bytes(pub Vec<u8>);
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Bytes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a Uint8Array or an array of numbers from 0 to 255")
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Bytes, E> {
+        Ok(Bytes(bytes.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Bytes, E> {
+        Ok(Bytes(bytes))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Bytes, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        Ok(Bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(Bytes::deserialize(json!([104, 105])).unwrap(), Bytes(vec![104, 105]));
+        assert_eq!(Bytes::deserialize(json!([])).unwrap(), Bytes(vec![]));
+        assert!(Bytes::deserialize(json!([256])).is_err());
+        assert!(Bytes::deserialize(json!([-1])).is_err());
+        assert!(Bytes::deserialize(json!("hi")).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+// ABOUTME: Content lines as iCalendar (RFC 5545) and vCard (RFC 6350) write them, unfolded and split into components
+// ABOUTME: Included through #[path] by the ics and vCard validators, which link the date, datetime and URL validators it calls
+
+use serde_json::{json, Value};
+
+// Error codes for text that isn't made of content lines
+pub const MALFORMED_LINE: &str = "malformed_line";
+pub const INVALID_LINE_BREAK: &str = "invalid_line_break";
+pub const LINE_TOO_LONG: &str = "line_too_long";
+pub const UNBALANCED_COMPONENT: &str = "unbalanced_component";
+
+// Lines longer than this many octets, line break aside, should be folded
+const MAX_LINE_OCTETS: usize = 75;
+// Components nest a few levels deep at most (an alarm in an event in a calendar)
+const MAX_NESTING: usize = 8;
+
+// A property after unfolding: its name without any vCard group, its parameters, and its value
+// as written. Names are uppercased, as both formats compare them ignoring case
+#[derive(Debug, PartialEq)]
+pub struct ContentLine {
+    pub name: String,
+    pub params: Vec<(String, Vec<String>)>,
+    pub value: String,
+    // The physical line it starts on, counting from 1
+    pub line: usize,
+}
+
+impl ContentLine {
+    // The first value of a parameter
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(key, _)| key == name).and_then(|(_, values)| values.first()).map(String::as_str)
+    }
+
+    // Whether the VALUE parameter names a value type, such as DATE or URI
+    pub fn value_type_is(&self, value_type: &str) -> bool {
+        self.param("VALUE").is_some_and(|value| value.eq_ignore_ascii_case(value_type))
+    }
+}
+
+// A BEGIN:NAME ... END:NAME block: its properties and the components nested in it
+#[derive(Debug, PartialEq)]
+pub struct Component {
+    pub name: String,
+    pub properties: Vec<ContentLine>,
+    pub children: Vec<Component>,
+    // The physical line of its BEGIN
+    pub line: usize,
+}
+
+impl Component {
+    pub fn property(&self, name: &str) -> Option<&ContentLine> {
+        self.properties.iter().find(|property| property.name == name)
+    }
+}
+
+// Why a file was rejected: an error code, the physical line it was found on, and the property
+// at fault where there is one
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    pub code: &'static str,
+    pub line: usize,
+    pub property: Option<String>,
+}
+
+impl Problem {
+    pub fn at(code: &'static str, line: usize) -> Self {
+        Problem { code, line, property: None }
+    }
+
+    pub fn with(code: &'static str, property: &ContentLine) -> Self {
+        Problem { code, line: property.line, property: Some(property.name.clone()) }
+    }
+}
+
+// Names are letters, digits and hyphens
+fn is_name(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+// One unfolded line: [group "."] name *(";" param) ":" value, where a parameter is a name, "="
+// and comma-separated values, each quoted or free of quotes, semicolons, colons and commas
+fn parse_line(text: &str, line: usize) -> Option<ContentLine> {
+    let end = text.find([';', ':'])?;
+    let name = match text[..end].split_once('.') {
+        Some((group, name)) if is_name(group) => name,
+        Some(_) => return None,
+        None => &text[..end],
+    };
+    if !is_name(name) {
+        return None;
+    }
+
+    let mut params = Vec::new();
+    let mut rest = &text[end..];
+    while let Some(param) = rest.strip_prefix(';') {
+        let (key, mut values_text) = param.split_once('=')?;
+        if !is_name(key) {
+            return None;
+        }
+        let mut values = Vec::new();
+        loop {
+            let (value, after) = match values_text.strip_prefix('"') {
+                Some(quoted) => {
+                    let close = quoted.find('"')?;
+                    (&quoted[..close], &quoted[close + 1..])
+                }
+                None => {
+                    let end = values_text.find(['"', ';', ':', ',']).unwrap_or(values_text.len());
+                    (&values_text[..end], &values_text[end..])
+                }
+            };
+            values.push(value.to_string());
+            match after.strip_prefix(',') {
+                Some(more) => values_text = more,
+                None => {
+                    rest = after;
+                    break;
+                }
+            }
+        }
+        params.push((key.to_ascii_uppercase(), values));
+    }
+
+    let value = rest.strip_prefix(':')?;
+    Some(ContentLine { name: name.to_ascii_uppercase(), params, value: value.to_string(), line })
+}
+
+// Unfolds the text and parses each of its lines. A line that starts with a space or tab
+// continues the one before it; strict checking also requires CRLF line breaks and lines of
+// at most 75 octets, as both RFCs do, though many exporters write bare LFs and long lines
+pub fn parse(text: &str, strict: bool) -> Result<Vec<ContentLine>, Problem> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut unfolded: Vec<(String, usize)> = Vec::new();
+    let mut physical = text.split('\n').enumerate().peekable();
+    while let Some((index, raw)) = physical.next() {
+        let line = index + 1;
+        let last = physical.peek().is_none();
+        let content = match raw.strip_suffix('\r') {
+            Some(content) => content,
+            None if strict && !last => return Err(Problem::at(INVALID_LINE_BREAK, line)),
+            None => raw,
+        };
+        if strict && content.len() > MAX_LINE_OCTETS {
+            return Err(Problem::at(LINE_TOO_LONG, line));
+        }
+        if let Some(continuation) = content.strip_prefix([' ', '\t']) {
+            match unfolded.last_mut() {
+                Some((logical, _)) => logical.push_str(continuation),
+                None => return Err(Problem::at(MALFORMED_LINE, line)),
+            }
+        } else if !content.is_empty() {
+            unfolded.push((content.to_string(), line));
+        }
+    }
+
+    unfolded
+        .into_iter()
+        .map(|(text, line)| parse_line(&text, line).ok_or(Problem::at(MALFORMED_LINE, line)))
+        .collect()
+}
+
+// Groups the lines into components by their BEGIN and END lines, which must pair up by name;
+// every property belongs to a component
+pub fn components(lines: Vec<ContentLine>) -> Result<Vec<Component>, Problem> {
+    let mut top = Vec::new();
+    let mut open: Vec<Component> = Vec::new();
+    for line in lines {
+        match line.name.as_str() {
+            "BEGIN" => {
+                if open.len() >= MAX_NESTING || !is_name(&line.value) {
+                    return Err(Problem::at(UNBALANCED_COMPONENT, line.line));
+                }
+                let name = line.value.to_ascii_uppercase();
+                open.push(Component { name, properties: Vec::new(), children: Vec::new(), line: line.line });
+            }
+            "END" => {
+                let component = match open.pop() {
+                    Some(component) if component.name.eq_ignore_ascii_case(&line.value) => component,
+                    _ => return Err(Problem::at(UNBALANCED_COMPONENT, line.line)),
+                };
+                match open.last_mut() {
+                    Some(parent) => parent.children.push(component),
+                    None => top.push(component),
+                }
+            }
+            _ => match open.last_mut() {
+                Some(component) => component.properties.push(line),
+                None => return Err(Problem::at(UNBALANCED_COMPONENT, line.line)),
+            },
+        }
+    }
+    match open.first() {
+        Some(unclosed) => Err(Problem::at(UNBALANCED_COMPONENT, unclosed.line)),
+        None => Ok(top),
+    }
+}
+
+// A date, in the basic (20240314) or extended (2024-03-14) form
+pub fn is_date(text: &str) -> bool {
+    date_validator::coerce_value(&Value::from(text), &json!({"iso8601": true})).is_some()
+}
+
+// A date and time, with or without a UTC offset
+pub fn is_date_time(text: &str) -> bool {
+    datetime_validator::coerce_value(&Value::from(text), &json!({"iso8601": true, "timezone": "optional"})).is_some()
+}
+
+// A URI of one of the common schemes, such as https:, mailto:, tel: or data:
+pub fn is_uri(text: &str) -> bool {
+    url_validator::coerce_value(&Value::from(text), &Value::Null).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let lines = parse("BEGIN:VCARD\r\nitem1.EMAIL;type=INTERNET,pref;X-LABEL=\"Work: main\":jane@\r\n example.com\r\n", false).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!((lines[0].name.as_str(), lines[0].value.as_str()), ("BEGIN", "VCARD"));
+        assert_eq!(lines[1].name, "EMAIL");
+        assert_eq!(lines[1].params, vec![
+            ("TYPE".to_string(), vec!["INTERNET".to_string(), "pref".to_string()]),
+            ("X-LABEL".to_string(), vec!["Work: main".to_string()]),
+        ]);
+        assert_eq!(lines[1].param("X-LABEL"), Some("Work: main"));
+        assert_eq!((lines[1].value.as_str(), lines[1].line), ("jane@example.com", 2));
+
+        // Values may hold colons and semicolons, and be empty
+        let lines = parse("DESCRIPTION:a;b:c\nX-EMPTY:\n", false).unwrap();
+        assert_eq!((lines[0].value.as_str(), lines[1].value.as_str()), ("a;b:c", ""));
+
+        assert_eq!(parse(" folded:start", false), Err(Problem::at(MALFORMED_LINE, 1)));
+        assert_eq!(parse("BEGIN:VCARD\nno colon\n", false), Err(Problem::at(MALFORMED_LINE, 2)));
+        assert_eq!(parse("BAD NAME:x", false), Err(Problem::at(MALFORMED_LINE, 1)));
+        assert_eq!(parse("X;PARAM:x", false), Err(Problem::at(MALFORMED_LINE, 1)));
+        assert_eq!(parse("X;P=\"unclosed:x", false), Err(Problem::at(MALFORMED_LINE, 1)));
+
+        // Strict checking wants CRLF and folded long lines
+        assert!(parse("A:b\r\nC:d\r\n", true).is_ok());
+        assert_eq!(parse("A:b\nC:d\r\n", true), Err(Problem::at(INVALID_LINE_BREAK, 1)));
+        let long = format!("NOTE:{}\r\n", "x".repeat(71));
+        assert_eq!(parse(&long, true), Err(Problem::at(LINE_TOO_LONG, 1)));
+        assert!(parse(&long, false).is_ok());
+    }
+
+    #[test]
+    fn test_components() {
+        let lines = parse("BEGIN:VCALENDAR\nVERSION:2.0\nBEGIN:VEVENT\nUID:1\nEND:VEVENT\nEND:vcalendar\n", false).unwrap();
+        let calendars = components(lines).unwrap();
+        assert_eq!(calendars.len(), 1);
+        assert_eq!(calendars[0].property("VERSION").map(|version| version.value.as_str()), Some("2.0"));
+        assert_eq!(calendars[0].children[0].name, "VEVENT");
+        assert_eq!(calendars[0].children[0].line, 3);
+
+        let unbalanced = |text: &str| components(parse(text, false).unwrap()).unwrap_err();
+        assert_eq!(unbalanced("BEGIN:VCARD\nFN:x\n"), Problem::at(UNBALANCED_COMPONENT, 1));
+        assert_eq!(unbalanced("BEGIN:A\nBEGIN:B\nEND:A\nEND:B\n"), Problem::at(UNBALANCED_COMPONENT, 3));
+        assert_eq!(unbalanced("FN:x\n"), Problem::at(UNBALANCED_COMPONENT, 1));
+        assert_eq!(unbalanced("END:VCARD\n"), Problem::at(UNBALANCED_COMPONENT, 1));
+        assert_eq!(unbalanced(&"BEGIN:A\n".repeat(20)), Problem::at(UNBALANCED_COMPONENT, MAX_NESTING + 1));
+    }
+
+    #[test]
+    fn test_values() {
+        assert!(is_date("20240314"));
+        assert!(is_date("2024-03-14"));
+        assert!(!is_date("20240230"));
+        assert!(is_date_time("20240314T153000Z"));
+        assert!(is_date_time("20240314T153000"));
+        assert!(!is_date_time("20240314T256000"));
+        assert!(is_uri("mailto:jane@example.com"));
+        assert!(is_uri("https://example.com/jane"));
+        assert!(!is_uri("not a uri"));
+    }
+}
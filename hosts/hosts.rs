@@ -0,0 +1,126 @@
+// ABOUTME: Hosts a server-side request must not be steered to: private and local IP addresses, and internal names
+// ABOUTME: Included through #[path] by the URL and webhook validators for their SSRF checks
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use url::Host;
+
+// Error codes for a host that reaches into a private network
+pub const PRIVATE_ADDRESS: &str = "private_address";
+pub const INTERNAL_HOST: &str = "internal_host";
+
+// Names that only resolve inside a network: localhost, mDNS, and the suffixes reserved or
+// commonly used for private zones
+const INTERNAL_SUFFIXES: &[&str] = &["localhost", "local", "internal", "intranet", "lan", "home.arpa", "corp"];
+
+pub fn is_internal_name(host: &str) -> bool {
+    let host = host.strip_suffix('.').unwrap_or(host);
+    // A single label is resolved against the local search domains
+    !host.contains('.')
+        || INTERNAL_SUFFIXES
+            .iter()
+            .any(|suffix| host == *suffix || host.strip_suffix(suffix).is_some_and(|rest| rest.ends_with('.')))
+}
+
+// Addresses a server-side request must not be steered to: loopback, private, link-local (which
+// includes cloud metadata endpoints), shared, unspecified, multicast and documentation ranges
+fn is_public_ipv4(address: Ipv4Addr) -> bool {
+    let [a, b, _, _] = address.octets();
+    !(address.is_loopback()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_unspecified()
+        || address.is_broadcast()
+        || address.is_multicast()
+        || address.is_documentation()
+        // 0.0.0.0/8, 100.64.0.0/10 (carrier-grade NAT), and 240.0.0.0/4 (reserved)
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || a >= 240)
+}
+
+fn is_public_ipv6(address: Ipv6Addr) -> bool {
+    // IPv4-mapped and -compatible addresses reach the IPv4 host
+    if let Some(v4) = address.to_ipv4() {
+        return is_public_ipv4(v4);
+    }
+    let first = address.segments()[0];
+    !(address.is_loopback()
+        || address.is_unspecified()
+        || address.is_multicast()
+        // fc00::/7 (unique local), fe80::/10 (link-local) and 2001:db8::/32 (documentation)
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        || (first == 0x2001 && address.segments()[1] == 0x0db8))
+}
+
+pub fn is_public_address(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(v4) => is_public_ipv4(v4),
+        IpAddr::V6(v6) => is_public_ipv6(v6),
+    }
+}
+
+// Why a URL's host reaches into a private network, or None for a public host. IP addresses
+// are classified as parsed, so with the url crate's parsing, "0x7f.1" and "2130706433" are
+// loopback addresses too; names aren't resolved, so a public name may still point inward.
+// Schemes the URL standard doesn't know, such as ssh: and git:, leave an IPv4 address as a
+// name, which is parsed here as an http: host would be, since their clients connect to it
+pub fn host_error(host: &Host<&str>) -> Option<&'static str> {
+    match host {
+        Host::Ipv4(address) if !is_public_address(IpAddr::V4(*address)) => Some(PRIVATE_ADDRESS),
+        Host::Ipv6(address) if !is_public_address(IpAddr::V6(*address)) => Some(PRIVATE_ADDRESS),
+        Host::Domain(name) => match Host::parse(name) {
+            Ok(Host::Ipv4(address)) => host_error(&Host::Ipv4(address)),
+            _ if is_internal_name(name) => Some(INTERNAL_HOST),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_addresses() {
+        for address in ["127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1", "0.0.0.0", "240.0.0.1"] {
+            assert!(!is_public_address(address.parse().unwrap()), "{address}");
+        }
+        for address in ["::1", "fd00::1", "fe80::1", "2001:db8::1", "::ffff:10.0.0.1"] {
+            assert!(!is_public_address(address.parse().unwrap()), "{address}");
+        }
+        for address in ["93.184.216.34", "100.128.0.1", "2606:2800:220:1:248:1893:25c8:1946", "::ffff:8.8.8.8"] {
+            assert!(is_public_address(address.parse().unwrap()), "{address}");
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        for name in ["localhost", "intranet", "printer.local", "metadata.google.internal", "router.home.arpa", "db.corp."] {
+            assert!(is_internal_name(name), "{name}");
+        }
+        for name in ["example.com", "localhost.example.com", "internal.example.com"] {
+            assert!(!is_internal_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_host_error() {
+        let url = |text: &str| url::Url::parse(text).unwrap();
+        assert_eq!(host_error(&url("http://0x7f.1/").host().unwrap()), Some(PRIVATE_ADDRESS));
+        assert_eq!(host_error(&url("http://[::1]/").host().unwrap()), Some(PRIVATE_ADDRESS));
+        assert_eq!(host_error(&Host::Domain("metadata.google.internal")), Some(INTERNAL_HOST));
+        assert_eq!(host_error(&Host::Domain("example.com")), None);
+
+        // Other schemes' hosts are names, even when they're IPv4 addresses
+        for text in ["ssh://127.0.0.1/x", "git://10.0.0.1/repo", "ssh://git@192.168.1.10:2222/repo.git", "git://0x7f.1/repo"] {
+            assert_eq!(host_error(&url(text).host().unwrap()), Some(PRIVATE_ADDRESS), "{text}");
+        }
+        assert_eq!(host_error(&url("ssh://[::1]/x").host().unwrap()), Some(PRIVATE_ADDRESS));
+        assert_eq!(host_error(&url("git://8.8.8.8/repo").host().unwrap()), None);
+        assert_eq!(host_error(&url("ssh://git.example.com/repo").host().unwrap()), None);
+        assert_eq!(host_error(&url("ssh://buildbox/repo").host().unwrap()), Some(INTERNAL_HOST));
+    }
+}
@@ -0,0 +1,182 @@
+// ABOUTME: Error codes shared by every validator, and their messages from a catalog per locale
+// ABOUTME: Included by every validator through #[path], localizing detailed results when the input names a locale
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+// Error codes any validator may report besides its own; a failure a validator has no code for
+// is reported as empty_value or invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    // Null, an empty or blank string, or an empty array or object
+    EmptyValue,
+    // A well-formed value outside the accepted range
+    OutOfRange,
+    // A URL whose scheme isn't accepted
+    BadScheme,
+    // Any other failure
+    Invalid,
+}
+
+impl ErrorCode {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::EmptyValue => "empty_value",
+            ErrorCode::OutOfRange => "out_of_range",
+            ErrorCode::BadScheme => "bad_scheme",
+            ErrorCode::Invalid => "invalid",
+        }
+    }
+
+    // The code for a failure without a more specific one, judged from the input alone
+    fn for_input(input: Option<&Value>) -> ErrorCode {
+        let empty = match input {
+            Some(Value::Null) => true,
+            Some(Value::String(text)) => text.trim().is_empty(),
+            Some(Value::Array(items)) => items.is_empty(),
+            Some(Value::Object(fields)) => fields.is_empty(),
+            _ => false,
+        };
+        if empty {
+            ErrorCode::EmptyValue
+        } else {
+            ErrorCode::Invalid
+        }
+    }
+}
+
+// The message catalogs, code → message, by language; English has every code, and the other
+// languages fall back to it for any they lack
+const CATALOGS: &[(&str, &str)] = &[("en", include_str!("en.json")), ("fr", include_str!("fr.json"))];
+
+fn catalog(language: &str) -> Option<&'static HashMap<String, String>> {
+    static PARSED: OnceLock<HashMap<&'static str, HashMap<String, String>>> = OnceLock::new();
+    PARSED
+        .get_or_init(|| {
+            CATALOGS
+                .iter()
+                .map(|(language, json)| (*language, serde_json::from_str(json).expect("message catalogs are valid JSON")))
+                .collect()
+        })
+        .get(language)
+}
+
+// The message for an error code in a BCP 47 locale such as "fr" or "fr-CA"; only the language
+// is used, and languages without a catalog get English
+pub fn message(code: &str, locale: &str) -> Option<&'static str> {
+    let language = locale.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+    catalog(&language)
+        .and_then(|messages| messages.get(code))
+        .or_else(|| catalog("en")?.get(code))
+        .map(String::as_str)
+}
+
+// A detailed result as JSON; when a locale is given, each failure in it gets an error code (a
+// shared one when the validator reported none) and the message for that code
+pub fn localize<T: Serialize>(result: &T, input: &Value, locale: Option<&str>) -> Value {
+    let mut result = serde_json::to_value(result).unwrap_or(Value::Null);
+    if let (Some(locale), Value::Object(fields)) = (locale, &mut result) {
+        annotate(fields, Some(input), locale);
+    }
+    result
+}
+
+fn annotate(result: &mut Map<String, Value>, input: Option<&Value>, locale: &str) {
+    if result.get("valid") == Some(&Value::Bool(false)) {
+        match result.get("errors") {
+            // An error code per field (the mail server validator's) gets a message per field
+            Some(Value::Object(errors)) => {
+                let messages: Map<String, Value> = errors
+                    .iter()
+                    .filter_map(|(field, code)| Some((field.clone(), Value::from(message(code.as_str()?, locale)?))))
+                    .collect();
+                result.insert("messages".to_string(), Value::Object(messages));
+            },
+            _ => {
+                let code = match result.get("error").and_then(Value::as_str) {
+                    Some(code) => code.to_string(),
+                    None => ErrorCode::for_input(input).as_str().to_string(),
+                };
+                if let Some(message) = message(&code, locale) {
+                    result.insert("message".to_string(), Value::from(message));
+                }
+                result.insert("error".to_string(), Value::from(code));
+            },
+        }
+    }
+
+    // Nested results: a record's fields, a key-value string's keys or a list's items
+    for (_, child) in result.iter_mut().filter(|(name, _)| name.as_str() != "value") {
+        let children: Vec<&mut Value> = match child {
+            Value::Object(map) if !map.contains_key("valid") => map.values_mut().collect(),
+            Value::Array(items) => items.iter_mut().collect(),
+            _ => continue,
+        };
+        for child in children {
+            if let Value::Object(nested) = child {
+                if nested.contains_key("valid") {
+                    annotate(nested, None, locale);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_catalogs() {
+        // Every language translates every English message, and nothing else
+        let english = catalog("en").unwrap();
+        for (language, _) in CATALOGS {
+            let messages = catalog(language).unwrap();
+            let mut codes: Vec<&String> = messages.keys().collect();
+            let mut expected: Vec<&String> = english.keys().collect();
+            codes.sort();
+            expected.sort();
+            assert_eq!(codes, expected, "{language}");
+        }
+        for code in [ErrorCode::EmptyValue, ErrorCode::OutOfRange, ErrorCode::BadScheme, ErrorCode::Invalid] {
+            assert!(english.contains_key(code.as_str()), "{code:?}");
+        }
+    }
+
+    #[test]
+    fn test_message() {
+        assert_eq!(message("empty_value", "en"), Some("A value is required."));
+        assert_eq!(message("empty_value", "fr-CA"), Some("Une valeur est requise."));
+        assert_eq!(message("empty_value", "FR"), message("empty_value", "fr"));
+        assert_eq!(message("empty_value", "de"), message("empty_value", "en"));
+        assert_eq!(message("no_such_code", "en"), None);
+    }
+
+    #[test]
+    fn test_localize() {
+        let failed = json!({"valid": false, "value": null});
+        assert_eq!(localize(&failed, &json!("x"), None), failed);
+        assert_eq!(
+            localize(&failed, &json!("  "), Some("en")),
+            json!({"valid": false, "value": null, "error": "empty_value", "message": "A value is required."})
+        );
+        assert_eq!(localize(&failed, &json!("x"), Some("en"))["error"], "invalid");
+
+        let passed = json!({"valid": true, "value": {"valid": false}});
+        assert_eq!(localize(&passed, &json!("x"), Some("en")), passed);
+
+        // Nested results and per-field error maps are localized too
+        let nested = localize(
+            &json!({"valid": false, "value": null, "fields": {"age": {"valid": false, "value": null}}, "errors": {"port": "out_of_range"}}),
+            &json!({"age": "x"}),
+            Some("fr"),
+        );
+        assert_eq!(nested["fields"]["age"]["error"], "invalid");
+        assert_eq!(nested["messages"]["port"], message("out_of_range", "fr").unwrap());
+        assert!(nested.get("message").is_none());
+    }
+}
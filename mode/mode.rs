@@ -0,0 +1,90 @@
+// ABOUTME: The strictness mode shared by the primitive validators: strict, lenient or coerce
+// ABOUTME: Included through #[path] by the boolean, integer, float and text validators as their `mode` option, and by the list validator
+
+use serde::{Deserialize, Serialize};
+
+// How far a validator goes to read a value as its type
+#[derive(Deserialize, Serialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    // Only the native JSON type: true, 42 or 3.5, but not "true" or "42"
+    Strict,
+    // The native type and its usual string forms
+    #[default]
+    Lenient,
+    // Anything that can reasonably be read as the type, such as "1,234" for 1234
+    Coerce,
+}
+
+// A number written as a string with its thousands separators removed: "1,234,567" and
+// "1_000.5" are accepted, but separators that don't split the integer part into groups of
+// three ("1,5", "12,34") make it None
+pub fn strip_grouping(text: &str) -> Option<String> {
+    let text = text.trim();
+    let (sign, unsigned) = match text.strip_prefix(['-', '+']) {
+        Some(unsigned) => (&text[..1], unsigned),
+        None => ("", text),
+    };
+    let (integer, rest) = unsigned.split_at(unsigned.find(['.', 'e', 'E']).unwrap_or(unsigned.len()));
+
+    let separator = match integer.chars().find(|c| matches!(c, ',' | '_')) {
+        Some(separator) => separator,
+        None => return Some(text.to_string()),
+    };
+    let groups: Vec<&str> = integer.split(separator).collect();
+    let grouped = (1..=3).contains(&groups[0].len())
+        && groups[1..].iter().all(|group| group.len() == 3)
+        && groups.iter().all(|group| group.bytes().all(|b| b.is_ascii_digit()));
+
+    grouped.then(|| format!("{sign}{}{rest}", groups.concat()))
+}
+
+// Languages that write decimals with a comma, such as "3,14"; spreadsheets in those locales
+// separate list items with a semicolon instead
+const DECIMAL_COMMA_LANGUAGES: &[&str] = &[
+    "bg", "ca", "cs", "da", "de", "el", "es", "et", "eu", "fi", "fr", "gl", "hr", "hu", "id", "is",
+    "it", "lt", "lv", "nb", "nl", "nn", "no", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr",
+    "uk", "vi",
+];
+
+// Whether a locale writes decimals with a comma, judged by its language subtag
+pub fn decimal_comma(locale: &str) -> bool {
+    let language = locale.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+    DECIMAL_COMMA_LANGUAGES.contains(&language.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode() {
+        assert_eq!(Mode::default(), Mode::Lenient);
+        assert_eq!(serde_json::from_str::<Mode>("\"coerce\"").unwrap(), Mode::Coerce);
+        assert!(serde_json::from_str::<Mode>("\"Strict\"").is_err());
+    }
+
+    #[test]
+    fn test_strip_grouping() {
+        assert_eq!(strip_grouping("1,234,567").as_deref(), Some("1234567"));
+        assert_eq!(strip_grouping(" -1_000.25 ").as_deref(), Some("-1000.25"));
+        assert_eq!(strip_grouping("+12,345e3").as_deref(), Some("+12345e3"));
+        assert_eq!(strip_grouping("42").as_deref(), Some("42"));
+        assert_eq!(strip_grouping("abc").as_deref(), Some("abc"));
+
+        assert_eq!(strip_grouping("1,5"), None);
+        assert_eq!(strip_grouping("1234,567"), None);
+        assert_eq!(strip_grouping(",123"), None);
+        assert_eq!(strip_grouping("1,000_000"), None);
+    }
+
+    #[test]
+    fn test_decimal_comma() {
+        assert!(decimal_comma("de-DE"));
+        assert!(decimal_comma("fr"));
+        assert!(decimal_comma("PT_br"));
+        assert!(!decimal_comma("en-US"));
+        assert!(!decimal_comma("ja"));
+        assert!(!decimal_comma(""));
+    }
+}
@@ -0,0 +1,47 @@
+// ABOUTME: Component Model bindings for a validator crate, generated from wit/validator.wit
+// ABOUTME: Included by every validator through #[path], delegating to the crate's coerce_value
+
+wit_bindgen::generate!({ world: "datatype-validator", path: "../../wit" });
+
+use exports::datatype_validators::validator::validator::Guest;
+use serde_json::Value;
+
+struct Component;
+
+impl Guest for Component {
+    fn validate(input: String) -> bool {
+        Self::coerce(input).is_some()
+    }
+
+    fn coerce(input: String) -> Option<String> {
+        let input: Value = serde_json::from_str(&input).ok()?;
+        let options = input.get("options").unwrap_or(&Value::Null);
+        super::coerce_value(input.get("value")?, options).map(|value| value.to_string())
+    }
+
+    fn describe() -> String {
+        super::description().to_string()
+    }
+}
+
+export!(Component);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_input() {
+        assert_eq!(Component::coerce("not json".to_string()), None);
+        assert_eq!(Component::coerce("{}".to_string()), None);
+        assert_eq!(Component::coerce(r#"{"value": null, "options": {"strict": true}}"#.to_string()), None);
+        assert!(!Component::validate("[]".to_string()));
+    }
+
+    #[test]
+    fn test_describe() {
+        let description: Value = serde_json::from_str(&Component::describe()).unwrap();
+        assert_eq!(description["name"], env!("CARGO_PKG_NAME"));
+        assert!(description["options"]["properties"].is_object());
+    }
+}
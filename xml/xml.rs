@@ -0,0 +1,320 @@
+// ABOUTME: A small XML parser, enough for feeds, outlines and sitemaps: elements, attributes, namespaces, text, CDATA and entities
+// ABOUTME: Included through #[path] by the feed, OPML and sitemap validators; a DTD is skipped, so entities it declares are errors
+
+// Error codes for text that isn't well-formed XML
+pub const MALFORMED_XML: &str = "malformed_xml";
+pub const MISMATCHED_TAG: &str = "mismatched_tag";
+pub const INVALID_ENTITY: &str = "invalid_entity";
+
+// Elements nest this deep at most, which no feed, outline or sitemap comes near
+const MAX_DEPTH: usize = 64;
+
+// Why a document was rejected, and the line the problem was found on
+pub type Problem = (&'static str, usize);
+
+// An element's attributes as names and decoded values, in the order written
+pub type Attributes = Vec<(String, String)>;
+
+// An element with everything inside it
+#[derive(Debug, PartialEq)]
+pub struct Element {
+    // The name as written, with any prefix, such as "itunes:image"
+    pub name: String,
+    // The namespace the name's prefix, or the default namespace, is bound to
+    pub namespace: Option<String>,
+    pub attributes: Attributes,
+    pub children: Vec<Element>,
+    // The text directly inside the element, with entities and CDATA sections decoded
+    pub text: String,
+    // The line its start tag is on, counting from 1
+    pub line: usize,
+}
+
+impl Element {
+    // The name without its prefix
+    pub fn local_name(&self) -> &str {
+        self.name.rsplit_once(':').map_or(&self.name, |(_, local_name)| local_name)
+    }
+
+    pub fn is(&self, namespace: Option<&str>, local_name: &str) -> bool {
+        self.namespace.as_deref() == namespace && self.local_name() == local_name
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    pub fn children<'a>(&'a self, namespace: Option<&'a str>, local_name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |child| child.is(namespace, local_name))
+    }
+
+    pub fn child(&self, namespace: Option<&str>, local_name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.is(namespace, local_name))
+    }
+}
+
+// The unread part of a document, and the line it starts on
+struct Reader<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.line += taken.matches('\n').count();
+        self.rest = rest;
+        taken
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        let found = self.rest.starts_with(prefix);
+        if found {
+            self.advance(prefix.len());
+        }
+        found
+    }
+
+    // Everything up to the end marker, which is skipped too
+    fn until(&mut self, end: &str) -> Result<&'a str, Problem> {
+        let at = self.rest.find(end).ok_or((MALFORMED_XML, self.line))?;
+        let taken = self.advance(at);
+        self.advance(end.len());
+        Ok(taken)
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let len = self.rest.len() - self.rest.trim_start_matches([' ', '\t', '\r', '\n']).len();
+        self.advance(len);
+        len > 0
+    }
+
+    // An element or attribute name: a letter, underscore, colon or non-ASCII character, then
+    // those, digits, hyphens and points
+    fn name(&mut self) -> Result<String, Problem> {
+        let start = |c: char| c.is_alphabetic() || c == '_' || c == ':' || !c.is_ascii();
+        let len = match self.rest.chars().next() {
+            Some(c) if start(c) => self
+                .rest
+                .find(|c: char| !(start(c) || c.is_ascii_digit() || c == '-' || c == '.'))
+                .unwrap_or(self.rest.len()),
+            _ => return Err((MALFORMED_XML, self.line)),
+        };
+        Ok(self.advance(len).to_string())
+    }
+
+    // A start tag after its "<": the name, the attributes with their values decoded, and
+    // whether the tag closes itself
+    fn start_tag(&mut self) -> Result<(String, Attributes, bool), Problem> {
+        let name = self.name()?;
+        let mut attributes = Attributes::new();
+        loop {
+            let spaced = self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok((name, attributes, true));
+            }
+            if self.eat(">") {
+                return Ok((name, attributes, false));
+            }
+            let line = self.line;
+            let attribute = self.name()?;
+            self.skip_whitespace();
+            if !spaced || !self.eat("=") || attributes.iter().any(|(key, _)| *key == attribute) {
+                return Err((MALFORMED_XML, line));
+            }
+            self.skip_whitespace();
+            let quote = match self.rest.chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err((MALFORMED_XML, self.line)),
+            };
+            self.advance(1);
+            let value = self.until(&quote.to_string())?;
+            if value.contains('<') {
+                return Err((MALFORMED_XML, line));
+            }
+            attributes.push((attribute, decode(value, line)?));
+        }
+    }
+
+    // A document type declaration after its "<!DOCTYPE", with any internal subset in brackets
+    fn doctype(&mut self) -> Result<(), Problem> {
+        let at = self.rest.find(['[', '>']).ok_or((MALFORMED_XML, self.line))?;
+        if self.advance(at + 1).ends_with('[') {
+            self.until("]")?;
+            self.skip_whitespace();
+            if !self.eat(">") {
+                return Err((MALFORMED_XML, self.line));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Text with its entity references replaced: the five XML predefines, and characters by number
+fn decode(text: &str, line: usize) -> Result<String, Problem> {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('&') {
+        decoded.push_str(&rest[..at]);
+        let (entity, after) = rest[at + 1..].split_once(';').ok_or((INVALID_ENTITY, line))?;
+        let character = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => match entity.strip_prefix("#x") {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => entity.strip_prefix('#').and_then(|decimal| decimal.parse().ok()),
+            }
+            .filter(|&code| code != 0)
+            .and_then(char::from_u32),
+        };
+        decoded.push(character.ok_or((INVALID_ENTITY, line))?);
+        rest = after;
+    }
+    decoded.push_str(rest);
+    Ok(decoded)
+}
+
+// The namespace a prefix (None for the default namespace) is bound to by an element's own
+// attributes or those of the elements around it, innermost first
+fn namespace(prefix: Option<&str>, attributes: &[(String, String)], open: &[Element]) -> Option<String> {
+    let declaration = match prefix {
+        Some("xml") => return Some("http://www.w3.org/XML/1998/namespace".to_string()),
+        Some(prefix) => format!("xmlns:{prefix}"),
+        None => "xmlns".to_string(),
+    };
+    let scopes = std::iter::once(attributes).chain(open.iter().rev().map(|element| element.attributes.as_slice()));
+    let uri = scopes.flat_map(|attributes| attributes.iter()).find(|(key, _)| *key == declaration).map(|(_, uri)| uri)?;
+    (!uri.is_empty()).then(|| uri.clone())
+}
+
+// Parses a document into its root element. Comments and processing instructions are skipped,
+// and so is a document type declaration before the root
+pub fn parse(text: &str) -> Result<Element, Problem> {
+    let mut reader = Reader { rest: text.strip_prefix('\u{feff}').unwrap_or(text), line: 1 };
+    let mut open: Vec<Element> = Vec::new();
+    let mut root: Option<Element> = None;
+
+    loop {
+        if open.is_empty() {
+            reader.skip_whitespace();
+        }
+        if reader.rest.is_empty() {
+            break;
+        }
+        let line = reader.line;
+        if reader.eat("<?") {
+            reader.until("?>")?;
+        } else if reader.eat("<!--") {
+            reader.until("-->")?;
+        } else if open.is_empty() && root.is_none() && reader.eat("<!DOCTYPE") {
+            reader.doctype()?;
+        } else if !open.is_empty() && reader.eat("<![CDATA[") {
+            let text = reader.until("]]>")?;
+            if let Some(element) = open.last_mut() {
+                element.text.push_str(text);
+            }
+        } else if reader.eat("</") {
+            let name = reader.name()?;
+            reader.skip_whitespace();
+            if !reader.eat(">") {
+                return Err((MALFORMED_XML, reader.line));
+            }
+            let element = match open.pop() {
+                Some(element) if element.name == name => element,
+                _ => return Err((MISMATCHED_TAG, line)),
+            };
+            match open.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => root = Some(element),
+            }
+        } else if reader.eat("<") {
+            // A second root element, or one nested too deep
+            if root.is_some() || open.len() >= MAX_DEPTH {
+                return Err((MALFORMED_XML, line));
+            }
+            let (name, attributes, closed) = reader.start_tag()?;
+            let prefix = name.split_once(':').map(|(prefix, _)| prefix);
+            let namespace = namespace(prefix, &attributes, &open);
+            let element = Element { name, namespace, attributes, children: Vec::new(), text: String::new(), line };
+            match (closed, open.last_mut()) {
+                (false, _) => open.push(element),
+                (true, Some(parent)) => parent.children.push(element),
+                (true, None) => root = Some(element),
+            }
+        } else {
+            // Text, which only elements may hold
+            let len = reader.rest.find('<').unwrap_or(reader.rest.len());
+            let text = reader.advance(len);
+            match open.last_mut() {
+                Some(element) => element.text.push_str(&decode(text, line)?),
+                None => return Err((MALFORMED_XML, line)),
+            }
+        }
+    }
+
+    // An element left open is missing its end tag
+    if let Some(element) = open.last() {
+        return Err((MISMATCHED_TAG, element.line));
+    }
+    root.ok_or((MALFORMED_XML, reader.line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let root = parse(
+            "\u{feff}<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<!-- a feed -->\n\
+             <rss version='2.0' xmlns:itunes=\"http://www.itunes.com/dtds/podcast-1.0.dtd\">\n\
+             <channel><title>Tom &amp; Jerry&#x2019;s &#233;pisodes</title>\n\
+             <description><![CDATA[<p>Cartoons</p>]]></description>\n\
+             <itunes:image href=\"a.jpg?x=1&amp;y=2\"/></channel>\n</rss>\n",
+        )
+        .unwrap();
+        assert_eq!((root.name.as_str(), root.attribute("version"), root.line), ("rss", Some("2.0"), 3));
+        let channel = root.child(None, "channel").unwrap();
+        assert_eq!(channel.child(None, "title").unwrap().text, "Tom & Jerry\u{2019}s \u{e9}pisodes");
+        assert_eq!(channel.child(None, "description").unwrap().text, "<p>Cartoons</p>");
+
+        // Prefixed names resolve to their namespace, and don't match unprefixed ones
+        let image = channel.child(Some("http://www.itunes.com/dtds/podcast-1.0.dtd"), "image").unwrap();
+        assert_eq!((image.local_name(), image.attribute("href"), image.line), ("image", Some("a.jpg?x=1&y=2"), 6));
+        assert!(channel.child(None, "image").is_none());
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let root = parse("<feed xmlns=\"http://www.w3.org/2005/Atom\"><entry/><x:y xmlns:x=\"urn:x\"><z xmlns=\"\"/></x:y></feed>").unwrap();
+        assert_eq!(root.namespace.as_deref(), Some("http://www.w3.org/2005/Atom"));
+        assert_eq!(root.children(Some("http://www.w3.org/2005/Atom"), "entry").count(), 1);
+        assert_eq!(root.children[1].namespace.as_deref(), Some("urn:x"));
+        assert_eq!(root.children[1].children[0].namespace, None);
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(parse("<a><b></a></b>"), Err((MISMATCHED_TAG, 1)));
+        assert_eq!(parse("<a>\n<b>\n</a>"), Err((MISMATCHED_TAG, 3)));
+        assert_eq!(parse("<a>\n<b>"), Err((MISMATCHED_TAG, 2)));
+        assert_eq!(parse("<a>&nbsp;</a>"), Err((INVALID_ENTITY, 1)));
+        assert_eq!(parse("<a>AT&T</a>"), Err((INVALID_ENTITY, 1)));
+        assert_eq!(parse("<a>&#0;</a>"), Err((INVALID_ENTITY, 1)));
+        assert_eq!(parse("<a b=\"1\" b=\"2\"/>"), Err((MALFORMED_XML, 1)));
+        assert_eq!(parse("<a b=1/>"), Err((MALFORMED_XML, 1)));
+        assert_eq!(parse("<a b=\"1\"c=\"2\"/>"), Err((MALFORMED_XML, 1)));
+        assert_eq!(parse("<a/><b/>"), Err((MALFORMED_XML, 1)));
+        assert_eq!(parse("text"), Err((MALFORMED_XML, 1)));
+        assert_eq!(parse("<a>\n<!-- open"), Err((MALFORMED_XML, 2)));
+        assert_eq!(parse(""), Err((MALFORMED_XML, 1)));
+        assert!(parse(&"<a>".repeat(MAX_DEPTH + 1)).is_err());
+
+        // A DTD is skipped, but not the entities it declares
+        assert!(parse("<!DOCTYPE rss PUBLIC \"-//Netscape Communications//DTD RSS 0.91//EN\" \"x.dtd\">\n<rss/>").is_ok());
+        assert_eq!(parse("<!DOCTYPE a [<!ENTITY e \"x\">]>\n<a>&e;</a>"), Err((INVALID_ENTITY, 2)));
+    }
+}